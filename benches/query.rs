@@ -0,0 +1,54 @@
+//! Measures cloning and (de)serializing a large `bool` query, e.g. to compare
+//! the effect of boxing (or not boxing) the kinds of queries of `Query`.
+//!
+//! Run it with `cargo bench --bench query`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use elastiql::search::query::{BooleanQuery, Query, TermQuery};
+
+/// The number of `term` filters of the `bool` query.
+const FILTERS: usize = 10_000;
+
+/// The number of times each operation is repeated.
+const ITERATIONS: u32 = 1_000;
+
+/// Returns the average time it takes to run `f`.
+fn measure(mut f: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    start.elapsed() / ITERATIONS
+}
+
+fn main() {
+    let mut boolean = BooleanQuery::default();
+    for i in 0..FILTERS {
+        boolean
+            .filter
+            .push(TermQuery::new(format!("field{}", i % 10), format!("value{}", i)).into());
+    }
+    let json = serde_json::to_string(&boolean).unwrap();
+
+    println!("size_of::<Query>(): {} B", std::mem::size_of::<Query>());
+    println!(
+        "clone: {:?}",
+        measure(|| {
+            black_box(boolean.clone());
+        })
+    );
+    println!(
+        "serialize: {:?}",
+        measure(|| {
+            black_box(serde_json::to_vec(&boolean).unwrap());
+        })
+    );
+    println!(
+        "deserialize: {:?}",
+        measure(|| {
+            black_box(serde_json::from_str::<BooleanQuery>(&json).unwrap());
+        })
+    );
+}
@@ -0,0 +1,4 @@
+fn main() {
+    // the prefix is baked into the GraphQL names at compile time
+    println!("cargo:rerun-if-env-changed=ELASTIQL_GRAPHQL_PREFIX");
+}
@@ -0,0 +1,517 @@
+//! Procedural macros used internally by [elastiql].
+//!
+//! [elastiql]: https://github.com/voxjar/elastiql
+
+#![forbid(unsafe_code)]
+#![warn(missing_docs, clippy::all)]
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Group, Ident, TokenStream as TokenStream2, TokenTree};
+use quote::{format_ident, quote};
+use syn::{
+    parse::ParseStream, parse_macro_input, parse_quote, spanned::Spanned, Attribute, AttributeArgs,
+    DeriveInput, Error, Fields, GenericArgument, ItemImpl, ItemStruct, Lit, LitStr, Meta,
+    NestedMeta, Path, PathArguments, Type,
+};
+
+/// Generates the GraphQL `FooInput` twin of an owned `Foo` struct.
+///
+/// The twin is only compiled with the `graphql` feature enabled and:
+///
+/// * derives `async_graphql::InputObject` and everything the owned struct
+///   derives except `async_graphql::SimpleObject`, `Deserialize` and
+///   `arbitrary::Arbitrary`, plus any traits listed in `derive(...)`
+/// * copies the docs and the `builder`/`serde` attributes of the owned struct
+///   and its fields, but not its `graphql`/`arbitrary` attributes
+/// * implements `schemars::JsonSchema` (with the `schemars` feature enabled)
+///   by re-using the schema of the owned struct if the owned struct doesn't
+///   derive `Serialize`, in which case its `Serialize` impl is expected to be
+///   repeated for the twin by [`macro@twin_impl`]
+/// * implements `From` in both directions
+///
+/// The attribute accepts an optional GraphQL `name` for the twin (which
+/// defaults to `FooInput`, see [`macro@graphql_name`]) and extra traits to
+/// `derive` for it, e.g.
+/// `#[input_twin(name = "TermFilterInput", derive(Deserialize))]`.
+///
+/// Fields accept an `#[input(...)]` attribute with the following options:
+///
+/// * `twin`: the field type (possibly wrapped in an `Option`, `Vec` or `Box`)
+///   has an input twin itself, which is used as the type of the field instead
+/// * `graphql(...)`: attributes for the field of the twin only, e.g.
+///   `graphql(default)`
+/// * `serde(...)`: attributes for the field of the twin *instead* of the
+///   `serde` attributes of the owned field
+#[proc_macro_attribute]
+pub fn input_twin(args: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as AttributeArgs);
+    let item = parse_macro_input!(item as ItemStruct);
+
+    expand(args, item)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Repeats an `impl` block for the input twin of a type (see
+/// [`macro@input_twin`]), with every mention of the type replaced by its twin,
+/// e.g. to implement `Serialize` for `FooInput` with the same body as for
+/// `Foo` instead of converting (and thus cloning) it into a `Foo` first.
+///
+/// The types default to the self type of the block, e.g. `Foo` for
+/// `impl Serialize for Foo`, or are listed as arguments, e.g. `Foo` for
+/// `#[twin_impl(Foo)] impl<'a> From<&'a Foo> for FooRef<'a>`. The repeated
+/// block is only compiled with the `graphql` feature enabled.
+#[proc_macro_attribute]
+pub fn twin_impl(args: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as AttributeArgs);
+    let item = parse_macro_input!(item as ItemImpl);
+
+    expand_impl(args, item)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Sets the GraphQL `name` of a type, i.e. `#[graphql_name("TermFilter")]` is
+/// equivalent to `#[graphql(name = "TermFilter")]`, except that the name is
+/// adjusted to the configured naming convention:
+///
+/// * with the `query-names` feature enabled, the `Filter` family of names is
+///   renamed to `Query` style names, e.g. `TermFilter` to `TermQuery` and
+///   `CompoundFilterInput` to `CompoundQueryInput`; `Filter` itself, which
+///   would collide with the conventional root `Query` type, is renamed to
+///   `SearchQuery`
+/// * the value of the `ELASTIQL_GRAPHQL_PREFIX` environment variable at
+///   compile time, if any, is prepended to the name, e.g. `EsTermFilter`
+///
+/// The `name` of an `input_twin` is adjusted the same way.
+#[proc_macro_attribute]
+pub fn graphql_name(args: TokenStream, item: TokenStream) -> TokenStream {
+    let name = parse_macro_input!(args as LitStr);
+    let mut item = parse_macro_input!(item as DeriveInput);
+
+    // the attribute must come after the derive that introduces it
+    let name = configured_name(&name.value());
+    item.attrs.push(parse_quote!(#[graphql(name = #name)]));
+    quote!(#item).into()
+}
+
+/// Returns `name` adjusted to the configured naming convention.
+///
+/// See [`macro@graphql_name`].
+fn configured_name(name: &str) -> String {
+    let name = if cfg!(feature = "query-names") {
+        match name {
+            "Filter" => "SearchQuery".to_string(),
+            "FilterInput" => "SearchQueryInput".to_string(),
+            name => name.replace("Filter", "Query"),
+        }
+    } else {
+        name.to_string()
+    };
+
+    format!(
+        "{}{}",
+        option_env!("ELASTIQL_GRAPHQL_PREFIX").unwrap_or(""),
+        name
+    )
+}
+
+/// The parsed `#[input(...)]` attribute of a field.
+#[derive(Default)]
+struct FieldOptions {
+    twin: bool,
+    graphql: Vec<TokenStream2>,
+    serde: Vec<TokenStream2>,
+}
+
+fn expand(args: AttributeArgs, mut item: ItemStruct) -> syn::Result<TokenStream2> {
+    let mut graphql_name = None;
+    let mut extra_derives = vec![];
+    for arg in args {
+        match arg {
+            NestedMeta::Meta(Meta::List(ref list)) if list.path.is_ident("derive") => {
+                for nested in &list.nested {
+                    match nested {
+                        NestedMeta::Meta(Meta::Path(path)) => extra_derives.push(path.clone()),
+                        nested => return Err(Error::new(nested.span(), "expected a trait")),
+                    }
+                }
+            }
+            NestedMeta::Meta(Meta::NameValue(ref nv)) if nv.path.is_ident("name") => match nv.lit {
+                Lit::Str(ref name) => graphql_name = Some(configured_name(&name.value())),
+                ref lit => return Err(Error::new(lit.span(), "expected a string")),
+            },
+            arg => {
+                return Err(Error::new(
+                    arg.span(),
+                    "expected `name = \"...\"` or `derive(...)`",
+                ))
+            }
+        }
+    }
+
+    let owned = item.ident.clone();
+    let input = format_ident!("{}Input", owned);
+    let vis = item.vis.clone();
+
+    let mut derives = vec![];
+    let mut derives_serialize = false;
+    let mut derives_json_schema = false;
+    let mut attrs = vec![];
+    for attr in &item.attrs {
+        if attr.path.is_ident("derive") {
+            let paths = attr.parse_args_with(
+                syn::punctuated::Punctuated::<Path, syn::Token![,]>::parse_terminated,
+            )?;
+            for path in paths {
+                match last_ident(&path).as_deref() {
+                    Some("Deserialize") | Some("SimpleObject") | Some("Arbitrary") => {}
+                    Some("Serialize") => {
+                        derives_serialize = true;
+                        derives.push(path);
+                    }
+                    Some("JsonSchema") => {
+                        derives_json_schema = true;
+                        derives.push(path);
+                    }
+                    _ => derives.push(path),
+                }
+            }
+        } else {
+            attrs.push(attr.clone());
+        }
+    }
+    derives.extend(extra_derives);
+    if !derives_serialize && derives_json_schema {
+        // the schema is re-used from the owned struct, just like `Serialize`
+        derives.retain(|path| last_ident(path).as_deref() != Some("JsonSchema"));
+        derives_json_schema = false;
+    }
+    let is_kept = |attr: &Attribute| {
+        let name = attr_name(attr);
+        is_copied(attr)
+            && (derives_serialize || name != "serde")
+            && (derives_json_schema || name != "schemars")
+    };
+    attrs.retain(is_kept);
+
+    let fields = match item.fields {
+        Fields::Named(ref mut fields) => &mut fields.named,
+        _ => {
+            return Err(Error::new(
+                item.ident.span(),
+                "`input_twin` only supports structs with named fields",
+            ))
+        }
+    };
+
+    let mut input_fields = vec![];
+    let mut from_input = vec![];
+    let mut from_owned = vec![];
+    for field in fields.iter_mut() {
+        let mut options = FieldOptions::default();
+        let mut field_attrs = vec![];
+        let mut owned_attrs = vec![];
+        for attr in field.attrs.drain(..) {
+            if attr.path.is_ident("input") {
+                parse_field_options(&attr, &mut options)?;
+                continue;
+            }
+            if is_kept(&attr) {
+                field_attrs.push(attr.clone());
+            }
+            owned_attrs.push(attr);
+        }
+        field.attrs = owned_attrs;
+        if !options.serde.is_empty() {
+            field_attrs.retain(|attr| attr_name(attr) != "serde");
+        }
+
+        let ident = field.ident.clone().expect("named field");
+        let graphql = &options.graphql;
+        let serde = &options.serde;
+        let ty = if options.twin {
+            twin_type(&field.ty)?
+        } else {
+            field.ty.clone()
+        };
+        let field_vis = &field.vis;
+        input_fields.push(quote! {
+            #(#field_attrs)*
+            #(#[graphql(#graphql)])*
+            #(#[serde(#serde)])*
+            #field_vis #ident: #ty
+        });
+
+        if options.twin {
+            let to_owned = convert(quote!(input.#ident), &field.ty)?;
+            let to_input = convert(quote!(owned.#ident), &field.ty)?;
+            from_input.push(quote!(#ident: #to_owned));
+            from_owned.push(quote!(#ident: #to_input));
+        } else {
+            from_input.push(quote!(#ident: input.#ident));
+            from_owned.push(quote!(#ident: owned.#ident));
+        }
+    }
+
+    let graphql_name = graphql_name.unwrap_or_else(|| configured_name(&input.to_string()));
+
+    let json_schema = if derives_serialize {
+        None
+    } else {
+        Some(quote! {
+            #[cfg(all(feature = "graphql", feature = "schemars"))]
+            impl ::schemars::JsonSchema for #input {
+                #[inline]
+                fn schema_name() -> ::std::string::String {
+                    <#owned as ::schemars::JsonSchema>::schema_name()
+                }
+
+                #[inline]
+                fn json_schema(gen: &mut ::schemars::gen::SchemaGenerator) -> ::schemars::schema::Schema {
+                    <#owned as ::schemars::JsonSchema>::json_schema(gen)
+                }
+            }
+        })
+    };
+
+    Ok(quote! {
+        #item
+
+        #[cfg(feature = "graphql")]
+        #[derive(async_graphql::InputObject, #(#derives),*)]
+        #(#attrs)*
+        #[graphql(name = #graphql_name)]
+        #vis struct #input {
+            #(#input_fields,)*
+        }
+
+        #[cfg(feature = "graphql")]
+        impl ::std::convert::From<#input> for #owned {
+            #[inline]
+            fn from(input: #input) -> Self {
+                #owned {
+                    #(#from_input,)*
+                }
+            }
+        }
+
+        #[cfg(feature = "graphql")]
+        impl ::std::convert::From<#owned> for #input {
+            #[inline]
+            fn from(owned: #owned) -> Self {
+                #input {
+                    #(#from_owned,)*
+                }
+            }
+        }
+
+        #json_schema
+    })
+}
+
+fn expand_impl(args: AttributeArgs, item: ItemImpl) -> syn::Result<TokenStream2> {
+    let mut owned = vec![];
+    for arg in args {
+        match arg {
+            NestedMeta::Meta(Meta::Path(ref path)) if path.get_ident().is_some() => {
+                owned.push(path.get_ident().cloned().expect("an identifier"));
+            }
+            arg => return Err(Error::new(arg.span(), "expected a type")),
+        }
+    }
+    if owned.is_empty() {
+        let ident = match *item.self_ty {
+            Type::Path(ref ty) => ty.path.segments.last().map(|segment| &segment.ident),
+            _ => None,
+        };
+        owned.push(
+            ident
+                .cloned()
+                .ok_or_else(|| Error::new(item.self_ty.span(), "expected a path to a type"))?,
+        );
+    }
+
+    let twin = replace_idents(quote!(#item), &owned);
+
+    Ok(quote! {
+        #item
+
+        #[cfg(feature = "graphql")]
+        #twin
+    })
+}
+
+/// Returns `tokens` with every identifier in `owned` replaced by its input
+/// twin, e.g. `FooInput` for `Foo`.
+fn replace_idents(tokens: TokenStream2, owned: &[Ident]) -> TokenStream2 {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Ident(ref ident) if owned.contains(ident) => {
+                TokenTree::Ident(format_ident!("{}Input", ident, span = ident.span()))
+            }
+            TokenTree::Group(ref group) => {
+                let mut twin = Group::new(group.delimiter(), replace_idents(group.stream(), owned));
+                twin.set_span(group.span());
+                TokenTree::Group(twin)
+            }
+            token => token,
+        })
+        .collect()
+}
+
+/// Returns `true` if `attr` should be copied to the input twin.
+///
+/// Note that the compiler already expanded any `cfg_attr` attributes of the
+/// struct (but not of its fields), so e.g. the `builder` attributes are copied
+/// as is.
+fn is_copied(attr: &Attribute) -> bool {
+    !matches!(
+        attr_name(attr).as_str(),
+        "input_twin" | "graphql" | "graphql_name" | "arbitrary"
+    )
+}
+
+/// Returns the name of `attr`, looking through `cfg_attr`, e.g. `serde` for
+/// `#[cfg_attr(feature = "foo", serde(default))]`.
+fn attr_name(attr: &Attribute) -> String {
+    let path = if attr.path.is_ident("cfg_attr") {
+        attr.parse_args_with(|input: ParseStream| {
+            input.parse::<NestedMeta>()?;
+            input.parse::<syn::Token![,]>()?;
+            let path = input.parse::<Path>()?;
+            input.parse::<TokenStream2>()?;
+            Ok(path)
+        })
+        .unwrap_or_else(|_| attr.path.clone())
+    } else {
+        attr.path.clone()
+    };
+
+    last_ident(&path).unwrap_or_default()
+}
+
+fn parse_field_options(attr: &Attribute, options: &mut FieldOptions) -> syn::Result<()> {
+    let args = attr.parse_args_with(
+        syn::punctuated::Punctuated::<NestedMeta, syn::Token![,]>::parse_terminated,
+    )?;
+
+    for arg in args {
+        match arg {
+            NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("twin") => {
+                options.twin = true;
+            }
+            NestedMeta::Meta(Meta::List(ref list)) if list.path.is_ident("graphql") => {
+                let nested = &list.nested;
+                options.graphql.push(quote!(#nested));
+            }
+            NestedMeta::Meta(Meta::List(ref list)) if list.path.is_ident("serde") => {
+                let nested = &list.nested;
+                options.serde.push(quote!(#nested));
+            }
+            arg => {
+                return Err(Error::new(
+                    arg.span(),
+                    "expected `twin`, `graphql(...)` or `serde(...)`",
+                ))
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the last identifier of `path`, e.g. `Serialize` for
+/// `serde::Serialize`.
+fn last_ident(path: &Path) -> Option<String> {
+    path.segments
+        .last()
+        .map(|segment| segment.ident.to_string())
+}
+
+/// Returns the single generic type argument of `ty` if it's an `Option`,
+/// `Vec` or `Box`.
+fn wrapped(ty: &Type) -> Option<(String, &Type)> {
+    let path = match ty {
+        Type::Path(ty) if ty.qself.is_none() => &ty.path,
+        _ => return None,
+    };
+    let segment = path.segments.last()?;
+    let name = segment.ident.to_string();
+    if !matches!(name.as_str(), "Option" | "Vec" | "Box") {
+        return None;
+    }
+
+    match segment.arguments {
+        PathArguments::AngleBracketed(ref args) if args.args.len() == 1 => {
+            match args.args.first() {
+                Some(GenericArgument::Type(ref inner)) => Some((name, inner)),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns `ty` with the innermost type replaced by its input twin, e.g.
+/// `Option<Vec<FooInput>>` for `Option<Vec<Foo>>`.
+fn twin_type(ty: &Type) -> syn::Result<Type> {
+    let mut twin = ty.clone();
+
+    let path = match twin {
+        Type::Path(ref mut ty) => &mut ty.path,
+        _ => {
+            return Err(Error::new(
+                ty.span(),
+                "expected a path to a type with a twin",
+            ))
+        }
+    };
+    let segment = path
+        .segments
+        .last_mut()
+        .ok_or_else(|| Error::new(ty.span(), "expected a type"))?;
+
+    if let Some((_, inner)) = wrapped(ty) {
+        let inner = twin_type(inner)?;
+        if let PathArguments::AngleBracketed(ref mut args) = segment.arguments {
+            args.args[0] = GenericArgument::Type(inner);
+        }
+    } else {
+        segment.ident = format_ident!("{}Input", segment.ident);
+    }
+
+    Ok(twin)
+}
+
+/// Returns an expression that converts `expr` of type `ty` into its twin (or
+/// vice versa).
+fn convert(expr: TokenStream2, ty: &Type) -> syn::Result<TokenStream2> {
+    let into = quote!(::std::convert::Into::into);
+
+    Ok(match wrapped(ty) {
+        Some((wrapper, inner)) => {
+            let is_plain = wrapped(inner).is_none();
+            match wrapper.as_str() {
+                "Option" if is_plain => quote!(#expr.map(#into)),
+                "Option" => {
+                    let inner = convert(quote!(value), inner)?;
+                    quote!(#expr.map(|value| #inner))
+                }
+                "Vec" if is_plain => quote!(#expr.into_iter().map(#into).collect()),
+                "Vec" => {
+                    let inner = convert(quote!(value), inner)?;
+                    quote!(#expr.into_iter().map(|value| #inner).collect())
+                }
+                _ => {
+                    let inner = convert(quote!((*#expr)), inner)?;
+                    quote!(::std::boxed::Box::new(#inner))
+                }
+            }
+        }
+        None => quote!(#into(#expr)),
+    })
+}
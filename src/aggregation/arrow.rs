@@ -0,0 +1,154 @@
+//! Converts aggregation [`Response`]s into [Arrow] record batches, e.g. to load
+//! them straight into a [polars] `DataFrame`.
+//!
+//! [Arrow]: https://arrow.apache.org
+//! [polars]: https://pola.rs
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+use arrow_array::{ArrayRef, Float64Array, RecordBatch, RecordBatchOptions, StringArray};
+use arrow_schema::{ArrowError, DataType, Field, Schema};
+
+use super::Response;
+
+impl Response {
+    /// Converts this into an [Arrow] `RecordBatch` with a row per path of
+    /// [bucket keys](super::ComputedResult::bucket_keys), sorted by path.
+    ///
+    /// The first columns are the bucket keys of each row, from the outermost
+    /// bucket aggregation inwards, named `key_0`, `key_1`, etc., followed by a
+    /// column for each aggregation, named after it, sorted by name. Keys and
+    /// values that don't apply to a row, e.g. of a metric outside of any
+    /// bucket, are null.
+    ///
+    /// [Arrow]: https://arrow.apache.org
+    pub fn to_record_batch(&self) -> Result<RecordBatch, ArrowError> {
+        let mut paths: Vec<&[String]> = self
+            .aggregations
+            .iter()
+            .flat_map(|result| result.bucket_keys.iter().map(Vec::as_slice))
+            .collect();
+        paths.sort_unstable();
+        paths.dedup();
+        let rows: HashMap<_, _> = paths
+            .iter()
+            .enumerate()
+            .map(|(i, path)| (*path, i))
+            .collect();
+
+        // results of the same aggregation in different (outer) buckets share a
+        // column, since their rows are told apart by their keys
+        let mut columns: BTreeMap<&str, Vec<Option<f64>>> = BTreeMap::new();
+        for result in &self.aggregations {
+            let values = columns
+                .entry(&result.name)
+                .or_insert_with(|| vec![None; paths.len()]);
+            for (value, keys) in result.values.iter().zip(&result.bucket_keys) {
+                values[rows[keys.as_slice()]] = Some(*value);
+            }
+        }
+
+        let depth = paths.iter().map(|path| path.len()).max().unwrap_or(0);
+        let mut fields = Vec::with_capacity(depth + columns.len());
+        let mut arrays: Vec<ArrayRef> = Vec::with_capacity(depth + columns.len());
+
+        for level in 0..depth {
+            let keys: StringArray = paths
+                .iter()
+                .map(|path| path.get(level).map(String::as_str))
+                .collect();
+            fields.push(Field::new(format!("key_{}", level), DataType::Utf8, true));
+            arrays.push(Arc::new(keys));
+        }
+
+        for (name, values) in columns {
+            fields.push(Field::new(name, DataType::Float64, true));
+            arrays.push(Arc::new(Float64Array::from(values)));
+        }
+
+        let options = RecordBatchOptions::new().with_row_count(Some(paths.len()));
+        RecordBatch::try_new_with_options(Arc::new(Schema::new(fields)), arrays, &options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use arrow_array::Array;
+    use serde_json::json;
+
+    #[test]
+    fn has_a_column_per_bucket_key_and_aggregation() {
+        let response: Response = serde_json::from_value(json!({
+            "aggregations": {
+                "max#LONGEST": { "value": 30.0 },
+                "PER_TEAM": {
+                    "buckets": [{
+                        "key": "sales",
+                        "doc_count": 3,
+                        "PER_AGENT": {
+                            "buckets": [
+                                { "key": "will", "doc_count": 1, "sum#DURATION": { "value": 30.0 } },
+                                { "key": "dallin", "doc_count": 2, "sum#DURATION": { "value": 20.0 } }
+                            ]
+                        }
+                    }]
+                }
+            }
+        }))
+        .unwrap();
+
+        let batch = response.to_record_batch().unwrap();
+        let schema = batch.schema();
+        let names: Vec<_> = schema
+            .fields()
+            .iter()
+            .map(|field| field.name().as_str())
+            .collect();
+        assert_eq!(names, vec!["key_0", "key_1", "DURATION", "LONGEST"]);
+        assert_eq!(batch.num_rows(), 3);
+
+        let team = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        let agent = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        let duration = batch
+            .column(2)
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+        let longest = batch
+            .column(3)
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+
+        // the row of the top-level metric has no keys
+        assert!(team.is_null(0) && agent.is_null(0));
+        assert_eq!(longest.value(0), 30.0);
+        assert!(duration.is_null(0));
+
+        assert_eq!((team.value(1), agent.value(1)), ("sales", "dallin"));
+        assert_eq!(duration.value(1), 20.0);
+        assert_eq!((team.value(2), agent.value(2)), ("sales", "will"));
+        assert_eq!(duration.value(2), 30.0);
+        assert!(longest.is_null(2));
+    }
+
+    #[test]
+    fn can_convert_empty_responses() {
+        let response = Response::default();
+        let batch = response.to_record_batch().unwrap();
+        assert_eq!((batch.num_rows(), batch.num_columns()), (0, 0));
+    }
+}
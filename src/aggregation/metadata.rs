@@ -0,0 +1,200 @@
+//! The custom [metadata] of aggregations.
+//!
+//! [metadata]: https://www.elastic.co/guide/en/elasticsearch/reference/current/agg-metadata.html
+
+use std::iter::FromIterator;
+
+#[cfg(feature = "schemars")]
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::de::DeserializeOwned;
+use serde::ser::{SerializeMap, Serializer};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::scalars::Map;
+
+/// The key of [`AggregationMetadata::skip`].
+const SKIP: &str = "_skip";
+
+/// The key of [`AggregationMetadata::label`].
+const LABEL: &str = "_label";
+
+/// The custom [metadata] of an aggregation, which Elasticsearch returns
+/// alongside its results.
+///
+/// It is (de)serialized as a plain JSON object (and exposed through GraphQL as
+/// a JSON object scalar), with the special keys `_skip` and `_label` for the
+/// typed fields and any other keys in `extra`, so untyped maps are still
+/// accepted:
+///
+/// ```
+/// use elastiql::aggregation::AggregationMetadata;
+///
+/// let metadata: AggregationMetadata =
+///     serde_json::from_str(r#"{ "_skip": true, "color": "red" }"#).unwrap();
+/// assert!(metadata.skip);
+/// assert_eq!(metadata.extra.len(), 1);
+/// ```
+///
+/// [metadata]: https://www.elastic.co/guide/en/elasticsearch/reference/current/agg-metadata.html
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct AggregationMetadata {
+    /// Whether to calculate the results of the aggregation (e.g. for its
+    /// sub-aggregations or a pipeline aggregation) but leave them out of the
+    /// flattened [`Response`](super::Response). (`_skip`)
+    pub skip: bool,
+
+    /// The name to display the results of the aggregation under instead of
+    /// its name, see [`ComputedResult::label`](super::ComputedResult::label).
+    /// (`_label`)
+    pub label: Option<String>,
+
+    /// Any other metadata, as is.
+    pub extra: Map,
+}
+
+impl AggregationMetadata {
+    /// Returns `true` if this has no metadata at all.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        !self.skip && self.label.is_none() && self.extra.is_empty()
+    }
+}
+
+impl From<Map> for AggregationMetadata {
+    /// Takes the special keys out of an untyped map, leaving them in `extra` if
+    /// they have the wrong type.
+    #[inline]
+    fn from(mut extra: Map) -> Self {
+        AggregationMetadata {
+            skip: take(&mut extra, SKIP).unwrap_or(false),
+            label: take(&mut extra, LABEL),
+            extra,
+        }
+    }
+}
+
+impl<T> FromIterator<T> for AggregationMetadata
+where
+    Map: FromIterator<T>,
+{
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Map::from_iter(iter).into()
+    }
+}
+
+/// Removes `key` from `map` and returns its value, unless it isn't a `T`.
+#[inline]
+fn take<T: DeserializeOwned>(map: &mut Map, key: &str) -> Option<T> {
+    let value = serde_json::to_value(map.get(key)?).ok()?;
+    let value = serde_json::from_value(value).ok()?;
+    map.remove(key);
+    Some(value)
+}
+
+impl Serialize for AggregationMetadata {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        if self.skip {
+            map.serialize_entry(SKIP, &true)?;
+        }
+        if let Some(label) = &self.label {
+            map.serialize_entry(LABEL, label)?;
+        }
+        for (key, value) in &self.extra {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for AggregationMetadata {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<AggregationMetadata, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Map::deserialize(deserializer)?.into())
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl JsonSchema for AggregationMetadata {
+    #[inline]
+    fn schema_name() -> String {
+        "AggregationMetadata".to_string()
+    }
+
+    #[inline]
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        crate::schema::Map::json_schema(gen)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for AggregationMetadata {
+    #[inline]
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(AggregationMetadata {
+            skip: u.arbitrary()?,
+            label: if u.arbitrary()? {
+                Some(crate::arbitrary::field(u)?)
+            } else {
+                None
+            },
+            extra: {
+                let mut extra = crate::arbitrary::map(u)?;
+                extra.remove(SKIP);
+                extra.remove(LABEL);
+                extra
+            },
+        })
+    }
+}
+
+#[cfg(feature = "graphql")]
+#[async_graphql::Scalar(name = "AggregationMetadata")]
+impl async_graphql::ScalarType for AggregationMetadata {
+    #[inline]
+    fn parse(value: async_graphql::Value) -> async_graphql::InputValueResult<Self> {
+        match value {
+            async_graphql::Value::Object(_) => Ok(serde_json::from_value(value.into_json()?)?),
+            _ => Err(async_graphql::InputValueError::expected_type(value)),
+        }
+    }
+
+    #[inline]
+    fn to_value(&self) -> async_graphql::Value {
+        serde_json::to_value(self)
+            .and_then(async_graphql::Value::from_json)
+            .unwrap_or(async_graphql::Value::Null)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn can_round_trip_untyped_maps() {
+        let json = json!({ "_skip": true, "_label": "Average duration", "color": "red" });
+
+        let metadata: AggregationMetadata = serde_json::from_value(json.clone()).unwrap();
+        assert!(metadata.skip);
+        assert_eq!(metadata.label.as_deref(), Some("Average duration"));
+        assert_eq!(metadata.extra.len(), 1);
+        assert_eq!(serde_json::to_value(&metadata).unwrap(), json);
+    }
+
+    #[test]
+    fn keeps_special_keys_of_the_wrong_type() {
+        let json = json!({ "_skip": "yes" });
+
+        let metadata: AggregationMetadata = serde_json::from_value(json.clone()).unwrap();
+        assert!(!metadata.skip);
+        assert_eq!(serde_json::to_value(&metadata).unwrap(), json);
+    }
+}
@@ -2,9 +2,13 @@
 //!
 //! [aggregating]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations.html
 
-pub use self::{request::*, response::*};
+pub use self::{metadata::*, request::*, response::*, search_request::*};
 
+#[cfg(all(feature = "arrow", not(target_arch = "wasm32")))]
+mod arrow;
+mod metadata;
 mod request;
 mod response;
+mod search_request;
 mod serialization_deserialization;
 pub mod types;
@@ -3,7 +3,7 @@
 //! [aggregating]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations.html
 
 pub use super::response::*;
-use super::types::*;
+use super::{types::*, AggregationMetadata};
 
 use crate::search::query::CompoundQuery;
 #[cfg(feature = "graphql")]
@@ -20,9 +20,9 @@ use crate::search::query::CompoundQueryInput;
 /// [aggregation]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations.html
 /// [union input types]: https://github.com/graphql/graphql-spec/blob/master/rfcs/InputUnion.md
 #[cfg(feature = "graphql")]
+#[elastiql_derive::graphql_name("AggregationInput")]
 #[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(async_graphql::InputObject, Clone, Debug)]
-#[graphql(name = "AggregationInput")]
+#[derive(async_graphql::InputObject, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
 pub struct RequestInput {
     /// The name for this aggregation.
@@ -126,12 +126,11 @@ pub struct RequestInput {
     /// percentile ranks over numeric values extracted from the aggregated
     /// documents.
     ///
-    /// *Note*: [Percentiles are (usually) estimated].
+    /// See [`PercentileRanksAggregation`].
     ///
     /// [*metrics*]:  https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-metrics.html
-    /// [Percentiles are (usually) estimated]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-metrics-percentile-aggregation.html#search-aggregations-metrics-percentile-aggregation-approximation
     #[cfg_attr(feature = "builder", builder(default))]
-    pub percentile_ranks: Option<InnerAggregationInput>,
+    pub percentile_ranks: Option<PercentileRanksAggregationInput>,
 
     /// A `multi-value` [*metrics*] aggregation that computes stats over numeric
     /// values extracted from the aggregated documents.
@@ -312,16 +311,25 @@ pub struct RequestInput {
     #[cfg_attr(feature = "builder", builder(default))]
     pub reverse_nested: Option<ReverseNestedAggregationInput>,
 
+    /// Any other kinds of aggregations, e.g. ones provided by a plugin or not
+    /// (yet) supported by this crate, keyed by their type, e.g.
+    /// `{ "rate": { "unit": "year" } }`.
+    ///
+    /// These are emitted verbatim in the body of this aggregation, alongside
+    /// any of its other fields.
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub raw: Option<crate::scalars::Map>,
+
     /// The custom [metadata] to associate with this aggregation that will be
     /// returned alongside the results for this aggregation.
     ///
-    /// If the optional special key `_skip` is set to `true`, the results for
-    /// this aggregation will be calculated but not returned with the other
+    /// See [`AggregationMetadata`] for the special keys, e.g. to calculate
+    /// the results of this aggregation but not return them with the other
     /// results.
     ///
     /// [metadata]: https://www.elastic.co/guide/en/elasticsearch/reference/current/agg-metadata.html
     #[cfg_attr(feature = "builder", builder(default))]
-    pub metadata: Option<crate::scalars::Map>,
+    pub metadata: Option<AggregationMetadata>,
 
     /// The sub aggregations, if any.
     #[cfg_attr(feature = "builder", builder(default))]
@@ -332,11 +340,10 @@ pub struct RequestInput {
 /// information over a set of documents.
 ///
 /// [aggregation]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations.html
-#[cfg_attr(test, derive(PartialEq))]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("Aggregation"))]
 #[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
-#[cfg_attr(feature = "graphql", graphql(name = "Aggregation"))]
 #[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
 pub struct Request {
     /// The name for this aggregation.
@@ -441,12 +448,11 @@ pub struct Request {
     /// percentile ranks over numeric values extracted from the aggregated
     /// documents.
     ///
-    /// *Note*: [Percentiles are (usually) estimated].
+    /// See [`PercentileRanksAggregation`].
     ///
     /// [*metrics*]:  https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-metrics.html
-    /// [Percentiles are (usually) estimated]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-metrics-percentile-aggregation.html#search-aggregations-metrics-percentile-aggregation-approximation
     #[cfg_attr(feature = "builder", builder(default))]
-    pub percentile_ranks: Option<InnerAggregation>,
+    pub percentile_ranks: Option<PercentileRanksAggregation>,
 
     /// A `multi-value` [*metrics*] aggregation that computes stats over numeric
     /// values extracted from the aggregated documents.
@@ -627,22 +633,34 @@ pub struct Request {
     #[cfg_attr(feature = "builder", builder(default))]
     pub reverse_nested: Option<ReverseNestedAggregation>,
 
+    /// Any other kinds of aggregations, e.g. ones provided by a plugin or not
+    /// (yet) supported by this crate, keyed by their type, e.g.
+    /// `{ "rate": { "unit": "year" } }`.
+    ///
+    /// These are emitted verbatim in the body of this aggregation, alongside
+    /// any of its other fields.
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub raw: Option<crate::scalars::Map>,
+
     /// The custom [metadata] to associate with this aggregation that will be
     /// returned alongside the results for this aggregation.
     ///
-    /// If the optional special key `_skip` is set to `true`, the results for
-    /// this aggregation will be calculated but not returned with the other
+    /// See [`AggregationMetadata`] for the special keys, e.g. to calculate
+    /// the results of this aggregation but not return them with the other
     /// results.
     ///
     /// [metadata]: https://www.elastic.co/guide/en/elasticsearch/reference/current/agg-metadata.html
     #[cfg_attr(feature = "builder", builder(default))]
-    pub metadata: Option<crate::scalars::Map>,
+    pub metadata: Option<AggregationMetadata>,
 
     /// The sub aggregation, if any.
     #[cfg_attr(feature = "builder", builder(default))]
     pub aggregations: Option<Vec<Request>>,
 }
 
+impl_display_json!(Request);
+impl_canonical_json!(Request);
+
 // TODO: auto generate this with a proc_macro?
 #[cfg(feature = "graphql")]
 impl From<RequestInput> for Request {
@@ -677,6 +695,49 @@ impl From<RequestInput> for Request {
             bucket_sort: aggregation.bucket_sort.map(Into::into),
             nested: aggregation.nested.map(Into::into),
             reverse_nested: aggregation.reverse_nested.map(Into::into),
+            raw: aggregation.raw,
+            metadata: aggregation.metadata,
+            aggregations: aggregation
+                .aggregations
+                .map(|aggs| aggs.into_iter().map(Into::into).collect()),
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl From<Request> for RequestInput {
+    #[inline]
+    fn from(aggregation: Request) -> Self {
+        Self {
+            name: aggregation.name,
+            avg: aggregation.avg.map(Into::into),
+            weighted_avg: aggregation.weighted_avg.map(Into::into),
+            cardinality: aggregation.cardinality.map(Into::into),
+            max: aggregation.max.map(Into::into),
+            min: aggregation.min.map(Into::into),
+            median_absolute_deviation: aggregation.median_absolute_deviation.map(Into::into),
+            percentiles: aggregation.percentiles.map(Into::into),
+            percentile_ranks: aggregation.percentile_ranks.map(Into::into),
+            stats: aggregation.stats.map(Into::into),
+            extended_stats: aggregation.extended_stats.map(Into::into),
+            sum: aggregation.sum.map(Into::into),
+            value_count: aggregation.value_count.map(Into::into),
+            filters: aggregation.filters.map(Into::into),
+            terms: aggregation.terms.map(Into::into),
+            range: aggregation.range.map(Into::into),
+            date_range: aggregation.date_range.map(Into::into),
+            date_histogram: aggregation.date_histogram.map(Into::into),
+            auto_date_histogram: aggregation.auto_date_histogram.map(Into::into),
+            histogram: aggregation.histogram.map(Into::into),
+            variable_width_histogram: aggregation.variable_width_histogram.map(Into::into),
+            sampler: aggregation.sampler.map(Into::into),
+            significant_text: aggregation.significant_text.map(Into::into),
+            bucket_script: aggregation.bucket_script.map(Into::into),
+            bucket_selector: aggregation.bucket_selector.map(Into::into),
+            bucket_sort: aggregation.bucket_sort.map(Into::into),
+            nested: aggregation.nested.map(Into::into),
+            reverse_nested: aggregation.reverse_nested.map(Into::into),
+            raw: aggregation.raw,
             metadata: aggregation.metadata,
             aggregations: aggregation
                 .aggregations
@@ -2,26 +2,83 @@
 //!
 //! [aggregating]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations.html
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
+use super::AggregationMetadata;
+use crate::search::Count;
+
+pub use super::serialization_deserialization::StrictResponse;
 pub(crate) use super::serialization_deserialization::*;
 
 // TODO: rename?
 // TODO: add more fields
 /// The response from performing an aggregation.
+#[cfg_attr(
+    feature = "graphql",
+    elastiql_derive::graphql_name("AggregationResponse")
+)]
 #[cfg_attr(test, derive(PartialEq))]
 #[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
-#[cfg_attr(feature = "graphql", graphql(name = "AggregationResponse"))]
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone, Default, Debug)]
 #[serde(from = "ElasticAggregationResponse")]
 pub struct Response {
     /// The aggregations.
     pub aggregations: Vec<ComputedResult>,
+
+    /// The total number of documents matching the query, i.e. that were
+    /// aggregated (if known).
+    pub total_hits: Option<Count>,
+
+    /// Time it took for the database to process the request (if known).
+    pub took: Option<u64>,
+
+    /// Whether or not the database request timed out before completing (if
+    /// known), in which case the aggregations may be partial.
+    pub timed_out: Option<bool>,
+
+    /// How many shards the request ran on and how many of them failed (if
+    /// known).
+    pub shards: Option<Shards>,
+}
+
+impl Response {
+    /// Whether or not the aggregations may be partial, i.e. the request timed
+    /// out or failed on some of the shards.
+    #[inline]
+    pub fn is_partial(&self) -> bool {
+        self.timed_out == Some(true) || matches!(self.shards, Some(shards) if shards.failed > 0)
+    }
+}
+
+/// The number of shards a request ran on, by outcome.
+#[cfg_attr(
+    feature = "graphql",
+    elastiql_derive::graphql_name("AggregationShards")
+)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[derive(Serialize, Deserialize, Clone, Copy, Default, Debug, PartialEq, Eq, Hash)]
+pub struct Shards {
+    /// The total number of shards the request should have run on.
+    pub total: u64,
+
+    /// The number of shards the request ran on successfully.
+    pub successful: u64,
+
+    /// The number of shards that were skipped, e.g. because none of their
+    /// documents could match.
+    #[serde(default)]
+    pub skipped: u64,
+
+    /// The number of shards the request failed on.
+    pub failed: u64,
 }
 
 /// An individual result from performing an aggregation/calculation.
+#[cfg_attr(
+    feature = "graphql",
+    elastiql_derive::graphql_name("AggregationResult")
+)]
 #[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
-#[cfg_attr(feature = "graphql", graphql(name = "AggregationResult"))]
 #[derive(Deserialize, Clone, Debug)]
 pub struct ComputedResult {
     /// The parent of this aggregation (if any).
@@ -40,15 +97,45 @@ pub struct ComputedResult {
     /// The actual values/result of performing this aggregation.
     pub values: Vec<f64>,
 
+    /// The keys of the buckets each of the `values` belongs to, from the
+    /// outermost bucket aggregation inwards, so that results nested several
+    /// bucket aggregations deep can be grouped by each of them.
+    #[serde(default)]
+    pub bucket_keys: Vec<Vec<String>>,
+
     /// The user-supplied metadata attached to this aggregation.
-    pub metadata: Option<crate::scalars::Map>,
+    pub metadata: Option<AggregationMetadata>,
+
+    /// The upper bound of the error on the document count of each term of a
+    /// `terms` aggregation, see [document counts are approximate].
+    ///
+    /// [document counts are approximate]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-bucket-terms-aggregation.html#search-aggregations-bucket-terms-aggregation-approximate-counts
+    #[serde(default)]
+    pub doc_count_error_upper_bound: Option<u64>,
+
+    /// The number of documents of a `terms` aggregation whose terms aren't
+    /// part of the response, i.e. the "other" terms.
+    #[serde(default)]
+    pub sum_other_doc_count: Option<u64>,
+}
+
+impl ComputedResult {
+    /// The name to display this result under, i.e. the `_label` of its
+    /// [metadata](AggregationMetadata::label) or else its name.
+    #[inline]
+    pub fn label(&self) -> &str {
+        self.metadata
+            .as_ref()
+            .and_then(|metadata| metadata.label.as_deref())
+            .unwrap_or(&self.name)
+    }
 }
 
 // TODO: generate this with proc-macro from Aggregation struct
 /// The type of aggregation.
-#[cfg_attr(feature = "graphql", derive(async_graphql::Enum, Eq, PartialEq, Copy))]
-#[cfg_attr(feature = "graphql", graphql(name = "AggregationType"))]
-#[derive(Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("AggregationType"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Ty {
     /// metric
     Avg,
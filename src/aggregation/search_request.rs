@@ -0,0 +1,84 @@
+//! The [request body] of an aggregation-only search.
+//!
+//! [request body]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations.html#return-only-agg-results
+
+use std::collections::HashMap;
+
+use serde::ser::{SerializeMap, Serializer};
+use serde::Serialize;
+
+use super::{serialization_deserialization::serde_sub_aggregations, Request};
+use crate::{mapping::RuntimeField, search::query::CompoundQuery};
+
+/// The request body of a search that only returns the results of its
+/// `aggregations` over the documents matching its `query`, i.e. with a `size`
+/// of `0`, whose response can be parsed as an aggregation
+/// [`Response`](super::Response).
+///
+/// ```
+/// use elastiql::{
+///     aggregation::{types::InnerAggregation, Request, SearchRequest},
+///     search::query::TermQuery,
+/// };
+///
+/// let request = SearchRequest::builder()
+///     .query(TermQuery::new("status", "open"))
+///     .aggregations(vec![Request::builder()
+///         .name("avg_duration".to_string())
+///         .avg(InnerAggregation::builder().field("duration".to_string()).build())
+///         .build()])
+///     .build();
+///
+/// assert_eq!(
+///     serde_json::to_value(&request).unwrap(),
+///     serde_json::json!({
+///         "query": { "bool": { "filter": [{ "term": { "status": { "value": "open" } } }] } },
+///         "size": 0,
+///         "aggs": { "avg_duration": { "avg": { "field": "duration" } } }
+///     })
+/// );
+/// ```
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(default, setter(into))))]
+pub struct SearchRequest {
+    /// The query matching the documents to aggregate.
+    pub query: CompoundQuery,
+
+    /// The aggregations to perform.
+    pub aggregations: Vec<Request>,
+
+    /// The [runtime fields] defined for this request only, by name, which can
+    /// be aggregated on like any other field.
+    ///
+    /// [runtime fields]: https://www.elastic.co/guide/en/elasticsearch/reference/current/runtime-search-request.html
+    pub runtime_mappings: HashMap<String, RuntimeField>,
+}
+
+impl Serialize for SearchRequest {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        /// Serializes the aggregations as a single object, by name.
+        struct Aggregations<'a>(&'a [Request]);
+
+        impl Serialize for Aggregations<'_> {
+            #[inline]
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serde_sub_aggregations::serialize(&Some(self.0), serializer)
+            }
+        }
+
+        let mut map = serializer.serialize_map(None)?;
+        if !self.query.is_empty() {
+            map.serialize_entry("query", &self.query)?;
+        }
+        map.serialize_entry("size", &0)?;
+        map.serialize_entry("aggs", &Aggregations(&self.aggregations))?;
+        if !self.runtime_mappings.is_empty() {
+            map.serialize_entry("runtime_mappings", &self.runtime_mappings)?;
+        }
+        map.end()
+    }
+}
+
+impl_display_json!(SearchRequest);
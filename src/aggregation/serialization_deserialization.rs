@@ -4,230 +4,290 @@
 
 use std::{collections::HashMap, fmt};
 
-use serde::de::{self, Deserializer, MapAccess, Visitor};
+#[cfg(feature = "arbitrary")]
+use arbitrary::Unstructured;
+#[cfg(feature = "schemars")]
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::de::{self, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
 use serde::ser::{SerializeMap, Serializer};
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "graphql")]
 use super::request::RequestInput as AggregationInput;
-use super::{request::Request as Aggregation, response::Ty, types::*, ComputedResult, Response};
-use crate::search::query::CompoundQuery;
+use super::{
+    request::Request as Aggregation, response::Ty, types::*, AggregationMetadata, ComputedResult,
+    Response, Shards,
+};
+use crate::search::{query::CompoundQuery, Count};
 
 #[cfg(feature = "graphql")]
 impl Serialize for AggregationInput {
     #[inline]
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         let mut map = serializer.serialize_map(Some(1))?;
-        map.serialize_entry(&self.name, &SubAggregation::from(self.to_owned()))?;
+        let aggregation = Aggregation::from(self.to_owned());
+        map.serialize_entry(&self.name, &SubAggregationRef::from(&aggregation))?;
         map.end()
     }
 }
 
-// TODO: auto generate this with a proc-macro?
-#[allow(clippy::missing_docs_in_private_items)]
-#[derive(Serialize, Deserialize, Clone, Debug)]
-pub(super) struct SubAggregation {
+#[cfg(feature = "graphql")]
+impl<'de> Deserialize<'de> for AggregationInput {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<AggregationInput, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Aggregation::deserialize(deserializer)?.into())
+    }
+}
+
+/// Returns the key of a kind of aggregation in the body of an `Aggregation`,
+/// i.e. its name unless it is renamed.
+macro_rules! kind_key {
+    ($kind:ident) => {
+        stringify!($kind)
+    };
+    ($kind:ident $key:literal) => {
+        $key
+    };
+}
+
+/// Defines the (de)serialization of the body of an `Aggregation`, which has a
+/// field for each of the given kinds of aggregations, along with its schema
+/// (`SubAggregation`).
+///
+/// Adding a new kind of aggregation only requires adding it to the
+/// invocation below (and to `Aggregation` and `AggregationInput`).
+macro_rules! sub_aggregation {
+    ($($kind:ident $(as $key:literal)?: $ty:ty,)+) => {
+        /// The body of an `Aggregation`, which only describes its JSON
+        /// schema; the body is (de)serialized directly from/into an
+        /// `Aggregation`.
+        #[cfg(feature = "schemars")]
+        #[allow(dead_code, clippy::missing_docs_in_private_items)]
+        #[derive(schemars::JsonSchema)]
+        pub(super) struct SubAggregation {
+            $(
+                #[serde(default, skip_serializing_if = "Option::is_none")]
+                $(#[serde(rename = $key)])?
+                $kind: Option<$ty>,
+            )+
+
+            #[serde(flatten)]
+            #[schemars(with = "crate::schema::Map")]
+            raw: Option<crate::scalars::Map>,
+
+            #[serde(default, rename = "meta", skip_serializing_if = "Option::is_none")]
+            metadata: Option<AggregationMetadata>,
+
+            #[serde(default, rename = "aggs", skip_serializing_if = "Option::is_none")]
+            #[schemars(with = "Option<HashMap<String, SubAggregation>>")]
+            aggregations: Option<Vec<Aggregation>>,
+        }
+
+        /// The borrowed body of an `Aggregation`, which serializes it without
+        /// cloning it (and all of its sub-aggregations).
+        #[allow(clippy::missing_docs_in_private_items)]
+        #[derive(Serialize)]
+        struct SubAggregationRef<'a> {
+            $(
+                #[serde(skip_serializing_if = "Option::is_none")]
+                $(#[serde(rename = $key)])?
+                $kind: Option<&'a $ty>,
+            )+
+
+            #[serde(flatten)]
+            raw: Option<&'a crate::scalars::Map>,
+
+            #[serde(rename = "meta", skip_serializing_if = "Option::is_none")]
+            metadata: Option<&'a AggregationMetadata>,
+
+            #[serde(
+                rename = "aggs",
+                skip_serializing_if = "Option::is_none",
+                serialize_with = "serde_sub_aggregations::serialize"
+            )]
+            aggregations: Option<&'a [Aggregation]>,
+        }
+
+        impl<'a> From<&'a Aggregation> for SubAggregationRef<'a> {
+            #[inline]
+            fn from(aggregation: &'a Aggregation) -> SubAggregationRef<'a> {
+                SubAggregationRef {
+                    $($kind: aggregation.$kind.as_ref(),)+
+                    raw: aggregation.raw.as_ref(),
+                    metadata: aggregation.metadata.as_ref(),
+                    aggregations: aggregation.aggregations.as_deref(),
+                }
+            }
+        }
+
+        impl<'de> Visitor<'de> for AggregationBody {
+            type Value = Aggregation;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("the body of an `Aggregation`")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut aggregation = Aggregation::empty(self.0);
+
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        $(key if key == kind_key!($kind $($key)?) => {
+                            aggregation.$kind = map.next_value()?;
+                        })+
+                        "meta" => aggregation.metadata = map.next_value()?,
+                        "aggs" => {
+                            aggregation.aggregations = map
+                                .next_value::<Option<SubAggregations>>()?
+                                .map(|aggregations| aggregations.0);
+                        }
+                        // any other kind of aggregation is kept as is
+                        _ => {
+                            aggregation
+                                .raw
+                                .get_or_insert_with(Default::default)
+                                .insert(key, map.next_value()?);
+                        }
+                    }
+                }
+
+                Ok(aggregation)
+            }
+        }
+
+        impl Aggregation {
+            /// Returns an `Aggregation` named `name` without any kind.
+            #[inline]
+            fn empty(name: String) -> Aggregation {
+                Aggregation {
+                    name,
+                    $($kind: None,)+
+                    raw: None,
+                    metadata: None,
+                    aggregations: None,
+                }
+            }
+
+            /// Returns an arbitrary `Aggregation` of a single kind, only
+            /// nesting other aggregations up to `depth` levels deep.
+            #[cfg(feature = "arbitrary")]
+            fn arbitrary_with_depth(
+                u: &mut Unstructured,
+                name: String,
+                depth: usize,
+            ) -> arbitrary::Result<Aggregation> {
+                let kinds: &[fn(&mut Unstructured, &mut Aggregation) -> arbitrary::Result<()>] = &[
+                    $(|u, aggregation| {
+                        aggregation.$kind = Some(u.arbitrary()?);
+                        Ok(())
+                    },)+
+                ];
+
+                let mut aggregation = Aggregation::empty(name);
+                u.choose(kinds)?(u, &mut aggregation)?;
+                aggregation.metadata = u.arbitrary()?;
+                if depth > 0 && u.arbitrary()? {
+                    let len = u.int_in_range(0..=3)?;
+                    aggregation.aggregations = Some(
+                        (0..len)
+                            .map(|i| {
+                                // sibling aggregations must have unique names
+                                let name = format!("{}_{}", i, crate::arbitrary::field(u)?);
+                                Aggregation::arbitrary_with_depth(u, name, depth - 1)
+                            })
+                            .collect::<arbitrary::Result<_>>()?,
+                    );
+                }
+
+                Ok(aggregation)
+            }
+        }
+    };
+}
+
+sub_aggregation! {
     // Metric aggregations
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    avg: Option<InnerAggregation>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    weighted_avg: Option<WeightedAverageAggregation>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    cardinality: Option<InnerAggregation>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    max: Option<InnerAggregation>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    min: Option<InnerAggregation>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    median_absolute_deviation: Option<InnerAggregation>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    percentiles: Option<InnerAggregation>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    percentile_ranks: Option<InnerAggregation>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    stats: Option<InnerAggregation>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    extended_stats: Option<InnerAggregation>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    sum: Option<InnerAggregation>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    value_count: Option<InnerAggregation>,
+    avg: InnerAggregation,
+    weighted_avg: WeightedAverageAggregation,
+    cardinality: InnerAggregation,
+    max: InnerAggregation,
+    min: InnerAggregation,
+    median_absolute_deviation: InnerAggregation,
+    percentiles: InnerAggregation,
+    percentile_ranks: PercentileRanksAggregation,
+    stats: InnerAggregation,
+    extended_stats: InnerAggregation,
+    sum: InnerAggregation,
+    value_count: InnerAggregation,
 
     // Bucketing aggregations
-    #[serde(default, rename = "filter", skip_serializing_if = "Option::is_none")]
-    filters: Option<CompoundQuery>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    terms: Option<TermsAggregation>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    range: Option<RangeAggregation>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    date_range: Option<DateRangeAggregation>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    date_histogram: Option<DateHistogramAggregation>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    auto_date_histogram: Option<AutoDateHistogramAggregation>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    histogram: Option<HistogramAggregation>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    variable_width_histogram: Option<VariableWidthHistogram>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    sampler: Option<SamplerAggregation>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    significant_text: Option<SignificantTextAggregation>,
+    filters as "filter": CompoundQuery,
+    terms: TermsAggregation,
+    range: RangeAggregation,
+    date_range: DateRangeAggregation,
+    date_histogram: DateHistogramAggregation,
+    auto_date_histogram: AutoDateHistogramAggregation,
+    histogram: HistogramAggregation,
+    variable_width_histogram: VariableWidthHistogram,
+    sampler: SamplerAggregation,
+    significant_text: SignificantTextAggregation,
 
     // Pipeline aggregations
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    bucket_script: Option<BucketScript>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    bucket_selector: Option<BucketSelector>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    bucket_sort: Option<BucketSort>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    nested: Option<NestedAggregation>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    reverse_nested: Option<ReverseNestedAggregation>,
-
-    #[serde(default, rename = "meta", skip_serializing_if = "Option::is_none")]
-    metadata: Option<crate::scalars::Map>,
-
-    #[serde(
-        default,
-        rename = "aggs",
-        skip_serializing_if = "Option::is_none",
-        with = "serde_sub_aggregations"
-    )]
-    aggregations: Option<Vec<Aggregation>>,
+    bucket_script: BucketScript,
+    bucket_selector: BucketSelector,
+    bucket_sort: BucketSort,
+    nested: NestedAggregation,
+    reverse_nested: ReverseNestedAggregation,
 }
 
-// TODO: auto generate this with a proc-macro?
-#[cfg(feature = "graphql")]
-impl From<AggregationInput> for SubAggregation {
+// TODO: re-use the serializer from the input type
+impl Serialize for Aggregation {
     #[inline]
-    fn from(aggregation: AggregationInput) -> SubAggregation {
-        SubAggregation {
-            avg: aggregation.avg.map(Into::into),
-            weighted_avg: aggregation.weighted_avg.map(Into::into),
-            cardinality: aggregation.cardinality.map(Into::into),
-            max: aggregation.max.map(Into::into),
-            min: aggregation.min.map(Into::into),
-            median_absolute_deviation: aggregation.median_absolute_deviation.map(Into::into),
-            percentiles: aggregation.percentiles.map(Into::into),
-            percentile_ranks: aggregation.percentile_ranks.map(Into::into),
-            stats: aggregation.stats.map(Into::into),
-            extended_stats: aggregation.extended_stats.map(Into::into),
-            sum: aggregation.sum.map(Into::into),
-            value_count: aggregation.value_count.map(Into::into),
-            filters: aggregation.filters.map(Into::into),
-            terms: aggregation.terms.map(Into::into),
-            range: aggregation.range.map(Into::into),
-            date_range: aggregation.date_range.map(Into::into),
-            date_histogram: aggregation.date_histogram.map(Into::into),
-            auto_date_histogram: aggregation.auto_date_histogram.map(Into::into),
-            histogram: aggregation.histogram.map(Into::into),
-            variable_width_histogram: aggregation.variable_width_histogram.map(Into::into),
-            sampler: aggregation.sampler.map(Into::into),
-            significant_text: aggregation.significant_text.map(Into::into),
-            bucket_script: aggregation.bucket_script.map(Into::into),
-            bucket_selector: aggregation.bucket_selector.map(Into::into),
-            bucket_sort: aggregation.bucket_sort.map(Into::into),
-            reverse_nested: aggregation.reverse_nested.map(Into::into),
-            nested: aggregation.nested.map(Into::into),
-            metadata: aggregation.metadata,
-            aggregations: aggregation
-                .aggregations
-                .map(|aggs| aggs.into_iter().map(Into::into).collect()),
-        }
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(&self.name, &SubAggregationRef::from(self))?;
+        map.end()
     }
 }
 
-// TODO: auto generate this with a proc-macro?
-impl From<Aggregation> for SubAggregation {
+#[cfg(feature = "schemars")]
+impl JsonSchema for Aggregation {
     #[inline]
-    fn from(aggregation: Aggregation) -> SubAggregation {
-        SubAggregation {
-            avg: aggregation.avg.map(Into::into),
-            weighted_avg: aggregation.weighted_avg.map(Into::into),
-            cardinality: aggregation.cardinality.map(Into::into),
-            max: aggregation.max.map(Into::into),
-            min: aggregation.min.map(Into::into),
-            median_absolute_deviation: aggregation.median_absolute_deviation.map(Into::into),
-            percentiles: aggregation.percentiles.map(Into::into),
-            percentile_ranks: aggregation.percentile_ranks.map(Into::into),
-            stats: aggregation.stats.map(Into::into),
-            extended_stats: aggregation.extended_stats.map(Into::into),
-            sum: aggregation.sum.map(Into::into),
-            value_count: aggregation.value_count.map(Into::into),
-            filters: aggregation.filters.map(Into::into),
-            terms: aggregation.terms.map(Into::into),
-            range: aggregation.range.map(Into::into),
-            date_range: aggregation.date_range.map(Into::into),
-            date_histogram: aggregation.date_histogram.map(Into::into),
-            auto_date_histogram: aggregation.auto_date_histogram.map(Into::into),
-            histogram: aggregation.histogram.map(Into::into),
-            variable_width_histogram: aggregation.variable_width_histogram.map(Into::into),
-            sampler: aggregation.sampler.map(Into::into),
-            significant_text: aggregation.significant_text.map(Into::into),
-            bucket_script: aggregation.bucket_script.map(Into::into),
-            bucket_selector: aggregation.bucket_selector.map(Into::into),
-            bucket_sort: aggregation.bucket_sort.map(Into::into),
-            reverse_nested: aggregation.reverse_nested.map(Into::into),
-            nested: aggregation.nested.map(Into::into),
-            metadata: aggregation.metadata,
-            aggregations: aggregation
-                .aggregations
-                .map(|aggs| aggs.into_iter().map(Into::into).collect()),
-        }
+    fn schema_name() -> String {
+        "Aggregation".to_string()
     }
-}
 
-impl Aggregation {
-    #[allow(clippy::missing_docs_in_private_items)]
-    pub(super) fn from_sub_aggregation(name: String, aggregation: SubAggregation) -> Aggregation {
-        Aggregation {
-            name,
-            avg: aggregation.avg.map(Into::into),
-            weighted_avg: aggregation.weighted_avg.map(Into::into),
-            cardinality: aggregation.cardinality.map(Into::into),
-            max: aggregation.max.map(Into::into),
-            min: aggregation.min.map(Into::into),
-            median_absolute_deviation: aggregation.median_absolute_deviation.map(Into::into),
-            percentiles: aggregation.percentiles.map(Into::into),
-            percentile_ranks: aggregation.percentile_ranks.map(Into::into),
-            stats: aggregation.stats.map(Into::into),
-            extended_stats: aggregation.extended_stats.map(Into::into),
-            sum: aggregation.sum.map(Into::into),
-            value_count: aggregation.value_count.map(Into::into),
-            filters: aggregation.filters.map(Into::into),
-            terms: aggregation.terms.map(Into::into),
-            range: aggregation.range.map(Into::into),
-            date_range: aggregation.date_range.map(Into::into),
-            date_histogram: aggregation.date_histogram.map(Into::into),
-            auto_date_histogram: aggregation.auto_date_histogram.map(Into::into),
-            histogram: aggregation.histogram.map(Into::into),
-            variable_width_histogram: aggregation.variable_width_histogram.map(Into::into),
-            sampler: aggregation.sampler.map(Into::into),
-            significant_text: aggregation.significant_text.map(Into::into),
-            bucket_script: aggregation.bucket_script.map(Into::into),
-            bucket_selector: aggregation.bucket_selector.map(Into::into),
-            bucket_sort: aggregation.bucket_sort.map(Into::into),
-            reverse_nested: aggregation.reverse_nested.map(Into::into),
-            nested: aggregation.nested.map(Into::into),
-            metadata: aggregation.metadata,
-            aggregations: aggregation
-                .aggregations
-                .map(|aggs| aggs.into_iter().map(Into::into).collect()),
-        }
+    #[inline]
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        crate::schema::keyed(gen.subschema_for::<SubAggregation>())
     }
 }
 
-// TODO: re-use the serializer from the input type
-impl Serialize for Aggregation {
+#[cfg(all(feature = "graphql", feature = "schemars"))]
+impl JsonSchema for AggregationInput {
     #[inline]
+    fn schema_name() -> String {
+        Aggregation::schema_name()
+    }
+
     #[inline]
-    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        let mut map = serializer.serialize_map(Some(1))?;
-        map.serialize_entry(&self.name, &SubAggregation::from(self.to_owned()))?;
-        map.end()
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        Aggregation::json_schema(gen)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Aggregation {
+    #[inline]
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let name = crate::arbitrary::field(u)?;
+        Aggregation::arbitrary_with_depth(u, name, crate::arbitrary::MAX_DEPTH)
     }
 }
 
@@ -257,9 +317,7 @@ impl<'de> serde::Deserialize<'de> for Aggregation {
                     .next_key::<String>()?
                     .ok_or_else(|| de::Error::missing_field("name"))?;
 
-                let agg: SubAggregation = map.next_value()?;
-
-                Ok(Aggregation::from_sub_aggregation(name, agg))
+                map.next_value_seed(AggregationBody(name))
             }
         }
 
@@ -267,11 +325,84 @@ impl<'de> serde::Deserialize<'de> for Aggregation {
     }
 }
 
+/// Deserializes the body of an `Aggregation` with the given name directly,
+/// i.e. without going through a `SubAggregation`.
+struct AggregationBody(String);
+
+impl<'de> DeserializeSeed<'de> for AggregationBody {
+    type Value = Aggregation;
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<Aggregation, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+/// The sub-aggregations of an `Aggregation`, keyed by their names, in the
+/// order they are deserialized.
+struct SubAggregations(Vec<Aggregation>);
+
+impl<'de> Deserialize<'de> for SubAggregations {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<SubAggregations, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        /// Visits `SubAggregations` during deserialization.
+        struct SubAggregationsVisitor;
+
+        impl<'de> Visitor<'de> for SubAggregationsVisitor {
+            type Value = SubAggregations;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map of `Aggregation`s by name")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut aggregations = Vec::with_capacity(map.size_hint().unwrap_or(0));
+                while let Some(name) = map.next_key::<String>()? {
+                    aggregations.push(map.next_value_seed(AggregationBody(name))?);
+                }
+
+                Ok(SubAggregations(aggregations))
+            }
+        }
+
+        deserializer.deserialize_map(SubAggregationsVisitor)
+    }
+}
+
 /// The raw JSON response to performing an aggregation from Elasticsearch.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Default, Debug)]
 pub(crate) struct ElasticAggregationResponse {
     #[serde(default, alias = "aggs")]
     aggregations: HashMap<String, ElasticAggregationResult>,
+
+    #[serde(default, skip_serializing)]
+    hits: Option<ElasticHits>,
+
+    #[serde(default)]
+    took: Option<u64>,
+
+    #[serde(default)]
+    timed_out: Option<bool>,
+
+    #[serde(default, rename = "_shards")]
+    shards: Option<Shards>,
+}
+
+/// The hits of the raw JSON response, of which only the total is kept; the
+/// hits themselves are usually empty, i.e. with a `size` of `0`.
+#[derive(Deserialize, Default, Debug)]
+struct ElasticHits {
+    #[serde(default)]
+    total: Option<Count>,
 }
 
 impl From<ElasticAggregationResponse> for Response {
@@ -285,14 +416,15 @@ impl From<ElasticAggregationResponse> for Response {
         // (parent, name) => AggregationResult
         let mut results: HashMap<(Option<&String>, String), ComputedResult> = HashMap::new();
 
-        let mut pending_aggs: Vec<(Option<&String>, _)> = vec![(None, &aggs)];
+        // (parent, keys of the enclosing buckets, aggregations)
+        let mut pending_aggs: Vec<(Option<&String>, Vec<&String>, _)> = vec![(None, vec![], &aggs)];
         while let Some(curr) = pending_aggs.pop() {
-            let (parent, aggs) = curr;
+            let (parent, bucket_keys, aggs) = curr;
 
             for (ty_and_name, curr_agg) in aggs.iter() {
                 let (ty, name) = split_ty_and_name(ty_and_name);
 
-                let mut handle_leaf_agg = |agg: &ElasticAggregationResult| {
+                let mut handle_leaf_agg = |agg: &ElasticAggregationResult, key: Option<&String>| {
                     if let Some(value) = agg.value_or_doc_count() {
                         if !agg.should_skip() {
                             #[allow(clippy::clone_on_copy)] // necessary for TypedBuilder
@@ -305,7 +437,13 @@ impl From<ElasticAggregationResponse> for Response {
                                         type_: ty.clone(),
                                         fields: vec![],
                                         values: vec![],
+                                        bucket_keys: vec![],
                                         metadata: agg.metadata.to_owned(),
+                                        // reported by e.g. a `terms` aggregation
+                                        // itself, not by its buckets
+                                        doc_count_error_upper_bound: curr_agg
+                                            .doc_count_error_upper_bound,
+                                        sum_other_doc_count: curr_agg.sum_other_doc_count,
                                     });
 
                             if let Some(key) = agg.parent_key.as_ref().or_else(|| agg.key.as_ref())
@@ -315,19 +453,28 @@ impl From<ElasticAggregationResponse> for Response {
 
                             // TODO: should we only push this if there is a `key`?
                             result.values.push(value);
+                            result
+                                .bucket_keys
+                                .push(bucket_keys.iter().copied().chain(key).cloned().collect());
                         }
                     }
                 };
 
-                handle_leaf_agg(curr_agg);
+                handle_leaf_agg(curr_agg, None);
 
-                pending_aggs.push((None, &curr_agg.aggregations));
+                pending_aggs.push((None, bucket_keys.clone(), &curr_agg.aggregations));
 
                 for bucket_agg in curr_agg.buckets.iter() {
                     if bucket_agg.aggregations.is_empty() {
-                        handle_leaf_agg(bucket_agg);
+                        handle_leaf_agg(bucket_agg, bucket_agg.key.as_ref());
                     } else {
-                        pending_aggs.push((curr_agg.parent_key.as_ref(), &bucket_agg.aggregations));
+                        let mut bucket_keys = bucket_keys.clone();
+                        bucket_keys.extend(bucket_agg.key.as_ref());
+                        pending_aggs.push((
+                            curr_agg.parent_key.as_ref(),
+                            bucket_keys,
+                            &bucket_agg.aggregations,
+                        ));
                     }
                 }
             }
@@ -335,6 +482,10 @@ impl From<ElasticAggregationResponse> for Response {
 
         Response {
             aggregations: results.into_iter().map(|(_, agg)| agg).collect(),
+            total_hits: response.hits.and_then(|hits| hits.total),
+            took: response.took,
+            timed_out: response.timed_out,
+            shards: response.shards,
         }
     }
 }
@@ -358,9 +509,11 @@ struct ElasticAggregationResult {
     parent_key: Option<String>,
     key: Option<String>,
     doc_count: Option<u64>,
+    doc_count_error_upper_bound: Option<u64>,
+    sum_other_doc_count: Option<u64>,
     value: Option<f64>,
     buckets: Vec<ElasticAggregationResult>,
-    metadata: Option<crate::scalars::Map>,
+    metadata: Option<AggregationMetadata>,
     aggregations: HashMap<String, ElasticAggregationResult>,
 }
 
@@ -378,123 +531,409 @@ impl ElasticAggregationResult {
         })
     }
 
+    #[inline]
     fn should_skip(&self) -> bool {
-        let mut skip = false;
-        if let Some(meta) = self.metadata.as_ref() {
-            if let Some(s) = meta.get("_skip") {
-                if let Some(b) = s.as_bool() {
-                    skip = b;
-                }
-            }
-        }
-        skip
+        matches!(self.metadata, Some(ref metadata) if metadata.skip)
     }
 }
 
 // TODO: replace with default implementation from Serde using an enum for ElasticAggregationResult
 impl<'de> serde::Deserialize<'de> for ElasticAggregationResult {
+    #[inline]
     fn deserialize<D>(deserializer: D) -> Result<ElasticAggregationResult, D::Error>
     where
         D: Deserializer<'de>,
     {
-        #[doc(hidden)]
-        /// Visits a `Agg` during deserialization.
-        struct ElasticAggregationResultVisitor;
+        ElasticAggregationResultSeed {
+            strict: false,
+            path: String::new(),
+        }
+        .deserialize(deserializer)
+    }
+}
+
+/// Deserializes an `ElasticAggregationResult` at `path` (e.g.
+/// `terms#by_status.buckets[0]`), which is only used in errors.
+///
+/// Unless `strict`, values that can't be parsed as (sub-)aggregation results
+/// are ignored.
+struct ElasticAggregationResultSeed {
+    strict: bool,
+    path: String,
+}
+
+impl ElasticAggregationResultSeed {
+    /// Returns a seed for the value at `key` of the current result.
+    #[inline]
+    fn child(&self, key: impl fmt::Display) -> ElasticAggregationResultSeed {
+        ElasticAggregationResultSeed {
+            strict: self.strict,
+            path: if self.path.is_empty() {
+                key.to_string()
+            } else {
+                format!("{}.{}", self.path, key)
+            },
+        }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for ElasticAggregationResultSeed {
+    type Value = ElasticAggregationResult;
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<ElasticAggregationResult, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
 
-        impl<'de> Visitor<'de> for ElasticAggregationResultVisitor {
-            type Value = ElasticAggregationResult;
+impl<'de> Visitor<'de> for ElasticAggregationResultSeed {
+    type Value = ElasticAggregationResult;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if self.path.is_empty() {
+            formatter.write_str("an aggregation result")
+        } else {
+            write!(formatter, "an aggregation result at `{}`", self.path)
+        }
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        // Make our own value so we don't need to depend on `serde_json::Value`
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Value {
+            Null,
+            Bool(bool),
+            Int(u64),
+            Float(f64),
+            String(String),
+            Array(Vec<Value>),
+            Object(HashMap<String, Value>),
+        }
+
+        let mut result = ElasticAggregationResult::default();
+
+        while let Some(k) = map.next_key::<String>()? {
+            match k.as_str() {
+                "key" => match map.next_value()? {
+                    Value::Bool(val) => {
+                        result.key = Some(val.to_string());
+                    }
+                    Value::Int(val) => {
+                        result.key = Some(val.to_string());
+                    }
+                    Value::Float(val) => {
+                        result.key = Some(val.to_string());
+                    }
+                    Value::String(val) => {
+                        result.key = Some(val);
+                    }
+                    _ if self.strict => {
+                        return Err(de::Error::custom(format_args!(
+                            "unsupported `key` of the aggregation result at `{}`",
+                            self.path
+                        )));
+                    }
+                    _ => {}
+                },
+                "key_as_string" => result.key = Some(map.next_value()?),
+                // `null` if there were no values to compute it over
+                "value" => result.value = map.next_value()?,
+                "buckets" => {
+                    result.buckets = map.next_value_seed(BucketsSeed(self.child("buckets")))?
+                }
+                // the values of e.g. `percentiles`, which are treated as buckets
+                "values" => {
+                    result.buckets = map.next_value_seed(ValuesSeed(self.child("values")))?
+                }
+                "doc_count" => result.doc_count = Some(map.next_value()?),
+                "doc_count_error_upper_bound" => {
+                    result.doc_count_error_upper_bound = Some(map.next_value()?)
+                }
+                "sum_other_doc_count" => result.sum_other_doc_count = Some(map.next_value()?),
+                "interval" => {
+                    // Must throw the next value away, otherwise the parser will fail
+                    let _: Value = map.next_value()?;
+                }
+                // formatted/derived values, which aren't errors even when strict
+                "value_as_string" | "from" | "to" | "from_as_string" | "to_as_string"
+                | "bg_count" | "score" => {
+                    let _: Value = map.next_value()?;
+                }
+                "meta" | "metadata" => result.metadata = Some(map.next_value()?),
+                _ => match map.next_value_seed(self.child(&k)) {
+                    Ok(val) => {
+                        // TODO(perf): make this a str?
+                        result.aggregations.insert(k.to_string(), val);
+                    }
+                    Err(err) if self.strict => return Err(err),
+                    Err(_err) => {}
+                },
+            }
+        }
+
+        let key = &result.key;
+        result.aggregations = result
+            .aggregations
+            .into_iter()
+            .map(|(name, mut agg)| {
+                agg.parent_key = key.clone();
+                (name, agg)
+            })
+            .collect();
+
+        Ok(result)
+    }
+}
+
+/// Deserializes the `buckets` of an `ElasticAggregationResult`, given the seed
+/// for the `buckets` themselves.
+///
+/// The buckets are either an array or, for aggregations with `keyed: true`, an
+/// object keyed by the key of each bucket (e.g. `{ "*-100.0": { ... } }`).
+struct BucketsSeed(ElasticAggregationResultSeed);
+
+impl<'de> DeserializeSeed<'de> for BucketsSeed {
+    type Value = Vec<ElasticAggregationResult>;
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for BucketsSeed {
+    type Value = Vec<ElasticAggregationResult>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "the buckets at `{}`", self.0.path)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut buckets = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(bucket) = seq.next_element_seed(ElasticAggregationResultSeed {
+            strict: self.0.strict,
+            path: format!("{}[{}]", self.0.path, buckets.len()),
+        })? {
+            buckets.push(bucket);
+        }
+
+        Ok(buckets)
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut buckets = Vec::with_capacity(map.size_hint().unwrap_or(0));
+        while let Some(key) = map.next_key::<String>()? {
+            let mut bucket = map.next_value_seed(self.0.child(&key))?;
+
+            // e.g. the buckets of `range` and `filters` only have a key here
+            if bucket.key.is_none() {
+                for aggregation in bucket.aggregations.values_mut() {
+                    aggregation.parent_key = Some(key.clone());
+                }
+                bucket.key = Some(key);
+            }
+            buckets.push(bucket);
+        }
+
+        Ok(buckets)
+    }
+}
+
+/// Deserializes the `values` of a multi-value metrics aggregation result (e.g.
+/// `percentiles`) as buckets with a `key` and a `value` each, whether they are
+/// keyed (e.g. `{ "95.0": 1.5 }`) or not (e.g. `[{ "key": 95.0, "value": 1.5 }]`).
+struct ValuesSeed(ElasticAggregationResultSeed);
+
+impl<'de> DeserializeSeed<'de> for ValuesSeed {
+    type Value = Vec<ElasticAggregationResult>;
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for ValuesSeed {
+    type Value = Vec<ElasticAggregationResult>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "the values at `{}`", self.0.path)
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut values = Vec::with_capacity(map.size_hint().unwrap_or(0));
+        while let Some(key) = map.next_key::<String>()? {
+            if key.ends_with("_as_string") {
+                let _: de::IgnoredAny = map.next_value()?;
+            } else {
+                values.push(ElasticAggregationResult {
+                    // `null` if there were no values to compute it over
+                    value: map.next_value()?,
+                    key: Some(key),
+                    ..Default::default()
+                });
+            }
+        }
+
+        Ok(values)
+    }
+
+    #[inline]
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        BucketsSeed(self.0).visit_seq(seq)
+    }
+}
+
+/// An aggregation [`Response`] that fails to deserialize instead of silently
+/// ignoring (sub-)aggregation results it can't parse.
+///
+/// The error names the path of the offending result, e.g.
+/// `terms#by_status.buckets[0].avg#latency.histogram`.
+///
+/// ```
+/// use elastiql::aggregation::StrictResponse;
+///
+/// let json = serde_json::json!({
+///     "aggregations": { "avg#latency": { "value": 1.5, "histogram": [1, 2] } }
+/// });
+///
+/// let err = serde_json::from_value::<StrictResponse>(json).unwrap_err();
+/// assert!(err.to_string().contains("`avg#latency.histogram`"), "{}", err);
+/// ```
+#[derive(Clone, Debug)]
+pub struct StrictResponse(pub Response);
+
+impl<'de> Deserialize<'de> for StrictResponse {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<StrictResponse, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        /// Visits the `aggregations` of a `StrictResponse`.
+        struct StrictResponseVisitor;
+
+        impl<'de> Visitor<'de> for StrictResponseVisitor {
+            type Value = StrictResponse;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                formatter.write_str("an `ElasticAggregationResult`")
+                formatter.write_str("an aggregation response")
             }
 
             fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
             where
                 A: MapAccess<'de>,
             {
-                // Make our own value so we don't need to depend on `serde_json::Value`
-                #[derive(Deserialize)]
-                #[serde(untagged)]
-                enum Value {
-                    Null,
-                    Bool(bool),
-                    Int(u64),
-                    Float(f64),
-                    String(String),
-                    Array(Vec<Value>),
-                    Object(HashMap<String, Value>),
-                }
-
-                let mut result = ElasticAggregationResult::default();
-
-                while let Some(k) = map.next_key::<String>()? {
-                    match k.as_str() {
-                        "key" => match map.next_value()? {
-                            Value::Bool(val) => {
-                                result.key = Some(val.to_string());
-                            }
-                            Value::Int(val) => {
-                                result.key = Some(val.to_string());
-                            }
-                            Value::Float(val) => {
-                                result.key = Some(val.to_string());
-                            }
-                            Value::String(val) => {
-                                result.key = Some(val);
-                            }
-                            _ => {}
-                        },
-                        "key_as_string" => result.key = Some(map.next_value()?),
-                        "value" => result.value = Some(map.next_value()?),
-                        "buckets" => result.buckets = map.next_value()?,
-                        "doc_count" => result.doc_count = Some(map.next_value()?),
-                        "doc_count_error_upper_bound" | "sum_other_doc_count" | "interval" => {
-                            // Must throw the next value away, otherwise the parser will fail
-                            let _: Value = map.next_value()?;
+                let mut response = ElasticAggregationResponse::default();
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "aggregations" | "aggs" => {
+                            response.aggregations = map.next_value_seed(StrictResultsSeed)?;
+                        }
+                        "took" => response.took = map.next_value()?,
+                        "timed_out" => response.timed_out = map.next_value()?,
+                        "_shards" => response.shards = map.next_value()?,
+                        "hits" => response.hits = map.next_value()?,
+                        _ => {
+                            map.next_value::<de::IgnoredAny>()?;
                         }
-                        "meta" | "metadata" => result.metadata = Some(map.next_value()?),
-                        _ => match map.next_value::<ElasticAggregationResult>() {
-                            Ok(val) => {
-                                // TODO(perf): make this a str?
-                                result.aggregations.insert(k.to_string(), val);
-                            }
-                            Err(_err) => {
-                                // TODO: should we error if this happens?
-                            }
-                        },
                     }
                 }
 
-                let key = &result.key;
-                result.aggregations = result
-                    .aggregations
-                    .into_iter()
-                    .map(|(name, mut agg)| {
-                        agg.parent_key = key.clone();
-                        (name, agg)
-                    })
-                    .collect();
-
-                Ok(result)
+                Ok(StrictResponse(response.into()))
             }
         }
 
-        deserializer.deserialize_map(ElasticAggregationResultVisitor)
+        deserializer.deserialize_map(StrictResponseVisitor)
+    }
+}
+
+/// Strictly deserializes the top-level aggregation results, by name.
+struct StrictResultsSeed;
+
+impl<'de> DeserializeSeed<'de> for StrictResultsSeed {
+    type Value = HashMap<String, ElasticAggregationResult>;
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for StrictResultsSeed {
+    type Value = HashMap<String, ElasticAggregationResult>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("aggregation results by name")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut results = HashMap::with_capacity(map.size_hint().unwrap_or(0));
+        while let Some(name) = map.next_key::<String>()? {
+            let seed = ElasticAggregationResultSeed {
+                strict: true,
+                path: name.clone(),
+            };
+            let result = map.next_value_seed(seed)?;
+            results.insert(name, result);
+        }
+
+        Ok(results)
+    }
+}
+
+impl From<StrictResponse> for Response {
+    #[inline]
+    fn from(response: StrictResponse) -> Self {
+        response.0
     }
 }
 
 pub(super) mod serde_sub_aggregations {
-    //! ser/de implementation for `SubAggregations`.
-    use std::collections::HashMap;
+    //! ser implementation for `SubAggregations`; they are deserialized by
+    //! `SubAggregations` itself.
 
-    use serde::{ser::SerializeMap, Deserialize, Deserializer, Serializer};
+    use serde::{ser::SerializeMap, Serializer};
 
-    use super::{Aggregation, SubAggregation};
+    use super::{Aggregation, SubAggregationRef};
 
     /// Serializes the data to a format expected by Elasticsearch, with the
     /// field name as a key.
     #[inline]
-    pub(crate) fn serialize<S>(aggs: &Option<Vec<Aggregation>>, ser: S) -> Result<S::Ok, S::Error>
+    pub(in crate::aggregation) fn serialize<S>(
+        aggs: &Option<&[Aggregation]>,
+        ser: S,
+    ) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
@@ -502,7 +941,7 @@ pub(super) mod serde_sub_aggregations {
             let mut map = ser.serialize_map(Some(aggs.len()))?;
 
             for agg in aggs.iter() {
-                map.serialize_entry(agg.name.as_str(), &SubAggregation::from(agg.to_owned()))?;
+                map.serialize_entry(agg.name.as_str(), &SubAggregationRef::from(agg))?;
             }
 
             map.end()
@@ -510,45 +949,6 @@ pub(super) mod serde_sub_aggregations {
             ser.serialize_none()
         }
     }
-
-    /// Deserializes the data from a format expected by Elasticsearch, with the
-    /// field name as a key.
-    #[inline]
-    #[cfg(not(test))]
-    pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<Option<Vec<Aggregation>>, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        Ok(
-            Option::deserialize(deserializer)?.map(|agg: HashMap<String, SubAggregation>| {
-                agg.into_iter()
-                    .map(|(name, sub_agg)| Aggregation::from_sub_aggregation(name, sub_agg))
-                    .collect()
-            }),
-        )
-    }
-
-    // HACK: this is so we don't have to manually derive PartialEq and potentially forget to add fields
-    #[doc(hidden)]
-    #[cfg(test)]
-    pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<Option<Vec<Aggregation>>, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        Ok(
-            Option::deserialize(deserializer)?.map(|agg: HashMap<String, SubAggregation>| {
-                let mut aggs: Vec<Aggregation> = agg
-                    .into_iter()
-                    .map(|(name, sub_agg)| Aggregation::from_sub_aggregation(name, sub_agg))
-                    .collect();
-
-                #[allow(clippy::expect_used)]
-                aggs.sort_by(|a, b| a.name.partial_cmp(&b.name).expect("invalid ordering"));
-
-                aggs
-            }),
-        )
-    }
 }
 
 #[cfg(test)]
@@ -557,7 +957,10 @@ mod tests {
 
     use serde_json::json;
 
-    use crate::search::query::TermsQuery;
+    use crate::search::{
+        query::{TermQuery, TermsQuery},
+        SortOrder,
+    };
 
     /// Simple smoke test. This also makes it so editors pick up this test mod as runnable.
     #[test]
@@ -569,6 +972,85 @@ mod tests {
         let _: Response = serde_json::from_value(result).unwrap();
     }
 
+    #[test]
+    fn can_display_as_json() {
+        let input = json!({ "agg": { "value_count": { "field": "id" } } });
+        let aggregation: Aggregation = serde_json::from_value(input).unwrap();
+
+        assert_eq!(
+            aggregation.to_string(),
+            r#"{"agg":{"value_count":{"field":"id"}}}"#
+        );
+        assert!(format!("{:#}", aggregation).contains("\n"));
+    }
+
+    #[test]
+    fn can_serialize_as_canonical_json() {
+        let input = json!({
+            "agg": {
+                "terms": { "field": "status", "size": 10 },
+                "aggs": { "b": { "max": { "field": "id" } }, "a": { "min": { "field": "id" } } }
+            }
+        });
+        let aggregation: Aggregation = serde_json::from_value(input).unwrap();
+
+        let json = aggregation.to_canonical_json().unwrap();
+        let canonical = r#"{"agg":{"aggs":{"a":{"min":{"field":"id"}},"b":{"max":{"field":"id"}}},"terms":{"field":"status","size":10}}}"#;
+        assert_eq!(json, canonical);
+    }
+
+    /// Times (de)serializing deeply nested aggregations, e.g.
+    /// `cargo test --release measure_nested_aggregations -- --ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn measure_nested_aggregations() {
+        use std::time::Instant;
+
+        /// Returns `width` `terms` aggregations, each with the same
+        /// sub-aggregations `depth - 1` levels deep.
+        fn nested(depth: usize, width: usize) -> serde_json::Value {
+            let mut aggs = serde_json::Map::new();
+            for i in 0..width {
+                let mut body = json!({ "terms": { "field": format!("field{}", i), "size": 10 } });
+                if depth > 1 {
+                    body["aggs"] = nested(depth - 1, width);
+                }
+                aggs.insert(format!("agg_{}_{}", depth, i), body);
+            }
+            aggs.into()
+        }
+
+        let json = json!({
+            "root": { "terms": { "field": "root" }, "aggs": nested(5, 4) }
+        })
+        .to_string();
+
+        let iterations = 1_000;
+        let start = Instant::now();
+        for _ in 0..iterations {
+            let aggregation: Aggregation = serde_json::from_str(&json).unwrap();
+            assert_eq!(aggregation.name, "root");
+        }
+        let deserialize = start.elapsed() / iterations;
+
+        let aggregation: Aggregation = serde_json::from_str(&json).unwrap();
+        let start = Instant::now();
+        for _ in 0..iterations {
+            assert_eq!(
+                serde_json::to_string(&aggregation).unwrap().len(),
+                json.len()
+            );
+        }
+        let serialize = start.elapsed() / iterations;
+
+        println!(
+            "{} bytes of nested aggregations: deserialize {:?}, serialize {:?}",
+            json.len(),
+            deserialize,
+            serialize
+        );
+    }
+
     mod aggregation_input {
         use super::*;
 
@@ -579,6 +1061,26 @@ mod tests {
             let _: Aggregation = serde_json::from_value(input).unwrap();
         }
 
+        #[cfg(feature = "graphql")]
+        #[test]
+        fn can_round_trip_inputs() {
+            let json = json!({
+                "PER_CATEGORY": {
+                    "terms": { "field": "category" },
+                    "aggs": {
+                        "AVG_PRICE": { "avg": { "field": "price" } },
+                        "SALES_RATE": { "rate": { "unit": "year" } }
+                    },
+                    "meta": { "color": "blue" }
+                }
+            });
+
+            let input: AggregationInput = serde_json::from_value(json.clone()).unwrap();
+            assert_eq!(input.name, "PER_CATEGORY");
+            assert_eq!(input.aggregations.as_ref().map(Vec::len), Some(2));
+            assert_eq!(serde_json::to_value(&input).unwrap(), json);
+        }
+
         macro_rules! test_case {
             ($name:ident : $agg:expr, $json_value:expr) => {
                 mod $name {
@@ -683,6 +1185,77 @@ mod tests {
             })
         );
 
+        test_case!(
+            raw_with_nest:
+            Aggregation::builder()
+                .name("PER_CATEGORY")
+                .terms(Some("category".into()))
+                .aggregations(vec![
+                    Aggregation::builder()
+                        .name("SALES_RATE")
+                        .raw(Some(
+                            [("rate".to_string(), json!({ "unit": "year" }).into())]
+                                .iter()
+                                .cloned()
+                                .collect()
+                        ))
+                        .build(),
+                ])
+                .build(),
+            json!({
+                "PER_CATEGORY": {
+                    "terms": { "field": "category" },
+                    "aggs": {
+                        "SALES_RATE": { "rate": { "unit": "year" } }
+                    }
+                },
+            })
+        );
+
+        test_case!(
+            bucket_script_with_gap_policy:
+            Aggregation::builder()
+                .name("PER_MONTH")
+                .date_histogram(
+                    DateHistogramAggregation::builder()
+                        .field("timestamp")
+                        .calendar_interval(Some(CalendarInterval::Month))
+                        .build()
+                )
+                .aggregations(vec![
+                    Aggregation::builder()
+                        .name("SALES")
+                        .sum(Some("price".into()))
+                        .build(),
+                    Aggregation::builder()
+                        .name("SALES_PER_DAY")
+                        .bucket_script(
+                            BucketScript::builder()
+                                .script("params.sales / 30")
+                                .buckets_path(Some(serde_json::from_value(json!({ "sales": "SALES" })).unwrap()))
+                                .gap_policy(Some(GapPolicy::KeepValues))
+                                .build()
+                        )
+                        .build(),
+                ])
+                .build(),
+            json!({
+                "PER_MONTH": {
+                    "date_histogram": { "field": "timestamp", "calendar_interval": "month" },
+                    "aggs": {
+                        "SALES": { "sum": { "field": "price" } },
+                        "SALES_PER_DAY": {
+                            "bucket_script": {
+                                "script": "params.sales / 30",
+                                "buckets_path": { "sales": "SALES" },
+                                "gap_policy": "keep_values"
+                            }
+                        }
+                    }
+                },
+            })
+        );
+
         test_case!(
             date_range_with_nest:
             Aggregation::builder()
@@ -716,6 +1289,170 @@ mod tests {
                 },
             })
         );
+
+        test_case!(
+            date_histogram_with_order:
+            Aggregation::builder()
+                .name("PER_DAY")
+                .date_histogram(
+                    DateHistogramAggregation::builder()
+                        .field("timestamp")
+                        .fixed_interval(Some("1d".into()))
+                        .min_doc_count(Some(1))
+                        .order(Some(BucketOrder::new("_count", SortOrder::Desc)))
+                        .build()
+                )
+                .build(),
+            json!({
+                "PER_DAY": {
+                    "date_histogram": {
+                        "field": "timestamp",
+                        "fixed_interval": "1d",
+                        "min_doc_count": 1,
+                        "order": { "_count": "desc" }
+                    }
+                },
+            })
+        );
+
+        test_case!(
+            keyed_date_histogram:
+            Aggregation::builder()
+                .name("PER_DAY")
+                .date_histogram(
+                    DateHistogramAggregation::builder()
+                        .field("timestamp")
+                        .calendar_interval(Some(CalendarInterval::Day))
+                        .keyed(Some(true))
+                        .build()
+                )
+                .build(),
+            json!({
+                "PER_DAY": {
+                    "date_histogram": {
+                        "field": "timestamp",
+                        "calendar_interval": "day",
+                        "keyed": true
+                    }
+                },
+            })
+        );
+
+        test_case!(
+            date_histogram_with_bounds:
+            Aggregation::builder()
+                .name("PER_DAY")
+                .date_histogram(
+                    DateHistogramAggregation::builder()
+                        .field("timestamp")
+                        .calendar_interval(Some(CalendarInterval::Day))
+                        .min_doc_count(Some(0))
+                        .extended_bounds(Some(
+                            DateHistogramBounds::builder()
+                                .min(Some("now-7d/d".into()))
+                                .max(Some("now/d".into()))
+                                .build()
+                        ))
+                        .hard_bounds(Some(
+                            DateHistogramBounds::builder()
+                                .min(Some("now-30d/d".into()))
+                                .build()
+                        ))
+                        .build()
+                )
+                .build(),
+            json!({
+                "PER_DAY": {
+                    "date_histogram": {
+                        "field": "timestamp",
+                        "calendar_interval": "day",
+                        "min_doc_count": 0,
+                        "extended_bounds": { "min": "now-7d/d", "max": "now/d" },
+                        "hard_bounds": { "min": "now-30d/d" }
+                    }
+                },
+            })
+        );
+
+        test_case!(
+            significant_text_with_options:
+            Aggregation::builder()
+                .name("KEYWORDS")
+                .significant_text(
+                    SignificantTextAggregation::builder()
+                        .field("content")
+                        .shard_size(Some(100))
+                        .min_doc_count(Some(3))
+                        .filter_duplicate_text(true)
+                        .background_filter(Some(TermQuery::new("team", "sales").into()))
+                        .chi_square(Some(
+                            SignificanceHeuristic::builder()
+                                .background_is_superset(Some(false))
+                                .build()
+                        ))
+                        .build()
+                )
+                .build(),
+            json!({
+                "KEYWORDS": {
+                    "significant_text": {
+                        "field": "content",
+                        "shard_size": 100,
+                        "min_doc_count": 3,
+                        "filter_duplicate_text": true,
+                        "background_filter": {
+                            "bool": { "filter": [{ "term": { "team": { "value": "sales" } } }] }
+                        },
+                        "chi_square": { "background_is_superset": false }
+                    }
+                },
+            })
+        );
+
+        test_case!(
+            significant_text_with_jlh:
+            Aggregation::builder()
+                .name("KEYWORDS")
+                .significant_text(
+                    SignificantTextAggregation::builder()
+                        .field("content")
+                        .jlh(true)
+                        .build()
+                )
+                .build(),
+            json!({
+                "KEYWORDS": {
+                    "significant_text": {
+                        "field": "content",
+                        "filter_duplicate_text": false,
+                        "jlh": {}
+                    }
+                },
+            })
+        );
+
+        test_case!(
+            percentile_ranks:
+            Aggregation::builder()
+                .name("LOAD_TIME_RANKS")
+                .percentile_ranks(
+                    PercentileRanksAggregation::builder()
+                        .field(Some("load_time".into()))
+                        .values(vec![500.0, 600.0])
+                        .keyed(Some(false))
+                        .build()
+                )
+                .build(),
+            json!({
+                "LOAD_TIME_RANKS": {
+                    "percentile_ranks": {
+                        "field": "load_time",
+                        "values": [500.0, 600.0],
+                        "keyed": false
+                    }
+                },
+            })
+        );
     }
 
     mod aggregation_results {
@@ -723,7 +1460,7 @@ mod tests {
 
         use std::cmp::Ordering;
 
-        use crate::aggregation::Response;
+        use crate::{aggregation::Response, search::CountRelation};
 
         /// Simple smoke test. This also makes it so editors pick up this test mod as runnable.
         #[test]
@@ -732,6 +1469,296 @@ mod tests {
             let _: Response = serde_json::from_value(result).unwrap();
         }
 
+        #[test]
+        fn strict_response_names_the_unparseable_result() {
+            let result = json!({
+                "aggregations": {
+                    "PER_AGENT": {
+                        "buckets": [
+                            { "key": "dallin", "doc_count": 7, "avg#AVG_DURATION": { "value": 1.0 } },
+                            { "key": "will", "doc_count": 7, "avg#AVG_DURATION": 2.0 }
+                        ]
+                    }
+                }
+            });
+
+            let lenient: Response = serde_json::from_value(result.clone()).unwrap();
+            let average = lenient
+                .aggregations
+                .iter()
+                .find(|result| result.name == "AVG_DURATION")
+                .unwrap();
+            assert_eq!(average.values, vec![1.0]);
+
+            let err = serde_json::from_value::<StrictResponse>(result).unwrap_err();
+            assert!(
+                err.to_string()
+                    .contains("`PER_AGENT.buckets[1].avg#AVG_DURATION`"),
+                "{}",
+                err
+            );
+        }
+
+        #[test]
+        fn strict_response_accepts_parseable_results() {
+            let result = json!({
+                "aggregations": {
+                    "PER_AGENT": {
+                        "doc_count_error_upper_bound": 0,
+                        "sum_other_doc_count": 0,
+                        "buckets": [
+                            { "key": "dallin", "doc_count": 7, "avg#AVG_DURATION": { "value": 1.0 } }
+                        ]
+                    }
+                }
+            });
+
+            let response: Response = serde_json::from_value::<StrictResponse>(result)
+                .unwrap()
+                .into();
+            assert_eq!(response.aggregations[0].name, "AVG_DURATION");
+            assert_eq!(response.aggregations[0].values, vec![1.0]);
+        }
+
+        #[test]
+        fn keeps_the_request_metadata() {
+            let result = json!({
+                "took": 1204,
+                "timed_out": false,
+                "_shards": { "total": 5, "successful": 4, "skipped": 0, "failed": 1 },
+                "aggregations": { "avg#AVG_DURATION": { "value": 1.0 } }
+            });
+
+            let lenient: Response = serde_json::from_value(result.clone()).unwrap();
+            let strict: Response = serde_json::from_value::<StrictResponse>(result)
+                .unwrap()
+                .into();
+            for response in [lenient, strict].iter() {
+                assert_eq!(response.took, Some(1204));
+                assert_eq!(response.timed_out, Some(false));
+                assert_eq!(response.shards.map(|shards| shards.failed), Some(1));
+                assert!(response.is_partial());
+            }
+
+            let response: Response = serde_json::from_value(json!({ "aggregations": {} })).unwrap();
+            assert_eq!((response.took, response.shards), (None, None));
+            assert!(!response.is_partial());
+        }
+
+        #[test]
+        fn labels_results_by_their_metadata() {
+            let result = json!({
+                "aggregations": {
+                    "avg#AVG_DURATION": {
+                        "value": 1.0,
+                        "meta": { "_label": "Average duration", "unit": "s" }
+                    },
+                    "max#MAX_DURATION": { "value": 2.0 },
+                    "min#MIN_DURATION": { "value": 0.0, "meta": { "_skip": true } }
+                }
+            });
+
+            let response: Response = serde_json::from_value(result).unwrap();
+            let mut labels: Vec<_> = response
+                .aggregations
+                .iter()
+                .map(ComputedResult::label)
+                .collect();
+            labels.sort_unstable();
+            assert_eq!(labels, vec!["Average duration", "MAX_DURATION"]);
+        }
+
+        #[test]
+        fn keeps_the_total_hits_of_aggregation_only_searches() {
+            let result = json!({
+                "hits": { "total": { "value": 10_000, "relation": "gte" }, "hits": [] },
+                "aggregations": { "avg#AVG_DURATION": { "value": 1.0 } }
+            });
+
+            for strict in &[false, true] {
+                let response: Response = if *strict {
+                    serde_json::from_value::<StrictResponse>(result.clone())
+                        .unwrap()
+                        .into()
+                } else {
+                    serde_json::from_value(result.clone()).unwrap()
+                };
+                let total_hits = response.total_hits.unwrap();
+                assert_eq!(total_hits.relation, CountRelation::GreaterThanOrEqualTo);
+                assert_eq!(total_hits.value, 10_000);
+                assert_eq!(response.aggregations.len(), 1);
+            }
+
+            let result = json!({ "hits": {}, "aggregations": {} });
+            let response: Response = serde_json::from_value(result).unwrap();
+            assert!(response.total_hits.is_none());
+        }
+
+        #[test]
+        fn keeps_the_accuracy_of_terms_aggregations() {
+            let result = json!({
+                "aggregations": {
+                    "sterms#PER_AGENT": {
+                        "doc_count_error_upper_bound": 2,
+                        "sum_other_doc_count": 42,
+                        "buckets": [
+                            { "key": "dallin", "doc_count": 7 },
+                            { "key": "will", "doc_count": 5 }
+                        ]
+                    }
+                }
+            });
+
+            let response: Response = serde_json::from_value(result).unwrap();
+            let per_agent = &response.aggregations[0];
+            assert_eq!(per_agent.name, "PER_AGENT");
+            assert_eq!(per_agent.values, vec![7.0, 5.0]);
+            assert_eq!(per_agent.doc_count_error_upper_bound, Some(2));
+            assert_eq!(per_agent.sum_other_doc_count, Some(42));
+        }
+
+        #[test]
+        fn parses_the_values_of_multi_value_metrics() {
+            let result = json!({
+                "aggregations": {
+                    "percentile_ranks#KEYED": {
+                        "values": { "500.0": 55.0, "500.0_as_string": "55.0", "600.0": 64.0 }
+                    },
+                    "percentile_ranks#UNKEYED": {
+                        "values": [{ "key": 500.0, "value": 55.0 }, { "key": 600.0, "value": null }]
+                    }
+                }
+            });
+
+            let response: Response = serde_json::from_str(&result.to_string()).unwrap();
+            let mut aggregations = response.aggregations;
+            aggregations.sort_by(|a, b| a.name.cmp(&b.name));
+
+            assert_eq!(aggregations[0].name, "KEYED");
+            assert_eq!(aggregations[0].type_, Ty::PercentileRanks);
+            assert_eq!(aggregations[0].fields, vec!["500.0", "600.0"]);
+            assert_eq!(aggregations[0].values, vec![55.0, 64.0]);
+
+            // `null` values are skipped
+            assert_eq!(aggregations[1].name, "UNKEYED");
+            assert_eq!(aggregations[1].fields, vec!["500"]);
+            assert_eq!(aggregations[1].values, vec![55.0]);
+
+            let strict: StrictResponse = serde_json::from_value(result).unwrap();
+            assert_eq!(strict.0.aggregations.len(), 2);
+        }
+
+        #[test]
+        fn parses_keyed_buckets() {
+            let result = json!({
+                "aggregations": {
+                    "range#PRICES": {
+                        "buckets": {
+                            "*-100.0": { "to": 100.0, "doc_count": 2 },
+                            "100.0-*": { "from": 100.0, "doc_count": 5 }
+                        }
+                    },
+                    "filters#MESSAGES": {
+                        "buckets": {
+                            "errors": { "doc_count": 1, "avg#DURATION": { "value": 3.0 } },
+                            "warnings": { "doc_count": 2, "avg#DURATION": { "value": 4.0 } }
+                        }
+                    },
+                    "date_histogram#PER_DAY": {
+                        "buckets": {
+                            "2021-01-01": {
+                                "key_as_string": "2021-01-01",
+                                "key": 1_609_459_200_000_u64,
+                                "doc_count": 3
+                            }
+                        }
+                    }
+                }
+            });
+
+            let response: StrictResponse = serde_json::from_value(result).unwrap();
+            let mut aggregations = response.0.aggregations;
+            aggregations.sort_by(|a, b| a.name.cmp(&b.name));
+
+            assert_eq!(aggregations[0].name, "DURATION");
+            let mut durations: Vec<_> = aggregations[0]
+                .bucket_keys
+                .iter()
+                .cloned()
+                .zip(aggregations[0].values.iter().copied())
+                .collect();
+            durations.sort_by(|a, b| a.0.cmp(&b.0));
+            assert_eq!(
+                durations,
+                vec![
+                    (vec!["errors".to_string()], 3.0),
+                    (vec!["warnings".to_string()], 4.0)
+                ]
+            );
+
+            assert_eq!(aggregations[1].name, "PER_DAY");
+            assert_eq!(aggregations[1].fields, vec!["2021-01-01"]);
+
+            assert_eq!(aggregations[2].name, "PRICES");
+            assert_eq!(aggregations[2].fields, vec!["*-100.0", "100.0-*"]);
+            assert_eq!(aggregations[2].values, vec![2.0, 5.0]);
+        }
+
+        #[test]
+        fn keeps_the_full_path_of_bucket_keys() {
+            let result = json!({
+                "aggregations": {
+                    "PER_TEAM": {
+                        "buckets": [{
+                            "key": "sales",
+                            "doc_count": 3,
+                            "PER_AGENT": {
+                                "buckets": [{
+                                    "key": "dallin",
+                                    "doc_count": 3,
+                                    "PER_DAY": {
+                                        "buckets": [
+                                            {
+                                                "key": "monday",
+                                                "doc_count": 1,
+                                                "sum#DURATION": { "value": 10.0 }
+                                            },
+                                            {
+                                                "key": "tuesday",
+                                                "doc_count": 2,
+                                                "sum#DURATION": { "value": 20.0 }
+                                            }
+                                        ]
+                                    }
+                                }]
+                            }
+                        }]
+                    }
+                }
+            });
+
+            let response: Response = serde_json::from_value(result).unwrap();
+            let duration = response
+                .aggregations
+                .iter()
+                .find(|result| result.name == "DURATION")
+                .unwrap();
+            let mut values: Vec<_> = duration
+                .values
+                .iter()
+                .zip(&duration.bucket_keys)
+                .map(|(value, keys)| (keys.join("/"), *value))
+                .collect();
+            values.sort_by(|a, b| a.0.cmp(&b.0));
+            assert_eq!(
+                values,
+                vec![
+                    ("sales/dallin/monday".to_string(), 10.0),
+                    ("sales/dallin/tuesday".to_string(), 20.0)
+                ]
+            );
+        }
+
         // TODO: use real `AggregationTypes` instead of `Unknown`
 
         macro_rules! test_case {
@@ -766,9 +1793,13 @@ mod tests {
                     name: "AVG_DURATION".to_string(),
                     fields: vec![],
                     values: vec![3.0, 4.0],
+                    bucket_keys: vec![vec![]],
                     metadata: Some([("test".to_string(), json!(true).into())].iter().cloned().collect()),
                     type_: Ty::Avg,
+                    doc_count_error_upper_bound: None,
+                    sum_other_doc_count: None,
                 }],
+                ..Default::default()
             },
             json!({
                 "aggregations": {
@@ -786,9 +1817,13 @@ mod tests {
                     name: "PERCENT_DEAD_AIR".to_string(),
                     fields: vec!["dallin".to_string(), "will".to_string()],
                     values: vec![0.009, 0.017],
+                    bucket_keys: vec![vec!["dallin".to_string()], vec!["will".to_string()]],
                     metadata: None,
                     type_: Ty::Unknown,
+                    doc_count_error_upper_bound: None,
+                    sum_other_doc_count: None,
                 }],
+                ..Default::default()
             },
             json!({
                 "aggregations": {
@@ -840,9 +1875,13 @@ mod tests {
                     name: "AVG_DURATION".to_string(),
                     fields: vec![],
                     values: vec![3.0, 4.0],
+                    bucket_keys: vec![vec![]],
                     metadata: None,
                     type_: Ty::Avg,
+                    doc_count_error_upper_bound: None,
+                    sum_other_doc_count: None,
                 }],
+                ..Default::default()
             },
             json!({ "aggregations": { "avg#AVG_DURATION": { "value": 353_964.312_5 } } })
         );
@@ -856,10 +1895,20 @@ mod tests {
                         name: "AVG_DURATION".to_string(),
                         fields: vec!["dallin".to_string(), "will".to_string()],
                         values: vec![462_430.123, 346_602.0],
+                        bucket_keys: vec![vec!["dallin".to_string()], vec!["will".to_string()]],
                         metadata: None,
                         type_: Ty::Avg,
+                        doc_count_error_upper_bound: None,
+                        sum_other_doc_count: None,
                     },
-                ]
+                ],
+                total_hits: Some(Count {
+                    relation: CountRelation::EqualTo,
+                    value: 20,
+                }),
+                took: Some(2),
+                timed_out: Some(false),
+                shards: None,
             },
             json!({
                 "took": 2,
@@ -899,50 +1948,80 @@ mod tests {
                         name: "COUNT_OF_CALLS".to_string(),
                         fields: vec!["dallin".to_string(), "will".to_string()],
                         values: vec![3.0, 4.0],
+                        bucket_keys: vec![vec!["sales".to_string(), "dallin".to_string()], vec!["sales".to_string(), "will".to_string()]],
                         metadata: None,
                         type_: Ty::ValueCount,
+                        doc_count_error_upper_bound: None,
+                        sum_other_doc_count: None,
                     },
                     ComputedResult {
                         parent: Some("sales".to_string()),
                         name: "SUM_OF_DURATION".to_string(),
                         fields: vec!["dallin".to_string(), "will".to_string()],
                         values: vec![2997.0, 2196.0],
+                        bucket_keys: vec![vec!["sales".to_string(), "dallin".to_string()], vec!["sales".to_string(), "will".to_string()]],
                         metadata: None,
                         type_: Ty::Sum,
+                        doc_count_error_upper_bound: None,
+                        sum_other_doc_count: None,
                     },
                     ComputedResult {
                         parent: Some("sales".to_string()),
                         name: "AVG_OF_DURATION".to_string(),
                         fields: vec!["dallin".to_string(), "will".to_string()],
                         values: vec![999.0, 549.0],
+                        bucket_keys: vec![vec!["sales".to_string(), "dallin".to_string()], vec!["sales".to_string(), "will".to_string()]],
                         metadata: None,
                         type_: Ty::Avg,
+                        doc_count_error_upper_bound: None,
+                        sum_other_doc_count: None,
                     },
                     ComputedResult {
                         parent: Some("(missing)".to_string()),
                         name: "COUNT_OF_CALLS".to_string(),
                         fields: vec!["dallin".to_string(), "will".to_string()],
                         values: vec![4.0, 3.0],
+                        bucket_keys: vec![vec!["(missing)".to_string(), "dallin".to_string()], vec!["(missing)".to_string(), "will".to_string()]],
                         metadata: None,
                         type_: Ty::ValueCount,
+                        doc_count_error_upper_bound: None,
+                        sum_other_doc_count: None,
                     },
                     ComputedResult {
                         parent: Some("(missing)".to_string()),
                         name: "SUM_OF_DURATION".to_string(),
                         fields: vec!["dallin".to_string(), "will".to_string()],
                         values: vec![3_234_017.0, 2_424_018.0],
+                        bucket_keys: vec![vec!["(missing)".to_string(), "dallin".to_string()], vec!["(missing)".to_string(), "will".to_string()]],
                         metadata: None,
                         type_: Ty::Sum,
+                        doc_count_error_upper_bound: None,
+                        sum_other_doc_count: None,
                     },
                     ComputedResult {
                         parent: Some("(missing)".to_string()),
                         name: "AVG_OF_DURATION".to_string(),
                         fields: vec!["dallin".to_string(), "will".to_string()],
                         values: vec![808_504.25, 808_006.0],
+                        bucket_keys: vec![vec!["(missing)".to_string(), "dallin".to_string()], vec!["(missing)".to_string(), "will".to_string()]],
                         metadata: None,
                         type_: Ty::Avg,
+                        doc_count_error_upper_bound: None,
+                        sum_other_doc_count: None,
                     },
                 ],
+                total_hits: Some(Count {
+                    relation: CountRelation::EqualTo,
+                    value: 14,
+                }),
+                took: Some(16),
+                timed_out: Some(false),
+                shards: Some(Shards {
+                    total: 2,
+                    successful: 2,
+                    skipped: 0,
+                    failed: 0,
+                }),
             },
             json!({
                 "took": 16,
@@ -1028,10 +2107,14 @@ mod tests {
                             "2018-12-01T00:00:00Z-*".to_string(),
                         ],
                         values: vec![0.0, 30.0],
+                        bucket_keys: vec![vec!["*-2018-12-01T00:00:00Z".to_string()], vec!["2018-12-01T00:00:00Z-*".to_string()]],
                         metadata: None,
                         type_: Ty::ValueCount,
+                        doc_count_error_upper_bound: None,
+                        sum_other_doc_count: None,
                     },
-                ]
+                ],
+                ..Default::default()
             },
             json!({
                 "aggregations": {
@@ -1072,10 +2155,14 @@ mod tests {
                             "2020-01-05T00:00:00.000Z".to_string(),
                         ],
                         values: vec![1.0, 2.0, 1.0, 1.0, 2.0],
+                        bucket_keys: vec![vec!["2020-01-01T00:00:00.000Z".to_string()], vec!["2020-01-02T00:00:00.000Z".to_string()], vec!["2020-01-03T00:00:00.000Z".to_string()], vec!["2020-01-04T00:00:00.000Z".to_string()], vec!["2020-01-05T00:00:00.000Z".to_string()]],
                         metadata: None,
                         type_: Ty::DateHistogram,
+                        doc_count_error_upper_bound: None,
+                        sum_other_doc_count: None,
                     },
-                ]
+                ],
+                ..Default::default()
             },
             json!({
                 "aggregations": {
@@ -1135,10 +2222,14 @@ mod tests {
                             "2020-01-05T00:00:00.000Z".to_string(),
                         ],
                         values: vec![1.0, 0.0, 2.0, 0.0, 1.0, 0.0, 1.0, 0.0, 2.0],
+                        bucket_keys: vec![vec!["2020-01-01T00:00:00.000Z".to_string()], vec!["2020-01-01T12:00:00.000Z".to_string()], vec!["2020-01-02T00:00:00.000Z".to_string()], vec!["2020-01-02T12:00:00.000Z".to_string()], vec!["2020-01-03T00:00:00.000Z".to_string()], vec!["2020-01-03T12:00:00.000Z".to_string()], vec!["2020-01-04T00:00:00.000Z".to_string()], vec!["2020-01-04T12:00:00.000Z".to_string()], vec!["2020-01-05T00:00:00.000Z".to_string()]],
                         metadata: None,
                         type_: Ty::AutoDateHistogram,
+                        doc_count_error_upper_bound: None,
+                        sum_other_doc_count: None,
                     },
-                ]
+                ],
+                ..Default::default()
             },
             json!({
                 "aggregations": {
@@ -1209,10 +2300,14 @@ mod tests {
                         name: "PER_AGENT".to_string(),
                         fields: vec!["Denmark".to_string()],
                         values: vec![1.0],
+                        bucket_keys: vec![vec!["Denmark".to_string()]],
                         metadata: None,
                         type_: Ty::Unknown,
+                        doc_count_error_upper_bound: None,
+                        sum_other_doc_count: None,
                     },
-                ]
+                ],
+                ..Default::default()
             },
             json!({
                 "aggregations": {
@@ -1,5 +1,6 @@
 //! Bucket script aggregation types.
 
+use elastiql_derive::input_twin;
 use serde::{Deserialize, Serialize};
 
 use super::GapPolicy;
@@ -11,45 +12,13 @@ use super::GapPolicy;
 ///
 /// [*pipeline aggregation*]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-pipeline.html
 /// [script]: https://www.elastic.co/guide/en/elasticsearch/reference/current/modules-scripting.html
-#[cfg(feature = "graphql")]
-#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(async_graphql::InputObject, Serialize, Clone, Debug)]
-#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
-pub struct BucketScriptInput {
-    /// The script to run for this aggregation.
-    pub script: String,
-
-    /// A map of script variables and their associated path to the buckets to
-    /// use for the variable (see [`buckets_path` Syntax] for more details)
-    ///
-    /// [`buckets_path` Syntax]: /// https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-pipeline.html#buckets-path-syntax
-    #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub buckets_path: Option<crate::scalars::Map>,
-
-    /// The policy to apply when gaps are found in the data
-    #[graphql(default_with = "Some(GapPolicy::Skip)")]
-    #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub gap_policy: Option<GapPolicy>,
-
-    /// Format to apply to the output value of this aggregation
-    #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub format: Option<String>,
-}
-
-/// A parent [*pipeline aggregation*] which executes a [script] which can
-/// perform per bucket computations on specified metrics in the parent
-/// multi-bucket aggregation. The specified metric must be numeric and the
-/// script must return a numeric value.
-///
-/// [*pipeline aggregation*]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-pipeline.html
-/// [script]: https://www.elastic.co/guide/en/elasticsearch/reference/current/modules-scripting.html
-#[cfg_attr(test, derive(PartialEq))]
+#[input_twin]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("BucketScript"))]
 #[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 #[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
 pub struct BucketScript {
     /// The script to run for this aggregation.
@@ -61,11 +30,14 @@ pub struct BucketScript {
     /// [`buckets_path` Syntax]: /// https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-pipeline.html#buckets-path-syntax
     #[cfg_attr(feature = "builder", builder(default))]
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<crate::schema::Map>"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::option_map))]
     pub buckets_path: Option<crate::scalars::Map>,
 
     /// The policy to apply when gaps are found in the data
     #[cfg_attr(feature = "builder", builder(default))]
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[input(graphql(default_with = "Some(GapPolicy::Skip)"))]
     pub gap_policy: Option<GapPolicy>,
 
     /// Format to apply to the output value of this aggregation
@@ -73,16 +45,3 @@ pub struct BucketScript {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub format: Option<String>,
 }
-
-#[cfg(feature = "graphql")]
-impl From<BucketScriptInput> for BucketScript {
-    #[inline]
-    fn from(input: BucketScriptInput) -> Self {
-        BucketScript {
-            script: input.script,
-            buckets_path: input.buckets_path,
-            gap_policy: input.gap_policy,
-            format: input.format,
-        }
-    }
-}
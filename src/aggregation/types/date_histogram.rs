@@ -1,7 +1,14 @@
 //! Date histogram aggregation types.
 
+use elastiql_derive::{input_twin, twin_impl};
+use serde::ser::{SerializeMap, Serializer};
 use serde::{Deserialize, Serialize};
 
+use super::BucketOrder;
+#[cfg(feature = "graphql")]
+use super::BucketOrderInput;
+use crate::compat::CompatVersion;
+
 /// This [*multi-bucket*] aggregation is similar to the normal [histogram], but it
 /// can only be used with date or date range values.
 ///
@@ -11,11 +18,18 @@ use serde::{Deserialize, Serialize};
 /// [Union input types]: https://github.com/graphql/graphql-spec/blob/master/rfcs/InputUnion.md
 /// [histogram]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-bucket-histogram-aggregation.html
 /// [*multi-bucket*]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-bucket.html
-#[cfg(feature = "graphql")]
+#[input_twin]
+#[cfg_attr(
+    feature = "graphql",
+    elastiql_derive::graphql_name("DateHistogramAggregation")
+)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 #[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(async_graphql::InputObject, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
-pub struct DateHistogramAggregationInput {
+pub struct DateHistogramAggregation {
     /// The field to perform the aggregation over.
     pub field: String,
 
@@ -23,7 +37,7 @@ pub struct DateHistogramAggregationInput {
     /// length of specific days, months have different amounts of days, and leap
     /// seconds can be tacked onto a particular year.
     #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
     pub calendar_interval: Option<CalendarInterval>,
 
     /// In contrast to calendar-aware intervals, fixed intervals are a fixed
@@ -31,7 +45,7 @@ pub struct DateHistogramAggregationInput {
     /// the calendar. One second is always composed of `1000ms`. This allows
     /// fixed intervals to be specified in any multiple of the supported units.
     #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
     pub fixed_interval: Option<String>,
 
     /// Indicates that bucketing and rounding should use a different timezone
@@ -44,7 +58,7 @@ pub struct DateHistogramAggregationInput {
     /// [ISO 8601]: https://www.iso.org/iso-8601-date-and-time-format.html
     /// [IANA timezone database]: https://www.iana.org/time-zones
     #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
     pub time_zone: Option<String>,
 
     /// Changes the start value of each bucket by the specified positive (`+`)
@@ -56,157 +70,177 @@ pub struct DateHistogramAggregationInput {
     ///
     /// [Time units]: https://www.elastic.co/guide/en/elasticsearch/reference/current/common-options.html#time-units
     #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
     pub offset: Option<String>,
 
     /// How the returned date should be [formatted].
     ///
     /// [formatted]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-bucket-daterange-aggregation.html#date-format-pattern
     #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
     pub format: Option<String>,
 
     /// Defines how documents that are missing a value should be treated. By
     /// default they will be ignored but it is also possible to treat them as if
     /// they had a value.
     #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
     pub missing: Option<String>,
-}
 
-/// This [*multi-bucket*] aggregation is similar to the normal [histogram], but it
-/// can only be used with date or date range values.
-///
-/// **Note**: until GraphQL [Union input types] are supported, either
-/// `calendarInterval` or `fixedInterval` *must* be specified but *not* both.
-///
-/// [Union input types]: https://github.com/graphql/graphql-spec/blob/master/rfcs/InputUnion.md
-/// [histogram]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-bucket-histogram-aggregation.html
-/// [*multi-bucket*]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-bucket.html
-#[cfg_attr(test, derive(PartialEq))]
-#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
-#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(Serialize, Deserialize, Clone, Debug)]
-#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
-pub struct DateHistogramAggregation {
-    /// The field to perform the aggregation over.
-    pub field: String,
-
-    /// Calendar-aware intervals understand that daylight savings changes the
-    /// length of specific days, months have different amounts of days, and leap
-    /// seconds can be tacked onto a particular year.
+    /// Only returns buckets with at least this many documents. (Defaults to
+    /// `0`, i.e. the gaps in the histogram are filled with empty buckets.)
     #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub calendar_interval: Option<CalendarInterval>,
+    #[serde(default)]
+    pub min_doc_count: Option<u64>,
 
-    /// In contrast to calendar-aware intervals, fixed intervals are a fixed
-    /// number of SI units and never deviate, regardless of where they fall on
-    /// the calendar. One second is always composed of `1000ms`. This allows
-    /// fixed intervals to be specified in any multiple of the supported units.
+    /// The order of the buckets. (Defaults to ascending by `_key`.)
     #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub fixed_interval: Option<String>,
+    #[serde(default)]
+    #[input(twin)]
+    pub order: Option<BucketOrder>,
 
-    /// Indicates that bucketing and rounding should use a different timezone
-    /// than the default UTC.
-    ///
-    /// Accepts either an [ISO 8601] UTC offset (e.g. `+01:00` or `-08:00`) or as
-    /// a timezone ID as specified in the [IANA timezone database], such as
-    /// `America/Los_Angeles`.
-    ///
-    /// [ISO 8601]: https://www.iso.org/iso-8601-date-and-time-format.html
-    /// [IANA timezone database]: https://www.iana.org/time-zones
+    /// Whether to return the buckets as an object keyed by their keys instead
+    /// of as an array. (Defaults to `false`.)
     #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub time_zone: Option<String>,
+    #[serde(default)]
+    pub keyed: Option<bool>,
 
-    /// Changes the start value of each bucket by the specified positive (`+`)
-    /// or negative offset (`-`) duration, such as `1h` for an hour, or `1d` for
-    /// a day. See [Time units] for more possible time duration options.
-    ///
-    /// **Note**: The start `offset` of each bucket is calculated after
-    /// `timeZone` adjustments have been made.
-    ///
-    /// [Time units]: https://www.elastic.co/guide/en/elasticsearch/reference/current/common-options.html#time-units
+    /// Forces the histogram to build (empty) buckets from `min` to `max`, even
+    /// if there are no documents there. This only makes sense when
+    /// `min_doc_count` is `0`.
     #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub offset: Option<String>,
+    #[serde(default)]
+    #[input(twin)]
+    pub extended_bounds: Option<DateHistogramBounds>,
 
-    /// How the returned date should be [formatted].
-    ///
-    /// [formatted]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-bucket-daterange-aggregation.html#date-format-pattern
+    /// Limits the buckets of the histogram to those from `min` to `max`, e.g.
+    /// to avoid a very large number of buckets for open ranges.
     #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub format: Option<String>,
+    #[serde(default)]
+    #[input(twin)]
+    pub hard_bounds: Option<DateHistogramBounds>,
+}
 
-    /// Defines how documents that are missing a value should be treated. By
-    /// default they will be ignored but it is also possible to treat them as if
-    /// they had a value.
-    #[cfg_attr(feature = "builder", builder(default))]
+/// Bounds for controlling the `DateHistogram`, either as dates (in the
+/// `format` of the aggregation, if any) or as [date math], e.g. `now-1d/d`.
+///
+/// [date math]: https://www.elastic.co/guide/en/elasticsearch/reference/current/common-options.html#date-math
+#[input_twin]
+#[cfg_attr(
+    feature = "graphql",
+    elastiql_derive::graphql_name("DateHistogramBounds")
+)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "builder", builder(field_defaults(default, setter(into))))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[allow(missing_docs)]
+pub struct DateHistogramBounds {
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub missing: Option<String>,
+    pub min: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<String>,
 }
 
-#[cfg(feature = "graphql")]
-impl From<DateHistogramAggregationInput> for DateHistogramAggregation {
+#[twin_impl]
+impl Serialize for DateHistogramAggregation {
     #[inline]
-    fn from(input: DateHistogramAggregationInput) -> Self {
-        DateHistogramAggregation {
-            field: input.field,
-            calendar_interval: input.calendar_interval,
-            fixed_interval: input.fixed_interval,
-            time_zone: input.time_zone,
-            offset: input.offset,
-            format: input.format,
-            missing: input.missing,
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("field", &self.field)?;
+
+        if CompatVersion::current() < CompatVersion::V7 {
+            // 6.x only supports the (since deprecated) `interval`
+            if let Some(interval) = &self.calendar_interval {
+                map.serialize_entry("interval", interval)?;
+            } else if let Some(interval) = &self.fixed_interval {
+                map.serialize_entry("interval", interval)?;
+            }
+        } else {
+            if let Some(interval) = &self.calendar_interval {
+                map.serialize_entry("calendar_interval", interval)?;
+            }
+            if let Some(interval) = &self.fixed_interval {
+                map.serialize_entry("fixed_interval", interval)?;
+            }
         }
+
+        let options = [
+            ("time_zone", &self.time_zone),
+            ("offset", &self.offset),
+            ("format", &self.format),
+            ("missing", &self.missing),
+        ];
+        for (key, value) in options.iter() {
+            if let Some(value) = value {
+                map.serialize_entry(key, value)?;
+            }
+        }
+
+        if let Some(min_doc_count) = &self.min_doc_count {
+            map.serialize_entry("min_doc_count", min_doc_count)?;
+        }
+        if let Some(order) = &self.order {
+            map.serialize_entry("order", order)?;
+        }
+        if let Some(keyed) = &self.keyed {
+            map.serialize_entry("keyed", keyed)?;
+        }
+        if let Some(bounds) = &self.extended_bounds {
+            map.serialize_entry("extended_bounds", bounds)?;
+        }
+        if let Some(bounds) = &self.hard_bounds {
+            map.serialize_entry("hard_bounds", bounds)?;
+        }
+
+        map.end()
     }
 }
 
-/// Calendar aware interval.
-#[cfg_attr(all(test, not(feature = "graphql")), derive(PartialEq))]
-#[cfg_attr(feature = "graphql", derive(async_graphql::Enum, Eq, PartialEq, Copy))]
-#[derive(Serialize, Deserialize, Clone, Debug)]
-#[serde(rename_all = "lowercase")]
-pub enum CalendarInterval {
-    /// One *minute* is the interval between `00` seconds of the first minute
-    /// and `00` seconds of the following minute in the specified timezone,
-    /// compensating for any intervening leap seconds, so that the number of
-    /// minutes and seconds past the hour is the same at the start and end.
-    Minute,
-
-    /// One *hour* is the interval between `00:00` minutes of the first hour and
-    /// `00:00` minutes of the following hour in the specified timezone,
-    /// compensating for any intervening leap seconds, so that the number of
-    /// minutes and seconds past the hour is the same at the start and end.
-    Hour,
-
-    /// One *day* is the interval between the start of the day, at the earliest
-    /// possible time, which is usually `00:00:00` (midnight), and the start of
-    /// of the following day in the specified timezone, compensating for any
-    /// intervening time changes.
-    Day,
-
-    /// One *week* is the interval between the start
-    /// `day_of_week:hour:minute:second` and the same day of the week and time
-    /// of the following week in the specified timezone.
-    Week,
-
-    /// One *month* is the interval between the start day of the month and time
-    /// of day and the same day of the month and time of the following month in
-    /// the specified timezone, so that the day of the month and time of day are
-    /// the same at the start and end.
-    Month,
-
-    /// One *quarter* is the interval between the start day of the month and
-    /// time of day and the same day of the month and time of day three months
-    /// later, so that the day of the month and time of day are the same at the
-    /// start and end.
-    Quarter,
-
-    /// One *year* is the interval between the start day of the month and time
-    /// of day and the same day of the month and time of day the following year
-    /// in the specified timezone, so that the date and time are the same at the
-    /// start and end.
-    Year,
+open_enum! {
+    /// Calendar aware interval.
+    pub enum CalendarInterval as "CalendarInterval" {
+        /// One *minute* is the interval between `00` seconds of the first minute
+        /// and `00` seconds of the following minute in the specified timezone,
+        /// compensating for any intervening leap seconds, so that the number of
+        /// minutes and seconds past the hour is the same at the start and end.
+        Minute => "minute" as "MINUTE",
+
+        /// One *hour* is the interval between `00:00` minutes of the first hour and
+        /// `00:00` minutes of the following hour in the specified timezone,
+        /// compensating for any intervening leap seconds, so that the number of
+        /// minutes and seconds past the hour is the same at the start and end.
+        Hour => "hour" as "HOUR",
+
+        /// One *day* is the interval between the start of the day, at the earliest
+        /// possible time, which is usually `00:00:00` (midnight), and the start of
+        /// of the following day in the specified timezone, compensating for any
+        /// intervening time changes.
+        Day => "day" as "DAY",
+
+        /// One *week* is the interval between the start
+        /// `day_of_week:hour:minute:second` and the same day of the week and time
+        /// of the following week in the specified timezone.
+        Week => "week" as "WEEK",
+
+        /// One *month* is the interval between the start day of the month and time
+        /// of day and the same day of the month and time of the following month in
+        /// the specified timezone, so that the day of the month and time of day are
+        /// the same at the start and end.
+        Month => "month" as "MONTH",
+
+        /// One *quarter* is the interval between the start day of the month and
+        /// time of day and the same day of the month and time of day three months
+        /// later, so that the day of the month and time of day are the same at the
+        /// start and end.
+        Quarter => "quarter" as "QUARTER",
+
+        /// One *year* is the interval between the start day of the month and time
+        /// of day and the same day of the month and time of day the following year
+        /// in the specified timezone, so that the date and time are the same at the
+        /// start and end.
+        Year => "year" as "YEAR",
+    }
 }
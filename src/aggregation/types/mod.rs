@@ -3,16 +3,24 @@
 //!
 //! [aggregation]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations.html
 
+use std::fmt;
+
+use elastiql_derive::{input_twin, twin_impl};
+#[cfg(feature = "schemars")]
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::de::{self, MapAccess, Visitor};
+use serde::ser::{SerializeMap, Serializer};
 use serde::{Deserialize, Serialize};
 
 pub use self::{
     auto_date_histogram::*, bucket_script::*, bucket_selector::*, bucket_sort::*,
-    date_histogram::*, date_range::*, histogram::*, nested::*, range::*, reverse_nested::*,
-    sampler::*, significant_text::*, terms::*, variable_width_histogram::*, weighted_average::*,
+    date_histogram::*, date_range::*, histogram::*, nested::*, percentile_ranks::*, range::*,
+    reverse_nested::*, sampler::*, significant_text::*, terms::*, variable_width_histogram::*,
+    weighted_average::*,
 };
-use crate::search::Script;
 #[cfg(feature = "graphql")]
 use crate::search::ScriptInput;
+use crate::search::{Script, SortOrder};
 
 mod auto_date_histogram;
 mod bucket_script;
@@ -22,6 +30,7 @@ mod date_histogram;
 mod date_range;
 mod histogram;
 mod nested;
+mod percentile_ranks;
 mod range;
 mod reverse_nested;
 mod sampler;
@@ -30,40 +39,19 @@ mod terms;
 mod variable_width_histogram;
 mod weighted_average;
 
-/// A generic input for an aggregation.
+/// A generic aggregation.
 ///
 /// **Note**: until GraphQL [Union input types] are supported either `field` or
 /// `script` *must* be specified but *not* both.
 ///
 /// [Union input types]: https://github.com/graphql/graphql-spec/blob/master/rfcs/InputUnion.md
-#[cfg(feature = "graphql")]
-#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(async_graphql::InputObject, Serialize, Clone, Debug)]
-#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
-pub struct InnerAggregationInput {
-    /// The field to perform the aggregation over.
-    #[cfg_attr(feature = "builder", builder(default))]
-    pub field: Option<String>,
-
-    /// The script to use.
-    #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub script: Option<ScriptInput>,
-
-    /// How missing values should be treated.
-    ///
-    /// By default they will be ignored, but it is also possible to treat them
-    /// as if they had the value.
-    #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub missing: Option<f64>,
-}
-
-/// A generic aggregation.
-#[cfg_attr(test, derive(PartialEq))]
+#[input_twin]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("InnerAggregation"))]
 #[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 #[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
 pub struct InnerAggregation {
     /// The field to perform the aggregation over.
@@ -73,6 +61,7 @@ pub struct InnerAggregation {
     /// The script to use.
     #[cfg_attr(feature = "builder", builder(default))]
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[input(twin)]
     pub script: Option<Script>,
 
     /// How missing values should be treated.
@@ -81,21 +70,10 @@ pub struct InnerAggregation {
     /// as if they had the value.
     #[cfg_attr(feature = "builder", builder(default))]
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::option_float))]
     pub missing: Option<f64>,
 }
 
-#[cfg(feature = "graphql")]
-impl From<InnerAggregationInput> for InnerAggregation {
-    #[inline]
-    fn from(aggregation: InnerAggregationInput) -> Self {
-        InnerAggregation {
-            field: aggregation.field,
-            script: aggregation.script.map(Into::into),
-            missing: aggregation.missing,
-        }
-    }
-}
-
 #[cfg(test)]
 impl<T: Into<String>> From<T> for InnerAggregation {
     #[inline]
@@ -109,9 +87,11 @@ impl<T: Into<String>> From<T> for InnerAggregation {
 }
 
 /// The policy to apply when gaps are found in the data.
-#[cfg_attr(all(test, not(feature = "graphql")), derive(PartialEq))]
-#[cfg_attr(feature = "graphql", derive(async_graphql::Enum, Eq, PartialEq, Copy))]
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("GapPolicy"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum GapPolicy {
     /// Treats missing data as if the bucket does not exist. It will skip the
@@ -121,4 +101,97 @@ pub enum GapPolicy {
     /// Replaces missing values with a zero (`0`) and pipeline aggregation
     /// computation will proceed as normal.
     InsertZeros,
+
+    /// Like [`GapPolicy::Skip`], but if the metric provides a non-null,
+    /// non-NaN value this value is used, otherwise the empty bucket is
+    /// skipped.
+    KeepValues,
+}
+
+/// The [order] of the buckets of a `multi-bucket` aggregation, e.g.
+/// `{ "_key": "asc" }`.
+///
+/// [order]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-bucket-terms-aggregation.html#search-aggregations-bucket-terms-aggregation-order
+#[input_twin]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("BucketOrder"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct BucketOrder {
+    /// What to order the buckets by, i.e. `_key`, `_count` or the name of a
+    /// single-value metrics sub-aggregation.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::field))]
+    pub key: String,
+
+    /// Whether to order the buckets in ascending or descending order.
+    pub order: SortOrder,
+}
+
+impl BucketOrder {
+    /// Constructs a new `BucketOrder`.
+    #[inline]
+    pub fn new(key: impl Into<String>, order: SortOrder) -> Self {
+        BucketOrder {
+            key: key.into(),
+            order,
+        }
+    }
+}
+
+#[twin_impl]
+impl Serialize for BucketOrder {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(&self.key, &self.order)?;
+        map.end()
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl JsonSchema for BucketOrder {
+    #[inline]
+    fn schema_name() -> String {
+        "BucketOrder".to_string()
+    }
+
+    #[inline]
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        crate::schema::keyed(SortOrder::json_schema(gen))
+    }
+}
+
+/// Visits a `BucketOrder` during deserialization.
+struct BucketOrderVisitor;
+
+impl<'de> Deserialize<'de> for BucketOrder {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<BucketOrder, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(BucketOrderVisitor)
+    }
+}
+
+impl<'de> Visitor<'de> for BucketOrderVisitor {
+    type Value = BucketOrder;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a `BucketOrder`")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let key = map
+            .next_key::<String>()?
+            .ok_or_else(|| de::Error::missing_field("key"))?;
+        let order = map.next_value()?;
+
+        Ok(BucketOrder { key, order })
+    }
 }
@@ -1,5 +1,6 @@
 //! [Nested aggregation](https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-bucket-nested-aggregation.html)
 
+use elastiql_derive::input_twin;
 use serde::{Deserialize, Serialize};
 
 /// A special single [*bucketing*] aggregation that enables aggregating [nested]
@@ -7,34 +8,18 @@ use serde::{Deserialize, Serialize};
 ///
 /// [*bucketing*]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-bucket.html
 /// [nested]: https://www.elastic.co/guide/en/elasticsearch/reference/current/nested.html
-#[cfg(feature = "graphql")]
-#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(async_graphql::InputObject, Serialize, Clone, Debug)]
-#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
-pub struct NestedAggregationInput {
-    /// The nested path to search.
-    pub path: String,
-}
-
-/// A special single [*bucketing*] aggregation that enables aggregating [nested]
-/// documents.
-///
-/// [*bucketing*]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-bucket.html
-/// [nested]: https://www.elastic.co/guide/en/elasticsearch/reference/current/nested.html
-#[cfg_attr(test, derive(PartialEq))]
+#[input_twin]
+#[cfg_attr(
+    feature = "graphql",
+    elastiql_derive::graphql_name("NestedAggregation")
+)]
 #[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 #[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
 pub struct NestedAggregation {
     /// The nested path to search.
     pub path: String,
 }
-
-#[cfg(feature = "graphql")]
-impl From<NestedAggregationInput> for NestedAggregation {
-    #[inline]
-    fn from(input: NestedAggregationInput) -> Self {
-        NestedAggregation { path: input.path }
-    }
-}
@@ -0,0 +1,69 @@
+//! [Percentile ranks] aggregation types.
+//!
+//! [Percentile ranks]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-metrics-percentile-rank-aggregation.html
+
+use elastiql_derive::input_twin;
+use serde::{Deserialize, Serialize};
+
+use crate::search::Script;
+#[cfg(feature = "graphql")]
+use crate::search::ScriptInput;
+
+/// A `multi-value` [*metrics*] aggregation that calculates one or more
+/// percentile ranks over numeric values extracted from the aggregated
+/// documents, i.e. the percentage of observed values which are below each of
+/// the given `values`.
+///
+/// Each rank is returned as one of the `values` of the result, with its value
+/// (e.g. `500.0`) as the corresponding field.
+///
+/// **Note**: until GraphQL [Union input types] are supported either `field` or
+/// `script` *must* be specified but *not* both.
+///
+/// *Note*: [Percentiles are (usually) estimated].
+///
+/// [*metrics*]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-metrics.html
+/// [Union input types]: https://github.com/graphql/graphql-spec/blob/master/rfcs/InputUnion.md
+/// [Percentiles are (usually) estimated]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-metrics-percentile-aggregation.html#search-aggregations-metrics-percentile-aggregation-approximation
+#[input_twin]
+#[cfg_attr(
+    feature = "graphql",
+    elastiql_derive::graphql_name("PercentileRanksAggregation")
+)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct PercentileRanksAggregation {
+    /// The field to perform the aggregation over.
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+
+    /// The script to use.
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[input(twin)]
+    pub script: Option<Script>,
+
+    /// The values to calculate the percentile ranks of, e.g. `[500, 600]`.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::floats))]
+    pub values: Vec<f64>,
+
+    /// Whether to return the ranks as an object keyed by their values instead
+    /// of as an array. (Defaults to `true`.)
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keyed: Option<bool>,
+
+    /// How missing values should be treated.
+    ///
+    /// By default they will be ignored, but it is also possible to treat them
+    /// as if they had the value.
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::option_float))]
+    pub missing: Option<f64>,
+}
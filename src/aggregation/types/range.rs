@@ -1,5 +1,6 @@
 //! Range aggregation types.
 
+use elastiql_derive::input_twin;
 use serde::{Deserialize, Serialize};
 
 use crate::search::Script;
@@ -20,44 +21,13 @@ use crate::search::ScriptInput;
 ///
 /// [Union input types]: https://github.com/graphql/graphql-spec/blob/master/rfcs/InputUnion.md
 /// [*multi-bucket*]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-bucket.html
-#[cfg(feature = "graphql")]
-#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(async_graphql::InputObject, Serialize, Clone, Debug)]
-#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
-pub struct RangeAggregationInput {
-    /// The field to perform the aggregation over.
-    #[cfg_attr(feature = "builder", builder(default))]
-    pub field: Option<String>,
-
-    /// A script used to calculate the field to perform the aggregation over.
-    #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub script: Option<ScriptInput>,
-
-    /// The ranges to use for the aggregation.
-    #[graphql(default)]
-    #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(default)]
-    pub ranges: Vec<RangeInput>,
-}
-
-/// A [*multi-bucket*] value source based aggregation that enables the user to
-/// define a set of ranges - each representing a bucket. During the aggregation
-/// process, the values extracted from each document will be checked against
-/// each bucket range and "bucket" the relevant/matching document.
-///
-/// **Note**: this aggregation *includes* the `from` value and *excludes* the
-/// `to` value for each range.
-///
-/// **Note**: until GraphQL [Union input types] are supported either `field` or
-/// `script` *must* be specified but *not* both.
-///
-/// [Union input types]: https://github.com/graphql/graphql-spec/blob/master/rfcs/InputUnion.md
-/// [*multi-bucket*]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-bucket.html
-#[cfg_attr(test, derive(PartialEq))]
+#[input_twin]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("RangeAggregation"))]
 #[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 #[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
 pub struct RangeAggregation {
     /// The field to perform the aggregation over.
@@ -67,70 +37,42 @@ pub struct RangeAggregation {
     /// A script used to calculate the field to perform the aggregation over.
     #[cfg_attr(feature = "builder", builder(default))]
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[input(twin)]
     pub script: Option<Script>,
 
     /// The ranges to use for the aggregation.
     #[cfg_attr(feature = "builder", builder(default))]
     #[cfg_attr(feature = "builder", builder(default))]
     #[serde(default)]
+    #[input(twin, graphql(default))]
     pub ranges: Vec<Range>,
-}
 
-#[cfg(feature = "graphql")]
-impl From<RangeAggregationInput> for RangeAggregation {
-    #[inline]
-    fn from(input: RangeAggregationInput) -> Self {
-        RangeAggregation {
-            field: input.field,
-            script: input.script.map(Into::into),
-            ranges: input.ranges.into_iter().map(Into::into).collect(),
-        }
-    }
-}
-
-/// A range/span of data.
-#[cfg(feature = "graphql")]
-#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(async_graphql::InputObject, Serialize, Clone, Debug)]
-#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
-pub struct RangeInput {
-    /// The value to return results *from* and including.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Whether to return the buckets as an object keyed by their ranges (e.g.
+    /// `*-100.0`) instead of as an array. (Defaults to `false`.)
     #[cfg_attr(feature = "builder", builder(default))]
-    from: Option<f64>,
-
-    /// The value to return results up *to* but *not* including.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    #[cfg_attr(feature = "builder", builder(default))]
-    to: Option<f64>,
+    pub keyed: Option<bool>,
 }
 
 /// A range/span of data.
-#[cfg_attr(test, derive(PartialEq))]
+#[input_twin]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("Range"))]
 #[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 #[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
 pub struct Range {
     /// The value to return results *from* and including.
     #[cfg_attr(feature = "builder", builder(default))]
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::option_float))]
     from: Option<f64>,
 
     /// The value to return results up *to* but *not* including.
     #[cfg_attr(feature = "builder", builder(default))]
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::option_float))]
     to: Option<f64>,
 }
-
-// TODO: generate this with a proc-macro?
-#[cfg(feature = "graphql")]
-impl From<RangeInput> for Range {
-    #[inline]
-    fn from(input: RangeInput) -> Self {
-        Range {
-            from: input.from,
-            to: input.to,
-        }
-    }
-}
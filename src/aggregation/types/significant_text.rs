@@ -2,19 +2,37 @@
 //!
 //! [Significant text]: https://www.elastic.co/guide/en/elasticsearch/reference/7.x/search-aggregations-bucket-significanttext-aggregation.html
 
+use elastiql_derive::input_twin;
 use serde::{Deserialize, Serialize};
 
+use crate::search::{query::CompoundQuery, Script};
+#[cfg(feature = "graphql")]
+use crate::search::{query::CompoundQueryInput, ScriptInput};
+
 /// An aggregation that returns interesting or unusual occurrences of free-text
 /// terms in a set.
 ///
 /// See the official documentation for [significant text] for more information.
 ///
+/// **Note**: until GraphQL [Union input types] are supported *at most one* of
+/// the significance heuristics (`mutual_information`, `chi_square`, `gnd`,
+/// `jlh`, `percentage` or `script_heuristic`) may be specified; Elasticsearch
+/// uses `jlh` if none is.
+///
 /// [significant text]: https://www.elastic.co/guide/en/elasticsearch/reference/7.x/search-aggregations-bucket-significanttext-aggregation.html
-#[cfg(feature = "graphql")]
+/// [Union input types]: https://github.com/graphql/graphql-spec/blob/master/rfcs/InputUnion.md
+#[input_twin]
+#[cfg_attr(
+    feature = "graphql",
+    elastiql_derive::graphql_name("SignificantTextAggregation")
+)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 #[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
 #[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
-#[derive(async_graphql::InputObject, Serialize, Clone, Debug)]
-pub struct SignificantTextAggregationInput {
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SignificantTextAggregation {
     /// The field to perform the aggregation over.
     pub field: String,
 
@@ -24,48 +42,164 @@ pub struct SignificantTextAggregationInput {
     #[cfg_attr(feature = "builder", builder(default))]
     pub size: Option<u64>,
 
-    #[allow(missing_docs)]
+    /// The number of candidate terms each shard returns, which should be
+    /// larger than `size` to make the final list of terms more accurate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub shard_size: Option<u64>,
+
+    /// Only returns terms found in at least this many documents.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub min_doc_count: Option<u64>,
+
+    /// Whether to filter out duplicate sections of text, e.g. copy-and-pasted
+    /// paragraphs, which would otherwise skew the statistics.
     #[serde(default)]
-    #[graphql(default)]
     #[cfg_attr(feature = "builder", builder(default))]
+    #[input(graphql(default))]
     pub filter_duplicate_text: bool,
+
+    /// The documents to compare the foreground documents to instead of the
+    /// whole index, i.e. the "background" set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[input(twin)]
+    pub background_filter: Option<CompoundQuery>,
+
+    /// Scores terms by their [mutual information].
+    ///
+    /// [mutual information]: https://www.elastic.co/guide/en/elasticsearch/reference/7.x/search-aggregations-bucket-significantterms-aggregation.html#_mutual_information
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[input(twin)]
+    pub mutual_information: Option<SignificanceHeuristic>,
+
+    /// Scores terms by [chi square].
+    ///
+    /// [chi square]: https://www.elastic.co/guide/en/elasticsearch/reference/7.x/search-aggregations-bucket-significantterms-aggregation.html#_chi_square
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[input(twin)]
+    pub chi_square: Option<SignificanceHeuristic>,
+
+    /// Scores terms by their [Google normalized distance].
+    ///
+    /// [Google normalized distance]: https://www.elastic.co/guide/en/elasticsearch/reference/7.x/search-aggregations-bucket-significantterms-aggregation.html#_google_normalized_distance
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[input(twin)]
+    pub gnd: Option<GndHeuristic>,
+
+    /// Whether to score terms by [JLH score], the default.
+    ///
+    /// [JLH score]: https://www.elastic.co/guide/en/elasticsearch/reference/7.x/search-aggregations-bucket-significantterms-aggregation.html#_jlh_score
+    #[serde(default, skip_serializing_if = "is_false", with = "empty_object")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<crate::schema::Map>"))]
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[input(graphql(default))]
+    pub jlh: bool,
+
+    /// Whether to score terms by the [percentage] of their foreground
+    /// documents in the background documents.
+    ///
+    /// [percentage]: https://www.elastic.co/guide/en/elasticsearch/reference/7.x/search-aggregations-bucket-significantterms-aggregation.html#_percentage
+    #[serde(default, skip_serializing_if = "is_false", with = "empty_object")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<crate::schema::Map>"))]
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[input(graphql(default))]
+    pub percentage: bool,
+
+    /// Scores terms with a [script].
+    ///
+    /// [script]: https://www.elastic.co/guide/en/elasticsearch/reference/7.x/search-aggregations-bucket-significantterms-aggregation.html#_scripted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[input(twin)]
+    pub script_heuristic: Option<ScriptHeuristic>,
 }
 
-/// An aggregation that returns interesting or unusual occurrences of free-text
-/// terms in a set.
-///
-/// See the official documentation for [significant text] for more information.
-///
-/// [significant text]: https://www.elastic.co/guide/en/elasticsearch/reference/7.x/search-aggregations-bucket-significanttext-aggregation.html
-#[cfg_attr(test, derive(PartialEq))]
+/// The options of the `mutual_information` and `chi_square` significance
+/// heuristics.
+#[input_twin]
+#[cfg_attr(
+    feature = "graphql",
+    elastiql_derive::graphql_name("SignificanceHeuristic")
+)]
 #[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 #[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
 #[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
-#[derive(Serialize, Deserialize, Clone, Debug)]
-pub struct SignificantTextAggregation {
-    /// The field to perform the aggregation over.
-    pub field: String,
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SignificanceHeuristic {
+    /// Whether to also score terms that appear less often in the foreground
+    /// than in the background documents. (Defaults to `false`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub include_negatives: Option<bool>,
 
-    /// The number of term buckets that should be returned out of the overall
-    /// terms list.
+    /// Whether the background documents contain the foreground documents,
+    /// which is the case unless there is a `background_filter` that doesn't
+    /// match them. (Defaults to `true`.)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[cfg_attr(feature = "builder", builder(default))]
-    pub size: Option<u64>,
+    pub background_is_superset: Option<bool>,
+}
 
-    #[allow(missing_docs)]
-    #[serde(default)]
+/// The options of the `gnd` significance heuristic.
+#[input_twin]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("GndHeuristic"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GndHeuristic {
+    /// Whether the background documents contain the foreground documents.
+    /// (Defaults to `true`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     #[cfg_attr(feature = "builder", builder(default))]
-    pub filter_duplicate_text: bool,
+    pub background_is_superset: Option<bool>,
 }
 
-#[cfg(feature = "graphql")]
-impl From<SignificantTextAggregationInput> for SignificantTextAggregation {
+/// The options of the `script_heuristic` significance heuristic.
+#[input_twin]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("ScriptHeuristic"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ScriptHeuristic {
+    /// The script computing the score of a term from `params._subset_freq`,
+    /// `params._superset_freq`, `params._subset_size` and
+    /// `params._superset_size`.
+    #[input(twin)]
+    pub script: Script,
+}
+
+#[inline]
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+/// (De)serializes a `bool` as an empty object if it is `true`, for the
+/// heuristics without options, e.g. `"jlh": {}`.
+mod empty_object {
+    use serde::{de::IgnoredAny, ser::SerializeMap, Deserialize, Deserializer, Serializer};
+
+    #[inline]
+    pub(super) fn serialize<S: Serializer>(_: &bool, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_map(Some(0))?.end()
+    }
+
     #[inline]
-    fn from(input: SignificantTextAggregationInput) -> Self {
-        Self {
-            field: input.field,
-            size: input.size,
-            filter_duplicate_text: input.filter_duplicate_text,
-        }
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<bool, D::Error> {
+        Option::<IgnoredAny>::deserialize(deserializer).map(|value| value.is_some())
     }
 }
@@ -2,6 +2,7 @@
 //!
 //! [Terms]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-bucket-terms-aggregation.html
 
+use elastiql_derive::input_twin;
 use serde::{Deserialize, Serialize};
 
 use crate::search::Script;
@@ -18,51 +19,13 @@ use crate::search::ScriptInput;
 ///
 /// [Union input types]: https://github.com/graphql/graphql-spec/blob/master/rfcs/InputUnion.md
 /// [*multi-bucketing*]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-bucket.html
-#[cfg(feature = "graphql")]
-#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(async_graphql::InputObject, Serialize, Clone, Debug)]
-#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
-pub struct TermsAggregationInput {
-    /// The field to perform the aggregation over.
-    pub field: Option<String>,
-
-    /// The script to use.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub script: Option<ScriptInput>,
-
-    /// The [size parameter] can be set to define how many term buckets should
-    /// be returned out of the overall terms list. By default, the node
-    /// coordinating the search process will request each shard to provide its
-    /// own top size term buckets and once all shards respond, it will reduce
-    /// the results to the final list that will then be returned to the client.
-    /// This means that if the number of unique terms is greater than size, the
-    /// returned list is slightly off and not accurate (it could be that the
-    /// term counts are slightly off and it could even be that a term that
-    /// should have been in the top size buckets was not returned).
-    ///
-    /// [size parameter]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-bucket-terms-aggregation.html#search-aggregations-bucket-terms-aggregation-size
-    #[graphql(default_with = "Some(1_000)")]
-    #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub size: Option<u64>,
-
-    /// How missing values should be treated.
-    ///
-    /// By default they will be ignored, but it is also possible to treat them
-    /// as if they had the value.
-    #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub missing: Option<f64>,
-}
-
-/// A [*multi-bucketing*] value source based aggregation where buckets are
-/// dynamically built - one per unique value.
-///
-/// [*multi-bucketing*]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-bucket.html
-#[cfg_attr(test, derive(PartialEq))]
+#[input_twin]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("TermsAggregation"))]
 #[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 #[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
 pub struct TermsAggregation {
     /// The field to perform the aggregation over.
@@ -70,6 +33,7 @@ pub struct TermsAggregation {
 
     /// The script to use.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[input(twin)]
     pub script: Option<Script>,
 
     /// The [size parameter] can be set to define how many term buckets should
@@ -85,6 +49,7 @@ pub struct TermsAggregation {
     /// [size parameter]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-bucket-terms-aggregation.html#search-aggregations-bucket-terms-aggregation-size
     #[cfg_attr(feature = "builder", builder(default))]
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[input(graphql(default_with = "Some(1_000)"))]
     pub size: Option<u64>,
 
     /// How missing values should be treated.
@@ -93,22 +58,10 @@ pub struct TermsAggregation {
     /// as if they had the value.
     #[cfg_attr(feature = "builder", builder(default))]
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::option_float))]
     pub missing: Option<f64>,
 }
 
-#[cfg(feature = "graphql")]
-impl From<TermsAggregationInput> for TermsAggregation {
-    #[inline]
-    fn from(aggregation: TermsAggregationInput) -> Self {
-        TermsAggregation {
-            field: aggregation.field,
-            script: aggregation.script.map(Into::into),
-            size: aggregation.size,
-            missing: aggregation.missing,
-        }
-    }
-}
-
 #[cfg(test)]
 impl<T: Into<String>> From<T> for TermsAggregation {
     #[inline]
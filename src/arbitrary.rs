@@ -0,0 +1,402 @@
+//! [`Arbitrary`] implementations for the recursive query types, along with
+//! helpers for generating values that survive a round trip through JSON.
+//!
+//! [`Arbitrary`]: https://docs.rs/arbitrary/latest/arbitrary/trait.Arbitrary.html
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+use serde_json::json;
+
+use crate::{
+    scalars::{Map, SortedValue},
+    search::query::span::{
+        SpanContainingQuery, SpanFirstQuery, SpanMultiQuery, SpanNearQuery, SpanNotQuery,
+        SpanOrQuery, SpanQuery, SpanTermQuery, SpanWithinQuery,
+    },
+    search::query::{
+        BooleanQuery, CompoundQuery, ExistsQuery, FuzzyQuery, GeoBoundingBoxQuery,
+        GeoDistanceQuery, GeoPolygonQuery, GeoShapeQuery, IdsQuery, KnnQuery,
+        MatchPhrasePrefixQuery, MatchPhraseQuery, MatchQuery, MoreLikeThisQuery, MultiMatchQuery,
+        NestedQuery, PercolateQuery, PrefixQuery, Query, QueryStringQuery, RangeQuery,
+        RankFeatureQuery, RegexpQuery, ScriptQuery, ScriptScoreQuery, SimpleQueryStringQuery,
+        TermQuery, TermsQuery, TermsSetQuery, WildcardQuery,
+    },
+};
+
+/// The maximum depth of nested queries and aggregations.
+pub(crate) const MAX_DEPTH: usize = 3;
+
+/// Field names that are special to Elasticsearch and thus not serialized as
+/// is, e.g. `score` is sorted by as `_score`, or that are serialized next to
+/// the field name, e.g. the `distance` of a `geo_distance` query.
+const RESERVED_FIELDS: &[&str] = &["boost", "count", "distance", "key", "score"];
+
+/// Returns an arbitrary, non-empty field name.
+#[inline]
+pub(crate) fn field(u: &mut Unstructured) -> Result<String> {
+    const CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz_.";
+
+    let len = u.int_in_range(1..=12)?;
+    let mut field = String::with_capacity(len);
+    for _ in 0..len {
+        field.push(*u.choose(CHARS)? as char);
+    }
+
+    if RESERVED_FIELDS.contains(&field.as_str()) {
+        field.insert(0, 'f');
+    }
+
+    Ok(field)
+}
+
+/// Returns an arbitrary, finite `f64` that is exactly representable in JSON.
+#[inline]
+pub(crate) fn float(u: &mut Unstructured) -> Result<f64> {
+    Ok(f64::from(i32::arbitrary(u)?) / 4.0)
+}
+
+/// Returns an optional [`float`].
+#[inline]
+pub(crate) fn option_float(u: &mut Unstructured) -> Result<Option<f64>> {
+    Ok(if u.arbitrary()? {
+        Some(float(u)?)
+    } else {
+        None
+    })
+}
+
+/// Returns an arbitrary, non-empty vector of [`float`]s.
+#[inline]
+pub(crate) fn floats(u: &mut Unstructured) -> Result<Vec<f64>> {
+    let len = u.int_in_range(1..=4)?;
+    (0..len).map(|_| float(u)).collect()
+}
+
+/// Returns an arbitrary, finite `f32` that is exactly representable in JSON.
+#[inline]
+pub(crate) fn float32(u: &mut Unstructured) -> Result<f32> {
+    Ok(f32::from(i16::arbitrary(u)?) / 4.0)
+}
+
+/// Returns an arbitrary, non-empty vector of [`float32`]s.
+#[inline]
+pub(crate) fn vector(u: &mut Unstructured) -> Result<Vec<f32>> {
+    let len = u.int_in_range(1..=4)?;
+    (0..len).map(|_| float32(u)).collect()
+}
+
+/// Returns an arbitrary JSON object of field names to strings.
+#[inline]
+pub(crate) fn map(u: &mut Unstructured) -> Result<Map> {
+    let len = u.int_in_range(0..=3)?;
+    let mut map = Map::with_capacity(len);
+    for _ in 0..len {
+        map.insert(field(u)?, json!(String::arbitrary(u)?).into());
+    }
+    Ok(map)
+}
+
+/// Returns an optional [`map`].
+#[inline]
+pub(crate) fn option_map(u: &mut Unstructured) -> Result<Option<Map>> {
+    Ok(if u.arbitrary()? { Some(map(u)?) } else { None })
+}
+
+/// Returns a few arbitrary [`map`]s.
+#[inline]
+pub(crate) fn maps(u: &mut Unstructured) -> Result<Vec<Map>> {
+    let len = u.int_in_range(0..=3)?;
+    (0..len).map(|_| map(u)).collect()
+}
+
+/// Returns arbitrary values to search after.
+///
+/// Only values that deserialize into the same variant are returned, e.g. no
+/// `SortedValue::Json` strings.
+#[inline]
+pub(crate) fn sorted_values(u: &mut Unstructured) -> Result<Vec<SortedValue>> {
+    let len = u.int_in_range(0..=3)?;
+    (0..len)
+        .map(|_| {
+            Ok(match u.choose_index(6)? {
+                0 => SortedValue::Null,
+                1 => SortedValue::Bool(u.arbitrary()?),
+                2 => SortedValue::Int(u.arbitrary()?),
+                3 => SortedValue::NegInt(u.int_in_range(i64::MIN..=-1)?),
+                4 => SortedValue::Float(float(u)?),
+                _ => SortedValue::String(u.arbitrary()?),
+            })
+        })
+        .collect()
+}
+
+/// Returns an arbitrary query of any kind, only nesting other queries up to
+/// `depth` levels deep.
+fn query(u: &mut Unstructured, depth: usize) -> Result<Query> {
+    let kinds = if depth == 0 { 26 } else { 29 };
+
+    Ok(match u.choose_index(kinds)? {
+        0 => u.arbitrary::<ExistsQuery>()?.into(),
+        1 => u.arbitrary::<TermQuery>()?.into(),
+        2 => u.arbitrary::<TermsQuery>()?.into(),
+        3 => u.arbitrary::<TermsSetQuery>()?.into(),
+        4 => u.arbitrary::<RangeQuery>()?.into(),
+        5 => u.arbitrary::<PrefixQuery>()?.into(),
+        6 => u.arbitrary::<RegexpQuery>()?.into(),
+        7 => u.arbitrary::<MatchQuery>()?.into(),
+        8 => u.arbitrary::<SimpleQueryStringQuery>()?.into(),
+        9 => u.arbitrary::<QueryStringQuery>()?.into(),
+        10 => u.arbitrary::<KnnQuery>()?.into(),
+        11 => u.arbitrary::<MatchPhraseQuery>()?.into(),
+        12 => u.arbitrary::<MatchPhrasePrefixQuery>()?.into(),
+        13 => u.arbitrary::<MultiMatchQuery>()?.into(),
+        14 => u.arbitrary::<FuzzyQuery>()?.into(),
+        15 => u.arbitrary::<WildcardQuery>()?.into(),
+        16 => u.arbitrary::<IdsQuery>()?.into(),
+        17 => u.arbitrary::<MoreLikeThisQuery>()?.into(),
+        18 => u.arbitrary::<PercolateQuery>()?.into(),
+        19 => u.arbitrary::<ScriptQuery>()?.into(),
+        20 => u.arbitrary::<GeoDistanceQuery>()?.into(),
+        21 => geo_bounding_box_query(u)?.into(),
+        22 => u.arbitrary::<GeoShapeQuery>()?.into(),
+        23 => u.arbitrary::<GeoPolygonQuery>()?.into(),
+        24 => span_query(u, depth)?.into(),
+        25 => u.arbitrary::<RankFeatureQuery>()?.into(),
+        26 => NestedQuery {
+            path: field(u)?,
+            query: boolean_query(u, depth - 1)?.into(),
+            ignore_unmapped: u.arbitrary()?,
+        }
+        .into(),
+        27 => ScriptScoreQuery {
+            query: boolean_query(u, depth - 1)?.into(),
+            script: u.arbitrary()?,
+            min_score: option_float(u)?,
+            boost: option_float(u)?,
+        }
+        .into(),
+        _ => boolean_query(u, depth - 1)?.into_query(),
+    })
+}
+
+/// Returns an arbitrary geo-bounding box query, with a `wkt` bounding box if
+/// none was generated since an empty one doesn't deserialize.
+fn geo_bounding_box_query(u: &mut Unstructured) -> Result<GeoBoundingBoxQuery> {
+    let mut query = u.arbitrary::<GeoBoundingBoxQuery>()?;
+    if query.top_left.is_none()
+        && query.bottom_right.is_none()
+        && query.wkt.is_none()
+        && query.top.is_none()
+        && query.left.is_none()
+        && query.bottom.is_none()
+        && query.right.is_none()
+    {
+        query.wkt = Some(u.arbitrary()?);
+    }
+    Ok(query)
+}
+
+/// Returns an arbitrary span query, only nesting other span queries up to
+/// `depth` levels deep.
+fn span_query(u: &mut Unstructured, depth: usize) -> Result<SpanQuery> {
+    let kinds = if depth == 0 { 2 } else { 8 };
+
+    Ok(match u.choose_index(kinds)? {
+        0 => u.arbitrary::<SpanTermQuery>()?.into(),
+        1 => SpanMultiQuery::new(multi_term_query(u)?).into(),
+        2 => SpanNearQuery {
+            clauses: span_queries(u, depth - 1)?,
+            slop: u.arbitrary()?,
+            in_order: u.arbitrary()?,
+            boost: option_float(u)?,
+        }
+        .into(),
+        3 => SpanFirstQuery {
+            match_: span_query(u, depth - 1)?,
+            end: u.arbitrary()?,
+            boost: option_float(u)?,
+        }
+        .into(),
+        4 => SpanOrQuery {
+            clauses: span_queries(u, depth - 1)?,
+            boost: option_float(u)?,
+        }
+        .into(),
+        5 => SpanNotQuery {
+            include: span_query(u, depth - 1)?,
+            exclude: span_query(u, depth - 1)?,
+            pre: u.arbitrary()?,
+            post: u.arbitrary()?,
+            dist: u.arbitrary()?,
+            boost: option_float(u)?,
+        }
+        .into(),
+        6 => SpanContainingQuery {
+            big: span_query(u, depth - 1)?,
+            little: span_query(u, depth - 1)?,
+            boost: option_float(u)?,
+        }
+        .into(),
+        _ => SpanWithinQuery {
+            big: span_query(u, depth - 1)?,
+            little: span_query(u, depth - 1)?,
+            boost: option_float(u)?,
+        }
+        .into(),
+    })
+}
+
+/// Returns arbitrary span queries, only nesting other span queries up to
+/// `depth` levels deep.
+fn span_queries(u: &mut Unstructured, depth: usize) -> Result<Vec<SpanQuery>> {
+    let len = u.int_in_range(0..=3)?;
+    (0..len).map(|_| span_query(u, depth)).collect()
+}
+
+/// Returns an arbitrary multi-term query, i.e. one a `span_multi` query can
+/// wrap.
+fn multi_term_query(u: &mut Unstructured) -> Result<Query> {
+    Ok(match u.choose_index(5)? {
+        0 => u.arbitrary::<PrefixQuery>()?.into(),
+        1 => u.arbitrary::<WildcardQuery>()?.into(),
+        2 => u.arbitrary::<RegexpQuery>()?.into(),
+        3 => u.arbitrary::<FuzzyQuery>()?.into(),
+        _ => u.arbitrary::<RangeQuery>()?.into(),
+    })
+}
+
+/// Returns arbitrary queries, only nesting other queries up to `depth` levels
+/// deep.
+fn queries(u: &mut Unstructured, depth: usize) -> Result<Vec<Query>> {
+    let len = u.int_in_range(0..=3)?;
+    (0..len).map(|_| query(u, depth)).collect()
+}
+
+/// Returns an arbitrary boolean query, only nesting other queries up to
+/// `depth` levels deep.
+fn boolean_query(u: &mut Unstructured, depth: usize) -> Result<BooleanQuery> {
+    Ok(BooleanQuery {
+        must: queries(u, depth)?,
+        filter: queries(u, depth)?,
+        should: queries(u, depth)?,
+        must_not: queries(u, depth)?,
+        minimum_should_match: u.arbitrary()?,
+        boost: option_float(u)?,
+    })
+}
+
+impl<'a> Arbitrary<'a> for Query {
+    #[inline]
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        query(u, MAX_DEPTH)
+    }
+}
+
+impl<'a> Arbitrary<'a> for BooleanQuery {
+    #[inline]
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        boolean_query(u, MAX_DEPTH)
+    }
+}
+
+impl<'a> Arbitrary<'a> for CompoundQuery {
+    #[inline]
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(CompoundQuery {
+            boolean: if u.arbitrary()? {
+                Some(u.arbitrary()?)
+            } else {
+                None
+            },
+            // a `root` query isn't generated since a `bool` one would be
+            // deserialized as the `boolean` query instead
+            root: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{aggregation::Request as Aggregation, search::Request};
+
+    /// Returns pseudo-random bytes to generate arbitrary values from.
+    fn bytes(seed: u64) -> Vec<u8> {
+        let mut state = seed;
+        (0..4096)
+            .map(|_| {
+                // xorshift
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state as u8
+            })
+            .collect()
+    }
+
+    macro_rules! test_case {
+        ($name:ident : $ty:ty) => {
+            #[test]
+            fn $name() {
+                for seed in 1..=256 {
+                    let bytes = bytes(seed);
+                    let expected: $ty = Unstructured::new(&bytes).arbitrary().unwrap();
+
+                    let j = serde_json::to_value(&expected).unwrap();
+                    let actual: $ty = serde_json::from_value(j.clone()).unwrap();
+                    assert_eq!(actual, expected, "{}", &j);
+                }
+            }
+        };
+    }
+
+    test_case!(query_round_trips: Query);
+    test_case!(boolean_query_round_trips: BooleanQuery);
+    test_case!(compound_query_round_trips: CompoundQuery);
+    test_case!(aggregation_round_trips: Aggregation);
+
+    #[test]
+    fn request_serializes_its_query() {
+        for seed in 1..=256 {
+            let bytes = bytes(seed);
+            let request: Request = Unstructured::new(&bytes).arbitrary().unwrap();
+
+            let j = serde_json::to_value(&request).unwrap();
+            let query: CompoundQuery = j
+                .get("query")
+                .map(|query| serde_json::from_value(query.clone()).unwrap())
+                .unwrap_or_default();
+            assert_eq!(query.is_empty(), request.query.is_empty(), "{}", &j);
+            if !query.is_empty() {
+                assert_eq!(query, request.query, "{}", &j);
+            }
+        }
+    }
+
+    #[test]
+    fn nesting_is_bounded() {
+        fn depth(query: &Query) -> usize {
+            let boolean = query
+                .boolean
+                .as_deref()
+                .or_else(|| query.nested.as_ref()?.query.boolean.as_ref())
+                .or_else(|| query.script_score.as_ref()?.query.boolean.as_ref());
+
+            boolean.map_or(0, |boolean| {
+                let queries = boolean
+                    .must
+                    .iter()
+                    .chain(&boolean.filter)
+                    .chain(&boolean.should)
+                    .chain(&boolean.must_not);
+                1 + queries.map(depth).max().unwrap_or(0)
+            })
+        }
+
+        for seed in 1..=256 {
+            let bytes = bytes(seed);
+            let query: Query = Unstructured::new(&bytes).arbitrary().unwrap();
+            assert!(depth(&query) <= MAX_DEPTH, "{:#?}", &query);
+        }
+    }
+}
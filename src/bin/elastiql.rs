@@ -0,0 +1,267 @@
+//! Validates, lints, simplifies or pretty-prints a query read from stdin, e.g.
+//! to check stored query files in CI.
+//!
+//! The input is either a query, e.g. `{ "term": { ... } }`, or a search request
+//! body with a `query` and/or `aggs`, whose other fields are passed through as
+//! they are.
+
+use std::{
+    env,
+    io::{self, Read},
+    process,
+};
+
+use elastiql::{
+    aggregation::Request as Aggregation,
+    search::query::{CompoundQuery, Severity, ValidationPolicy},
+};
+use serde_json::{Map, Value};
+
+const USAGE: &str = "\
+usage: elastiql <command> [options] < query.json
+
+commands:
+    validate    checks the query against the options below
+    lint        lists warnings about the query
+    simplify    prints a smaller query that matches the same documents
+    pretty      pretty-prints the query
+
+options:
+    --max-depth <n>             the maximum nesting depth of the query
+    --max-clauses <n>           the maximum number of clauses of the query
+    --no-leading-wildcards      rejects patterns starting with a wildcard
+
+Exits with 1 if the query fails validation or has lint warnings and with 2 if
+it can't be parsed.
+";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (command, options) = match args.split_first() {
+        Some((command, _)) if command == "-h" || command == "--help" => {
+            print!("{}", USAGE);
+            return;
+        }
+        Some(args) => args,
+        None => {
+            eprint!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    let mut input = String::new();
+    if let Err(error) = io::stdin().read_to_string(&mut input) {
+        eprintln!("elastiql: failed to read stdin: {}", error);
+        process::exit(2);
+    }
+
+    match run(command, options, &input) {
+        Ok((output, passed)) => {
+            if !output.is_empty() {
+                println!("{}", output);
+            }
+            if !passed {
+                process::exit(1);
+            }
+        }
+        Err(error) => {
+            eprintln!("elastiql: {}", error);
+            process::exit(2);
+        }
+    }
+}
+
+/// Runs `command` on the query in `input`, returning what to print and whether
+/// the query passed.
+fn run(command: &str, options: &[String], input: &str) -> Result<(String, bool), String> {
+    let policy = parse_options(options)?;
+    let input = Input::parse(input).map_err(|error| format!("invalid query: {}", error))?;
+
+    match command {
+        "validate" => match input.query.validate(&policy) {
+            Ok(()) => Ok((String::new(), true)),
+            Err(error) => Ok((error.to_string(), false)),
+        },
+        "lint" => {
+            let lints = input.query.lint();
+            let passed = lints.iter().all(|lint| lint.severity < Severity::Warning);
+            let lines: Vec<_> = lints.iter().map(ToString::to_string).collect();
+            Ok((lines.join("\n"), passed))
+        }
+        "simplify" => {
+            let input = Input {
+                query: input.query.simplify(),
+                ..input
+            };
+            Ok((input.to_json_pretty().map_err(|e| e.to_string())?, true))
+        }
+        "pretty" => Ok((input.to_json_pretty().map_err(|e| e.to_string())?, true)),
+        command => Err(format!("unknown command `{}`\n\n{}", command, USAGE)),
+    }
+}
+
+/// Parses the `--max-depth`, `--max-clauses` and `--no-leading-wildcards`
+/// options.
+fn parse_options(options: &[String]) -> Result<ValidationPolicy, String> {
+    let mut policy = ValidationPolicy::default();
+    let mut options = options.iter();
+    while let Some(option) = options.next() {
+        let mut number = || {
+            options
+                .next()
+                .and_then(|n| n.parse().ok())
+                .ok_or_else(|| format!("`{}` expects a number", option))
+        };
+        match option.as_str() {
+            "--max-depth" => policy.max_depth = Some(number()?),
+            "--max-clauses" => policy.max_clauses = Some(number()?),
+            "--no-leading-wildcards" => policy.allow_leading_wildcards = false,
+            option => return Err(format!("unknown option `{}`\n\n{}", option, USAGE)),
+        }
+    }
+    Ok(policy)
+}
+
+/// The keys of the aggregations of a search request body.
+const AGGREGATIONS: [&str; 2] = ["aggs", "aggregations"];
+
+/// A query along with the rest of the request body it came from (if any).
+struct Input {
+    query: CompoundQuery,
+    request: Option<Request>,
+}
+
+/// The parts of a search request body other than its `query`.
+struct Request {
+    /// The key and the parsed aggregations (if any).
+    aggregations: Option<(String, Vec<Aggregation>)>,
+    /// Everything else, as is.
+    rest: Map<String, Value>,
+}
+
+impl Input {
+    fn parse(json: &str) -> serde_json::Result<Input> {
+        match serde_json::from_str(json)? {
+            Value::Object(mut rest)
+                if rest.contains_key("query")
+                    || AGGREGATIONS.iter().any(|key| rest.contains_key(*key)) =>
+            {
+                let query = match rest.remove("query") {
+                    Some(query) => serde_json::from_value(query)?,
+                    None => CompoundQuery::default(),
+                };
+                let aggregations = match AGGREGATIONS
+                    .iter()
+                    .find_map(|key| Some((key.to_string(), rest.remove(*key)?)))
+                {
+                    Some((key, Value::Object(aggregations))) => {
+                        // an `Aggregation` is a single `{ name: body }` entry
+                        let aggregations = aggregations
+                            .into_iter()
+                            .map(|entry| {
+                                serde_json::from_value(Value::Object(
+                                    vec![entry].into_iter().collect(),
+                                ))
+                            })
+                            .collect::<serde_json::Result<_>>()?;
+                        Some((key, aggregations))
+                    }
+                    Some((key, _)) => {
+                        return Err(serde::de::Error::custom(format_args!(
+                            "`{}` must be an object",
+                            key
+                        )))
+                    }
+                    None => None,
+                };
+
+                Ok(Input {
+                    query,
+                    request: Some(Request { aggregations, rest }),
+                })
+            }
+            query => Ok(Input {
+                query: serde_json::from_value(query)?,
+                request: None,
+            }),
+        }
+    }
+
+    /// Serializes the query, back into its request body (if any).
+    fn to_json_pretty(&self) -> serde_json::Result<String> {
+        let request = match self.request {
+            Some(ref request) => request,
+            None => return self.query.to_json_pretty(),
+        };
+
+        let mut json = request.rest.clone();
+        if !self.query.is_empty() {
+            json.insert("query".to_string(), serde_json::to_value(&self.query)?);
+        }
+        if let Some((ref key, ref aggregations)) = request.aggregations {
+            let mut map = Map::new();
+            for aggregation in aggregations {
+                if let Value::Object(entry) = serde_json::to_value(aggregation)? {
+                    map.extend(entry);
+                }
+            }
+            json.insert(key.clone(), Value::Object(map));
+        }
+        serde_json::to_string_pretty(&json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn validates_and_lints_queries() {
+        let query = r#"{ "regexp": { "user": { "value": ".*chy" } } }"#;
+
+        assert_eq!(run("validate", &[], query), Ok((String::new(), true)));
+        let options = ["--no-leading-wildcards".to_string()];
+        assert!(!run("validate", &options, query).unwrap().1);
+
+        let (output, passed) = run("lint", &[], query).unwrap();
+        assert!(!passed);
+        assert_eq!(output, "`regexp`: pattern `.*chy` starts with a wildcard");
+    }
+
+    #[test]
+    fn keeps_the_rest_of_request_bodies() {
+        let request = json!({
+            "query": { "bool": { "filter": [{ "term": { "user": { "value": "kimchy" } } }] } },
+            "size": 10
+        });
+
+        let (output, _) = run("simplify", &[], &request.to_string()).unwrap();
+        let output: Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(output["size"], 10);
+        assert!(output["query"].is_object());
+    }
+
+    #[test]
+    fn parses_aggregations() {
+        let request = json!({
+            "aggs": { "by_user": { "terms": { "field": "user" } } }
+        });
+
+        let (output, _) = run("pretty", &[], &request.to_string()).unwrap();
+        let output: Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(output["aggs"]["by_user"]["terms"]["field"], "user");
+        assert!(output.get("query").is_none());
+
+        let request = json!({ "aggs": [] });
+        assert!(run("pretty", &[], &request.to_string()).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        assert!(run("pretty", &[], "{ \"term\": 1 }").is_err());
+        assert!(run("pretty", &["--max-depth".to_string()], "{}").is_err());
+        assert!(run("convert", &[], "{}").is_err());
+    }
+}
@@ -1,9 +1,169 @@
-//! Response types for [bulk] queries.
+//! Request helpers and response types for [bulk] queries.
 //!
 //! [bulk]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-bulk.html
 
-use crate::search::ErrResponse;
-use serde::Deserialize;
+use crate::search::{ErrResponse, VersionConflict};
+use serde::{Deserialize, Serialize};
+
+/// Serializes the `lines` of a bulk request, i.e. each action followed by its
+/// source document (if any), as the [NDJSON] body the bulk API expects.
+///
+/// [NDJSON]: http://ndjson.org
+pub fn to_ndjson<L: Serialize>(lines: impl IntoIterator<Item = L>) -> serde_json::Result<String> {
+    let mut body = String::new();
+    for line in lines {
+        body.push_str(&serde_json::to_string(&line)?);
+        body.push('\n');
+    }
+    Ok(body)
+}
+
+/// Serializes the `lines` of a bulk request as pretty-printed JSON, separated
+/// by blank lines, e.g. for debugging.
+///
+/// **Note**: this isn't valid NDJSON; use [`to_ndjson`] for the body of the
+/// request.
+pub fn to_ndjson_pretty<L: Serialize>(
+    lines: impl IntoIterator<Item = L>,
+) -> serde_json::Result<String> {
+    let lines = lines
+        .into_iter()
+        .map(|line| serde_json::to_string_pretty(&line))
+        .collect::<serde_json::Result<Vec<_>>>()?;
+    Ok(lines.join("\n\n"))
+}
+
+/// The action line of an operation in a bulk request, e.g.
+/// `{ "index": { "_id": "1" } }`, which is followed by its source document
+/// (except for `delete` operations).
+///
+/// ```
+/// use elastiql::bulk::{to_ndjson, Operation, OperationMetadata};
+///
+/// let operation = Operation::Index(
+///     OperationMetadata::builder()
+///         .id("1".to_string())
+///         .if_seq_no(362)
+///         .if_primary_term(2)
+///         .build(),
+/// );
+///
+/// assert_eq!(
+///     to_ndjson(&[operation]).unwrap(),
+///     "{\"index\":{\"_id\":\"1\",\"if_seq_no\":362,\"if_primary_term\":2}}\n"
+/// );
+/// ```
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Operation {
+    /// Indexes a document, replacing it if it exists.
+    Index(OperationMetadata),
+
+    /// Indexes a document, failing if it exists.
+    Create(OperationMetadata),
+
+    /// Partially updates a document.
+    Update(OperationMetadata),
+
+    /// Deletes a document.
+    Delete(OperationMetadata),
+}
+
+impl Operation {
+    /// The metadata of this operation.
+    #[inline]
+    pub fn metadata(&self) -> &OperationMetadata {
+        match self {
+            Operation::Index(metadata)
+            | Operation::Create(metadata)
+            | Operation::Update(metadata)
+            | Operation::Delete(metadata) => metadata,
+        }
+    }
+
+    /// The metadata of this operation, mutably.
+    #[inline]
+    pub fn metadata_mut(&mut self) -> &mut OperationMetadata {
+        match self {
+            Operation::Index(metadata)
+            | Operation::Create(metadata)
+            | Operation::Update(metadata)
+            | Operation::Delete(metadata) => metadata,
+        }
+    }
+}
+
+/// The metadata of an [`Operation`], which is left out of its action line if
+/// unset.
+///
+/// Set `if_seq_no` and `if_primary_term` (or `version`) for [optimistic
+/// concurrency control], i.e. to only apply the operation if the document
+/// hasn't changed since it was read.
+///
+/// [optimistic concurrency control]: https://www.elastic.co/guide/en/elasticsearch/reference/current/optimistic-concurrency-control.html
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(default, setter(into))))]
+pub struct OperationMetadata {
+    /// The index (or data stream or alias) of the document, unless it is in
+    /// the path of the request.
+    #[serde(rename = "_index", default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<String>,
+
+    /// The ID of the document, which is generated by `index` operations if
+    /// unset.
+    #[serde(rename = "_id", default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// Routes the operation to the shard of this value instead of the ID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub routing: Option<String>,
+
+    /// Only applies the operation if the document has this sequence number.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub if_seq_no: Option<u64>,
+
+    /// Only applies the operation if the document has this primary term.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub if_primary_term: Option<u64>,
+
+    /// The version of the document, checked according to `version_type`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<u64>,
+
+    /// How the `version` is checked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_type: Option<VersionType>,
+
+    /// Whether the `index` must be an alias. (Defaults to `false`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub require_alias: Option<bool>,
+
+    /// The ingest pipeline to preprocess the document with, instead of the
+    /// request's or the index's default one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pipeline: Option<String>,
+}
+
+/// How the [version] of a document is checked.
+///
+/// [version]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-index_.html#index-versioning
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum VersionType {
+    /// The version must match the document's, which is then incremented.
+    Internal,
+
+    /// The version must be greater than the document's, which is then set to
+    /// it, e.g. for versions maintained by another database.
+    External,
+
+    /// The version must be greater than or equal to the document's.
+    ExternalGte,
+}
 
 // TODO: add these upstream https://github.com/elastic/elasticsearch-rs/issues/75
 // TODO: add missing fields...
@@ -25,6 +185,18 @@ pub struct Response<T> {
     pub items: Vec<Action<T>>,
 }
 
+impl<T> Response<T> {
+    /// Returns the operations that failed because of a [`VersionConflict`],
+    /// along with their index in the request.
+    #[inline]
+    pub fn version_conflicts(&self) -> impl Iterator<Item = (usize, VersionConflict)> + '_ {
+        self.items
+            .iter()
+            .enumerate()
+            .filter_map(|(i, action)| Some((i, action.item().version_conflict()?)))
+    }
+}
+
 /// The result of a bulk operation.
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
@@ -42,15 +214,26 @@ pub enum Action<T> {
     Delete(Item<T>),
 }
 
+impl<T> Action<T> {
+    /// The result of the operation, regardless of its kind.
+    #[inline]
+    pub fn item(&self) -> &Item<T> {
+        match self {
+            Action::Create(item)
+            | Action::Index(item)
+            | Action::Update(item)
+            | Action::Delete(item) => item,
+        }
+    }
+}
+
 /// An individual bulk item.
 ///
 /// *TODO*: somehow use `std::error::Error` instead of this...
 #[derive(Deserialize, Debug)]
 #[serde(untagged)]
 pub enum Item<T> {
-    /// An `Ok` item.
-    Ok(OkItem<T>),
-
+    // NOTE: this must come first, since an `Err` item is also a valid `Ok` one
     /// An `Err` item.
     Err {
         /// The error.
@@ -59,6 +242,20 @@ pub enum Item<T> {
         /// The [HTTP status code](https://developer.mozilla.org/en-US/docs/Web/HTTP/Status).
         status: u16,
     },
+
+    /// An `Ok` item.
+    Ok(OkItem<T>),
+}
+
+impl<T> Item<T> {
+    /// Returns the error of this item as a [`VersionConflict`] if it is one.
+    #[inline]
+    pub fn version_conflict(&self) -> Option<VersionConflict> {
+        match self {
+            Item::Err { error, .. } => error.version_conflict(),
+            Item::Ok(_) => None,
+        }
+    }
 }
 
 /// An individual item from performing a successful bulk operation.
@@ -74,6 +271,26 @@ pub struct OkItem<T> {
     #[serde(rename = "_index")]
     pub index: String,
 
+    /// The [version] of the document after the operation.
+    ///
+    /// [version]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-index_.html#index-versioning
+    #[serde(default, rename = "_version")]
+    pub version: Option<u64>,
+
+    /// The sequence number of the document after the operation, used for
+    /// [optimistic concurrency control].
+    ///
+    /// [optimistic concurrency control]: https://www.elastic.co/guide/en/elasticsearch/reference/current/optimistic-concurrency-control.html
+    #[serde(default, rename = "_seq_no")]
+    pub sequence_number: Option<u64>,
+
+    /// The primary term of the document after the operation, used for
+    /// [optimistic concurrency control].
+    ///
+    /// [optimistic concurrency control]: https://www.elastic.co/guide/en/elasticsearch/reference/current/optimistic-concurrency-control.html
+    #[serde(default, rename = "_primary_term")]
+    pub primary_term: Option<u64>,
+
     /// The document's source (if requested).
     pub get: Option<Get<T>>,
 }
@@ -85,3 +302,103 @@ pub struct Get<T> {
     #[serde(rename = "_source")]
     pub source: Option<T>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn can_print_lines() {
+        let lines = vec![
+            json!({ "index": { "_id": "1" } }),
+            json!({ "user": "kimchy" }),
+        ];
+
+        assert_eq!(
+            to_ndjson(&lines).unwrap(),
+            "{\"index\":{\"_id\":\"1\"}}\n{\"user\":\"kimchy\"}\n"
+        );
+        assert_eq!(
+            to_ndjson_pretty(&lines).unwrap(),
+            "{\n  \"index\": {\n    \"_id\": \"1\"\n  }\n}\n\n{\n  \"user\": \"kimchy\"\n}"
+        );
+    }
+
+    #[test]
+    fn omits_unset_operation_metadata() {
+        let lines = vec![
+            Operation::Delete(OperationMetadata::default()),
+            Operation::Update(OperationMetadata {
+                id: Some("1".to_string()),
+                version: Some(4),
+                version_type: Some(VersionType::ExternalGte),
+                require_alias: Some(true),
+                pipeline: Some("enrich".to_string()),
+                ..Default::default()
+            }),
+        ];
+
+        let ndjson = to_ndjson(&lines).unwrap();
+        assert_eq!(
+            ndjson,
+            "{\"delete\":{}}\n{\"update\":{\"_id\":\"1\",\"version\":4,\"version_type\":\"external_gte\",\"require_alias\":true,\"pipeline\":\"enrich\"}}\n"
+        );
+
+        let parsed: Vec<Operation> = ndjson
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(parsed, lines);
+    }
+
+    #[test]
+    fn can_find_version_conflicts() {
+        let response: Response<serde_json::Value> = serde_json::from_value(json!({
+            "took": 3,
+            "errors": true,
+            "items": [
+                {
+                    "index": {
+                        "_index": "logs",
+                        "_id": "1",
+                        "_version": 2,
+                        "_seq_no": 6,
+                        "_primary_term": 1,
+                        "status": 200
+                    }
+                },
+                {
+                    "update": {
+                        "_index": "logs",
+                        "_id": "2",
+                        "status": 409,
+                        "error": {
+                            "type": "version_conflict_engine_exception",
+                            "reason": "[2]: version conflict, required seqNo [5], primary term [1]. current document has seqNo [6] and primary term [1]",
+                            "index": "logs",
+                            "index_uuid": "aAsFqTI0Tc2W0LCWgPNrOA"
+                        }
+                    }
+                }
+            ]
+        }))
+        .unwrap();
+
+        match response.items[0].item() {
+            Item::Ok(item) => {
+                assert_eq!(item.version, Some(2));
+                assert_eq!(item.sequence_number, Some(6));
+                assert_eq!(item.primary_term, Some(1));
+            }
+            item => panic!("expected an ok item, got {:?}", item),
+        }
+
+        let conflicts: Vec<_> = response.version_conflicts().collect();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].0, 1);
+        assert_eq!(conflicts[0].1.index, "logs");
+        assert!(conflicts[0].1.reason.starts_with("[2]: version conflict"));
+    }
+}
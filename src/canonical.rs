@@ -0,0 +1,170 @@
+//! Canonical, deterministic JSON serialization, e.g. for using queries as
+//! cache keys or comparing them across processes.
+//!
+//! The canonical form of a value is its regular JSON representation with:
+//!
+//! - the keys of every object sorted, regardless of the iteration order of
+//!   any `HashMap`s it was serialized from (or of `serde_json`'s
+//!   `preserve_order` feature)
+//! - floating point numbers without a fractional part written as integers,
+//!   e.g. `2.0` as `2` (and `-0.0` as `0`)
+//! - no insignificant whitespace
+//!
+//! Its [`fingerprint`] is a stable hash of it, e.g. for keying response caches.
+//!
+//! ```
+//! use elastiql::search::query::{Query, TermQuery};
+//!
+//! let query = Query::from(TermQuery {
+//!     boost: Some(2.0),
+//!     ..TermQuery::new("status", "open")
+//! });
+//!
+//! assert_eq!(
+//!     query.to_canonical_json().unwrap(),
+//!     r#"{"term":{"status":{"boost":2,"value":"open"}}}"#
+//! );
+//! ```
+
+use std::io;
+
+use serde::Serialize;
+use serde_json::{Map, Number, Value};
+
+/// The largest integer that every `f64` up to it can represent exactly.
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+/// The [FNV-1a] offset basis for 64-bit hashes.
+///
+/// [FNV-1a]: http://www.isthe.com/chongo/tech/comp/fnv/index.html
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// The [FNV-1a] prime for 64-bit hashes.
+///
+/// [FNV-1a]: http://www.isthe.com/chongo/tech/comp/fnv/index.html
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Serializes `value` as canonical JSON.
+#[inline]
+pub fn to_string<T: ?Sized + Serialize>(value: &T) -> serde_json::Result<String> {
+    serde_json::to_string(&to_value(value)?)
+}
+
+/// Converts `value` into the canonical form of a [`Value`].
+#[inline]
+pub fn to_value<T: ?Sized + Serialize>(value: &T) -> serde_json::Result<Value> {
+    Ok(canonicalize(serde_json::to_value(value)?))
+}
+
+/// Returns a 64-bit hash of the canonical JSON of `value`.
+///
+/// Unlike [`std::hash::Hash`], this is stable across processes, platforms and
+/// versions of Rust, so it can be shared, e.g. as the key of an external cache.
+///
+/// **Note**: this is *not* a cryptographic hash.
+pub fn fingerprint<T: ?Sized + Serialize>(value: &T) -> serde_json::Result<u64> {
+    let mut hasher = Fnv(FNV_OFFSET_BASIS);
+    serde_json::to_writer(&mut hasher, &to_value(value)?)?;
+    Ok(hasher.0)
+}
+
+/// An [FNV-1a] hasher that hashes everything written to it.
+///
+/// [FNV-1a]: http://www.isthe.com/chongo/tech/comp/fnv/index.html
+struct Fnv(u64);
+
+impl io::Write for Fnv {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(FNV_PRIME);
+        }
+        Ok(bytes.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Recursively sorts the keys of objects and normalizes the numbers in
+/// `value`.
+fn canonicalize(value: Value) -> Value {
+    match value {
+        Value::Number(number) => Value::Number(normalize(number)),
+        Value::Array(values) => Value::Array(values.into_iter().map(canonicalize).collect()),
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, canonicalize(value)))
+                    .collect::<Map<_, _>>(),
+            )
+        }
+        value => value,
+    }
+}
+
+/// Writes floats without a fractional part as integers, as long as they can
+/// be represented exactly.
+fn normalize(number: Number) -> Number {
+    match number.as_f64() {
+        Some(n) if number.is_f64() && n.fract() == 0.0 && n.abs() <= MAX_SAFE_INTEGER => {
+            Number::from(n as i64)
+        }
+        _ => number,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashMap;
+
+    use serde_json::json;
+
+    #[test]
+    fn sorts_keys() {
+        let value = json!({ "b": 1, "a": { "d": [{ "f": 1, "e": 2 }], "c": 3 } });
+        assert_eq!(
+            to_string(&value).unwrap(),
+            r#"{"a":{"c":3,"d":[{"e":2,"f":1}]},"b":1}"#
+        );
+    }
+
+    #[test]
+    fn is_independent_of_insertion_order() {
+        let a: HashMap<_, _> = (0..32).map(|i| (i.to_string(), i)).collect();
+        let b: HashMap<_, _> = (0..32).rev().map(|i| (i.to_string(), i)).collect();
+        assert_eq!(to_string(&a).unwrap(), to_string(&b).unwrap());
+    }
+
+    #[test]
+    fn fingerprints_canonical_json() {
+        assert_eq!(
+            fingerprint(&json!({ "a": 1.0, "b": [true] })).unwrap(),
+            fingerprint(&json!({ "b": [true], "a": 1 })).unwrap()
+        );
+        assert_ne!(
+            fingerprint(&json!({ "a": 1 })).unwrap(),
+            fingerprint(&json!({ "a": 2 })).unwrap()
+        );
+
+        // the FNV-1a hash of `null`
+        assert_eq!(fingerprint(&()).unwrap(), 0x5b9b_c4ba_5281_08e4);
+    }
+
+    #[test]
+    fn normalizes_numbers() {
+        let value = json!([2.0, -0.0, 2.5, 1e300, -3, u64::MAX]);
+        assert_eq!(
+            to_string(&value).unwrap(),
+            format!("[2,0,2.5,1e+300,-3,{}]", u64::MAX)
+        );
+    }
+}
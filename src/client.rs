@@ -0,0 +1,109 @@
+//! Adapters for the official [`elasticsearch`] client.
+//!
+//! Requests can be passed to the client as bodies directly, including where an
+//! [`elasticsearch::http::request::Body`] is expected, e.g. as a line of an
+//! `NdBody`, and responses can be parsed from the client's response:
+//!
+//! ```no_run
+//! use elasticsearch::{Elasticsearch, SearchParts};
+//! use elastiql::search::{query::TermQuery, Request, Response};
+//!
+//! # async fn search() -> Result<(), elasticsearch::Error> {
+//! let client = Elasticsearch::default();
+//! let request = Request::builder().query(TermQuery::new("id", "1")).build();
+//!
+//! let response = client.search(SearchParts::None).body(request).send().await?;
+//! let response = Response::<serde_json::Value>::from_client_response(response).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use bytes::{BufMut, BytesMut};
+use elasticsearch::http::{request::Body, response::Response as ClientResponse};
+use serde::de::DeserializeOwned;
+
+use crate::{aggregation, bulk, esql, search};
+
+/// Implements [`Body`] for each of the given request types by writing them as
+/// JSON.
+macro_rules! impl_body {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl Body for $ty {
+                #[inline]
+                fn write(&self, bytes: &mut BytesMut) -> Result<(), elasticsearch::Error> {
+                    serde_json::to_writer(bytes.writer(), self)?;
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+impl_body!(search::Request, aggregation::SearchRequest, esql::Request);
+
+impl<T: DeserializeOwned> search::Response<T> {
+    /// Parses the response to a search request sent with the official client.
+    #[inline]
+    pub async fn from_client_response(
+        response: ClientResponse,
+    ) -> Result<Self, elasticsearch::Error> {
+        response.json().await
+    }
+}
+
+impl<T: DeserializeOwned> bulk::Response<T> {
+    /// Parses the response to a bulk request sent with the official client.
+    #[inline]
+    pub async fn from_client_response(
+        response: ClientResponse,
+    ) -> Result<Self, elasticsearch::Error> {
+        response.json().await
+    }
+}
+
+impl aggregation::Response {
+    /// Parses the response to an aggregation request sent with the official
+    /// client.
+    #[inline]
+    pub async fn from_client_response(
+        response: ClientResponse,
+    ) -> Result<Self, elasticsearch::Error> {
+        response.json().await
+    }
+}
+
+impl esql::Response {
+    /// Parses the response to an ES|QL query sent with the official client.
+    #[inline]
+    pub async fn from_client_response(
+        response: ClientResponse,
+    ) -> Result<Self, elasticsearch::Error> {
+        response.json().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use elasticsearch::http::request::NdBody;
+
+    use crate::search::query::TermQuery;
+
+    #[test]
+    fn can_write_requests_as_bodies() {
+        let request = search::Request {
+            query: TermQuery::new("id", "1").into(),
+            ..search::Request::builder().build()
+        };
+
+        let mut bytes = BytesMut::new();
+        NdBody::new(vec![&request, &request])
+            .write(&mut bytes)
+            .unwrap();
+
+        let line = serde_json::to_string(&request).unwrap();
+        assert_eq!(&bytes[..], format!("{}\n{}\n", line, line).as_bytes());
+    }
+}
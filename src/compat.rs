@@ -0,0 +1,248 @@
+//! Serializing requests for a specific major version of [Elasticsearch], or
+//! for [OpenSearch].
+//!
+//! By default requests are serialized for Elasticsearch 7.x. To talk to
+//! clusters running a different major version (or OpenSearch), wrap the
+//! request in a [`Compat`], e.g.:
+//!
+//! ```
+//! use elastiql::compat::CompatVersion;
+//! use elastiql::search::Request;
+//!
+//! let request = Request::builder().track_total_hits(100u64).build();
+//!
+//! let body = CompatVersion::V6.to_value(&request).unwrap();
+//! assert!(body.get("track_total_hits").is_none());
+//! ```
+//!
+//! [Elasticsearch]: https://www.elastic.co/guide/en/elasticsearch/reference/current/index.html
+//! [OpenSearch]: https://opensearch.org/docs/latest/
+
+use std::cell::Cell;
+
+use serde::{Serialize, Serializer};
+use serde_json::Value as JsonValue;
+
+thread_local! {
+    /// The version that is currently being serialized for.
+    static VERSION: Cell<CompatVersion> = Cell::new(CompatVersion::default());
+}
+
+/// A major version of Elasticsearch (or OpenSearch) to serialize requests for.
+///
+/// The differences that are accounted for are:
+///
+/// * `6.x` only supports the (deprecated) `interval` of a `date_histogram`
+///   aggregation instead of a `calendar_interval` or `fixed_interval`
+/// * `6.x` doesn't support the `track_total_hits` search request parameter
+/// * OpenSearch uses its own syntax for the `knn` query, keyed by the vector
+///   field and with a `vector` instead of a `query_vector`
+///
+/// OpenSearch forked from Elasticsearch `7.10` and is thus ordered between
+/// `V7` and `V8`, e.g. it supports everything that was added in `7.x`.
+///
+/// Responses are deserialized the same regardless of the version, e.g. the
+/// total number of hits may be either a number (`6.x`) or an object (`7.x+`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CompatVersion {
+    /// Elasticsearch `6.x`.
+    V6,
+
+    /// Elasticsearch `7.x`.
+    V7,
+
+    /// OpenSearch `1.x` and `2.x`.
+    OpenSearch,
+
+    /// Elasticsearch `8.x`.
+    V8,
+}
+
+impl Default for CompatVersion {
+    /// Returns the "default value" for a `CompatVersion`, which is `V7`.
+    #[inline]
+    fn default() -> Self {
+        CompatVersion::V7
+    }
+}
+
+impl CompatVersion {
+    /// Returns the version that is currently being serialized for, which is
+    /// the default unless called while serializing a [`Compat`].
+    #[inline]
+    pub fn current() -> CompatVersion {
+        VERSION.with(Cell::get)
+    }
+
+    /// Serializes `value` as a JSON value for this version.
+    #[inline]
+    pub fn to_value<T: Serialize + ?Sized>(self, value: &T) -> serde_json::Result<JsonValue> {
+        serde_json::to_value(Compat::new(self, value))
+    }
+
+    /// Serializes `value` as a JSON string for this version.
+    #[inline]
+    pub fn to_string<T: Serialize + ?Sized>(self, value: &T) -> serde_json::Result<String> {
+        serde_json::to_string(&Compat::new(self, value))
+    }
+
+    /// Serializes `value` as a JSON byte vector for this version.
+    #[inline]
+    pub fn to_vec<T: Serialize + ?Sized>(self, value: &T) -> serde_json::Result<Vec<u8>> {
+        serde_json::to_vec(&Compat::new(self, value))
+    }
+}
+
+/// Serializes the wrapped value for a specific [`CompatVersion`].
+#[derive(Clone, Copy, Debug)]
+pub struct Compat<'a, T: ?Sized> {
+    /// The version to serialize for.
+    version: CompatVersion,
+
+    /// The value to serialize.
+    value: &'a T,
+}
+
+impl<'a, T: ?Sized> Compat<'a, T> {
+    /// Constructs a new `Compat` that serializes `value` for `version`.
+    #[inline]
+    pub fn new(version: CompatVersion, value: &'a T) -> Self {
+        Compat { version, value }
+    }
+}
+
+impl<T: Serialize + ?Sized> Serialize for Compat<'_, T> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        /// Restores the previous version when dropped, even when panicking.
+        struct Reset(CompatVersion);
+
+        impl Drop for Reset {
+            #[inline]
+            fn drop(&mut self) {
+                VERSION.with(|version| version.set(self.0));
+            }
+        }
+
+        let _reset = Reset(VERSION.with(|version| version.replace(self.version)));
+        self.value.serialize(serializer)
+    }
+}
+
+/// Returns `true` if `value` is `None` or unsupported by the current version,
+/// i.e. if it was added in Elasticsearch `7.x`.
+#[inline]
+pub(crate) fn is_none_or_before_v7<T>(value: &Option<T>) -> bool {
+    value.is_none() || CompatVersion::current() < CompatVersion::V7
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::aggregation::types::{CalendarInterval, DateHistogramAggregation};
+    use crate::search::{
+        query::{KnnQuery, Query},
+        Request,
+    };
+
+    fn date_histogram(
+        calendar_interval: Option<CalendarInterval>,
+        fixed_interval: Option<&str>,
+    ) -> DateHistogramAggregation {
+        DateHistogramAggregation {
+            field: "date".to_string(),
+            calendar_interval,
+            fixed_interval: fixed_interval.map(ToString::to_string),
+            time_zone: None,
+            offset: None,
+            format: None,
+            missing: None,
+            min_doc_count: None,
+            order: None,
+            keyed: None,
+            extended_bounds: None,
+            hard_bounds: None,
+        }
+    }
+
+    macro_rules! test_case {
+        ($name:ident : $f:expr, $v6:expr, $v7:expr, $v8:expr, $opensearch:expr) => {
+            mod $name {
+                use super::*;
+
+                #[test]
+                fn can_serialize_for_v6() {
+                    assert_eq!(CompatVersion::V6.to_value(&$f).unwrap(), $v6);
+                }
+
+                #[test]
+                fn can_serialize_for_v7() {
+                    assert_eq!(CompatVersion::V7.to_value(&$f).unwrap(), $v7);
+                    assert_eq!(serde_json::to_value(&$f).unwrap(), $v7);
+                }
+
+                #[test]
+                fn can_serialize_for_v8() {
+                    assert_eq!(CompatVersion::V8.to_value(&$f).unwrap(), $v8);
+                }
+
+                #[test]
+                fn can_serialize_for_opensearch() {
+                    assert_eq!(
+                        CompatVersion::OpenSearch.to_value(&$f).unwrap(),
+                        $opensearch
+                    );
+                }
+            }
+        };
+    }
+
+    test_case!(
+        calendar_interval:
+        date_histogram(Some(CalendarInterval::Month), None),
+        json!({ "field": "date", "interval": "month" }),
+        json!({ "field": "date", "calendar_interval": "month" }),
+        json!({ "field": "date", "calendar_interval": "month" }),
+        json!({ "field": "date", "calendar_interval": "month" })
+    );
+
+    test_case!(
+        fixed_interval:
+        date_histogram(None, Some("90m")),
+        json!({ "field": "date", "interval": "90m" }),
+        json!({ "field": "date", "fixed_interval": "90m" }),
+        json!({ "field": "date", "fixed_interval": "90m" }),
+        json!({ "field": "date", "fixed_interval": "90m" })
+    );
+
+    test_case!(
+        track_total_hits:
+        Request::builder().track_total_hits(10u64).build(),
+        json!({ "terminate_after": 0, "version": false, "seq_no_primary_term": false }),
+        json!({ "terminate_after": 0, "version": false, "seq_no_primary_term": false, "track_total_hits": 10 }),
+        json!({ "terminate_after": 0, "version": false, "seq_no_primary_term": false, "track_total_hits": 10 }),
+        json!({ "terminate_after": 0, "version": false, "seq_no_primary_term": false, "track_total_hits": 10 })
+    );
+
+    test_case!(
+        knn:
+        Query::from(KnnQuery::new("vector", vec![1.0], 2)),
+        json!({ "knn": { "field": "vector", "query_vector": [1.0], "k": 2 } }),
+        json!({ "knn": { "field": "vector", "query_vector": [1.0], "k": 2 } }),
+        json!({ "knn": { "field": "vector", "query_vector": [1.0], "k": 2 } }),
+        json!({ "knn": { "vector": { "vector": [1.0], "k": 2 } } })
+    );
+
+    #[test]
+    fn restores_the_previous_version() {
+        assert_eq!(CompatVersion::current(), CompatVersion::V7);
+
+        let nested = vec![Compat::new(CompatVersion::V8, &1)];
+        CompatVersion::V6.to_value(&nested).unwrap();
+
+        assert_eq!(CompatVersion::current(), CompatVersion::V7);
+    }
+}
@@ -0,0 +1,179 @@
+//! Request and response types for the [ES|QL] `_query` API.
+//!
+//! ```
+//! use elastiql::{esql, search::dsl};
+//!
+//! let request = esql::Request::builder()
+//!     .query("FROM logs | WHERE status == ? | STATS count = COUNT(*) BY host")
+//!     .filter(dsl::range("@timestamp").gte("now-1d"))
+//!     .params(vec!["error".into()])
+//!     .build();
+//! ```
+//!
+//! [ES|QL]: https://www.elastic.co/guide/en/elasticsearch/reference/current/esql.html
+
+use serde::{Deserialize, Serialize};
+
+use crate::{scalars::SortedValue, search::query::CompoundQuery};
+
+/// The [request body] for an ES|QL query.
+///
+/// [request body]: https://www.elastic.co/guide/en/elasticsearch/reference/current/esql-query-api.html#esql-query-api-request-body
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct Request {
+    /// The ES|QL query to run, e.g. `FROM logs | LIMIT 10`.
+    pub query: String,
+
+    /// A query to filter the documents the ES|QL query runs on.
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "CompoundQuery::is_empty")]
+    pub filter: CompoundQuery,
+
+    /// Values for the `?` placeholders in the `query`, in order.
+    ///
+    /// **Note**: named placeholders, e.g. `?status`, take a single object per
+    /// name instead, e.g. `{ "status": "error" }`.
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub params: Vec<SortedValue>,
+
+    /// Whether to return the `values` of the response by column instead of by
+    /// row. (Defaults to `false`.)
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default)]
+    pub columnar: bool,
+
+    /// The locale to format dates and numbers with, e.g. `en-US`.
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+}
+
+impl Request {
+    /// Constructs a new `Request` for `query`.
+    #[inline]
+    pub fn new(query: impl Into<String>) -> Self {
+        Request {
+            query: query.into(),
+            filter: CompoundQuery::default(),
+            params: vec![],
+            columnar: false,
+            locale: None,
+        }
+    }
+}
+
+/// A column of an ES|QL [`Response`].
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Column {
+    /// The name of the column, e.g. `host` or `count`.
+    pub name: String,
+
+    /// The Elasticsearch type of the values of the column, e.g. `keyword` or
+    /// `long`.
+    #[serde(rename = "type")]
+    pub ty: String,
+}
+
+/// The response for performing an ES|QL query.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct Response {
+    /// Time it took for the database to process the request, if returned.
+    #[serde(default)]
+    pub took: Option<u64>,
+
+    /// The columns of the results, in order.
+    pub columns: Vec<Column>,
+
+    /// The values of the results by row, or by column if the request was
+    /// `columnar`.
+    pub values: Vec<Vec<SortedValue>>,
+}
+
+impl Response {
+    /// Returns the index of the column named `name`.
+    #[inline]
+    pub fn column(&self, name: &str) -> Option<usize> {
+        self.columns.iter().position(|column| column.name == name)
+    }
+
+    /// Returns the value of the column named `name` in the row at `row`.
+    ///
+    /// **Note**: this assumes the values are by row, i.e. the request wasn't
+    /// `columnar`.
+    #[inline]
+    pub fn get(&self, row: usize, name: &str) -> Option<&SortedValue> {
+        self.values.get(row)?.get(self.column(name)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::search::query::TermQuery;
+
+    #[test]
+    fn can_serialize_request() {
+        let request = Request {
+            filter: TermQuery::new("status", "error").into(),
+            params: vec![SortedValue::Int(10), "web".into()],
+            ..Request::new("FROM logs | WHERE bytes > ? AND host == ? | LIMIT 10")
+        };
+
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({
+                "query": "FROM logs | WHERE bytes > ? AND host == ? | LIMIT 10",
+                "filter": { "bool": { "filter": [{ "term": { "status": { "value": "error" } } }] } },
+                "params": [10, "web"],
+                "columnar": false
+            })
+        );
+    }
+
+    #[test]
+    fn can_round_trip_request() {
+        let request = Request::new("FROM logs");
+
+        let j = serde_json::to_value(&request).unwrap();
+        assert_eq!(j, json!({ "query": "FROM logs", "columnar": false }));
+        assert_eq!(serde_json::from_value::<Request>(j).unwrap(), request);
+    }
+
+    #[test]
+    fn can_deserialize_response() {
+        let response: Response = serde_json::from_value(json!({
+            "took": 28,
+            "columns": [
+                { "name": "count", "type": "long" },
+                { "name": "host", "type": "keyword" }
+            ],
+            "values": [
+                [42, "web-1"],
+                [7, null]
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(response.took, Some(28));
+        assert_eq!(
+            response.columns[1],
+            Column {
+                name: "host".to_string(),
+                ty: "keyword".to_string(),
+            }
+        );
+        assert_eq!(response.column("host"), Some(1));
+        assert_eq!(response.get(0, "count"), Some(&SortedValue::Int(42)));
+        assert_eq!(response.get(0, "host"), Some(&"web-1".into()));
+        assert_eq!(response.get(1, "host"), Some(&SortedValue::Null));
+        assert_eq!(response.get(2, "host"), None);
+        assert_eq!(response.get(0, "missing"), None);
+    }
+}
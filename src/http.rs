@@ -0,0 +1,393 @@
+//! A minimal async HTTP client for running requests against Elasticsearch,
+//! for when the official [`elasticsearch`] crate is more than is needed.
+//!
+//! ```no_run
+//! use elastiql::{http::ElastiqlClient, search::{query::TermQuery, Request, Response}};
+//!
+//! # async fn search() -> Result<(), elastiql::http::Error> {
+//! let client = ElastiqlClient::new("http://localhost:9200");
+//! let request = Request::builder().query(TermQuery::new("id", "1")).build();
+//!
+//! let response: Response<serde_json::Value> = client.search("logs", &request).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`elasticsearch`]: https://crates.io/crates/elasticsearch
+
+use std::fmt;
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    aggregation, bulk,
+    search::{self, query::CompoundQuery},
+};
+
+/// A tiny client for the search, aggregation, bulk and count APIs of an Elasticsearch
+/// cluster.
+#[derive(Clone, Debug)]
+pub struct ElastiqlClient {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl ElastiqlClient {
+    /// Constructs a new `ElastiqlClient` for the cluster at `url`, e.g.
+    /// `http://localhost:9200`.
+    #[inline]
+    pub fn new(url: impl Into<String>) -> Self {
+        ElastiqlClient::with_client(reqwest::Client::new(), url)
+    }
+
+    /// Constructs a new `ElastiqlClient` for the cluster at `url` that sends
+    /// requests with `client`, e.g. to configure timeouts or authentication.
+    #[inline]
+    pub fn with_client(client: reqwest::Client, url: impl Into<String>) -> Self {
+        let mut url = url.into();
+        url.truncate(url.trim_end_matches('/').len());
+
+        ElastiqlClient { client, url }
+    }
+
+    /// Runs a [search] `request` against `index`, which may also be a comma
+    /// separated list of indices, an alias or a pattern.
+    ///
+    /// Errors returned by Elasticsearch are returned as
+    /// [`search::Response::Err`].
+    ///
+    /// [search]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-search.html
+    pub async fn search<T: DeserializeOwned>(
+        &self,
+        index: &str,
+        request: &search::Request,
+    ) -> Result<search::Response<T>, Error> {
+        let response = self
+            .client
+            .post(format!("{}/{}/_search", self.url, index))
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(serde_json::to_vec(request)?)
+            .send()
+            .await?;
+
+        Ok(serde_json::from_slice(&response.bytes().await?)?)
+    }
+
+    /// Runs an aggregation-only search `request` against `index`, i.e. one
+    /// that only returns the results of its aggregations.
+    pub async fn aggregate(
+        &self,
+        index: &str,
+        request: &aggregation::SearchRequest,
+    ) -> Result<aggregation::Response, Error> {
+        let response = self
+            .client
+            .post(format!("{}/{}/_search", self.url, index))
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(serde_json::to_vec(request)?)
+            .send()
+            .await?;
+
+        parse(response).await
+    }
+
+    /// Performs the [bulk] operations in `lines`, i.e. each action followed
+    /// by its source document (if any), against `index`.
+    ///
+    /// [bulk]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-bulk.html
+    pub async fn bulk<T, L>(
+        &self,
+        index: &str,
+        lines: impl IntoIterator<Item = L>,
+    ) -> Result<bulk::Response<T>, Error>
+    where
+        T: DeserializeOwned,
+        L: Serialize,
+    {
+        let body = bulk::to_ndjson(lines)?;
+
+        let response = self
+            .client
+            .post(format!("{}/{}/_bulk", self.url, index))
+            .header(reqwest::header::CONTENT_TYPE, "application/x-ndjson")
+            .body(body)
+            .send()
+            .await?;
+
+        parse(response).await
+    }
+
+    /// [Counts] the documents in `index` that match `query`.
+    ///
+    /// [Counts]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-count.html
+    pub async fn count(&self, index: &str, query: &CompoundQuery) -> Result<u64, Error> {
+        #[derive(Serialize)]
+        struct Request<'a> {
+            #[serde(skip_serializing_if = "CompoundQuery::is_empty")]
+            query: &'a CompoundQuery,
+        }
+
+        #[derive(Deserialize)]
+        struct Response {
+            count: u64,
+        }
+
+        let response = self
+            .client
+            .post(format!("{}/{}/_count", self.url, index))
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(serde_json::to_vec(&Request { query })?)
+            .send()
+            .await?;
+
+        parse::<Response>(response).await.map(|r| r.count)
+    }
+}
+
+/// Parses the body of a successful `response`.
+async fn parse<T: DeserializeOwned>(response: reqwest::Response) -> Result<T, Error> {
+    let status = response.status();
+    let body = response.bytes().await?;
+
+    if !status.is_success() {
+        return Err(Error::Status {
+            status: status.as_u16(),
+            body: String::from_utf8_lossy(&body).into_owned(),
+        });
+    }
+
+    Ok(serde_json::from_slice(&body)?)
+}
+
+/// An error from sending a request with an [`ElastiqlClient`].
+#[derive(Debug)]
+pub enum Error {
+    /// The request couldn't be sent or the response couldn't be read.
+    Http(reqwest::Error),
+
+    /// The request couldn't be serialized or the response couldn't be
+    /// deserialized.
+    Json(serde_json::Error),
+
+    /// Elasticsearch responded with an unsuccessful status.
+    Status {
+        /// The [HTTP status code](https://developer.mozilla.org/en-US/docs/Web/HTTP/Status).
+        status: u16,
+
+        /// The body of the response.
+        body: String,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Http(err) => write!(f, "HTTP error: {}", err),
+            Error::Json(err) => write!(f, "JSON error: {}", err),
+            Error::Status { status, body } => {
+                write!(f, "Elasticsearch responded with {}: {}", status, body)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Http(err) => Some(err),
+            Error::Json(err) => Some(err),
+            Error::Status { .. } => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    #[inline]
+    fn from(err: reqwest::Error) -> Self {
+        Error::Http(err)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    #[inline]
+    fn from(err: serde_json::Error) -> Self {
+        Error::Json(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    use serde_json::json;
+
+    use crate::search::query::TermQuery;
+
+    /// Serves a single HTTP request, responding with `status` and `body`, and
+    /// returns the URL it is served at along with a handle to the request line
+    /// and body that were received.
+    fn serve(
+        status: u16,
+        body: serde_json::Value,
+    ) -> (String, thread::JoinHandle<(String, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+
+            let mut content_length = 0;
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                if header.trim().is_empty() {
+                    break;
+                }
+                let (name, value) = header.split_at(header.find(':').unwrap());
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value[1..].trim().parse().unwrap();
+                }
+            }
+
+            let mut request_body = vec![0; content_length];
+            reader.read_exact(&mut request_body).unwrap();
+
+            let body = body.to_string();
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 {} OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+
+            (
+                request_line.trim().to_string(),
+                String::from_utf8(request_body).unwrap(),
+            )
+        });
+
+        (url, handle)
+    }
+
+    #[tokio::test]
+    async fn can_search() {
+        let (url, handle) = serve(
+            200,
+            json!({
+                "took": 1,
+                "timed_out": false,
+                "hits": { "total": { "relation": "eq", "value": 0 }, "hits": [] }
+            }),
+        );
+        let request = search::Request {
+            query: TermQuery::new("id", "1").into(),
+            ..search::Request::builder().build()
+        };
+
+        let response = ElastiqlClient::new(url)
+            .search::<serde_json::Value>("logs", &request)
+            .await
+            .unwrap();
+        assert!(matches!(response, search::Response::Ok(_)));
+
+        let (request_line, body) = handle.join().unwrap();
+        assert_eq!(request_line, "POST /logs/_search HTTP/1.1");
+        assert_eq!(body, serde_json::to_string(&request).unwrap());
+    }
+
+    #[tokio::test]
+    async fn can_aggregate() {
+        let (url, handle) = serve(
+            200,
+            json!({
+                "took": 1,
+                "timed_out": false,
+                "hits": { "total": { "relation": "eq", "value": 3 }, "hits": [] },
+                "aggregations": { "avg#avg_duration": { "value": 1.5 } }
+            }),
+        );
+        let request = aggregation::SearchRequest {
+            query: TermQuery::new("id", "1").into(),
+            ..Default::default()
+        };
+
+        let response = ElastiqlClient::new(url)
+            .aggregate("logs", &request)
+            .await
+            .unwrap();
+        assert_eq!(response.total_hits.map(|count| count.value), Some(3));
+        assert_eq!(response.aggregations[0].values, vec![1.5]);
+
+        let (request_line, body) = handle.join().unwrap();
+        assert_eq!(request_line, "POST /logs/_search HTTP/1.1");
+        assert_eq!(body, serde_json::to_string(&request).unwrap());
+    }
+
+    #[tokio::test]
+    async fn can_bulk() {
+        let (url, handle) = serve(
+            200,
+            json!({
+                "took": 1,
+                "errors": false,
+                "items": [{ "index": { "_id": "1", "_index": "logs", "status": 201 } }]
+            }),
+        );
+
+        let response = ElastiqlClient::new(url)
+            .bulk::<serde_json::Value, _>(
+                "logs",
+                vec![
+                    json!({ "index": { "_id": "1" } }),
+                    json!({ "message": "a" }),
+                ],
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.items.len(), 1);
+
+        let (request_line, body) = handle.join().unwrap();
+        assert_eq!(request_line, "POST /logs/_bulk HTTP/1.1");
+        assert_eq!(body, "{\"index\":{\"_id\":\"1\"}}\n{\"message\":\"a\"}\n");
+    }
+
+    #[tokio::test]
+    async fn can_count() {
+        let (url, handle) = serve(200, json!({ "count": 42, "_shards": {} }));
+
+        let count = ElastiqlClient::new(url)
+            .count("logs", &CompoundQuery::default())
+            .await
+            .unwrap();
+        assert_eq!(count, 42);
+
+        let (request_line, body) = handle.join().unwrap();
+        assert_eq!(request_line, "POST /logs/_count HTTP/1.1");
+        assert_eq!(body, "{}");
+    }
+
+    #[tokio::test]
+    async fn returns_unsuccessful_statuses() {
+        let (url, handle) = serve(404, json!({ "error": "no such index", "status": 404 }));
+
+        let err = ElastiqlClient::new(url)
+            .count("missing", &CompoundQuery::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Status { status: 404, .. }));
+
+        handle.join().unwrap();
+    }
+}
@@ -0,0 +1,236 @@
+//! Types for the [aliases API], which atomically adds and removes aliases.
+//!
+//! [aliases API]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-aliases.html
+
+use serde::{Deserialize, Serialize};
+
+use crate::search::query::CompoundQuery;
+
+/// The options of an [alias], i.e. a secondary name for one or more indices.
+///
+/// [alias]: https://www.elastic.co/guide/en/elasticsearch/reference/current/aliases.html
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(default, setter(into))))]
+pub struct Alias {
+    /// Limits the documents the alias can access, i.e. a [filtered alias].
+    ///
+    /// [filtered alias]: https://www.elastic.co/guide/en/elasticsearch/reference/current/aliases.html#filter-alias
+    #[serde(default, skip_serializing_if = "CompoundQuery::is_empty")]
+    pub filter: CompoundQuery,
+
+    /// The routing value used for both indexing and searching through the
+    /// alias.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub routing: Option<String>,
+
+    /// The routing value used for indexing through the alias, instead of
+    /// `routing`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index_routing: Option<String>,
+
+    /// The routing value(s) used for searching through the alias, instead of
+    /// `routing`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search_routing: Option<String>,
+
+    /// Whether the index is the one documents are written to when the alias
+    /// points to more than one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_write_index: Option<bool>,
+
+    /// Whether the alias is hidden from wildcard expressions. (Defaults to
+    /// `false`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_hidden: Option<bool>,
+}
+
+/// The body of a request to the [aliases API], which performs all of its
+/// `actions` atomically.
+///
+/// ```
+/// use elastiql::{
+///     index::{Alias, AliasAction, AliasesRequest},
+///     search::query::{BooleanQuery, CompoundQuery, TermQuery},
+/// };
+///
+/// let request = AliasesRequest {
+///     actions: vec![
+///         AliasAction::remove("logs-2021.01", "logs-current"),
+///         AliasAction::add_with(
+///             "logs-2021.02",
+///             "logs-current",
+///             Alias {
+///                 filter: CompoundQuery {
+///                     boolean: Some(BooleanQuery {
+///                         filter: vec![TermQuery::new("tenant", "acme").into()],
+///                         ..BooleanQuery::default()
+///                     }),
+///                     root: None,
+///                 },
+///                 is_write_index: Some(true),
+///                 ..Alias::default()
+///             },
+///         ),
+///     ],
+/// };
+/// ```
+///
+/// [aliases API]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-aliases.html
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+pub struct AliasesRequest {
+    /// The actions to perform.
+    pub actions: Vec<AliasAction>,
+}
+
+impl_display_json!(AliasesRequest);
+
+/// An action of an [`AliasesRequest`].
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AliasAction {
+    /// Adds an alias to an index (or updates it, if it already exists).
+    Add(Box<AddAlias>),
+
+    /// Removes an alias from an index.
+    Remove(RemoveAlias),
+
+    /// Deletes an index (e.g. to replace it with an alias of the same name).
+    RemoveIndex(RemoveIndex),
+}
+
+impl AliasAction {
+    /// Adds the `alias` to the `index`.
+    #[inline]
+    pub fn add(index: impl Into<String>, alias: impl Into<String>) -> Self {
+        AliasAction::add_with(index, alias, Alias::default())
+    }
+
+    /// Adds the `alias` with `options`, e.g. a `filter`, to the `index`.
+    #[inline]
+    pub fn add_with(index: impl Into<String>, alias: impl Into<String>, options: Alias) -> Self {
+        AliasAction::Add(Box::new(AddAlias {
+            index: index.into(),
+            alias: alias.into(),
+            options,
+        }))
+    }
+
+    /// Removes the `alias` from the `index`.
+    #[inline]
+    pub fn remove(index: impl Into<String>, alias: impl Into<String>) -> Self {
+        AliasAction::Remove(RemoveAlias {
+            index: index.into(),
+            alias: alias.into(),
+            must_exist: None,
+        })
+    }
+
+    /// Deletes the `index`.
+    #[inline]
+    pub fn remove_index(index: impl Into<String>) -> Self {
+        AliasAction::RemoveIndex(RemoveIndex {
+            index: index.into(),
+        })
+    }
+}
+
+/// Adds an alias to an index.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AddAlias {
+    /// The index (or pattern of indices) to add the alias to.
+    pub index: String,
+
+    /// The name of the alias.
+    pub alias: String,
+
+    /// The options of the alias.
+    #[serde(flatten)]
+    pub options: Alias,
+}
+
+/// Removes an alias from an index.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RemoveAlias {
+    /// The index (or pattern of indices) to remove the alias from.
+    pub index: String,
+
+    /// The name (or pattern) of the alias.
+    pub alias: String,
+
+    /// Whether the request fails if the alias doesn't exist. (Defaults to
+    /// `false`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub must_exist: Option<bool>,
+}
+
+/// Deletes an index.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RemoveIndex {
+    /// The index to delete.
+    pub index: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::search::query::{BooleanQuery, TermQuery};
+
+    #[test]
+    fn can_serialize_actions() {
+        let request = AliasesRequest {
+            actions: vec![
+                AliasAction::remove("logs-1", "logs"),
+                AliasAction::add_with(
+                    "logs-2",
+                    "logs",
+                    Alias {
+                        filter: CompoundQuery {
+                            boolean: Some(BooleanQuery {
+                                filter: vec![TermQuery::new("tenant", "acme").into()],
+                                ..BooleanQuery::default()
+                            }),
+                            root: None,
+                        },
+                        routing: Some("1".to_string()),
+                        is_write_index: Some(true),
+                        ..Alias::default()
+                    },
+                ),
+                AliasAction::remove_index("logs"),
+            ],
+        };
+
+        let json = json!({
+            "actions": [
+                { "remove": { "index": "logs-1", "alias": "logs" } },
+                {
+                    "add": {
+                        "index": "logs-2",
+                        "alias": "logs",
+                        "filter": {
+                            "bool": { "filter": [{ "term": { "tenant": { "value": "acme" } } }] }
+                        },
+                        "routing": "1",
+                        "is_write_index": true
+                    }
+                },
+                { "remove_index": { "index": "logs" } }
+            ]
+        });
+        assert_eq!(serde_json::to_value(&request).unwrap(), json);
+        assert_eq!(
+            serde_json::from_value::<AliasesRequest>(json).unwrap(),
+            request
+        );
+    }
+}
@@ -0,0 +1,319 @@
+//! Types for the [create index API], which creates an index with its settings,
+//! mapping and aliases.
+//!
+//! [create index API]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-create-index.html
+
+use std::{collections::HashMap, error::Error, fmt};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::Alias;
+use crate::{
+    mapping::{analysis::Analysis, Mapping},
+    search::query::{Query, QueryKind, QueryVisitor, ValidationError, ValidationPolicy},
+};
+
+/// The body of a request to the [create index API].
+///
+/// ```
+/// use elastiql::{
+///     index::{Alias, CreateIndexRequest, IndexSettings},
+///     mapping::{KeywordProperty, Mapping, Property},
+///     search::query::{CompoundQuery, TermQuery, ValidationPolicy},
+/// };
+///
+/// let request = CreateIndexRequest::builder()
+///     .settings(IndexSettings {
+///         number_of_shards: Some(1),
+///         ..IndexSettings::default()
+///     })
+///     .mappings(Mapping {
+///         properties: vec![(
+///             "tenant".to_string(),
+///             Property::Keyword(KeywordProperty::default()),
+///         )]
+///         .into_iter()
+///         .collect(),
+///         ..Mapping::default()
+///     })
+///     .aliases(
+///         vec![(
+///             "acme".to_string(),
+///             Alias {
+///                 filter: CompoundQuery::from(TermQuery::new("tenant", "acme")),
+///                 ..Alias::default()
+///             },
+///         )]
+///         .into_iter()
+///         .collect::<std::collections::HashMap<_, _>>(),
+///     )
+///     .build();
+///
+/// assert!(request.validate(&ValidationPolicy::default()).is_ok());
+/// ```
+///
+/// [create index API]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-create-index.html
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(default, setter(into))))]
+pub struct CreateIndexRequest {
+    /// The settings of the index.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(setter(strip_option)))]
+    pub settings: Option<IndexSettings>,
+
+    /// The mapping of the index.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(setter(strip_option)))]
+    pub mappings: Option<Mapping>,
+
+    /// The aliases of the index, by name.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub aliases: HashMap<String, Alias>,
+}
+
+impl_display_json!(CreateIndexRequest);
+
+impl CreateIndexRequest {
+    /// Checks that the settings are sensible and that the `filter` of each
+    /// alias is made up of valid queries (i.e. each clause defines exactly one
+    /// kind of query) which satisfy `policy`.
+    pub fn validate(&self, policy: &ValidationPolicy) -> Result<(), CreateIndexError> {
+        if let Some(IndexSettings {
+            number_of_shards: Some(0),
+            ..
+        }) = self.settings
+        {
+            return Err(CreateIndexError::NoShards);
+        }
+
+        // validate the aliases in a stable order, so the same error is reported
+        // for the same request
+        let mut aliases: Vec<_> = self.aliases.iter().collect();
+        aliases.sort_unstable_by_key(|(name, _)| name.as_str());
+
+        for (name, alias) in aliases {
+            let mut clauses = InvalidClauses::default();
+            alias.filter.walk(&mut clauses);
+            if let Some(kinds) = clauses.0 {
+                return Err(CreateIndexError::InvalidClause {
+                    alias: name.clone(),
+                    kinds,
+                });
+            }
+
+            alias
+                .filter
+                .validate(policy)
+                .map_err(|error| CreateIndexError::InvalidFilter {
+                    alias: name.clone(),
+                    error,
+                })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Finds the first clause of a query that doesn't define exactly one kind of
+/// query.
+#[derive(Default)]
+struct InvalidClauses(Option<Vec<QueryKind>>);
+
+impl QueryVisitor for InvalidClauses {
+    #[inline]
+    fn visit_query(&mut self, query: &Query) {
+        if self.0.is_none() && query.kind().is_none() {
+            self.0 = Some(query.kinds());
+        }
+    }
+}
+
+/// The [settings] of an index.
+///
+/// Settings without a dedicated field are kept in `other`.
+///
+/// [settings]: https://www.elastic.co/guide/en/elasticsearch/reference/current/index-modules.html#index-modules-settings
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(default, setter(into))))]
+pub struct IndexSettings {
+    /// The number of primary shards, which can't be changed after the index is
+    /// created. (Defaults to `1`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub number_of_shards: Option<u32>,
+
+    /// The number of replicas of each primary shard. (Defaults to `1`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub number_of_replicas: Option<u32>,
+
+    /// How often to make recent changes visible to search, e.g. `1s`, or `-1`
+    /// to disable refreshing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_interval: Option<String>,
+
+    /// The custom analyzers (and their building blocks) of the index.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub analysis: Option<Analysis>,
+
+    /// Any other settings, e.g. `max_result_window`.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// The reason a [`CreateIndexRequest`] failed
+/// [`validate`](CreateIndexRequest::validate).
+#[derive(Clone, PartialEq, Debug)]
+pub enum CreateIndexError {
+    /// The index has `0` primary shards.
+    NoShards,
+    /// A clause of the `filter` of an alias doesn't define exactly one kind
+    /// of query.
+    InvalidClause {
+        /// The name of the alias.
+        alias: String,
+        /// The kinds of all of the defined fields of the invalid clause.
+        kinds: Vec<QueryKind>,
+    },
+    /// The `filter` of an alias doesn't satisfy the `ValidationPolicy`.
+    InvalidFilter {
+        /// The name of the alias.
+        alias: String,
+        /// The reason the filter is invalid.
+        error: ValidationError,
+    },
+}
+
+impl fmt::Display for CreateIndexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CreateIndexError::NoShards => f.write_str("index must have at least one shard"),
+            CreateIndexError::InvalidClause { alias, kinds } => write!(
+                f,
+                "filter of alias `{}` has a clause with {} kinds of query, expected exactly one",
+                alias,
+                kinds.len()
+            ),
+            CreateIndexError::InvalidFilter { alias, error } => {
+                write!(f, "invalid filter of alias `{}`: {}", alias, error)
+            }
+        }
+    }
+}
+
+impl Error for CreateIndexError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CreateIndexError::InvalidFilter { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::search::query::{BooleanQuery, CompoundQuery, TermQuery};
+
+    #[test]
+    fn can_serialize_request() {
+        let request: CreateIndexRequest = serde_json::from_value(json!({
+            "settings": {
+                "number_of_shards": 3,
+                "refresh_interval": "30s",
+                "max_result_window": 50000,
+                "analysis": {
+                    "analyzer": { "folded": { "tokenizer": "standard", "filter": ["asciifolding"] } }
+                }
+            },
+            "mappings": { "properties": { "tenant": { "type": "keyword" } } },
+            "aliases": {
+                "acme": { "filter": { "bool": { "filter": [{ "term": { "tenant": { "value": "acme" } } }] } } },
+                "all": {}
+            }
+        }))
+        .unwrap();
+
+        let settings = request.settings.as_ref().unwrap();
+        assert_eq!(settings.number_of_shards, Some(3));
+        assert_eq!(settings.other["max_result_window"], json!(50000));
+        assert!(settings
+            .analysis
+            .as_ref()
+            .unwrap()
+            .analyzer
+            .contains_key("folded"));
+        assert!(request.aliases["all"].filter.is_empty());
+
+        assert_eq!(
+            serde_json::to_value(&request).unwrap()["settings"],
+            json!({
+                "number_of_shards": 3,
+                "refresh_interval": "30s",
+                "max_result_window": 50000,
+                "analysis": {
+                    "analyzer": {
+                        "folded": { "type": "custom", "tokenizer": "standard", "filter": ["asciifolding"] }
+                    }
+                }
+            })
+        );
+        assert!(request.validate(&ValidationPolicy::default()).is_ok());
+    }
+
+    #[test]
+    fn validates_alias_filters() {
+        let alias = |filter: CompoundQuery| Alias {
+            filter,
+            ..Alias::default()
+        };
+
+        let mut request = CreateIndexRequest::default();
+        request.aliases.insert(
+            "empty".to_string(),
+            alias(CompoundQuery::from(BooleanQuery {
+                filter: vec![Query::default()],
+                ..BooleanQuery::default()
+            })),
+        );
+        assert_eq!(
+            request.validate(&ValidationPolicy::default()),
+            Err(CreateIndexError::InvalidClause {
+                alias: "empty".to_string(),
+                kinds: vec![],
+            })
+        );
+
+        request.aliases.insert(
+            "empty".to_string(),
+            alias(CompoundQuery::from(TermQuery::new("tenant", "acme"))),
+        );
+        let policy = ValidationPolicy {
+            denied: vec![QueryKind::Term],
+            ..ValidationPolicy::default()
+        };
+        assert_eq!(
+            request.validate(&policy),
+            Err(CreateIndexError::InvalidFilter {
+                alias: "empty".to_string(),
+                error: ValidationError::Denied(QueryKind::Term),
+            })
+        );
+
+        request.settings = Some(IndexSettings {
+            number_of_shards: Some(0),
+            ..IndexSettings::default()
+        });
+        assert_eq!(
+            request.validate(&ValidationPolicy::default()),
+            Err(CreateIndexError::NoShards)
+        );
+    }
+}
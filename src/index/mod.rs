@@ -0,0 +1,22 @@
+//! Request and response types for managing indices, e.g. [creating] them or
+//! their [aliases].
+//!
+//! [creating]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-create-index.html
+//! [aliases]: https://www.elastic.co/guide/en/elasticsearch/reference/current/aliases.html
+
+use serde::Deserialize;
+
+pub use alias::*;
+pub use create::*;
+
+mod alias;
+mod create;
+
+/// The response to a request that changes the cluster, e.g. updating
+/// aliases, once the change is acknowledged.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AcknowledgedResponse {
+    /// Whether the change was acknowledged by all of the nodes before the
+    /// request timed out.
+    pub acknowledged: bool,
+}
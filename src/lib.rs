@@ -5,7 +5,274 @@
 //!
 //! [Elasticsearch]: https://www.elastic.co/guide/en/elasticsearch/reference/current/index.html
 
+/// Adds `to_json_compact` and `to_json_pretty` methods to each of the given
+/// types and implements `Display` for them as their compact JSON
+/// representation, or as pretty-printed JSON with the alternate flag (`{:#}`).
+macro_rules! impl_display_json {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl $ty {
+                /// Serializes this as compact JSON, i.e. on a single line.
+                #[inline]
+                pub fn to_json_compact(&self) -> serde_json::Result<String> {
+                    serde_json::to_string(self)
+                }
+
+                /// Serializes this as pretty-printed JSON, e.g. for debugging.
+                #[inline]
+                pub fn to_json_pretty(&self) -> serde_json::Result<String> {
+                    serde_json::to_string_pretty(self)
+                }
+            }
+
+            impl std::fmt::Display for $ty {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    let json = if f.alternate() {
+                        self.to_json_pretty()
+                    } else {
+                        self.to_json_compact()
+                    };
+                    f.write_str(&json.map_err(|_| std::fmt::Error)?)
+                }
+            }
+        )*
+    };
+}
+
+/// Adds `to_canonical_json` and `fingerprint` methods to each of the given
+/// types.
+///
+/// See the [`canonical`] module.
+macro_rules! impl_canonical_json {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl $ty {
+                /// Serializes this as canonical JSON, i.e. with sorted keys and
+                /// normalized numbers, so that equal values always serialize
+                /// identically.
+                ///
+                /// See the [`canonical`](crate::canonical) module.
+                #[inline]
+                pub fn to_canonical_json(&self) -> serde_json::Result<String> {
+                    crate::canonical::to_string(self)
+                }
+
+                /// Returns a stable 64-bit hash of the canonical JSON of this,
+                /// e.g. for use as a cache key.
+                ///
+                /// See [`canonical::fingerprint`](crate::canonical::fingerprint).
+                #[inline]
+                pub fn fingerprint(&self) -> serde_json::Result<u64> {
+                    crate::canonical::fingerprint(self)
+                }
+            }
+        )*
+    };
+}
+
+/// Defines an "open" enum of string values, i.e. one with an `Other(String)`
+/// variant for the values that aren't (yet) known, e.g. those added by a newer
+/// version of Elasticsearch or by a plugin, along with the names Elasticsearch
+/// uses for each of its known variants.
+///
+/// The enum is (de)serialized as a string and exposed through GraphQL as an
+/// enum (named by the literal after `as`) of the known variants, named by the
+/// literal after their own `as`, e.g. `MAX`. The `Other` values are only
+/// (de)serialized; they are returned as `OTHER` and can't be used as inputs.
+macro_rules! open_enum {
+    (
+        $(#[$attr:meta])*
+        pub enum $ty:ident as $graphql_name:literal {
+            $($(#[doc = $doc:literal])* $variant:ident => $name:literal as $graphql_variant:literal,)+
+        }
+    ) => {
+        $(#[$attr])*
+        #[cfg_attr(feature = "graphql", derive(async_graphql::Description))]
+        #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+        pub enum $ty {
+            $($(#[doc = $doc])* $variant,)+
+
+            /// Any other value, e.g. one added by a newer version of
+            /// Elasticsearch or by a plugin.
+            Other(String),
+        }
+
+        impl $ty {
+            /// The name of this value as understood by Elasticsearch.
+            #[inline]
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $($ty::$variant => $name,)+
+                    $ty::Other(name) => name,
+                }
+            }
+        }
+
+        impl From<&str> for $ty {
+            #[inline]
+            fn from(name: &str) -> Self {
+                match name {
+                    $($name => $ty::$variant,)+
+                    name => $ty::Other(name.to_string()),
+                }
+            }
+        }
+
+        impl From<String> for $ty {
+            #[inline]
+            fn from(name: String) -> Self {
+                $ty::from(name.as_str())
+            }
+        }
+
+        impl std::fmt::Display for $ty {
+            #[inline]
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl serde::Serialize for $ty {
+            #[inline]
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $ty {
+            #[inline]
+            fn deserialize<D>(deserializer: D) -> Result<$ty, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                Ok($ty::from(<String as serde::Deserialize>::deserialize(deserializer)?))
+            }
+        }
+
+        #[cfg(feature = "schemars")]
+        impl schemars::JsonSchema for $ty {
+            #[inline]
+            fn schema_name() -> String {
+                stringify!($ty).to_string()
+            }
+
+            #[inline]
+            fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+                String::json_schema(gen)
+            }
+        }
+
+        /// Only the known variants are generated, since an arbitrary `Other`
+        /// value may well be the name of a known one.
+        #[cfg(feature = "arbitrary")]
+        impl<'a> arbitrary::Arbitrary<'a> for $ty {
+            #[inline]
+            fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+                Ok($ty::from(*u.choose(&[$($name),+])?))
+            }
+        }
+
+        #[cfg(feature = "graphql")]
+        impl async_graphql::Type for $ty {
+            #[inline]
+            fn type_name() -> std::borrow::Cow<'static, str> {
+                std::borrow::Cow::Borrowed($graphql_name)
+            }
+
+            fn create_type_info(registry: &mut async_graphql::registry::Registry) -> String {
+                registry.create_type::<Self, _>(|_| {
+                    let mut enum_values = async_graphql::indexmap::IndexMap::new();
+                    $(
+                        enum_values.insert($graphql_variant, async_graphql::registry::MetaEnumValue {
+                            name: $graphql_variant,
+                            description: Some(concat!($($doc),*).trim()),
+                            deprecation: Default::default(),
+                            visible: None,
+                        });
+                    )+
+                    enum_values.insert("OTHER", async_graphql::registry::MetaEnumValue {
+                        name: "OTHER",
+                        description: Some(
+                            "Any other value, e.g. one added by a newer version of Elasticsearch \
+                             or by a plugin. It can't be used as an input.",
+                        ),
+                        deprecation: Default::default(),
+                        visible: None,
+                    });
+
+                    async_graphql::registry::MetaType::Enum {
+                        name: $graphql_name.to_string(),
+                        description: Some(<Self as async_graphql::Description>::description()),
+                        enum_values,
+                        visible: None,
+                        rust_typename: std::any::type_name::<Self>(),
+                    }
+                })
+            }
+        }
+
+        #[cfg(feature = "graphql")]
+        impl async_graphql::InputType for $ty {
+            fn parse(value: Option<async_graphql::Value>) -> async_graphql::InputValueResult<Self> {
+                let value = value.unwrap_or_default();
+                let name = match value {
+                    async_graphql::Value::Enum(ref name) => name.as_str(),
+                    async_graphql::Value::String(ref name) => name.as_str(),
+                    _ => return Err(async_graphql::InputValueError::expected_type(value)),
+                };
+
+                match name {
+                    $($graphql_variant => Ok($ty::$variant),)+
+                    name => Err(async_graphql::InputValueError::custom(format!(
+                        r#"Enumeration type does not contain value "{}"."#,
+                        name
+                    ))),
+                }
+            }
+
+            #[inline]
+            fn to_value(&self) -> async_graphql::Value {
+                async_graphql::Value::Enum(async_graphql::Name::new(match self {
+                    $($ty::$variant => $graphql_variant,)+
+                    $ty::Other(_) => "OTHER",
+                }))
+            }
+        }
+
+        #[cfg(feature = "graphql")]
+        #[async_graphql::async_trait::async_trait]
+        impl async_graphql::OutputType for $ty {
+            #[inline]
+            async fn resolve(
+                &self,
+                _: &async_graphql::ContextSelectionSet<'_>,
+                _: &async_graphql::Positioned<async_graphql::parser::types::Field>,
+            ) -> async_graphql::ServerResult<async_graphql::Value> {
+                Ok(async_graphql::InputType::to_value(self))
+            }
+        }
+    };
+}
+
 pub mod aggregation;
 pub mod bulk;
+pub mod canonical;
+#[cfg(all(feature = "elasticsearch-client", not(target_arch = "wasm32")))]
+pub mod client;
+pub mod compat;
+pub mod esql;
+#[cfg(feature = "http")]
+pub mod http;
+pub mod index;
+pub mod mapping;
 pub mod scalars;
 pub mod search;
+pub mod snapshot;
+#[cfg(feature = "testing")]
+pub mod testing;
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+
+#[cfg(feature = "schemars")]
+mod schema;
@@ -0,0 +1,793 @@
+//! Definitions of custom [analyzers] and their building blocks, i.e. the
+//! `analysis` settings of an index.
+//!
+//! ```
+//! use elastiql::mapping::analysis::{Analysis, Analyzer, TokenFilter};
+//! use serde_json::json;
+//!
+//! let analysis: Analysis = serde_json::from_value(json!({
+//!     "analyzer": {
+//!         "autocomplete": {
+//!             "type": "custom",
+//!             "tokenizer": "standard",
+//!             "filter": ["lowercase", "autocomplete_filter"]
+//!         }
+//!     },
+//!     "filter": {
+//!         "autocomplete_filter": { "type": "edge_ngram", "min_gram": 1, "max_gram": 20 }
+//!     }
+//! }))
+//! .unwrap();
+//!
+//! assert!(matches!(analysis.analyzer["autocomplete"], Analyzer::Custom { .. }));
+//! assert!(matches!(
+//!     analysis.filter["autocomplete_filter"],
+//!     TokenFilter::EdgeNGram { min_gram: Some(1), max_gram: Some(20), .. }
+//! ));
+//! ```
+//!
+//! [analyzers]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis.html
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// The `analysis` settings of an index, i.e. its custom analyzers,
+/// normalizers, tokenizers, character filters and token filters, by name.
+///
+/// The names can be referenced from the `analyzer`, `search_analyzer`,
+/// `search_quote_analyzer` and `normalizer` parameters of properties and from
+/// the `analyzer` parameter of full text queries.
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(default, setter(into))))]
+pub struct Analysis {
+    /// The custom analyzers.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub analyzer: HashMap<String, Analyzer>,
+
+    /// The custom normalizers.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub normalizer: HashMap<String, Normalizer>,
+
+    /// The custom tokenizers.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub tokenizer: HashMap<String, Tokenizer>,
+
+    /// The custom character filters.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub char_filter: HashMap<String, CharFilter>,
+
+    /// The custom token filters.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub filter: HashMap<String, TokenFilter>,
+}
+
+impl_display_json!(Analysis);
+impl_canonical_json!(Analysis);
+
+/// The stop words to remove, either the name of a predefined list, e.g.
+/// `_english_` or `_none_`, or a list of words.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum Stopwords {
+    /// The name of a predefined list of stop words, e.g. `_english_`.
+    Predefined(String),
+
+    /// The stop words.
+    List(Vec<String>),
+}
+
+/// An [analyzer], which converts text into tokens with zero or more
+/// character filters, a tokenizer and zero or more token filters.
+///
+/// Analyzers of types that aren't defined here, e.g. language analyzers or
+/// those added by plugins, are kept as [`Analyzer::Other`].
+///
+/// [analyzer]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analyzer-anatomy.html
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(remote = "Self", tag = "type", rename_all = "snake_case")]
+pub enum Analyzer {
+    /// A [`custom`] analyzer built from the given building blocks.
+    ///
+    /// [`custom`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-custom-analyzer.html
+    Custom {
+        /// The name of the tokenizer.
+        tokenizer: String,
+
+        /// The names of the character filters, in order.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        char_filter: Vec<String>,
+
+        /// The names of the token filters, in order.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        filter: Vec<String>,
+
+        /// The gap between the positions of the values of an array.
+        /// (Defaults to `100`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        position_increment_gap: Option<u32>,
+    },
+
+    /// The [`standard`] analyzer, configured.
+    ///
+    /// [`standard`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-standard-analyzer.html
+    Standard {
+        /// The maximum length of a token. (Defaults to `255`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_token_length: Option<u32>,
+
+        /// The stop words to remove. (Defaults to `_none_`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        stopwords: Option<Stopwords>,
+    },
+
+    /// The [`simple`] analyzer, which splits on non-letters and lowercases.
+    ///
+    /// [`simple`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-simple-analyzer.html
+    Simple,
+
+    /// The [`whitespace`] analyzer, which splits on whitespace.
+    ///
+    /// [`whitespace`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-whitespace-analyzer.html
+    Whitespace,
+
+    /// The [`keyword`] analyzer, which keeps the whole text as a single token.
+    ///
+    /// [`keyword`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-keyword-analyzer.html
+    Keyword,
+
+    /// The [`stop`] analyzer, configured.
+    ///
+    /// [`stop`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-stop-analyzer.html
+    Stop {
+        /// The stop words to remove. (Defaults to `_english_`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        stopwords: Option<Stopwords>,
+
+        /// The path to a file of stop words.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        stopwords_path: Option<String>,
+    },
+
+    /// The [`pattern`] analyzer, configured.
+    ///
+    /// [`pattern`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-pattern-analyzer.html
+    Pattern {
+        /// The Java regular expression that separates tokens. (Defaults to
+        /// `\W+`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pattern: Option<String>,
+
+        /// The Java regular expression flags, separated by `|`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        flags: Option<String>,
+
+        /// Whether tokens are lowercased. (Defaults to `true`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        lowercase: Option<bool>,
+
+        /// The stop words to remove. (Defaults to `_none_`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        stopwords: Option<Stopwords>,
+    },
+
+    /// The [`fingerprint`] analyzer, configured.
+    ///
+    /// [`fingerprint`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-fingerprint-analyzer.html
+    Fingerprint {
+        /// The character used to join the tokens. (Defaults to a space.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        separator: Option<String>,
+
+        /// The maximum size of the fingerprint. (Defaults to `255`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_output_size: Option<u32>,
+
+        /// The stop words to remove. (Defaults to `_none_`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        stopwords: Option<Stopwords>,
+    },
+
+    /// An analyzer of any other type, e.g. `english`.
+    #[serde(skip)]
+    Other {
+        /// The type of the analyzer.
+        ty: String,
+
+        /// The parameters of the analyzer.
+        options: Map<String, Value>,
+    },
+}
+
+impl Analyzer {
+    /// The types of the analyzers that aren't deserialized as
+    /// [`Analyzer::Other`].
+    const TYPES: &'static [&'static str] = &[
+        "custom",
+        "standard",
+        "simple",
+        "whitespace",
+        "keyword",
+        "stop",
+        "pattern",
+        "fingerprint",
+    ];
+}
+
+impl_serde_with_other!(Analyzer, Some("custom"));
+
+/// A [normalizer], which is like an analyzer that only produces a single
+/// token, for `keyword` fields.
+///
+/// [normalizer]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-normalizers.html
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(remote = "Self", tag = "type", rename_all = "snake_case")]
+pub enum Normalizer {
+    /// A `custom` normalizer built from the given building blocks.
+    Custom {
+        /// The names of the character filters, in order.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        char_filter: Vec<String>,
+
+        /// The names of the token filters, in order.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        filter: Vec<String>,
+    },
+
+    /// A normalizer of any other type.
+    #[serde(skip)]
+    Other {
+        /// The type of the normalizer.
+        ty: String,
+
+        /// The parameters of the normalizer.
+        options: Map<String, Value>,
+    },
+}
+
+impl Normalizer {
+    /// The types of the normalizers that aren't deserialized as
+    /// [`Normalizer::Other`].
+    const TYPES: &'static [&'static str] = &["custom"];
+}
+
+impl_serde_with_other!(Normalizer, Some("custom"));
+
+/// A [tokenizer], which splits text into tokens.
+///
+/// Tokenizers of types that aren't defined here, e.g. those added by plugins,
+/// are kept as [`Tokenizer::Other`].
+///
+/// [tokenizer]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-tokenizers.html
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(remote = "Self", tag = "type", rename_all = "snake_case")]
+pub enum Tokenizer {
+    /// The [`standard`] tokenizer, which splits on word boundaries.
+    ///
+    /// [`standard`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-standard-tokenizer.html
+    Standard {
+        /// The maximum length of a token. (Defaults to `255`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_token_length: Option<u32>,
+    },
+
+    /// The [`whitespace`] tokenizer, which splits on whitespace.
+    ///
+    /// [`whitespace`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-whitespace-tokenizer.html
+    Whitespace {
+        /// The maximum length of a token. (Defaults to `255`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_token_length: Option<u32>,
+    },
+
+    /// The [`keyword`] tokenizer, which keeps the whole text as a single
+    /// token.
+    ///
+    /// [`keyword`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-keyword-tokenizer.html
+    Keyword {
+        /// The number of characters read into the term buffer at a time.
+        /// (Defaults to `256`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        buffer_size: Option<u32>,
+    },
+
+    /// The [`letter`] tokenizer, which splits on non-letters.
+    ///
+    /// [`letter`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-letter-tokenizer.html
+    Letter,
+
+    /// The [`lowercase`] tokenizer, which splits on non-letters and
+    /// lowercases.
+    ///
+    /// [`lowercase`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-lowercase-tokenizer.html
+    Lowercase,
+
+    /// The [`pattern`] tokenizer, which splits on (or captures) a regular
+    /// expression.
+    ///
+    /// [`pattern`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-pattern-tokenizer.html
+    Pattern {
+        /// The Java regular expression. (Defaults to `\W+`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pattern: Option<String>,
+
+        /// The Java regular expression flags, separated by `|`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        flags: Option<String>,
+
+        /// The capture group to extract as tokens, or `-1` to split on the
+        /// pattern. (Defaults to `-1`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        group: Option<i32>,
+    },
+
+    /// The [`ngram`] tokenizer, which emits the n-grams of each word.
+    ///
+    /// [`ngram`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-ngram-tokenizer.html
+    #[serde(rename = "ngram")]
+    NGram {
+        /// The minimum length of an n-gram. (Defaults to `1`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min_gram: Option<u32>,
+
+        /// The maximum length of an n-gram. (Defaults to `2`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_gram: Option<u32>,
+
+        /// The classes of characters to keep in tokens, e.g. `letter` or
+        /// `digit`. (Defaults to all of them.)
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        token_chars: Vec<String>,
+    },
+
+    /// The [`edge_ngram`] tokenizer, which emits the n-grams from the start
+    /// of each word.
+    ///
+    /// [`edge_ngram`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-edgengram-tokenizer.html
+    #[serde(rename = "edge_ngram")]
+    EdgeNGram {
+        /// The minimum length of an n-gram. (Defaults to `1`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min_gram: Option<u32>,
+
+        /// The maximum length of an n-gram. (Defaults to `2`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_gram: Option<u32>,
+
+        /// The classes of characters to keep in tokens, e.g. `letter` or
+        /// `digit`. (Defaults to all of them.)
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        token_chars: Vec<String>,
+    },
+
+    /// The [`path_hierarchy`] tokenizer, which emits each level of a path.
+    ///
+    /// [`path_hierarchy`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-pathhierarchy-tokenizer.html
+    PathHierarchy {
+        /// The path separator. (Defaults to `/`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        delimiter: Option<String>,
+
+        /// The character to replace the delimiter with.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        replacement: Option<String>,
+
+        /// Whether the levels are emitted from the end of the path.
+        /// (Defaults to `false`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reverse: Option<bool>,
+
+        /// The number of initial levels to skip. (Defaults to `0`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        skip: Option<u32>,
+    },
+
+    /// A tokenizer of any other type, e.g. one added by a plugin.
+    #[serde(skip)]
+    Other {
+        /// The type of the tokenizer.
+        ty: String,
+
+        /// The parameters of the tokenizer.
+        options: Map<String, Value>,
+    },
+}
+
+impl Tokenizer {
+    /// The types of the tokenizers that aren't deserialized as
+    /// [`Tokenizer::Other`].
+    const TYPES: &'static [&'static str] = &[
+        "standard",
+        "whitespace",
+        "keyword",
+        "letter",
+        "lowercase",
+        "pattern",
+        "ngram",
+        "edge_ngram",
+        "path_hierarchy",
+    ];
+}
+
+impl_serde_with_other!(Tokenizer, None);
+
+/// A [character filter], which transforms text before it is tokenized.
+///
+/// Character filters of types that aren't defined here are kept as
+/// [`CharFilter::Other`].
+///
+/// [character filter]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-charfilters.html
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(remote = "Self", tag = "type", rename_all = "snake_case")]
+pub enum CharFilter {
+    /// The [`html_strip`] character filter, which strips HTML elements.
+    ///
+    /// [`html_strip`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-htmlstrip-charfilter.html
+    HtmlStrip {
+        /// The HTML elements to keep.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        escaped_tags: Vec<String>,
+    },
+
+    /// The [`mapping`] character filter, which replaces strings with others.
+    ///
+    /// [`mapping`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-mapping-charfilter.html
+    Mapping {
+        /// The replacements, e.g. `:) => _happy_`.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        mappings: Vec<String>,
+
+        /// The path to a file of replacements.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        mappings_path: Option<String>,
+    },
+
+    /// The [`pattern_replace`] character filter, which replaces the matches
+    /// of a regular expression.
+    ///
+    /// [`pattern_replace`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-pattern-replace-charfilter.html
+    PatternReplace {
+        /// The Java regular expression.
+        pattern: String,
+
+        /// The replacement, which can refer to capture groups, e.g. `$1`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        replacement: Option<String>,
+
+        /// The Java regular expression flags, separated by `|`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        flags: Option<String>,
+    },
+
+    /// A character filter of any other type, e.g. one added by a plugin.
+    #[serde(skip)]
+    Other {
+        /// The type of the character filter.
+        ty: String,
+
+        /// The parameters of the character filter.
+        options: Map<String, Value>,
+    },
+}
+
+impl CharFilter {
+    /// The types of the character filters that aren't deserialized as
+    /// [`CharFilter::Other`].
+    const TYPES: &'static [&'static str] = &["html_strip", "mapping", "pattern_replace"];
+}
+
+impl_serde_with_other!(CharFilter, None);
+
+/// A [token filter], which adds, removes or changes tokens.
+///
+/// Token filters of types that aren't defined here are kept as
+/// [`TokenFilter::Other`].
+///
+/// [token filter]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-tokenfilters.html
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(remote = "Self", tag = "type", rename_all = "snake_case")]
+pub enum TokenFilter {
+    /// The [`lowercase`] token filter.
+    ///
+    /// [`lowercase`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-lowercase-tokenfilter.html
+    Lowercase {
+        /// The language specific rules to use, e.g. `greek` or `turkish`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        language: Option<String>,
+    },
+
+    /// The [`uppercase`] token filter.
+    ///
+    /// [`uppercase`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-uppercase-tokenfilter.html
+    Uppercase,
+
+    /// The [`asciifolding`] token filter, which converts characters to their
+    /// ASCII equivalent, e.g. `é` to `e`.
+    ///
+    /// [`asciifolding`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-asciifolding-tokenfilter.html
+    Asciifolding {
+        /// Whether the original tokens are kept as well. (Defaults to
+        /// `false`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        preserve_original: Option<bool>,
+    },
+
+    /// The [`stop`] token filter, which removes stop words.
+    ///
+    /// [`stop`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-stop-tokenfilter.html
+    Stop {
+        /// The stop words to remove. (Defaults to `_english_`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        stopwords: Option<Stopwords>,
+
+        /// The path to a file of stop words.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        stopwords_path: Option<String>,
+
+        /// Whether stop words are matched case insensitively. (Defaults to
+        /// `false`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        ignore_case: Option<bool>,
+    },
+
+    /// The [`stemmer`] token filter.
+    ///
+    /// [`stemmer`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-stemmer-tokenfilter.html
+    Stemmer {
+        /// The language of the stemmer, e.g. `english` or `light_german`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        language: Option<String>,
+    },
+
+    /// The [`synonym`] token filter.
+    ///
+    /// [`synonym`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-synonym-tokenfilter.html
+    Synonym {
+        /// The synonym rules, e.g. `i-pod, i pod => ipod`.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        synonyms: Vec<String>,
+
+        /// The path to a file of synonym rules.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        synonyms_path: Option<String>,
+
+        /// Whether equivalent synonyms are expanded to all of each other.
+        /// (Defaults to `true`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        expand: Option<bool>,
+
+        /// Whether invalid rules are ignored. (Defaults to `false`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        lenient: Option<bool>,
+    },
+
+    /// The [`synonym_graph`] token filter, which handles multi-word synonyms
+    /// when searching.
+    ///
+    /// [`synonym_graph`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-synonym-graph-tokenfilter.html
+    SynonymGraph {
+        /// The synonym rules, e.g. `ny, new york`.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        synonyms: Vec<String>,
+
+        /// The path to a file of synonym rules.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        synonyms_path: Option<String>,
+
+        /// Whether equivalent synonyms are expanded to all of each other.
+        /// (Defaults to `true`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        expand: Option<bool>,
+
+        /// Whether invalid rules are ignored. (Defaults to `false`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        lenient: Option<bool>,
+    },
+
+    /// The [`ngram`] token filter, which emits the n-grams of each token.
+    ///
+    /// [`ngram`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-ngram-tokenfilter.html
+    #[serde(rename = "ngram")]
+    NGram {
+        /// The minimum length of an n-gram. (Defaults to `1`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min_gram: Option<u32>,
+
+        /// The maximum length of an n-gram. (Defaults to `2`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_gram: Option<u32>,
+    },
+
+    /// The [`edge_ngram`] token filter, which emits the n-grams from the
+    /// start of each token.
+    ///
+    /// [`edge_ngram`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-edgengram-tokenfilter.html
+    #[serde(rename = "edge_ngram")]
+    EdgeNGram {
+        /// The minimum length of an n-gram. (Defaults to `1`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min_gram: Option<u32>,
+
+        /// The maximum length of an n-gram. (Defaults to `2`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_gram: Option<u32>,
+    },
+
+    /// The [`shingle`] token filter, which emits word n-grams.
+    ///
+    /// [`shingle`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-shingle-tokenfilter.html
+    Shingle {
+        /// The minimum number of tokens per shingle. (Defaults to `2`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min_shingle_size: Option<u32>,
+
+        /// The maximum number of tokens per shingle. (Defaults to `2`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_shingle_size: Option<u32>,
+
+        /// Whether the original tokens are emitted as well. (Defaults to
+        /// `true`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        output_unigrams: Option<bool>,
+    },
+
+    /// The [`length`] token filter, which removes tokens that are too short
+    /// or too long.
+    ///
+    /// [`length`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-length-tokenfilter.html
+    Length {
+        /// The minimum length of a token. (Defaults to `0`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min: Option<u32>,
+
+        /// The maximum length of a token.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max: Option<u32>,
+    },
+
+    /// The [`trim`] token filter, which removes leading and trailing
+    /// whitespace.
+    ///
+    /// [`trim`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-trim-tokenfilter.html
+    Trim,
+
+    /// The [`unique`] token filter, which removes duplicate tokens.
+    ///
+    /// [`unique`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis-unique-tokenfilter.html
+    Unique {
+        /// Whether only duplicates in the same position are removed.
+        /// (Defaults to `false`.)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        only_on_same_position: Option<bool>,
+    },
+
+    /// A token filter of any other type, e.g. `word_delimiter_graph`.
+    #[serde(skip)]
+    Other {
+        /// The type of the token filter.
+        ty: String,
+
+        /// The parameters of the token filter.
+        options: Map<String, Value>,
+    },
+}
+
+impl TokenFilter {
+    /// The types of the token filters that aren't deserialized as
+    /// [`TokenFilter::Other`].
+    const TYPES: &'static [&'static str] = &[
+        "lowercase",
+        "uppercase",
+        "asciifolding",
+        "stop",
+        "stemmer",
+        "synonym",
+        "synonym_graph",
+        "ngram",
+        "edge_ngram",
+        "shingle",
+        "length",
+        "trim",
+        "unique",
+    ];
+}
+
+impl_serde_with_other!(TokenFilter, None);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn can_round_trip_analysis() {
+        let json = json!({
+            "analyzer": {
+                "folding": {
+                    "type": "custom",
+                    "tokenizer": "standard",
+                    "char_filter": ["html_strip"],
+                    "filter": ["lowercase", "asciifolding", "english_stop"]
+                },
+                "std": { "type": "standard", "stopwords": ["a", "the"] },
+                "my_english": { "type": "english", "stem_exclusion": ["organization"] }
+            },
+            "normalizer": {
+                "lowercase": { "type": "custom", "filter": ["lowercase"] }
+            },
+            "tokenizer": {
+                "autocomplete": {
+                    "type": "edge_ngram",
+                    "min_gram": 2,
+                    "max_gram": 10,
+                    "token_chars": ["letter", "digit"]
+                },
+                "letters": { "type": "letter" }
+            },
+            "char_filter": {
+                "emoticons": { "type": "mapping", "mappings": [":) => _happy_"] },
+                "icu": { "type": "icu_normalizer", "name": "nfkc_cf" }
+            },
+            "filter": {
+                "english_stop": { "type": "stop", "stopwords": "_english_" },
+                "synonyms": { "type": "synonym_graph", "synonyms": ["ny, new york"] },
+                "delimiter": { "type": "word_delimiter_graph", "preserve_original": true },
+                "trim": { "type": "trim" }
+            }
+        });
+
+        let analysis: Analysis = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(
+            analysis.analyzer["std"],
+            Analyzer::Standard {
+                max_token_length: None,
+                stopwords: Some(Stopwords::List(vec!["a".to_string(), "the".to_string()])),
+            }
+        );
+        assert!(matches!(
+            analysis.analyzer["my_english"],
+            Analyzer::Other { ref ty, .. } if ty == "english"
+        ));
+        assert!(matches!(analysis.tokenizer["letters"], Tokenizer::Letter));
+        assert!(matches!(
+            analysis.char_filter["icu"],
+            CharFilter::Other { ref ty, .. } if ty == "icu_normalizer"
+        ));
+        assert_eq!(
+            analysis.filter["english_stop"],
+            TokenFilter::Stop {
+                stopwords: Some(Stopwords::Predefined("_english_".to_string())),
+                stopwords_path: None,
+                ignore_case: None,
+            }
+        );
+        assert_eq!(serde_json::to_value(&analysis).unwrap(), json);
+    }
+
+    #[test]
+    fn deserializes_analyzers_without_a_type_as_custom() {
+        let analyzer: Analyzer =
+            serde_json::from_value(json!({ "tokenizer": "whitespace" })).unwrap();
+        assert_eq!(
+            analyzer,
+            Analyzer::Custom {
+                tokenizer: "whitespace".to_string(),
+                char_filter: vec![],
+                filter: vec![],
+                position_increment_gap: None,
+            }
+        );
+
+        assert!(serde_json::from_value::<TokenFilter>(json!({ "min": 1 })).is_err());
+    }
+}
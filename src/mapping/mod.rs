@@ -0,0 +1,366 @@
+//! Types for defining and introspecting the [mapping] of an index, i.e. the
+//! fields of its documents and how they are indexed.
+//!
+//! ```
+//! use elastiql::mapping::{Dynamic, KeywordProperty, Mapping, Property, TextProperty};
+//!
+//! let mapping = Mapping {
+//!     dynamic: Some(Dynamic::Strict),
+//!     properties: vec![
+//!         ("id".to_string(), Property::Keyword(KeywordProperty::default())),
+//!         (
+//!             "title".to_string(),
+//!             Property::Text(TextProperty {
+//!                 fields: vec![(
+//!                     "raw".to_string(),
+//!                     Property::Keyword(KeywordProperty::default()),
+//!                 )]
+//!                 .into_iter()
+//!                 .collect(),
+//!                 ..TextProperty::default()
+//!             }),
+//!         ),
+//!     ]
+//!     .into_iter()
+//!     .collect(),
+//!     ..Mapping::default()
+//! };
+//!
+//! assert_eq!(
+//!     serde_json::to_value(&mapping).unwrap(),
+//!     serde_json::json!({
+//!         "dynamic": "strict",
+//!         "properties": {
+//!             "id": { "type": "keyword" },
+//!             "title": { "type": "text", "fields": { "raw": { "type": "keyword" } } }
+//!         }
+//!     })
+//! );
+//! ```
+//!
+//! [mapping]: https://www.elastic.co/guide/en/elasticsearch/reference/current/mapping.html
+
+use std::{collections::HashMap, fmt};
+
+#[cfg(feature = "schemars")]
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::{
+    de::{self, Deserializer, Visitor},
+    Deserialize, Serialize, Serializer,
+};
+
+pub use self::{property::*, runtime::*};
+
+/// Implements `Serialize` and `Deserialize` for an enum that is internally
+/// tagged by its `type` (i.e. is derived with `#[serde(remote = "Self", tag =
+/// "type")]`), which keeps definitions of any types not in its `TYPES` as its
+/// `Other { ty, options }` variant.
+///
+/// Definitions without a `type` are deserialized as the `$default` type, if
+/// any.
+macro_rules! impl_serde_with_other {
+    ($ty:ident, $default:expr) => {
+        impl serde::Serialize for $ty {
+            #[inline]
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                use serde::ser::SerializeMap;
+
+                match self {
+                    $ty::Other { ty, options } => {
+                        let mut map = serializer.serialize_map(Some(options.len() + 1))?;
+                        map.serialize_entry("type", ty)?;
+                        for (key, value) in options {
+                            map.serialize_entry(key, value)?;
+                        }
+                        map.end()
+                    }
+                    _ => $ty::serialize(self, serializer),
+                }
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $ty {
+            #[inline]
+            fn deserialize<D>(deserializer: D) -> Result<$ty, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                use serde::de::Error;
+                use serde_json::{Map, Value};
+
+                let mut options = Map::<String, Value>::deserialize(deserializer)?;
+
+                let default: Option<&str> = $default;
+                let ty = match options.remove("type") {
+                    Some(Value::String(ty)) => ty,
+                    Some(_) => return Err(D::Error::custom("expected the `type` to be a string")),
+                    None => match default {
+                        Some(ty) => ty.to_string(),
+                        None => return Err(D::Error::missing_field("type")),
+                    },
+                };
+
+                if !$ty::TYPES.contains(&ty.as_str()) {
+                    return Ok($ty::Other { ty, options });
+                }
+
+                options.insert("type".to_string(), Value::String(ty));
+                $ty::deserialize(Value::Object(options)).map_err(D::Error::custom)
+            }
+        }
+    };
+}
+
+pub mod analysis;
+mod property;
+mod runtime;
+
+/// The [mapping] of an index, as passed when creating an index or returned
+/// when getting its mapping.
+///
+/// [mapping]: https://www.elastic.co/guide/en/elasticsearch/reference/current/mapping.html
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(default, setter(into))))]
+pub struct Mapping {
+    /// Whether (and how) new fields are added to the mapping when they are
+    /// encountered in a document.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dynamic: Option<Dynamic>,
+
+    /// Whether new string fields that look like dates are mapped as `date`
+    /// fields. (Defaults to `true`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_detection: Option<bool>,
+
+    /// Whether new string fields that look like numbers are mapped as numeric
+    /// fields. (Defaults to `false`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub numeric_detection: Option<bool>,
+
+    /// The fields of the documents, by name.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub properties: HashMap<String, Property>,
+
+    /// The [runtime fields] of the documents, by name.
+    ///
+    /// [runtime fields]: https://www.elastic.co/guide/en/elasticsearch/reference/current/runtime-mapping-fields.html
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub runtime: HashMap<String, RuntimeField>,
+
+    /// Custom [metadata] about the index.
+    ///
+    /// [metadata]: https://www.elastic.co/guide/en/elasticsearch/reference/current/mapping-meta-field.html
+    #[serde(default, rename = "_meta", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<crate::schema::Map>"))]
+    pub meta: Option<crate::scalars::Map>,
+}
+
+impl_display_json!(Mapping);
+impl_canonical_json!(Mapping);
+
+/// The [`dynamic`] mapping setting, which controls whether (and how) new
+/// fields are added to a mapping when they are encountered in a document.
+///
+/// [`dynamic`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/dynamic.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Dynamic {
+    /// New fields are added to the mapping.
+    True,
+
+    /// New fields are ignored; they are kept in `_source` but not indexed.
+    False,
+
+    /// Documents with new fields are rejected.
+    Strict,
+
+    /// New fields are added to the mapping as runtime fields.
+    Runtime,
+}
+
+impl Dynamic {
+    /// The name of this setting as understood by Elasticsearch.
+    #[inline]
+    fn as_str(self) -> &'static str {
+        match self {
+            Dynamic::True => "true",
+            Dynamic::False => "false",
+            Dynamic::Strict => "strict",
+            Dynamic::Runtime => "runtime",
+        }
+    }
+}
+
+impl Serialize for Dynamic {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Dynamic {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Dynamic, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        /// Visits a `Dynamic` during deserialization, which Elasticsearch
+        /// accepts as either a boolean or a string.
+        struct DynamicVisitor;
+
+        impl<'de> Visitor<'de> for DynamicVisitor {
+            type Value = Dynamic;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a boolean or one of `true`, `false`, `strict` or `runtime`")
+            }
+
+            fn visit_bool<E: de::Error>(self, value: bool) -> Result<Self::Value, E> {
+                Ok(if value { Dynamic::True } else { Dynamic::False })
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                match value {
+                    "true" => Ok(Dynamic::True),
+                    "false" => Ok(Dynamic::False),
+                    "strict" => Ok(Dynamic::Strict),
+                    "runtime" => Ok(Dynamic::Runtime),
+                    _ => Err(de::Error::unknown_variant(
+                        value,
+                        &["true", "false", "strict", "runtime"],
+                    )),
+                }
+            }
+        }
+
+        deserializer.deserialize_any(DynamicVisitor)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl JsonSchema for Dynamic {
+    #[inline]
+    fn schema_name() -> String {
+        "Dynamic".to_string()
+    }
+
+    #[inline]
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        /// The ways Elasticsearch accepts a `Dynamic`.
+        #[allow(dead_code)]
+        #[derive(schemars::JsonSchema)]
+        #[serde(untagged)]
+        enum Dynamic {
+            /// `true` or `false`.
+            Bool(bool),
+            /// The name of the setting.
+            Name(DynamicName),
+        }
+
+        /// The names of the `Dynamic` settings.
+        #[allow(dead_code)]
+        #[derive(schemars::JsonSchema)]
+        #[serde(rename_all = "snake_case")]
+        enum DynamicName {
+            /// `true`
+            True,
+            /// `false`
+            False,
+            /// `strict`
+            Strict,
+            /// `runtime`
+            Runtime,
+        }
+
+        Dynamic::json_schema(gen)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn can_round_trip_mapping() {
+        let json = json!({
+            "dynamic": "strict",
+            "date_detection": false,
+            "_meta": { "version": 2 },
+            "properties": {
+                "id": { "type": "keyword", "ignore_above": 256 },
+                "title": {
+                    "type": "text",
+                    "analyzer": "english",
+                    "fields": { "raw": { "type": "keyword" } }
+                },
+                "created": { "type": "date", "format": "strict_date_optional_time||epoch_millis" },
+                "duration": { "type": "long", "null_value": 0.0 },
+                "price": { "type": "scaled_float", "scaling_factor": 100.0 },
+                "embedding": { "type": "dense_vector", "dims": 3, "index": true, "similarity": "cosine" },
+                "comments": {
+                    "type": "nested",
+                    "properties": { "text": { "type": "text" } }
+                },
+                "user": {
+                    "type": "object",
+                    "dynamic": "false",
+                    "properties": { "name": { "type": "keyword" } }
+                },
+                "labels": { "type": "flattened" },
+                "suggest": { "type": "completion", "analyzer": "simple" }
+            },
+            "runtime": {
+                "day_of_week": {
+                    "type": "keyword",
+                    "script": { "source": "emit(doc['created'].value.dayOfWeekEnum.toString())" }
+                }
+            }
+        });
+
+        let mapping: Mapping = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(mapping.dynamic, Some(Dynamic::Strict));
+        assert!(matches!(
+            mapping.properties["suggest"],
+            Property::Other { ref ty, .. } if ty == "completion"
+        ));
+        assert_eq!(serde_json::to_value(&mapping).unwrap(), json);
+    }
+
+    #[test]
+    fn can_deserialize_implicit_objects() {
+        let mapping: Mapping = serde_json::from_value(json!({
+            "properties": {
+                "user": { "properties": { "name": { "type": "keyword" } } }
+            }
+        }))
+        .unwrap();
+
+        match &mapping.properties["user"] {
+            Property::Object(object) => {
+                assert_eq!(
+                    object.properties["name"],
+                    Property::Keyword(KeywordProperty::default())
+                );
+            }
+            property => panic!("expected an object, got: {:?}", property),
+        }
+    }
+
+    #[test]
+    fn can_deserialize_dynamic() {
+        for (json, expected) in [
+            (json!(true), Dynamic::True),
+            (json!("true"), Dynamic::True),
+            (json!(false), Dynamic::False),
+            (json!("strict"), Dynamic::Strict),
+            (json!("runtime"), Dynamic::Runtime),
+        ] {
+            assert_eq!(serde_json::from_value::<Dynamic>(json).unwrap(), expected);
+        }
+
+        assert!(serde_json::from_value::<Dynamic>(json!("yes")).is_err());
+    }
+}
@@ -0,0 +1,530 @@
+//! The [field data types] of the properties of a mapping.
+//!
+//! [field data types]: https://www.elastic.co/guide/en/elasticsearch/reference/current/mapping-types.html
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::Dynamic;
+
+/// A property (i.e. field) of a mapping and its [field data type].
+///
+/// Properties of types that aren't defined here, e.g. those added by plugins,
+/// are kept as [`Property::Other`].
+///
+/// **Note**: objects are returned without a `type` when getting a mapping;
+/// properties without a `type` are deserialized as [`Property::Object`].
+///
+/// [field data type]: https://www.elastic.co/guide/en/elasticsearch/reference/current/mapping-types.html
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(remote = "Self", tag = "type", rename_all = "snake_case")]
+pub enum Property {
+    /// A full-text [`text`] field.
+    ///
+    /// [`text`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/text.html
+    Text(TextProperty),
+
+    /// A structured [`keyword`] field, e.g. an ID, an email address or a tag.
+    ///
+    /// [`keyword`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/keyword.html
+    Keyword(KeywordProperty),
+
+    /// A [`date`] field, with millisecond resolution.
+    ///
+    /// [`date`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/date.html
+    Date(DateProperty),
+
+    /// A [`date_nanos`] field, with nanosecond resolution.
+    ///
+    /// [`date_nanos`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/date_nanos.html
+    DateNanos(DateProperty),
+
+    /// A signed 64-bit integer [numeric] field.
+    ///
+    /// [numeric]: https://www.elastic.co/guide/en/elasticsearch/reference/current/number.html
+    Long(NumberProperty),
+
+    /// A signed 32-bit integer [numeric] field.
+    ///
+    /// [numeric]: https://www.elastic.co/guide/en/elasticsearch/reference/current/number.html
+    Integer(NumberProperty),
+
+    /// A signed 16-bit integer [numeric] field.
+    ///
+    /// [numeric]: https://www.elastic.co/guide/en/elasticsearch/reference/current/number.html
+    Short(NumberProperty),
+
+    /// A signed 8-bit integer [numeric] field.
+    ///
+    /// [numeric]: https://www.elastic.co/guide/en/elasticsearch/reference/current/number.html
+    Byte(NumberProperty),
+
+    /// A double-precision 64-bit floating point [numeric] field.
+    ///
+    /// [numeric]: https://www.elastic.co/guide/en/elasticsearch/reference/current/number.html
+    Double(NumberProperty),
+
+    /// A single-precision 32-bit floating point [numeric] field.
+    ///
+    /// [numeric]: https://www.elastic.co/guide/en/elasticsearch/reference/current/number.html
+    Float(NumberProperty),
+
+    /// A half-precision 16-bit floating point [numeric] field.
+    ///
+    /// [numeric]: https://www.elastic.co/guide/en/elasticsearch/reference/current/number.html
+    HalfFloat(NumberProperty),
+
+    /// An unsigned 64-bit integer [numeric] field.
+    ///
+    /// [numeric]: https://www.elastic.co/guide/en/elasticsearch/reference/current/number.html
+    UnsignedLong(NumberProperty),
+
+    /// A floating point [numeric] field that is stored as a `long`, scaled by
+    /// a fixed factor.
+    ///
+    /// [numeric]: https://www.elastic.co/guide/en/elasticsearch/reference/current/number.html
+    ScaledFloat(ScaledFloatProperty),
+
+    /// A [`boolean`] field.
+    ///
+    /// [`boolean`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/boolean.html
+    Boolean(BooleanProperty),
+
+    /// A Base64 encoded [`binary`] field, which isn't searchable.
+    ///
+    /// [`binary`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/binary.html
+    Binary(BasicProperty),
+
+    /// An IPv4 or IPv6 address [`ip`] field.
+    ///
+    /// [`ip`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/ip.html
+    Ip(BasicProperty),
+
+    /// A latitude/longitude [`geo_point`] field.
+    ///
+    /// [`geo_point`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/geo-point.html
+    GeoPoint(BasicProperty),
+
+    /// A [`geo_shape`] field, e.g. a polygon.
+    ///
+    /// [`geo_shape`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/geo-shape.html
+    GeoShape(BasicProperty),
+
+    /// A [`flattened`] field, which maps an entire object as a single field.
+    ///
+    /// [`flattened`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/flattened.html
+    Flattened(BasicProperty),
+
+    /// A [`dense_vector`] field, e.g. for kNN search.
+    ///
+    /// [`dense_vector`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/dense-vector.html
+    DenseVector(DenseVectorProperty),
+
+    /// An [`object`] field, i.e. a JSON object.
+    ///
+    /// [`object`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/object.html
+    Object(ObjectProperty),
+
+    /// A [`nested`] field, i.e. an array of objects that can be queried
+    /// independently of each other.
+    ///
+    /// [`nested`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/nested.html
+    Nested(NestedProperty),
+
+    /// A property of any other type, e.g. one added by a plugin.
+    #[serde(skip)]
+    Other {
+        /// The type of the property, e.g. `completion`.
+        ty: String,
+
+        /// The parameters of the property.
+        options: Map<String, Value>,
+    },
+}
+
+impl Property {
+    /// The types of the properties that aren't deserialized as
+    /// [`Property::Other`].
+    const TYPES: &'static [&'static str] = &[
+        "text",
+        "keyword",
+        "date",
+        "date_nanos",
+        "long",
+        "integer",
+        "short",
+        "byte",
+        "double",
+        "float",
+        "half_float",
+        "unsigned_long",
+        "scaled_float",
+        "boolean",
+        "binary",
+        "ip",
+        "geo_point",
+        "geo_shape",
+        "flattened",
+        "dense_vector",
+        "object",
+        "nested",
+    ];
+}
+
+impl_serde_with_other!(Property, Some("object"));
+
+/// The parameters of a [`text`] property.
+///
+/// [`text`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/text.html
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(default, setter(into))))]
+pub struct TextProperty {
+    /// The [analyzer] used when indexing (and searching, unless a
+    /// `search_analyzer` is specified). (Defaults to `standard`.)
+    ///
+    /// [analyzer]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analyzer.html
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub analyzer: Option<String>,
+
+    /// The analyzer used when searching.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search_analyzer: Option<String>,
+
+    /// The analyzer used when searching for a phrase.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search_quote_analyzer: Option<String>,
+
+    /// Whether the field is searchable. (Defaults to `true`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<bool>,
+
+    /// Whether the field is stored separately from `_source`. (Defaults to
+    /// `false`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub store: Option<bool>,
+
+    /// Whether the field can be used for sorting and aggregations, which uses
+    /// a lot of memory. (Defaults to `false`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fielddata: Option<bool>,
+
+    /// Whether field length is taken into account when scoring. (Defaults to
+    /// `true`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub norms: Option<bool>,
+
+    /// The [multi-fields] that index this field in other ways, e.g. as a
+    /// `keyword`.
+    ///
+    /// [multi-fields]: https://www.elastic.co/guide/en/elasticsearch/reference/current/multi-fields.html
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub fields: HashMap<String, Property>,
+
+    /// The fields to copy the values of this field into.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub copy_to: Vec<String>,
+}
+
+/// The parameters of a [`keyword`] property.
+///
+/// [`keyword`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/keyword.html
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(default, setter(into))))]
+pub struct KeywordProperty {
+    /// Strings longer than this many characters aren't indexed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ignore_above: Option<u32>,
+
+    /// The [normalizer] applied to values before indexing.
+    ///
+    /// [normalizer]: https://www.elastic.co/guide/en/elasticsearch/reference/current/normalizer.html
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normalizer: Option<String>,
+
+    /// The value indexed in place of a `null`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub null_value: Option<String>,
+
+    /// Whether the field is searchable. (Defaults to `true`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<bool>,
+
+    /// Whether the field is stored on disk in a column-stride fashion, for
+    /// sorting and aggregations. (Defaults to `true`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc_values: Option<bool>,
+
+    /// Whether the field is stored separately from `_source`. (Defaults to
+    /// `false`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub store: Option<bool>,
+
+    /// The [multi-fields] that index this field in other ways.
+    ///
+    /// [multi-fields]: https://www.elastic.co/guide/en/elasticsearch/reference/current/multi-fields.html
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub fields: HashMap<String, Property>,
+
+    /// The fields to copy the values of this field into.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub copy_to: Vec<String>,
+}
+
+/// The parameters of a [`date`] or [`date_nanos`] property.
+///
+/// [`date`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/date.html
+/// [`date_nanos`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/date_nanos.html
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(default, setter(into))))]
+pub struct DateProperty {
+    /// The [format(s)] of the dates, e.g. `yyyy-MM-dd||epoch_millis`.
+    /// (Defaults to `strict_date_optional_time||epoch_millis`.)
+    ///
+    /// [format(s)]: https://www.elastic.co/guide/en/elasticsearch/reference/current/mapping-date-format.html
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+
+    /// The value indexed in place of a `null`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub null_value: Option<String>,
+
+    /// Whether malformed dates are ignored instead of rejecting the document.
+    /// (Defaults to `false`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ignore_malformed: Option<bool>,
+
+    /// Whether the field is searchable. (Defaults to `true`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<bool>,
+
+    /// Whether the field is stored on disk in a column-stride fashion, for
+    /// sorting and aggregations. (Defaults to `true`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc_values: Option<bool>,
+
+    /// Whether the field is stored separately from `_source`. (Defaults to
+    /// `false`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub store: Option<bool>,
+
+    /// The [multi-fields] that index this field in other ways.
+    ///
+    /// [multi-fields]: https://www.elastic.co/guide/en/elasticsearch/reference/current/multi-fields.html
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub fields: HashMap<String, Property>,
+
+    /// The fields to copy the values of this field into.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub copy_to: Vec<String>,
+}
+
+/// The parameters of a [numeric] property.
+///
+/// [numeric]: https://www.elastic.co/guide/en/elasticsearch/reference/current/number.html
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(default, setter(into))))]
+pub struct NumberProperty {
+    /// Whether strings are converted to numbers and floats are truncated for
+    /// integers. (Defaults to `true`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coerce: Option<bool>,
+
+    /// The value indexed in place of a `null`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub null_value: Option<f64>,
+
+    /// Whether malformed numbers are ignored instead of rejecting the
+    /// document. (Defaults to `false`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ignore_malformed: Option<bool>,
+
+    /// Whether the field is searchable. (Defaults to `true`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<bool>,
+
+    /// Whether the field is stored on disk in a column-stride fashion, for
+    /// sorting and aggregations. (Defaults to `true`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc_values: Option<bool>,
+
+    /// Whether the field is stored separately from `_source`. (Defaults to
+    /// `false`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub store: Option<bool>,
+
+    /// The [multi-fields] that index this field in other ways.
+    ///
+    /// [multi-fields]: https://www.elastic.co/guide/en/elasticsearch/reference/current/multi-fields.html
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub fields: HashMap<String, Property>,
+
+    /// The fields to copy the values of this field into.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub copy_to: Vec<String>,
+}
+
+/// The parameters of a [`scaled_float`] property.
+///
+/// [`scaled_float`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/number.html#scaled-float-params
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct ScaledFloatProperty {
+    /// The factor values are multiplied by before being stored as a `long`,
+    /// e.g. `100` for prices in cents.
+    pub scaling_factor: f64,
+
+    /// The parameters shared with the other numeric properties.
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(flatten)]
+    pub number: NumberProperty,
+}
+
+/// The parameters of a [`boolean`] property.
+///
+/// [`boolean`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/boolean.html
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(default, setter(into))))]
+pub struct BooleanProperty {
+    /// The value indexed in place of a `null`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub null_value: Option<bool>,
+
+    /// Whether the field is searchable. (Defaults to `true`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<bool>,
+
+    /// Whether the field is stored on disk in a column-stride fashion, for
+    /// sorting and aggregations. (Defaults to `true`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc_values: Option<bool>,
+
+    /// Whether the field is stored separately from `_source`. (Defaults to
+    /// `false`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub store: Option<bool>,
+}
+
+/// The common parameters of the properties that don't have any others, e.g.
+/// [`ip`] or [`geo_point`].
+///
+/// [`ip`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/ip.html
+/// [`geo_point`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/geo-point.html
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(default, setter(into))))]
+pub struct BasicProperty {
+    /// Whether malformed values are ignored instead of rejecting the
+    /// document. (Defaults to `false`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ignore_malformed: Option<bool>,
+
+    /// Whether the field is searchable. (Defaults to `true`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<bool>,
+
+    /// Whether the field is stored on disk in a column-stride fashion, for
+    /// sorting and aggregations.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc_values: Option<bool>,
+
+    /// Whether the field is stored separately from `_source`. (Defaults to
+    /// `false`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub store: Option<bool>,
+
+    /// The fields to copy the values of this field into.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub copy_to: Vec<String>,
+}
+
+/// The parameters of a [`dense_vector`] property.
+///
+/// [`dense_vector`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/dense-vector.html
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct DenseVectorProperty {
+    /// The number of dimensions of the vectors.
+    pub dims: u32,
+
+    /// Whether the vectors are indexed for kNN search.
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<bool>,
+
+    /// The similarity metric used for kNN search, e.g. `cosine`, `dot_product`
+    /// or `l2_norm`.
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub similarity: Option<String>,
+
+    /// The type of the elements of the vectors, e.g. `float` or `byte`.
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub element_type: Option<String>,
+}
+
+/// The parameters of an [`object`] property.
+///
+/// [`object`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/object.html
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(default, setter(into))))]
+pub struct ObjectProperty {
+    /// Whether (and how) new fields are added to this object.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dynamic: Option<Dynamic>,
+
+    /// Whether the object is parsed and indexed at all. (Defaults to `true`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// The fields of the object, by name.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub properties: HashMap<String, Property>,
+}
+
+/// The parameters of a [`nested`] property.
+///
+/// [`nested`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/nested.html
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(default, setter(into))))]
+pub struct NestedProperty {
+    /// Whether (and how) new fields are added to the nested objects.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dynamic: Option<Dynamic>,
+
+    /// Whether the fields of the nested objects are also added to the parent
+    /// document as regular (i.e. flattened) fields.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_in_parent: Option<bool>,
+
+    /// Whether the fields of the nested objects are also added to the root
+    /// document as regular (i.e. flattened) fields.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_in_root: Option<bool>,
+
+    /// The fields of the nested objects, by name.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub properties: HashMap<String, Property>,
+}
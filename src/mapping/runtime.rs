@@ -0,0 +1,145 @@
+//! The definitions of [runtime fields], which are evaluated when a document
+//! is searched instead of when it is indexed.
+//!
+//! [runtime fields]: https://www.elastic.co/guide/en/elasticsearch/reference/current/runtime.html
+
+use serde::{Deserialize, Serialize};
+
+use crate::search::Script;
+
+/// A [runtime field], as defined in the `runtime` section of a [`Mapping`] or
+/// in the [`runtime_mappings`] of a search request.
+///
+/// [runtime field]: https://www.elastic.co/guide/en/elasticsearch/reference/current/runtime-mapping-fields.html
+/// [`Mapping`]: super::Mapping
+/// [`runtime_mappings`]: crate::search::Request::runtime_mappings
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct RuntimeField {
+    /// The type of the values emitted by the field.
+    #[serde(rename = "type")]
+    pub ty: RuntimeFieldType,
+
+    /// The script emitting the values of the field. Without one, the values
+    /// are read from the field of the same name in `_source`.
+    #[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script: Option<Script>,
+
+    /// The [format] of the values of a `date` field.
+    ///
+    /// [format]: https://www.elastic.co/guide/en/elasticsearch/reference/current/mapping-date-format.html
+    #[cfg_attr(feature = "builder", builder(default, setter(strip_option)))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+}
+
+impl RuntimeField {
+    /// Creates a runtime field of type `ty` whose values are emitted by
+    /// `script`.
+    #[inline]
+    pub fn new(ty: RuntimeFieldType, script: impl Into<Script>) -> Self {
+        RuntimeField {
+            ty,
+            script: Some(script.into()),
+            format: None,
+        }
+    }
+}
+
+impl From<RuntimeFieldType> for RuntimeField {
+    #[inline]
+    fn from(ty: RuntimeFieldType) -> Self {
+        RuntimeField {
+            ty,
+            script: None,
+            format: None,
+        }
+    }
+}
+
+/// The [types] of the values a [`RuntimeField`] can emit.
+///
+/// [types]: https://www.elastic.co/guide/en/elasticsearch/reference/current/runtime-mapping-fields.html
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum RuntimeFieldType {
+    /// A structured `keyword`.
+    Keyword,
+
+    /// A signed 64-bit integer.
+    Long,
+
+    /// A double-precision 64-bit floating point number.
+    Double,
+
+    /// A `date`, with millisecond resolution.
+    Date,
+
+    /// An IPv4 or IPv6 address.
+    Ip,
+
+    /// A `boolean`.
+    Boolean,
+
+    /// A latitude/longitude point.
+    GeoPoint,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn can_round_trip_runtime_fields() {
+        let json = json!({
+            "type": "date",
+            "script": { "source": "emit(doc['timestamp'].value.toInstant().toEpochMilli())" },
+            "format": "yyyy-MM-dd"
+        });
+
+        let field: RuntimeField = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(field.ty, RuntimeFieldType::Date);
+        assert_eq!(field.format.as_deref(), Some("yyyy-MM-dd"));
+        assert_eq!(serde_json::to_value(&field).unwrap(), json);
+
+        assert_eq!(
+            serde_json::to_value(RuntimeField::from(RuntimeFieldType::GeoPoint)).unwrap(),
+            json!({ "type": "geo_point" })
+        );
+    }
+
+    #[test]
+    fn can_serialize_runtime_mappings() {
+        let request = crate::search::Request::builder()
+            .runtime_mappings(
+                vec![(
+                    "total".to_string(),
+                    RuntimeField::new(
+                        RuntimeFieldType::Double,
+                        Script::inline("emit(doc['price'].value * doc['quantity'].value)"),
+                    ),
+                )]
+                .into_iter()
+                .collect::<std::collections::HashMap<_, _>>(),
+            )
+            .build();
+
+        assert_eq!(
+            serde_json::to_value(&request).unwrap()["runtime_mappings"],
+            json!({
+                "total": {
+                    "type": "double",
+                    "script": { "source": "emit(doc['price'].value * doc['quantity'].value)" }
+                }
+            })
+        );
+    }
+}
@@ -0,0 +1,209 @@
+//! A scalar that represents a geographic point.
+
+use std::{error::Error, fmt, str::FromStr};
+
+use elastiql_derive::{input_twin, twin_impl};
+#[cfg(feature = "schemars")]
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::ser::{SerializeStruct, Serializer};
+use serde::{de, Deserialize, Deserializer, Serialize};
+
+/// A [geographic point], e.g. the origin of a `geo_distance` query.
+///
+/// It is serialized as an object, e.g. `{ "lat": 40.7, "lon": -74.0 }`, but
+/// may also be deserialized from a `"lat,lon"` string, e.g. `"40.7,-74.0"`,
+/// or a `[lon, lat]` array, e.g. `[-74.0, 40.7]` (note the GeoJSON order).
+///
+/// ```
+/// use elastiql::scalars::GeoPoint;
+///
+/// let point: GeoPoint = serde_json::from_str("[-74.0, 40.7]").unwrap();
+/// assert_eq!(point, GeoPoint::new(40.7, -74.0));
+/// assert_eq!(point, "40.7,-74.0".parse().unwrap());
+/// ```
+///
+/// [geographic point]: https://www.elastic.co/guide/en/elasticsearch/reference/current/geo-point.html
+#[input_twin]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("GeoPoint"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+pub struct GeoPoint {
+    /// The latitude, between `-90` and `90` degrees.
+    pub lat: f64,
+
+    /// The longitude, between `-180` and `180` degrees.
+    pub lon: f64,
+}
+
+impl GeoPoint {
+    /// Constructs a new `GeoPoint`.
+    #[inline]
+    pub fn new(lat: f64, lon: f64) -> GeoPoint {
+        GeoPoint { lat, lon }
+    }
+
+    /// Checks that the latitude and longitude are within their ranges.
+    pub fn validate(&self) -> Result<(), GeoPointError> {
+        if !(-90.0..=90.0).contains(&self.lat) {
+            return Err(GeoPointError::InvalidLatitude(self.lat));
+        }
+        if !(-180.0..=180.0).contains(&self.lon) {
+            return Err(GeoPointError::InvalidLongitude(self.lon));
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for GeoPoint {
+    type Err = GeoPointError;
+
+    /// Parses a `"lat,lon"` string, e.g. `"40.7,-74.0"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || GeoPointError::Invalid(s.to_string());
+
+        let (lat, lon) = s.split_once(',').ok_or_else(invalid)?;
+        let point = GeoPoint {
+            lat: lat.trim().parse().map_err(|_| invalid())?,
+            lon: lon.trim().parse().map_err(|_| invalid())?,
+        };
+
+        point.validate()?;
+        Ok(point)
+    }
+}
+
+impl fmt::Display for GeoPoint {
+    /// Formats the point as a `"lat,lon"` string.
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.lat, self.lon)
+    }
+}
+
+/// The reason a [`GeoPoint`] is invalid.
+#[derive(Clone, PartialEq, Debug)]
+pub enum GeoPointError {
+    /// The string isn't in the `"lat,lon"` format.
+    Invalid(String),
+    /// The latitude isn't between `-90` and `90` degrees.
+    InvalidLatitude(f64),
+    /// The longitude isn't between `-180` and `180` degrees.
+    InvalidLongitude(f64),
+}
+
+impl fmt::Display for GeoPointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GeoPointError::Invalid(s) => write!(f, "`{}` is not a `lat,lon` point", s),
+            GeoPointError::InvalidLatitude(lat) => write!(f, "invalid latitude `{}`", lat),
+            GeoPointError::InvalidLongitude(lon) => write!(f, "invalid longitude `{}`", lon),
+        }
+    }
+}
+
+impl Error for GeoPointError {}
+
+/// The forms Elasticsearch accepts a [`GeoPoint`] in.
+#[allow(clippy::missing_docs_in_private_items)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum GeoPointRepr {
+    Object { lat: f64, lon: f64 },
+    String(String),
+    Array([f64; 2]),
+}
+
+#[twin_impl]
+impl Serialize for GeoPoint {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut point = serializer.serialize_struct("GeoPoint", 2)?;
+        point.serialize_field("lat", &self.lat)?;
+        point.serialize_field("lon", &self.lon)?;
+        point.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for GeoPoint {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<GeoPoint, D::Error> {
+        let point = match GeoPointRepr::deserialize(deserializer)? {
+            GeoPointRepr::Object { lat, lon } => GeoPoint { lat, lon },
+            GeoPointRepr::String(s) => return s.parse().map_err(de::Error::custom),
+            GeoPointRepr::Array([lon, lat]) => GeoPoint { lat, lon },
+        };
+
+        point.validate().map_err(de::Error::custom)?;
+        Ok(point)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl JsonSchema for GeoPoint {
+    #[inline]
+    fn schema_name() -> String {
+        "GeoPoint".to_string()
+    }
+
+    #[inline]
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        GeoPointRepr::json_schema(gen)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for GeoPoint {
+    #[inline]
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        // quarter degrees are exactly representable in JSON
+        Ok(GeoPoint {
+            lat: f64::from(u.int_in_range(-360..=360)?) / 4.0,
+            lon: f64::from(u.int_in_range(-720..=720)?) / 4.0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn can_deserialize_all_forms() {
+        let point = GeoPoint::new(40.7, -74.0);
+
+        for j in &[
+            json!({ "lat": 40.7, "lon": -74.0 }),
+            json!("40.7,-74.0"),
+            json!("40.7, -74.0"),
+            json!([-74.0, 40.7]),
+        ] {
+            assert_eq!(
+                serde_json::from_value::<GeoPoint>(j.clone()).unwrap(),
+                point
+            );
+        }
+
+        assert_eq!(
+            serde_json::to_value(point).unwrap(),
+            json!({ "lat": 40.7, "lon": -74.0 })
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_points() {
+        assert_eq!(
+            "40.7".parse::<GeoPoint>(),
+            Err(GeoPointError::Invalid("40.7".to_string()))
+        );
+        assert_eq!(
+            "91,0".parse::<GeoPoint>(),
+            Err(GeoPointError::InvalidLatitude(91.0))
+        );
+        assert!(serde_json::from_value::<GeoPoint>(json!([181.0, 0.0])).is_err());
+        assert!(serde_json::from_value::<GeoPoint>(json!({ "lat": 0.0 })).is_err());
+        assert!(serde_json::from_value::<GeoPoint>(json!("drm3btev3e86")).is_err());
+    }
+}
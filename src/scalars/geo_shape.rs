@@ -0,0 +1,246 @@
+//! A scalar that represents a [GeoJSON] geometry.
+//!
+//! [GeoJSON]: https://datatracker.ietf.org/doc/html/rfc7946
+
+use serde::{Deserialize, Serialize};
+
+/// The `[lon, lat]` coordinates of a position (note the GeoJSON order).
+pub type Position = [f64; 2];
+
+/// A [GeoJSON] geometry (or an Elasticsearch `envelope`), e.g. the shape of a
+/// `geo_shape` query.
+///
+/// It is exposed through GraphQL as a JSON scalar.
+///
+/// ```
+/// use elastiql::scalars::GeoShape;
+///
+/// let shape: GeoShape = serde_json::from_str(
+///     r#"{ "type": "envelope", "coordinates": [[13.0, 53.0], [14.0, 52.0]] }"#,
+/// )
+/// .unwrap();
+/// assert_eq!(
+///     shape,
+///     GeoShape::Envelope {
+///         coordinates: [[13.0, 53.0], [14.0, 52.0]]
+///     }
+/// );
+/// ```
+///
+/// [GeoJSON]: https://datatracker.ietf.org/doc/html/rfc7946
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type")]
+pub enum GeoShape {
+    /// A single position.
+    #[serde(alias = "point")]
+    Point {
+        /// The position.
+        coordinates: Position,
+    },
+
+    /// A line through two or more positions.
+    #[serde(alias = "linestring")]
+    LineString {
+        /// The positions.
+        coordinates: Vec<Position>,
+    },
+
+    /// A polygon, i.e. an outer ring followed by any holes, where each ring is
+    /// a closed line (its first and last positions are the same).
+    #[serde(alias = "polygon")]
+    Polygon {
+        /// The rings.
+        coordinates: Vec<Vec<Position>>,
+    },
+
+    /// Several positions.
+    #[serde(alias = "multipoint")]
+    MultiPoint {
+        /// The positions.
+        coordinates: Vec<Position>,
+    },
+
+    /// Several lines.
+    #[serde(alias = "multilinestring")]
+    MultiLineString {
+        /// The positions of each line.
+        coordinates: Vec<Vec<Position>>,
+    },
+
+    /// Several polygons.
+    #[serde(alias = "multipolygon")]
+    MultiPolygon {
+        /// The rings of each polygon.
+        coordinates: Vec<Vec<Vec<Position>>>,
+    },
+
+    /// Several geometries of any type.
+    #[serde(alias = "geometrycollection")]
+    GeometryCollection {
+        /// The geometries.
+        geometries: Vec<GeoShape>,
+    },
+
+    /// A bounding rectangle, given by its upper left and lower right
+    /// positions.
+    #[serde(rename = "envelope")]
+    Envelope {
+        /// The upper left and lower right positions.
+        coordinates: [Position; 2],
+    },
+}
+
+impl GeoShape {
+    /// The name of the type of this geometry, e.g. `Polygon`.
+    #[inline]
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            GeoShape::Point { .. } => "Point",
+            GeoShape::LineString { .. } => "LineString",
+            GeoShape::Polygon { .. } => "Polygon",
+            GeoShape::MultiPoint { .. } => "MultiPoint",
+            GeoShape::MultiLineString { .. } => "MultiLineString",
+            GeoShape::MultiPolygon { .. } => "MultiPolygon",
+            GeoShape::GeometryCollection { .. } => "GeometryCollection",
+            GeoShape::Envelope { .. } => "envelope",
+        }
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for GeoShape {
+    #[inline]
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        // only nests geometries one level deep
+        Ok(match u.choose_index(8)? {
+            7 => GeoShape::GeometryCollection {
+                geometries: (0..u.int_in_range(0..=2)?)
+                    .map(|_| simple_shape(u))
+                    .collect::<arbitrary::Result<_>>()?,
+            },
+            _ => simple_shape(u)?,
+        })
+    }
+}
+
+/// Returns an arbitrary geometry other than a `GeometryCollection`.
+#[cfg(feature = "arbitrary")]
+fn simple_shape(u: &mut arbitrary::Unstructured) -> arbitrary::Result<GeoShape> {
+    use crate::arbitrary::float;
+
+    fn position(u: &mut arbitrary::Unstructured) -> arbitrary::Result<Position> {
+        Ok([float(u)?, float(u)?])
+    }
+    fn positions(u: &mut arbitrary::Unstructured) -> arbitrary::Result<Vec<Position>> {
+        (0..u.int_in_range(1..=4)?).map(|_| position(u)).collect()
+    }
+    fn lines(u: &mut arbitrary::Unstructured) -> arbitrary::Result<Vec<Vec<Position>>> {
+        (0..u.int_in_range(1..=2)?).map(|_| positions(u)).collect()
+    }
+
+    Ok(match u.choose_index(7)? {
+        0 => GeoShape::Point {
+            coordinates: position(u)?,
+        },
+        1 => GeoShape::LineString {
+            coordinates: positions(u)?,
+        },
+        2 => GeoShape::Polygon {
+            coordinates: lines(u)?,
+        },
+        3 => GeoShape::MultiPoint {
+            coordinates: positions(u)?,
+        },
+        4 => GeoShape::MultiLineString {
+            coordinates: lines(u)?,
+        },
+        5 => GeoShape::MultiPolygon {
+            coordinates: (0..u.int_in_range(1..=2)?)
+                .map(|_| lines(u))
+                .collect::<arbitrary::Result<_>>()?,
+        },
+        _ => GeoShape::Envelope {
+            coordinates: [position(u)?, position(u)?],
+        },
+    })
+}
+
+#[cfg(feature = "graphql")]
+#[async_graphql::Scalar(name = "GeoShape")]
+impl async_graphql::ScalarType for GeoShape {
+    #[inline]
+    fn parse(value: async_graphql::Value) -> async_graphql::InputValueResult<Self> {
+        match value {
+            async_graphql::Value::Object(_) => Ok(serde_json::from_value(value.into_json()?)?),
+            _ => Err(async_graphql::InputValueError::expected_type(value)),
+        }
+    }
+
+    #[inline]
+    fn to_value(&self) -> async_graphql::Value {
+        serde_json::to_value(self)
+            .and_then(async_graphql::Value::from_json)
+            .unwrap_or(async_graphql::Value::Null)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn can_serialize_and_deserialize() {
+        let shape = GeoShape::GeometryCollection {
+            geometries: vec![
+                GeoShape::Point {
+                    coordinates: [100.0, 0.0],
+                },
+                GeoShape::Polygon {
+                    coordinates: vec![vec![[100.0, 0.0], [101.0, 0.0], [101.0, 1.0], [100.0, 0.0]]],
+                },
+            ],
+        };
+        let j = json!({
+            "type": "GeometryCollection",
+            "geometries": [
+                { "type": "Point", "coordinates": [100.0, 0.0] },
+                {
+                    "type": "Polygon",
+                    "coordinates": [[[100.0, 0.0], [101.0, 0.0], [101.0, 1.0], [100.0, 0.0]]]
+                }
+            ]
+        });
+
+        assert_eq!(serde_json::to_value(&shape).unwrap(), j);
+        assert_eq!(serde_json::from_value::<GeoShape>(j).unwrap(), shape);
+    }
+
+    #[test]
+    fn accepts_lowercase_types() {
+        let j = json!({ "type": "linestring", "coordinates": [[-77.0, 38.8], [-77.0, 38.9]] });
+        assert_eq!(
+            serde_json::from_value::<GeoShape>(j).unwrap(),
+            GeoShape::LineString {
+                coordinates: vec![[-77.0, 38.8], [-77.0, 38.9]]
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_invalid_shapes_is_err() {
+        for j in &[
+            json!({ "type": "Circle", "coordinates": [0.0, 0.0] }),
+            json!({ "type": "Point", "coordinates": [[0.0, 0.0]] }),
+            json!({ "coordinates": [0.0, 0.0] }),
+        ] {
+            assert!(
+                serde_json::from_value::<GeoShape>(j.clone()).is_err(),
+                "{}",
+                j
+            );
+        }
+    }
+}
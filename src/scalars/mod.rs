@@ -4,8 +4,10 @@ use std::collections::HashMap;
 
 use serde_json::Value as JsonValue;
 
-pub use self::sorted_value::*;
+pub use self::{geo_point::*, geo_shape::*, sorted_value::*};
 
+mod geo_point;
+mod geo_shape;
 mod sorted_value;
 
 // TODO: remove `Map` type alias; better way to conditionally compile?
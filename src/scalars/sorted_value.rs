@@ -1,17 +1,33 @@
-//! A scalar that represents a number or a string.
+//! A scalar that represents any value Elasticsearch may sort by.
 
 use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
 
-/// An int, float or a string value.
-#[allow(missing_docs)]
+/// A value Elasticsearch returned in a hit's `sort` array, e.g. an int, float,
+/// string, boolean or `null`.
+///
+/// Integers are kept as `u64`/`i64` so 64-bit values beyond `f64` precision
+/// survive a round trip through `search_after`; anything else is kept as
+/// arbitrary JSON.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 #[cfg_attr(feature = "graphql", derive(async_graphql::Description))]
 #[serde(untagged)]
 pub enum SortedValue {
+    /// A `null` value, e.g. for documents missing the sort field.
     Null,
+    /// A boolean value.
+    Bool(bool),
+    /// A non-negative integer, e.g. a `long` or `unsigned_long`.
     Int(u64),
+    /// A negative integer.
+    NegInt(i64),
+    /// A floating point number.
     Float(f64),
+    /// A string value.
     String(String),
+    /// Any other JSON value, e.g. an array or an object.
+    Json(JsonValue),
 }
 
 impl From<u8> for SortedValue {
@@ -35,6 +51,24 @@ impl From<u64> for SortedValue {
     }
 }
 
+impl From<i64> for SortedValue {
+    #[inline]
+    fn from(val: i64) -> Self {
+        if val < 0 {
+            SortedValue::NegInt(val)
+        } else {
+            SortedValue::Int(val as u64)
+        }
+    }
+}
+
+impl From<bool> for SortedValue {
+    #[inline]
+    fn from(val: bool) -> Self {
+        SortedValue::Bool(val)
+    }
+}
+
 impl From<f64> for SortedValue {
     #[inline]
     fn from(val: f64) -> Self {
@@ -49,6 +83,53 @@ impl From<String> for SortedValue {
     }
 }
 
+impl From<&str> for SortedValue {
+    #[inline]
+    fn from(val: &str) -> Self {
+        SortedValue::String(val.to_string())
+    }
+}
+
+impl From<JsonValue> for SortedValue {
+    #[inline]
+    fn from(val: JsonValue) -> Self {
+        match val {
+            JsonValue::Null => SortedValue::Null,
+            JsonValue::Bool(val) => SortedValue::Bool(val),
+            JsonValue::Number(ref num) => {
+                if let Some(v) = num.as_u64() {
+                    SortedValue::Int(v)
+                } else if let Some(v) = num.as_i64() {
+                    SortedValue::NegInt(v)
+                } else if let Some(v) = num.as_f64() {
+                    SortedValue::Float(v)
+                } else {
+                    SortedValue::Json(val)
+                }
+            }
+            JsonValue::String(val) => SortedValue::String(val),
+            JsonValue::Array(_) | JsonValue::Object(_) => SortedValue::Json(val),
+        }
+    }
+}
+
+impl From<SortedValue> for JsonValue {
+    #[inline]
+    fn from(val: SortedValue) -> Self {
+        match val {
+            SortedValue::Null => JsonValue::Null,
+            SortedValue::Bool(val) => JsonValue::Bool(val),
+            SortedValue::Int(val) => JsonValue::Number(val.into()),
+            SortedValue::NegInt(val) => JsonValue::Number(val.into()),
+            SortedValue::Float(val) => serde_json::Number::from_f64(val)
+                .map(JsonValue::Number)
+                .unwrap_or(JsonValue::Null),
+            SortedValue::String(val) => JsonValue::String(val),
+            SortedValue::Json(val) => val,
+        }
+    }
+}
+
 #[cfg(feature = "graphql")]
 #[async_graphql::Scalar(use_type_description)]
 impl async_graphql::ScalarType for SortedValue {
@@ -56,25 +137,24 @@ impl async_graphql::ScalarType for SortedValue {
     fn parse(value: async_graphql::Value) -> async_graphql::InputValueResult<Self> {
         match value {
             async_graphql::Value::Null => Ok(SortedValue::Null),
+            async_graphql::Value::Boolean(val) => Ok(SortedValue::Bool(val)),
             async_graphql::Value::Number(ref val) => {
                 if let Some(v) = val.as_u64() {
                     Ok(v.into())
+                } else if let Some(v) = val.as_i64() {
+                    Ok(v.into())
                 } else if let Some(v) = val.as_f64() {
-                    if v < 0.0 {
-                        Err(async_graphql::InputValueError::expected_type(value))
-                    } else {
-                        Ok(v.into())
-                    }
+                    Ok(v.into())
                 } else {
                     Err(async_graphql::InputValueError::expected_type(value))
                 }
             }
             async_graphql::Value::String(val) => Ok(SortedValue::String(val)),
-            async_graphql::Value::Object(_)
-            | async_graphql::Value::Binary(_)
-            | async_graphql::Value::Boolean(_)
-            | async_graphql::Value::Enum(_)
-            | async_graphql::Value::List(_) => {
+            async_graphql::Value::Object(_) | async_graphql::Value::List(_) => value
+                .into_json()
+                .map(SortedValue::Json)
+                .map_err(async_graphql::InputValueError::custom),
+            async_graphql::Value::Binary(_) | async_graphql::Value::Enum(_) => {
                 Err(async_graphql::InputValueError::expected_type(value))
             }
         }
@@ -84,7 +164,9 @@ impl async_graphql::ScalarType for SortedValue {
     fn to_value(&self) -> async_graphql::Value {
         match *self {
             SortedValue::Null => async_graphql::Value::Null,
+            SortedValue::Bool(val) => async_graphql::Value::Boolean(val),
             SortedValue::Int(val) => async_graphql::Value::Number(val.into()),
+            SortedValue::NegInt(val) => async_graphql::Value::Number(val.into()),
             SortedValue::Float(val) => {
                 let val = async_graphql::Number::from_f64(val).unwrap_or_else(|| {
                     // `NaN` and `infinite` values are not valid JSON
@@ -97,15 +179,78 @@ impl async_graphql::ScalarType for SortedValue {
                 async_graphql::Value::Number(val)
             }
             SortedValue::String(ref val) => async_graphql::Value::String(val.clone()),
+            SortedValue::Json(ref val) => async_graphql::Value::from_json(val.clone())
+                .unwrap_or_else(|err| {
+                    panic!(
+                        "invalid JSON value: `{}` encountered when converting \
+                         a `SortedValue` to a `graphql::Value`: {}",
+                        val, err
+                    )
+                }),
         }
     }
 }
 
 #[cfg(test)]
-#[cfg(feature = "graphql")]
 mod tests {
     use super::*;
 
+    macro_rules! test_case {
+        ($name:ident : $j:expr, $v:expr) => {
+            mod $name {
+                use super::*;
+
+                #[test]
+                fn can_serialize() {
+                    assert_eq!(serde_json::to_string(&$v).unwrap(), $j);
+                }
+
+                #[test]
+                fn can_deserialize() {
+                    let v: SortedValue = serde_json::from_str($j).unwrap();
+                    assert_eq!(v, $v);
+                }
+            }
+        };
+    }
+
+    test_case!(null: "null", SortedValue::Null);
+    test_case!(boolean: "true", SortedValue::Bool(true));
+    test_case!(u64_max: "18446744073709551615", SortedValue::Int(u64::MAX));
+    test_case!(i64_min: "-9223372036854775808", SortedValue::NegInt(i64::MIN));
+    test_case!(float: "-1.5", SortedValue::Float(-1.5));
+    test_case!(string: r#""x""#, SortedValue::from("x"));
+    test_case!(array: r#"[1,"x"]"#, SortedValue::Json(serde_json::json!([1, "x"])));
+
+    #[test]
+    fn from_json_value_is_lossless() {
+        let j: JsonValue = serde_json::from_str("[9007199254740993,-1,true,null]").unwrap();
+        let values: Vec<SortedValue> = j
+            .as_array()
+            .unwrap()
+            .iter()
+            .cloned()
+            .map(SortedValue::from)
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                SortedValue::Int(9007199254740993),
+                SortedValue::NegInt(-1),
+                SortedValue::Bool(true),
+                SortedValue::Null,
+            ]
+        );
+        let back: Vec<JsonValue> = values.into_iter().map(JsonValue::from).collect();
+        assert_eq!(JsonValue::Array(back), j);
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "graphql")]
+mod graphql_tests {
+    use super::*;
+
     use async_graphql::{ScalarType, Value as GraphQLValue};
     use serde_json::Number as JsonNumber;
 
@@ -143,18 +288,35 @@ mod tests {
         assert_eq!(val, SortedValue::Int(x));
     }
 
-    // TODO: implement `Eq` to assert we received the correct error
     #[test]
-    fn parse_negative_is_err() {
-        let json_number = JsonNumber::from_f64(-0.00000000000001).unwrap();
-        let result = SortedValue::parse(GraphQLValue::Number(json_number));
-        // use assert instead of `#[should_panic]` so we keep the output pretty with `--no-capture`
-        assert!(result.is_err());
+    fn can_parse_negative() {
+        let x: f64 = -0.00000000000001;
+        let json_number = JsonNumber::from_f64(x).unwrap();
+        let val = SortedValue::parse(GraphQLValue::Number(json_number)).unwrap();
+        assert_eq!(val, SortedValue::Float(x));
 
         let x: i64 = -101;
-        let result = SortedValue::parse(GraphQLValue::Number(x.into()));
-        // use assert instead of `#[should_panic]` so we keep the output pretty with `--no-capture`
-        assert!(result.is_err());
+        let val = SortedValue::parse(GraphQLValue::Number(x.into())).unwrap();
+        assert_eq!(val, SortedValue::NegInt(x));
+    }
+
+    #[test]
+    fn can_parse_bool() {
+        let val = SortedValue::parse(GraphQLValue::Boolean(true)).unwrap();
+        assert_eq!(val, SortedValue::Bool(true));
+    }
+
+    #[test]
+    fn can_parse_list() {
+        let val =
+            SortedValue::parse(GraphQLValue::List(vec![GraphQLValue::Number(1.into())])).unwrap();
+        assert_eq!(val, SortedValue::Json(serde_json::json!([1])));
+    }
+
+    #[test]
+    fn big_u64_round_trips_through_graphql() {
+        let x = SortedValue::Int(u64::MAX);
+        assert_eq!(SortedValue::parse(x.to_value()).unwrap(), x);
     }
 
     #[test]
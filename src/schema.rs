@@ -0,0 +1,102 @@
+//! Helpers for implementing [`JsonSchema`] for types that are (de)serialized
+//! by hand.
+//!
+//! [`JsonSchema`]: https://docs.rs/schemars/latest/schemars/trait.JsonSchema.html
+
+use std::collections::HashMap;
+
+use schemars::schema::{InstanceType, Schema, SchemaObject};
+use serde_json::Value as JsonValue;
+
+/// The schema of a [`crate::scalars::Map`], which doesn't implement
+/// `JsonSchema` itself when the `graphql` feature is enabled.
+pub(crate) type Map = HashMap<String, JsonValue>;
+
+/// Returns the schema of an object, e.g. the body of a query.
+#[inline]
+pub(crate) fn object<'a>(
+    properties: impl IntoIterator<Item = (&'a str, Schema)>,
+    required: &[&str],
+) -> SchemaObject {
+    let mut schema = SchemaObject {
+        instance_type: Some(InstanceType::Object.into()),
+        ..Default::default()
+    };
+
+    let object = schema.object();
+    object.properties.extend(
+        properties
+            .into_iter()
+            .map(|(name, schema)| (name.to_string(), schema)),
+    );
+    object
+        .required
+        .extend(required.iter().map(ToString::to_string));
+
+    schema
+}
+
+/// Returns the schema of an object with exactly one, arbitrarily named
+/// property (e.g. the field a query applies to) whose value matches `value`.
+#[inline]
+pub(crate) fn keyed(value: Schema) -> Schema {
+    let mut schema = object(None, &[]);
+
+    let object = schema.object();
+    object.min_properties = Some(1);
+    object.max_properties = Some(1);
+    object.additional_properties = Some(Box::new(value));
+
+    schema.into()
+}
+
+/// Returns the schema of a term, i.e. the value of e.g. a term query, which
+/// may be a string, number or boolean.
+#[inline]
+pub(crate) fn term() -> Schema {
+    SchemaObject {
+        instance_type: Some(
+            vec![
+                InstanceType::String,
+                InstanceType::Number,
+                InstanceType::Boolean,
+            ]
+            .into(),
+        ),
+        ..Default::default()
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use schemars::schema_for;
+    use serde_json::json;
+
+    use crate::{aggregation, search};
+
+    #[test]
+    fn can_generate_request_schemas() {
+        let schema = serde_json::to_value(schema_for!(search::Request)).unwrap();
+        for definition in &["CompoundQuery", "Query", "TermQuery", "Sort"] {
+            assert!(
+                schema["definitions"].get(definition).is_some(),
+                "missing {}",
+                definition
+            );
+        }
+
+        let schema = serde_json::to_value(schema_for!(aggregation::Request)).unwrap();
+        assert!(schema["definitions"].get("SubAggregation").is_some());
+    }
+
+    #[test]
+    fn field_keyed_queries_have_a_single_property() {
+        let schema = serde_json::to_value(schema_for!(search::query::TermQuery)).unwrap();
+
+        assert_eq!(schema["type"], json!("object"));
+        assert_eq!(schema["minProperties"], json!(1));
+        assert_eq!(schema["maxProperties"], json!(1));
+        assert_eq!(schema["additionalProperties"]["required"], json!(["value"]));
+    }
+}
@@ -0,0 +1,315 @@
+//! Borrowed variants of the search [response](super::OkResponse) types.
+//!
+//! These deserialize exactly like their owned counterparts but borrow their
+//! strings from the input where possible (i.e. when they contain no escape
+//! sequences), so parsing a large response doesn't copy every `_id`, `_index`
+//! and highlighted snippet.
+//!
+//! ```
+//! use elastiql::search::OkResponseRef;
+//!
+//! let json = r#"{
+//!     "took": 1,
+//!     "timed_out": false,
+//!     "hits": {
+//!         "total": { "relation": "eq", "value": 1 },
+//!         "hits": [{ "_id": "1", "_index": "logs", "_source": { "level": "error" } }]
+//!     }
+//! }"#;
+//!
+//! let response: OkResponseRef<serde_json::Value> = serde_json::from_str(json).unwrap();
+//! assert_eq!(response.hits.hits[0].id, "1");
+//! ```
+
+use std::{borrow::Cow, collections::HashMap};
+
+use serde::{Deserialize, Deserializer};
+
+use super::{Count, Hit, Hits, OkResponse};
+
+/// A borrowed [`OkResponse`].
+#[derive(Deserialize, Debug)]
+pub struct OkResponseRef<'a, T> {
+    /// Time it took for the database to process the request.
+    pub took: u64,
+
+    /// Whether or not the database request timed out before completing.
+    pub timed_out: bool,
+
+    /// The hits matched by the search query.
+    #[serde(borrow)]
+    pub hits: HitsRef<'a, T>,
+}
+
+impl<T> OkResponseRef<'_, T> {
+    /// Converts this into an owned [`OkResponse`], only cloning the strings
+    /// that are borrowed.
+    #[inline]
+    pub fn into_owned(self) -> OkResponse<T> {
+        OkResponse {
+            took: self.took,
+            timed_out: self.timed_out,
+            hits: self.hits.into_owned(),
+        }
+    }
+}
+
+/// Borrowed [`Hits`].
+#[derive(Deserialize, Debug)]
+pub struct HitsRef<'a, T> {
+    /// The total count of the hits/matches.
+    #[serde(default, rename = "total")]
+    pub total_count: Count,
+
+    /// The maximum score for any of the hits/matches.
+    #[serde(default)]
+    pub max_score: Option<f32>,
+
+    // XXX: must be `Vec::new` instead of normal `default`
+    /// The search hits.
+    #[serde(borrow, default = "Vec::new")]
+    pub hits: Vec<HitRef<'a, T>>,
+}
+
+impl<T> HitsRef<'_, T> {
+    /// Converts these into owned [`Hits`], only cloning the strings that are
+    /// borrowed.
+    #[inline]
+    pub fn into_owned(self) -> Hits<T> {
+        Hits {
+            total_count: self.total_count,
+            max_score: self.max_score,
+            hits: self.hits.into_iter().map(HitRef::into_owned).collect(),
+        }
+    }
+}
+
+/// A borrowed [`Hit`].
+///
+/// **Note**: the `_source` is deserialized as a `T`, which may itself borrow
+/// from the input, e.g. a struct of `&'a str`s.
+#[derive(Deserialize, Debug)]
+pub struct HitRef<'a, T> {
+    /// The database Id of this `Document`.
+    #[serde(borrow, rename = "_id")]
+    pub id: Cow<'a, str>,
+
+    /// The name of the database index that this `Document` belongs to.
+    #[serde(borrow, rename = "_index")]
+    pub index: Cow<'a, str>,
+
+    /// The actual `Document` of this search hit/match.
+    #[serde(rename = "_source")]
+    pub source: T,
+
+    /// The [version] number of this `Document`.
+    ///
+    /// [version]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-index_.html#index-versioning
+    #[serde(rename = "_version")]
+    pub version: Option<u64>,
+
+    /// The sequence number number of this `Document`, used for
+    /// [optimistic concurrency control].
+    ///
+    /// [optimistic concurrency control]: https://www.elastic.co/guide/en/elasticsearch/reference/current/optimistic-concurrency-control.html
+    #[serde(rename = "_seq_no")]
+    pub sequence_number: Option<u64>,
+
+    /// The primary term of this `Document`, used for
+    /// [optimistic concurrency control].
+    ///
+    /// [optimistic concurrency control]: https://www.elastic.co/guide/en/elasticsearch/reference/current/optimistic-concurrency-control.html
+    #[serde(rename = "_primary_term")]
+    pub primary_term: Option<u64>,
+
+    /// The relevance score for this search hit.
+    #[serde(rename = "_score")]
+    pub score: Option<f32>,
+
+    /// The [highlighted] snippets of the part(s) of the field(s) matching the
+    /// search query.
+    ///
+    /// [highlighted]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-request-highlighting.html
+    #[serde(borrow, default, deserialize_with = "deserialize_highlight")]
+    pub highlight: HashMap<Cow<'a, str>, Vec<Cow<'a, str>>>,
+
+    /// The values of the requested [`script_fields`], by name.
+    ///
+    /// [`script_fields`]: crate::search::Request::script_fields
+    #[serde(default)]
+    pub fields: HashMap<String, Vec<serde_json::Value>>,
+
+    /// The live cursor from which to search after to fascilitate [pagination].
+    ///
+    /// [pagination]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-request-body.html#request-body-search-search-after
+    #[serde(default)]
+    pub sort: Vec<serde_json::Value>,
+}
+
+impl<T> HitRef<'_, T> {
+    /// Converts this into an owned [`Hit`], only cloning the strings that are
+    /// borrowed.
+    #[inline]
+    pub fn into_owned(self) -> Hit<T> {
+        Hit {
+            id: self.id.into_owned(),
+            index: self.index.into_owned(),
+            source: self.source,
+            version: self.version,
+            sequence_number: self.sequence_number,
+            primary_term: self.primary_term,
+            score: self.score,
+            highlight: self
+                .highlight
+                .into_iter()
+                .map(|(field, snippets)| {
+                    let snippets = snippets.into_iter().map(Cow::into_owned).collect();
+                    (field.into_owned(), snippets)
+                })
+                .collect(),
+            fields: self.fields,
+            sort: self.sort,
+        }
+    }
+}
+
+/// A string that borrows from the input where possible.
+///
+/// Unlike a field of type `Cow<str>`, a `Cow<str>` nested in e.g. a `Vec` or
+/// the keys of a `HashMap` is always owned when deserialized by serde.
+#[derive(Deserialize, PartialEq, Eq, Hash)]
+struct BorrowedStr<'a>(#[serde(borrow)] Cow<'a, str>);
+
+/// The borrowed highlighted snippets of a [`HitRef`], by field name.
+type Highlight<'a> = HashMap<Cow<'a, str>, Vec<Cow<'a, str>>>;
+
+/// Deserializes the `highlight` of a [`HitRef`], borrowing its field names and
+/// snippets.
+#[inline]
+fn deserialize_highlight<'de, D>(deserializer: D) -> Result<Highlight<'de>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let highlight = HashMap::<BorrowedStr<'de>, Vec<BorrowedStr<'de>>>::deserialize(deserializer)?;
+    Ok(highlight
+        .into_iter()
+        .map(|(field, snippets)| {
+            let snippets = snippets.into_iter().map(|snippet| snippet.0).collect();
+            (field.0, snippets)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A document that borrows its fields from the response.
+    #[derive(Deserialize, Debug)]
+    struct Document<'a> {
+        #[serde(borrow)]
+        title: Cow<'a, str>,
+    }
+
+    #[test]
+    fn borrows_strings_without_escapes() {
+        let json = r#"{
+            "took": 3,
+            "timed_out": false,
+            "hits": {
+                "total": { "relation": "eq", "value": 2 },
+                "max_score": 1.5,
+                "hits": [
+                    {
+                        "_id": "1",
+                        "_index": "books",
+                        "_score": 1.5,
+                        "_source": { "title": "Dune" },
+                        "highlight": { "title": ["<em>Dune</em>"] }
+                    },
+                    {
+                        "_id": "2",
+                        "_index": "books",
+                        "_score": 0.5,
+                        "_source": { "title": "\"Emma\"" }
+                    }
+                ]
+            }
+        }"#;
+
+        let response: OkResponseRef<Document> = serde_json::from_str(json).unwrap();
+        let hits = &response.hits.hits;
+        assert!(matches!(hits[0].id, Cow::Borrowed("1")));
+        assert!(matches!(hits[0].index, Cow::Borrowed("books")));
+        assert!(matches!(hits[0].source.title, Cow::Borrowed("Dune")));
+        assert!(matches!(
+            hits[0].highlight["title"][0],
+            Cow::Borrowed("<em>Dune</em>")
+        ));
+        assert!(matches!(hits[1].source.title, Cow::Owned(ref title) if title == "\"Emma\""));
+
+        let response = response.into_owned();
+        assert_eq!(response.hits.total_count.value, 2);
+        assert_eq!(response.hits.hits[0].id, "1");
+        assert_eq!(
+            response.hits.hits[0].highlight["title"],
+            vec!["<em>Dune</em>"]
+        );
+    }
+
+    /// Compares parsing a large response with and without borrowing, e.g.
+    /// `cargo test --release measure_borrowed_parsing -- --ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn measure_borrowed_parsing() {
+        use std::time::Instant;
+
+        /// The owned counterpart of `Document`.
+        #[derive(Deserialize, Debug)]
+        struct OwnedDocument {
+            title: String,
+        }
+
+        let hits: Vec<_> = (0..10_000)
+            .map(|i| {
+                serde_json::json!({
+                    "_id": format!("book-{:08}", i),
+                    "_index": "books",
+                    "_score": 1.0,
+                    "_source": { "title": format!("The adventures of book number {}", i) },
+                    "highlight": { "title": [format!("The <em>adventures</em> of book {}", i)] }
+                })
+            })
+            .collect();
+        let json = serde_json::json!({
+            "took": 1,
+            "timed_out": false,
+            "hits": { "total": { "relation": "eq", "value": 10_000 }, "hits": hits }
+        })
+        .to_string();
+
+        let expected = "The adventures of book number 9999";
+
+        let iterations = 100;
+        let start = Instant::now();
+        for _ in 0..iterations {
+            let response: OkResponse<OwnedDocument> = serde_json::from_str(&json).unwrap();
+            assert_eq!(response.hits.hits[9_999].source.title, expected);
+        }
+        let owned = start.elapsed() / iterations;
+
+        let start = Instant::now();
+        for _ in 0..iterations {
+            let response: OkResponseRef<Document> = serde_json::from_str(&json).unwrap();
+            assert_eq!(response.hits.hits[9_999].source.title, expected);
+        }
+        let borrowed = start.elapsed() / iterations;
+
+        println!(
+            "parsing {} bytes: owned {:?}, borrowed {:?}",
+            json.len(),
+            owned,
+            borrowed
+        );
+    }
+}
@@ -0,0 +1,181 @@
+//! [Relay]-style connections for paginating through search results in a
+//! GraphQL API.
+//!
+//! [Relay]: https://relay.dev/graphql/connections.htm
+
+use async_graphql::connection::{Connection, Edge, EmptyFields};
+
+pub use async_graphql::connection::PageInfo;
+
+use crate::search::{Count, Cursor, OkResponse, Request};
+
+/// A page of search hits, e.g. `DocumentConnection` for a `Document` node.
+///
+/// See [`OkResponse::into_connection`].
+pub type SearchConnection<T> = Connection<Cursor, T, SearchConnectionFields, EmptyFields>;
+
+/// A search hit in a [`SearchConnection`] along with its cursor.
+pub type SearchEdge<T> = Edge<Cursor, T, EmptyFields>;
+
+/// The fields of a [`SearchConnection`] besides its `edges` and `pageInfo`.
+#[cfg_attr(
+    feature = "graphql",
+    elastiql_derive::graphql_name("SearchConnectionFields")
+)]
+#[derive(async_graphql::SimpleObject, Debug)]
+pub struct SearchConnectionFields {
+    /// The total count of the hits/matches.
+    pub total_count: Count,
+}
+
+impl<T> OkResponse<T> {
+    /// Converts the hits of this response to `request` into a page of a
+    /// [`SearchConnection`], with a [`Cursor`] for each hit.
+    ///
+    /// There is assumed to be a previous page if `request` has an `after`
+    /// cursor, and a next page if this page is full, i.e. has `size` hits
+    /// (Elasticsearch returns `10` by default), unless the total count says
+    /// otherwise.
+    pub fn into_connection(self, request: &Request) -> SearchConnection<T> {
+        let hits = self.hits.hits;
+        let total_count = self.hits.total_count;
+
+        let returned = hits.len() as u64;
+        let has_next_page =
+            returned > 0 && returned >= request.size.unwrap_or(10) && total_count.value > returned;
+
+        let mut connection = SearchConnection::with_additional_fields(
+            !request.after.is_empty(),
+            has_next_page,
+            SearchConnectionFields { total_count },
+        );
+        connection.append(
+            hits.into_iter()
+                .map(|hit| SearchEdge::new(Cursor::for_hit(request, &hit), hit.source)),
+        );
+
+        connection
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema, SimpleObject};
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(SimpleObject, Deserialize)]
+    struct Document {
+        title: String,
+    }
+
+    fn response(total: u64) -> OkResponse<Document> {
+        serde_json::from_value(json!({
+            "took": 1,
+            "timed_out": false,
+            "hits": {
+                "total": { "relation": "eq", "value": total },
+                "hits": [
+                    {
+                        "_id": "1",
+                        "_index": "docs",
+                        "_source": { "title": "a" },
+                        "_version": null,
+                        "_seq_no": null,
+                        "_primary_term": null,
+                        "_score": null,
+                        "sort": [1]
+                    },
+                    {
+                        "_id": "2",
+                        "_index": "docs",
+                        "_source": { "title": "b" },
+                        "_version": null,
+                        "_seq_no": null,
+                        "_primary_term": null,
+                        "_score": null,
+                        "sort": [2]
+                    }
+                ]
+            }
+        }))
+        .unwrap()
+    }
+
+    struct Query(Request, u64);
+
+    #[Object]
+    impl Query {
+        async fn documents(&self) -> SearchConnection<Document> {
+            response(self.1).into_connection(&self.0)
+        }
+    }
+
+    async fn execute(request: Request, total: u64, query: &str) -> serde_json::Value {
+        let schema = Schema::new(Query(request, total), EmptyMutation, EmptySubscription);
+        let response = schema.execute(query).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        response.data.into_json().unwrap()
+    }
+
+    fn request(size: u64) -> Request {
+        Request {
+            size: Some(size),
+            ..Request::builder().build()
+        }
+    }
+
+    #[tokio::test]
+    async fn can_resolve_connection() {
+        let data = execute(
+            request(2),
+            3,
+            "{ documents { totalCount { value } pageInfo { hasPreviousPage hasNextPage } \
+             edges { node { title } } } }",
+        )
+        .await;
+
+        assert_eq!(
+            data,
+            json!({
+                "documents": {
+                    "totalCount": { "value": 3 },
+                    "pageInfo": { "hasPreviousPage": false, "hasNextPage": true },
+                    "edges": [{ "node": { "title": "a" } }, { "node": { "title": "b" } }]
+                }
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn can_paginate_with_end_cursor() {
+        let data = execute(request(2), 3, "{ documents { pageInfo { endCursor } } }").await;
+        let token = data["documents"]["pageInfo"]["endCursor"].as_str().unwrap();
+
+        let mut next = request(2);
+        next.set_cursor(token).unwrap();
+        assert_eq!(next.after, vec![2_u64.into()]);
+    }
+
+    #[tokio::test]
+    async fn has_no_next_page_after_last_hit() {
+        let request = Request {
+            after: vec![0_u64.into()],
+            ..request(2)
+        };
+        let data = execute(
+            request,
+            2,
+            "{ documents { pageInfo { hasPreviousPage hasNextPage } } }",
+        )
+        .await;
+
+        assert_eq!(
+            data["documents"]["pageInfo"],
+            json!({ "hasPreviousPage": true, "hasNextPage": false })
+        );
+    }
+}
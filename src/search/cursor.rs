@@ -0,0 +1,315 @@
+//! Opaque cursors for paginating through search results with [`search_after`].
+//!
+//! [`search_after`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/paginate-search-results.html#search-after
+
+use std::{error::Error, fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    scalars::SortedValue,
+    search::{Hit, OkResponse, Request, Response, Sort},
+};
+
+/// An opaque token that points at a hit in the (sorted) results of a search,
+/// from which the next page of results can be requested.
+///
+/// A `Cursor` packs the `sort` of the request along with the `sort` values of
+/// the hit, so that it can be checked that the same `sort` accompanies the
+/// cursor when it is used; `search_after` silently returns the wrong results
+/// otherwise.
+///
+/// ```
+/// use elastiql::search::{Cursor, Request};
+///
+/// let request: Request = Request::builder()
+///     .sort(vec![serde_json::from_str(r#"{ "created": { "order": "desc" } }"#).unwrap()])
+///     .build();
+///
+/// let token = Cursor::new(request.sort.clone(), vec![1_614_556_800_000_u64.into()]).encode();
+///
+/// let mut next = request.clone();
+/// next.set_cursor(&token).unwrap();
+/// assert_eq!(next.after, vec![1_614_556_800_000_u64.into()]);
+///
+/// let mut other = Request::builder().build();
+/// assert!(other.set_cursor(&token).is_err());
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Cursor {
+    /// The sort of the request the hit was returned for.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    sort: Vec<Sort>,
+
+    /// The sort values of the hit.
+    after: Vec<SortedValue>,
+}
+
+impl Cursor {
+    /// Constructs a new `Cursor` pointing at the hit with the `after` sort
+    /// values in the results of a request sorted by `sort`.
+    #[inline]
+    pub fn new(sort: impl Into<Vec<Sort>>, after: impl Into<Vec<SortedValue>>) -> Self {
+        Cursor {
+            sort: sort.into(),
+            after: after.into(),
+        }
+    }
+
+    /// Constructs a new `Cursor` pointing at `hit` in the results of `request`.
+    #[inline]
+    pub fn for_hit<T>(request: &Request, hit: &Hit<T>) -> Self {
+        Cursor::new(
+            request.sort.clone(),
+            hit.sort
+                .iter()
+                .cloned()
+                .map(SortedValue::from)
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// The sort of the request this cursor points into the results of.
+    #[inline]
+    pub fn sort(&self) -> &[Sort] {
+        &self.sort
+    }
+
+    /// The sort values of the hit this cursor points at.
+    #[inline]
+    pub fn after(&self) -> &[SortedValue] {
+        &self.after
+    }
+
+    /// Encodes this cursor as an opaque, URL safe token.
+    pub fn encode(&self) -> String {
+        // serializing a `Cursor` can't fail; all of the map keys are strings
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        base64::encode_config(json, base64::URL_SAFE_NO_PAD)
+    }
+
+    /// Decodes a token returned by [`Cursor::encode`].
+    pub fn decode(token: &str) -> Result<Self, CursorError> {
+        let json = base64::decode_config(token, base64::URL_SAFE_NO_PAD)
+            .map_err(|_| CursorError::Malformed)?;
+        serde_json::from_slice(&json).map_err(|_| CursorError::Malformed)
+    }
+
+    /// Returns the sort values of the hit this cursor points at, if it points
+    /// into the results of a request sorted by `sort`.
+    pub fn into_after(self, sort: &[Sort]) -> Result<Vec<SortedValue>, CursorError> {
+        if self.sort != sort {
+            return Err(CursorError::SortMismatch);
+        }
+
+        Ok(self.after)
+    }
+}
+
+impl fmt::Display for Cursor {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.encode())
+    }
+}
+
+impl FromStr for Cursor {
+    type Err = CursorError;
+
+    #[inline]
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        Cursor::decode(token)
+    }
+}
+
+/// The reason a [`Cursor`] couldn't be used.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CursorError {
+    /// The token isn't an encoded `Cursor`.
+    Malformed,
+    /// The cursor points into the results of a request with a different
+    /// `sort`.
+    SortMismatch,
+}
+
+impl fmt::Display for CursorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CursorError::Malformed => f.write_str("malformed cursor"),
+            CursorError::SortMismatch => {
+                f.write_str("cursor was created for a request with a different sort")
+            }
+        }
+    }
+}
+
+impl Error for CursorError {}
+
+#[cfg(feature = "graphql")]
+impl async_graphql::connection::CursorType for Cursor {
+    type Error = CursorError;
+
+    #[inline]
+    fn decode_cursor(token: &str) -> Result<Self, Self::Error> {
+        Cursor::decode(token)
+    }
+
+    #[inline]
+    fn encode_cursor(&self) -> String {
+        self.encode()
+    }
+}
+
+impl Request {
+    /// Decodes the `token` of a [`Cursor`] and sets `after` to its sort
+    /// values, as long as it was created for a request with the same `sort`.
+    pub fn set_cursor(&mut self, token: &str) -> Result<(), CursorError> {
+        self.after = Cursor::decode(token)?.into_after(&self.sort)?;
+        Ok(())
+    }
+}
+
+impl<T> OkResponse<T> {
+    /// Returns a copy of `request`, which this is the response to, for the
+    /// next page of results, i.e. with `after` set to the sort values of the
+    /// last hit.
+    ///
+    /// Returns `None` if this is the last page, i.e. there are fewer hits than
+    /// the `size` of the request, or if the last hit has no sort values, e.g.
+    /// because the request isn't sorted.
+    ///
+    /// ```
+    /// # use elastiql::search::{OkResponse, Request};
+    /// # fn search(_: &Request) -> OkResponse<serde_json::Value> {
+    /// #     serde_json::from_value(serde_json::json!({
+    /// #         "took": 1, "timed_out": false, "hits": { "hits": [] }
+    /// #     }))
+    /// #     .unwrap()
+    /// # }
+    /// let mut request = Request::builder().build();
+    /// while let Some(next) = search(&request).next_request(&request) {
+    ///     request = next;
+    /// }
+    /// ```
+    pub fn next_request(&self, request: &Request) -> Option<Request> {
+        let last = self.hits.hits.last()?;
+        let is_last_page =
+            matches!(request.size, Some(size) if (self.hits.hits.len() as u64) < size);
+        if is_last_page || last.sort.is_empty() {
+            return None;
+        }
+
+        let mut next = request.clone();
+        next.after = Cursor::for_hit(request, last).after;
+        Some(next)
+    }
+}
+
+impl<T> Response<T> {
+    /// Returns a copy of `request`, which this is the response to, for the
+    /// next page of results, or `None` if this is an error or the last page.
+    ///
+    /// See [`OkResponse::next_request`].
+    #[inline]
+    pub fn next_request(&self, request: &Request) -> Option<Request> {
+        match self {
+            Response::Ok(response) => response.next_request(request),
+            Response::Err { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    fn sort() -> Vec<Sort> {
+        serde_json::from_value(
+            json!([{ "created": { "order": "desc" } }, { "id": { "order": "asc" } }]),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn can_round_trip() {
+        let cursor = Cursor::new(sort(), vec![SortedValue::Int(u64::MAX), "abc".into()]);
+
+        let token = cursor.encode();
+        assert!(token
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        assert_eq!(token, cursor.to_string());
+        assert_eq!(Cursor::decode(&token).unwrap(), cursor);
+        assert_eq!(token.parse::<Cursor>().unwrap(), cursor);
+    }
+
+    #[test]
+    fn can_create_from_hit() {
+        let hit: Hit<serde_json::Value> = serde_json::from_value(json!({
+            "_id": "1",
+            "_index": "logs",
+            "_source": {},
+            "_version": null,
+            "_seq_no": null,
+            "_primary_term": null,
+            "_score": null,
+            "sort": [1_614_556_800_000_u64, "1"]
+        }))
+        .unwrap();
+        let request = Request {
+            sort: sort(),
+            ..Request::builder().build()
+        };
+
+        let cursor = Cursor::for_hit(&request, &hit);
+        assert_eq!(cursor.sort(), &sort()[..]);
+        assert_eq!(cursor.after(), &[1_614_556_800_000_u64.into(), "1".into()]);
+    }
+
+    #[test]
+    fn can_request_the_next_page() {
+        let request = Request {
+            sort: sort(),
+            size: Some(2),
+            ..Request::builder().build()
+        };
+        let page = |hits: serde_json::Value| -> OkResponse<serde_json::Value> {
+            serde_json::from_value(
+                json!({ "took": 1, "timed_out": false, "hits": { "hits": hits } }),
+            )
+            .unwrap()
+        };
+        let hit = |id: &str, created: u64| json!({ "_id": id, "_index": "logs", "_source": {}, "sort": [created, id] });
+
+        let next = page(json!([hit("1", 2), hit("2", 1)]))
+            .next_request(&request)
+            .unwrap();
+        assert_eq!(next.after, vec![1_u64.into(), "2".into()]);
+        assert_eq!((&next.sort, next.size), (&request.sort, request.size));
+
+        // the last page, either short or empty
+        assert_eq!(page(json!([hit("3", 0)])).next_request(&next), None);
+        assert_eq!(page(json!([])).next_request(&next), None);
+    }
+
+    #[test]
+    fn rejects_malformed_tokens() {
+        assert_eq!(Cursor::decode("not a cursor"), Err(CursorError::Malformed));
+        assert_eq!(Cursor::decode("e30"), Err(CursorError::Malformed));
+    }
+
+    #[test]
+    fn rejects_different_sorts() {
+        let token = Cursor::new(sort(), vec![1_u64.into(), "1".into()]).encode();
+
+        let mut request = Request::builder().build();
+        assert_eq!(request.set_cursor(&token), Err(CursorError::SortMismatch));
+        assert!(request.after.is_empty());
+
+        request.sort = sort();
+        request.set_cursor(&token).unwrap();
+        assert_eq!(request.after, vec![1_u64.into(), "1".into()]);
+    }
+}
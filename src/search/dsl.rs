@@ -0,0 +1,566 @@
+//! Short constructors and combinators for composing [`Query`] values.
+//!
+//! ```
+//! use elastiql::search::dsl::{self, QueryExt};
+//!
+//! let query = dsl::term("status", "open")
+//!     .and(dsl::range("age").gte(18))
+//!     .or(dsl::exists("admin").boost(2.0))
+//!     .and(dsl::prefix("user", "ki").not());
+//! ```
+//!
+//! The same combinators are available as the `&`, `|` and `!` operators:
+//!
+//! ```
+//! use elastiql::search::dsl;
+//!
+//! let query = dsl::term("status", "open")
+//!     & (dsl::exists("admin") | dsl::range("age").gte(18))
+//!     & !dsl::prefix("user", "ki");
+//! ```
+
+use crate::scalars::{GeoPoint, GeoShape, Map};
+use crate::search::query::span::{
+    SpanContainingQuery, SpanFirstQuery, SpanMultiQuery, SpanNearQuery, SpanNotQuery, SpanOrQuery,
+    SpanQuery, SpanTermQuery, SpanWithinQuery,
+};
+use crate::search::query::{
+    BooleanQuery, CompoundQuery, ExistsQuery, FuzzyQuery, GeoBoundingBoxQuery, GeoDistanceQuery,
+    GeoPolygonQuery, GeoShapeQuery, IdsQuery, KnnQuery, LikeItem, MatchPhrasePrefixQuery,
+    MatchPhraseQuery, MatchQuery, MoreLikeThisQuery, MultiMatchQuery, NestedQuery, PercolateQuery,
+    PrefixQuery, Query, QueryStringQuery, RangeQuery, RankFeatureQuery, RegexpQuery, ScriptQuery,
+    ScriptScoreQuery, SimpleQueryStringQuery, TermQuery, TermsQuery, TermsSetQuery, WildcardQuery,
+};
+use crate::search::Script;
+
+/// Returns a [`TermQuery`] for an **exact** `value` in `field`.
+#[inline]
+pub fn term(field: impl Into<String>, value: impl Into<String>) -> TermQuery {
+    TermQuery::new(field, value)
+}
+
+/// Returns a [`TermsQuery`] for one or more **exact** `values` in `field`.
+#[inline]
+pub fn terms<T: Into<String>>(
+    field: impl Into<String>,
+    values: impl IntoIterator<Item = T>,
+) -> TermsQuery {
+    TermsQuery::new(field, values)
+}
+
+/// Returns a [`TermsSetQuery`] for documents with as many of the **exact**
+/// `terms` in `field` as their `minimum_should_match_field`.
+#[inline]
+pub fn terms_set<T: Into<String>>(
+    field: impl Into<String>,
+    terms: impl IntoIterator<Item = T>,
+    minimum_should_match_field: impl Into<String>,
+) -> TermsSetQuery {
+    TermsSetQuery::new(field, terms, minimum_should_match_field)
+}
+
+/// Returns an [`IdsQuery`] for the documents with one of the `_id`s in
+/// `values`.
+#[inline]
+pub fn ids<T: Into<String>>(values: impl IntoIterator<Item = T>) -> IdsQuery {
+    IdsQuery::new(values)
+}
+
+/// Returns an unbounded [`RangeQuery`] on `field`; use e.g.
+/// [`RangeQuery::gte`] to set its bounds.
+#[inline]
+pub fn range(field: impl Into<String>) -> RangeQuery {
+    RangeQuery::new(field)
+}
+
+/// Returns an [`ExistsQuery`] for documents with an indexed value for `field`.
+#[inline]
+pub fn exists(field: impl Into<String>) -> ExistsQuery {
+    ExistsQuery::new(field)
+}
+
+/// Returns a [`PrefixQuery`] for `field` values starting with `value`.
+#[inline]
+pub fn prefix(field: impl Into<String>, value: impl Into<String>) -> PrefixQuery {
+    PrefixQuery::new(field, value)
+}
+
+/// Returns a [`WildcardQuery`] for `field` values matching the wildcard
+/// pattern `value`.
+#[inline]
+pub fn wildcard(field: impl Into<String>, value: impl Into<String>) -> WildcardQuery {
+    WildcardQuery::new(field, value)
+}
+
+/// Returns a [`RegexpQuery`] for `field` values matching the regular
+/// expression `value`.
+#[inline]
+pub fn regexp(field: impl Into<String>, value: impl Into<String>) -> RegexpQuery {
+    RegexpQuery::new(field, value, None::<String>)
+}
+
+/// Returns a [`FuzzyQuery`] for `field` values similar to `value`.
+#[inline]
+pub fn fuzzy(field: impl Into<String>, value: impl Into<String>) -> FuzzyQuery {
+    FuzzyQuery::new(field, value)
+}
+
+/// Returns a full text [`MatchQuery`] of `query` on `field`.
+#[inline]
+pub fn match_(field: impl Into<String>, query: impl Into<String>) -> MatchQuery {
+    MatchQuery::new(field, query)
+}
+
+/// Returns a [`MatchPhraseQuery`] of the phrase `query` on `field`.
+#[inline]
+pub fn match_phrase(field: impl Into<String>, query: impl Into<String>) -> MatchPhraseQuery {
+    MatchPhraseQuery::new(field, query)
+}
+
+/// Returns a [`MatchPhrasePrefixQuery`] of `query` on `field`, whose last term
+/// is used as a prefix.
+#[inline]
+pub fn match_phrase_prefix(
+    field: impl Into<String>,
+    query: impl Into<String>,
+) -> MatchPhrasePrefixQuery {
+    MatchPhrasePrefixQuery::new(field, query)
+}
+
+/// Returns a [`MultiMatchQuery`] of `query` on `fields`.
+#[inline]
+pub fn multi_match<T: Into<String>>(
+    fields: impl IntoIterator<Item = T>,
+    query: impl Into<String>,
+) -> MultiMatchQuery {
+    MultiMatchQuery::new(fields, query)
+}
+
+/// Returns a [`MoreLikeThisQuery`] for documents like the texts and/or
+/// documents in `like` on `fields`.
+#[inline]
+pub fn more_like_this<T: Into<String>>(
+    fields: impl IntoIterator<Item = T>,
+    like: impl IntoIterator<Item = LikeItem>,
+) -> MoreLikeThisQuery {
+    MoreLikeThisQuery::new(fields, like)
+}
+
+/// Returns a [`PercolateQuery`] for the queries stored in `field` matching
+/// `document`.
+#[inline]
+pub fn percolate(field: impl Into<String>, document: Map) -> PercolateQuery {
+    PercolateQuery::new(field, document)
+}
+
+/// Returns a [`ScriptQuery`] for the documents `script` returns `true` for.
+#[inline]
+pub fn script(script: Script) -> ScriptQuery {
+    ScriptQuery::new(script)
+}
+
+/// Returns a [`ScriptScoreQuery`] scoring the documents returned by `query`
+/// with `script`.
+#[inline]
+pub fn script_score(query: impl Into<CompoundQuery>, script: Script) -> ScriptScoreQuery {
+    ScriptScoreQuery::new(query, script)
+}
+
+/// Returns a [`GeoDistanceQuery`] for the points in `field` within `distance`
+/// of `point`.
+#[inline]
+pub fn geo_distance(
+    field: impl Into<String>,
+    point: GeoPoint,
+    distance: impl Into<String>,
+) -> GeoDistanceQuery {
+    GeoDistanceQuery::new(field, point, distance)
+}
+
+/// Returns a [`GeoBoundingBoxQuery`] for the points in `field` within the
+/// bounding box from `top_left` to `bottom_right`.
+#[inline]
+pub fn geo_bounding_box(
+    field: impl Into<String>,
+    top_left: GeoPoint,
+    bottom_right: GeoPoint,
+) -> GeoBoundingBoxQuery {
+    GeoBoundingBoxQuery::new(field, top_left, bottom_right)
+}
+
+/// Returns a [`GeoShapeQuery`] for the shapes in `field` that intersect
+/// `shape`.
+#[inline]
+pub fn geo_shape(field: impl Into<String>, shape: GeoShape) -> GeoShapeQuery {
+    GeoShapeQuery::new(field, shape)
+}
+
+/// Returns a [`GeoPolygonQuery`] for the points in `field` within the polygon
+/// with the vertices `points`.
+#[inline]
+pub fn geo_polygon(field: impl Into<String>, points: Vec<GeoPoint>) -> GeoPolygonQuery {
+    GeoPolygonQuery::new(field, points)
+}
+
+/// Returns a [`SpanTermQuery`] for the spans of an **exact** `value` in
+/// `field`.
+#[inline]
+pub fn span_term(field: impl Into<String>, value: impl Into<String>) -> SpanTermQuery {
+    SpanTermQuery::new(field, value)
+}
+
+/// Returns a [`SpanNearQuery`] for the spans of `clauses` with at most `slop`
+/// unmatched positions between them.
+#[inline]
+pub fn span_near<T: Into<SpanQuery>>(
+    clauses: impl IntoIterator<Item = T>,
+    slop: u32,
+) -> SpanNearQuery {
+    SpanNearQuery::new(clauses, slop)
+}
+
+/// Returns a [`SpanFirstQuery`] for the spans of `match_` which end at most at
+/// position `end`.
+#[inline]
+pub fn span_first(match_: impl Into<SpanQuery>, end: u32) -> SpanFirstQuery {
+    SpanFirstQuery::new(match_, end)
+}
+
+/// Returns a [`SpanOrQuery`] for the spans of any of `clauses`.
+#[inline]
+pub fn span_or<T: Into<SpanQuery>>(clauses: impl IntoIterator<Item = T>) -> SpanOrQuery {
+    SpanOrQuery::new(clauses)
+}
+
+/// Returns a [`SpanNotQuery`] for the spans of `include` which don't overlap
+/// the spans of `exclude`.
+#[inline]
+pub fn span_not(include: impl Into<SpanQuery>, exclude: impl Into<SpanQuery>) -> SpanNotQuery {
+    SpanNotQuery::new(include, exclude)
+}
+
+/// Returns a [`SpanContainingQuery`] for the spans of `big` which contain a
+/// span of `little`.
+#[inline]
+pub fn span_containing(
+    big: impl Into<SpanQuery>,
+    little: impl Into<SpanQuery>,
+) -> SpanContainingQuery {
+    SpanContainingQuery::new(big, little)
+}
+
+/// Returns a [`SpanWithinQuery`] for the spans of `little` which are within a
+/// span of `big`.
+#[inline]
+pub fn span_within(big: impl Into<SpanQuery>, little: impl Into<SpanQuery>) -> SpanWithinQuery {
+    SpanWithinQuery::new(big, little)
+}
+
+/// Returns a [`SpanMultiQuery`] wrapping a multi-term query, e.g. a `prefix`
+/// query.
+#[inline]
+pub fn span_multi(match_: impl Into<Query>) -> SpanMultiQuery {
+    SpanMultiQuery::new(match_)
+}
+
+/// Returns a [`SimpleQueryStringQuery`] of `query` on `fields`.
+#[inline]
+pub fn simple_query_string<T: Into<String>>(
+    fields: impl IntoIterator<Item = T>,
+    query: impl Into<String>,
+) -> SimpleQueryStringQuery {
+    SimpleQueryStringQuery::new(fields, query)
+}
+
+/// Returns a [`KnnQuery`] for the `k` nearest neighbors of `query_vector` in
+/// the vector `field`.
+#[inline]
+pub fn knn(field: impl Into<String>, query_vector: impl Into<Vec<f32>>, k: u64) -> KnnQuery {
+    KnnQuery::new(field, query_vector, k)
+}
+
+/// Returns a [`RankFeatureQuery`] boosting the scores of documents by the
+/// value of the rank feature `field`.
+#[inline]
+pub fn rank_feature(field: impl Into<String>) -> RankFeatureQuery {
+    RankFeatureQuery::new(field)
+}
+
+/// Returns a [`NestedQuery`] running `query` on the nested objects at `path`.
+#[inline]
+pub fn nested(path: impl Into<String>, query: impl Into<CompoundQuery>) -> NestedQuery {
+    NestedQuery::new(path, query, false)
+}
+
+/// Combinators for anything that can be converted into a [`Query`].
+pub trait QueryExt: Into<Query> {
+    /// Returns a `bool` query where both `self` and `other` **must** match.
+    ///
+    /// If `self` is already a `bool` query with only `must` clauses, `other`
+    /// is appended to them instead.
+    #[inline]
+    fn and(self, other: impl Into<Query>) -> Query {
+        combine(self.into(), other.into(), |query| &mut query.must)
+    }
+
+    /// Returns a `bool` query where either `self` or `other` **should** match.
+    ///
+    /// If `self` is already a `bool` query with only `should` clauses, `other`
+    /// is appended to them instead.
+    #[inline]
+    fn or(self, other: impl Into<Query>) -> Query {
+        combine(self.into(), other.into(), |query| &mut query.should)
+    }
+
+    /// Returns a `bool` query where `self` **must not** match.
+    #[inline]
+    fn not(self) -> Query {
+        let mut query = empty_boolean();
+        query.must_not.push(self.into());
+        query.into_query()
+    }
+
+    /// Sets the `boost` of `self`, wrapping it in a `bool` query if it doesn't
+    /// support boosting itself.
+    fn boost(self, boost: f64) -> Query {
+        let mut query = self.into();
+        if let Some(ref mut q) = query.term {
+            q.boost = Some(boost);
+        } else if let Some(ref mut q) = query.terms {
+            q.boost = Some(boost);
+        } else if let Some(ref mut q) = query.range {
+            q.boost = Some(boost);
+        } else if let Some(ref mut q) = query.boolean {
+            q.boost = Some(boost);
+        } else {
+            let mut wrapper = empty_boolean();
+            wrapper.must.push(query);
+            wrapper.boost = Some(boost);
+            return wrapper.into_query();
+        }
+        query
+    }
+}
+
+impl<T: Into<Query>> QueryExt for T {}
+
+/// Implements the `&`, `|` and `!` operators for query types as shorthands for
+/// [`QueryExt::and`], [`QueryExt::or`] and [`QueryExt::not`], e.g.
+/// `term_a & (term_b | term_c) & !term_d`.
+macro_rules! impl_ops {
+    ($into:path : $($ty:ty),* $(,)?) => {
+        $(
+            impl<T: Into<Query>> std::ops::BitAnd<T> for $ty {
+                type Output = Query;
+
+                #[inline]
+                fn bitand(self, other: T) -> Query {
+                    $into(self).and(other)
+                }
+            }
+
+            impl<T: Into<Query>> std::ops::BitOr<T> for $ty {
+                type Output = Query;
+
+                #[inline]
+                fn bitor(self, other: T) -> Query {
+                    $into(self).or(other)
+                }
+            }
+
+            impl std::ops::Not for $ty {
+                type Output = Query;
+
+                #[inline]
+                fn not(self) -> Query {
+                    QueryExt::not($into(self))
+                }
+            }
+        )*
+    };
+}
+
+impl_ops!(BooleanQuery::into_query: BooleanQuery);
+impl_ops!(
+    Query::from:
+    Query,
+    ExistsQuery,
+    TermQuery,
+    TermsQuery,
+    TermsSetQuery,
+    IdsQuery,
+    RangeQuery,
+    PrefixQuery,
+    WildcardQuery,
+    RegexpQuery,
+    FuzzyQuery,
+    MatchQuery,
+    MatchPhraseQuery,
+    MatchPhrasePrefixQuery,
+    MultiMatchQuery,
+    MoreLikeThisQuery,
+    PercolateQuery,
+    ScriptQuery,
+    GeoDistanceQuery,
+    GeoBoundingBoxQuery,
+    GeoShapeQuery,
+    GeoPolygonQuery,
+    SpanQuery,
+    SpanTermQuery,
+    SpanNearQuery,
+    SpanFirstQuery,
+    SpanOrQuery,
+    SpanNotQuery,
+    SpanContainingQuery,
+    SpanWithinQuery,
+    SpanMultiQuery,
+    SimpleQueryStringQuery,
+    QueryStringQuery,
+    KnnQuery,
+    RankFeatureQuery,
+    ScriptScoreQuery,
+    NestedQuery,
+);
+
+/// Returns a `BooleanQuery` without any clauses.
+#[inline]
+fn empty_boolean() -> BooleanQuery {
+    BooleanQuery {
+        must: vec![],
+        filter: vec![],
+        should: vec![],
+        must_not: vec![],
+        minimum_should_match: None,
+        boost: None,
+    }
+}
+
+/// Appends `other` to the `clauses` of `query` if it's a `bool` query with
+/// nothing but those clauses, otherwise returns a new `bool` query with both
+/// `query` and `other` in its `clauses`.
+fn combine(
+    mut query: Query,
+    other: Query,
+    clauses: impl Fn(&mut BooleanQuery) -> &mut Vec<Query>,
+) -> Query {
+    if let Some(mut boolean) = query.boolean.take() {
+        if query.is_empty() && only_clauses(&mut boolean, &clauses) {
+            clauses(&mut boolean).push(other);
+            return boolean.into_query();
+        }
+        query.boolean = Some(boolean);
+    }
+
+    let mut boolean = empty_boolean();
+    clauses(&mut boolean).extend(vec![query, other]);
+    boolean.into_query()
+}
+
+/// Returns `true` if `query` has no options set and only has clauses in the
+/// list returned by `clauses`.
+fn only_clauses(
+    query: &mut BooleanQuery,
+    clauses: impl Fn(&mut BooleanQuery) -> &mut Vec<Query>,
+) -> bool {
+    if query.minimum_should_match.is_some() || query.boost.is_some() {
+        return false;
+    }
+    let len = clauses(query).len();
+    len == query.must.len() + query.filter.len() + query.should.len() + query.must_not.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn can_combine_queries() {
+        let query = term("status", "open")
+            .and(range("age").gte(18))
+            .and(exists("user"))
+            .or(regexp("user", "k.*y").boost(2.0))
+            .and(prefix("user", "ki").not());
+
+        let expected = json!({
+            "bool": {
+                "must": [
+                    {
+                        "bool": {
+                            "should": [
+                                {
+                                    "bool": {
+                                        "must": [
+                                            { "term": { "status": { "value": "open" } } },
+                                            { "range": { "age": { "gte": "18" } } },
+                                            { "exists": { "field": "user" } }
+                                        ]
+                                    }
+                                },
+                                {
+                                    "bool": {
+                                        "must": [{ "regexp": { "user": { "value": "k.*y" } } }],
+                                        "boost": 2.0
+                                    }
+                                }
+                            ]
+                        }
+                    },
+                    {
+                        "bool": {
+                            "must_not": [{
+                                "prefix": { "user": { "value": "ki", "case_insensitive": false } }
+                            }]
+                        }
+                    }
+                ]
+            }
+        });
+        assert_eq!(serde_json::to_value(&query).unwrap(), expected);
+    }
+
+    #[test]
+    fn can_combine_queries_with_operators() {
+        let query =
+            term("status", "open") & (exists("user") | prefix("user", "ki")) & !range("age").lt(18);
+
+        let expected = term("status", "open")
+            .and(exists("user").or(prefix("user", "ki")))
+            .and(range("age").lt(18).not());
+        assert_eq!(query, expected);
+
+        let expected = json!({
+            "bool": {
+                "must": [
+                    { "term": { "status": { "value": "open" } } },
+                    {
+                        "bool": {
+                            "should": [
+                                { "exists": { "field": "user" } },
+                                { "prefix": { "user": { "value": "ki", "case_insensitive": false } } }
+                            ]
+                        }
+                    },
+                    { "bool": { "must_not": [{ "range": { "age": { "lt": "18" } } }] } }
+                ]
+            }
+        });
+        assert_eq!(serde_json::to_value(&query).unwrap(), expected);
+    }
+
+    #[test]
+    fn operators_flatten_like_combinators() {
+        let query = term("a", "1") | term("b", "2") | term("c", "3");
+        assert_eq!(query.boolean.unwrap().should.len(), 3);
+
+        let query = !(term("a", "1") & term("b", "2"));
+        assert_eq!(query.boolean.unwrap().must_not.len(), 1);
+    }
+
+    #[test]
+    fn can_boost_leaf_queries() {
+        let query = term("status", "open").boost(2.0);
+        assert_eq!(query.term.unwrap().boost, Some(2.0));
+    }
+}
@@ -0,0 +1,245 @@
+//! Aliases of the [`query`](super::query) types named after their GraphQL
+//! `Filter` counterparts, e.g. [`TermFilter`] for [`TermQuery`].
+//!
+//! There is only one implementation of each query kind; these are the very
+//! same types, so new query kinds only need to be added to `search::query`.
+//! It also means no conversions are needed when migrating from one family of
+//! names to the other; a `Filter` *is* a `Query`:
+//!
+//! ```
+//! use elastiql::search::{
+//!     filter::{CompoundFilter, TermFilter},
+//!     query::{CompoundQuery, TermQuery},
+//! };
+//!
+//! let filter: CompoundFilter = CompoundFilter::from(TermFilter::new("user", "kimchy"));
+//! let query: CompoundQuery = filter;
+//! assert_eq!(query, CompoundQuery::from(TermQuery::new("user", "kimchy")));
+//! ```
+
+use super::query::span::{
+    SpanContainingQuery, SpanFirstQuery, SpanMultiQuery, SpanNearQuery, SpanNotQuery, SpanOrQuery,
+    SpanQuery, SpanTermQuery, SpanWithinQuery,
+};
+#[cfg(feature = "graphql")]
+use super::query::span::{
+    SpanContainingQueryInput, SpanFirstQueryInput, SpanMultiQueryInput, SpanNearQueryInput,
+    SpanNotQueryInput, SpanOrQueryInput, SpanQueryInput, SpanTermQueryInput, SpanWithinQueryInput,
+};
+use super::query::{
+    BooleanQuery, CompoundQuery, ExistsQuery, FuzzyQuery, GeoBoundingBoxQuery, GeoDistanceQuery,
+    GeoPolygonQuery, GeoShapeQuery, IdsQuery, KnnQuery, MatchPhrasePrefixQuery, MatchPhraseQuery,
+    MatchQuery, MoreLikeThisQuery, MultiMatchQuery, NestedQuery, PercolateQuery, PrefixQuery,
+    Query, QueryField, QueryStringQuery, RangeQuery, RankFeatureQuery, RegexpQuery, ScriptQuery,
+    ScriptScoreQuery, SimpleQueryStringQuery, TermQuery, TermsQuery, TermsSetQuery, WildcardQuery,
+};
+#[cfg(feature = "graphql")]
+use super::query::{
+    BooleanQueryInput, CompoundQueryInput, ExistsQueryInput, FuzzyQueryInput,
+    GeoBoundingBoxQueryInput, GeoDistanceQueryInput, GeoPolygonQueryInput, GeoShapeQueryInput,
+    IdsQueryInput, KnnQueryInput, MatchPhrasePrefixQueryInput, MatchPhraseQueryInput,
+    MatchQueryInput, MoreLikeThisQueryInput, MultiMatchQueryInput, NestedQueryInput,
+    PercolateQueryInput, PrefixQueryInput, QueryInput, QueryStringQueryInput, RangeQueryInput,
+    RankFeatureQueryInput, RegexpQueryInput, ScriptQueryInput, ScriptScoreQueryInput,
+    SimpleQueryStringQueryInput, TermQueryInput, TermsQueryInput, TermsSetQueryInput,
+    WildcardQueryInput,
+};
+
+/// An alias of [`Query`].
+pub type Filter = Query;
+/// An alias of [`QueryField`].
+pub type FilterField = QueryField;
+/// An alias of [`CompoundQuery`].
+pub type CompoundFilter = CompoundQuery;
+/// An alias of [`BooleanQuery`].
+pub type BooleanFilter = BooleanQuery;
+/// An alias of [`ExistsQuery`].
+pub type ExistsFilter = ExistsQuery;
+/// An alias of [`TermQuery`].
+pub type TermFilter = TermQuery;
+/// An alias of [`TermsQuery`].
+pub type TermsFilter = TermsQuery;
+/// An alias of [`TermsSetQuery`].
+pub type TermsSetFilter = TermsSetQuery;
+/// An alias of [`IdsQuery`].
+pub type IdsFilter = IdsQuery;
+/// An alias of [`RangeQuery`].
+pub type RangeFilter = RangeQuery;
+/// An alias of [`PrefixQuery`].
+pub type PrefixFilter = PrefixQuery;
+/// An alias of [`WildcardQuery`].
+pub type WildcardFilter = WildcardQuery;
+/// An alias of [`RegexpQuery`].
+pub type RegexpFilter = RegexpQuery;
+/// An alias of [`FuzzyQuery`].
+pub type FuzzyFilter = FuzzyQuery;
+/// An alias of [`MatchQuery`].
+pub type MatchFilter = MatchQuery;
+/// An alias of [`MatchPhraseQuery`].
+pub type MatchPhraseFilter = MatchPhraseQuery;
+/// An alias of [`MatchPhrasePrefixQuery`].
+pub type MatchPhrasePrefixFilter = MatchPhrasePrefixQuery;
+/// An alias of [`MultiMatchQuery`].
+pub type MultiMatchFilter = MultiMatchQuery;
+/// An alias of [`MoreLikeThisQuery`].
+pub type MoreLikeThisFilter = MoreLikeThisQuery;
+/// An alias of [`PercolateQuery`].
+pub type PercolateFilter = PercolateQuery;
+/// An alias of [`ScriptQuery`].
+pub type ScriptFilter = ScriptQuery;
+/// An alias of [`GeoDistanceQuery`].
+pub type GeoDistanceFilter = GeoDistanceQuery;
+/// An alias of [`GeoBoundingBoxQuery`].
+pub type GeoBoundingBoxFilter = GeoBoundingBoxQuery;
+/// An alias of [`GeoShapeQuery`].
+pub type GeoShapeFilter = GeoShapeQuery;
+/// An alias of [`GeoPolygonQuery`].
+pub type GeoPolygonFilter = GeoPolygonQuery;
+/// An alias of [`SpanQuery`].
+pub type SpanFilter = SpanQuery;
+/// An alias of [`SpanTermQuery`].
+pub type SpanTermFilter = SpanTermQuery;
+/// An alias of [`SpanNearQuery`].
+pub type SpanNearFilter = SpanNearQuery;
+/// An alias of [`SpanFirstQuery`].
+pub type SpanFirstFilter = SpanFirstQuery;
+/// An alias of [`SpanOrQuery`].
+pub type SpanOrFilter = SpanOrQuery;
+/// An alias of [`SpanNotQuery`].
+pub type SpanNotFilter = SpanNotQuery;
+/// An alias of [`SpanContainingQuery`].
+pub type SpanContainingFilter = SpanContainingQuery;
+/// An alias of [`SpanWithinQuery`].
+pub type SpanWithinFilter = SpanWithinQuery;
+/// An alias of [`SpanMultiQuery`].
+pub type SpanMultiFilter = SpanMultiQuery;
+/// An alias of [`SimpleQueryStringQuery`].
+pub type SimpleQueryStringFilter = SimpleQueryStringQuery;
+/// An alias of [`QueryStringQuery`].
+pub type QueryStringFilter = QueryStringQuery;
+/// An alias of [`KnnQuery`].
+pub type KnnFilter = KnnQuery;
+/// An alias of [`RankFeatureQuery`].
+pub type RankFeatureFilter = RankFeatureQuery;
+/// An alias of [`ScriptScoreQuery`].
+pub type ScriptScoreFilter = ScriptScoreQuery;
+/// An alias of [`NestedQuery`].
+pub type NestedFilter = NestedQuery;
+
+/// An alias of [`QueryInput`].
+#[cfg(feature = "graphql")]
+pub type FilterInput = QueryInput;
+/// An alias of [`CompoundQueryInput`].
+#[cfg(feature = "graphql")]
+pub type CompoundFilterInput = CompoundQueryInput;
+/// An alias of [`BooleanQueryInput`].
+#[cfg(feature = "graphql")]
+pub type BooleanFilterInput = BooleanQueryInput;
+/// An alias of [`ExistsQueryInput`].
+#[cfg(feature = "graphql")]
+pub type ExistsFilterInput = ExistsQueryInput;
+/// An alias of [`TermQueryInput`].
+#[cfg(feature = "graphql")]
+pub type TermFilterInput = TermQueryInput;
+/// An alias of [`TermsQueryInput`].
+#[cfg(feature = "graphql")]
+pub type TermsFilterInput = TermsQueryInput;
+/// An alias of [`TermsSetQueryInput`].
+#[cfg(feature = "graphql")]
+pub type TermsSetFilterInput = TermsSetQueryInput;
+/// An alias of [`IdsQueryInput`].
+#[cfg(feature = "graphql")]
+pub type IdsFilterInput = IdsQueryInput;
+/// An alias of [`RangeQueryInput`].
+#[cfg(feature = "graphql")]
+pub type RangeFilterInput = RangeQueryInput;
+/// An alias of [`PrefixQueryInput`].
+#[cfg(feature = "graphql")]
+pub type PrefixFilterInput = PrefixQueryInput;
+/// An alias of [`WildcardQueryInput`].
+#[cfg(feature = "graphql")]
+pub type WildcardFilterInput = WildcardQueryInput;
+/// An alias of [`RegexpQueryInput`].
+#[cfg(feature = "graphql")]
+pub type RegexpFilterInput = RegexpQueryInput;
+/// An alias of [`FuzzyQueryInput`].
+#[cfg(feature = "graphql")]
+pub type FuzzyFilterInput = FuzzyQueryInput;
+/// An alias of [`MatchQueryInput`].
+#[cfg(feature = "graphql")]
+pub type MatchFilterInput = MatchQueryInput;
+/// An alias of [`MatchPhraseQueryInput`].
+#[cfg(feature = "graphql")]
+pub type MatchPhraseFilterInput = MatchPhraseQueryInput;
+/// An alias of [`MatchPhrasePrefixQueryInput`].
+#[cfg(feature = "graphql")]
+pub type MatchPhrasePrefixFilterInput = MatchPhrasePrefixQueryInput;
+/// An alias of [`MultiMatchQueryInput`].
+#[cfg(feature = "graphql")]
+pub type MultiMatchFilterInput = MultiMatchQueryInput;
+/// An alias of [`MoreLikeThisQueryInput`].
+#[cfg(feature = "graphql")]
+pub type MoreLikeThisFilterInput = MoreLikeThisQueryInput;
+/// An alias of [`PercolateQueryInput`].
+#[cfg(feature = "graphql")]
+pub type PercolateFilterInput = PercolateQueryInput;
+/// An alias of [`ScriptQueryInput`].
+#[cfg(feature = "graphql")]
+pub type ScriptFilterInput = ScriptQueryInput;
+/// An alias of [`GeoDistanceQueryInput`].
+#[cfg(feature = "graphql")]
+pub type GeoDistanceFilterInput = GeoDistanceQueryInput;
+/// An alias of [`GeoBoundingBoxQueryInput`].
+#[cfg(feature = "graphql")]
+pub type GeoBoundingBoxFilterInput = GeoBoundingBoxQueryInput;
+/// An alias of [`GeoShapeQueryInput`].
+#[cfg(feature = "graphql")]
+pub type GeoShapeFilterInput = GeoShapeQueryInput;
+/// An alias of [`GeoPolygonQueryInput`].
+#[cfg(feature = "graphql")]
+pub type GeoPolygonFilterInput = GeoPolygonQueryInput;
+/// An alias of [`SpanQueryInput`].
+#[cfg(feature = "graphql")]
+pub type SpanFilterInput = SpanQueryInput;
+/// An alias of [`SpanTermQueryInput`].
+#[cfg(feature = "graphql")]
+pub type SpanTermFilterInput = SpanTermQueryInput;
+/// An alias of [`SpanNearQueryInput`].
+#[cfg(feature = "graphql")]
+pub type SpanNearFilterInput = SpanNearQueryInput;
+/// An alias of [`SpanFirstQueryInput`].
+#[cfg(feature = "graphql")]
+pub type SpanFirstFilterInput = SpanFirstQueryInput;
+/// An alias of [`SpanOrQueryInput`].
+#[cfg(feature = "graphql")]
+pub type SpanOrFilterInput = SpanOrQueryInput;
+/// An alias of [`SpanNotQueryInput`].
+#[cfg(feature = "graphql")]
+pub type SpanNotFilterInput = SpanNotQueryInput;
+/// An alias of [`SpanContainingQueryInput`].
+#[cfg(feature = "graphql")]
+pub type SpanContainingFilterInput = SpanContainingQueryInput;
+/// An alias of [`SpanWithinQueryInput`].
+#[cfg(feature = "graphql")]
+pub type SpanWithinFilterInput = SpanWithinQueryInput;
+/// An alias of [`SpanMultiQueryInput`].
+#[cfg(feature = "graphql")]
+pub type SpanMultiFilterInput = SpanMultiQueryInput;
+/// An alias of [`SimpleQueryStringQueryInput`].
+#[cfg(feature = "graphql")]
+pub type SimpleQueryStringFilterInput = SimpleQueryStringQueryInput;
+/// An alias of [`QueryStringQueryInput`].
+#[cfg(feature = "graphql")]
+pub type QueryStringFilterInput = QueryStringQueryInput;
+/// An alias of [`KnnQueryInput`].
+#[cfg(feature = "graphql")]
+pub type KnnFilterInput = KnnQueryInput;
+/// An alias of [`RankFeatureQueryInput`].
+#[cfg(feature = "graphql")]
+pub type RankFeatureFilterInput = RankFeatureQueryInput;
+/// An alias of [`ScriptScoreQueryInput`].
+#[cfg(feature = "graphql")]
+pub type ScriptScoreFilterInput = ScriptScoreQueryInput;
+/// An alias of [`NestedQueryInput`].
+#[cfg(feature = "graphql")]
+pub type NestedFilterInput = NestedQueryInput;
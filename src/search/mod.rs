@@ -3,8 +3,16 @@
 //! [searching]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-search.html
 //! [Query DSL]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl.html
 
-pub use self::{request::*, response::*, script::*, sort::*};
+#[cfg(feature = "graphql")]
+pub use self::connection::*;
+pub use self::{borrowed::*, cursor::*, request::*, response::*, script::*, sort::*};
 
+mod borrowed;
+#[cfg(feature = "graphql")]
+mod connection;
+mod cursor;
+pub mod dsl;
+pub mod filter;
 pub mod query;
 mod request;
 mod response;
@@ -0,0 +1,574 @@
+//! A backend-agnostic representation of queries, i.e. a normalized tree of
+//! predicates combined with boolean operators, so that other storage backends
+//! (e.g. SQL or tantivy) can consume the same user filters.
+//!
+//! An [`Expr`] converts into an equivalent [`Query`] without losing anything;
+//! the conversion back ([`Expr::from_query`]) is best-effort: scoring and
+//! analysis options (e.g. `boost` or `time_zone`) are dropped, and queries
+//! without an equivalent (e.g. `query_string` or `knn`) are rejected.
+//!
+//! ```
+//! use elastiql::search::query::{
+//!     ast::{Comparison, Expr},
+//!     Query,
+//! };
+//!
+//! let expr = Expr::and(vec![
+//!     Expr::compare("status", Comparison::Eq, "open"),
+//!     Expr::compare("age", Comparison::Gte, "18"),
+//!     Expr::exists("deleted_at").negate(),
+//! ]);
+//!
+//! let query = Query::from(expr.clone());
+//! assert_eq!(Expr::from_query(&query).unwrap(), expr);
+//! ```
+
+use std::{error::Error, fmt};
+
+use super::{
+    BooleanQuery, CompoundQuery, ExistsQuery, MatchQuery, NestedQuery, PrefixQuery, Query,
+    QueryKind, RangeQuery, RegexpQuery, TermQuery, TermsQuery,
+};
+
+/// A backend-agnostic query expression.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr {
+    /// Matches if all of the expressions match; matches everything if there
+    /// are none.
+    And(Vec<Expr>),
+
+    /// Matches if any of the expressions match; matches nothing if there are
+    /// none.
+    Or(Vec<Expr>),
+
+    /// Matches if the expression doesn't match.
+    Not(Box<Expr>),
+
+    /// Matches if any of the nested objects at `path` match the expression.
+    Nested {
+        /// The path to the nested objects.
+        path: String,
+        /// The expression the nested objects are matched against.
+        expr: Box<Expr>,
+    },
+
+    /// A predicate on a single field.
+    Predicate(Predicate),
+}
+
+/// A predicate on the value(s) of a single field.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Predicate {
+    /// The field has a (non-null) value.
+    Exists {
+        /// The name of the field.
+        field: String,
+    },
+
+    /// The value of the field compares to `value` as given by `op`.
+    Compare {
+        /// The name of the field.
+        field: String,
+        /// How the value of the field is compared to `value`.
+        op: Comparison,
+        /// The value compared against, e.g. `"42"` or `"now-1d"`.
+        value: String,
+    },
+
+    /// The value of the field is one of `values`.
+    In {
+        /// The name of the field.
+        field: String,
+        /// The values to match exactly.
+        values: Vec<String>,
+    },
+
+    /// The value of the field starts with `prefix`.
+    Prefix {
+        /// The name of the field.
+        field: String,
+        /// The prefix to match.
+        prefix: String,
+    },
+
+    /// The value of the field matches the regular expression `pattern`, in
+    /// the [Lucene syntax].
+    ///
+    /// [Lucene syntax]: https://www.elastic.co/guide/en/elasticsearch/reference/current/regexp-syntax.html
+    Regex {
+        /// The name of the field.
+        field: String,
+        /// The regular expression to match.
+        pattern: String,
+    },
+
+    /// The (analyzed, full-text) value of the field matches `text`.
+    Match {
+        /// The name of the field.
+        field: String,
+        /// The text to match.
+        text: String,
+    },
+}
+
+/// The comparison operator of a [`Predicate::Compare`].
+#[allow(missing_docs)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Comparison {
+    Eq,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+}
+
+/// The reason a [`Query`] can't be converted into an [`Expr`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum AstError {
+    /// The kind of query has no backend-agnostic equivalent, e.g. `knn`.
+    Unsupported(QueryKind),
+
+    /// The `minimum_should_match` of a `bool` query requires more than one
+    /// of its `should` clauses to match.
+    MinimumShouldMatch(String),
+}
+
+impl fmt::Display for AstError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AstError::Unsupported(kind) => write!(f, "`{}` queries are not supported", kind),
+            AstError::MinimumShouldMatch(value) => {
+                write!(f, "`minimum_should_match` of `{}` is not supported", value)
+            }
+        }
+    }
+}
+
+impl Error for AstError {}
+
+impl Expr {
+    /// Matches if all of the `exprs` match.
+    #[inline]
+    pub fn and(exprs: impl IntoIterator<Item = Expr>) -> Expr {
+        Expr::And(exprs.into_iter().collect())
+    }
+
+    /// Matches if any of the `exprs` match.
+    #[inline]
+    pub fn or(exprs: impl IntoIterator<Item = Expr>) -> Expr {
+        Expr::Or(exprs.into_iter().collect())
+    }
+
+    /// Matches if this doesn't match.
+    #[inline]
+    pub fn negate(self) -> Expr {
+        Expr::Not(Box::new(self))
+    }
+
+    /// Matches if any of the nested objects at `path` match `expr`.
+    #[inline]
+    pub fn nested(path: impl Into<String>, expr: Expr) -> Expr {
+        Expr::Nested {
+            path: path.into(),
+            expr: Box::new(expr),
+        }
+    }
+
+    /// Matches if `field` has a value.
+    #[inline]
+    pub fn exists(field: impl Into<String>) -> Expr {
+        Expr::Predicate(Predicate::Exists {
+            field: field.into(),
+        })
+    }
+
+    /// Matches if the value of `field` compares to `value` as given by `op`.
+    #[inline]
+    pub fn compare(field: impl Into<String>, op: Comparison, value: impl Into<String>) -> Expr {
+        Expr::Predicate(Predicate::Compare {
+            field: field.into(),
+            op,
+            value: value.into(),
+        })
+    }
+
+    /// Matches if the value of `field` is one of `values`.
+    #[inline]
+    pub fn in_<T: Into<String>>(
+        field: impl Into<String>,
+        values: impl IntoIterator<Item = T>,
+    ) -> Expr {
+        Expr::Predicate(Predicate::In {
+            field: field.into(),
+            values: values.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    /// Matches if the value of `field` starts with `prefix`.
+    #[inline]
+    pub fn prefix(field: impl Into<String>, prefix: impl Into<String>) -> Expr {
+        Expr::Predicate(Predicate::Prefix {
+            field: field.into(),
+            prefix: prefix.into(),
+        })
+    }
+
+    /// Matches if the value of `field` matches the regular expression
+    /// `pattern`.
+    #[inline]
+    pub fn regex(field: impl Into<String>, pattern: impl Into<String>) -> Expr {
+        Expr::Predicate(Predicate::Regex {
+            field: field.into(),
+            pattern: pattern.into(),
+        })
+    }
+
+    /// Matches if the value of `field` matches the full-text `text`.
+    #[inline]
+    pub fn match_(field: impl Into<String>, text: impl Into<String>) -> Expr {
+        Expr::Predicate(Predicate::Match {
+            field: field.into(),
+            text: text.into(),
+        })
+    }
+
+    /// Converts a `Query` into an equivalent `Expr`, as far as filtering is
+    /// concerned.
+    ///
+    /// A `Query` with several fields defined matches if all of them match, a
+    /// `range` query becomes the comparisons of each of its bounds and the
+    /// `should` clauses of a `bool` query are dropped if they only contribute
+    /// to the score. `And`s and `Or`s of a single expression are replaced by
+    /// that expression.
+    pub fn from_query(query: &Query) -> Result<Expr, AstError> {
+        let mut exprs = Vec::new();
+
+        if let Some(ref query) = query.exists {
+            exprs.push(Expr::exists(query.field.as_str()));
+        }
+        if let Some(ref query) = query.term {
+            exprs.push(Expr::compare(
+                query.field.as_str(),
+                Comparison::Eq,
+                query.value.as_str(),
+            ));
+        }
+        if let Some(ref query) = query.terms {
+            exprs.push(Expr::in_(query.field.as_str(), &query.values));
+        }
+        if let Some(ref query) = query.ids {
+            exprs.push(Expr::in_("_id", &query.values));
+        }
+        if let Some(ref query) = query.range {
+            let bounds = [
+                (Comparison::Gt, &query.greater_than),
+                (Comparison::Gte, &query.greater_than_or_equal_to),
+                (Comparison::Lt, &query.less_than),
+                (Comparison::Lte, &query.less_than_or_equal_to),
+            ];
+            for (op, value) in bounds.iter() {
+                if let Some(value) = value {
+                    exprs.push(Expr::compare(query.field.as_str(), *op, value.as_str()));
+                }
+            }
+        }
+        if let Some(ref query) = query.prefix {
+            exprs.push(Expr::prefix(query.field.as_str(), query.value.as_str()));
+        }
+        if query.wildcard.is_some() {
+            return Err(AstError::Unsupported(QueryKind::Wildcard));
+        }
+        if let Some(ref query) = query.regexp {
+            exprs.push(Expr::regex(query.field.as_str(), query.value.as_str()));
+        }
+        if query.fuzzy.is_some() {
+            return Err(AstError::Unsupported(QueryKind::Fuzzy));
+        }
+        if let Some(ref query) = query.match_ {
+            exprs.push(Expr::match_(query.field.as_str(), query.query.as_str()));
+        }
+        if query.match_phrase.is_some() {
+            return Err(AstError::Unsupported(QueryKind::MatchPhrase));
+        }
+        if query.match_phrase_prefix.is_some() {
+            return Err(AstError::Unsupported(QueryKind::MatchPhrasePrefix));
+        }
+        if query.multi_match.is_some() {
+            return Err(AstError::Unsupported(QueryKind::MultiMatch));
+        }
+        if query.more_like_this.is_some() {
+            return Err(AstError::Unsupported(QueryKind::MoreLikeThis));
+        }
+        if query.percolate.is_some() {
+            return Err(AstError::Unsupported(QueryKind::Percolate));
+        }
+        if query.script.is_some() {
+            return Err(AstError::Unsupported(QueryKind::Script));
+        }
+        if query.terms_set.is_some() {
+            return Err(AstError::Unsupported(QueryKind::TermsSet));
+        }
+        if query.geo_distance.is_some() {
+            return Err(AstError::Unsupported(QueryKind::GeoDistance));
+        }
+        if query.geo_bounding_box.is_some() {
+            return Err(AstError::Unsupported(QueryKind::GeoBoundingBox));
+        }
+        if query.geo_shape.is_some() {
+            return Err(AstError::Unsupported(QueryKind::GeoShape));
+        }
+        if query.geo_polygon.is_some() {
+            return Err(AstError::Unsupported(QueryKind::GeoPolygon));
+        }
+        if query.span_term.is_some() {
+            return Err(AstError::Unsupported(QueryKind::SpanTerm));
+        }
+        if query.span_near.is_some() {
+            return Err(AstError::Unsupported(QueryKind::SpanNear));
+        }
+        if query.span_first.is_some() {
+            return Err(AstError::Unsupported(QueryKind::SpanFirst));
+        }
+        if query.span_or.is_some() {
+            return Err(AstError::Unsupported(QueryKind::SpanOr));
+        }
+        if query.span_not.is_some() {
+            return Err(AstError::Unsupported(QueryKind::SpanNot));
+        }
+        if query.span_containing.is_some() {
+            return Err(AstError::Unsupported(QueryKind::SpanContaining));
+        }
+        if query.span_within.is_some() {
+            return Err(AstError::Unsupported(QueryKind::SpanWithin));
+        }
+        if query.span_multi.is_some() {
+            return Err(AstError::Unsupported(QueryKind::SpanMulti));
+        }
+        if query.simple_query_string.is_some() {
+            return Err(AstError::Unsupported(QueryKind::SimpleQueryString));
+        }
+        if query.query_string.is_some() {
+            return Err(AstError::Unsupported(QueryKind::QueryString));
+        }
+        if query.knn.is_some() {
+            return Err(AstError::Unsupported(QueryKind::Knn));
+        }
+        if query.rank_feature.is_some() {
+            return Err(AstError::Unsupported(QueryKind::RankFeature));
+        }
+        if let Some(ref query) = query.script_score {
+            // the script only changes the scores of the matching documents
+            // unless it is used to exclude some of them
+            if query.min_score.is_some() {
+                return Err(AstError::Unsupported(QueryKind::ScriptScore));
+            }
+            exprs.push(Expr::from_compound_query(&query.query)?);
+        }
+        if let Some(ref query) = query.nested {
+            exprs.push(Expr::nested(
+                query.path.as_str(),
+                Expr::from_compound_query(&query.query)?,
+            ));
+        }
+        if let Some(ref query) = query.boolean {
+            exprs.push(Expr::from_boolean_query(query)?);
+        }
+
+        Ok(all(exprs))
+    }
+
+    /// Converts a `CompoundQuery` into an equivalent `Expr`.
+    ///
+    /// See [`Expr::from_query`].
+    pub fn from_compound_query(query: &CompoundQuery) -> Result<Expr, AstError> {
+        let mut exprs = Vec::new();
+        if let Some(ref root) = query.root {
+            exprs.push(Expr::from_query(root)?);
+        }
+        if let Some(ref boolean) = query.boolean {
+            exprs.push(Expr::from_boolean_query(boolean)?);
+        }
+        Ok(all(exprs))
+    }
+
+    /// Converts a `BooleanQuery` into an equivalent `Expr`.
+    ///
+    /// See [`Expr::from_query`].
+    pub fn from_boolean_query(query: &BooleanQuery) -> Result<Expr, AstError> {
+        let mut exprs = query
+            .must
+            .iter()
+            .chain(&query.filter)
+            .map(Expr::from_query)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let should_is_required = match query.minimum_should_match.as_deref() {
+            None => exprs.is_empty() && !query.should.is_empty(),
+            Some("1") => true,
+            Some(value) => return Err(AstError::MinimumShouldMatch(value.to_string())),
+        };
+        if should_is_required {
+            let should = query
+                .should
+                .iter()
+                .map(Expr::from_query)
+                .collect::<Result<Vec<_>, _>>()?;
+            exprs.push(any(should));
+        }
+
+        for query in &query.must_not {
+            exprs.push(Expr::from_query(query)?.negate());
+        }
+
+        Ok(all(exprs))
+    }
+}
+
+/// Returns an `And` of `exprs`, or the expression itself if there's only one.
+#[inline]
+fn all(mut exprs: Vec<Expr>) -> Expr {
+    if exprs.len() == 1 {
+        exprs.remove(0)
+    } else {
+        Expr::And(exprs)
+    }
+}
+
+/// Returns an `Or` of `exprs`, or the expression itself if there's only one.
+#[inline]
+fn any(mut exprs: Vec<Expr>) -> Expr {
+    if exprs.len() == 1 {
+        exprs.remove(0)
+    } else {
+        Expr::Or(exprs)
+    }
+}
+
+impl From<Expr> for Query {
+    fn from(expr: Expr) -> Query {
+        match expr {
+            Expr::And(exprs) => {
+                let mut query = BooleanQuery::default();
+                for expr in exprs {
+                    match expr {
+                        Expr::Not(expr) => query.push_must_not(*expr),
+                        expr => query.push(expr),
+                    }
+                }
+                query.into_query()
+            }
+            Expr::Or(exprs) => BooleanQuery {
+                should: exprs.into_iter().map(Query::from).collect(),
+                minimum_should_match: Some("1".to_string()),
+                ..Default::default()
+            }
+            .into_query(),
+            Expr::Not(expr) => BooleanQuery {
+                must_not: vec![Query::from(*expr)],
+                ..Default::default()
+            }
+            .into_query(),
+            Expr::Nested { path, expr } => {
+                NestedQuery::new(path, CompoundQuery::from_query(Query::from(*expr)), false).into()
+            }
+            Expr::Predicate(predicate) => predicate.into(),
+        }
+    }
+}
+
+impl From<Predicate> for Query {
+    fn from(predicate: Predicate) -> Query {
+        match predicate {
+            Predicate::Exists { field } => ExistsQuery::new(field).into(),
+            Predicate::Compare { field, op, value } => match op {
+                Comparison::Eq => TermQuery::new(field, value).into(),
+                Comparison::Gt => RangeQuery::new(field).gt(value).into(),
+                Comparison::Gte => RangeQuery::new(field).gte(value).into(),
+                Comparison::Lt => RangeQuery::new(field).lt(value).into(),
+                Comparison::Lte => RangeQuery::new(field).lte(value).into(),
+            },
+            Predicate::In { field, values } => TermsQuery::new(field, values).into(),
+            Predicate::Prefix { field, prefix } => PrefixQuery::new(field, prefix).into(),
+            Predicate::Regex { field, pattern } => {
+                RegexpQuery::new(field, pattern, None::<String>).into()
+            }
+            Predicate::Match { field, text } => MatchQuery::new(field, text).into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn can_round_trip_through_queries() {
+        let exprs = vec![
+            Expr::exists("title"),
+            Expr::in_("status", vec!["open", "pending"]),
+            Expr::prefix("name", "jo").negate().negate(),
+            Expr::or(vec![
+                Expr::regex("code", "[a-z]+"),
+                Expr::match_("body", "quick brown fox"),
+            ]),
+            Expr::and(vec![
+                Expr::compare("age", Comparison::Gt, "18"),
+                Expr::compare("age", Comparison::Lte, "65"),
+                Expr::nested("comments", Expr::compare("votes", Comparison::Lt, "3")),
+            ]),
+            Expr::and(vec![]),
+            Expr::or(vec![]),
+        ];
+
+        for expr in exprs {
+            let query = Query::from(expr.clone());
+            assert_eq!(Expr::from_query(&query), Ok(expr));
+        }
+    }
+
+    #[test]
+    fn can_convert_queries() {
+        let query: Query = serde_json::from_value(json!({
+            "bool": {
+                "filter": [{ "range": { "age": { "gte": "18", "lt": "65", "boost": 2.0 } } }],
+                "should": [{ "term": { "vip": true } }],
+                "must_not": [{ "exists": { "field": "deleted_at" } }]
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            Expr::from_query(&query),
+            Ok(Expr::and(vec![
+                Expr::and(vec![
+                    Expr::compare("age", Comparison::Gte, "18"),
+                    Expr::compare("age", Comparison::Lt, "65"),
+                ]),
+                Expr::exists("deleted_at").negate(),
+            ]))
+        );
+
+        let query: Query = serde_json::from_value(json!({
+            "bool": {
+                "should": [{ "term": { "a": "1" } }, { "term": { "b": "2" } }],
+                "minimum_should_match": "2"
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            Expr::from_query(&query),
+            Err(AstError::MinimumShouldMatch("2".to_string()))
+        );
+
+        let query: Query = serde_json::from_value(json!({
+            "knn": { "field": "embedding", "query_vector": [0.5], "k": 3 }
+        }))
+        .unwrap();
+        assert_eq!(
+            Expr::from_query(&query),
+            Err(AstError::Unsupported(QueryKind::Knn))
+        );
+    }
+}
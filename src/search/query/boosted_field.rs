@@ -0,0 +1,218 @@
+//! The caret (`^`) notation for [per-field boosts], e.g. `title^3`, used by
+//! the `fields` of `query_string` and `simple_query_string` queries.
+//!
+//! ```
+//! use elastiql::search::query::{BoostedField, SimpleQueryStringQuery};
+//!
+//! let query = SimpleQueryStringQuery::new(
+//!     vec![BoostedField::new("title", 3.0), BoostedField::from("body")],
+//!     "kimchy",
+//! );
+//! assert_eq!(query.fields, vec!["title^3", "body"]);
+//!
+//! let field: BoostedField = "title^1.5".parse().unwrap();
+//! assert_eq!(field, BoostedField::new("title", 1.5));
+//! ```
+//!
+//! [per-field boosts]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-query-string-query.html#_boosting
+
+use std::{convert::TryFrom, error::Error, fmt, str::FromStr};
+
+#[cfg(feature = "schemars")]
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::{Deserialize, Serialize};
+
+/// A field name (which may contain wildcards, e.g. `title.*`) along with the
+/// boost of the relevance scores of its matches (if any).
+///
+/// It is (de)serialized in the caret notation, e.g. `"title^3"`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(into = "String", try_from = "String")]
+pub struct BoostedField {
+    /// The name of the field.
+    pub field: String,
+
+    /// The boost of the field, e.g. `3.0` to triple the relevance scores of
+    /// its matches. (Defaults to `1.0`.)
+    pub boost: Option<f64>,
+}
+
+impl BoostedField {
+    /// Constructs a new `BoostedField` for `field` with `boost`.
+    #[inline]
+    pub fn new(field: impl Into<String>, boost: f64) -> Self {
+        BoostedField {
+            field: field.into(),
+            boost: Some(boost),
+        }
+    }
+
+    /// Checks that the field name is neither empty nor contains a caret and
+    /// that the boost (if any) is a finite, non-negative number.
+    pub fn validate(&self) -> Result<(), BoostedFieldError> {
+        if self.field.is_empty() {
+            return Err(BoostedFieldError::EmptyField);
+        }
+        if self.field.contains('^') {
+            return Err(BoostedFieldError::InvalidField(self.field.clone()));
+        }
+        match self.boost {
+            Some(boost) if !boost.is_finite() || boost < 0.0 => {
+                Err(BoostedFieldError::InvalidBoost(boost.to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl From<&str> for BoostedField {
+    /// Returns `field` without a boost, as is; see [`FromStr`] for parsing
+    /// the caret notation.
+    #[inline]
+    fn from(field: &str) -> Self {
+        BoostedField {
+            field: field.to_string(),
+            boost: None,
+        }
+    }
+}
+
+impl FromStr for BoostedField {
+    type Err = BoostedFieldError;
+
+    /// Parses the caret notation, e.g. `title^3`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let field = match s.rfind('^') {
+            Some(i) => {
+                let boost = &s[i + 1..];
+                BoostedField {
+                    field: s[..i].to_string(),
+                    boost: Some(
+                        boost
+                            .parse()
+                            .map_err(|_| BoostedFieldError::InvalidBoost(boost.to_string()))?,
+                    ),
+                }
+            }
+            None => BoostedField::from(s),
+        };
+
+        field.validate()?;
+        Ok(field)
+    }
+}
+
+impl TryFrom<String> for BoostedField {
+    type Error = BoostedFieldError;
+
+    #[inline]
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl fmt::Display for BoostedField {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.boost {
+            Some(boost) => write!(f, "{}^{}", self.field, boost),
+            None => f.write_str(&self.field),
+        }
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl JsonSchema for BoostedField {
+    #[inline]
+    fn schema_name() -> String {
+        "BoostedField".to_string()
+    }
+
+    #[inline]
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
+
+impl From<BoostedField> for String {
+    #[inline]
+    fn from(field: BoostedField) -> Self {
+        field.to_string()
+    }
+}
+
+/// The reason a [`BoostedField`] is invalid.
+#[derive(Clone, PartialEq, Debug)]
+pub enum BoostedFieldError {
+    /// The field name is empty.
+    EmptyField,
+    /// The field name contains a caret.
+    InvalidField(String),
+    /// The boost isn't a finite, non-negative number.
+    InvalidBoost(String),
+}
+
+impl fmt::Display for BoostedFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BoostedFieldError::EmptyField => f.write_str("field name is empty"),
+            BoostedFieldError::InvalidField(field) => {
+                write!(f, "field name `{}` contains a caret", field)
+            }
+            BoostedFieldError::InvalidBoost(boost) => write!(f, "invalid boost `{}`", boost),
+        }
+    }
+}
+
+impl Error for BoostedFieldError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_and_print_the_caret_notation() {
+        for s in &["title", "title^3", "title.*^0.5", "*.name^10"] {
+            let field: BoostedField = s.parse().unwrap();
+            assert_eq!(field.to_string(), *s);
+        }
+
+        assert_eq!(
+            serde_json::to_value(BoostedField::new("title", 2.0)).unwrap(),
+            "title^2"
+        );
+        assert_eq!(
+            serde_json::from_str::<BoostedField>("\"title^2\"").unwrap(),
+            BoostedField::new("title", 2.0)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_fields() {
+        assert_eq!(
+            "".parse::<BoostedField>(),
+            Err(BoostedFieldError::EmptyField)
+        );
+        assert_eq!(
+            "^3".parse::<BoostedField>(),
+            Err(BoostedFieldError::EmptyField)
+        );
+        assert_eq!(
+            "title^".parse::<BoostedField>(),
+            Err(BoostedFieldError::InvalidBoost(String::new()))
+        );
+        assert_eq!(
+            "title^x".parse::<BoostedField>(),
+            Err(BoostedFieldError::InvalidBoost("x".to_string()))
+        );
+        assert_eq!(
+            "title^^3".parse::<BoostedField>(),
+            Err(BoostedFieldError::InvalidField("title^".to_string()))
+        );
+        assert_eq!(
+            BoostedField::new("title", -1.0).validate(),
+            Err(BoostedFieldError::InvalidBoost("-1".to_string()))
+        );
+        assert!(serde_json::from_str::<BoostedField>("\"title^inf\"").is_err());
+    }
+}
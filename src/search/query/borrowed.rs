@@ -0,0 +1,551 @@
+//! Borrowed variants of the most commonly built leaf queries.
+//!
+//! These serialize exactly like their owned counterparts but borrow their
+//! strings where possible, so building (and serializing) a query from existing
+//! `&str` data doesn't require cloning every field.
+
+use std::borrow::Cow;
+
+use elastiql_derive::twin_impl;
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+use super::{MatchQuery, Query, RangeQuery, TermQuery, TermsQuery};
+#[cfg(feature = "graphql")]
+use super::{RangeQueryInput, TermQueryInput};
+
+/// A borrowed [`TermQuery`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct TermQueryRef<'a> {
+    /// The name of the field to query.
+    pub field: Cow<'a, str>,
+
+    /// The **exact** term to find in the provided field.
+    pub value: Cow<'a, str>,
+
+    /// Floating point number used to decrease or increase the relevance scores
+    /// of a query.
+    pub boost: Option<f64>,
+}
+
+impl<'a> TermQueryRef<'a> {
+    /// Constructs a new `TermQueryRef`.
+    #[inline]
+    pub fn new(field: impl Into<Cow<'a, str>>, value: impl Into<Cow<'a, str>>) -> Self {
+        TermQueryRef {
+            field: field.into(),
+            value: value.into(),
+            boost: None,
+        }
+    }
+
+    /// Converts this into an owned [`TermQuery`], only cloning the strings
+    /// that are borrowed.
+    #[inline]
+    pub fn into_owned(self) -> TermQuery {
+        TermQuery {
+            field: self.field.into_owned(),
+            value: self.value.into_owned(),
+            boost: self.boost,
+        }
+    }
+}
+
+#[twin_impl(TermQuery)]
+impl<'a> From<&'a TermQuery> for TermQueryRef<'a> {
+    #[inline]
+    fn from(query: &'a TermQuery) -> Self {
+        TermQueryRef {
+            field: Cow::Borrowed(&query.field),
+            value: Cow::Borrowed(&query.value),
+            boost: query.boost,
+        }
+    }
+}
+
+impl From<TermQueryRef<'_>> for TermQuery {
+    #[inline]
+    fn from(query: TermQueryRef<'_>) -> Self {
+        query.into_owned()
+    }
+}
+
+impl Serialize for TermQueryRef<'_> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        /// The body of a `term` query.
+        struct Inner<'b>(&'b TermQueryRef<'b>);
+
+        impl Serialize for Inner<'_> {
+            #[inline]
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut map = serializer.serialize_map(None)?;
+                map.serialize_entry("value", &self.0.value)?;
+                if let Some(boost) = self.0.boost {
+                    map.serialize_entry("boost", &boost)?;
+                }
+                map.end()
+            }
+        }
+
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(&self.field, &Inner(self))?;
+        map.end()
+    }
+}
+
+/// A borrowed [`TermsQuery`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct TermsQueryRef<'a> {
+    /// The name of the field to query.
+    pub field: Cow<'a, str>,
+
+    /// The **exact** terms to find in the provided field.
+    pub values: Vec<Cow<'a, str>>,
+
+    /// Floating point number used to decrease or increase the relevance scores
+    /// of a query.
+    pub boost: Option<f64>,
+}
+
+impl<'a> TermsQueryRef<'a> {
+    /// Constructs a new `TermsQueryRef`.
+    #[inline]
+    pub fn new<T: Into<Cow<'a, str>>>(
+        field: impl Into<Cow<'a, str>>,
+        values: impl IntoIterator<Item = T>,
+    ) -> Self {
+        TermsQueryRef {
+            field: field.into(),
+            values: values.into_iter().map(Into::into).collect(),
+            boost: None,
+        }
+    }
+
+    /// Converts this into an owned [`TermsQuery`], only cloning the strings
+    /// that are borrowed.
+    #[inline]
+    pub fn into_owned(self) -> TermsQuery {
+        TermsQuery {
+            field: self.field.into_owned(),
+            values: self.values.into_iter().map(Cow::into_owned).collect(),
+            boost: self.boost,
+        }
+    }
+}
+
+impl<'a> From<&'a TermsQuery> for TermsQueryRef<'a> {
+    #[inline]
+    fn from(query: &'a TermsQuery) -> Self {
+        TermsQueryRef {
+            field: Cow::Borrowed(&query.field),
+            values: query
+                .values
+                .iter()
+                .map(|v| Cow::Borrowed(v.as_str()))
+                .collect(),
+            boost: query.boost,
+        }
+    }
+}
+
+impl From<TermsQueryRef<'_>> for TermsQuery {
+    #[inline]
+    fn from(query: TermsQueryRef<'_>) -> Self {
+        query.into_owned()
+    }
+}
+
+impl Serialize for TermsQueryRef<'_> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry(&self.field, &self.values)?;
+        if let Some(boost) = self.boost {
+            map.serialize_entry("boost", &boost)?;
+        }
+        map.end()
+    }
+}
+
+/// A borrowed [`RangeQuery`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct RangeQueryRef<'a> {
+    /// The name of the field to query.
+    pub field: Cow<'a, str>,
+
+    /// Greater than.
+    pub greater_than: Option<Cow<'a, str>>,
+
+    /// Greater than or equal to.
+    pub greater_than_or_equal_to: Option<Cow<'a, str>>,
+
+    /// Less than.
+    pub less_than: Option<Cow<'a, str>>,
+
+    /// Less than or equal to.
+    pub less_than_or_equal_to: Option<Cow<'a, str>>,
+
+    /// UTC offset or IANA time zone used to convert date values in the query
+    /// to UTC.
+    pub time_zone: Option<Cow<'a, str>>,
+
+    /// Floating point number used to decrease or increase the relevance scores
+    /// of a query.
+    pub boost: Option<f64>,
+}
+
+impl<'a> RangeQueryRef<'a> {
+    /// Constructs a new, unbounded `RangeQueryRef` on `field`.
+    #[inline]
+    pub fn new(field: impl Into<Cow<'a, str>>) -> Self {
+        RangeQueryRef {
+            field: field.into(),
+            greater_than: None,
+            greater_than_or_equal_to: None,
+            less_than: None,
+            less_than_or_equal_to: None,
+            time_zone: None,
+            boost: None,
+        }
+    }
+
+    /// Only match values greater than `value`.
+    #[inline]
+    pub fn gt(mut self, value: impl Into<Cow<'a, str>>) -> Self {
+        self.greater_than = Some(value.into());
+        self
+    }
+
+    /// Only match values greater than or equal to `value`.
+    #[inline]
+    pub fn gte(mut self, value: impl Into<Cow<'a, str>>) -> Self {
+        self.greater_than_or_equal_to = Some(value.into());
+        self
+    }
+
+    /// Only match values less than `value`.
+    #[inline]
+    pub fn lt(mut self, value: impl Into<Cow<'a, str>>) -> Self {
+        self.less_than = Some(value.into());
+        self
+    }
+
+    /// Only match values less than or equal to `value`.
+    #[inline]
+    pub fn lte(mut self, value: impl Into<Cow<'a, str>>) -> Self {
+        self.less_than_or_equal_to = Some(value.into());
+        self
+    }
+
+    /// Converts this into an owned [`RangeQuery`], only cloning the strings
+    /// that are borrowed.
+    #[inline]
+    pub fn into_owned(self) -> RangeQuery {
+        RangeQuery {
+            field: self.field.into_owned(),
+            greater_than: self.greater_than.map(Cow::into_owned),
+            greater_than_or_equal_to: self.greater_than_or_equal_to.map(Cow::into_owned),
+            less_than: self.less_than.map(Cow::into_owned),
+            less_than_or_equal_to: self.less_than_or_equal_to.map(Cow::into_owned),
+            time_zone: self.time_zone.map(Cow::into_owned),
+            boost: self.boost,
+        }
+    }
+}
+
+#[twin_impl(RangeQuery)]
+impl<'a> From<&'a RangeQuery> for RangeQueryRef<'a> {
+    #[inline]
+    fn from(query: &'a RangeQuery) -> Self {
+        let borrow = |value: &'a Option<String>| value.as_deref().map(Cow::Borrowed);
+
+        RangeQueryRef {
+            field: Cow::Borrowed(&query.field),
+            greater_than: borrow(&query.greater_than),
+            greater_than_or_equal_to: borrow(&query.greater_than_or_equal_to),
+            less_than: borrow(&query.less_than),
+            less_than_or_equal_to: borrow(&query.less_than_or_equal_to),
+            time_zone: borrow(&query.time_zone),
+            boost: query.boost,
+        }
+    }
+}
+
+impl From<RangeQueryRef<'_>> for RangeQuery {
+    #[inline]
+    fn from(query: RangeQueryRef<'_>) -> Self {
+        query.into_owned()
+    }
+}
+
+impl Serialize for RangeQueryRef<'_> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        /// The body of a `range` query.
+        struct Inner<'b>(&'b RangeQueryRef<'b>);
+
+        impl Serialize for Inner<'_> {
+            #[inline]
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let query = self.0;
+                let bounds = [
+                    ("gt", &query.greater_than),
+                    ("gte", &query.greater_than_or_equal_to),
+                    ("lt", &query.less_than),
+                    ("lte", &query.less_than_or_equal_to),
+                    ("time_zone", &query.time_zone),
+                ];
+
+                let mut map = serializer.serialize_map(None)?;
+                for (key, value) in bounds.iter() {
+                    if let Some(value) = value {
+                        map.serialize_entry(key, value)?;
+                    }
+                }
+                if let Some(boost) = query.boost {
+                    map.serialize_entry("boost", &boost)?;
+                }
+                map.end()
+            }
+        }
+
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(&self.field, &Inner(self))?;
+        map.end()
+    }
+}
+
+/// A borrowed [`MatchQuery`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MatchQueryRef<'a> {
+    /// The name of the field to query.
+    pub field: Cow<'a, str>,
+
+    /// The text, number, boolean value or date to find in the provided field.
+    pub query: Cow<'a, str>,
+}
+
+impl<'a> MatchQueryRef<'a> {
+    /// Constructs a new `MatchQueryRef`.
+    #[inline]
+    pub fn new(field: impl Into<Cow<'a, str>>, query: impl Into<Cow<'a, str>>) -> Self {
+        MatchQueryRef {
+            field: field.into(),
+            query: query.into(),
+        }
+    }
+
+    /// Converts this into an owned [`MatchQuery`], only cloning the strings
+    /// that are borrowed.
+    #[inline]
+    pub fn into_owned(self) -> MatchQuery {
+        MatchQuery {
+            field: self.field.into_owned(),
+            query: self.query.into_owned(),
+        }
+    }
+}
+
+impl<'a> From<&'a MatchQuery> for MatchQueryRef<'a> {
+    #[inline]
+    fn from(query: &'a MatchQuery) -> Self {
+        MatchQueryRef {
+            field: Cow::Borrowed(&query.field),
+            query: Cow::Borrowed(&query.query),
+        }
+    }
+}
+
+impl From<MatchQueryRef<'_>> for MatchQuery {
+    #[inline]
+    fn from(query: MatchQueryRef<'_>) -> Self {
+        query.into_owned()
+    }
+}
+
+impl Serialize for MatchQueryRef<'_> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        /// The body of a `match` query.
+        struct Inner<'b>(&'b str);
+
+        impl Serialize for Inner<'_> {
+            #[inline]
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("query", self.0)?;
+                map.end()
+            }
+        }
+
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(&self.field, &Inner(&self.query))?;
+        map.end()
+    }
+}
+
+/// A single borrowed search query, which serializes exactly like the
+/// equivalent [`Query`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum QueryRef<'a> {
+    /// A [`term` query](TermQueryRef).
+    Term(TermQueryRef<'a>),
+
+    /// A [`terms` query](TermsQueryRef).
+    Terms(TermsQueryRef<'a>),
+
+    /// A [`range` query](RangeQueryRef).
+    Range(RangeQueryRef<'a>),
+
+    /// A [`match` query](MatchQueryRef).
+    Match(MatchQueryRef<'a>),
+
+    /// Any other query.
+    Query(&'a Query),
+}
+
+impl QueryRef<'_> {
+    /// Converts this into an owned [`Query`], only cloning the data that is
+    /// borrowed.
+    #[inline]
+    pub fn into_owned(self) -> Query {
+        match self {
+            QueryRef::Term(query) => query.into_owned().into(),
+            QueryRef::Terms(query) => query.into_owned().into(),
+            QueryRef::Range(query) => query.into_owned().into(),
+            QueryRef::Match(query) => query.into_owned().into(),
+            QueryRef::Query(query) => query.clone(),
+        }
+    }
+}
+
+impl<'a> From<TermQueryRef<'a>> for QueryRef<'a> {
+    #[inline]
+    fn from(query: TermQueryRef<'a>) -> Self {
+        QueryRef::Term(query)
+    }
+}
+
+impl<'a> From<TermsQueryRef<'a>> for QueryRef<'a> {
+    #[inline]
+    fn from(query: TermsQueryRef<'a>) -> Self {
+        QueryRef::Terms(query)
+    }
+}
+
+impl<'a> From<RangeQueryRef<'a>> for QueryRef<'a> {
+    #[inline]
+    fn from(query: RangeQueryRef<'a>) -> Self {
+        QueryRef::Range(query)
+    }
+}
+
+impl<'a> From<MatchQueryRef<'a>> for QueryRef<'a> {
+    #[inline]
+    fn from(query: MatchQueryRef<'a>) -> Self {
+        QueryRef::Match(query)
+    }
+}
+
+impl<'a> From<&'a Query> for QueryRef<'a> {
+    #[inline]
+    fn from(query: &'a Query) -> Self {
+        QueryRef::Query(query)
+    }
+}
+
+impl From<QueryRef<'_>> for Query {
+    #[inline]
+    fn from(query: QueryRef<'_>) -> Self {
+        query.into_owned()
+    }
+}
+
+impl Serialize for QueryRef<'_> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if let QueryRef::Query(query) = self {
+            return query.serialize(serializer);
+        }
+
+        let mut map = serializer.serialize_map(Some(1))?;
+        match self {
+            QueryRef::Term(query) => map.serialize_entry("term", query)?,
+            QueryRef::Terms(query) => map.serialize_entry("terms", query)?,
+            QueryRef::Range(query) => map.serialize_entry("range", query)?,
+            QueryRef::Match(query) => map.serialize_entry("match", query)?,
+            QueryRef::Query(_) => unreachable!(),
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_case {
+        ($name:ident : $borrowed:expr, $owned:expr) => {
+            mod $name {
+                use super::*;
+
+                #[test]
+                fn serializes_like_owned() {
+                    let borrowed = QueryRef::from($borrowed);
+                    let owned = Query::from($owned);
+
+                    assert_eq!(
+                        serde_json::to_value(&borrowed).unwrap(),
+                        serde_json::to_value(&owned).unwrap(),
+                    );
+                }
+
+                #[test]
+                fn can_convert_into_owned() {
+                    let borrowed = QueryRef::from($borrowed);
+                    assert_eq!(borrowed.into_owned(), Query::from($owned));
+                }
+            }
+        };
+    }
+
+    test_case!(
+        term:
+        TermQueryRef::new("status", "open"),
+        TermQuery::new("status", "open")
+    );
+
+    test_case!(
+        terms:
+        TermsQueryRef::new("status", vec!["open", "closed"]),
+        TermsQuery::new("status", vec!["open", "closed"])
+    );
+
+    test_case!(
+        range:
+        RangeQueryRef::new("age").gte("18").lt("65"),
+        RangeQuery::new("age").gte(18).lt(65)
+    );
+
+    test_case!(
+        match_:
+        MatchQueryRef::new("name", "kim"),
+        MatchQuery::new("name", "kim")
+    );
+
+    #[test]
+    fn can_borrow_owned_queries() {
+        let query = TermQuery {
+            boost: Some(2.0),
+            ..TermQuery::new("status", "open")
+        };
+
+        let borrowed = TermQueryRef::from(&query);
+        assert!(matches!(borrowed.field, Cow::Borrowed("status")));
+        assert!(matches!(borrowed.value, Cow::Borrowed("open")));
+        assert_eq!(
+            serde_json::to_value(&borrowed).unwrap(),
+            serde_json::to_value(&query).unwrap(),
+        );
+    }
+}
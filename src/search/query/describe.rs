@@ -0,0 +1,838 @@
+//! Human-readable explanations of (possibly deeply nested) queries, e.g. for
+//! audit logs or for showing end users what a saved filter does.
+
+use std::fmt::Write;
+
+use super::span::{
+    SpanContainingQuery, SpanFirstQuery, SpanMultiQuery, SpanNearQuery, SpanNotQuery, SpanOrQuery,
+    SpanQuery, SpanTermQuery, SpanWithinQuery,
+};
+use super::{BooleanQuery, CompoundQuery, GeoShapeRelation, LikeItem, Query, RangeQuery};
+
+/// The number of spaces each nesting level is indented by.
+const INDENT: usize = 2;
+
+/// Renders queries as indented lines of text.
+#[derive(Default)]
+struct Describer {
+    out: String,
+}
+
+impl Describer {
+    /// Appends a line of `text` indented `depth` levels deep.
+    fn line(&mut self, depth: usize, text: impl AsRef<str>) {
+        if !self.out.is_empty() {
+            self.out.push('\n');
+        }
+        let _ = write!(self.out, "{:1$}{2}", "", depth * INDENT, text.as_ref());
+    }
+
+    fn query(&mut self, query: &Query, depth: usize) {
+        if query.is_empty() {
+            self.line(depth, "matches all documents");
+        }
+
+        if let Some(ref query) = query.exists {
+            self.line(depth, format!("{} exists", query.field));
+        }
+        if let Some(ref query) = query.term {
+            self.line(
+                depth,
+                format!("{} is {:?}{}", query.field, query.value, boost(query.boost)),
+            );
+        }
+        if let Some(ref query) = query.terms {
+            let values: Vec<_> = query.values.iter().map(|v| format!("{:?}", v)).collect();
+            self.line(
+                depth,
+                format!(
+                    "{} is one of {}{}",
+                    query.field,
+                    values.join(", "),
+                    boost(query.boost)
+                ),
+            );
+        }
+        if let Some(ref query) = query.terms_set {
+            let terms: Vec<_> = query.terms.iter().map(|t| format!("{:?}", t)).collect();
+            let minimum = match (
+                &query.minimum_should_match_field,
+                &query.minimum_should_match_script,
+            ) {
+                (Some(field), _) => format!("at least {}", field),
+                (_, Some(_)) => "at least as many as a script returns".to_string(),
+                _ => "some".to_string(),
+            };
+            self.line(
+                depth,
+                format!(
+                    "{} contains {} of {}{}",
+                    query.field,
+                    minimum,
+                    terms.join(", "),
+                    boost(query.boost)
+                ),
+            );
+        }
+        if let Some(ref query) = query.ids {
+            let values: Vec<_> = query.values.iter().map(|v| format!("{:?}", v)).collect();
+            self.line(depth, format!("_id is one of {}", values.join(", ")));
+        }
+        if let Some(ref query) = query.range {
+            self.line(depth, range(query));
+        }
+        if let Some(ref query) = query.prefix {
+            let case = if query.case_insensitive {
+                " (case insensitive)"
+            } else {
+                ""
+            };
+            self.line(
+                depth,
+                format!(
+                    "{} starts with {:?}{}{}",
+                    query.field,
+                    query.value,
+                    case,
+                    boost(query.boost)
+                ),
+            );
+        }
+        if let Some(ref query) = query.wildcard {
+            let case = if query.case_insensitive {
+                " (case insensitive)"
+            } else {
+                ""
+            };
+            self.line(
+                depth,
+                format!(
+                    "{} matches the wildcard pattern {:?}{}{}",
+                    query.field,
+                    query.value,
+                    case,
+                    boost(query.boost)
+                ),
+            );
+        }
+        if let Some(ref query) = query.regexp {
+            self.line(
+                depth,
+                format!(
+                    "{} matches the regular expression /{}/",
+                    query.field, query.value
+                ),
+            );
+        }
+        if let Some(ref query) = query.fuzzy {
+            self.line(
+                depth,
+                format!("{} is similar to {:?}", query.field, query.value),
+            );
+        }
+        if let Some(ref query) = query.match_ {
+            self.line(depth, format!("{} matches {:?}", query.field, query.query));
+        }
+        if let Some(ref query) = query.match_phrase {
+            self.line(
+                depth,
+                format!("{} matches the phrase {:?}", query.field, query.query),
+            );
+        }
+        if let Some(ref query) = query.match_phrase_prefix {
+            self.line(
+                depth,
+                format!(
+                    "{} matches the phrase prefix {:?}",
+                    query.field, query.query
+                ),
+            );
+        }
+        if let Some(ref query) = query.multi_match {
+            self.line(
+                depth,
+                format!("{} matches {:?}", fields(&query.fields), query.query),
+            );
+        }
+        if let Some(ref query) = query.more_like_this {
+            self.line(
+                depth,
+                format!(
+                    "more like {} on {}",
+                    like_items(&query.like),
+                    fields(&query.fields)
+                ),
+            );
+        }
+        if let Some(ref query) = query.percolate {
+            let documents = match (&query.index, &query.id) {
+                (Some(index), Some(id)) => format!("document {:?} in {}", id, index),
+                _ if query.document.is_some() => "a document".to_string(),
+                _ => format!("{} documents", query.documents.len()),
+            };
+            self.line(depth, format!("{} matches {}", query.field, documents));
+        }
+        if query.script.is_some() {
+            self.line(depth, "a script returns true");
+        }
+        if let Some(ref query) = query.geo_distance {
+            self.line(
+                depth,
+                format!(
+                    "{} is within {} of {}",
+                    query.field, query.distance, query.point
+                ),
+            );
+        }
+        if let Some(ref query) = query.geo_bounding_box {
+            let bounding_box = match (&query.top_left, &query.bottom_right, &query.wkt) {
+                (Some(top_left), Some(bottom_right), _) => {
+                    format!("the box from {} to {}", top_left, bottom_right)
+                }
+                (_, _, Some(wkt)) => wkt.to_string(),
+                _ => "a bounding box".to_string(),
+            };
+            self.line(depth, format!("{} is within {}", query.field, bounding_box));
+        }
+        if let Some(ref query) = query.geo_shape {
+            let shape = match (&query.shape, &query.indexed_shape) {
+                (Some(shape), _) => format!("a {}", shape.type_name()),
+                (_, Some(indexed)) => {
+                    format!(
+                        "the shape of document {:?} in {}",
+                        indexed.id, indexed.index
+                    )
+                }
+                _ => "a shape".to_string(),
+            };
+            let relation = query.relation.unwrap_or(GeoShapeRelation::Intersects);
+            self.line(
+                depth,
+                format!("{} {} {}", query.field, relation.as_phrase(), shape),
+            );
+        }
+        if let Some(ref query) = query.geo_polygon {
+            self.line(
+                depth,
+                format!(
+                    "{} is within a polygon of {} points",
+                    query.field,
+                    query.points.len()
+                ),
+            );
+        }
+        if let Some(ref query) = query.simple_query_string {
+            self.line(
+                depth,
+                format!(
+                    "simple query string {:?} on {}",
+                    query.query,
+                    fields(&query.fields)
+                ),
+            );
+        }
+        if let Some(ref query) = query.query_string {
+            let fields = match query.default_field {
+                Some(ref field) if query.fields.is_empty() => field.to_owned(),
+                _ => fields(&query.fields),
+            };
+            self.line(
+                depth,
+                format!("query string {:?} on {}", query.query, fields),
+            );
+        }
+        if let Some(ref query) = query.knn {
+            self.line(
+                depth,
+                format!(
+                    "{} is one of the {} nearest neighbors of a {}-dimensional vector{}",
+                    query.field,
+                    query.k,
+                    query.query_vector.len(),
+                    boost(query.boost)
+                ),
+            );
+        }
+        if let Some(ref query) = query.rank_feature {
+            self.line(
+                depth,
+                format!(
+                    "scored by the {} of {}{}",
+                    query.function(),
+                    query.field,
+                    boost(query.boost)
+                ),
+            );
+        }
+        if let Some(ref query) = query.span_term {
+            self.span_term(query, depth);
+        }
+        if let Some(ref query) = query.span_near {
+            self.span_near(query, depth);
+        }
+        if let Some(ref query) = query.span_first {
+            self.span_first(query, depth);
+        }
+        if let Some(ref query) = query.span_or {
+            self.span_or(query, depth);
+        }
+        if let Some(ref query) = query.span_not {
+            self.span_not(query, depth);
+        }
+        if let Some(ref query) = query.span_containing {
+            self.span_containing(query, depth);
+        }
+        if let Some(ref query) = query.span_within {
+            self.span_within(query, depth);
+        }
+        if let Some(ref query) = query.span_multi {
+            self.span_multi(query, depth);
+        }
+        if let Some(ref query) = query.script_score {
+            self.line(depth, "scored by a script, matches:");
+            self.compound(&query.query, depth + 1);
+        }
+        if let Some(ref query) = query.nested {
+            self.line(depth, format!("nested objects at {} match:", query.path));
+            self.compound(&query.query, depth + 1);
+        }
+        if let Some(ref query) = query.boolean {
+            self.boolean(query, depth);
+        }
+    }
+
+    fn compound(&mut self, query: &CompoundQuery, depth: usize) {
+        match (&query.boolean, &query.root) {
+            (Some(boolean), Some(root)) => {
+                self.line(depth, "must match all of:");
+                self.query(root, depth + 1);
+                self.boolean(boolean, depth + 1);
+            }
+            (Some(boolean), None) => self.boolean(boolean, depth),
+            (None, Some(root)) => self.query(root, depth),
+            (None, None) => self.line(depth, "matches all documents"),
+        }
+    }
+
+    fn boolean(&mut self, query: &BooleanQuery, depth: usize) {
+        if query.is_empty() {
+            self.line(depth, "matches all documents");
+        }
+
+        self.clauses(depth, "must match all of:", &query.must);
+        self.clauses(depth, "must match all of (without scoring):", &query.filter);
+
+        let required = !query.must.is_empty() || !query.filter.is_empty();
+        let should = match query.minimum_should_match {
+            Some(ref minimum) => format!("must match at least {} of:", minimum),
+            None if required => "should match any of (for scoring):".to_string(),
+            None => "must match at least one of:".to_string(),
+        };
+        self.clauses(depth, &should, &query.should);
+
+        self.clauses(depth, "must not match any of:", &query.must_not);
+
+        if let Some(boost) = query.boost {
+            self.line(depth, format!("with a boost of {}", boost));
+        }
+    }
+
+    fn span(&mut self, query: &SpanQuery, depth: usize) {
+        if query.is_empty() {
+            self.line(depth, "matches no spans");
+        }
+
+        if let Some(ref query) = query.span_term {
+            self.span_term(query, depth);
+        }
+        if let Some(ref query) = query.span_near {
+            self.span_near(query, depth);
+        }
+        if let Some(ref query) = query.span_first {
+            self.span_first(query, depth);
+        }
+        if let Some(ref query) = query.span_or {
+            self.span_or(query, depth);
+        }
+        if let Some(ref query) = query.span_not {
+            self.span_not(query, depth);
+        }
+        if let Some(ref query) = query.span_containing {
+            self.span_containing(query, depth);
+        }
+        if let Some(ref query) = query.span_within {
+            self.span_within(query, depth);
+        }
+        if let Some(ref query) = query.span_multi {
+            self.span_multi(query, depth);
+        }
+    }
+
+    fn span_term(&mut self, query: &SpanTermQuery, depth: usize) {
+        self.line(
+            depth,
+            format!(
+                "{} contains the term {:?}{}",
+                query.field,
+                query.value,
+                boost(query.boost)
+            ),
+        );
+    }
+
+    fn span_near(&mut self, query: &SpanNearQuery, depth: usize) {
+        let order = if query.in_order == Some(false) {
+            ""
+        } else {
+            " (in order)"
+        };
+        self.line(
+            depth,
+            format!(
+                "spans within {} positions of each other{}{}:",
+                query.slop.unwrap_or(0),
+                order,
+                boost(query.boost)
+            ),
+        );
+        for clause in &query.clauses {
+            self.span(clause, depth + 1);
+        }
+    }
+
+    fn span_first(&mut self, query: &SpanFirstQuery, depth: usize) {
+        self.line(
+            depth,
+            format!(
+                "spans ending within the first {} positions{}:",
+                query.end,
+                boost(query.boost)
+            ),
+        );
+        self.span(&query.match_, depth + 1);
+    }
+
+    fn span_or(&mut self, query: &SpanOrQuery, depth: usize) {
+        self.line(depth, format!("any of the spans{}:", boost(query.boost)));
+        for clause in &query.clauses {
+            self.span(clause, depth + 1);
+        }
+    }
+
+    fn span_not(&mut self, query: &SpanNotQuery, depth: usize) {
+        self.line(depth, format!("spans of{}:", boost(query.boost)));
+        self.span(&query.include, depth + 1);
+        self.line(depth, "not overlapping spans of:");
+        self.span(&query.exclude, depth + 1);
+    }
+
+    fn span_containing(&mut self, query: &SpanContainingQuery, depth: usize) {
+        self.line(depth, format!("spans of{}:", boost(query.boost)));
+        self.span(&query.big, depth + 1);
+        self.line(depth, "containing spans of:");
+        self.span(&query.little, depth + 1);
+    }
+
+    fn span_within(&mut self, query: &SpanWithinQuery, depth: usize) {
+        self.line(depth, format!("spans of{}:", boost(query.boost)));
+        self.span(&query.little, depth + 1);
+        self.line(depth, "within spans of:");
+        self.span(&query.big, depth + 1);
+    }
+
+    fn span_multi(&mut self, query: &SpanMultiQuery, depth: usize) {
+        self.line(depth, "spans of the terms matching:");
+        self.query(&query.match_, depth + 1);
+    }
+
+    /// Appends the clauses of a `bool` query, if there are any.
+    fn clauses(&mut self, depth: usize, header: &str, queries: &[Query]) {
+        if queries.is_empty() {
+            return;
+        }
+
+        self.line(depth, header);
+        for query in queries {
+            self.query(query, depth + 1);
+        }
+    }
+}
+
+/// Returns a description of the bounds of a `range` query.
+fn range(query: &RangeQuery) -> String {
+    let bounds: Vec<_> = [
+        (">", &query.greater_than),
+        (">=", &query.greater_than_or_equal_to),
+        ("<", &query.less_than),
+        ("<=", &query.less_than_or_equal_to),
+    ]
+    .iter()
+    .filter_map(|(op, value)| Some(format!("{} {:?}", op, value.as_ref()?)))
+    .collect();
+
+    let mut description = if bounds.is_empty() {
+        format!("{} has any value", query.field)
+    } else {
+        format!("{} is {}", query.field, bounds.join(" and "))
+    };
+    if let Some(ref time_zone) = query.time_zone {
+        let _ = write!(description, " (in time zone {})", time_zone);
+    }
+    description.push_str(&boost(query.boost));
+
+    description
+}
+
+/// Returns a description of the fields a full text query runs on.
+fn fields(fields: &[String]) -> String {
+    if fields.is_empty() {
+        "the default fields".to_string()
+    } else {
+        fields.join(", ")
+    }
+}
+
+/// Returns a description of the texts and/or documents of a `more_like_this`
+/// query.
+fn like_items(items: &[LikeItem]) -> String {
+    items
+        .iter()
+        .map(|item| match (&item.text, &item.index, &item.id) {
+            (Some(text), _, _) => format!("{:?}", text),
+            (None, Some(index), Some(id)) => format!("document {:?} in {}", id, index),
+            (None, None, Some(id)) => format!("document {:?}", id),
+            _ => "a document".to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Returns a description of an optional boost.
+fn boost(boost: Option<f64>) -> String {
+    boost.map_or_else(String::new, |boost| format!(" (boost {})", boost))
+}
+
+impl Query {
+    /// Returns an indented, human-readable explanation of this query and all
+    /// of its nested clauses, e.g.:
+    ///
+    /// ```text
+    /// must match all of:
+    ///   status is "open"
+    ///   created is >= "now-7d" and <= "now"
+    /// ```
+    pub fn describe(&self) -> String {
+        let mut describer = Describer::default();
+        describer.query(self, 0);
+        describer.out
+    }
+}
+
+impl BooleanQuery {
+    /// Returns an indented, human-readable explanation of this query and all
+    /// of its clauses.
+    ///
+    /// See [`Query::describe`].
+    pub fn describe(&self) -> String {
+        let mut describer = Describer::default();
+        describer.boolean(self, 0);
+        describer.out
+    }
+}
+
+impl CompoundQuery {
+    /// Returns an indented, human-readable explanation of this query and all
+    /// of its clauses.
+    ///
+    /// See [`Query::describe`].
+    pub fn describe(&self) -> String {
+        let mut describer = Describer::default();
+        describer.compound(self, 0);
+        describer.out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::scalars::GeoPoint;
+    use crate::search::query::{
+        ExistsQuery, FuzzyQuery, GeoBoundingBoxQuery, GeoDistanceQuery, GeoPolygonQuery,
+        GeoShapeQuery, IdsQuery, KnnQuery, MatchPhrasePrefixQuery, MatchPhraseQuery, MatchQuery,
+        MoreLikeThisQuery, MultiMatchQuery, NestedQuery, PercolateQuery, PrefixQuery,
+        QueryStringQuery, RankFeatureLog, RankFeatureQuery, RegexpQuery, ScriptQuery,
+        ScriptScoreQuery, TermQuery, TermsQuery, TermsSetQuery, WildcardQuery,
+    };
+    use crate::search::Script;
+
+    macro_rules! test_case {
+        ($name:ident : $f:expr, $s:expr) => {
+            #[test]
+            fn $name() {
+                assert_eq!(Query::from($f).describe(), $s);
+            }
+        };
+    }
+
+    test_case!(exists: ExistsQuery::new("user"), "user exists");
+
+    test_case!(term: TermQuery::new("status", "open"), r#"status is "open""#);
+
+    test_case!(
+        terms:
+        TermsQuery::new("status", vec!["open", "closed"]),
+        r#"status is one of "open", "closed""#
+    );
+
+    test_case!(
+        terms_set:
+        TermsSetQuery::new("tags", vec!["rust", "search"], "required_matches"),
+        r#"tags contains at least required_matches of "rust", "search""#
+    );
+
+    test_case!(
+        ids:
+        IdsQuery::new(vec!["1", "4"]),
+        r#"_id is one of "1", "4""#
+    );
+
+    test_case!(
+        range:
+        RangeQuery::new("age").gte(18).lt(65),
+        r#"age is >= "18" and < "65""#
+    );
+
+    test_case!(
+        prefix:
+        PrefixQuery::new("user", "ki"),
+        r#"user starts with "ki""#
+    );
+
+    test_case!(
+        wildcard:
+        WildcardQuery::new("user", "ki*y"),
+        r#"user matches the wildcard pattern "ki*y""#
+    );
+
+    test_case!(
+        regexp:
+        RegexpQuery::new("user", "k.*y", None::<String>),
+        "user matches the regular expression /k.*y/"
+    );
+
+    test_case!(
+        fuzzy:
+        FuzzyQuery::new("user", "ki"),
+        r#"user is similar to "ki""#
+    );
+
+    test_case!(
+        match_:
+        MatchQuery::new("message", "quick fox"),
+        r#"message matches "quick fox""#
+    );
+
+    test_case!(
+        match_phrase:
+        MatchPhraseQuery::new("message", "quick fox"),
+        r#"message matches the phrase "quick fox""#
+    );
+
+    test_case!(
+        match_phrase_prefix:
+        MatchPhrasePrefixQuery::new("message", "quick f"),
+        r#"message matches the phrase prefix "quick f""#
+    );
+
+    test_case!(
+        multi_match:
+        MultiMatchQuery::new(vec!["subject^3", "message"], "quick fox"),
+        r#"subject^3, message matches "quick fox""#
+    );
+
+    test_case!(
+        more_like_this:
+        MoreLikeThisQuery::new(
+            vec!["title"],
+            vec![LikeItem::from("Once upon a time"), LikeItem::document("imdb", "1")]
+        ),
+        r#"more like "Once upon a time", document "1" in imdb on title"#
+    );
+
+    test_case!(
+        percolate:
+        PercolateQuery::indexed("query", "my-index", "2"),
+        r#"query matches document "2" in my-index"#
+    );
+
+    test_case!(
+        script:
+        ScriptQuery::new(Script::inline("doc['num1'].value > 1")),
+        "a script returns true"
+    );
+
+    test_case!(
+        script_score:
+        ScriptScoreQuery::new(
+            CompoundQuery::from_query(TermQuery::new("status", "open")),
+            Script::inline("doc['likes'].value / 10")
+        ),
+        "scored by a script, matches:\n  status is \"open\""
+    );
+
+    test_case!(
+        geo_distance:
+        GeoDistanceQuery::new("pin.location", GeoPoint::new(40.0, -70.5), "12km"),
+        "pin.location is within 12km of 40,-70.5"
+    );
+
+    test_case!(
+        geo_bounding_box:
+        GeoBoundingBoxQuery::new(
+            "pin.location",
+            GeoPoint::new(40.73, -74.1),
+            GeoPoint::new(40.01, -71.12)
+        ),
+        "pin.location is within the box from 40.73,-74.1 to 40.01,-71.12"
+    );
+
+    test_case!(
+        geo_shape:
+        GeoShapeQuery {
+            relation: Some(GeoShapeRelation::Within),
+            ..GeoShapeQuery::indexed("location", "shapes", "deu")
+        },
+        "location is within the shape of document \"deu\" in shapes"
+    );
+
+    test_case!(
+        geo_polygon:
+        GeoPolygonQuery::new(
+            "person.location",
+            vec![
+                GeoPoint::new(40.0, -70.0),
+                GeoPoint::new(30.0, -80.0),
+                GeoPoint::new(20.0, -90.0)
+            ]
+        ),
+        "person.location is within a polygon of 3 points"
+    );
+
+    test_case!(
+        spans:
+        SpanNotQuery::new(
+            SpanNearQuery::new(
+                vec![
+                    SpanQuery::from(SpanTermQuery::new("body", "quick")),
+                    SpanMultiQuery::new(PrefixQuery::new("body", "fo")).into(),
+                ],
+                1
+            ),
+            SpanTermQuery::new("body", "brown")
+        ),
+        r#"spans of:
+  spans within 1 positions of each other (in order):
+    body contains the term "quick"
+    spans of the terms matching:
+      body starts with "fo"
+not overlapping spans of:
+  body contains the term "brown""#
+    );
+
+    test_case!(
+        rank_feature:
+        RankFeatureQuery {
+            log: Some(RankFeatureLog { scaling_factor: 4.0 }),
+            ..RankFeatureQuery::new("pagerank")
+        },
+        "scored by the log of pagerank"
+    );
+
+    test_case!(
+        knn:
+        KnnQuery::new("image", vec![1.0, 2.0], 5),
+        "image is one of the 5 nearest neighbors of a 2-dimensional vector"
+    );
+
+    test_case!(
+        boolean:
+        BooleanQuery {
+            must: vec![TermQuery::new("status", "open").into()],
+            filter: vec![RangeQuery::new("created").gte("now-7d").lte("now").into()],
+            should: vec![PrefixQuery::new("user", "ki").into()],
+            must_not: vec![ExistsQuery::new("deleted").into()],
+            minimum_should_match: None,
+            boost: Some(2.0),
+        }
+        .into_query(),
+        r#"must match all of:
+  status is "open"
+must match all of (without scoring):
+  created is >= "now-7d" and <= "now"
+should match any of (for scoring):
+  user starts with "ki"
+must not match any of:
+  deleted exists
+with a boost of 2"#
+    );
+
+    test_case!(
+        nested:
+        NestedQuery::new(
+            "comments",
+            BooleanQuery {
+                must: vec![],
+                filter: vec![],
+                should: vec![
+                    MatchQuery::new("comments.text", "great").into(),
+                    TermQuery {
+                        boost: Some(1.5),
+                        ..TermQuery::new("comments.author", "kimchy")
+                    }
+                    .into(),
+                ],
+                must_not: vec![],
+                minimum_should_match: Some("2".to_string()),
+                boost: None,
+            },
+            false,
+        ),
+        r#"nested objects at comments match:
+  must match at least 2 of:
+    comments.text matches "great"
+    comments.author is "kimchy" (boost 1.5)"#
+    );
+
+    #[test]
+    fn can_describe_parsed_queries() {
+        let query: Query = "status:open AND (user:ki* OR NOT age:[18 TO *])"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            query.describe(),
+            r#"must match all of:
+  status matches "open"
+  must match at least one of:
+    user starts with "ki"
+  must not match any of:
+    age is >= "18""#
+        );
+    }
+
+    #[test]
+    fn can_describe_empty_queries() {
+        assert_eq!(Query::default().describe(), "matches all documents");
+        assert_eq!(CompoundQuery::default().describe(), "matches all documents");
+
+        let query = QueryStringQuery {
+            default_field: Some("message".to_string()),
+            ..serde_json::from_str(r#"{ "query": "open" }"#).unwrap()
+        };
+        assert_eq!(
+            Query::from(query).describe(),
+            r#"query string "open" on message"#
+        );
+    }
+}
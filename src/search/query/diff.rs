@@ -0,0 +1,412 @@
+//! Structural differences between two versions of a query, e.g. for the
+//! history of a saved search or to show what changed in the filter of an
+//! alert.
+//!
+//! The clauses of `bool` queries are compared regardless of their order; a
+//! removed and an added clause of the same kind on the same field (e.g. two
+//! `term` queries on `status`) are reported as a single change.
+//!
+//! ```
+//! use elastiql::search::query::{Change, CompoundQuery, TermQuery};
+//!
+//! let mut old = CompoundQuery::default();
+//! old.push(TermQuery::new("status", "open"));
+//! let mut new = old.clone();
+//! new.push(TermQuery::new("user", "kimchy"));
+//!
+//! assert_eq!(
+//!     old.diff(&new).changes,
+//!     vec![Change::Added {
+//!         path: "bool.filter[1]".to_string(),
+//!         query: TermQuery::new("user", "kimchy").into(),
+//!     }]
+//! );
+//! ```
+
+use std::fmt;
+
+use super::{BooleanQuery, CompoundQuery, Query, QueryKind};
+
+/// A difference between two versions of a query, with the path to the clause
+/// in the (JSON) query, e.g. `bool.must[0].nested.query.term`.
+///
+/// The paths of removed clauses point into the old version of the query, the
+/// paths of added and changed clauses into the new one.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Change {
+    /// A clause was added.
+    Added {
+        /// The path to the clause.
+        path: String,
+        /// The added clause.
+        query: Query,
+    },
+
+    /// A clause was removed.
+    Removed {
+        /// The path to the clause.
+        path: String,
+        /// The removed clause.
+        query: Query,
+    },
+
+    /// A clause was changed, e.g. the value of a `term` query.
+    Changed {
+        /// The path to the clause.
+        path: String,
+        /// The old version of the clause.
+        old: Box<Query>,
+        /// The new version of the clause.
+        new: Box<Query>,
+    },
+}
+
+impl Change {
+    /// The path to the clause that changed.
+    #[inline]
+    pub fn path(&self) -> &str {
+        match self {
+            Change::Added { path, .. }
+            | Change::Removed { path, .. }
+            | Change::Changed { path, .. } => path,
+        }
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = match self.path() {
+            "" => "query",
+            path => path,
+        };
+        match self {
+            Change::Added { query, .. } => write!(f, "added `{}`: {}", path, query),
+            Change::Removed { query, .. } => write!(f, "removed `{}`: {}", path, query),
+            Change::Changed { old, new, .. } => {
+                write!(f, "changed `{}`: {} -> {}", path, old, new)
+            }
+        }
+    }
+}
+
+/// The differences between two versions of a query.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct QueryDiff {
+    /// The changed clauses.
+    pub changes: Vec<Change>,
+}
+
+impl QueryDiff {
+    /// Returns `true` if both versions of the query are the same.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl fmt::Display for QueryDiff {
+    /// Writes each change on its own line.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, change) in self.changes.iter().enumerate() {
+            if i > 0 {
+                f.write_str("\n")?;
+            }
+            write!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the differences between the `old` and `new` versions of a query.
+#[inline]
+pub fn diff(old: &Query, new: &Query) -> QueryDiff {
+    let mut diff = QueryDiff::default();
+    diff.query(old, new, "");
+    diff
+}
+
+impl Query {
+    /// Returns the differences between this and the `new` version of it.
+    ///
+    /// See [`diff`].
+    #[inline]
+    pub fn diff(&self, new: &Query) -> QueryDiff {
+        diff(self, new)
+    }
+}
+
+impl CompoundQuery {
+    /// Returns the differences between this and the `new` version of it.
+    ///
+    /// See [`diff`].
+    #[inline]
+    pub fn diff(&self, new: &CompoundQuery) -> QueryDiff {
+        let mut diff = QueryDiff::default();
+        diff.compound(self, new, "");
+        diff
+    }
+}
+
+impl QueryDiff {
+    fn query(&mut self, old: &Query, new: &Query, path: &str) {
+        if old == new {
+            return;
+        }
+
+        match (old.kind(), new.kind()) {
+            (Some(QueryKind::Boolean), Some(QueryKind::Boolean)) => {
+                if let (Some(old), Some(new)) = (&old.boolean, &new.boolean) {
+                    if has_same_options(old, new) {
+                        return self.boolean(old, new, &join(path, "bool"));
+                    }
+                }
+            }
+            (Some(QueryKind::Nested), Some(QueryKind::Nested)) => {
+                if let (Some(old), Some(new)) = (&old.nested, &new.nested) {
+                    let (mut old_options, mut new_options) = (old.clone(), new.clone());
+                    old_options.query = CompoundQuery::default();
+                    new_options.query = CompoundQuery::default();
+                    if old_options == new_options {
+                        let path = join(path, "nested.query");
+                        return self.compound(&old.query, &new.query, &path);
+                    }
+                }
+            }
+            (Some(QueryKind::ScriptScore), Some(QueryKind::ScriptScore)) => {
+                if let (Some(old), Some(new)) = (&old.script_score, &new.script_score) {
+                    let (mut old_options, mut new_options) = (old.clone(), new.clone());
+                    old_options.query = CompoundQuery::default();
+                    new_options.query = CompoundQuery::default();
+                    if old_options == new_options {
+                        let path = join(path, "script_score.query");
+                        return self.compound(&old.query, &new.query, &path);
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        self.changes.push(Change::Changed {
+            path: path.to_string(),
+            old: Box::new(old.clone()),
+            new: Box::new(new.clone()),
+        });
+    }
+
+    #[inline]
+    fn compound(&mut self, old: &CompoundQuery, new: &CompoundQuery, path: &str) {
+        self.query(&into_query(old), &into_query(new), path)
+    }
+
+    fn boolean(&mut self, old: &BooleanQuery, new: &BooleanQuery, path: &str) {
+        let occurrences = [
+            ("must", &old.must, &new.must),
+            ("filter", &old.filter, &new.filter),
+            ("should", &old.should, &new.should),
+            ("must_not", &old.must_not, &new.must_not),
+        ];
+        for (occurrence, old, new) in occurrences.iter() {
+            let path = |i: usize| format!("{}.{}[{}]", path, occurrence, i);
+
+            // clauses that are in both versions, wherever they are
+            let mut old_left: Vec<_> = (0..old.len()).collect();
+            let mut new_left = vec![];
+            for (j, clause) in new.iter().enumerate() {
+                match old_left.iter().position(|&i| old[i] == *clause) {
+                    Some(position) => {
+                        old_left.remove(position);
+                    }
+                    None => new_left.push(j),
+                }
+            }
+
+            // the rest is paired up by kind and field
+            let mut pairs = vec![];
+            for j in new_left {
+                let key = key(&new[j]);
+                match old_left
+                    .iter()
+                    .position(|&i| key.is_some() && key == self::key(&old[i]))
+                {
+                    Some(position) => pairs.push((Some(old_left.remove(position)), j)),
+                    None => pairs.push((None, j)),
+                }
+            }
+
+            for i in old_left {
+                self.changes.push(Change::Removed {
+                    path: path(i),
+                    query: old[i].clone(),
+                });
+            }
+            for (i, j) in pairs {
+                match i {
+                    Some(i) => self.query(&old[i], &new[j], &path(j)),
+                    None => self.changes.push(Change::Added {
+                        path: path(j),
+                        query: new[j].clone(),
+                    }),
+                }
+            }
+        }
+    }
+}
+
+/// Whether the options (i.e. everything but the clauses) of two `bool`
+/// queries are the same.
+#[inline]
+fn has_same_options(old: &BooleanQuery, new: &BooleanQuery) -> bool {
+    old.minimum_should_match == new.minimum_should_match && old.boost == new.boost
+}
+
+/// Returns the kind of `query` and the field (or `nested` path) it is on (if
+/// any), which identify a clause across versions of a query.
+fn key(query: &Query) -> Option<(QueryKind, &str)> {
+    let field = if let Some(ref query) = query.exists {
+        &query.field
+    } else if let Some(ref query) = query.term {
+        &query.field
+    } else if let Some(ref query) = query.terms {
+        &query.field
+    } else if let Some(ref query) = query.terms_set {
+        &query.field
+    } else if query.ids.is_some() {
+        "_id"
+    } else if let Some(ref query) = query.range {
+        &query.field
+    } else if let Some(ref query) = query.prefix {
+        &query.field
+    } else if let Some(ref query) = query.wildcard {
+        &query.field
+    } else if let Some(ref query) = query.regexp {
+        &query.field
+    } else if let Some(ref query) = query.fuzzy {
+        &query.field
+    } else if let Some(ref query) = query.match_ {
+        &query.field
+    } else if let Some(ref query) = query.match_phrase {
+        &query.field
+    } else if let Some(ref query) = query.match_phrase_prefix {
+        &query.field
+    } else if let Some(ref query) = query.percolate {
+        &query.field
+    } else if let Some(ref query) = query.geo_distance {
+        &query.field
+    } else if let Some(ref query) = query.geo_bounding_box {
+        &query.field
+    } else if let Some(ref query) = query.geo_shape {
+        &query.field
+    } else if let Some(ref query) = query.geo_polygon {
+        &query.field
+    } else if let Some(ref query) = query.span_term {
+        &query.field
+    } else if let Some(ref query) = query.knn {
+        &query.field
+    } else if let Some(ref query) = query.rank_feature {
+        &query.field
+    } else if let Some(ref query) = query.nested {
+        &query.path
+    } else {
+        ""
+    };
+
+    query.kind().map(|kind| (kind, field))
+}
+
+/// Returns `query` as a single `Query`, i.e. its `bool` query (with its `root`
+/// query, if any, in its `must` clauses) or its `root` query.
+#[inline]
+fn into_query(query: &CompoundQuery) -> Query {
+    match (&query.boolean, &query.root) {
+        (None, Some(root)) => (**root).clone(),
+        (None, None) => Query::default(),
+        _ => {
+            let mut query = query.clone();
+            std::mem::take(query.boolean_mut()).into_query()
+        }
+    }
+}
+
+/// Appends `key` to `path`.
+#[inline]
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::search::query::{ExistsQuery, NestedQuery, TermQuery};
+
+    fn query(status: &str, author: &str) -> CompoundQuery {
+        let mut query = CompoundQuery::default();
+        query.push(TermQuery::new("status", status));
+        query.push(ExistsQuery::new("user"));
+        query.push_must_not(NestedQuery::new(
+            "comments",
+            TermQuery::new("comments.author", author),
+            false,
+        ));
+        query
+    }
+
+    #[test]
+    fn same_queries_have_no_changes() {
+        assert!(query("open", "kimchy")
+            .diff(&query("open", "kimchy"))
+            .is_empty());
+    }
+
+    #[test]
+    fn reports_changes_by_path() {
+        let old = query("open", "kimchy");
+        let mut new = query("closed", "elastic");
+        new.boolean.as_mut().unwrap().filter.reverse();
+        new.push_should(ExistsQuery::new("tags"));
+
+        let diff = old.diff(&new);
+        assert_eq!(
+            diff.changes,
+            vec![
+                Change::Changed {
+                    path: "bool.filter[1]".to_string(),
+                    old: Box::new(TermQuery::new("status", "open").into()),
+                    new: Box::new(TermQuery::new("status", "closed").into()),
+                },
+                Change::Added {
+                    path: "bool.should[0]".to_string(),
+                    query: ExistsQuery::new("tags").into(),
+                },
+                Change::Changed {
+                    path: "bool.must_not[0].nested.query.bool.filter[0]".to_string(),
+                    old: Box::new(TermQuery::new("comments.author", "kimchy").into()),
+                    new: Box::new(TermQuery::new("comments.author", "elastic").into()),
+                },
+            ]
+        );
+        assert_eq!(
+            diff.changes[1].to_string(),
+            r#"added `bool.should[0]`: {"exists":{"field":"tags"}}"#
+        );
+    }
+
+    #[test]
+    fn reports_removed_clauses() {
+        let old = query("open", "kimchy");
+        let mut new = old.clone();
+        new.boolean.as_mut().unwrap().filter.remove(0);
+
+        assert_eq!(
+            old.diff(&new).changes,
+            vec![Change::Removed {
+                path: "bool.filter[0]".to_string(),
+                query: TermQuery::new("status", "open").into(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,297 @@
+//! Descriptions of the fields that can be queried, e.g. to expose what can be
+//! filtered on through GraphQL.
+
+use std::collections::HashMap;
+
+use crate::mapping::{Mapping, Property, RuntimeFieldType};
+
+/// Describes a field that can be queried and its type.
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("FilterField"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[derive(Debug)]
+pub struct QueryField {
+    /// The field name.
+    pub field: String,
+
+    // TODO: rename to `ty` once https://github.com/async-graphql/async-graphql/issues/164
+    /// The type
+    pub type_: FieldType,
+}
+
+impl QueryField {
+    /// Create a new `QueryField`.
+    #[inline]
+    pub fn new(field: impl Into<String>, ty: impl Into<FieldType>) -> Self {
+        Self {
+            field: field.into(),
+            type_: ty.into(),
+        }
+    }
+
+    /// Returns the fields of a [`Mapping`], flattened to their full paths
+    /// (e.g. `user.name`), including its multi-fields (e.g. `title.raw`),
+    /// nested fields and runtime fields, sorted by path.
+    ///
+    /// Plain objects aren't queryable themselves, so only their fields are
+    /// returned; `nested` fields are returned too since they are the `path`
+    /// of nested queries.
+    pub fn from_mapping(mapping: &Mapping) -> Vec<QueryField> {
+        // runtime fields shadow the fields of the same name, so they go first
+        // to be kept by the (stable) sort and dedup below
+        let mut fields = mapping
+            .runtime
+            .iter()
+            .map(|(name, field)| QueryField::new(name.as_str(), field.ty))
+            .collect();
+        flatten_properties("", &mapping.properties, &mut fields);
+
+        fields.sort_by(|a, b| a.field.cmp(&b.field));
+        fields.dedup_by(|a, b| a.field == b.field);
+        fields
+    }
+
+    /// Returns the fields of a mapping given as JSON, e.g. the `mappings` of
+    /// an index as returned by the [get mapping API].
+    ///
+    /// See [`QueryField::from_mapping`].
+    ///
+    /// [get mapping API]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-get-mapping.html
+    #[inline]
+    pub fn from_mapping_json(
+        mapping: serde_json::Value,
+    ) -> Result<Vec<QueryField>, serde_json::Error> {
+        Ok(QueryField::from_mapping(&serde_json::from_value(mapping)?))
+    }
+}
+
+/// Pushes the queryable fields of `properties`, prefixing their names with
+/// `prefix`, onto `fields`.
+fn flatten_properties(
+    prefix: &str,
+    properties: &HashMap<String, Property>,
+    fields: &mut Vec<QueryField>,
+) {
+    for (name, property) in properties {
+        let path = format!("{}{}", prefix, name);
+
+        let multi_fields = match property {
+            Property::Object(object) => {
+                flatten_properties(&format!("{}.", path), &object.properties, fields);
+                continue;
+            }
+            Property::Nested(nested) => {
+                flatten_properties(&format!("{}.", path), &nested.properties, fields);
+                None
+            }
+            Property::Text(text) => Some(&text.fields),
+            Property::Keyword(keyword) => Some(&keyword.fields),
+            Property::Date(date) | Property::DateNanos(date) => Some(&date.fields),
+            Property::Long(number)
+            | Property::Integer(number)
+            | Property::Short(number)
+            | Property::Byte(number)
+            | Property::Double(number)
+            | Property::Float(number)
+            | Property::HalfFloat(number)
+            | Property::UnsignedLong(number) => Some(&number.fields),
+            _ => None,
+        };
+
+        if let Some(multi_fields) = multi_fields {
+            flatten_properties(&format!("{}.", path), multi_fields, fields);
+        }
+        fields.push(QueryField::new(path, property));
+    }
+}
+
+open_enum! {
+    /// The [field data type] of a field.
+    ///
+    /// [field data type]: https://www.elastic.co/guide/en/elasticsearch/reference/current/mapping-types.html
+    pub enum FieldType as "FieldType" {
+        /// A full-text `text` field.
+        Text => "text" as "TEXT",
+        /// A structured `keyword` field.
+        Keyword => "keyword" as "KEYWORD",
+        /// A `date` field, with millisecond resolution.
+        Date => "date" as "DATE",
+        /// A `date_nanos` field, with nanosecond resolution.
+        DateNanos => "date_nanos" as "DATE_NANOS",
+        /// A signed 64-bit integer field.
+        Long => "long" as "LONG",
+        /// A signed 32-bit integer field.
+        Integer => "integer" as "INTEGER",
+        /// A signed 16-bit integer field.
+        Short => "short" as "SHORT",
+        /// A signed 8-bit integer field.
+        Byte => "byte" as "BYTE",
+        /// A double-precision 64-bit floating point field.
+        Double => "double" as "DOUBLE",
+        /// A single-precision 32-bit floating point field.
+        Float => "float" as "FLOAT",
+        /// A half-precision 16-bit floating point field.
+        HalfFloat => "half_float" as "HALF_FLOAT",
+        /// An unsigned 64-bit integer field.
+        UnsignedLong => "unsigned_long" as "UNSIGNED_LONG",
+        /// A floating point field stored as a `long`, scaled by a fixed factor.
+        ScaledFloat => "scaled_float" as "SCALED_FLOAT",
+        /// A `boolean` field.
+        Boolean => "boolean" as "BOOLEAN",
+        /// A Base64 encoded `binary` field.
+        Binary => "binary" as "BINARY",
+        /// An IPv4 or IPv6 address field.
+        Ip => "ip" as "IP",
+        /// A latitude/longitude point field.
+        GeoPoint => "geo_point" as "GEO_POINT",
+        /// A `geo_shape` field, e.g. a polygon.
+        GeoShape => "geo_shape" as "GEO_SHAPE",
+        /// A `flattened` field, which maps an entire object as a single field.
+        Flattened => "flattened" as "FLATTENED",
+        /// A `dense_vector` field.
+        DenseVector => "dense_vector" as "DENSE_VECTOR",
+        /// An `object` field.
+        Object => "object" as "OBJECT",
+        /// A `nested` field.
+        Nested => "nested" as "NESTED",
+    }
+}
+
+impl From<&Property> for FieldType {
+    #[inline]
+    fn from(property: &Property) -> Self {
+        match property {
+            Property::Text(_) => FieldType::Text,
+            Property::Keyword(_) => FieldType::Keyword,
+            Property::Date(_) => FieldType::Date,
+            Property::DateNanos(_) => FieldType::DateNanos,
+            Property::Long(_) => FieldType::Long,
+            Property::Integer(_) => FieldType::Integer,
+            Property::Short(_) => FieldType::Short,
+            Property::Byte(_) => FieldType::Byte,
+            Property::Double(_) => FieldType::Double,
+            Property::Float(_) => FieldType::Float,
+            Property::HalfFloat(_) => FieldType::HalfFloat,
+            Property::UnsignedLong(_) => FieldType::UnsignedLong,
+            Property::ScaledFloat(_) => FieldType::ScaledFloat,
+            Property::Boolean(_) => FieldType::Boolean,
+            Property::Binary(_) => FieldType::Binary,
+            Property::Ip(_) => FieldType::Ip,
+            Property::GeoPoint(_) => FieldType::GeoPoint,
+            Property::GeoShape(_) => FieldType::GeoShape,
+            Property::Flattened(_) => FieldType::Flattened,
+            Property::DenseVector(_) => FieldType::DenseVector,
+            Property::Object(_) => FieldType::Object,
+            Property::Nested(_) => FieldType::Nested,
+            Property::Other { ty, .. } => FieldType::from(ty.as_str()),
+        }
+    }
+}
+
+impl From<RuntimeFieldType> for FieldType {
+    #[inline]
+    fn from(ty: RuntimeFieldType) -> Self {
+        match ty {
+            RuntimeFieldType::Keyword => FieldType::Keyword,
+            RuntimeFieldType::Long => FieldType::Long,
+            RuntimeFieldType::Double => FieldType::Double,
+            RuntimeFieldType::Date => FieldType::Date,
+            RuntimeFieldType::Ip => FieldType::Ip,
+            RuntimeFieldType::Boolean => FieldType::Boolean,
+            RuntimeFieldType::GeoPoint => FieldType::GeoPoint,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::mapping::KeywordProperty;
+
+    #[test]
+    fn can_convert_field_types() {
+        assert_eq!(FieldType::from("half_float"), FieldType::HalfFloat);
+        assert_eq!(
+            FieldType::from("completion"),
+            FieldType::Other("completion".to_string())
+        );
+        assert_eq!(FieldType::GeoPoint.to_string(), "geo_point");
+
+        assert_eq!(
+            FieldType::from(&Property::Keyword(KeywordProperty::default())),
+            FieldType::Keyword
+        );
+        assert_eq!(FieldType::from(RuntimeFieldType::Double), FieldType::Double);
+
+        let field = QueryField::new("created", "date");
+        assert_eq!(field.type_, FieldType::Date);
+    }
+
+    #[test]
+    #[cfg(feature = "graphql")]
+    fn is_a_graphql_enum() {
+        use async_graphql::{
+            EmptyMutation, EmptySubscription, InputType, Name, Object, Schema, Value,
+        };
+
+        struct Query;
+
+        #[Object]
+        impl Query {
+            async fn field_type(&self, ty: FieldType) -> FieldType {
+                ty
+            }
+        }
+
+        let sdl = Schema::new(Query, EmptyMutation, EmptySubscription).sdl();
+        assert!(sdl.contains("enum FieldType {"), "{}", sdl);
+        assert!(sdl.contains("\tHALF_FLOAT\n"), "{}", sdl);
+
+        let value = Value::Enum(Name::new("HALF_FLOAT"));
+        assert_eq!(
+            FieldType::parse(Some(value.clone())).ok(),
+            Some(FieldType::HalfFloat)
+        );
+        assert_eq!(FieldType::HalfFloat.to_value(), value);
+
+        let other = FieldType::from("completion");
+        assert_eq!(other.to_value(), Value::Enum(Name::new("OTHER")));
+        assert!(FieldType::parse(Some(other.to_value())).is_err());
+        assert!(FieldType::parse(Some(Value::Enum(Name::new("half_float")))).is_err());
+    }
+
+    #[test]
+    fn can_flatten_mappings() {
+        let fields = QueryField::from_mapping_json(json!({
+            "properties": {
+                "title": { "type": "text", "fields": { "raw": { "type": "keyword" } } },
+                "user": { "properties": { "name": { "type": "keyword" } } },
+                "comments": {
+                    "type": "nested",
+                    "properties": { "created": { "type": "date" } }
+                },
+                "suggest": { "type": "completion" }
+            },
+            "runtime": { "day_of_week": { "type": "keyword" } }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            fields
+                .iter()
+                .map(|field| (field.field.as_str(), field.type_.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("comments", "nested"),
+                ("comments.created", "date"),
+                ("day_of_week", "keyword"),
+                ("suggest", "completion"),
+                ("title", "text"),
+                ("title.raw", "keyword"),
+                ("user.name", "keyword"),
+            ]
+        );
+    }
+}
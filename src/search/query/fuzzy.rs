@@ -0,0 +1,276 @@
+//! [Fuzzy query](https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-fuzzy-query.html)
+
+use std::fmt;
+
+use elastiql_derive::{input_twin, twin_impl};
+#[cfg(feature = "schemars")]
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::de::{self, MapAccess, Visitor};
+use serde::ser::{SerializeMap, Serializer};
+use serde::{Deserialize, Serialize};
+
+#[allow(clippy::missing_docs_in_private_items)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize)]
+struct InnerFuzzyQuery {
+    value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fuzziness: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_expansions: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    prefix_length: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    transpositions: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rewrite: Option<String>,
+}
+
+/// The body of a `FuzzyQuery`, which Elasticsearch also accepts as just the
+/// value itself, e.g. `{ "user": "ki" }`.
+#[allow(clippy::missing_docs_in_private_items)]
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum FuzzyQueryBody {
+    Short(String),
+    Long(InnerFuzzyQuery),
+}
+
+/// A [Fuzzy query] returns documents that contain terms similar to the search
+/// term, as measured by a [Levenshtein edit distance].
+///
+/// [Fuzzy query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-fuzzy-query.html
+/// [Levenshtein edit distance]: https://en.wikipedia.org/wiki/Levenshtein_distance
+#[input_twin(name = "FuzzyFilterInput")]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("FuzzyFilter"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct FuzzyQuery {
+    /// The name of the field to query.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::field))]
+    pub field: String,
+
+    /// The term you wish to find in the provided `field`.
+    pub value: String,
+
+    /// Maximum edit distance allowed for matching, e.g. `AUTO`. See
+    /// [Fuzziness] for valid values and more information. (Defaults to
+    /// `AUTO`.)
+    ///
+    /// [Fuzziness]: https://www.elastic.co/guide/en/elasticsearch/reference/current/common-options.html#fuzziness
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub fuzziness: Option<String>,
+
+    /// Maximum number of variations created. (Defaults to `50`.)
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub max_expansions: Option<u32>,
+
+    /// Number of beginning characters left unchanged when creating
+    /// expansions. (Defaults to `0`.)
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub prefix_length: Option<u32>,
+
+    /// Whether edits include transpositions of two adjacent characters
+    /// (`ab` -> `ba`). (Defaults to `true`.)
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub transpositions: Option<bool>,
+
+    /// Method used to rewrite the query. For valid values and more
+    /// information, see the [`rewrite` parameter].
+    ///
+    /// [`rewrite` parameter]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-multi-term-rewrite.html
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub rewrite: Option<String>,
+}
+
+impl FuzzyQuery {
+    /// Constructs a new `FuzzyQuery`.
+    #[inline]
+    pub fn new(field: impl Into<String>, value: impl Into<String>) -> Self {
+        FuzzyQuery {
+            field: field.into(),
+            value: value.into(),
+            fuzziness: None,
+            max_expansions: None,
+            prefix_length: None,
+            transpositions: None,
+            rewrite: None,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl FuzzyQueryInput {
+    /// Constructs a new `FuzzyQueryInput`.
+    #[inline]
+    pub fn new(field: impl Into<String>, value: impl Into<String>) -> Self {
+        FuzzyQueryInput {
+            field: field.into(),
+            value: value.into(),
+            fuzziness: None,
+            max_expansions: None,
+            prefix_length: None,
+            transpositions: None,
+            rewrite: None,
+        }
+    }
+}
+
+// TODO: re-use the serializer from the input type
+#[twin_impl]
+impl Serialize for FuzzyQuery {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(1))?;
+
+        let inner = InnerFuzzyQuery {
+            value: self.value.to_owned(),
+            fuzziness: self.fuzziness.to_owned(),
+            max_expansions: self.max_expansions,
+            prefix_length: self.prefix_length,
+            transpositions: self.transpositions,
+            rewrite: self.rewrite.to_owned(),
+        };
+
+        map.serialize_entry(&self.field, &inner)?;
+
+        map.end()
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl JsonSchema for FuzzyQuery {
+    #[inline]
+    fn schema_name() -> String {
+        "FuzzyQuery".to_string()
+    }
+
+    #[inline]
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        crate::schema::keyed(InnerFuzzyQuery::json_schema(gen))
+    }
+}
+
+/// Visits a `FuzzyQuery` during deserialization.
+struct FuzzyQueryVisitor;
+
+impl<'de> serde::Deserialize<'de> for FuzzyQuery {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<FuzzyQuery, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(FuzzyQueryVisitor)
+    }
+}
+
+impl<'de> Visitor<'de> for FuzzyQueryVisitor {
+    type Value = FuzzyQuery;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a `FuzzyQuery`")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let field = map
+            .next_key::<String>()?
+            .ok_or_else(|| de::Error::missing_field("field"))?;
+
+        Ok(match map.next_value()? {
+            FuzzyQueryBody::Short(value) => FuzzyQuery::new(field, value),
+            FuzzyQueryBody::Long(inner) => FuzzyQuery {
+                field,
+                value: inner.value,
+                fuzziness: inner.fuzziness,
+                max_expansions: inner.max_expansions,
+                prefix_length: inner.prefix_length,
+                transpositions: inner.transpositions,
+                rewrite: inner.rewrite,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    macro_rules! test_case {
+        ($name:ident : $f:expr, $j:expr) => {
+            mod $name {
+                use super::*;
+
+                #[test]
+                fn can_serialize() {
+                    assert_eq!(serde_json::to_value(&$f).unwrap(), $j);
+                }
+
+                #[test]
+                fn can_deserialize() {
+                    assert_eq!(serde_json::from_value::<FuzzyQuery>($j).unwrap(), $f);
+                }
+            }
+        };
+    }
+
+    test_case!(
+        simple:
+        FuzzyQuery::new("user", "ki"),
+        json!({ "user": { "value": "ki" } })
+    );
+
+    test_case!(
+        with_options:
+        FuzzyQuery {
+            field: "user".to_string(),
+            value: "ki".to_string(),
+            fuzziness: Some("AUTO".to_string()),
+            max_expansions: Some(50),
+            prefix_length: Some(0),
+            transpositions: Some(true),
+            rewrite: Some("constant_score".to_string()),
+        },
+        json!({
+            "user": {
+                "value": "ki",
+                "fuzziness": "AUTO",
+                "max_expansions": 50,
+                "prefix_length": 0,
+                "transpositions": true,
+                "rewrite": "constant_score"
+            }
+        })
+    );
+
+    #[test]
+    fn can_deserialize_short_form() {
+        let j = json!({ "user": "ki" });
+        assert_eq!(
+            serde_json::from_value::<FuzzyQuery>(j).unwrap(),
+            FuzzyQuery::new("user", "ki")
+        );
+    }
+
+    #[test]
+    fn deserialize_invalid_values_is_err() {
+        let j = r#"{ "user": null }"#;
+        assert!(serde_json::from_str::<FuzzyQuery>(j).is_err(), "{}", &j);
+
+        let j = r#"{ "user": { "value": 1 } }"#;
+        assert!(serde_json::from_str::<FuzzyQuery>(j).is_err(), "{}", &j);
+
+        let j = r#"{ "user": { "value": "ki", "prefix_length": -1 } }"#;
+        assert!(serde_json::from_str::<FuzzyQuery>(j).is_err(), "{}", &j);
+
+        let j = r#"{ "user": { "value": "ki", "transpositions": "yes" } }"#;
+        assert!(serde_json::from_str::<FuzzyQuery>(j).is_err(), "{}", &j);
+    }
+}
@@ -0,0 +1,433 @@
+//! [Geo-bounding box query](https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-geo-bounding-box-query.html)
+
+use std::fmt;
+
+use elastiql_derive::{input_twin, twin_impl};
+#[cfg(feature = "schemars")]
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::de::{self, MapAccess, Visitor};
+use serde::ser::{SerializeMap, Serializer};
+use serde::{Deserialize, Serialize};
+
+use crate::scalars::GeoPoint;
+#[cfg(feature = "graphql")]
+use crate::scalars::GeoPointInput;
+
+#[allow(clippy::missing_docs_in_private_items)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct InnerGeoBoundingBox<P = GeoPoint> {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    top_left: Option<P>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bottom_right: Option<P>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    wkt: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    top: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    left: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bottom: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    right: Option<f64>,
+}
+
+/// A [Geo-bounding box query] returns documents with a `geo_point` (or
+/// `geo_shape`) that intersects a bounding box.
+///
+/// **Note**: until GraphQL [Union input types] are supported the bounding box
+/// must be specified *either* by its `top_left` and `bottom_right` points,
+/// *or* as `wkt`, *or* by its `top`, `left`, `bottom` and `right` vertices.
+///
+/// [Geo-bounding box query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-geo-bounding-box-query.html
+/// [Union input types]: https://github.com/graphql/graphql-spec/blob/master/rfcs/InputUnion.md
+#[input_twin(name = "GeoBoundingBoxFilterInput")]
+#[cfg_attr(
+    feature = "graphql",
+    elastiql_derive::graphql_name("GeoBoundingBoxFilter")
+)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct GeoBoundingBoxQuery {
+    /// The name of the field to query.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::field))]
+    pub field: String,
+
+    /// The top left corner of the bounding box.
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[input(twin)]
+    pub top_left: Option<GeoPoint>,
+
+    /// The bottom right corner of the bounding box.
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[input(twin)]
+    pub bottom_right: Option<GeoPoint>,
+
+    /// The bounding box in the [Well-Known Text] `BBOX` format, e.g.
+    /// `BBOX (-74.1, -71.12, 40.73, 40.01)`.
+    ///
+    /// [Well-Known Text]: https://docs.opengeospatial.org/is/12-063r5/12-063r5.html
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub wkt: Option<String>,
+
+    /// The latitude of the top edge of the bounding box.
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::option_float))]
+    pub top: Option<f64>,
+
+    /// The longitude of the left edge of the bounding box.
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::option_float))]
+    pub left: Option<f64>,
+
+    /// The latitude of the bottom edge of the bounding box.
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::option_float))]
+    pub bottom: Option<f64>,
+
+    /// The longitude of the right edge of the bounding box.
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::option_float))]
+    pub right: Option<f64>,
+
+    /// How to handle invalid coordinates. (Defaults to `STRICT`.)
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub validation_method: Option<GeoValidationMethod>,
+
+    /// Whether to ignore an unmapped `field` and not return any documents
+    /// instead of an error. (Defaults to `false`.)
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub ignore_unmapped: Option<bool>,
+}
+
+impl GeoBoundingBoxQuery {
+    /// Constructs a new `GeoBoundingBoxQuery` for the bounding box from
+    /// `top_left` to `bottom_right`.
+    #[inline]
+    pub fn new(
+        field: impl Into<String>,
+        top_left: GeoPoint,
+        bottom_right: GeoPoint,
+    ) -> GeoBoundingBoxQuery {
+        GeoBoundingBoxQuery {
+            top_left: Some(top_left),
+            bottom_right: Some(bottom_right),
+            ..GeoBoundingBoxQuery::empty(field.into())
+        }
+    }
+
+    /// Constructs a new `GeoBoundingBoxQuery` for the bounding box in the
+    /// [Well-Known Text] `BBOX` format, e.g.
+    /// `BBOX (-74.1, -71.12, 40.73, 40.01)`.
+    ///
+    /// [Well-Known Text]: https://docs.opengeospatial.org/is/12-063r5/12-063r5.html
+    #[inline]
+    pub fn from_wkt(field: impl Into<String>, wkt: impl Into<String>) -> GeoBoundingBoxQuery {
+        GeoBoundingBoxQuery {
+            wkt: Some(wkt.into()),
+            ..GeoBoundingBoxQuery::empty(field.into())
+        }
+    }
+
+    /// Returns a `GeoBoundingBoxQuery` on `field` without a bounding box.
+    #[inline]
+    fn empty(field: String) -> GeoBoundingBoxQuery {
+        GeoBoundingBoxQuery {
+            field,
+            top_left: None,
+            bottom_right: None,
+            wkt: None,
+            top: None,
+            left: None,
+            bottom: None,
+            right: None,
+            validation_method: None,
+            ignore_unmapped: None,
+        }
+    }
+}
+
+/// How a geo query handles invalid coordinates.
+#[cfg_attr(
+    feature = "graphql",
+    elastiql_derive::graphql_name("GeoValidationMethod")
+)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum GeoValidationMethod {
+    /// Invalid coordinates are an error.
+    Strict,
+
+    /// Invalid coordinates are accepted as is.
+    IgnoreMalformed,
+
+    /// Invalid coordinates are accepted and normalized.
+    Coerce,
+}
+
+// TODO: re-use the serializer from the input type
+#[twin_impl]
+impl Serialize for GeoBoundingBoxQuery {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+
+        let inner = InnerGeoBoundingBox {
+            top_left: self.top_left,
+            bottom_right: self.bottom_right,
+            wkt: self.wkt.to_owned(),
+            top: self.top,
+            left: self.left,
+            bottom: self.bottom,
+            right: self.right,
+        };
+        map.serialize_entry(&self.field, &inner)?;
+
+        if let Some(ref validation_method) = self.validation_method {
+            map.serialize_entry("validation_method", validation_method)?;
+        }
+        if let Some(ref ignore_unmapped) = self.ignore_unmapped {
+            map.serialize_entry("ignore_unmapped", ignore_unmapped)?;
+        }
+
+        map.end()
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl JsonSchema for GeoBoundingBoxQuery {
+    #[inline]
+    fn schema_name() -> String {
+        "GeoBoundingBoxQuery".to_string()
+    }
+
+    #[inline]
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let mut schema = crate::schema::object(
+            vec![
+                (
+                    "validation_method",
+                    gen.subschema_for::<GeoValidationMethod>(),
+                ),
+                ("ignore_unmapped", gen.subschema_for::<bool>()),
+            ],
+            &[],
+        );
+        schema.object().additional_properties =
+            Some(Box::new(<InnerGeoBoundingBox>::json_schema(gen)));
+        schema.into()
+    }
+}
+
+/// Visits a `GeoBoundingBoxQuery` during deserialization.
+struct GeoBoundingBoxQueryVisitor;
+
+impl<'de> serde::Deserialize<'de> for GeoBoundingBoxQuery {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<GeoBoundingBoxQuery, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(GeoBoundingBoxQueryVisitor)
+    }
+}
+
+impl<'de> Visitor<'de> for GeoBoundingBoxQueryVisitor {
+    type Value = GeoBoundingBoxQuery;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a `GeoBoundingBoxQuery`")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut field = None;
+        let mut validation_method = None;
+        let mut ignore_unmapped = None;
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "validation_method" => validation_method = map.next_value()?,
+                "ignore_unmapped" => ignore_unmapped = map.next_value()?,
+                _ if field.is_some() => {
+                    return Err(de::Error::custom(format!(
+                        "expected a single field but found `{}`",
+                        key
+                    )))
+                }
+                _ => field = Some((key, map.next_value::<InnerGeoBoundingBox>()?)),
+            }
+        }
+
+        let (field, inner) = field.ok_or_else(|| de::Error::missing_field("field"))?;
+        let has_vertices = inner.top.is_some()
+            || inner.left.is_some()
+            || inner.bottom.is_some()
+            || inner.right.is_some();
+        if inner.top_left.is_none()
+            && inner.bottom_right.is_none()
+            && inner.wkt.is_none()
+            && !has_vertices
+        {
+            return Err(de::Error::custom(format!(
+                "expected the corners, `wkt` or vertices of the bounding box of `{}`",
+                field
+            )));
+        }
+
+        Ok(GeoBoundingBoxQuery {
+            field,
+            top_left: inner.top_left,
+            bottom_right: inner.bottom_right,
+            wkt: inner.wkt,
+            top: inner.top,
+            left: inner.left,
+            bottom: inner.bottom,
+            right: inner.right,
+            validation_method,
+            ignore_unmapped,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    macro_rules! test_case {
+        ($name:ident : $f:expr, $j:expr) => {
+            mod $name {
+                use super::*;
+
+                #[test]
+                fn can_serialize() {
+                    assert_eq!(serde_json::to_value(&$f).unwrap(), $j);
+                }
+
+                #[test]
+                fn can_deserialize() {
+                    assert_eq!(
+                        serde_json::from_value::<GeoBoundingBoxQuery>($j).unwrap(),
+                        $f
+                    );
+                }
+            }
+        };
+    }
+
+    test_case!(
+        corners:
+        GeoBoundingBoxQuery {
+            validation_method: Some(GeoValidationMethod::IgnoreMalformed),
+            ignore_unmapped: Some(true),
+            ..GeoBoundingBoxQuery::new(
+                "pin.location",
+                GeoPoint::new(40.73, -74.1),
+                GeoPoint::new(40.01, -71.12),
+            )
+        },
+        json!({
+            "pin.location": {
+                "top_left": { "lat": 40.73, "lon": -74.1 },
+                "bottom_right": { "lat": 40.01, "lon": -71.12 }
+            },
+            "validation_method": "IGNORE_MALFORMED",
+            "ignore_unmapped": true
+        })
+    );
+
+    test_case!(
+        wkt:
+        GeoBoundingBoxQuery::from_wkt("pin.location", "BBOX (-74.1, -71.12, 40.73, 40.01)"),
+        json!({ "pin.location": { "wkt": "BBOX (-74.1, -71.12, 40.73, 40.01)" } })
+    );
+
+    test_case!(
+        vertices:
+        GeoBoundingBoxQuery {
+            top: Some(40.73),
+            left: Some(-74.1),
+            bottom: Some(40.01),
+            right: Some(-71.12),
+            ..GeoBoundingBoxQuery::empty("pin.location".to_string())
+        },
+        json!({
+            "pin.location": { "top": 40.73, "left": -74.1, "bottom": 40.01, "right": -71.12 }
+        })
+    );
+
+    #[test]
+    #[cfg(feature = "graphql")]
+    fn input_serializes_like_the_query() {
+        let query = GeoBoundingBoxQuery {
+            validation_method: Some(GeoValidationMethod::IgnoreMalformed),
+            ..GeoBoundingBoxQuery::new(
+                "pin.location",
+                GeoPoint::new(40.73, -74.1),
+                GeoPoint::new(40.01, -71.12),
+            )
+        };
+
+        assert_eq!(
+            serde_json::to_value(GeoBoundingBoxQueryInput::from(query.clone())).unwrap(),
+            serde_json::to_value(query).unwrap()
+        );
+    }
+
+    #[test]
+    fn deserialize_invalid_values_is_err() {
+        let j = r#"{ "validation_method": "STRICT" }"#;
+        assert!(
+            serde_json::from_str::<GeoBoundingBoxQuery>(j).is_err(),
+            "{}",
+            &j
+        );
+
+        let j = r#"{ "a": { "wkt": "BBOX (1, 2, 3, 4)" }, "b": {} }"#;
+        assert!(
+            serde_json::from_str::<GeoBoundingBoxQuery>(j).is_err(),
+            "{}",
+            &j
+        );
+
+        let j = r#"{ "a": { "top_left": "91,0" } }"#;
+        assert!(
+            serde_json::from_str::<GeoBoundingBoxQuery>(j).is_err(),
+            "{}",
+            &j
+        );
+
+        let j = r#"{ "a": {} }"#;
+        assert!(
+            serde_json::from_str::<GeoBoundingBoxQuery>(j).is_err(),
+            "{}",
+            &j
+        );
+
+        let j = r#"{ "a": { "top_right": "40.73,-71.12", "bottom_left": "40.01,-74.1" } }"#;
+        assert!(
+            serde_json::from_str::<GeoBoundingBoxQuery>(j).is_err(),
+            "{}",
+            &j
+        );
+
+        let j = r#"{ "a": { "top_lfet": "40.73,-74.1", "bottom_right": "40.01,-71.12" } }"#;
+        assert!(
+            serde_json::from_str::<GeoBoundingBoxQuery>(j).is_err(),
+            "{}",
+            &j
+        );
+    }
+}
@@ -0,0 +1,240 @@
+//! [Geo-distance query](https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-geo-distance-query.html)
+
+use std::fmt;
+
+use elastiql_derive::{input_twin, twin_impl};
+#[cfg(feature = "schemars")]
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::de::{self, MapAccess, Visitor};
+use serde::ser::{SerializeMap, Serializer};
+use serde::{Deserialize, Serialize};
+
+use crate::scalars::GeoPoint;
+#[cfg(feature = "graphql")]
+use crate::scalars::GeoPointInput;
+
+/// A [Geo-distance query] returns documents with a `geo_point` (or
+/// `geo_shape`) within the provided `distance` of a `point`.
+///
+/// [Geo-distance query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-geo-distance-query.html
+#[input_twin(name = "GeoDistanceFilterInput")]
+#[cfg_attr(
+    feature = "graphql",
+    elastiql_derive::graphql_name("GeoDistanceFilter")
+)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct GeoDistanceQuery {
+    /// The name of the field to query.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::field))]
+    pub field: String,
+
+    /// The point the distance is measured from.
+    #[input(twin)]
+    pub point: GeoPoint,
+
+    /// The maximum [distance] from the `point`, e.g. `12km`.
+    ///
+    /// [distance]: https://www.elastic.co/guide/en/elasticsearch/reference/current/api-conventions.html#distance-units
+    pub distance: String,
+
+    /// How to compute the distance. (Defaults to `arc`.)
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub distance_type: Option<GeoDistanceType>,
+}
+
+impl GeoDistanceQuery {
+    /// Constructs a new `GeoDistanceQuery`.
+    #[inline]
+    pub fn new(
+        field: impl Into<String>,
+        point: GeoPoint,
+        distance: impl Into<String>,
+    ) -> GeoDistanceQuery {
+        GeoDistanceQuery {
+            field: field.into(),
+            point,
+            distance: distance.into(),
+            distance_type: None,
+        }
+    }
+}
+
+/// How a [`GeoDistanceQuery`] computes the distance between two points.
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("GeoDistanceType"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum GeoDistanceType {
+    /// Accurately, along the surface of the earth.
+    Arc,
+
+    /// Faster, but inaccurate over long distances and close to the poles.
+    Plane,
+}
+
+#[twin_impl]
+impl Serialize for GeoDistanceQuery {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("distance", &self.distance)?;
+        if let Some(ref distance_type) = self.distance_type {
+            map.serialize_entry("distance_type", distance_type)?;
+        }
+        map.serialize_entry(&self.field, &self.point)?;
+        map.end()
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl JsonSchema for GeoDistanceQuery {
+    #[inline]
+    fn schema_name() -> String {
+        "GeoDistanceQuery".to_string()
+    }
+
+    #[inline]
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let mut schema = crate::schema::object(
+            vec![
+                ("distance", gen.subschema_for::<String>()),
+                ("distance_type", gen.subschema_for::<GeoDistanceType>()),
+            ],
+            &["distance"],
+        );
+        schema.object().additional_properties = Some(Box::new(gen.subschema_for::<GeoPoint>()));
+        schema.into()
+    }
+}
+
+/// Visits a `GeoDistanceQuery` during deserialization.
+struct GeoDistanceQueryVisitor;
+
+impl<'de> serde::Deserialize<'de> for GeoDistanceQuery {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<GeoDistanceQuery, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(GeoDistanceQueryVisitor)
+    }
+}
+
+impl<'de> Visitor<'de> for GeoDistanceQueryVisitor {
+    type Value = GeoDistanceQuery;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a `GeoDistanceQuery`")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut field = None;
+        let mut distance = None;
+        let mut distance_type = None;
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "distance" => distance = Some(map.next_value()?),
+                "distance_type" => distance_type = map.next_value()?,
+                _ if field.is_some() => {
+                    return Err(de::Error::custom(format!(
+                        "expected a single field but found `{}`",
+                        key
+                    )))
+                }
+                _ => field = Some((key, map.next_value()?)),
+            }
+        }
+
+        let (field, point) = field.ok_or_else(|| de::Error::missing_field("field"))?;
+        Ok(GeoDistanceQuery {
+            field,
+            point,
+            distance: distance.ok_or_else(|| de::Error::missing_field("distance"))?,
+            distance_type,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn can_serialize_and_deserialize() {
+        let query = GeoDistanceQuery {
+            distance_type: Some(GeoDistanceType::Plane),
+            ..GeoDistanceQuery::new("pin.location", GeoPoint::new(40.0, -70.0), "200km")
+        };
+        let j = json!({
+            "distance": "200km",
+            "distance_type": "plane",
+            "pin.location": { "lat": 40.0, "lon": -70.0 }
+        });
+
+        assert_eq!(serde_json::to_value(&query).unwrap(), j);
+        assert_eq!(
+            serde_json::from_value::<GeoDistanceQuery>(j).unwrap(),
+            query
+        );
+    }
+
+    #[test]
+    fn can_deserialize_other_point_forms() {
+        let query = GeoDistanceQuery::new("pin.location", GeoPoint::new(40.0, -70.0), "12km");
+
+        let j = json!({ "pin.location": "40,-70", "distance": "12km" });
+        assert_eq!(
+            serde_json::from_value::<GeoDistanceQuery>(j).unwrap(),
+            query
+        );
+
+        let j = json!({ "distance": "12km", "pin.location": [-70.0, 40.0] });
+        assert_eq!(
+            serde_json::from_value::<GeoDistanceQuery>(j).unwrap(),
+            query
+        );
+    }
+
+    #[test]
+    fn deserialize_invalid_values_is_err() {
+        let j = r#"{ "pin.location": "40,-70" }"#;
+        assert!(
+            serde_json::from_str::<GeoDistanceQuery>(j).is_err(),
+            "{}",
+            &j
+        );
+
+        let j = r#"{ "distance": "12km" }"#;
+        assert!(
+            serde_json::from_str::<GeoDistanceQuery>(j).is_err(),
+            "{}",
+            &j
+        );
+
+        let j = r#"{ "distance": "12km", "a": "40,-70", "b": "40,-70" }"#;
+        assert!(
+            serde_json::from_str::<GeoDistanceQuery>(j).is_err(),
+            "{}",
+            &j
+        );
+
+        let j = r#"{ "distance": "12km", "distance_type": "sphere", "a": "40,-70" }"#;
+        assert!(
+            serde_json::from_str::<GeoDistanceQuery>(j).is_err(),
+            "{}",
+            &j
+        );
+    }
+}
@@ -0,0 +1,245 @@
+//! [Geo-polygon query](https://www.elastic.co/guide/en/elasticsearch/reference/7.x/query-dsl-geo-polygon-query.html)
+
+use std::fmt;
+
+use elastiql_derive::{input_twin, twin_impl};
+#[cfg(feature = "schemars")]
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::de::{self, MapAccess, Visitor};
+use serde::ser::{SerializeMap, Serializer};
+use serde::{Deserialize, Serialize};
+
+use super::GeoValidationMethod;
+use crate::scalars::GeoPoint;
+#[cfg(feature = "graphql")]
+use crate::scalars::GeoPointInput;
+
+#[allow(clippy::missing_docs_in_private_items)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize)]
+struct InnerGeoPolygonQuery<P = GeoPoint> {
+    points: Vec<P>,
+}
+
+/// A [Geo-polygon query] returns documents with a `geo_point` within a
+/// polygon.
+///
+/// **Note**: it is deprecated since Elasticsearch 7.12 in favor of the
+/// [`GeoShapeQuery`](super::GeoShapeQuery), but still needed for older
+/// indices.
+///
+/// [Geo-polygon query]: https://www.elastic.co/guide/en/elasticsearch/reference/7.x/query-dsl-geo-polygon-query.html
+#[input_twin(name = "GeoPolygonFilterInput")]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("GeoPolygonFilter"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct GeoPolygonQuery {
+    /// The name of the field to query.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::field))]
+    pub field: String,
+
+    /// The vertices of the polygon.
+    #[input(twin)]
+    pub points: Vec<GeoPoint>,
+
+    /// How to handle invalid coordinates. (Defaults to `STRICT`.)
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub validation_method: Option<GeoValidationMethod>,
+}
+
+impl GeoPolygonQuery {
+    /// Constructs a new `GeoPolygonQuery` for the polygon with the vertices
+    /// `points`.
+    #[inline]
+    pub fn new(field: impl Into<String>, points: Vec<GeoPoint>) -> GeoPolygonQuery {
+        GeoPolygonQuery {
+            field: field.into(),
+            points,
+            validation_method: None,
+        }
+    }
+}
+
+// TODO: re-use the serializer from the input type
+#[twin_impl]
+impl Serialize for GeoPolygonQuery {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+
+        let inner = InnerGeoPolygonQuery {
+            points: self.points.to_owned(),
+        };
+        map.serialize_entry(&self.field, &inner)?;
+
+        if let Some(ref validation_method) = self.validation_method {
+            map.serialize_entry("validation_method", validation_method)?;
+        }
+
+        map.end()
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl JsonSchema for GeoPolygonQuery {
+    #[inline]
+    fn schema_name() -> String {
+        "GeoPolygonQuery".to_string()
+    }
+
+    #[inline]
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let mut schema = crate::schema::object(
+            vec![(
+                "validation_method",
+                gen.subschema_for::<GeoValidationMethod>(),
+            )],
+            &[],
+        );
+        schema.object().additional_properties =
+            Some(Box::new(<InnerGeoPolygonQuery>::json_schema(gen)));
+        schema.into()
+    }
+}
+
+/// Visits a `GeoPolygonQuery` during deserialization.
+struct GeoPolygonQueryVisitor;
+
+impl<'de> serde::Deserialize<'de> for GeoPolygonQuery {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<GeoPolygonQuery, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(GeoPolygonQueryVisitor)
+    }
+}
+
+impl<'de> Visitor<'de> for GeoPolygonQueryVisitor {
+    type Value = GeoPolygonQuery;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a `GeoPolygonQuery`")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut field = None;
+        let mut validation_method = None;
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "validation_method" => validation_method = map.next_value()?,
+                _ if field.is_some() => {
+                    return Err(de::Error::custom(format!(
+                        "expected a single field but found `{}`",
+                        key
+                    )))
+                }
+                _ => field = Some((key, map.next_value::<InnerGeoPolygonQuery>()?)),
+            }
+        }
+
+        let (field, inner) = field.ok_or_else(|| de::Error::missing_field("field"))?;
+        Ok(GeoPolygonQuery {
+            field,
+            points: inner.points,
+            validation_method,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    macro_rules! test_case {
+        ($name:ident : $f:expr, $j:expr) => {
+            mod $name {
+                use super::*;
+
+                #[test]
+                fn can_serialize() {
+                    assert_eq!(serde_json::to_value(&$f).unwrap(), $j);
+                }
+
+                #[test]
+                fn can_deserialize() {
+                    assert_eq!(serde_json::from_value::<GeoPolygonQuery>($j).unwrap(), $f);
+                }
+            }
+        };
+    }
+
+    test_case!(
+        simple:
+        GeoPolygonQuery {
+            validation_method: Some(GeoValidationMethod::Coerce),
+            ..GeoPolygonQuery::new(
+                "person.location",
+                vec![
+                    GeoPoint::new(40.0, -70.0),
+                    GeoPoint::new(30.0, -80.0),
+                    GeoPoint::new(20.0, -90.0),
+                ],
+            )
+        },
+        json!({
+            "person.location": {
+                "points": [
+                    { "lat": 40.0, "lon": -70.0 },
+                    { "lat": 30.0, "lon": -80.0 },
+                    { "lat": 20.0, "lon": -90.0 }
+                ]
+            },
+            "validation_method": "COERCE"
+        })
+    );
+
+    #[test]
+    fn can_deserialize_string_points() {
+        let j = json!({ "person.location": { "points": ["40,-70", "30,-80", "20,-90"] } });
+        assert_eq!(
+            serde_json::from_value::<GeoPolygonQuery>(j).unwrap(),
+            GeoPolygonQuery::new(
+                "person.location",
+                vec![
+                    GeoPoint::new(40.0, -70.0),
+                    GeoPoint::new(30.0, -80.0),
+                    GeoPoint::new(20.0, -90.0),
+                ],
+            )
+        );
+    }
+
+    #[test]
+    fn deserialize_invalid_values_is_err() {
+        let j = r#"{ "validation_method": "STRICT" }"#;
+        assert!(
+            serde_json::from_str::<GeoPolygonQuery>(j).is_err(),
+            "{}",
+            &j
+        );
+
+        let j = r#"{ "a": {} }"#;
+        assert!(
+            serde_json::from_str::<GeoPolygonQuery>(j).is_err(),
+            "{}",
+            &j
+        );
+
+        let j = r#"{ "a": { "points": ["91,0"] } }"#;
+        assert!(
+            serde_json::from_str::<GeoPolygonQuery>(j).is_err(),
+            "{}",
+            &j
+        );
+    }
+}
@@ -0,0 +1,343 @@
+//! [Geo-shape query](https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-geo-shape-query.html)
+
+use std::fmt;
+
+use elastiql_derive::{input_twin, twin_impl};
+#[cfg(feature = "schemars")]
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::de::{self, MapAccess, Visitor};
+use serde::ser::{SerializeMap, Serializer};
+use serde::{Deserialize, Serialize};
+
+use crate::scalars::GeoShape;
+
+#[allow(clippy::missing_docs_in_private_items)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize)]
+#[serde(bound(deserialize = "I: Deserialize<'de>"))]
+struct InnerGeoShapeQuery<I = IndexedShape> {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    shape: Option<GeoShape>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    indexed_shape: Option<I>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    relation: Option<GeoShapeRelation>,
+}
+
+/// A [Geo-shape query] returns documents with a `geo_shape` (or `geo_point`)
+/// with a spatial `relation` to a shape.
+///
+/// **Note**: until GraphQL [Union input types] are supported the shape must
+/// be specified *either* inline as `shape` *or* as an `indexed_shape`.
+///
+/// [Geo-shape query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-geo-shape-query.html
+/// [Union input types]: https://github.com/graphql/graphql-spec/blob/master/rfcs/InputUnion.md
+#[input_twin(name = "GeoShapeFilterInput")]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("GeoShapeFilter"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct GeoShapeQuery {
+    /// The name of the field to query.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::field))]
+    pub field: String,
+
+    /// The shape as [GeoJSON].
+    ///
+    /// [GeoJSON]: https://datatracker.ietf.org/doc/html/rfc7946
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub shape: Option<GeoShape>,
+
+    /// A reference to a shape that is already indexed in another document.
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[input(twin)]
+    pub indexed_shape: Option<IndexedShape>,
+
+    /// The spatial relation of the `field` to the shape. (Defaults to
+    /// `INTERSECTS`.)
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub relation: Option<GeoShapeRelation>,
+
+    /// Whether to ignore an unmapped `field` and not return any documents
+    /// instead of an error. (Defaults to `false`.)
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub ignore_unmapped: Option<bool>,
+}
+
+impl GeoShapeQuery {
+    /// Constructs a new `GeoShapeQuery` for an inline `shape`.
+    #[inline]
+    pub fn new(field: impl Into<String>, shape: GeoShape) -> GeoShapeQuery {
+        GeoShapeQuery {
+            shape: Some(shape),
+            ..GeoShapeQuery::empty(field.into())
+        }
+    }
+
+    /// Constructs a new `GeoShapeQuery` for the shape indexed in the
+    /// document with `id` in `index`.
+    #[inline]
+    pub fn indexed(
+        field: impl Into<String>,
+        index: impl Into<String>,
+        id: impl Into<String>,
+    ) -> GeoShapeQuery {
+        GeoShapeQuery {
+            indexed_shape: Some(IndexedShape::new(index, id)),
+            ..GeoShapeQuery::empty(field.into())
+        }
+    }
+
+    /// Returns a `GeoShapeQuery` on `field` without a shape.
+    #[inline]
+    fn empty(field: String) -> GeoShapeQuery {
+        GeoShapeQuery {
+            field,
+            shape: None,
+            indexed_shape: None,
+            relation: None,
+            ignore_unmapped: None,
+        }
+    }
+}
+
+/// A reference to a shape that is indexed in another document, e.g. to reuse
+/// a list of country borders.
+#[input_twin]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("IndexedShape"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct IndexedShape {
+    /// The index of the document with the shape.
+    pub index: String,
+
+    /// The ID of the document with the shape.
+    pub id: String,
+
+    /// The field of the document with the shape. (Defaults to `shape`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub path: Option<String>,
+
+    /// The routing of the document with the shape.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub routing: Option<String>,
+}
+
+impl IndexedShape {
+    /// Constructs a new `IndexedShape` for the document with `id` in `index`.
+    #[inline]
+    pub fn new(index: impl Into<String>, id: impl Into<String>) -> IndexedShape {
+        IndexedShape {
+            index: index.into(),
+            id: id.into(),
+            path: None,
+            routing: None,
+        }
+    }
+}
+
+/// The spatial relation of the field of a [`GeoShapeQuery`] to its shape.
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("GeoShapeRelation"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum GeoShapeRelation {
+    /// The field intersects the shape.
+    Intersects,
+
+    /// The field has nothing in common with the shape.
+    Disjoint,
+
+    /// The field is within the shape.
+    Within,
+
+    /// The field contains the shape.
+    Contains,
+}
+
+impl GeoShapeRelation {
+    /// Describes the relation, e.g. `is within`.
+    #[inline]
+    pub(crate) fn as_phrase(self) -> &'static str {
+        match self {
+            GeoShapeRelation::Intersects => "intersects",
+            GeoShapeRelation::Disjoint => "is disjoint from",
+            GeoShapeRelation::Within => "is within",
+            GeoShapeRelation::Contains => "contains",
+        }
+    }
+}
+
+// TODO: re-use the serializer from the input type
+#[twin_impl]
+impl Serialize for GeoShapeQuery {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+
+        let inner = InnerGeoShapeQuery {
+            shape: self.shape.to_owned(),
+            indexed_shape: self.indexed_shape.to_owned(),
+            relation: self.relation,
+        };
+        map.serialize_entry(&self.field, &inner)?;
+
+        if let Some(ref ignore_unmapped) = self.ignore_unmapped {
+            map.serialize_entry("ignore_unmapped", ignore_unmapped)?;
+        }
+
+        map.end()
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl JsonSchema for GeoShapeQuery {
+    #[inline]
+    fn schema_name() -> String {
+        "GeoShapeQuery".to_string()
+    }
+
+    #[inline]
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let mut schema =
+            crate::schema::object(vec![("ignore_unmapped", gen.subschema_for::<bool>())], &[]);
+        schema.object().additional_properties =
+            Some(Box::new(<InnerGeoShapeQuery>::json_schema(gen)));
+        schema.into()
+    }
+}
+
+/// Visits a `GeoShapeQuery` during deserialization.
+struct GeoShapeQueryVisitor;
+
+impl<'de> serde::Deserialize<'de> for GeoShapeQuery {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<GeoShapeQuery, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(GeoShapeQueryVisitor)
+    }
+}
+
+impl<'de> Visitor<'de> for GeoShapeQueryVisitor {
+    type Value = GeoShapeQuery;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a `GeoShapeQuery`")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut field = None;
+        let mut ignore_unmapped = None;
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "ignore_unmapped" => ignore_unmapped = map.next_value()?,
+                _ if field.is_some() => {
+                    return Err(de::Error::custom(format!(
+                        "expected a single field but found `{}`",
+                        key
+                    )))
+                }
+                _ => field = Some((key, map.next_value::<InnerGeoShapeQuery>()?)),
+            }
+        }
+
+        let (field, inner) = field.ok_or_else(|| de::Error::missing_field("field"))?;
+        Ok(GeoShapeQuery {
+            field,
+            shape: inner.shape,
+            indexed_shape: inner.indexed_shape,
+            relation: inner.relation,
+            ignore_unmapped,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    macro_rules! test_case {
+        ($name:ident : $f:expr, $j:expr) => {
+            mod $name {
+                use super::*;
+
+                #[test]
+                fn can_serialize() {
+                    assert_eq!(serde_json::to_value(&$f).unwrap(), $j);
+                }
+
+                #[test]
+                fn can_deserialize() {
+                    assert_eq!(serde_json::from_value::<GeoShapeQuery>($j).unwrap(), $f);
+                }
+            }
+        };
+    }
+
+    test_case!(
+        inline_shape:
+        GeoShapeQuery {
+            relation: Some(GeoShapeRelation::Within),
+            ignore_unmapped: Some(true),
+            ..GeoShapeQuery::new(
+                "location",
+                GeoShape::Envelope {
+                    coordinates: [[13.0, 53.0], [14.0, 52.0]],
+                },
+            )
+        },
+        json!({
+            "location": {
+                "shape": { "type": "envelope", "coordinates": [[13.0, 53.0], [14.0, 52.0]] },
+                "relation": "within"
+            },
+            "ignore_unmapped": true
+        })
+    );
+
+    test_case!(
+        indexed_shape:
+        GeoShapeQuery {
+            indexed_shape: Some(IndexedShape {
+                path: Some("location".to_string()),
+                ..IndexedShape::new("shapes", "deu")
+            }),
+            ..GeoShapeQuery::empty("location".to_string())
+        },
+        json!({
+            "location": {
+                "indexed_shape": { "index": "shapes", "id": "deu", "path": "location" }
+            }
+        })
+    );
+
+    #[test]
+    fn deserialize_invalid_values_is_err() {
+        let j = r#"{ "ignore_unmapped": true }"#;
+        assert!(serde_json::from_str::<GeoShapeQuery>(j).is_err(), "{}", &j);
+
+        let j = r#"{ "a": { "relation": "overlaps" } }"#;
+        assert!(serde_json::from_str::<GeoShapeQuery>(j).is_err(), "{}", &j);
+
+        let j = r#"{ "a": { "shape": { "type": "circle", "coordinates": [0, 0] } } }"#;
+        assert!(serde_json::from_str::<GeoShapeQuery>(j).is_err(), "{}", &j);
+    }
+}
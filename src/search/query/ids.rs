@@ -0,0 +1,66 @@
+//! [IDs query](https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-ids-query.html)
+
+use elastiql_derive::input_twin;
+use serde::{Deserialize, Serialize};
+
+/// An [IDs query] returns documents based on their IDs, i.e. their `_id`
+/// field.
+///
+/// [IDs query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-ids-query.html
+#[input_twin(name = "IdsFilterInput")]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("IdsFilter"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct IdsQuery {
+    /// The IDs of the documents to return.
+    pub values: Vec<String>,
+}
+
+impl IdsQuery {
+    /// Constructs a new `IdsQuery`.
+    #[inline]
+    pub fn new<I, T>(values: I) -> IdsQuery
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        IdsQuery {
+            values: values.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl IdsQueryInput {
+    /// Constructs a new `IdsQueryInput`.
+    #[inline]
+    pub fn new<I, T>(values: I) -> IdsQueryInput
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        IdsQueryInput {
+            values: values.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn can_serialize_and_deserialize() {
+        let query = IdsQuery::new(vec!["1", "4", "100"]);
+        let j = json!({ "values": ["1", "4", "100"] });
+
+        assert_eq!(serde_json::to_value(&query).unwrap(), j);
+        assert_eq!(serde_json::from_value::<IdsQuery>(j).unwrap(), query);
+    }
+}
@@ -0,0 +1,254 @@
+//! The kinds of queries a [`Query`] may contain.
+
+use std::fmt;
+
+use super::span::SpanQuery;
+use super::Query;
+#[cfg(feature = "graphql")]
+use super::QueryInput;
+
+/// The kind of a leaf or compound query, e.g. `term` or `bool`.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum QueryKind {
+    Exists,
+    Term,
+    Terms,
+    TermsSet,
+    Ids,
+    Range,
+    Prefix,
+    Wildcard,
+    Regexp,
+    Fuzzy,
+    Match,
+    MatchPhrase,
+    MatchPhrasePrefix,
+    MultiMatch,
+    MoreLikeThis,
+    Percolate,
+    Script,
+    GeoDistance,
+    GeoBoundingBox,
+    GeoShape,
+    GeoPolygon,
+    SpanTerm,
+    SpanNear,
+    SpanFirst,
+    SpanOr,
+    SpanNot,
+    SpanContaining,
+    SpanWithin,
+    SpanMulti,
+    SimpleQueryString,
+    QueryString,
+    Knn,
+    RankFeature,
+    ScriptScore,
+    Nested,
+    Boolean,
+}
+
+impl QueryKind {
+    /// Returns the name Elasticsearch uses for this kind of query, e.g.
+    /// `query_string`.
+    #[inline]
+    pub fn name(self) -> &'static str {
+        match self {
+            QueryKind::Exists => "exists",
+            QueryKind::Term => "term",
+            QueryKind::Terms => "terms",
+            QueryKind::TermsSet => "terms_set",
+            QueryKind::Ids => "ids",
+            QueryKind::Range => "range",
+            QueryKind::Prefix => "prefix",
+            QueryKind::Wildcard => "wildcard",
+            QueryKind::Regexp => "regexp",
+            QueryKind::Fuzzy => "fuzzy",
+            QueryKind::Match => "match",
+            QueryKind::MatchPhrase => "match_phrase",
+            QueryKind::MatchPhrasePrefix => "match_phrase_prefix",
+            QueryKind::MultiMatch => "multi_match",
+            QueryKind::MoreLikeThis => "more_like_this",
+            QueryKind::Percolate => "percolate",
+            QueryKind::Script => "script",
+            QueryKind::GeoDistance => "geo_distance",
+            QueryKind::GeoBoundingBox => "geo_bounding_box",
+            QueryKind::GeoShape => "geo_shape",
+            QueryKind::GeoPolygon => "geo_polygon",
+            QueryKind::SpanTerm => "span_term",
+            QueryKind::SpanNear => "span_near",
+            QueryKind::SpanFirst => "span_first",
+            QueryKind::SpanOr => "span_or",
+            QueryKind::SpanNot => "span_not",
+            QueryKind::SpanContaining => "span_containing",
+            QueryKind::SpanWithin => "span_within",
+            QueryKind::SpanMulti => "span_multi",
+            QueryKind::SimpleQueryString => "simple_query_string",
+            QueryKind::QueryString => "query_string",
+            QueryKind::Knn => "knn",
+            QueryKind::RankFeature => "rank_feature",
+            QueryKind::ScriptScore => "script_score",
+            QueryKind::Nested => "nested",
+            QueryKind::Boolean => "bool",
+        }
+    }
+}
+
+impl fmt::Display for QueryKind {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl Query {
+    /// Returns the kinds of all of the defined fields of this `Query`.
+    ///
+    /// A valid `Query` has exactly one.
+    pub fn kinds(&self) -> Vec<QueryKind> {
+        let fields = [
+            (self.exists.is_some(), QueryKind::Exists),
+            (self.term.is_some(), QueryKind::Term),
+            (self.terms.is_some(), QueryKind::Terms),
+            (self.terms_set.is_some(), QueryKind::TermsSet),
+            (self.ids.is_some(), QueryKind::Ids),
+            (self.range.is_some(), QueryKind::Range),
+            (self.prefix.is_some(), QueryKind::Prefix),
+            (self.wildcard.is_some(), QueryKind::Wildcard),
+            (self.regexp.is_some(), QueryKind::Regexp),
+            (self.fuzzy.is_some(), QueryKind::Fuzzy),
+            (self.match_.is_some(), QueryKind::Match),
+            (self.match_phrase.is_some(), QueryKind::MatchPhrase),
+            (
+                self.match_phrase_prefix.is_some(),
+                QueryKind::MatchPhrasePrefix,
+            ),
+            (self.multi_match.is_some(), QueryKind::MultiMatch),
+            (self.more_like_this.is_some(), QueryKind::MoreLikeThis),
+            (self.percolate.is_some(), QueryKind::Percolate),
+            (self.script.is_some(), QueryKind::Script),
+            (self.geo_distance.is_some(), QueryKind::GeoDistance),
+            (self.geo_bounding_box.is_some(), QueryKind::GeoBoundingBox),
+            (self.geo_shape.is_some(), QueryKind::GeoShape),
+            (self.geo_polygon.is_some(), QueryKind::GeoPolygon),
+            (self.span_term.is_some(), QueryKind::SpanTerm),
+            (self.span_near.is_some(), QueryKind::SpanNear),
+            (self.span_first.is_some(), QueryKind::SpanFirst),
+            (self.span_or.is_some(), QueryKind::SpanOr),
+            (self.span_not.is_some(), QueryKind::SpanNot),
+            (self.span_containing.is_some(), QueryKind::SpanContaining),
+            (self.span_within.is_some(), QueryKind::SpanWithin),
+            (self.span_multi.is_some(), QueryKind::SpanMulti),
+            (
+                self.simple_query_string.is_some(),
+                QueryKind::SimpleQueryString,
+            ),
+            (self.query_string.is_some(), QueryKind::QueryString),
+            (self.knn.is_some(), QueryKind::Knn),
+            (self.rank_feature.is_some(), QueryKind::RankFeature),
+            (self.script_score.is_some(), QueryKind::ScriptScore),
+            (self.nested.is_some(), QueryKind::Nested),
+            (self.boolean.is_some(), QueryKind::Boolean),
+        ];
+
+        fields
+            .iter()
+            .filter(|(is_some, _)| *is_some)
+            .map(|(_, kind)| *kind)
+            .collect()
+    }
+
+    /// Returns the kind of this `Query`, or `None` if it doesn't define
+    /// exactly one field.
+    #[inline]
+    pub fn kind(&self) -> Option<QueryKind> {
+        match *self.kinds() {
+            [kind] => Some(kind),
+            _ => None,
+        }
+    }
+}
+
+impl SpanQuery {
+    /// Returns the kinds of all of the defined fields of this `SpanQuery`.
+    ///
+    /// A valid `SpanQuery` has exactly one.
+    pub fn kinds(&self) -> Vec<QueryKind> {
+        let fields = [
+            (self.span_term.is_some(), QueryKind::SpanTerm),
+            (self.span_near.is_some(), QueryKind::SpanNear),
+            (self.span_first.is_some(), QueryKind::SpanFirst),
+            (self.span_or.is_some(), QueryKind::SpanOr),
+            (self.span_not.is_some(), QueryKind::SpanNot),
+            (self.span_containing.is_some(), QueryKind::SpanContaining),
+            (self.span_within.is_some(), QueryKind::SpanWithin),
+            (self.span_multi.is_some(), QueryKind::SpanMulti),
+        ];
+
+        fields
+            .iter()
+            .filter(|(is_some, _)| *is_some)
+            .map(|(_, kind)| *kind)
+            .collect()
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl QueryInput {
+    /// Returns the kinds of all of the defined fields of this `QueryInput`.
+    ///
+    /// A valid `QueryInput` has exactly one.
+    pub fn kinds(&self) -> Vec<QueryKind> {
+        let fields = [
+            (self.exists.is_some(), QueryKind::Exists),
+            (self.term.is_some(), QueryKind::Term),
+            (self.terms.is_some(), QueryKind::Terms),
+            (self.terms_set.is_some(), QueryKind::TermsSet),
+            (self.ids.is_some(), QueryKind::Ids),
+            (self.range.is_some(), QueryKind::Range),
+            (self.prefix.is_some(), QueryKind::Prefix),
+            (self.wildcard.is_some(), QueryKind::Wildcard),
+            (self.regexp.is_some(), QueryKind::Regexp),
+            (self.fuzzy.is_some(), QueryKind::Fuzzy),
+            (self.match_.is_some(), QueryKind::Match),
+            (self.match_phrase.is_some(), QueryKind::MatchPhrase),
+            (
+                self.match_phrase_prefix.is_some(),
+                QueryKind::MatchPhrasePrefix,
+            ),
+            (self.multi_match.is_some(), QueryKind::MultiMatch),
+            (self.more_like_this.is_some(), QueryKind::MoreLikeThis),
+            (self.percolate.is_some(), QueryKind::Percolate),
+            (self.script.is_some(), QueryKind::Script),
+            (self.geo_distance.is_some(), QueryKind::GeoDistance),
+            (self.geo_bounding_box.is_some(), QueryKind::GeoBoundingBox),
+            (self.geo_shape.is_some(), QueryKind::GeoShape),
+            (self.geo_polygon.is_some(), QueryKind::GeoPolygon),
+            (self.span_term.is_some(), QueryKind::SpanTerm),
+            (self.span_near.is_some(), QueryKind::SpanNear),
+            (self.span_first.is_some(), QueryKind::SpanFirst),
+            (self.span_or.is_some(), QueryKind::SpanOr),
+            (self.span_not.is_some(), QueryKind::SpanNot),
+            (self.span_containing.is_some(), QueryKind::SpanContaining),
+            (self.span_within.is_some(), QueryKind::SpanWithin),
+            (self.span_multi.is_some(), QueryKind::SpanMulti),
+            (
+                self.simple_query_string.is_some(),
+                QueryKind::SimpleQueryString,
+            ),
+            (self.query_string.is_some(), QueryKind::QueryString),
+            (self.knn.is_some(), QueryKind::Knn),
+            (self.rank_feature.is_some(), QueryKind::RankFeature),
+            (self.script_score.is_some(), QueryKind::ScriptScore),
+            (self.nested.is_some(), QueryKind::Nested),
+            (self.boolean.is_some(), QueryKind::Boolean),
+        ];
+
+        fields
+            .iter()
+            .filter(|(is_some, _)| *is_some)
+            .map(|(_, kind)| *kind)
+            .collect()
+    }
+}
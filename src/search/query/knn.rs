@@ -0,0 +1,320 @@
+//! [k-nearest neighbor (kNN) query](https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-knn-query.html)
+
+use std::collections::HashMap;
+
+use elastiql_derive::{input_twin, twin_impl};
+#[cfg(feature = "schemars")]
+use schemars::{
+    gen::SchemaGenerator,
+    schema::{Schema, SchemaObject, SubschemaValidation},
+    JsonSchema,
+};
+use serde::{
+    de,
+    ser::{SerializeMap, Serializer},
+    Deserialize, Serialize,
+};
+
+use crate::compat::CompatVersion;
+
+/// The body of a `KnnQuery` as understood by Elasticsearch.
+#[allow(clippy::missing_docs_in_private_items)]
+#[derive(Deserialize)]
+struct InnerElasticsearchKnnQuery {
+    field: String,
+    query_vector: Vec<f32>,
+    k: u64,
+    #[serde(default)]
+    num_candidates: Option<u64>,
+    #[serde(default)]
+    boost: Option<f64>,
+}
+
+/// The body of a `KnnQuery` as understood by OpenSearch, keyed by the field.
+#[allow(clippy::missing_docs_in_private_items)]
+#[derive(Serialize, Deserialize)]
+struct InnerOpenSearchKnnQuery {
+    vector: Vec<f32>,
+    k: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    boost: Option<f64>,
+}
+
+/// Either dialect of a `KnnQuery`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum InnerKnnQuery {
+    /// The Elasticsearch dialect.
+    Elasticsearch(InnerElasticsearchKnnQuery),
+
+    /// The OpenSearch dialect.
+    OpenSearch(HashMap<String, InnerOpenSearchKnnQuery>),
+}
+
+/// A [k-nearest neighbor (kNN) query] finds the `k` nearest vectors to a query
+/// vector in a `dense_vector` (Elasticsearch) or `knn_vector` (OpenSearch)
+/// field, as measured by a similarity metric.
+///
+/// **Note**: Elasticsearch and OpenSearch use a different syntax for this
+/// query; it is serialized for OpenSearch when serializing for
+/// `CompatVersion::OpenSearch`, otherwise for Elasticsearch `8.x`. Both are
+/// deserialized.
+///
+/// [k-nearest neighbor (kNN) query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-knn-query.html
+#[input_twin(name = "KnnFilterInput")]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("KnnFilter"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct KnnQuery {
+    /// The name of the vector field to search against.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::field))]
+    pub field: String,
+
+    /// The query vector, which must have the same number of dimensions as the
+    /// vector field.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::vector))]
+    pub query_vector: Vec<f32>,
+
+    /// The number of nearest neighbors to return.
+    pub k: u64,
+
+    /// The number of nearest neighbor candidates to consider per shard.
+    ///
+    /// **Note**: This is only supported by Elasticsearch and thus not
+    /// serialized for OpenSearch.
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub num_candidates: Option<u64>,
+
+    /// Floating point number used to decrease or increase the
+    /// [relevance scores] of a query. Defaults to 1.0.
+    ///
+    /// [relevance scores]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-filter-context.html#relevance-scores
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::option_float))]
+    pub boost: Option<f64>,
+}
+
+impl KnnQuery {
+    /// Constructs a new `KnnQuery` for the `k` nearest neighbors of
+    /// `query_vector` in `field`.
+    #[inline]
+    pub fn new(field: impl Into<String>, query_vector: impl Into<Vec<f32>>, k: u64) -> Self {
+        KnnQuery {
+            field: field.into(),
+            query_vector: query_vector.into(),
+            k,
+            num_candidates: None,
+            boost: None,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl KnnQueryInput {
+    /// Constructs a new `KnnQueryInput` for the `k` nearest neighbors of
+    /// `query_vector` in `field`.
+    #[inline]
+    pub fn new(field: impl Into<String>, query_vector: impl Into<Vec<f32>>, k: u64) -> Self {
+        KnnQueryInput {
+            field: field.into(),
+            query_vector: query_vector.into(),
+            k,
+            num_candidates: None,
+            boost: None,
+        }
+    }
+}
+
+#[twin_impl]
+impl Serialize for KnnQuery {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if CompatVersion::current() == CompatVersion::OpenSearch {
+            let mut map = serializer.serialize_map(Some(1))?;
+
+            let inner = InnerOpenSearchKnnQuery {
+                vector: self.query_vector.clone(),
+                k: self.k,
+                boost: self.boost,
+            };
+            map.serialize_entry(&self.field, &inner)?;
+
+            return map.end();
+        }
+
+        let mut map = serializer.serialize_map(None)?;
+
+        map.serialize_entry("field", &self.field)?;
+        map.serialize_entry("query_vector", &self.query_vector)?;
+        map.serialize_entry("k", &self.k)?;
+        if let Some(num_candidates) = self.num_candidates {
+            map.serialize_entry("num_candidates", &num_candidates)?;
+        }
+        if let Some(boost) = self.boost {
+            map.serialize_entry("boost", &boost)?;
+        }
+
+        map.end()
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl JsonSchema for KnnQuery {
+    #[inline]
+    fn schema_name() -> String {
+        "KnnQuery".to_string()
+    }
+
+    #[inline]
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let elasticsearch = crate::schema::object(
+            vec![
+                ("field", gen.subschema_for::<String>()),
+                ("query_vector", gen.subschema_for::<Vec<f32>>()),
+                ("k", gen.subschema_for::<u64>()),
+                ("num_candidates", gen.subschema_for::<u64>()),
+                ("boost", gen.subschema_for::<f64>()),
+            ],
+            &["field", "query_vector", "k"],
+        );
+        let opensearch = crate::schema::object(
+            vec![
+                ("vector", gen.subschema_for::<Vec<f32>>()),
+                ("k", gen.subschema_for::<u64>()),
+                ("boost", gen.subschema_for::<f64>()),
+            ],
+            &["vector", "k"],
+        );
+
+        SchemaObject {
+            subschemas: Some(Box::new(SubschemaValidation {
+                any_of: Some(vec![
+                    elasticsearch.into(),
+                    crate::schema::keyed(opensearch.into()),
+                ]),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+impl<'de> Deserialize<'de> for KnnQuery {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<KnnQuery, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        match InnerKnnQuery::deserialize(deserializer)? {
+            InnerKnnQuery::Elasticsearch(inner) => Ok(KnnQuery {
+                field: inner.field,
+                query_vector: inner.query_vector,
+                k: inner.k,
+                num_candidates: inner.num_candidates,
+                boost: inner.boost,
+            }),
+            InnerKnnQuery::OpenSearch(fields) => {
+                if fields.len() != 1 {
+                    return Err(de::Error::invalid_length(
+                        fields.len(),
+                        &"a single field to query",
+                    ));
+                }
+
+                let (field, inner) = fields.into_iter().next().expect("a single field");
+                Ok(KnnQuery {
+                    field,
+                    query_vector: inner.vector,
+                    k: inner.k,
+                    num_candidates: None,
+                    boost: inner.boost,
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    macro_rules! test_case {
+        ($name:ident : $f:expr, $elasticsearch:expr, $opensearch:expr) => {
+            mod $name {
+                use super::*;
+
+                #[test]
+                fn can_serialize_for_elasticsearch() {
+                    assert_eq!(serde_json::to_value(&$f).unwrap(), $elasticsearch);
+                    assert_eq!(CompatVersion::V8.to_value(&$f).unwrap(), $elasticsearch);
+                }
+
+                #[test]
+                fn can_serialize_for_opensearch() {
+                    assert_eq!(
+                        CompatVersion::OpenSearch.to_value(&$f).unwrap(),
+                        $opensearch
+                    );
+                }
+
+                #[test]
+                fn can_deserialize() {
+                    let mut expected = $f;
+                    let actual: KnnQuery = serde_json::from_value($elasticsearch).unwrap();
+                    assert_eq!(actual, expected);
+
+                    expected.num_candidates = None;
+                    let actual: KnnQuery = serde_json::from_value($opensearch).unwrap();
+                    assert_eq!(actual, expected);
+                }
+            }
+        };
+    }
+
+    test_case!(
+        simple:
+        KnnQuery::new("image_vector", vec![0.5, -1.0, 2.25], 10),
+        json!({ "field": "image_vector", "query_vector": [0.5, -1.0, 2.25], "k": 10 }),
+        json!({ "image_vector": { "vector": [0.5, -1.0, 2.25], "k": 10 } })
+    );
+
+    test_case!(
+        with_num_candidates_and_boost:
+        KnnQuery {
+            field: "image_vector".to_string(),
+            query_vector: vec![1.0, 2.0],
+            k: 3,
+            num_candidates: Some(100),
+            boost: Some(2.5),
+        },
+        json!({
+            "field": "image_vector",
+            "query_vector": [1.0, 2.0],
+            "k": 3,
+            "num_candidates": 100,
+            "boost": 2.5
+        }),
+        json!({ "image_vector": { "vector": [1.0, 2.0], "k": 3, "boost": 2.5 } })
+    );
+
+    #[test]
+    fn deserialize_invalid_values_is_err() {
+        let j = r#"{ "field": "image_vector", "query_vector": [1.0] }"#;
+        assert!(serde_json::from_str::<KnnQuery>(j).is_err(), "{}", &j);
+
+        let j = r#"{ "image_vector": { "vector": "nan", "k": 1 } }"#;
+        assert!(serde_json::from_str::<KnnQuery>(j).is_err(), "{}", &j);
+
+        let j = r#"{ "a": { "vector": [1.0], "k": 1 }, "b": { "vector": [1.0], "k": 1 } }"#;
+        assert!(serde_json::from_str::<KnnQuery>(j).is_err(), "{}", &j);
+
+        let j = r#"{}"#;
+        assert!(serde_json::from_str::<KnnQuery>(j).is_err(), "{}", &j);
+    }
+}
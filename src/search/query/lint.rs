@@ -0,0 +1,449 @@
+//! Warnings about queries that are valid but likely slow or not doing what was
+//! intended, e.g. to surface them to the author of a saved search.
+//!
+//! Unlike [`validate`](super::validate), linting never rejects a query; it
+//! returns every [`Lint`] it finds along with the path to the offending
+//! clause.
+//!
+//! ```
+//! use elastiql::search::query::{CompoundQuery, LintKind, RegexpQuery};
+//!
+//! let mut query = CompoundQuery::default();
+//! query.push(RegexpQuery::new("user", ".*chy", None::<String>));
+//!
+//! let lints = query.lint();
+//! assert_eq!(lints[0].kind, LintKind::LeadingWildcard { pattern: ".*chy".to_string() });
+//! assert_eq!(lints[0].path, "bool.filter[0].regexp");
+//! ```
+
+use std::fmt;
+
+use super::span::SpanClause;
+use super::{BooleanQuery, CompoundQuery, Query};
+
+/// The thresholds [`lint_with`] warns about.
+#[derive(Clone, Debug)]
+pub struct LintOptions {
+    /// The maximum number of values of a `terms` query. (Defaults to `1000`.)
+    pub max_terms: usize,
+
+    /// The maximum number of `should` clauses of a `bool` query. (Defaults to
+    /// `1000`.)
+    pub max_should: usize,
+}
+
+impl Default for LintOptions {
+    #[inline]
+    fn default() -> Self {
+        LintOptions {
+            max_terms: 1_000,
+            max_should: 1_000,
+        }
+    }
+}
+
+/// How much a [`Lint`] matters.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Severity {
+    /// The query may not do what was intended.
+    Info,
+    /// The query is likely to be slow or to use a lot of memory.
+    Warning,
+}
+
+/// What is wrong with the clause a [`Lint`] points at.
+#[derive(Clone, PartialEq, Debug)]
+pub enum LintKind {
+    /// A `regexp`, `wildcard` or `query_string` pattern starts with a
+    /// wildcard, so it has to check every term in the index.
+    LeadingWildcard {
+        /// The pattern starting with a wildcard.
+        pattern: String,
+    },
+    /// A `regexp` pattern doesn't start with a literal prefix, e.g.
+    /// `[a-z]+ing`; regular expressions always match whole terms, so it has
+    /// to check every term in the index too.
+    UnanchoredRegexp {
+        /// The pattern without a literal prefix.
+        pattern: String,
+    },
+    /// A `terms` (or `terms_set`) query has more values than `max_terms`.
+    TooManyTerms {
+        /// The number of values.
+        len: usize,
+    },
+    /// A `match` query is on a field that looks like a `keyword` field, e.g.
+    /// `status.keyword` or `user_id`, where a `term` query is likely meant.
+    MatchOnKeyword {
+        /// The name of the field.
+        field: String,
+    },
+    /// A `bool` query has more `should` clauses than `max_should`.
+    TooManyShould {
+        /// The number of `should` clauses.
+        len: usize,
+    },
+}
+
+impl LintKind {
+    /// How much this matters.
+    #[inline]
+    pub fn severity(&self) -> Severity {
+        match self {
+            LintKind::MatchOnKeyword { .. } => Severity::Info,
+            _ => Severity::Warning,
+        }
+    }
+}
+
+/// A warning about a clause of a [`Query`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct Lint {
+    /// What is wrong with the clause.
+    pub kind: LintKind,
+
+    /// How much it matters.
+    pub severity: Severity,
+
+    /// The path to the clause in the (JSON) query, e.g.
+    /// `bool.must[0].nested.query.term`.
+    pub path: String,
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}`: ", self.path)?;
+        match &self.kind {
+            LintKind::LeadingWildcard { pattern } => {
+                write!(f, "pattern `{}` starts with a wildcard", pattern)
+            }
+            LintKind::UnanchoredRegexp { pattern } => {
+                write!(f, "pattern `{}` has no literal prefix", pattern)
+            }
+            LintKind::TooManyTerms { len } => write!(f, "query has {} terms", len),
+            LintKind::MatchOnKeyword { field } => write!(
+                f,
+                "`match` query on `{}`, which looks like a `keyword` field",
+                field
+            ),
+            LintKind::TooManyShould { len } => write!(f, "query has {} `should` clauses", len),
+        }
+    }
+}
+
+/// Returns the [`Lint`]s of `query` and all of its nested clauses, using the
+/// default [`LintOptions`].
+#[inline]
+pub fn lint(query: &Query) -> Vec<Lint> {
+    lint_with(query, &LintOptions::default())
+}
+
+/// Returns the [`Lint`]s of `query` and all of its nested clauses.
+#[inline]
+pub fn lint_with(query: &Query, options: &LintOptions) -> Vec<Lint> {
+    let mut linter = Linter::new(options);
+    linter.query(query, "");
+    linter.lints
+}
+
+impl Query {
+    /// Returns the [`Lint`]s of this query and all of its nested clauses.
+    ///
+    /// See [`lint`].
+    #[inline]
+    pub fn lint(&self) -> Vec<Lint> {
+        lint(self)
+    }
+}
+
+impl CompoundQuery {
+    /// Returns the [`Lint`]s of this query and all of its nested clauses.
+    ///
+    /// See [`lint`].
+    #[inline]
+    pub fn lint(&self) -> Vec<Lint> {
+        let options = LintOptions::default();
+        let mut linter = Linter::new(&options);
+        linter.compound(self, "");
+        linter.lints
+    }
+}
+
+/// Collects the lints of a single [`lint_with`] call.
+struct Linter<'a> {
+    options: &'a LintOptions,
+    lints: Vec<Lint>,
+}
+
+impl<'a> Linter<'a> {
+    #[inline]
+    fn new(options: &'a LintOptions) -> Self {
+        Linter {
+            options,
+            lints: vec![],
+        }
+    }
+
+    #[inline]
+    fn push(&mut self, kind: LintKind, path: String) {
+        self.lints.push(Lint {
+            severity: kind.severity(),
+            kind,
+            path,
+        });
+    }
+
+    fn query(&mut self, query: &Query, path: &str) {
+        if let Some(ref regexp) = query.regexp {
+            let pattern = &regexp.value;
+            if pattern.starts_with(".*") || pattern.starts_with(".+") {
+                let pattern = pattern.clone();
+                self.push(LintKind::LeadingWildcard { pattern }, join(path, "regexp"));
+            } else if pattern.starts_with(|c| ".([<@".contains(c)) {
+                let pattern = pattern.clone();
+                self.push(LintKind::UnanchoredRegexp { pattern }, join(path, "regexp"));
+            }
+        }
+        if let Some(ref wildcard) = query.wildcard {
+            if wildcard.value.starts_with('*') || wildcard.value.starts_with('?') {
+                let pattern = wildcard.value.clone();
+                self.push(
+                    LintKind::LeadingWildcard { pattern },
+                    join(path, "wildcard"),
+                );
+            }
+        }
+        if let Some(ref query_string) = query.query_string {
+            let is_separator = |c: char| c.is_whitespace() || "()[]{}:\"^~".contains(c);
+            let pattern = query_string
+                .query
+                .split(is_separator)
+                .find(|term| term.starts_with('*') || term.starts_with('?'));
+            if let Some(pattern) = pattern {
+                let pattern = pattern.to_string();
+                self.push(
+                    LintKind::LeadingWildcard { pattern },
+                    join(path, "query_string"),
+                );
+            }
+        }
+        if let Some(ref terms) = query.terms {
+            let len = terms.values.len();
+            if len > self.options.max_terms {
+                self.push(LintKind::TooManyTerms { len }, join(path, "terms"));
+            }
+        }
+        if let Some(ref terms_set) = query.terms_set {
+            let len = terms_set.terms.len();
+            if len > self.options.max_terms {
+                self.push(LintKind::TooManyTerms { len }, join(path, "terms_set"));
+            }
+        }
+        if let Some(ref match_) = query.match_ {
+            if looks_like_keyword(&match_.field) {
+                let field = match_.field.clone();
+                self.push(LintKind::MatchOnKeyword { field }, join(path, "match"));
+            }
+        }
+        self.spans(query.span_clauses(), path);
+        if let Some(ref script_score) = query.script_score {
+            self.compound(&script_score.query, &join(path, "script_score.query"));
+        }
+        if let Some(ref nested) = query.nested {
+            self.compound(&nested.query, &join(path, "nested.query"));
+        }
+        if let Some(ref boolean) = query.boolean {
+            self.boolean(boolean, &join(path, "bool"));
+        }
+    }
+
+    /// Lints the queries nested in a span query at `path`.
+    fn spans(&mut self, clauses: Vec<(String, SpanClause<'_>)>, path: &str) {
+        for (key, clause) in clauses {
+            let path = join(path, &key);
+            match clause {
+                SpanClause::Span(span) => self.spans(span.clauses(), &path),
+                SpanClause::MultiTerm(query) => self.query(query, &path),
+            }
+        }
+    }
+
+    #[inline]
+    fn compound(&mut self, query: &CompoundQuery, path: &str) {
+        // the `root` query is flattened into the compound query
+        if let Some(ref root) = query.root {
+            self.query(root, path);
+        }
+        if let Some(ref boolean) = query.boolean {
+            self.boolean(boolean, &join(path, "bool"));
+        }
+    }
+
+    fn boolean(&mut self, query: &BooleanQuery, path: &str) {
+        let len = query.should.len();
+        if len > self.options.max_should {
+            self.push(LintKind::TooManyShould { len }, path.to_string());
+        }
+
+        let clauses = [
+            ("must", &query.must),
+            ("filter", &query.filter),
+            ("should", &query.should),
+            ("must_not", &query.must_not),
+        ];
+        for (occurrence, clauses) in clauses.iter() {
+            for (i, clause) in clauses.iter().enumerate() {
+                self.query(clause, &format!("{}.{}[{}]", path, occurrence, i));
+            }
+        }
+    }
+}
+
+/// Appends `key` to `path`.
+#[inline]
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+/// Whether `field` looks like the name of a `keyword` field, i.e. a
+/// `.keyword`/`.raw` multi-field or an id.
+#[inline]
+fn looks_like_keyword(field: &str) -> bool {
+    let name = field.rsplit('.').next().unwrap_or(field);
+    field.ends_with(".keyword") || field.ends_with(".raw") || name == "id" || name.ends_with("_id")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::search::query::{MatchQuery, NestedQuery, RegexpQuery, TermsQuery, WildcardQuery};
+
+    #[test]
+    fn finds_nested_lints() {
+        let mut boolean = BooleanQuery::default();
+        boolean.push_must(MatchQuery::new("user_id", "kimchy"));
+        boolean.push_must(MatchQuery::new("message", "kimchy"));
+        boolean.push(NestedQuery::new(
+            "comments",
+            RegexpQuery::new("comments.author", "[a-z]+chy", None::<String>),
+            false,
+        ));
+
+        let lints = lint(&boolean.into_query());
+        assert_eq!(
+            lints,
+            vec![
+                Lint {
+                    kind: LintKind::MatchOnKeyword {
+                        field: "user_id".to_string()
+                    },
+                    severity: Severity::Info,
+                    path: "bool.must[0].match".to_string(),
+                },
+                Lint {
+                    kind: LintKind::UnanchoredRegexp {
+                        pattern: "[a-z]+chy".to_string()
+                    },
+                    severity: Severity::Warning,
+                    path: "bool.filter[0].nested.query.bool.filter[0].regexp".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            lints[1].to_string(),
+            "`bool.filter[0].nested.query.bool.filter[0].regexp`: pattern `[a-z]+chy` has no literal prefix"
+        );
+    }
+
+    #[test]
+    fn can_limit_terms_and_should_clauses() {
+        let options = LintOptions {
+            max_terms: 2,
+            max_should: 1,
+        };
+
+        let mut boolean = BooleanQuery::default();
+        boolean.push_should(TermsQuery::new("tags", vec!["a", "b", "c"]));
+        boolean.push_should(TermsQuery::new("tags", vec!["a", "b"]));
+        let query = boolean.into_query();
+
+        let kinds: Vec<_> = lint_with(&query, &options)
+            .into_iter()
+            .map(|lint| (lint.kind, lint.path))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (LintKind::TooManyShould { len: 2 }, "bool".to_string()),
+                (
+                    LintKind::TooManyTerms { len: 3 },
+                    "bool.should[0].terms".to_string()
+                ),
+            ]
+        );
+        assert!(lint(&query).is_empty());
+    }
+
+    #[test]
+    fn finds_leading_wildcards_in_wildcard_queries() {
+        let query = Query::from(WildcardQuery::new("user", "*chy"));
+        let lints = query.lint();
+        assert_eq!(
+            lints[0].kind,
+            LintKind::LeadingWildcard {
+                pattern: "*chy".to_string()
+            }
+        );
+        assert_eq!(lints[0].path, "wildcard");
+        assert!(Query::from(WildcardQuery::new("user", "ki*y"))
+            .lint()
+            .is_empty());
+    }
+
+    #[test]
+    fn finds_leading_wildcards_in_span_queries() {
+        let query: Query = serde_json::from_str(
+            r#"{
+                "span_near": {
+                    "clauses": [
+                        { "span_term": { "user": { "value": "kimchy" } } },
+                        { "span_multi": { "match": { "regexp": { "user": { "value": ".*chy" } } } } }
+                    ],
+                    "slop": 1
+                }
+            }"#,
+        )
+        .unwrap();
+        let lints = query.lint();
+        assert_eq!(
+            lints[0].kind,
+            LintKind::LeadingWildcard {
+                pattern: ".*chy".to_string()
+            }
+        );
+        assert_eq!(
+            lints[0].path,
+            "span_near.clauses[1].span_multi.match.regexp"
+        );
+    }
+
+    #[test]
+    fn finds_leading_wildcards_in_query_strings() {
+        let query: CompoundQuery =
+            serde_json::from_str(r#"{ "query_string": { "query": "user:(kimchy OR *chy)" } }"#)
+                .unwrap();
+        let lints = query.lint();
+        assert_eq!(
+            lints[0].kind,
+            LintKind::LeadingWildcard {
+                pattern: "*chy".to_string()
+            }
+        );
+        assert_eq!(lints[0].path, "query_string");
+    }
+}
@@ -2,6 +2,9 @@
 
 use std::{collections::HashMap, fmt};
 
+use elastiql_derive::{input_twin, twin_impl};
+#[cfg(feature = "schemars")]
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
 use serde::de::{self, MapAccess, Visitor};
 use serde::ser::{Serialize, SerializeMap, Serializer};
 
@@ -13,80 +16,16 @@ use serde::ser::{Serialize, SerializeMap, Serializer};
 /// including options for fuzzy matching.
 ///
 /// [Match query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-match-query.html#query-dsl-match-query
-#[cfg(feature = "graphql")]
-#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(async_graphql::InputObject, Clone, Debug)]
-#[graphql(name = "MatchFilterInput")]
-#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
-pub struct MatchQueryInput {
-    /// The name of the field to query.
-    pub field: String,
-
-    /// Text, number, boolean value or date you wish to find in the provided
-    /// `field`.
-    ///
-    /// The [match query] analyzes any provided text before performing a search.
-    /// This means the match query can search text fields for analyzed tokens
-    /// rather than an exact term.
-    ///
-    /// [match query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-match-query.html#query-dsl-match-query
-    pub query: String,
-}
-
-#[cfg(feature = "graphql")]
-impl MatchQueryInput {
-    /// Constructs a new `MatchQueryInput`.
-    #[inline]
-    pub fn new(field: impl Into<String>, query: impl Into<String>) -> MatchQueryInput {
-        MatchQueryInput {
-            field: field.into(),
-            query: query.into(),
-        }
-    }
-}
-
-#[cfg(feature = "graphql")]
-impl From<MatchQuery> for MatchQueryInput {
-    #[inline]
-    fn from(query: MatchQuery) -> Self {
-        Self {
-            field: query.field,
-            query: query.query,
-        }
-    }
-}
-
-#[cfg(feature = "graphql")]
-impl Serialize for MatchQueryInput {
-    #[inline]
-    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        let mut map = serializer.serialize_map(Some(1))?;
-
-        let mut values: HashMap<&str, &str> = HashMap::new();
-        values.insert("query", &self.query);
-
-        map.serialize_entry(&self.field, &values)?;
-
-        map.end()
-    }
-}
-
-// TODO: add additional options
-/// A [Match query] returns documents that match a provided text, number, date
-/// or boolean value. The provided text is analyzed before matching.
-///
-/// The [Match query] is the standard query for performing a full-text search,
-/// including options for fuzzy matching.
-///
-/// [Match query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-match-query.html#query-dsl-match-query
-#[cfg_attr(test, derive(PartialEq))]
+#[input_twin(name = "MatchFilterInput")]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("MatchFilter"))]
 #[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
-#[cfg_attr(feature = "graphql", graphql(name = "MatchFilter"))]
 #[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(Clone, Debug)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
 pub struct MatchQuery {
     /// The name of the field to query.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::field))]
     pub field: String,
 
     /// Text, number, boolean value or date you wish to find in the provided
@@ -112,17 +51,19 @@ impl MatchQuery {
 }
 
 #[cfg(feature = "graphql")]
-impl From<MatchQueryInput> for MatchQuery {
+impl MatchQueryInput {
+    /// Constructs a new `MatchQueryInput`.
     #[inline]
-    fn from(input: MatchQueryInput) -> MatchQuery {
-        MatchQuery {
-            field: input.field,
-            query: input.query,
+    pub fn new(field: impl Into<String>, query: impl Into<String>) -> MatchQueryInput {
+        MatchQueryInput {
+            field: field.into(),
+            query: query.into(),
         }
     }
 }
 
 // TODO: re-use the serializer from the input type
+#[twin_impl]
 impl Serialize for MatchQuery {
     #[inline]
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
@@ -137,6 +78,21 @@ impl Serialize for MatchQuery {
     }
 }
 
+#[cfg(feature = "schemars")]
+impl JsonSchema for MatchQuery {
+    #[inline]
+    fn schema_name() -> String {
+        "MatchQuery".to_string()
+    }
+
+    #[inline]
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let query =
+            crate::schema::object(vec![("query", gen.subschema_for::<String>())], &["query"]);
+        crate::schema::keyed(query.into())
+    }
+}
+
 /// Visits a `MatchQuery` during deserialization.
 struct MatchQueryVisitor;
 
@@ -0,0 +1,300 @@
+//! [Match phrase query](https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-match-query-phrase.html)
+
+use std::fmt;
+
+use elastiql_derive::{input_twin, twin_impl};
+#[cfg(feature = "schemars")]
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::de::{self, MapAccess, Visitor};
+use serde::ser::{SerializeMap, Serializer};
+use serde::{Deserialize, Serialize};
+
+#[allow(clippy::missing_docs_in_private_items)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize)]
+struct InnerMatchPhraseQuery {
+    query: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    analyzer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    slop: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    zero_terms_query: Option<ZeroTermsQuery>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    boost: Option<f64>,
+}
+
+/// The body of a `MatchPhraseQuery`, which Elasticsearch also accepts as just
+/// the phrase itself, e.g. `{ "message": "this is a test" }`.
+#[allow(clippy::missing_docs_in_private_items)]
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum MatchPhraseQueryBody {
+    Short(String),
+    Long(InnerMatchPhraseQuery),
+}
+
+/// A [Match phrase query] analyzes the provided text and returns documents
+/// that contain its terms as a phrase, i.e. in the same order and (up to
+/// `slop`) next to each other.
+///
+/// [Match phrase query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-match-query-phrase.html
+#[input_twin(name = "MatchPhraseFilterInput")]
+#[cfg_attr(
+    feature = "graphql",
+    elastiql_derive::graphql_name("MatchPhraseFilter")
+)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct MatchPhraseQuery {
+    /// The name of the field to query.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::field))]
+    pub field: String,
+
+    /// The phrase you wish to find in the provided `field`, which is analyzed
+    /// before performing the search.
+    pub query: String,
+
+    /// The [analyzer] used to convert the `query` into tokens. (Defaults to
+    /// the search analyzer of the `field`.)
+    ///
+    /// [analyzer]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis.html
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub analyzer: Option<String>,
+
+    /// The maximum number of positions the terms of the phrase may be moved
+    /// to match, e.g. `1` to also match `quick fox` for `fox quick`.
+    /// (Defaults to `0`.)
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub slop: Option<u32>,
+
+    /// Whether to match no or all documents if the analyzer removes all of the
+    /// tokens of the `query`, e.g. because they are all stop words. (Defaults
+    /// to `NONE`.)
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub zero_terms_query: Option<ZeroTermsQuery>,
+
+    /// Floating point number used to decrease or increase the relevance scores
+    /// of the query. (Defaults to `1.0`.)
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::option_float))]
+    pub boost: Option<f64>,
+}
+
+impl MatchPhraseQuery {
+    /// Constructs a new `MatchPhraseQuery`.
+    #[inline]
+    pub fn new(field: impl Into<String>, query: impl Into<String>) -> MatchPhraseQuery {
+        MatchPhraseQuery {
+            field: field.into(),
+            query: query.into(),
+            analyzer: None,
+            slop: None,
+            zero_terms_query: None,
+            boost: None,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl MatchPhraseQueryInput {
+    /// Constructs a new `MatchPhraseQueryInput`.
+    #[inline]
+    pub fn new(field: impl Into<String>, query: impl Into<String>) -> MatchPhraseQueryInput {
+        MatchPhraseQueryInput {
+            field: field.into(),
+            query: query.into(),
+            analyzer: None,
+            slop: None,
+            zero_terms_query: None,
+            boost: None,
+        }
+    }
+}
+
+/// Which documents a [`MatchPhraseQuery`] returns if the analyzer removes all
+/// of the tokens of its `query`.
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("ZeroTermsQuery"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ZeroTermsQuery {
+    /// No documents are returned.
+    None,
+
+    /// All documents are returned, like a `match_all` query.
+    All,
+}
+
+// TODO: re-use the serializer from the input type
+#[twin_impl]
+impl Serialize for MatchPhraseQuery {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(1))?;
+
+        let inner = InnerMatchPhraseQuery {
+            query: self.query.to_owned(),
+            analyzer: self.analyzer.to_owned(),
+            slop: self.slop,
+            zero_terms_query: self.zero_terms_query,
+            boost: self.boost,
+        };
+
+        map.serialize_entry(&self.field, &inner)?;
+
+        map.end()
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl JsonSchema for MatchPhraseQuery {
+    #[inline]
+    fn schema_name() -> String {
+        "MatchPhraseQuery".to_string()
+    }
+
+    #[inline]
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        crate::schema::keyed(InnerMatchPhraseQuery::json_schema(gen))
+    }
+}
+
+/// Visits a `MatchPhraseQuery` during deserialization.
+struct MatchPhraseQueryVisitor;
+
+impl<'de> serde::Deserialize<'de> for MatchPhraseQuery {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<MatchPhraseQuery, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(MatchPhraseQueryVisitor)
+    }
+}
+
+impl<'de> Visitor<'de> for MatchPhraseQueryVisitor {
+    type Value = MatchPhraseQuery;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a `MatchPhraseQuery`")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let field = map
+            .next_key::<String>()?
+            .ok_or_else(|| de::Error::missing_field("field"))?;
+
+        Ok(match map.next_value()? {
+            MatchPhraseQueryBody::Short(query) => MatchPhraseQuery::new(field, query),
+            MatchPhraseQueryBody::Long(inner) => MatchPhraseQuery {
+                field,
+                query: inner.query,
+                analyzer: inner.analyzer,
+                slop: inner.slop,
+                zero_terms_query: inner.zero_terms_query,
+                boost: inner.boost,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    macro_rules! test_case {
+        ($name:ident : $f:expr, $j:expr) => {
+            mod $name {
+                use super::*;
+
+                #[test]
+                fn can_serialize() {
+                    assert_eq!(serde_json::to_value(&$f).unwrap(), $j);
+                }
+
+                #[test]
+                fn can_deserialize() {
+                    assert_eq!(serde_json::from_value::<MatchPhraseQuery>($j).unwrap(), $f);
+                }
+            }
+        };
+    }
+
+    test_case!(
+        simple:
+        MatchPhraseQuery::new("message", "this is a test"),
+        json!({ "message": { "query": "this is a test" } })
+    );
+
+    test_case!(
+        with_options:
+        MatchPhraseQuery {
+            field: "message".to_string(),
+            query: "quick fox".to_string(),
+            analyzer: Some("my_analyzer".to_string()),
+            slop: Some(1),
+            zero_terms_query: Some(ZeroTermsQuery::All),
+            boost: Some(2.0),
+        },
+        json!({
+            "message": {
+                "query": "quick fox",
+                "analyzer": "my_analyzer",
+                "slop": 1,
+                "zero_terms_query": "all",
+                "boost": 2.0
+            }
+        })
+    );
+
+    #[test]
+    fn can_deserialize_short_form() {
+        let j = json!({ "message": "this is a test" });
+        assert_eq!(
+            serde_json::from_value::<MatchPhraseQuery>(j).unwrap(),
+            MatchPhraseQuery::new("message", "this is a test")
+        );
+    }
+
+    #[test]
+    fn deserialize_invalid_values_is_err() {
+        let j = r#"{ "message": null }"#;
+        assert!(
+            serde_json::from_str::<MatchPhraseQuery>(j).is_err(),
+            "{}",
+            &j
+        );
+
+        let j = r#"{ "message": { "query": 1 } }"#;
+        assert!(
+            serde_json::from_str::<MatchPhraseQuery>(j).is_err(),
+            "{}",
+            &j
+        );
+
+        let j = r#"{ "message": { "query": "fox", "slop": -1 } }"#;
+        assert!(
+            serde_json::from_str::<MatchPhraseQuery>(j).is_err(),
+            "{}",
+            &j
+        );
+
+        let j = r#"{ "message": { "query": "fox", "zero_terms_query": "some" } }"#;
+        assert!(
+            serde_json::from_str::<MatchPhraseQuery>(j).is_err(),
+            "{}",
+            &j
+        );
+    }
+}
@@ -0,0 +1,257 @@
+//! [Match phrase prefix query](https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-match-query-phrase-prefix.html)
+
+use std::fmt;
+
+use elastiql_derive::{input_twin, twin_impl};
+#[cfg(feature = "schemars")]
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::de::{self, MapAccess, Visitor};
+use serde::ser::{SerializeMap, Serializer};
+use serde::{Deserialize, Serialize};
+
+#[allow(clippy::missing_docs_in_private_items)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize)]
+struct InnerMatchPhrasePrefixQuery {
+    query: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    analyzer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_expansions: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    slop: Option<u32>,
+}
+
+/// The body of a `MatchPhrasePrefixQuery`, which Elasticsearch also accepts as
+/// just the text itself, e.g. `{ "message": "quick brown f" }`.
+#[allow(clippy::missing_docs_in_private_items)]
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum MatchPhrasePrefixQueryBody {
+    Short(String),
+    Long(InnerMatchPhrasePrefixQuery),
+}
+
+/// A [Match phrase prefix query] returns documents that contain the terms of
+/// the provided text in the same order, treating the last term as a prefix,
+/// e.g. for autocompletion.
+///
+/// [Match phrase prefix query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-match-query-phrase-prefix.html
+#[input_twin(name = "MatchPhrasePrefixFilterInput")]
+#[cfg_attr(
+    feature = "graphql",
+    elastiql_derive::graphql_name("MatchPhrasePrefixFilter")
+)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct MatchPhrasePrefixQuery {
+    /// The name of the field to query.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::field))]
+    pub field: String,
+
+    /// The text you wish to find in the provided `field`, whose last term is
+    /// used as a prefix.
+    pub query: String,
+
+    /// The [analyzer] used to convert the `query` into tokens. (Defaults to
+    /// the search analyzer of the `field`.)
+    ///
+    /// [analyzer]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis.html
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub analyzer: Option<String>,
+
+    /// The maximum number of terms the last term of the `query` is expanded
+    /// to. (Defaults to `50`.)
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub max_expansions: Option<u32>,
+
+    /// The maximum number of positions the terms of the phrase may be moved
+    /// to match. (Defaults to `0`.)
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub slop: Option<u32>,
+}
+
+impl MatchPhrasePrefixQuery {
+    /// Constructs a new `MatchPhrasePrefixQuery`.
+    #[inline]
+    pub fn new(field: impl Into<String>, query: impl Into<String>) -> MatchPhrasePrefixQuery {
+        MatchPhrasePrefixQuery {
+            field: field.into(),
+            query: query.into(),
+            analyzer: None,
+            max_expansions: None,
+            slop: None,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl MatchPhrasePrefixQueryInput {
+    /// Constructs a new `MatchPhrasePrefixQueryInput`.
+    #[inline]
+    pub fn new(field: impl Into<String>, query: impl Into<String>) -> MatchPhrasePrefixQueryInput {
+        MatchPhrasePrefixQueryInput {
+            field: field.into(),
+            query: query.into(),
+            analyzer: None,
+            max_expansions: None,
+            slop: None,
+        }
+    }
+}
+
+// TODO: re-use the serializer from the input type
+#[twin_impl]
+impl Serialize for MatchPhrasePrefixQuery {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(1))?;
+
+        let inner = InnerMatchPhrasePrefixQuery {
+            query: self.query.to_owned(),
+            analyzer: self.analyzer.to_owned(),
+            max_expansions: self.max_expansions,
+            slop: self.slop,
+        };
+
+        map.serialize_entry(&self.field, &inner)?;
+
+        map.end()
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl JsonSchema for MatchPhrasePrefixQuery {
+    #[inline]
+    fn schema_name() -> String {
+        "MatchPhrasePrefixQuery".to_string()
+    }
+
+    #[inline]
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        crate::schema::keyed(InnerMatchPhrasePrefixQuery::json_schema(gen))
+    }
+}
+
+/// Visits a `MatchPhrasePrefixQuery` during deserialization.
+struct MatchPhrasePrefixQueryVisitor;
+
+impl<'de> serde::Deserialize<'de> for MatchPhrasePrefixQuery {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<MatchPhrasePrefixQuery, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(MatchPhrasePrefixQueryVisitor)
+    }
+}
+
+impl<'de> Visitor<'de> for MatchPhrasePrefixQueryVisitor {
+    type Value = MatchPhrasePrefixQuery;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a `MatchPhrasePrefixQuery`")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let field = map
+            .next_key::<String>()?
+            .ok_or_else(|| de::Error::missing_field("field"))?;
+
+        Ok(match map.next_value()? {
+            MatchPhrasePrefixQueryBody::Short(query) => MatchPhrasePrefixQuery::new(field, query),
+            MatchPhrasePrefixQueryBody::Long(inner) => MatchPhrasePrefixQuery {
+                field,
+                query: inner.query,
+                analyzer: inner.analyzer,
+                max_expansions: inner.max_expansions,
+                slop: inner.slop,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    macro_rules! test_case {
+        ($name:ident : $f:expr, $j:expr) => {
+            mod $name {
+                use super::*;
+
+                #[test]
+                fn can_serialize() {
+                    assert_eq!(serde_json::to_value(&$f).unwrap(), $j);
+                }
+
+                #[test]
+                fn can_deserialize() {
+                    assert_eq!(
+                        serde_json::from_value::<MatchPhrasePrefixQuery>($j).unwrap(),
+                        $f
+                    );
+                }
+            }
+        };
+    }
+
+    test_case!(
+        simple:
+        MatchPhrasePrefixQuery::new("message", "quick brown f"),
+        json!({ "message": { "query": "quick brown f" } })
+    );
+
+    test_case!(
+        with_options:
+        MatchPhrasePrefixQuery {
+            field: "message".to_string(),
+            query: "quick brown f".to_string(),
+            analyzer: Some("my_analyzer".to_string()),
+            max_expansions: Some(10),
+            slop: Some(1),
+        },
+        json!({
+            "message": {
+                "query": "quick brown f",
+                "analyzer": "my_analyzer",
+                "max_expansions": 10,
+                "slop": 1
+            }
+        })
+    );
+
+    #[test]
+    fn can_deserialize_short_form() {
+        let j = json!({ "message": "quick brown f" });
+        assert_eq!(
+            serde_json::from_value::<MatchPhrasePrefixQuery>(j).unwrap(),
+            MatchPhrasePrefixQuery::new("message", "quick brown f")
+        );
+    }
+
+    #[test]
+    fn deserialize_invalid_values_is_err() {
+        let j = r#"{ "message": null }"#;
+        assert!(
+            serde_json::from_str::<MatchPhrasePrefixQuery>(j).is_err(),
+            "{}",
+            &j
+        );
+
+        let j = r#"{ "message": { "query": "quick", "max_expansions": -1 } }"#;
+        assert!(
+            serde_json::from_str::<MatchPhrasePrefixQuery>(j).is_err(),
+            "{}",
+            &j
+        );
+    }
+}
@@ -4,23 +4,72 @@
 
 use std::default::Default;
 
-use serde::{Deserialize, Serialize};
+use elastiql_derive::input_twin;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use self::span::{
+    SpanContainingQuery, SpanFirstQuery, SpanMultiQuery, SpanNearQuery, SpanNotQuery, SpanOrQuery,
+    SpanTermQuery, SpanWithinQuery,
+};
+#[cfg(feature = "graphql")]
+use self::span::{
+    SpanContainingQueryInput, SpanFirstQueryInput, SpanMultiQueryInput, SpanNearQueryInput,
+    SpanNotQueryInput, SpanOrQueryInput, SpanTermQueryInput, SpanWithinQueryInput,
+};
 
 pub use self::{
-    exists::*, match_::*, nested::*, prefix::*, query_string::*, range::*, regexp::*,
-    simple_query_string::*, term::*, terms::*,
+    boosted_field::*, borrowed::*, diff::*, exists::*, field::*, fuzzy::*, geo_bounding_box::*,
+    geo_distance::*, geo_polygon::*, geo_shape::*, ids::*, kind::*, knn::*, lint::*, match_::*,
+    match_phrase::*, match_phrase_prefix::*, more_like_this::*, multi_match::*, nested::*,
+    occurrence::*, parse::*, percolate::*, prefix::*, query_string::*, range::*, rank_feature::*,
+    regexp::*, script::*, script_score::*, simple_query_string::*, template::*, term::*, terms::*,
+    terms_set::*, transform::*, validate::*, visit::*, wildcard::*,
 };
 
+pub mod ast;
+pub mod span;
+
+mod boosted_field;
+mod borrowed;
+mod describe;
+mod diff;
 mod exists;
+mod field;
+mod fuzzy;
+mod geo_bounding_box;
+mod geo_distance;
+mod geo_polygon;
+mod geo_shape;
+mod ids;
+mod kind;
+mod knn;
+mod lint;
 mod match_;
+mod match_phrase;
+mod match_phrase_prefix;
+mod more_like_this;
+mod multi_match;
 mod nested;
+mod occurrence;
+mod parse;
+mod percolate;
 mod prefix;
 mod query_string;
 mod range;
+mod rank_feature;
 mod regexp;
+mod script;
+mod script_score;
 mod simple_query_string;
+mod simplify;
+mod template;
 mod term;
 mod terms;
+mod terms_set;
+mod transform;
+mod validate;
+mod visit;
+mod wildcard;
 
 // TODO: make this file smaller!
 
@@ -29,12 +78,14 @@ mod terms;
 /// to filter context.
 ///
 /// [Compound queries]: https://www.elastic.co/guide/en/elasticsearch/reference/current/compound-queries.html
-#[cfg(feature = "graphql")]
+#[input_twin(name = "CompoundFilterInput")]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("CompoundFilter"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 #[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(async_graphql::InputObject, Serialize, Default, Clone, Debug)]
-#[graphql(name = "CompoundFilterInput")]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Default, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
-pub struct CompoundQueryInput {
+pub struct CompoundQuery {
     /// The default query for combining multiple leaf or compound query clauses,
     /// as must, should, must_not, or filter clauses. The must and should
     /// clauses have their scores combined — the more matching clauses, the
@@ -42,129 +93,201 @@ pub struct CompoundQueryInput {
     /// context.
     #[cfg_attr(feature = "builder", builder(default))]
     #[serde(default, rename = "bool", skip_serializing_if = "Option::is_none")]
-    pub boolean: Option<BooleanQueryInput>,
+    #[input(twin)]
+    pub boolean: Option<BooleanQuery>,
+
+    /// Any other query at the root of the request instead of a `bool` query,
+    /// e.g. a `term` query.
+    ///
+    /// **Note**: this should not be defined along with `boolean`; pushing a
+    /// clause onto a `CompoundQuery` moves its `root` into the `must` clauses
+    /// of its `bool` query first.
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    #[input(twin)]
+    pub root: Option<Box<Query>>,
 }
 
-#[cfg(feature = "graphql")]
-impl CompoundQueryInput {
-    /// Returns `true` if this `CompoundQueryInput` is empty.
+impl CompoundQuery {
+    /// Returns a `CompoundQuery` with `query` at its root, i.e. its `bool`
+    /// query if it is one, otherwise its `root`.
+    #[inline]
+    pub fn from_query(query: impl Into<Query>) -> CompoundQuery {
+        let mut query = query.into();
+        let boolean = query.boolean.take();
+        if query.is_empty() {
+            CompoundQuery {
+                boolean: boolean.map(|boolean| *boolean),
+                root: None,
+            }
+        } else {
+            query.boolean = boolean;
+            CompoundQuery {
+                boolean: None,
+                root: Some(Box::new(query)),
+            }
+        }
+    }
+
+    /// Returns `true` if this `CompoundQuery` is empty.
     #[inline]
     pub fn is_empty(&self) -> bool {
-        self.boolean
+        self.root
             .as_ref()
-            .map_or_else(|| true, |filter| filter.is_empty())
+            .map_or_else(|| true, |query| query.is_empty())
+            && self
+                .boolean
+                .as_ref()
+                .map_or_else(|| true, |filter| filter.is_empty())
     }
 
     /// Appends a `filter` on to the current list of filters.
     #[inline]
-    pub fn push(&mut self, filter: impl Into<QueryInput>) {
-        if let Some(ref mut boolean) = self.boolean {
-            boolean.push(filter)
-        } else {
-            self.boolean = Some(BooleanQueryInput {
-                must: vec![],
-                filter: vec![filter.into()],
-                should: vec![],
-                must_not: vec![],
-                minimum_should_match: None,
-                boost: None,
-            })
-        }
+    pub fn push(&mut self, filter: impl Into<Query>) {
+        self.boolean_mut().push(filter)
     }
-}
 
-#[cfg(feature = "graphql")]
-impl From<Option<CompoundQueryInput>> for CompoundQueryInput {
+    /// Appends a query that **must** match and contributes to the score.
     #[inline]
-    fn from(filter: Option<CompoundQueryInput>) -> CompoundQueryInput {
-        filter.unwrap_or_default()
+    pub fn push_must(&mut self, query: impl Into<Query>) {
+        self.boolean_mut().push_must(query)
     }
-}
 
-#[cfg(feature = "graphql")]
-impl<T: Into<BooleanQueryInput>> From<T> for CompoundQueryInput {
+    /// Appends a query that **should** match.
+    ///
+    /// See [`BooleanQuery::push_should`].
     #[inline]
-    fn from(filter: T) -> CompoundQueryInput {
-        CompoundQueryInput {
-            boolean: Some(filter.into()),
+    pub fn push_should(&mut self, query: impl Into<Query>) {
+        self.boolean_mut().push_should(query)
+    }
+
+    /// Appends a query that **must not** match.
+    #[inline]
+    pub fn push_must_not(&mut self, query: impl Into<Query>) {
+        self.boolean_mut().push_must_not(query)
+    }
+
+    /// Returns a query that matches the documents this query doesn't match.
+    #[inline]
+    pub fn negate(mut self) -> CompoundQuery {
+        let boolean = std::mem::take(self.boolean_mut());
+        CompoundQuery {
+            boolean: Some(boolean.negate()),
+            root: None,
         }
     }
-}
 
-#[cfg(feature = "graphql")]
-impl From<CompoundQuery> for CompoundQueryInput {
+    /// Returns the `bool` query, inserting an empty one if there is none and
+    /// moving the `root` query (if any) into its `must` clauses.
     #[inline]
-    fn from(query: CompoundQuery) -> Self {
-        Self {
-            boolean: query.boolean.map(Into::into),
+    fn boolean_mut(&mut self) -> &mut BooleanQuery {
+        let root = self.root.take();
+        let boolean = self.boolean.get_or_insert_with(Default::default);
+        if let Some(root) = root {
+            boolean.must.push(*root);
         }
+        boolean
     }
 }
 
-/// [Compound queries] wrap other compound or leaf queries, either to combine
-/// their results and scores, to change their behavior, or to switch from query
-/// to filter context.
-///
-/// [Compound queries]: https://www.elastic.co/guide/en/elasticsearch/reference/current/compound-queries.html
-#[cfg_attr(test, derive(PartialEq))]
-#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
-#[cfg_attr(feature = "graphql", graphql(name = "CompoundFilter"))]
-#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(Serialize, Deserialize, Default, Clone, Debug)]
-#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
-pub struct CompoundQuery {
-    /// The default query for combining multiple leaf or compound query clauses,
-    /// as must, should, must_not, or filter clauses. The must and should
-    /// clauses have their scores combined — the more matching clauses, the
-    /// better — while the must_not and filter clauses are executed in filter
-    /// context.
-    #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(default, rename = "bool", skip_serializing_if = "Option::is_none")]
-    pub boolean: Option<BooleanQuery>,
+impl<'de> Deserialize<'de> for CompoundQuery {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<CompoundQuery, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(CompoundQuery::from_query(Query::deserialize(deserializer)?))
+    }
 }
 
-impl CompoundQuery {
-    /// Returns `true` if this `CompoundQuery` is empty.
+#[cfg(feature = "graphql")]
+impl CompoundQueryInput {
+    /// Returns `true` if this `CompoundQueryInput` is empty.
     #[inline]
     pub fn is_empty(&self) -> bool {
-        self.boolean
+        self.root
             .as_ref()
-            .map_or_else(|| true, |filter| filter.is_empty())
+            .map_or_else(|| true, |query| query.kinds().is_empty())
+            && self
+                .boolean
+                .as_ref()
+                .map_or_else(|| true, |filter| filter.is_empty())
     }
 
     /// Appends a `filter` on to the current list of filters.
     #[inline]
-    pub fn push(&mut self, filter: impl Into<Query>) {
-        if let Some(ref mut boolean) = self.boolean {
-            boolean.push(filter)
-        } else {
-            self.boolean = Some(BooleanQuery {
-                must: vec![],
-                filter: vec![filter.into()],
-                should: vec![],
-                must_not: vec![],
-                minimum_should_match: None,
-                boost: None,
-            })
+    pub fn push(&mut self, filter: impl Into<QueryInput>) {
+        self.boolean_mut().push(filter)
+    }
+
+    /// Appends a query that **must** match and contributes to the score.
+    #[inline]
+    pub fn push_must(&mut self, query: impl Into<QueryInput>) {
+        self.boolean_mut().push_must(query)
+    }
+
+    /// Appends a query that **should** match.
+    ///
+    /// See [`BooleanQueryInput::push_should`].
+    #[inline]
+    pub fn push_should(&mut self, query: impl Into<QueryInput>) {
+        self.boolean_mut().push_should(query)
+    }
+
+    /// Appends a query that **must not** match.
+    #[inline]
+    pub fn push_must_not(&mut self, query: impl Into<QueryInput>) {
+        self.boolean_mut().push_must_not(query)
+    }
+
+    /// Returns a query that matches the documents this query doesn't match.
+    #[inline]
+    pub fn negate(mut self) -> CompoundQueryInput {
+        let boolean = std::mem::take(self.boolean_mut());
+        CompoundQueryInput {
+            boolean: Some(boolean.negate()),
+            root: None,
+        }
+    }
+
+    /// Returns the `bool` query, inserting an empty one if there is none and
+    /// moving the `root` query (if any) into its `must` clauses.
+    #[inline]
+    fn boolean_mut(&mut self) -> &mut BooleanQueryInput {
+        let root = self.root.take();
+        let boolean = self.boolean.get_or_insert_with(Default::default);
+        if let Some(root) = root {
+            boolean.must.push(*root);
         }
+        boolean
     }
 }
 
-impl<T: Into<BooleanQuery>> From<T> for CompoundQuery {
+#[cfg(feature = "graphql")]
+impl From<Option<CompoundQueryInput>> for CompoundQueryInput {
     #[inline]
-    fn from(filter: T) -> CompoundQuery {
-        CompoundQuery {
+    fn from(filter: Option<CompoundQueryInput>) -> CompoundQueryInput {
+        filter.unwrap_or_default()
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl<T: Into<BooleanQueryInput>> From<T> for CompoundQueryInput {
+    #[inline]
+    fn from(filter: T) -> CompoundQueryInput {
+        CompoundQueryInput {
             boolean: Some(filter.into()),
+            root: None,
         }
     }
 }
 
-#[cfg(feature = "graphql")]
-impl From<CompoundQueryInput> for CompoundQuery {
+impl<T: Into<BooleanQuery>> From<T> for CompoundQuery {
     #[inline]
-    fn from(input: CompoundQueryInput) -> CompoundQuery {
+    fn from(filter: T) -> CompoundQuery {
         CompoundQuery {
-            boolean: Some(input.boolean.unwrap_or_default().into()),
+            boolean: Some(filter.into()),
+            root: None,
         }
     }
 }
@@ -175,9 +298,10 @@ impl From<CompoundQueryInput> for CompoundQuery {
 ///
 /// [query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-bool-query.html
 #[cfg(feature = "graphql")]
+#[elastiql_derive::graphql_name("BooleanFilterInput")]
 #[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(async_graphql::InputObject, Serialize, Default, Clone, Debug)]
-#[graphql(name = "BooleanFilterInput")]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(async_graphql::InputObject, Serialize, Default, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
 pub struct BooleanQueryInput {
     /// The clause (query) must appear in matching documents and will
@@ -264,6 +388,42 @@ impl BooleanQueryInput {
         // TODO: should we always default to `filter` context?
         self.filter.push(filter.into())
     }
+
+    /// Appends a query to the `must` clauses, which **must** match and
+    /// contribute to the score.
+    #[inline]
+    pub fn push_must(&mut self, query: impl Into<QueryInput>) {
+        self.must.push(query.into())
+    }
+
+    /// Appends a query to the `should` clauses, which **should** match.
+    ///
+    /// If there are no `must` or `filter` clauses (and no
+    /// `minimum_should_match`), at least one `should` clause must match;
+    /// otherwise they only contribute to the score.
+    #[inline]
+    pub fn push_should(&mut self, query: impl Into<QueryInput>) {
+        self.should.push(query.into())
+    }
+
+    /// Appends a query to the `must_not` clauses, which **must not** match.
+    #[inline]
+    pub fn push_must_not(&mut self, query: impl Into<QueryInput>) {
+        self.must_not.push(query.into())
+    }
+
+    /// Returns a `bool` query that matches the documents this query doesn't
+    /// match, i.e. with this query as its only `must_not` clause.
+    #[inline]
+    pub fn negate(self) -> BooleanQueryInput {
+        BooleanQueryInput {
+            must_not: vec![QueryInput {
+                boolean: Some(Box::new(self)),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
 }
 
 #[cfg(feature = "graphql")]
@@ -301,11 +461,11 @@ impl From<BooleanQuery> for BooleanQueryInput {
 /// typed occurrence.
 ///
 /// [query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-bool-query.html
-#[cfg_attr(test, derive(PartialEq))]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("BooleanFilter"))]
 #[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
-#[cfg_attr(feature = "graphql", graphql(name = "BooleanFilter"))]
 #[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
 pub struct BooleanQuery {
     /// The clause (query) **must** appear in matching documents and *will
@@ -387,6 +547,48 @@ impl BooleanQuery {
         // TODO: should we always default to `filter` context?
         self.filter.push(filter.into())
     }
+
+    /// Appends a query to the `must` clauses, which **must** match and
+    /// contribute to the score.
+    #[inline]
+    pub fn push_must(&mut self, query: impl Into<Query>) {
+        self.must.push(query.into())
+    }
+
+    /// Appends a query to the `should` clauses, which **should** match.
+    ///
+    /// If there are no `must` or `filter` clauses (and no
+    /// `minimum_should_match`), at least one `should` clause must match;
+    /// otherwise they only contribute to the score.
+    #[inline]
+    pub fn push_should(&mut self, query: impl Into<Query>) {
+        self.should.push(query.into())
+    }
+
+    /// Appends a query to the `must_not` clauses, which **must not** match.
+    #[inline]
+    pub fn push_must_not(&mut self, query: impl Into<Query>) {
+        self.must_not.push(query.into())
+    }
+
+    /// Returns a `bool` query that matches the documents this query doesn't
+    /// match, i.e. with this query as its only `must_not` clause.
+    #[inline]
+    pub fn negate(self) -> BooleanQuery {
+        BooleanQuery {
+            must_not: vec![self.into_query()],
+            ..Default::default()
+        }
+    }
+
+    /// Wraps this `BooleanQuery` in a `Query`.
+    #[inline]
+    pub(crate) fn into_query(self) -> Query {
+        Query {
+            boolean: Some(Box::new(self)),
+            ..Default::default()
+        }
+    }
 }
 
 #[cfg(feature = "graphql")]
@@ -424,7 +626,9 @@ impl<T: Into<Query>> From<T> for BooleanQuery {
 /// **Note**: If a filter over a list of objects does not return the
 /// expected results, try a `NestedQueryInput`.
 ///
-/// **Note**: Specifying more than one field will result in an error.
+/// **Note**: Specifying more than one field will result in an error; use
+/// [`QueryInput::validate`] or [`QueryInput::try_into_query`] to catch it
+/// before sending the query to Elasticsearch.
 ///
 /// **TODO**: Change this type once [union input types] are supported by GraphQL
 /// to only allow specifying a single field.
@@ -432,9 +636,10 @@ impl<T: Into<Query>> From<T> for BooleanQuery {
 /// [union input types]: https://github.com/graphql/graphql-spec/blob/master/rfcs/InputUnion.md
 #[allow(missing_docs)]
 #[cfg(feature = "graphql")]
+#[elastiql_derive::graphql_name("FilterInput")]
 #[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(async_graphql::InputObject, Serialize, Clone, Debug)]
-#[graphql(name = "FilterInput")]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(async_graphql::InputObject, Serialize, Default, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
 pub struct QueryInput {
     #[cfg_attr(feature = "builder", builder(default))]
@@ -451,35 +656,135 @@ pub struct QueryInput {
 
     #[cfg_attr(feature = "builder", builder(default))]
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub range: Option<RangeQueryInput>,
+    pub terms_set: Option<Box<TermsSetQueryInput>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ids: Option<IdsQueryInput>,
 
     #[cfg_attr(feature = "builder", builder(default))]
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub prefix: Option<PrefixQueryInput>,
+    pub range: Option<Box<RangeQueryInput>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<Box<PrefixQueryInput>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wildcard: Option<Box<WildcardQueryInput>>,
 
     #[cfg_attr(feature = "builder", builder(default))]
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub regexp: Option<RegexpQueryInput>,
 
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fuzzy: Option<Box<FuzzyQueryInput>>,
+
     #[cfg_attr(feature = "builder", builder(default))]
     #[serde(default, rename = "match", skip_serializing_if = "Option::is_none")]
     pub match_: Option<MatchQueryInput>,
 
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub match_phrase: Option<Box<MatchPhraseQueryInput>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub match_phrase_prefix: Option<Box<MatchPhrasePrefixQueryInput>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multi_match: Option<Box<MultiMatchQueryInput>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub more_like_this: Option<Box<MoreLikeThisQueryInput>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub percolate: Option<Box<PercolateQueryInput>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script: Option<Box<ScriptQueryInput>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub geo_distance: Option<Box<GeoDistanceQueryInput>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub geo_bounding_box: Option<Box<GeoBoundingBoxQueryInput>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub geo_shape: Option<Box<GeoShapeQueryInput>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub geo_polygon: Option<Box<GeoPolygonQueryInput>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span_term: Option<Box<SpanTermQueryInput>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span_near: Option<Box<SpanNearQueryInput>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span_first: Option<Box<SpanFirstQueryInput>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span_or: Option<Box<SpanOrQueryInput>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span_not: Option<Box<SpanNotQueryInput>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span_containing: Option<Box<SpanContainingQueryInput>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span_within: Option<Box<SpanWithinQueryInput>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span_multi: Option<Box<SpanMultiQueryInput>>,
+
     #[cfg_attr(feature = "builder", builder(default))]
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub simple_query_string: Option<SimpleQueryStringQueryInput>,
 
     #[cfg_attr(feature = "builder", builder(default))]
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub query_string: Option<QueryStringQueryInput>,
+    pub query_string: Option<Box<QueryStringQueryInput>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub knn: Option<KnnQueryInput>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rank_feature: Option<Box<RankFeatureQueryInput>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script_score: Option<Box<ScriptScoreQueryInput>>,
 
     #[cfg_attr(feature = "builder", builder(default))]
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub nested: Option<NestedQueryInput>,
+    pub nested: Option<Box<NestedQueryInput>>,
 
     /// A nested bool query.
     #[serde(rename = "bool", default, skip_serializing_if = "Option::is_none")]
-    pub boolean: Option<BooleanQueryInput>,
+    pub boolean: Option<Box<BooleanQueryInput>>,
 }
 
 #[cfg(feature = "graphql")]
@@ -490,14 +795,43 @@ impl From<Query> for QueryInput {
             exists: query.exists.map(Into::into),
             term: query.term.map(Into::into),
             terms: query.terms.map(Into::into),
-            range: query.range.map(Into::into),
-            prefix: query.prefix.map(Into::into),
+            terms_set: query.terms_set.map(|query| Box::new((*query).into())),
+            ids: query.ids.map(Into::into),
+            range: query.range.map(|query| Box::new((*query).into())),
+            prefix: query.prefix.map(|query| Box::new((*query).into())),
+            wildcard: query.wildcard.map(|query| Box::new((*query).into())),
             regexp: query.regexp.map(Into::into),
+            fuzzy: query.fuzzy.map(|query| Box::new((*query).into())),
             match_: query.match_.map(Into::into),
+            match_phrase: query.match_phrase.map(|query| Box::new((*query).into())),
+            match_phrase_prefix: query
+                .match_phrase_prefix
+                .map(|query| Box::new((*query).into())),
+            multi_match: query.multi_match.map(|query| Box::new((*query).into())),
+            more_like_this: query.more_like_this.map(|query| Box::new((*query).into())),
+            percolate: query.percolate.map(|query| Box::new((*query).into())),
+            script: query.script.map(|query| Box::new((*query).into())),
+            geo_distance: query.geo_distance.map(|query| Box::new((*query).into())),
+            geo_bounding_box: query
+                .geo_bounding_box
+                .map(|query| Box::new((*query).into())),
+            geo_shape: query.geo_shape.map(|query| Box::new((*query).into())),
+            geo_polygon: query.geo_polygon.map(|query| Box::new((*query).into())),
+            span_term: query.span_term.map(|query| Box::new((*query).into())),
+            span_near: query.span_near.map(|query| Box::new((*query).into())),
+            span_first: query.span_first.map(|query| Box::new((*query).into())),
+            span_or: query.span_or.map(|query| Box::new((*query).into())),
+            span_not: query.span_not.map(|query| Box::new((*query).into())),
+            span_containing: query.span_containing.map(|query| Box::new((*query).into())),
+            span_within: query.span_within.map(|query| Box::new((*query).into())),
+            span_multi: query.span_multi.map(|query| Box::new((*query).into())),
             simple_query_string: query.simple_query_string.map(Into::into),
-            query_string: query.query_string.map(Into::into),
-            nested: query.nested.map(Into::into),
-            boolean: query.boolean.map(Into::into),
+            query_string: query.query_string.map(|query| Box::new((*query).into())),
+            knn: query.knn.map(Into::into),
+            rank_feature: query.rank_feature.map(|query| Box::new((*query).into())),
+            script_score: query.script_score.map(|query| Box::new((*query).into())),
+            nested: query.nested.map(|query| Box::new((*query).into())),
+            boolean: query.boolean.map(|query| Box::new((*query).into())),
         }
     }
 }
@@ -510,12 +844,37 @@ impl From<ExistsQueryInput> for QueryInput {
             exists: Some(filter),
             term: None,
             terms: None,
+            terms_set: None,
+            ids: None,
             range: None,
             prefix: None,
+            wildcard: None,
             regexp: None,
+            fuzzy: None,
             match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
             simple_query_string: None,
             query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
             nested: None,
             boolean: None,
         }
@@ -530,12 +889,37 @@ impl From<TermQueryInput> for QueryInput {
             exists: None,
             term: Some(filter),
             terms: None,
+            terms_set: None,
+            ids: None,
             range: None,
             prefix: None,
+            wildcard: None,
             regexp: None,
+            fuzzy: None,
             match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
             simple_query_string: None,
             query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
             nested: None,
             boolean: None,
         }
@@ -550,12 +934,37 @@ impl From<TermsQueryInput> for QueryInput {
             exists: None,
             term: None,
             terms: Some(filter),
+            terms_set: None,
+            ids: None,
             range: None,
             prefix: None,
+            wildcard: None,
             regexp: None,
+            fuzzy: None,
             match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
             simple_query_string: None,
             query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
             nested: None,
             boolean: None,
         }
@@ -563,19 +972,44 @@ impl From<TermsQueryInput> for QueryInput {
 }
 
 #[cfg(feature = "graphql")]
-impl From<RangeQueryInput> for QueryInput {
+impl From<TermsSetQueryInput> for QueryInput {
     #[inline]
-    fn from(filter: RangeQueryInput) -> QueryInput {
+    fn from(filter: TermsSetQueryInput) -> QueryInput {
         QueryInput {
             exists: None,
             term: None,
             terms: None,
-            range: Some(filter),
+            terms_set: Some(Box::new(filter)),
+            ids: None,
+            range: None,
             prefix: None,
+            wildcard: None,
             regexp: None,
+            fuzzy: None,
             match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
             simple_query_string: None,
             query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
             nested: None,
             boolean: None,
         }
@@ -583,19 +1017,44 @@ impl From<RangeQueryInput> for QueryInput {
 }
 
 #[cfg(feature = "graphql")]
-impl From<PrefixQueryInput> for QueryInput {
+impl From<IdsQueryInput> for QueryInput {
     #[inline]
-    fn from(filter: PrefixQueryInput) -> QueryInput {
+    fn from(filter: IdsQueryInput) -> QueryInput {
         QueryInput {
             exists: None,
             term: None,
             terms: None,
+            terms_set: None,
+            ids: Some(filter),
             range: None,
-            prefix: Some(filter),
+            prefix: None,
+            wildcard: None,
             regexp: None,
+            fuzzy: None,
             match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
             simple_query_string: None,
             query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
             nested: None,
             boolean: None,
         }
@@ -603,19 +1062,44 @@ impl From<PrefixQueryInput> for QueryInput {
 }
 
 #[cfg(feature = "graphql")]
-impl From<RegexpQueryInput> for QueryInput {
+impl From<RangeQueryInput> for QueryInput {
     #[inline]
-    fn from(filter: RegexpQueryInput) -> QueryInput {
+    fn from(filter: RangeQueryInput) -> QueryInput {
         QueryInput {
             exists: None,
             term: None,
             terms: None,
-            range: None,
+            terms_set: None,
+            ids: None,
+            range: Some(Box::new(filter)),
             prefix: None,
-            regexp: Some(filter),
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
             match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
             simple_query_string: None,
             query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
             nested: None,
             boolean: None,
         }
@@ -623,19 +1107,44 @@ impl From<RegexpQueryInput> for QueryInput {
 }
 
 #[cfg(feature = "graphql")]
-impl From<MatchQueryInput> for QueryInput {
+impl From<PrefixQueryInput> for QueryInput {
     #[inline]
-    fn from(filter: MatchQueryInput) -> QueryInput {
+    fn from(filter: PrefixQueryInput) -> QueryInput {
         QueryInput {
             exists: None,
             term: None,
             terms: None,
+            terms_set: None,
+            ids: None,
             range: None,
-            prefix: None,
+            prefix: Some(Box::new(filter)),
+            wildcard: None,
             regexp: None,
-            match_: Some(filter),
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
             simple_query_string: None,
             query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
             nested: None,
             boolean: None,
         }
@@ -643,19 +1152,44 @@ impl From<MatchQueryInput> for QueryInput {
 }
 
 #[cfg(feature = "graphql")]
-impl From<SimpleQueryStringQueryInput> for QueryInput {
+impl From<WildcardQueryInput> for QueryInput {
     #[inline]
-    fn from(filter: SimpleQueryStringQueryInput) -> QueryInput {
+    fn from(filter: WildcardQueryInput) -> QueryInput {
         QueryInput {
             exists: None,
             term: None,
             terms: None,
+            terms_set: None,
+            ids: None,
             range: None,
             prefix: None,
+            wildcard: Some(Box::new(filter)),
             regexp: None,
+            fuzzy: None,
             match_: None,
-            simple_query_string: Some(filter),
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
             query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
             nested: None,
             boolean: None,
         }
@@ -663,19 +1197,44 @@ impl From<SimpleQueryStringQueryInput> for QueryInput {
 }
 
 #[cfg(feature = "graphql")]
-impl From<QueryStringQueryInput> for QueryInput {
+impl From<RegexpQueryInput> for QueryInput {
     #[inline]
-    fn from(filter: QueryStringQueryInput) -> QueryInput {
+    fn from(filter: RegexpQueryInput) -> QueryInput {
         QueryInput {
             exists: None,
             term: None,
             terms: None,
+            terms_set: None,
+            ids: None,
             range: None,
             prefix: None,
-            regexp: None,
+            wildcard: None,
+            regexp: Some(filter),
+            fuzzy: None,
             match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
             simple_query_string: None,
-            query_string: Some(filter),
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
             nested: None,
             boolean: None,
         }
@@ -683,231 +1242,2703 @@ impl From<QueryStringQueryInput> for QueryInput {
 }
 
 #[cfg(feature = "graphql")]
-impl From<NestedQueryInput> for QueryInput {
+impl From<FuzzyQueryInput> for QueryInput {
     #[inline]
-    fn from(filter: NestedQueryInput) -> QueryInput {
+    fn from(filter: FuzzyQueryInput) -> QueryInput {
+        QueryInput {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: Some(Box::new(filter)),
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl From<MatchQueryInput> for QueryInput {
+    #[inline]
+    fn from(filter: MatchQueryInput) -> QueryInput {
+        QueryInput {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: Some(filter),
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl From<MatchPhraseQueryInput> for QueryInput {
+    #[inline]
+    fn from(filter: MatchPhraseQueryInput) -> QueryInput {
+        QueryInput {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: Some(Box::new(filter)),
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl From<MatchPhrasePrefixQueryInput> for QueryInput {
+    #[inline]
+    fn from(filter: MatchPhrasePrefixQueryInput) -> QueryInput {
+        QueryInput {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: Some(Box::new(filter)),
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl From<MultiMatchQueryInput> for QueryInput {
+    #[inline]
+    fn from(filter: MultiMatchQueryInput) -> QueryInput {
+        QueryInput {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: Some(Box::new(filter)),
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl From<MoreLikeThisQueryInput> for QueryInput {
+    #[inline]
+    fn from(filter: MoreLikeThisQueryInput) -> QueryInput {
+        QueryInput {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: Some(Box::new(filter)),
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl From<PercolateQueryInput> for QueryInput {
+    #[inline]
+    fn from(filter: PercolateQueryInput) -> QueryInput {
+        QueryInput {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: Some(Box::new(filter)),
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl From<ScriptQueryInput> for QueryInput {
+    #[inline]
+    fn from(filter: ScriptQueryInput) -> QueryInput {
+        QueryInput {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: Some(Box::new(filter)),
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl From<GeoDistanceQueryInput> for QueryInput {
+    #[inline]
+    fn from(filter: GeoDistanceQueryInput) -> QueryInput {
+        QueryInput {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: Some(Box::new(filter)),
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl From<GeoBoundingBoxQueryInput> for QueryInput {
+    #[inline]
+    fn from(filter: GeoBoundingBoxQueryInput) -> QueryInput {
+        QueryInput {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: Some(Box::new(filter)),
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl From<GeoShapeQueryInput> for QueryInput {
+    #[inline]
+    fn from(filter: GeoShapeQueryInput) -> QueryInput {
+        QueryInput {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: Some(Box::new(filter)),
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl From<GeoPolygonQueryInput> for QueryInput {
+    #[inline]
+    fn from(filter: GeoPolygonQueryInput) -> QueryInput {
+        QueryInput {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: Some(Box::new(filter)),
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl From<SpanTermQueryInput> for QueryInput {
+    #[inline]
+    fn from(filter: SpanTermQueryInput) -> QueryInput {
+        QueryInput {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: Some(Box::new(filter)),
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl From<SpanNearQueryInput> for QueryInput {
+    #[inline]
+    fn from(filter: SpanNearQueryInput) -> QueryInput {
+        QueryInput {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: Some(Box::new(filter)),
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl From<SpanFirstQueryInput> for QueryInput {
+    #[inline]
+    fn from(filter: SpanFirstQueryInput) -> QueryInput {
         QueryInput {
             exists: None,
             term: None,
             terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: Some(Box::new(filter)),
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl From<SpanOrQueryInput> for QueryInput {
+    #[inline]
+    fn from(filter: SpanOrQueryInput) -> QueryInput {
+        QueryInput {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: Some(Box::new(filter)),
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl From<SpanNotQueryInput> for QueryInput {
+    #[inline]
+    fn from(filter: SpanNotQueryInput) -> QueryInput {
+        QueryInput {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: Some(Box::new(filter)),
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl From<SpanContainingQueryInput> for QueryInput {
+    #[inline]
+    fn from(filter: SpanContainingQueryInput) -> QueryInput {
+        QueryInput {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: Some(Box::new(filter)),
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl From<SpanWithinQueryInput> for QueryInput {
+    #[inline]
+    fn from(filter: SpanWithinQueryInput) -> QueryInput {
+        QueryInput {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: Some(Box::new(filter)),
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl From<SpanMultiQueryInput> for QueryInput {
+    #[inline]
+    fn from(filter: SpanMultiQueryInput) -> QueryInput {
+        QueryInput {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: Some(Box::new(filter)),
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl From<SimpleQueryStringQueryInput> for QueryInput {
+    #[inline]
+    fn from(filter: SimpleQueryStringQueryInput) -> QueryInput {
+        QueryInput {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: Some(filter),
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl From<QueryStringQueryInput> for QueryInput {
+    #[inline]
+    fn from(filter: QueryStringQueryInput) -> QueryInput {
+        QueryInput {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: Some(Box::new(filter)),
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl From<KnnQueryInput> for QueryInput {
+    #[inline]
+    fn from(filter: KnnQueryInput) -> QueryInput {
+        QueryInput {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: Some(filter),
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl From<RankFeatureQueryInput> for QueryInput {
+    #[inline]
+    fn from(filter: RankFeatureQueryInput) -> QueryInput {
+        QueryInput {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: Some(Box::new(filter)),
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl From<ScriptScoreQueryInput> for QueryInput {
+    #[inline]
+    fn from(filter: ScriptScoreQueryInput) -> QueryInput {
+        QueryInput {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: Some(Box::new(filter)),
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl From<NestedQueryInput> for QueryInput {
+    #[inline]
+    fn from(filter: NestedQueryInput) -> QueryInput {
+        QueryInput {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: Some(Box::new(filter)),
+            boolean: None,
+        }
+    }
+}
+
+/// A single search query.
+///
+/// **Note**: This should *never* have more than *one* defined (and non-null) field.
+///
+/// The larger, and rarer, `query_string`, `nested` and `bool` queries are
+/// boxed so that e.g. a `Vec` of `term` queries doesn't pay for their size.
+#[allow(missing_docs)]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("Filter"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct Query {
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exists: Option<ExistsQuery>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub term: Option<TermQuery>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub terms: Option<TermsQuery>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub terms_set: Option<Box<TermsSetQuery>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ids: Option<IdsQuery>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range: Option<Box<RangeQuery>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<Box<PrefixQuery>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wildcard: Option<Box<WildcardQuery>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub regexp: Option<RegexpQuery>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fuzzy: Option<Box<FuzzyQuery>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, rename = "match", skip_serializing_if = "Option::is_none")]
+    pub match_: Option<MatchQuery>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub match_phrase: Option<Box<MatchPhraseQuery>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub match_phrase_prefix: Option<Box<MatchPhrasePrefixQuery>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multi_match: Option<Box<MultiMatchQuery>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub more_like_this: Option<Box<MoreLikeThisQuery>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub percolate: Option<Box<PercolateQuery>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script: Option<Box<ScriptQuery>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub geo_distance: Option<Box<GeoDistanceQuery>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub geo_bounding_box: Option<Box<GeoBoundingBoxQuery>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub geo_shape: Option<Box<GeoShapeQuery>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub geo_polygon: Option<Box<GeoPolygonQuery>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span_term: Option<Box<SpanTermQuery>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span_near: Option<Box<SpanNearQuery>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span_first: Option<Box<SpanFirstQuery>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span_or: Option<Box<SpanOrQuery>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span_not: Option<Box<SpanNotQuery>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span_containing: Option<Box<SpanContainingQuery>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span_within: Option<Box<SpanWithinQuery>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span_multi: Option<Box<SpanMultiQuery>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub simple_query_string: Option<SimpleQueryStringQuery>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query_string: Option<Box<QueryStringQuery>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub knn: Option<KnnQuery>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rank_feature: Option<Box<RankFeatureQuery>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script_score: Option<Box<ScriptScoreQuery>>,
+
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nested: Option<Box<NestedQuery>>,
+
+    /// A nested bool query.
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(rename = "bool", default, skip_serializing_if = "Option::is_none")]
+    pub boolean: Option<Box<BooleanQuery>>,
+}
+
+impl Query {
+    /// Returns `true` if none of the fields of this `Query` are defined.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.exists.is_none()
+            && self.term.is_none()
+            && self.terms.is_none()
+            && self.terms_set.is_none()
+            && self.ids.is_none()
+            && self.range.is_none()
+            && self.prefix.is_none()
+            && self.wildcard.is_none()
+            && self.regexp.is_none()
+            && self.fuzzy.is_none()
+            && self.match_.is_none()
+            && self.match_phrase.is_none()
+            && self.match_phrase_prefix.is_none()
+            && self.multi_match.is_none()
+            && self.more_like_this.is_none()
+            && self.percolate.is_none()
+            && self.script.is_none()
+            && self.geo_distance.is_none()
+            && self.geo_bounding_box.is_none()
+            && self.geo_shape.is_none()
+            && self.geo_polygon.is_none()
+            && self.span_term.is_none()
+            && self.span_near.is_none()
+            && self.span_first.is_none()
+            && self.span_or.is_none()
+            && self.span_not.is_none()
+            && self.span_containing.is_none()
+            && self.span_within.is_none()
+            && self.span_multi.is_none()
+            && self.simple_query_string.is_none()
+            && self.query_string.is_none()
+            && self.knn.is_none()
+            && self.rank_feature.is_none()
+            && self.script_score.is_none()
+            && self.nested.is_none()
+            && self.boolean.is_none()
+    }
+}
+
+// **Note**: unlike `FromStr`, which parses Lucene query syntax, these display
+// the JSON query DSL.
+impl_display_json!(Query, BooleanQuery, CompoundQuery);
+impl_canonical_json!(Query, BooleanQuery, CompoundQuery);
+
+#[cfg(feature = "graphql")]
+impl From<QueryInput> for Query {
+    #[inline]
+    fn from(input: QueryInput) -> Query {
+        Query {
+            exists: input.exists.map(Into::into),
+            term: input.term.map(Into::into),
+            terms: input.terms.map(Into::into),
+            terms_set: input.terms_set.map(|query| Box::new((*query).into())),
+            ids: input.ids.map(Into::into),
+            range: input.range.map(|query| Box::new((*query).into())),
+            prefix: input.prefix.map(|query| Box::new((*query).into())),
+            wildcard: input.wildcard.map(|query| Box::new((*query).into())),
+            regexp: input.regexp.map(Into::into),
+            fuzzy: input.fuzzy.map(|query| Box::new((*query).into())),
+            match_: input.match_.map(Into::into),
+            match_phrase: input.match_phrase.map(|query| Box::new((*query).into())),
+            match_phrase_prefix: input
+                .match_phrase_prefix
+                .map(|query| Box::new((*query).into())),
+            multi_match: input.multi_match.map(|query| Box::new((*query).into())),
+            more_like_this: input.more_like_this.map(|query| Box::new((*query).into())),
+            percolate: input.percolate.map(|query| Box::new((*query).into())),
+            script: input.script.map(|query| Box::new((*query).into())),
+            geo_distance: input.geo_distance.map(|query| Box::new((*query).into())),
+            geo_bounding_box: input
+                .geo_bounding_box
+                .map(|query| Box::new((*query).into())),
+            geo_shape: input.geo_shape.map(|query| Box::new((*query).into())),
+            geo_polygon: input.geo_polygon.map(|query| Box::new((*query).into())),
+            span_term: input.span_term.map(|query| Box::new((*query).into())),
+            span_near: input.span_near.map(|query| Box::new((*query).into())),
+            span_first: input.span_first.map(|query| Box::new((*query).into())),
+            span_or: input.span_or.map(|query| Box::new((*query).into())),
+            span_not: input.span_not.map(|query| Box::new((*query).into())),
+            span_containing: input.span_containing.map(|query| Box::new((*query).into())),
+            span_within: input.span_within.map(|query| Box::new((*query).into())),
+            span_multi: input.span_multi.map(|query| Box::new((*query).into())),
+            simple_query_string: input.simple_query_string.map(Into::into),
+            query_string: input.query_string.map(|query| Box::new((*query).into())),
+            knn: input.knn.map(Into::into),
+            rank_feature: input.rank_feature.map(|query| Box::new((*query).into())),
+            script_score: input.script_score.map(|query| Box::new((*query).into())),
+            nested: input.nested.map(|query| Box::new((*query).into())),
+            boolean: input.boolean.map(|query| Box::new((*query).into())),
+        }
+    }
+}
+
+impl From<ExistsQuery> for Query {
+    #[inline]
+    fn from(filter: ExistsQuery) -> Query {
+        Query {
+            exists: Some(filter),
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+impl From<TermQuery> for Query {
+    #[inline]
+    fn from(filter: TermQuery) -> Query {
+        Query {
+            exists: None,
+            term: Some(filter),
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+impl From<TermsQuery> for Query {
+    #[inline]
+    fn from(filter: TermsQuery) -> Query {
+        Query {
+            exists: None,
+            term: None,
+            terms: Some(filter),
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+impl From<TermsSetQuery> for Query {
+    #[inline]
+    fn from(filter: TermsSetQuery) -> Query {
+        Query {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: Some(Box::new(filter)),
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+impl From<IdsQuery> for Query {
+    #[inline]
+    fn from(filter: IdsQuery) -> Query {
+        Query {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: Some(filter),
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+impl From<RangeQuery> for Query {
+    #[inline]
+    fn from(filter: RangeQuery) -> Query {
+        Query {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: Some(Box::new(filter)),
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+impl From<PrefixQuery> for Query {
+    #[inline]
+    fn from(filter: PrefixQuery) -> Query {
+        Query {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: Some(Box::new(filter)),
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+impl From<WildcardQuery> for Query {
+    #[inline]
+    fn from(filter: WildcardQuery) -> Query {
+        Query {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: Some(Box::new(filter)),
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+impl From<RegexpQuery> for Query {
+    #[inline]
+    fn from(filter: RegexpQuery) -> Query {
+        Query {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: Some(filter),
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+impl From<FuzzyQuery> for Query {
+    #[inline]
+    fn from(filter: FuzzyQuery) -> Query {
+        Query {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: Some(Box::new(filter)),
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+impl From<MatchQuery> for Query {
+    #[inline]
+    fn from(filter: MatchQuery) -> Query {
+        Query {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+
+            match_: Some(filter),
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+impl From<MatchPhraseQuery> for Query {
+    #[inline]
+    fn from(filter: MatchPhraseQuery) -> Query {
+        Query {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: Some(Box::new(filter)),
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+impl From<MatchPhrasePrefixQuery> for Query {
+    #[inline]
+    fn from(filter: MatchPhrasePrefixQuery) -> Query {
+        Query {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: Some(Box::new(filter)),
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+impl From<MultiMatchQuery> for Query {
+    #[inline]
+    fn from(filter: MultiMatchQuery) -> Query {
+        Query {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: Some(Box::new(filter)),
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+impl From<MoreLikeThisQuery> for Query {
+    #[inline]
+    fn from(filter: MoreLikeThisQuery) -> Query {
+        Query {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: Some(Box::new(filter)),
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
+
+impl From<PercolateQuery> for Query {
+    #[inline]
+    fn from(filter: PercolateQuery) -> Query {
+        Query {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
             range: None,
             prefix: None,
+            wildcard: None,
             regexp: None,
+            fuzzy: None,
             match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: Some(Box::new(filter)),
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
             simple_query_string: None,
             query_string: None,
-            nested: Some(filter),
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
             boolean: None,
         }
     }
 }
 
-/// A single search query.
-///
-/// **Note**: This should *never* have more than *one* defined (and non-null) field.
-#[allow(missing_docs)]
-#[cfg_attr(test, derive(PartialEq))]
-#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
-#[cfg_attr(feature = "graphql", graphql(name = "Filter"))]
-#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(Serialize, Deserialize, Clone, Debug)]
-#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
-pub struct Query {
-    #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub exists: Option<ExistsQuery>,
-
-    #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub term: Option<TermQuery>,
-
-    #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub terms: Option<TermsQuery>,
-
-    #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub range: Option<RangeQuery>,
-
-    #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub prefix: Option<PrefixQuery>,
-
-    #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub regexp: Option<RegexpQuery>,
-
-    #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(default, rename = "match", skip_serializing_if = "Option::is_none")]
-    pub match_: Option<MatchQuery>,
-
-    #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub simple_query_string: Option<SimpleQueryStringQuery>,
+impl From<ScriptQuery> for Query {
+    #[inline]
+    fn from(filter: ScriptQuery) -> Query {
+        Query {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: Some(Box::new(filter)),
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
 
-    #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub query_string: Option<QueryStringQuery>,
+impl From<GeoDistanceQuery> for Query {
+    #[inline]
+    fn from(filter: GeoDistanceQuery) -> Query {
+        Query {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: Some(Box::new(filter)),
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
 
-    #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub nested: Option<NestedQuery>,
+impl From<GeoBoundingBoxQuery> for Query {
+    #[inline]
+    fn from(filter: GeoBoundingBoxQuery) -> Query {
+        Query {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: Some(Box::new(filter)),
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
 
-    /// A nested bool query.
-    #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(rename = "bool", default, skip_serializing_if = "Option::is_none")]
-    pub boolean: Option<BooleanQuery>,
+impl From<GeoShapeQuery> for Query {
+    #[inline]
+    fn from(filter: GeoShapeQuery) -> Query {
+        Query {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: Some(Box::new(filter)),
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
 }
 
-#[cfg(feature = "graphql")]
-impl From<QueryInput> for Query {
+impl From<GeoPolygonQuery> for Query {
     #[inline]
-    fn from(input: QueryInput) -> Query {
+    fn from(filter: GeoPolygonQuery) -> Query {
         Query {
-            exists: input.exists.map(Into::into),
-            term: input.term.map(Into::into),
-            terms: input.terms.map(Into::into),
-            range: input.range.map(Into::into),
-            prefix: input.prefix.map(Into::into),
-            regexp: input.regexp.map(Into::into),
-            match_: input.match_.map(Into::into),
-            simple_query_string: input.simple_query_string.map(Into::into),
-            query_string: input.query_string.map(Into::into),
-            nested: input.nested.map(Into::into),
-            boolean: input.boolean.map(Into::into),
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: Some(Box::new(filter)),
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
         }
     }
 }
 
-impl From<ExistsQuery> for Query {
+impl From<SpanTermQuery> for Query {
     #[inline]
-    fn from(filter: ExistsQuery) -> Query {
+    fn from(filter: SpanTermQuery) -> Query {
         Query {
-            exists: Some(filter),
+            exists: None,
             term: None,
             terms: None,
+            terms_set: None,
+            ids: None,
             range: None,
             prefix: None,
+            wildcard: None,
             regexp: None,
+            fuzzy: None,
             match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: Some(Box::new(filter)),
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
             simple_query_string: None,
             query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
             nested: None,
             boolean: None,
         }
     }
 }
 
-impl From<TermQuery> for Query {
+impl From<SpanNearQuery> for Query {
     #[inline]
-    fn from(filter: TermQuery) -> Query {
+    fn from(filter: SpanNearQuery) -> Query {
         Query {
             exists: None,
-            term: Some(filter),
+            term: None,
             terms: None,
+            terms_set: None,
+            ids: None,
             range: None,
             prefix: None,
+            wildcard: None,
             regexp: None,
+            fuzzy: None,
             match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: Some(Box::new(filter)),
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
             simple_query_string: None,
             query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
             nested: None,
             boolean: None,
         }
     }
 }
 
-impl From<TermsQuery> for Query {
+impl From<SpanFirstQuery> for Query {
     #[inline]
-    fn from(filter: TermsQuery) -> Query {
+    fn from(filter: SpanFirstQuery) -> Query {
         Query {
             exists: None,
             term: None,
-            terms: Some(filter),
+            terms: None,
+            terms_set: None,
+            ids: None,
             range: None,
             prefix: None,
+            wildcard: None,
             regexp: None,
+            fuzzy: None,
             match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: Some(Box::new(filter)),
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
             simple_query_string: None,
             query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
             nested: None,
             boolean: None,
         }
     }
 }
 
-impl From<RangeQuery> for Query {
+impl From<SpanOrQuery> for Query {
     #[inline]
-    fn from(filter: RangeQuery) -> Query {
+    fn from(filter: SpanOrQuery) -> Query {
         Query {
             exists: None,
             term: None,
             terms: None,
-            range: Some(filter),
+            terms_set: None,
+            ids: None,
+            range: None,
             prefix: None,
+            wildcard: None,
             regexp: None,
+            fuzzy: None,
             match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: Some(Box::new(filter)),
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
             simple_query_string: None,
             query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
             nested: None,
             boolean: None,
         }
     }
 }
 
-#[cfg(feature = "graphql")]
-impl From<PrefixQuery> for Query {
+impl From<SpanNotQuery> for Query {
     #[inline]
-    fn from(filter: PrefixQuery) -> Query {
+    fn from(filter: SpanNotQuery) -> Query {
         Query {
             exists: None,
             term: None,
             terms: None,
+            terms_set: None,
+            ids: None,
             range: None,
-            prefix: Some(filter),
+            prefix: None,
+            wildcard: None,
             regexp: None,
+            fuzzy: None,
             match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: Some(Box::new(filter)),
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
             simple_query_string: None,
             query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
             nested: None,
             boolean: None,
         }
     }
 }
 
-impl From<RegexpQuery> for Query {
+impl From<SpanContainingQuery> for Query {
     #[inline]
-    fn from(filter: RegexpQuery) -> Query {
+    fn from(filter: SpanContainingQuery) -> Query {
         Query {
             exists: None,
             term: None,
             terms: None,
+            terms_set: None,
+            ids: None,
             range: None,
             prefix: None,
-            regexp: Some(filter),
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
             match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: Some(Box::new(filter)),
+            span_within: None,
+            span_multi: None,
             simple_query_string: None,
             query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
             nested: None,
             boolean: None,
         }
     }
 }
 
-impl From<MatchQuery> for Query {
+impl From<SpanWithinQuery> for Query {
     #[inline]
-    fn from(filter: MatchQuery) -> Query {
+    fn from(filter: SpanWithinQuery) -> Query {
         Query {
             exists: None,
             term: None,
             terms: None,
+            terms_set: None,
+            ids: None,
             range: None,
             prefix: None,
+            wildcard: None,
             regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: Some(Box::new(filter)),
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
 
-            match_: Some(filter),
+impl From<SpanMultiQuery> for Query {
+    #[inline]
+    fn from(filter: SpanMultiQuery) -> Query {
+        Query {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: Some(Box::new(filter)),
             simple_query_string: None,
             query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
             nested: None,
             boolean: None,
         }
@@ -921,12 +3952,37 @@ impl From<SimpleQueryStringQuery> for Query {
             exists: None,
             term: None,
             terms: None,
+            terms_set: None,
+            ids: None,
             range: None,
             prefix: None,
+            wildcard: None,
             regexp: None,
+            fuzzy: None,
             match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
             simple_query_string: Some(filter),
             query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
             nested: None,
             boolean: None,
         }
@@ -940,57 +3996,378 @@ impl From<QueryStringQuery> for Query {
             exists: None,
             term: None,
             terms: None,
+            terms_set: None,
+            ids: None,
             range: None,
             prefix: None,
+            wildcard: None,
             regexp: None,
+            fuzzy: None,
             match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
             simple_query_string: None,
-            query_string: Some(filter),
+            query_string: Some(Box::new(filter)),
+            knn: None,
+            rank_feature: None,
+            script_score: None,
             nested: None,
             boolean: None,
         }
     }
 }
 
-impl From<NestedQuery> for Query {
+impl From<KnnQuery> for Query {
     #[inline]
-    fn from(filter: NestedQuery) -> Query {
+    fn from(filter: KnnQuery) -> Query {
         Query {
             exists: None,
             term: None,
             terms: None,
+            terms_set: None,
+            ids: None,
             range: None,
             prefix: None,
+            wildcard: None,
             regexp: None,
+            fuzzy: None,
             match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
             simple_query_string: None,
             query_string: None,
-            nested: Some(filter),
+            knn: Some(filter),
+            rank_feature: None,
+            script_score: None,
+            nested: None,
             boolean: None,
         }
     }
 }
 
-/// Describes a field that can be queried and its type.
-#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
-#[cfg_attr(feature = "graphql", graphql(name = "FilterField"))]
-#[derive(Debug)]
-pub struct QueryField {
-    /// The field name.
-    pub field: String,
+impl From<RankFeatureQuery> for Query {
+    #[inline]
+    fn from(filter: RankFeatureQuery) -> Query {
+        Query {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: Some(Box::new(filter)),
+            script_score: None,
+            nested: None,
+            boolean: None,
+        }
+    }
+}
 
-    // TODO: rename to `ty` once https://github.com/async-graphql/async-graphql/issues/164
-    /// The type
-    pub type_: String,
+impl From<ScriptScoreQuery> for Query {
+    #[inline]
+    fn from(filter: ScriptScoreQuery) -> Query {
+        Query {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: Some(Box::new(filter)),
+            nested: None,
+            boolean: None,
+        }
+    }
 }
 
-impl QueryField {
-    /// Create a new `QueryField`.
+impl From<NestedQuery> for Query {
     #[inline]
-    pub fn new(field: impl Into<String>, ty: impl Into<String>) -> Self {
-        Self {
-            field: field.into(),
-            type_: ty.into(),
+    fn from(filter: NestedQuery) -> Query {
+        Query {
+            exists: None,
+            term: None,
+            terms: None,
+            terms_set: None,
+            ids: None,
+            range: None,
+            prefix: None,
+            wildcard: None,
+            regexp: None,
+            fuzzy: None,
+            match_: None,
+            match_phrase: None,
+            match_phrase_prefix: None,
+            multi_match: None,
+            more_like_this: None,
+            percolate: None,
+            script: None,
+            geo_distance: None,
+            geo_bounding_box: None,
+            geo_shape: None,
+            geo_polygon: None,
+            span_term: None,
+            span_near: None,
+            span_first: None,
+            span_or: None,
+            span_not: None,
+            span_containing: None,
+            span_within: None,
+            span_multi: None,
+            simple_query_string: None,
+            query_string: None,
+            knn: None,
+            rank_feature: None,
+            script_score: None,
+            nested: Some(Box::new(filter)),
+            boolean: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn can_push_clauses() {
+        let mut query = CompoundQuery::default();
+        query.push(TermQuery::new("status", "open"));
+        query.push_must(MatchQuery::new("message", "error"));
+        query.push_should(PrefixQuery::new("user", "ki"));
+        query.push_must_not(ExistsQuery::new("deleted"));
+
+        assert_eq!(
+            serde_json::to_value(&query).unwrap(),
+            json!({
+                "bool": {
+                    "must": [{ "match": { "message": { "query": "error" } } }],
+                    "filter": [{ "term": { "status": { "value": "open" } } }],
+                    "should": [{ "prefix": { "user": { "value": "ki", "case_insensitive": false } } }],
+                    "must_not": [{ "exists": { "field": "deleted" } }]
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn can_have_any_query_at_the_root() {
+        let json = json!({ "term": { "status": { "value": "open" } } });
+        let mut query: CompoundQuery = serde_json::from_value(json.clone()).unwrap();
+        assert!(query.boolean.is_none());
+        assert_eq!(serde_json::to_value(&query).unwrap(), json);
+
+        let boolean = json!({ "bool": { "filter": [json.clone()] } });
+        let parsed: CompoundQuery = serde_json::from_value(boolean.clone()).unwrap();
+        assert!(parsed.root.is_none());
+        assert_eq!(
+            parsed,
+            CompoundQuery::from(TermQuery::new("status", "open"))
+        );
+
+        query.push(ExistsQuery::new("user"));
+        assert_eq!(
+            serde_json::to_value(&query).unwrap(),
+            json!({
+                "bool": {
+                    "must": [json],
+                    "filter": [{ "exists": { "field": "user" } }]
+                }
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn boxes_large_queries() {
+        // the `terms_set`, `range`, `prefix`, `wildcard`, `fuzzy`, `match_phrase`,
+        // `match_phrase_prefix`, `multi_match`, `more_like_this`, `percolate`,
+        // `script`, `geo_distance`, `geo_bounding_box`, `geo_shape`,
+        // `geo_polygon`, span, `query_string`, `rank_feature`, `script_score`,
+        // `nested` and `bool` queries are boxed so that large `bool` queries of
+        // e.g. `term` queries stay (relatively) compact; boxing the
+        // `query_string`, `nested` and `bool` queries alone shrank a `Query`
+        // from 1216 to 648 bytes
+        //
+        // NOTE: every new kind of query adds (at least) a pointer, so raise
+        // this a little when needed, but box a kind if it adds much more; see
+        // `benches/query.rs` for the effect on cloning and (de)serializing
+        assert!(std::mem::size_of::<Query>() <= 700);
+    }
+
+    #[test]
+    fn can_negate() {
+        let query = CompoundQuery::from(TermQuery::new("status", "open")).negate();
+
+        assert_eq!(
+            serde_json::to_value(&query).unwrap(),
+            json!({
+                "bool": {
+                    "must_not": [{
+                        "bool": { "filter": [{ "term": { "status": { "value": "open" } } }] }
+                    }]
+                }
+            })
+        );
+
+        let query = BooleanQuery::from(TermQuery::new("status", "open")).negate();
+        assert_eq!(query.must_not.len(), 1);
+        assert!(query.must.is_empty() && query.filter.is_empty() && query.should.is_empty());
+    }
+
+    #[test]
+    fn can_display_as_json() {
+        let query = Query::from(TermQuery::new("status", "open"));
+        assert_eq!(query.to_string(), r#"{"term":{"status":{"value":"open"}}}"#);
+        assert_eq!(
+            format!("{:#}", query),
+            r#"{
+  "term": {
+    "status": {
+      "value": "open"
+    }
+  }
+}"#
+        );
+
+        let query = CompoundQuery::from(ExistsQuery::new("user"));
+        assert_eq!(
+            query.to_string(),
+            r#"{"bool":{"filter":[{"exists":{"field":"user"}}]}}"#
+        );
+        assert_eq!(
+            query.boolean.unwrap().to_string(),
+            r#"{"filter":[{"exists":{"field":"user"}}]}"#
+        );
+    }
+
+    #[cfg(feature = "graphql")]
+    #[test]
+    fn can_push_and_negate_inputs() {
+        let mut input = CompoundQueryInput::default();
+        input.push_must(TermQueryInput::new("status", "open"));
+        input.push_should(PrefixQueryInput::new("user", "ki"));
+        input.push_must_not(ExistsQueryInput::new("deleted"));
+
+        let mut expected = CompoundQuery::default();
+        expected.push_must(TermQuery::new("status", "open"));
+        expected.push_should(PrefixQuery::new("user", "ki"));
+        expected.push_must_not(ExistsQuery::new("deleted"));
+
+        assert_eq!(CompoundQuery::from(input.clone()), expected);
+        assert_eq!(CompoundQuery::from(input.negate()), expected.negate());
+    }
+
+    #[cfg(feature = "graphql")]
+    #[test]
+    fn can_be_used_in_a_federated_subgraph() {
+        use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema};
+
+        struct Subgraph;
+
+        #[Object]
+        impl Subgraph {
+            async fn filter(&self, filter: CompoundQueryInput) -> CompoundQuery {
+                filter.into()
+            }
         }
+
+        let schema = Schema::build(Subgraph, EmptyMutation, EmptySubscription)
+            .enable_federation()
+            .finish();
+        let sdl = schema.federation_sdl();
+
+        // elastiql types are value types, i.e. they don't have any `@key`s
+        let output = <CompoundQuery as async_graphql::Type>::type_name();
+        assert!(sdl.contains(&format!("type {} {{", output)), "{}", sdl);
+        let input = <CompoundQueryInput as async_graphql::Type>::type_name();
+        assert!(sdl.contains(&format!("input {} {{", input)), "{}", sdl);
+        assert!(!sdl.contains("@key"), "{}", sdl);
     }
 }
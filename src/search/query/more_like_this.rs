@@ -0,0 +1,356 @@
+//! [More like this query](https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-mlt-query.html)
+
+use elastiql_derive::{input_twin, twin_impl};
+#[cfg(feature = "schemars")]
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::ser::{SerializeMap, Serializer};
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// A [More like this query] returns documents that are "like" a given set of
+/// texts and/or documents, e.g. for recommendations.
+///
+/// [More like this query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-mlt-query.html
+#[input_twin(name = "MoreLikeThisFilterInput")]
+#[cfg_attr(
+    feature = "graphql",
+    elastiql_derive::graphql_name("MoreLikeThisFilter")
+)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct MoreLikeThisQuery {
+    /// The name of the fields to fetch and analyze the text from. (Defaults to
+    /// the `index.query.default_field` index setting.)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[input(graphql(default))]
+    pub fields: Vec<String>,
+
+    /// The texts and/or documents to find documents like.
+    #[serde(deserialize_with = "one_or_many")]
+    #[input(twin)]
+    pub like: Vec<LikeItem>,
+
+    /// The texts and/or documents whose terms are excluded from the terms
+    /// selected from `like`.
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        deserialize_with = "one_or_many"
+    )]
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[input(twin, graphql(default))]
+    pub unlike: Vec<LikeItem>,
+
+    /// The minimum frequency of a term in `like` for it to be selected.
+    /// (Defaults to `2`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub min_term_freq: Option<u32>,
+
+    /// The maximum number of terms selected from `like`. (Defaults to `25`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub max_query_terms: Option<u32>,
+
+    /// The minimum number of documents a term has to occur in for it to be
+    /// selected. (Defaults to `5`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub min_doc_freq: Option<u32>,
+
+    /// The [analyzer] used to analyze the free texts of `like`. (Defaults to
+    /// the analyzer of the first of the `fields`.)
+    ///
+    /// [analyzer]: https://www.elastic.co/guide/en/elasticsearch/reference/current/analysis.html
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub analyzer: Option<String>,
+}
+
+impl MoreLikeThisQuery {
+    /// Constructs a new `MoreLikeThisQuery`.
+    #[inline]
+    pub fn new<I, T>(fields: I, like: impl IntoIterator<Item = LikeItem>) -> MoreLikeThisQuery
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        MoreLikeThisQuery {
+            fields: fields.into_iter().map(Into::into).collect(),
+            like: like.into_iter().collect(),
+            unlike: vec![],
+            min_term_freq: None,
+            max_query_terms: None,
+            min_doc_freq: None,
+            analyzer: None,
+        }
+    }
+}
+
+/// Deserializes either a single [`LikeItem`] or a sequence of them, just like
+/// Elasticsearch accepts them.
+#[inline]
+fn one_or_many<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<LikeItem>, D::Error> {
+    #[allow(clippy::missing_docs_in_private_items)]
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        // tried first since a `LikeDocument` deserializes from a sequence too
+        Many(Vec<LikeItem>),
+        One(LikeItem),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::Many(items) => items,
+        OneOrMany::One(item) => vec![item],
+    })
+}
+
+/// A free text or a reference to a document in the `like` or `unlike` of a
+/// [`MoreLikeThisQuery`].
+///
+/// **Note**: until GraphQL [Union input types] are supported either `text` or
+/// the `index` and `id` of a document may be specified; the `text` takes
+/// precedence.
+///
+/// It is (de)serialized as Elasticsearch expects it, i.e. as either the text
+/// itself or as a document reference, e.g. `{ "_index": "imdb", "_id": "1" }`.
+/// Other document references, e.g. artificial documents (`doc`), aren't
+/// supported and fail to deserialize.
+///
+/// [Union input types]: https://github.com/graphql/graphql-spec/blob/master/rfcs/InputUnion.md
+#[input_twin(name = "LikeItemInput")]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("LikeItem"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[derive(Clone, Default, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "builder", builder(field_defaults(default, setter(into))))]
+pub struct LikeItem {
+    /// A free text.
+    pub text: Option<String>,
+
+    /// The index of the document. (Defaults to the index being searched.)
+    pub index: Option<String>,
+
+    /// The ID of the document.
+    pub id: Option<String>,
+}
+
+impl LikeItem {
+    /// Constructs a new `LikeItem` referencing the document with `id` in
+    /// `index`.
+    #[inline]
+    pub fn document(index: impl Into<String>, id: impl Into<String>) -> LikeItem {
+        LikeItem {
+            text: None,
+            index: Some(index.into()),
+            id: Some(id.into()),
+        }
+    }
+}
+
+impl From<&str> for LikeItem {
+    /// Returns a `LikeItem` of the free `text`.
+    #[inline]
+    fn from(text: &str) -> Self {
+        text.to_string().into()
+    }
+}
+
+impl From<String> for LikeItem {
+    /// Returns a `LikeItem` of the free `text`.
+    #[inline]
+    fn from(text: String) -> Self {
+        LikeItem {
+            text: Some(text),
+            ..LikeItem::default()
+        }
+    }
+}
+
+/// The way Elasticsearch represents a [`LikeItem`].
+#[allow(clippy::missing_docs_in_private_items)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum LikeItemRepr {
+    Text(String),
+    Document(LikeDocument),
+}
+
+#[allow(clippy::missing_docs_in_private_items)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LikeDocument {
+    #[serde(default, rename = "_index")]
+    index: Option<String>,
+    #[serde(default, rename = "_id")]
+    id: Option<String>,
+}
+
+#[twin_impl]
+impl Serialize for LikeItem {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if let Some(ref text) = self.text {
+            return serializer.serialize_str(text);
+        }
+
+        let mut map = serializer.serialize_map(None)?;
+        if let Some(ref index) = self.index {
+            map.serialize_entry("_index", index)?;
+        }
+        if let Some(ref id) = self.id {
+            map.serialize_entry("_id", id)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for LikeItem {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<LikeItem, D::Error> {
+        Ok(match LikeItemRepr::deserialize(deserializer)? {
+            LikeItemRepr::Text(text) => text.into(),
+            LikeItemRepr::Document(document) => LikeItem {
+                text: None,
+                index: document.index,
+                id: document.id,
+            },
+        })
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl JsonSchema for LikeItem {
+    #[inline]
+    fn schema_name() -> String {
+        "LikeItem".to_string()
+    }
+
+    #[inline]
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        LikeItemRepr::json_schema(gen)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for LikeItem {
+    #[inline]
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        // the `text` takes precedence, so it is never combined with a document
+        Ok(if u.arbitrary()? {
+            u.arbitrary::<String>()?.into()
+        } else {
+            LikeItem {
+                text: None,
+                index: u.arbitrary()?,
+                id: u.arbitrary()?,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn can_serialize_and_deserialize() {
+        let query = MoreLikeThisQuery {
+            unlike: vec![LikeItem::from("horror")],
+            min_term_freq: Some(1),
+            max_query_terms: Some(12),
+            min_doc_freq: Some(2),
+            analyzer: Some("english".to_string()),
+            ..MoreLikeThisQuery::new(
+                vec!["title", "description"],
+                vec![
+                    LikeItem::from("Once upon a time"),
+                    LikeItem::document("imdb", "1"),
+                ],
+            )
+        };
+        let j = json!({
+            "fields": ["title", "description"],
+            "like": ["Once upon a time", { "_index": "imdb", "_id": "1" }],
+            "unlike": ["horror"],
+            "min_term_freq": 1,
+            "max_query_terms": 12,
+            "min_doc_freq": 2,
+            "analyzer": "english"
+        });
+
+        assert_eq!(serde_json::to_value(&query).unwrap(), j);
+        assert_eq!(
+            serde_json::from_value::<MoreLikeThisQuery>(j).unwrap(),
+            query
+        );
+    }
+
+    #[test]
+    fn can_reference_documents_in_the_searched_index() {
+        let j = json!({ "like": [{ "_id": "1" }] });
+        let query: MoreLikeThisQuery = serde_json::from_value(j.clone()).unwrap();
+
+        assert_eq!(query.like[0].id.as_deref(), Some("1"));
+        assert_eq!(query.like[0].index, None);
+        assert_eq!(serde_json::to_value(&query).unwrap(), j);
+    }
+
+    #[test]
+    fn can_deserialize_single_like_items() {
+        let j = json!({
+            "fields": ["title"],
+            "like": "Once upon a time",
+            "unlike": { "_index": "imdb", "_id": "1" }
+        });
+        let query: MoreLikeThisQuery = serde_json::from_value(j).unwrap();
+
+        assert_eq!(
+            query,
+            MoreLikeThisQuery {
+                unlike: vec![LikeItem::document("imdb", "1")],
+                ..MoreLikeThisQuery::new(vec!["title"], vec!["Once upon a time".into()])
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_invalid_like_items_is_err() {
+        let j = r#"{ "like": [1] }"#;
+        assert!(
+            serde_json::from_str::<MoreLikeThisQuery>(j).is_err(),
+            "{}",
+            &j
+        );
+
+        let j = r#"{ "like": [{ "_id": 1 }] }"#;
+        assert!(
+            serde_json::from_str::<MoreLikeThisQuery>(j).is_err(),
+            "{}",
+            &j
+        );
+
+        let j = r#"{ "like": [{ "_index": "imdb", "doc": { "title": "Once upon a time" } }] }"#;
+        assert!(
+            serde_json::from_str::<MoreLikeThisQuery>(j).is_err(),
+            "{}",
+            &j
+        );
+
+        let j = r#"{ "like": { "doc": { "title": "Once upon a time" } } }"#;
+        assert!(
+            serde_json::from_str::<MoreLikeThisQuery>(j).is_err(),
+            "{}",
+            &j
+        );
+    }
+}
@@ -0,0 +1,203 @@
+//! [Multi-match query](https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-multi-match-query.html)
+
+use elastiql_derive::input_twin;
+use serde::{Deserialize, Serialize};
+
+use super::QueryStringBooleanOperator;
+
+/// A [Multi-match query] builds on the [`match` query] to allow queries over
+/// multiple fields, combining the matches of each field as its `type`
+/// describes.
+///
+/// [Multi-match query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-multi-match-query.html
+/// [`match` query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-match-query.html
+#[input_twin(name = "MultiMatchFilterInput")]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("MultiMatchFilter"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct MultiMatchQuery {
+    /// The text you wish to find in the provided `fields`.
+    pub query: String,
+
+    /// The name of the fields to query. (Defaults to the
+    /// `index.query.default_field` index setting.)
+    ///
+    /// Accepts wildcard expressions, e.g. `*_name`. You also can boost
+    /// relevance scores for matches to particular fields using a caret (`^`)
+    /// notation, e.g. `subject^3`; see [`BoostedField`](super::BoostedField)
+    /// to build or parse them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[input(graphql(default))]
+    pub fields: Vec<String>,
+
+    /// How the matches of each field are combined into a score. (Defaults to
+    /// `BEST_FIELDS`.)
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    // TODO: rename to `ty` once https://github.com/async-graphql/async-graphql/issues/164
+    pub type_: Option<MultiMatchType>,
+
+    /// Boolean logic used to combine the terms of the analyzed `query`.
+    /// (Defaults to `OR`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub operator: Option<QueryStringBooleanOperator>,
+
+    /// The factor, between `0.0` and `1.0`, the scores of the other matching
+    /// fields are multiplied by before being added to the score of the best
+    /// field. (Defaults to `0.0`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::option_float))]
+    pub tie_breaker: Option<f64>,
+
+    /// Maximum edit distance allowed for matching, e.g. `AUTO`. See
+    /// [Fuzziness] for valid values and more information. Not supported by
+    /// the `CROSS_FIELDS`, `PHRASE` and `PHRASE_PREFIX` types.
+    ///
+    /// [Fuzziness]: https://www.elastic.co/guide/en/elasticsearch/reference/current/common-options.html#fuzziness
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub fuzziness: Option<String>,
+
+    /// Minimum number of clauses that must match for a document to be
+    /// returned. See the [`minimum_should_match` parameter] for valid values
+    /// and more information.
+    ///
+    /// [`minimum_should_match` parameter]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-minimum-should-match.html
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub minimum_should_match: Option<String>,
+}
+
+impl MultiMatchQuery {
+    /// Constructs a new `MultiMatchQuery`.
+    #[inline]
+    pub fn new<I, T>(fields: I, query: impl Into<String>) -> MultiMatchQuery
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        MultiMatchQuery {
+            query: query.into(),
+            fields: fields.into_iter().map(|f| f.into()).collect(),
+            type_: None,
+            operator: None,
+            tie_breaker: None,
+            fuzziness: None,
+            minimum_should_match: None,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl MultiMatchQueryInput {
+    /// Constructs a new `MultiMatchQueryInput`.
+    #[inline]
+    pub fn new<I, T>(fields: I, query: impl Into<String>) -> MultiMatchQueryInput
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        MultiMatchQueryInput {
+            query: query.into(),
+            fields: fields.into_iter().map(|f| f.into()).collect(),
+            type_: None,
+            operator: None,
+            tie_breaker: None,
+            fuzziness: None,
+            minimum_should_match: None,
+        }
+    }
+}
+
+/// How a [`MultiMatchQuery`] is executed and scored, see [Types of
+/// `multi_match` query].
+///
+/// [Types of `multi_match` query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-multi-match-query.html#multi-match-types
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("MultiMatchType"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum MultiMatchType {
+    /// Finds documents which match any field, but uses the score of the best
+    /// field.
+    BestFields,
+
+    /// Finds documents which match any field and combines the score of each
+    /// field.
+    MostFields,
+
+    /// Treats fields with the same analyzer as though they were one big field
+    /// and looks for each term in any field.
+    CrossFields,
+
+    /// Runs a `match_phrase` query on each field and uses the score of the
+    /// best field.
+    Phrase,
+
+    /// Runs a `match_phrase_prefix` query on each field and uses the score of
+    /// the best field.
+    PhrasePrefix,
+
+    /// Creates a `match_bool_prefix` query on each field and combines the
+    /// score of each field.
+    BoolPrefix,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn can_serialize_and_deserialize() {
+        let query = MultiMatchQuery {
+            type_: Some(MultiMatchType::BestFields),
+            operator: Some(QueryStringBooleanOperator::And),
+            tie_breaker: Some(0.3),
+            fuzziness: Some("AUTO".to_string()),
+            minimum_should_match: Some("75%".to_string()),
+            ..MultiMatchQuery::new(vec!["subject^3", "message"], "this is a test")
+        };
+        let j = json!({
+            "query": "this is a test",
+            "fields": ["subject^3", "message"],
+            "type": "best_fields",
+            "operator": "AND",
+            "tie_breaker": 0.3,
+            "fuzziness": "AUTO",
+            "minimum_should_match": "75%"
+        });
+
+        assert_eq!(serde_json::to_value(&query).unwrap(), j);
+        assert_eq!(serde_json::from_value::<MultiMatchQuery>(j).unwrap(), query);
+    }
+
+    #[test]
+    fn can_deserialize_lowercase_operators() {
+        let j = json!({ "query": "this is a test", "operator": "and" });
+        let query: MultiMatchQuery = serde_json::from_value(j).unwrap();
+        assert_eq!(query.operator, Some(QueryStringBooleanOperator::And));
+
+        let j = json!({ "query": "this is a test", "operator": "or" });
+        let query: MultiMatchQuery = serde_json::from_value(j).unwrap();
+        assert_eq!(query.operator, Some(QueryStringBooleanOperator::Or));
+    }
+
+    #[test]
+    fn omits_unset_options() {
+        assert_eq!(
+            serde_json::to_value(MultiMatchQuery::new(Vec::<String>::new(), "test")).unwrap(),
+            json!({ "query": "test" })
+        );
+    }
+}
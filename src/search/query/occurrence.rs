@@ -0,0 +1,174 @@
+//! Building `bool` queries from iterators of their clauses.
+//!
+//! ```
+//! use elastiql::search::query::{BooleanQuery, OccurrenceWrapped, Query, TermQuery};
+//!
+//! // plain queries are collected into the `filter` clauses
+//! let query: BooleanQuery = vec!["open", "pending"]
+//!     .into_iter()
+//!     .map(|status| Query::from(TermQuery::new("status", status)))
+//!     .collect();
+//! assert_eq!(query.filter.len(), 2);
+//!
+//! let query: BooleanQuery = vec![
+//!     OccurrenceWrapped::Must(TermQuery::new("status", "open").into()),
+//!     OccurrenceWrapped::MustNot(TermQuery::new("user", "kimchy").into()),
+//! ]
+//! .into_iter()
+//! .collect();
+//! assert_eq!((query.must.len(), query.must_not.len()), (1, 1));
+//! ```
+
+use std::iter::FromIterator;
+
+use super::{BooleanQuery, CompoundQuery, Query};
+
+/// A query wrapped in the clause (occurrence) of a `bool` query it should be
+/// added to, e.g. when [collecting](FromIterator) a [`BooleanQuery`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum OccurrenceWrapped {
+    /// A query that **must** match and contributes to the score.
+    Must(Query),
+
+    /// A query that **must** match, in [filter context].
+    ///
+    /// [filter context]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-filter-context.html
+    Filter(Query),
+
+    /// A query that **should** match.
+    ///
+    /// See [`BooleanQuery::push_should`].
+    Should(Query),
+
+    /// A query that **must not** match.
+    MustNot(Query),
+}
+
+impl BooleanQuery {
+    /// Appends `query` to the clauses it is wrapped in.
+    #[inline]
+    pub fn push_wrapped(&mut self, query: OccurrenceWrapped) {
+        match query {
+            OccurrenceWrapped::Must(query) => self.must.push(query),
+            OccurrenceWrapped::Filter(query) => self.filter.push(query),
+            OccurrenceWrapped::Should(query) => self.should.push(query),
+            OccurrenceWrapped::MustNot(query) => self.must_not.push(query),
+        }
+    }
+}
+
+impl CompoundQuery {
+    /// Appends `query` to the clauses it is wrapped in.
+    ///
+    /// See [`BooleanQuery::push_wrapped`].
+    #[inline]
+    pub fn push_wrapped(&mut self, query: OccurrenceWrapped) {
+        self.boolean_mut().push_wrapped(query)
+    }
+}
+
+impl Extend<Query> for BooleanQuery {
+    /// Appends the queries to the `filter` clauses, like [`BooleanQuery::push`].
+    #[inline]
+    fn extend<I: IntoIterator<Item = Query>>(&mut self, queries: I) {
+        self.filter.extend(queries)
+    }
+}
+
+impl Extend<OccurrenceWrapped> for BooleanQuery {
+    #[inline]
+    fn extend<I: IntoIterator<Item = OccurrenceWrapped>>(&mut self, queries: I) {
+        for query in queries {
+            self.push_wrapped(query);
+        }
+    }
+}
+
+impl Extend<Query> for CompoundQuery {
+    /// Appends the queries to the `filter` clauses, like
+    /// [`CompoundQuery::push`].
+    #[inline]
+    fn extend<I: IntoIterator<Item = Query>>(&mut self, queries: I) {
+        self.boolean_mut().extend(queries)
+    }
+}
+
+impl Extend<OccurrenceWrapped> for CompoundQuery {
+    #[inline]
+    fn extend<I: IntoIterator<Item = OccurrenceWrapped>>(&mut self, queries: I) {
+        self.boolean_mut().extend(queries)
+    }
+}
+
+impl FromIterator<Query> for BooleanQuery {
+    /// Collects the queries into the `filter` clauses.
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = Query>>(queries: I) -> Self {
+        BooleanQuery {
+            filter: queries.into_iter().collect(),
+            ..Default::default()
+        }
+    }
+}
+
+impl FromIterator<OccurrenceWrapped> for BooleanQuery {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = OccurrenceWrapped>>(queries: I) -> Self {
+        let mut query = BooleanQuery::default();
+        query.extend(queries);
+        query
+    }
+}
+
+impl FromIterator<Query> for CompoundQuery {
+    /// Collects the queries into the `filter` clauses of the `bool` query.
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = Query>>(queries: I) -> Self {
+        CompoundQuery {
+            boolean: Some(queries.into_iter().collect()),
+            root: None,
+        }
+    }
+}
+
+impl FromIterator<OccurrenceWrapped> for CompoundQuery {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = OccurrenceWrapped>>(queries: I) -> Self {
+        CompoundQuery {
+            boolean: Some(queries.into_iter().collect()),
+            root: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::search::query::{ExistsQuery, TermQuery};
+
+    #[test]
+    fn can_collect_and_extend_compound_queries() {
+        let mut query: CompoundQuery = vec![TermQuery::new("status", "open")]
+            .into_iter()
+            .map(Query::from)
+            .collect();
+        query.extend(vec![
+            OccurrenceWrapped::Should(TermQuery::new("user", "kimchy").into()),
+            OccurrenceWrapped::MustNot(ExistsQuery::new("deleted").into()),
+        ]);
+
+        assert_eq!(
+            serde_json::to_value(&query).unwrap(),
+            json!({
+                "bool": {
+                    "filter": [{ "term": { "status": { "value": "open" } } }],
+                    "should": [{ "term": { "user": { "value": "kimchy" } } }],
+                    "must_not": [{ "exists": { "field": "deleted" } }]
+                }
+            })
+        );
+    }
+}
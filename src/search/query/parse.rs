@@ -0,0 +1,1205 @@
+//! A parser for the Lucene [query string syntax], turning e.g. user supplied
+//! search strings into a structured [`Query`] tree that can be validated,
+//! visited and transformed like any other query.
+//!
+//! [query string syntax]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-query-string-query.html#query-string-syntax
+
+use std::{error::Error, fmt, str::FromStr};
+
+use super::{
+    BooleanQuery, ExistsQuery, MatchPhraseQuery, MatchQuery, PrefixQuery, Query,
+    QueryStringBooleanOperator, QueryStringQuery, RangeQuery, RegexpQuery, WildcardQuery,
+};
+
+/// Options for [`parse`]ing a query string.
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct ParseOptions {
+    /// The field to query for terms without an explicit field, e.g. `open` as
+    /// opposed to `status:open`. Such terms are an error if this is `None`.
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub default_field: Option<String>,
+
+    /// How to combine terms without an explicit operator, e.g. `open closed`.
+    /// (Defaults to `OR`.)
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub default_operator: QueryStringBooleanOperator,
+}
+
+/// The maximum number of nested groups in a query string, e.g. `((open))`
+/// nests 2 groups, which keeps the recursive parser from overflowing the
+/// stack.
+pub const MAX_GROUP_DEPTH: usize = 64;
+
+/// The reason a query string failed to [`parse`].
+///
+/// Positions are byte offsets into the query string.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ParseError {
+    /// The query string ended before the query was complete.
+    UnexpectedEnd,
+    /// The query string contains a token where it isn't allowed.
+    Unexpected {
+        /// The position of the token.
+        position: usize,
+        /// The unexpected token.
+        found: String,
+    },
+    /// The query string contains a term without a field and there is no
+    /// default field.
+    MissingField {
+        /// The position of the term.
+        position: usize,
+    },
+    /// The query string uses syntax that the parser doesn't support, e.g.
+    /// wildcards in field names or fuzzy queries.
+    Unsupported {
+        /// The position of the syntax.
+        position: usize,
+        /// The kind of syntax that is unsupported.
+        syntax: &'static str,
+    },
+    /// The query string nests groups more than [`MAX_GROUP_DEPTH`] levels deep.
+    TooDeep {
+        /// The position of the group that is too deep.
+        position: usize,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnexpectedEnd => f.write_str("unexpected end of query string"),
+            ParseError::Unexpected { position, found } => {
+                write!(f, "unexpected `{}` at position {}", found, position)
+            }
+            ParseError::MissingField { position } => write!(
+                f,
+                "term at position {} has no field and there is no default field",
+                position
+            ),
+            ParseError::Unsupported { position, syntax } => {
+                write!(f, "{} at position {} are not supported", syntax, position)
+            }
+            ParseError::TooDeep { position } => write!(
+                f,
+                "group at position {} is nested more than {} levels deep",
+                position, MAX_GROUP_DEPTH
+            ),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses a query string in the Lucene [query string syntax] into a `Query`.
+///
+/// The supported syntax is:
+///
+/// * terms, optionally with a field, e.g. `open` or `status:open`, which
+///   become `match` queries
+/// * phrases, e.g. `message:"quick fox"`, which become `match_phrase` queries,
+///   or `match` queries for single words, e.g. `status:"open"`
+/// * trailing wildcards, e.g. `user:ki*`, which become `prefix` queries, or
+///   `exists` queries for `user:*`
+/// * other wildcards, e.g. `user:k?m*y`, which become `wildcard` queries
+/// * `_exists_:user`, which becomes an `exists` query
+/// * regular expressions, e.g. `user:/k.*y/`, which become `regexp` queries
+/// * ranges, e.g. `age:[18 TO *]`, `created:{now-7d TO now]` or `age:>=18`,
+///   which become `range` queries
+/// * the boolean operators `AND`, `OR` and `NOT` (or `&&`, `||` and `!`) and
+///   the `+` and `-` modifiers, which become `bool` queries
+/// * grouping, e.g. `(open OR closed) AND user:kimchy` or
+///   `status:(open OR closed)`, up to [`MAX_GROUP_DEPTH`] levels deep
+/// * boosts, e.g. `status:open^2`
+///
+/// [query string syntax]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-query-string-query.html#query-string-syntax
+///
+/// ```
+/// use elastiql::search::query::{parse, ParseOptions, Query, QueryKind};
+///
+/// let query = parse("status:open AND created:[now-7d TO now]", &ParseOptions::default())?;
+/// assert_eq!(query.kind(), Some(QueryKind::Boolean));
+///
+/// // or, equivalently
+/// let query: Query = "status:open AND created:[now-7d TO now]".parse()?;
+/// # Ok::<(), elastiql::search::query::ParseError>(())
+/// ```
+#[inline]
+pub fn parse(input: &str, options: &ParseOptions) -> Result<Query, ParseError> {
+    let tokens = Lexer::new(input).tokens()?;
+    Parser::new(tokens, input.len(), options).parse()
+}
+
+impl FromStr for Query {
+    type Err = ParseError;
+
+    /// Parses a query string with the default [`ParseOptions`].
+    ///
+    /// See [`parse`].
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s, &ParseOptions::default())
+    }
+}
+
+impl QueryStringQuery {
+    /// Parses the `query` of this `query_string` query, using its
+    /// `default_field` (or its only field) and `default_operator`.
+    ///
+    /// See [`parse`].
+    #[inline]
+    pub fn parse(&self) -> Result<Query, ParseError> {
+        let default_field = match (&self.default_field, self.fields.as_slice()) {
+            (Some(field), _) | (None, [field]) => Some(field.to_owned()),
+            _ => None,
+        };
+
+        let options = ParseOptions {
+            default_field,
+            default_operator: self.default_operator,
+        };
+        parse(&self.query, &options)
+    }
+}
+
+/// A comparison operator of a one-sided range, e.g. `>=` in `age:>=18`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Comparison {
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+}
+
+/// A token of a query string.
+#[derive(Clone, PartialEq, Debug)]
+enum Token {
+    LeftParen,
+    RightParen,
+    Colon,
+    Plus,
+    Minus,
+    Not,
+    And,
+    Or,
+    Boost(f64),
+    Comparison(Comparison),
+    /// A term with the byte offsets of its unescaped wildcards (`*` and `?`).
+    Term(String, Vec<usize>),
+    Phrase(String),
+    Regexp(String),
+    Range {
+        lower: Option<String>,
+        upper: Option<String>,
+        include_lower: bool,
+        include_upper: bool,
+    },
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::LeftParen => f.write_str("("),
+            Token::RightParen => f.write_str(")"),
+            Token::Colon => f.write_str(":"),
+            Token::Plus => f.write_str("+"),
+            Token::Minus => f.write_str("-"),
+            Token::Not => f.write_str("NOT"),
+            Token::And => f.write_str("AND"),
+            Token::Or => f.write_str("OR"),
+            Token::Boost(boost) => write!(f, "^{}", boost),
+            Token::Comparison(Comparison::Gt) => f.write_str(">"),
+            Token::Comparison(Comparison::Gte) => f.write_str(">="),
+            Token::Comparison(Comparison::Lt) => f.write_str("<"),
+            Token::Comparison(Comparison::Lte) => f.write_str("<="),
+            Token::Term(term, _) => f.write_str(term),
+            Token::Phrase(phrase) => write!(f, "\"{}\"", phrase),
+            Token::Regexp(regexp) => write!(f, "/{}/", regexp),
+            Token::Range { .. } => f.write_str("range"),
+        }
+    }
+}
+
+/// Returns `true` if `c` ends a term (unless escaped).
+#[inline]
+fn is_term_end(c: char) -> bool {
+    c.is_whitespace()
+        || matches!(
+            c,
+            '(' | ')' | ':' | '^' | '~' | '"' | '[' | ']' | '{' | '}' | '/'
+        )
+}
+
+/// Returns the pattern of a `wildcard` query for the (unescaped) `term` with
+/// the unescaped wildcards at the byte offsets `wildcards`, i.e. escaping its
+/// other `*`, `?` and `\` characters.
+fn wildcard_pattern(term: &str, wildcards: &[usize]) -> String {
+    let mut pattern = String::with_capacity(term.len());
+    for (i, c) in term.char_indices() {
+        if matches!(c, '*' | '?' | '\\') && !wildcards.contains(&i) {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    pattern
+}
+
+/// Splits a query string into tokens.
+struct Lexer<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> Lexer<'a> {
+    #[inline]
+    fn new(input: &'a str) -> Self {
+        Lexer { input, position: 0 }
+    }
+
+    #[inline]
+    fn peek(&self) -> Option<char> {
+        self.input[self.position..].chars().next()
+    }
+
+    #[inline]
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += c.len_utf8();
+        Some(c)
+    }
+
+    #[inline]
+    fn eat(&mut self, s: &str) -> bool {
+        if self.input[self.position..].starts_with(s) {
+            self.position += s.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    #[inline]
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(c) if c.is_whitespace()) {
+            self.bump();
+        }
+    }
+
+    /// Returns an error for an unexpected character at `position`.
+    #[inline]
+    fn unexpected(&self, position: usize) -> ParseError {
+        match self.input[position..].chars().next() {
+            Some(c) => ParseError::Unexpected {
+                position,
+                found: c.to_string(),
+            },
+            None => ParseError::UnexpectedEnd,
+        }
+    }
+
+    /// Returns all of the tokens along with their positions.
+    fn tokens(mut self) -> Result<Vec<(usize, Token)>, ParseError> {
+        let mut tokens = vec![];
+
+        loop {
+            self.skip_whitespace();
+
+            let start = self.position;
+            let c = match self.peek() {
+                Some(c) => c,
+                None => return Ok(tokens),
+            };
+
+            let token = match c {
+                '(' | ')' | ':' | '+' | '-' | '!' => {
+                    self.bump();
+                    match c {
+                        '(' => Token::LeftParen,
+                        ')' => Token::RightParen,
+                        ':' => Token::Colon,
+                        '+' => Token::Plus,
+                        '-' => Token::Minus,
+                        _ => Token::Not,
+                    }
+                }
+                '&' if self.eat("&&") => Token::And,
+                '|' if self.eat("||") => Token::Or,
+                '>' | '<' => {
+                    self.bump();
+                    let inclusive = self.eat("=");
+                    Token::Comparison(match (c, inclusive) {
+                        ('>', false) => Comparison::Gt,
+                        ('>', true) => Comparison::Gte,
+                        ('<', false) => Comparison::Lt,
+                        _ => Comparison::Lte,
+                    })
+                }
+                '^' => {
+                    self.bump();
+                    let boost = self.term()?.0;
+                    match boost.parse() {
+                        Ok(boost) => Token::Boost(boost),
+                        Err(_) => return Err(self.unexpected(start + 1)),
+                    }
+                }
+                '~' => {
+                    return Err(ParseError::Unsupported {
+                        position: start,
+                        syntax: "fuzzy and proximity queries",
+                    })
+                }
+                '"' => {
+                    self.bump();
+                    Token::Phrase(self.delimited('"')?)
+                }
+                '/' => {
+                    self.bump();
+                    Token::Regexp(self.delimited('/')?)
+                }
+                '[' | '{' => {
+                    self.bump();
+                    self.range(c == '[')?
+                }
+                ']' | '}' => return Err(self.unexpected(start)),
+                _ => {
+                    let (term, wildcards) = self.term()?;
+                    match term.as_str() {
+                        "AND" if wildcards.is_empty() => Token::And,
+                        "OR" if wildcards.is_empty() => Token::Or,
+                        "NOT" if wildcards.is_empty() => Token::Not,
+                        _ => Token::Term(term, wildcards),
+                    }
+                }
+            };
+
+            tokens.push((start, token));
+        }
+    }
+
+    /// Returns the (unescaped) term at the current position along with the
+    /// byte offsets of its unescaped wildcards.
+    fn term(&mut self) -> Result<(String, Vec<usize>), ParseError> {
+        let start = self.position;
+        let mut term = String::new();
+        let mut wildcards = vec![];
+
+        while let Some(c) = self.peek() {
+            if is_term_end(c) {
+                break;
+            }
+
+            self.bump();
+            match c {
+                '\\' => term.push(self.bump().ok_or(ParseError::UnexpectedEnd)?),
+                '*' | '?' => {
+                    wildcards.push(term.len());
+                    term.push(c);
+                }
+                _ => term.push(c),
+            }
+        }
+
+        if term.is_empty() {
+            return Err(self.unexpected(start));
+        }
+
+        Ok((term, wildcards))
+    }
+
+    /// Returns the (unescaped) text up to the closing `delimiter`.
+    fn delimited(&mut self, delimiter: char) -> Result<String, ParseError> {
+        let mut text = String::new();
+
+        loop {
+            match self.bump().ok_or(ParseError::UnexpectedEnd)? {
+                '\\' => {
+                    let c = self.bump().ok_or(ParseError::UnexpectedEnd)?;
+                    // keep escapes other than the delimiter in regexps, which
+                    // have their own escaping rules
+                    if delimiter == '/' && c != '/' {
+                        text.push('\\');
+                    }
+                    text.push(c);
+                }
+                c if c == delimiter => return Ok(text),
+                c => text.push(c),
+            }
+        }
+    }
+
+    /// Returns the bound of a range, or `None` if it's unbounded (`*`).
+    fn bound(&mut self) -> Result<Option<String>, ParseError> {
+        self.skip_whitespace();
+
+        if self.eat("\"") {
+            return self.delimited('"').map(Some);
+        }
+
+        let start = self.position;
+        let mut bound = String::new();
+        let mut unbounded = false;
+
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() || matches!(c, ']' | '}') {
+                break;
+            }
+
+            self.bump();
+            match c {
+                '\\' => bound.push(self.bump().ok_or(ParseError::UnexpectedEnd)?),
+                '*' if bound.is_empty() => unbounded = true,
+                _ => bound.push(c),
+            }
+        }
+
+        match (unbounded, bound.is_empty()) {
+            (true, true) => Ok(None),
+            (false, false) => Ok(Some(bound)),
+            _ => Err(self.unexpected(start)),
+        }
+    }
+
+    /// Returns the range after its opening bracket, e.g. `18 TO 65]`.
+    fn range(&mut self, include_lower: bool) -> Result<Token, ParseError> {
+        let lower = self.bound()?;
+
+        self.skip_whitespace();
+        let to = self.position;
+        if !self.eat("TO") || !matches!(self.peek(), Some(c) if c.is_whitespace()) {
+            return Err(self.unexpected(to));
+        }
+
+        let upper = self.bound()?;
+
+        self.skip_whitespace();
+        let include_upper = match self.bump() {
+            Some(']') => true,
+            Some('}') => false,
+            Some(_) => return Err(self.unexpected(self.position - 1)),
+            None => return Err(ParseError::UnexpectedEnd),
+        };
+
+        Ok(Token::Range {
+            lower,
+            upper,
+            include_lower,
+            include_upper,
+        })
+    }
+}
+
+/// A modifier of a clause, e.g. `+` in `+status:open`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Modifier {
+    /// The clause is combined using the surrounding operator.
+    None,
+    /// The clause must match (`+`).
+    Required,
+    /// The clause must not match (`-`, `NOT` or `!`).
+    Prohibited,
+}
+
+/// Returns a `bool` query without any clauses.
+#[inline]
+fn boolean() -> BooleanQuery {
+    BooleanQuery {
+        must: vec![],
+        filter: vec![],
+        should: vec![],
+        must_not: vec![],
+        minimum_should_match: None,
+        boost: None,
+    }
+}
+
+/// Combines `clauses` into a single clause, putting clauses without a modifier
+/// in the `must` (if `conjunction`) or `should` clause of a `bool` query.
+fn combine(mut clauses: Vec<(Modifier, Query)>, conjunction: bool) -> (Modifier, Query) {
+    if clauses.len() == 1 {
+        return clauses.remove(0);
+    }
+
+    let mut boolean = boolean();
+    for (modifier, query) in clauses {
+        match modifier {
+            Modifier::None if conjunction => boolean.must.push(query),
+            Modifier::None => boolean.should.push(query),
+            Modifier::Required => boolean.must.push(query),
+            Modifier::Prohibited => boolean.must_not.push(query),
+        }
+    }
+
+    (Modifier::None, boolean.into_query())
+}
+
+/// Returns `query` with the given modifier applied.
+fn resolve((modifier, query): (Modifier, Query)) -> Query {
+    match modifier {
+        Modifier::None | Modifier::Required => query,
+        Modifier::Prohibited => BooleanQuery {
+            must_not: vec![query],
+            ..boolean()
+        }
+        .into_query(),
+    }
+}
+
+/// Returns `query` with the given boost, wrapping it in a `bool` query if it
+/// doesn't support boosting itself.
+fn boost(mut query: Query, boost: f64) -> Query {
+    let slot = if let Some(ref mut term) = query.term {
+        Some(&mut term.boost)
+    } else if let Some(ref mut range) = query.range {
+        Some(&mut range.boost)
+    } else if let Some(ref mut prefix) = query.prefix {
+        Some(&mut prefix.boost)
+    } else if let Some(ref mut knn) = query.knn {
+        Some(&mut knn.boost)
+    } else {
+        query
+            .boolean
+            .as_mut()
+            .map(|boolean| &mut boolean.boost)
+            .filter(|boost| boost.is_none())
+    };
+
+    match slot {
+        Some(slot) => {
+            *slot = Some(boost);
+            query
+        }
+        None => BooleanQuery {
+            must: vec![query],
+            boost: Some(boost),
+            ..boolean()
+        }
+        .into_query(),
+    }
+}
+
+/// Parses tokens into a `Query`.
+struct Parser<'a> {
+    tokens: std::iter::Peekable<std::vec::IntoIter<(usize, Token)>>,
+    len: usize,
+    options: &'a ParseOptions,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    #[inline]
+    fn new(tokens: Vec<(usize, Token)>, len: usize, options: &'a ParseOptions) -> Self {
+        Parser {
+            tokens: tokens.into_iter().peekable(),
+            len,
+            options,
+            depth: 0,
+        }
+    }
+
+    #[inline]
+    fn peek(&mut self) -> Option<&Token> {
+        self.tokens.peek().map(|(_, token)| token)
+    }
+
+    #[inline]
+    fn next(&mut self) -> Result<(usize, Token), ParseError> {
+        self.tokens.next().ok_or(ParseError::UnexpectedEnd)
+    }
+
+    #[inline]
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.tokens.next();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns `true` if the next token starts a clause.
+    #[inline]
+    fn at_clause(&mut self) -> bool {
+        matches!(
+            self.peek(),
+            Some(Token::LeftParen)
+                | Some(Token::Plus)
+                | Some(Token::Minus)
+                | Some(Token::Not)
+                | Some(Token::Comparison(_))
+                | Some(Token::Term(..))
+                | Some(Token::Phrase(_))
+                | Some(Token::Regexp(_))
+                | Some(Token::Range { .. })
+        )
+    }
+
+    fn parse(mut self) -> Result<Query, ParseError> {
+        let field = self.options.default_field.clone();
+        let query = self.disjunction(field.as_deref())?;
+
+        match self.tokens.next() {
+            Some((position, token)) => Err(ParseError::Unexpected {
+                position,
+                found: token.to_string(),
+            }),
+            None if self.len == 0 => Err(ParseError::UnexpectedEnd),
+            None => Ok(resolve(query)),
+        }
+    }
+
+    /// Parses clauses separated by `OR` (or by nothing if that is the default
+    /// operator).
+    fn disjunction(&mut self, field: Option<&str>) -> Result<(Modifier, Query), ParseError> {
+        let implicit = self.options.default_operator == QueryStringBooleanOperator::Or;
+
+        let mut clauses = vec![self.conjunction(field)?];
+        while self.eat(&Token::Or) || (implicit && self.at_clause()) {
+            clauses.push(self.conjunction(field)?);
+        }
+
+        Ok(combine(clauses, false))
+    }
+
+    /// Parses clauses separated by `AND` (or by nothing if that is the default
+    /// operator).
+    fn conjunction(&mut self, field: Option<&str>) -> Result<(Modifier, Query), ParseError> {
+        let implicit = self.options.default_operator == QueryStringBooleanOperator::And;
+
+        let mut clauses = vec![self.clause(field)?];
+        while self.eat(&Token::And) || (implicit && self.at_clause()) {
+            clauses.push(self.clause(field)?);
+        }
+
+        Ok(combine(clauses, true))
+    }
+
+    /// Parses a single, optionally modified and boosted, clause.
+    fn clause(&mut self, field: Option<&str>) -> Result<(Modifier, Query), ParseError> {
+        let modifier = if self.eat(&Token::Plus) {
+            Modifier::Required
+        } else if self.eat(&Token::Minus) || self.eat(&Token::Not) {
+            Modifier::Prohibited
+        } else {
+            Modifier::None
+        };
+
+        let mut query = self.primary(field)?;
+        if let Some(&Token::Boost(factor)) = self.peek() {
+            self.tokens.next();
+            query = boost(query, factor);
+        }
+
+        Ok((modifier, query))
+    }
+
+    /// Parses a group, or a term with an optional field.
+    fn primary(&mut self, field: Option<&str>) -> Result<Query, ParseError> {
+        let (position, token) = self.next()?;
+
+        match token {
+            Token::LeftParen => self.group(field, position),
+            Token::Term(name, wildcards) if self.peek() == Some(&Token::Colon) => {
+                self.tokens.next();
+
+                if !wildcards.is_empty() {
+                    return Err(ParseError::Unsupported {
+                        position,
+                        syntax: "wildcard fields",
+                    });
+                }
+
+                if let Some((position, Token::LeftParen)) = self.tokens.peek() {
+                    let position = *position;
+                    self.tokens.next();
+                    return self.group(Some(&name), position);
+                }
+
+                let (position, token) = self.next()?;
+                match token {
+                    Token::Term(exists, wildcards)
+                        if name == "_exists_" && wildcards.is_empty() =>
+                    {
+                        Ok(ExistsQuery::new(exists).into())
+                    }
+                    token => self.value(&name, position, token),
+                }
+            }
+            token => match field {
+                Some(field) => self.value(field, position, token),
+                None => match token {
+                    Token::Term(..)
+                    | Token::Phrase(_)
+                    | Token::Regexp(_)
+                    | Token::Range { .. }
+                    | Token::Comparison(_) => Err(ParseError::MissingField { position }),
+                    token => Err(ParseError::Unexpected {
+                        position,
+                        found: token.to_string(),
+                    }),
+                },
+            },
+        }
+    }
+
+    /// Parses the rest of a parenthesized group that starts at `position`.
+    fn group(&mut self, field: Option<&str>, position: usize) -> Result<Query, ParseError> {
+        if self.depth == MAX_GROUP_DEPTH {
+            return Err(ParseError::TooDeep { position });
+        }
+
+        self.depth += 1;
+        let query = resolve(self.disjunction(field)?);
+        self.depth -= 1;
+
+        match self.tokens.next() {
+            Some((_, Token::RightParen)) => Ok(query),
+            Some((position, token)) => Err(ParseError::Unexpected {
+                position,
+                found: token.to_string(),
+            }),
+            None => Err(ParseError::UnexpectedEnd),
+        }
+    }
+
+    /// Returns the query for the value `token` of `field`.
+    fn value(&mut self, field: &str, position: usize, token: Token) -> Result<Query, ParseError> {
+        let query = match token {
+            Token::Term(term, wildcards) => match wildcards.as_slice() {
+                [] => MatchQuery::new(field, term).into(),
+                [0] if term == "*" => ExistsQuery::new(field).into(),
+                [last] if *last == term.len() - 1 && term.ends_with('*') => {
+                    PrefixQuery::new(field, &term[..*last]).into()
+                }
+                wildcards => WildcardQuery::new(field, wildcard_pattern(&term, wildcards)).into(),
+            },
+            Token::Phrase(phrase) if phrase.split_whitespace().count() > 1 => {
+                MatchPhraseQuery::new(field, phrase).into()
+            }
+            Token::Phrase(phrase) => MatchQuery::new(field, phrase).into(),
+            Token::Regexp(regexp) => RegexpQuery::new(field, regexp, None::<String>).into(),
+            Token::Range {
+                lower,
+                upper,
+                include_lower,
+                include_upper,
+            } => {
+                let mut range = RangeQuery::new(field);
+                match lower {
+                    Some(lower) if include_lower => range.greater_than_or_equal_to = Some(lower),
+                    lower => range.greater_than = lower,
+                }
+                match upper {
+                    Some(upper) if include_upper => range.less_than_or_equal_to = Some(upper),
+                    upper => range.less_than = upper,
+                }
+                range.into()
+            }
+            Token::Comparison(comparison) => {
+                let value = match self.next()? {
+                    (_, Token::Term(value, wildcards)) if wildcards.is_empty() => value,
+                    (_, Token::Phrase(value)) => value,
+                    (position, token) => {
+                        return Err(ParseError::Unexpected {
+                            position,
+                            found: token.to_string(),
+                        })
+                    }
+                };
+
+                let range = RangeQuery::new(field);
+                match comparison {
+                    Comparison::Gt => range.gt(value),
+                    Comparison::Gte => range.gte(value),
+                    Comparison::Lt => range.lt(value),
+                    Comparison::Lte => range.lte(value),
+                }
+                .into()
+            }
+            token => {
+                return Err(ParseError::Unexpected {
+                    position,
+                    found: token.to_string(),
+                })
+            }
+        };
+
+        Ok(query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    macro_rules! test_case {
+        ($name:ident : $s:expr, $j:expr) => {
+            #[test]
+            fn $name() {
+                let options = ParseOptions {
+                    default_field: Some("message".to_string()),
+                    default_operator: QueryStringBooleanOperator::Or,
+                };
+                let query = parse($s, &options).unwrap();
+                assert_eq!(serde_json::to_value(&query).unwrap(), $j);
+            }
+        };
+    }
+
+    test_case!(
+        term:
+        "status:open",
+        json!({ "match": { "status": { "query": "open" } } })
+    );
+
+    test_case!(
+        default_field:
+        "open",
+        json!({ "match": { "message": { "query": "open" } } })
+    );
+
+    test_case!(
+        escaped_term:
+        r"url:http\:\/\/example.com",
+        json!({ "match": { "url": { "query": "http://example.com" } } })
+    );
+
+    test_case!(
+        single_word_phrase:
+        r#"status:"open""#,
+        json!({ "match": { "status": { "query": "open" } } })
+    );
+
+    test_case!(
+        phrase:
+        r#"message:"quick fox""#,
+        json!({ "match_phrase": { "message": { "query": "quick fox" } } })
+    );
+
+    test_case!(
+        prefix:
+        "user:ki*",
+        json!({ "prefix": { "user": { "value": "ki", "case_insensitive": false } } })
+    );
+
+    test_case!(
+        wildcard:
+        "user:k?m*y",
+        json!({ "wildcard": { "user": { "value": "k?m*y", "case_insensitive": false } } })
+    );
+
+    test_case!(
+        leading_wildcard:
+        r"user:?imch\*y",
+        json!({ "wildcard": { "user": { "value": r"?imch\*y", "case_insensitive": false } } })
+    );
+
+    test_case!(
+        exists:
+        "_exists_:user",
+        json!({ "exists": { "field": "user" } })
+    );
+
+    test_case!(
+        exists_wildcard:
+        "user:*",
+        json!({ "exists": { "field": "user" } })
+    );
+
+    test_case!(
+        regexp:
+        r"user:/k.*y\/\d/",
+        json!({ "regexp": { "user": { "value": r"k.*y/\d" } } })
+    );
+
+    test_case!(
+        inclusive_range:
+        "created:[now-7d TO now]",
+        json!({ "range": { "created": { "gte": "now-7d", "lte": "now" } } })
+    );
+
+    test_case!(
+        mixed_range:
+        "age:{18 TO *]",
+        json!({ "range": { "age": { "gt": "18" } } })
+    );
+
+    test_case!(
+        quoted_range:
+        r#"created:["2020-01-01 00:00" TO "2021-01-01 00:00"}"#,
+        json!({ "range": { "created": { "gte": "2020-01-01 00:00", "lt": "2021-01-01 00:00" } } })
+    );
+
+    test_case!(
+        comparison:
+        "age:>=18",
+        json!({ "range": { "age": { "gte": "18" } } })
+    );
+
+    test_case!(
+        and:
+        "status:open AND created:[now-7d TO now]",
+        json!({
+            "bool": {
+                "must": [
+                    { "match": { "status": { "query": "open" } } },
+                    { "range": { "created": { "gte": "now-7d", "lte": "now" } } }
+                ]
+            }
+        })
+    );
+
+    test_case!(
+        or_binds_looser_than_and:
+        "a OR b && c",
+        json!({
+            "bool": {
+                "should": [
+                    { "match": { "message": { "query": "a" } } },
+                    {
+                        "bool": {
+                            "must": [
+                                { "match": { "message": { "query": "b" } } },
+                                { "match": { "message": { "query": "c" } } }
+                            ]
+                        }
+                    }
+                ]
+            }
+        })
+    );
+
+    test_case!(
+        implicit_or:
+        "a b",
+        json!({
+            "bool": {
+                "should": [
+                    { "match": { "message": { "query": "a" } } },
+                    { "match": { "message": { "query": "b" } } }
+                ]
+            }
+        })
+    );
+
+    test_case!(
+        modifiers:
+        "+a -b c",
+        json!({
+            "bool": {
+                "must": [{ "match": { "message": { "query": "a" } } }],
+                "should": [{ "match": { "message": { "query": "c" } } }],
+                "must_not": [{ "match": { "message": { "query": "b" } } }]
+            }
+        })
+    );
+
+    test_case!(
+        not:
+        "NOT status:closed",
+        json!({ "bool": { "must_not": [{ "match": { "status": { "query": "closed" } } }] } })
+    );
+
+    test_case!(
+        and_not:
+        "a AND !b",
+        json!({
+            "bool": {
+                "must": [{ "match": { "message": { "query": "a" } } }],
+                "must_not": [{ "match": { "message": { "query": "b" } } }]
+            }
+        })
+    );
+
+    test_case!(
+        group:
+        "(a OR b) AND user:kimchy",
+        json!({
+            "bool": {
+                "must": [
+                    {
+                        "bool": {
+                            "should": [
+                                { "match": { "message": { "query": "a" } } },
+                                { "match": { "message": { "query": "b" } } }
+                            ]
+                        }
+                    },
+                    { "match": { "user": { "query": "kimchy" } } }
+                ]
+            }
+        })
+    );
+
+    test_case!(
+        field_group:
+        "status:(open OR closed)",
+        json!({
+            "bool": {
+                "should": [
+                    { "match": { "status": { "query": "open" } } },
+                    { "match": { "status": { "query": "closed" } } }
+                ]
+            }
+        })
+    );
+
+    test_case!(
+        boost:
+        "created:[now-7d TO now]^2 status:open^1.5",
+        json!({
+            "bool": {
+                "should": [
+                    { "range": { "created": { "gte": "now-7d", "lte": "now", "boost": 2.0 } } },
+                    {
+                        "bool": {
+                            "must": [{ "match": { "status": { "query": "open" } } }],
+                            "boost": 1.5
+                        }
+                    }
+                ]
+            }
+        })
+    );
+
+    test_case!(
+        boosted_prefix:
+        "user:ki*^2",
+        json!({ "prefix": { "user": { "value": "ki", "case_insensitive": false, "boost": 2.0 } } })
+    );
+
+    #[test]
+    fn can_use_default_operator_and() {
+        let options = ParseOptions {
+            default_field: Some("message".to_string()),
+            default_operator: QueryStringBooleanOperator::And,
+        };
+
+        let query = parse("a b OR c", &options).unwrap();
+        assert_eq!(
+            serde_json::to_value(&query).unwrap(),
+            json!({
+                "bool": {
+                    "should": [
+                        {
+                            "bool": {
+                                "must": [
+                                    { "match": { "message": { "query": "a" } } },
+                                    { "match": { "message": { "query": "b" } } }
+                                ]
+                            }
+                        },
+                        { "match": { "message": { "query": "c" } } }
+                    ]
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn can_parse_query_string_queries() {
+        let query: QueryStringQuery = serde_json::from_value(json!({
+            "query": "open closed",
+            "default_field": "status",
+            "default_operator": "AND"
+        }))
+        .unwrap();
+
+        assert_eq!(
+            query.parse().unwrap(),
+            "status:open AND status:closed".parse().unwrap()
+        );
+    }
+
+    #[test]
+    fn parsed_queries_can_be_transformed() {
+        let mut query: Query = "status:open AND (user:ki* OR age:>18)".parse().unwrap();
+        query.map_fields(|field| format!("doc.{}", field));
+
+        assert_eq!(
+            query,
+            "doc.status:open AND (doc.user:ki* OR doc.age:>18)"
+                .parse()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn invalid_syntax_is_err() {
+        let options = ParseOptions::default();
+
+        for (s, err) in &[
+            ("", ParseError::UnexpectedEnd),
+            ("status:", ParseError::UnexpectedEnd),
+            ("(status:open", ParseError::UnexpectedEnd),
+            ("status:[1 TO 2", ParseError::UnexpectedEnd),
+            (r#"status:"open"#, ParseError::UnexpectedEnd),
+            ("open", ParseError::MissingField { position: 0 }),
+            ("a:b OR c", ParseError::MissingField { position: 7 }),
+            (
+                "status:open)",
+                ParseError::Unexpected {
+                    position: 11,
+                    found: ")".to_string(),
+                },
+            ),
+            ("status:open AND", ParseError::UnexpectedEnd),
+            (
+                "status:[1 2]",
+                ParseError::Unexpected {
+                    position: 10,
+                    found: "2".to_string(),
+                },
+            ),
+            (
+                "status:open^x",
+                ParseError::Unexpected {
+                    position: 12,
+                    found: "x".to_string(),
+                },
+            ),
+        ] {
+            assert_eq!(parse(s, &options).as_ref(), Err(err), "{}", s);
+        }
+    }
+
+    #[test]
+    fn deeply_nested_groups_are_err() {
+        let options = ParseOptions::default();
+
+        let nested = |depth| format!("{}status:open{}", "(".repeat(depth), ")".repeat(depth));
+        assert!(parse(&nested(MAX_GROUP_DEPTH), &options).is_ok());
+        assert_eq!(
+            parse(&nested(MAX_GROUP_DEPTH + 1), &options),
+            Err(ParseError::TooDeep {
+                position: MAX_GROUP_DEPTH
+            })
+        );
+        assert_eq!(
+            parse(&nested(10_000), &options),
+            Err(ParseError::TooDeep {
+                position: MAX_GROUP_DEPTH
+            })
+        );
+    }
+
+    #[test]
+    fn unsupported_syntax_is_err() {
+        let options = ParseOptions::default();
+
+        for (s, syntax) in &[
+            ("user.*:kimchy", "wildcard fields"),
+            ("user:kimchy~", "fuzzy and proximity queries"),
+        ] {
+            match parse(s, &options) {
+                Err(ParseError::Unsupported { syntax: actual, .. }) => {
+                    assert_eq!(&actual, syntax, "{}", s)
+                }
+                result => panic!("{}: {:?}", s, result),
+            }
+        }
+    }
+}
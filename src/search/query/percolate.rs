@@ -0,0 +1,167 @@
+//! [Percolate query](https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-percolate-query.html)
+
+use elastiql_derive::input_twin;
+use serde::{Deserialize, Serialize};
+
+use crate::scalars::Map;
+
+/// A [Percolate query] returns the queries stored in a `percolator` field that
+/// match the provided document(s), i.e. it searches "in reverse".
+///
+/// **Note**: until GraphQL [Union input types] are supported either the
+/// `document`, the `documents` or the `index` and `id` of a stored document
+/// may be specified.
+///
+/// [Percolate query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-percolate-query.html
+/// [Union input types]: https://github.com/graphql/graphql-spec/blob/master/rfcs/InputUnion.md
+#[input_twin(name = "PercolateFilterInput")]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("PercolateFilter"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct PercolateQuery {
+    /// The name of the `percolator` field holding the stored queries.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::field))]
+    pub field: String,
+
+    /// The source of the document to match.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<crate::schema::Map>"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::option_map))]
+    pub document: Option<Map>,
+
+    /// The sources of the documents to match, any of which a stored query has
+    /// to match.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[cfg_attr(feature = "schemars", schemars(with = "Vec<crate::schema::Map>"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::maps))]
+    #[input(graphql(default))]
+    pub documents: Vec<Map>,
+
+    /// The index of the already indexed document to match.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub index: Option<String>,
+
+    /// The ID of the already indexed document to match.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub id: Option<String>,
+
+    /// The routing used to fetch the already indexed document.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub routing: Option<String>,
+
+    /// The preference used to fetch the already indexed document.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub preference: Option<String>,
+
+    /// The expected version of the already indexed document.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub version: Option<u64>,
+}
+
+impl PercolateQuery {
+    /// Constructs a new `PercolateQuery` for the queries stored in `field`
+    /// matching `document`.
+    #[inline]
+    pub fn new(field: impl Into<String>, document: Map) -> PercolateQuery {
+        PercolateQuery {
+            document: Some(document),
+            ..PercolateQuery::empty(field.into())
+        }
+    }
+
+    /// Constructs a new `PercolateQuery` for the queries stored in `field`
+    /// matching the already indexed document with `id` in `index`.
+    #[inline]
+    pub fn indexed(
+        field: impl Into<String>,
+        index: impl Into<String>,
+        id: impl Into<String>,
+    ) -> PercolateQuery {
+        PercolateQuery {
+            index: Some(index.into()),
+            id: Some(id.into()),
+            ..PercolateQuery::empty(field.into())
+        }
+    }
+
+    /// Returns a `PercolateQuery` on `field` without any documents.
+    #[inline]
+    fn empty(field: String) -> PercolateQuery {
+        PercolateQuery {
+            field,
+            document: None,
+            documents: vec![],
+            index: None,
+            id: None,
+            routing: None,
+            preference: None,
+            version: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    /// Returns `json` as a [`Map`].
+    fn map(json: serde_json::Value) -> Map {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn can_serialize_and_deserialize_documents() {
+        let query = PercolateQuery::new("query", map(json!({ "message": "bonsai tree" })));
+        let j = json!({ "field": "query", "document": { "message": "bonsai tree" } });
+
+        assert_eq!(serde_json::to_value(&query).unwrap(), j);
+        assert_eq!(serde_json::from_value::<PercolateQuery>(j).unwrap(), query);
+
+        let query = PercolateQuery {
+            documents: vec![
+                map(json!({ "message": "bonsai tree" })),
+                map(json!({ "message": "new tree" })),
+            ],
+            ..PercolateQuery::empty("query".to_string())
+        };
+        let j = json!({
+            "field": "query",
+            "documents": [{ "message": "bonsai tree" }, { "message": "new tree" }]
+        });
+
+        assert_eq!(serde_json::to_value(&query).unwrap(), j);
+        assert_eq!(serde_json::from_value::<PercolateQuery>(j).unwrap(), query);
+    }
+
+    #[test]
+    fn can_serialize_and_deserialize_indexed_documents() {
+        let query = PercolateQuery {
+            routing: Some("user1".to_string()),
+            version: Some(2),
+            ..PercolateQuery::indexed("query", "my-index-000001", "2")
+        };
+        let j = json!({
+            "field": "query",
+            "index": "my-index-000001",
+            "id": "2",
+            "routing": "user1",
+            "version": 2
+        });
+
+        assert_eq!(serde_json::to_value(&query).unwrap(), j);
+        assert_eq!(serde_json::from_value::<PercolateQuery>(j).unwrap(), query);
+    }
+}
@@ -2,106 +2,74 @@
 
 use std::fmt;
 
+use elastiql_derive::{input_twin, twin_impl};
+#[cfg(feature = "schemars")]
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
 use serde::de::{self, MapAccess, Visitor};
 use serde::ser::{SerializeMap, Serializer};
 use serde::{Deserialize, Serialize};
 
 #[allow(clippy::missing_docs_in_private_items)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[derive(Serialize, Deserialize)]
 struct InnerPrefixQuery {
     value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rewrite: Option<String>,
     #[serde(default)]
     case_insensitive: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    boost: Option<f64>,
 }
 
-/// A [Prefix query] returns documents that contain a specific prefix in a
-/// provided field.
-///
-/// [Prefix query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-prefix-query.html
-#[cfg(feature = "graphql")]
-#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(async_graphql::InputObject, Clone, Debug)]
-#[graphql(name = "PrefixFilterInput")]
-#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
-pub struct PrefixQueryInput {
-    /// The name of the field to query.
-    pub field: String,
-
-    /// Beginning characters of terms you wish to find in the provided `field`.
-    pub value: String,
-
-    /// Allows ASCII case insensitive matching of the value with the indexed
-    /// field values when set to true. Default is false which means the case
-    /// sensitivity of matching depends on the underlying field’s mapping.
-    #[graphql(default)]
-    #[cfg_attr(feature = "builder", builder(default))]
-    pub case_insensitive: bool,
-}
-
-#[cfg(feature = "graphql")]
-impl PrefixQueryInput {
-    /// Constructs a new `PrefixQueryInput`.
-    #[inline]
-    pub fn new(field: impl Into<String>, value: impl Into<String>) -> Self {
-        PrefixQueryInput {
-            field: field.into(),
-            value: value.into(),
-            case_insensitive: false,
-        }
-    }
-}
-
-#[cfg(feature = "graphql")]
-impl From<PrefixQuery> for PrefixQueryInput {
-    #[inline]
-    fn from(query: PrefixQuery) -> Self {
-        Self {
-            field: query.field,
-            value: query.value,
-            case_insensitive: query.case_insensitive,
-        }
-    }
-}
-
-#[cfg(feature = "graphql")]
-impl Serialize for PrefixQueryInput {
-    #[inline]
-    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        let mut map = serializer.serialize_map(Some(1))?;
-
-        let inner = InnerPrefixQuery {
-            value: self.value.to_owned(),
-            case_insensitive: self.case_insensitive,
-        };
-
-        map.serialize_entry(&self.field, &inner)?;
-
-        map.end()
-    }
+/// The body of a `PrefixQuery`, which Elasticsearch also accepts as just the
+/// prefix itself, e.g. `{ "user": "ki" }`.
+#[allow(clippy::missing_docs_in_private_items)]
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PrefixQueryBody {
+    Short(String),
+    Long(InnerPrefixQuery),
 }
 
 /// A [Prefix query] returns documents that contain a specific prefix in a
 /// provided field.
 ///
 /// [Prefix query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-prefix-query.html
-#[cfg_attr(test, derive(PartialEq))]
+#[input_twin(name = "PrefixFilterInput")]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("PrefixFilter"))]
 #[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
-#[cfg_attr(feature = "graphql", graphql(name = "PrefixFilter"))]
 #[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(Clone, Debug)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
 pub struct PrefixQuery {
     /// The name of the field to query.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::field))]
     pub field: String,
 
     /// Beginning characters of terms you wish to find in the provided `field`.
     pub value: String,
 
+    /// Method used to rewrite the query. For valid values and more
+    /// information, see the [`rewrite` parameter].
+    ///
+    /// [`rewrite` parameter]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-multi-term-rewrite.html
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub rewrite: Option<String>,
+
     /// Allows ASCII case insensitive matching of the value with the indexed
     /// field values when set to true. Default is false which means the case
     /// sensitivity of matching depends on the underlying field’s mapping.
     #[cfg_attr(feature = "builder", builder(default))]
+    #[input(graphql(default))]
     pub case_insensitive: bool,
+
+    /// Floating point number used to decrease or increase the relevance scores
+    /// of the query. (Defaults to `1.0`.)
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::option_float))]
+    pub boost: Option<f64>,
 }
 
 impl PrefixQuery {
@@ -111,24 +79,30 @@ impl PrefixQuery {
         PrefixQuery {
             field: field.into(),
             value: value.into(),
+            rewrite: None,
             case_insensitive: false,
+            boost: None,
         }
     }
 }
 
 #[cfg(feature = "graphql")]
-impl From<PrefixQueryInput> for PrefixQuery {
+impl PrefixQueryInput {
+    /// Constructs a new `PrefixQueryInput`.
     #[inline]
-    fn from(input: PrefixQueryInput) -> PrefixQuery {
-        PrefixQuery {
-            field: input.field,
-            value: input.value,
-            case_insensitive: input.case_insensitive,
+    pub fn new(field: impl Into<String>, value: impl Into<String>) -> Self {
+        PrefixQueryInput {
+            field: field.into(),
+            value: value.into(),
+            rewrite: None,
+            case_insensitive: false,
+            boost: None,
         }
     }
 }
 
 // TODO: re-use the serializer from the input type
+#[twin_impl]
 impl Serialize for PrefixQuery {
     #[inline]
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
@@ -136,7 +110,9 @@ impl Serialize for PrefixQuery {
 
         let inner = InnerPrefixQuery {
             value: self.value.to_owned(),
+            rewrite: self.rewrite.to_owned(),
             case_insensitive: self.case_insensitive,
+            boost: self.boost,
         };
 
         map.serialize_entry(&self.field, &inner)?;
@@ -145,6 +121,19 @@ impl Serialize for PrefixQuery {
     }
 }
 
+#[cfg(feature = "schemars")]
+impl JsonSchema for PrefixQuery {
+    #[inline]
+    fn schema_name() -> String {
+        "PrefixQuery".to_string()
+    }
+
+    #[inline]
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        crate::schema::keyed(InnerPrefixQuery::json_schema(gen))
+    }
+}
+
 /// Visits a `PrefixQuery` during deserialization.
 struct PrefixQueryVisitor;
 
@@ -173,12 +162,15 @@ impl<'de> Visitor<'de> for PrefixQueryVisitor {
             .next_key::<String>()?
             .ok_or_else(|| de::Error::missing_field("field"))?;
 
-        let inner: InnerPrefixQuery = map.next_value()?;
-
-        Ok(PrefixQuery {
-            field,
-            value: inner.value.to_owned(),
-            case_insensitive: inner.case_insensitive,
+        Ok(match map.next_value()? {
+            PrefixQueryBody::Short(value) => PrefixQuery::new(field, value),
+            PrefixQueryBody::Long(inner) => PrefixQuery {
+                field,
+                value: inner.value,
+                rewrite: inner.rewrite,
+                case_insensitive: inner.case_insensitive,
+                boost: inner.boost,
+            },
         })
     }
 }
@@ -212,7 +204,9 @@ mod tests {
         PrefixQuery {
             field: "userProfile".to_string(),
             value: "Kimchy".to_string(),
+            rewrite: None,
             case_insensitive: false,
+            boost: None,
         },
         json!({ "userProfile": { "value": "Kimchy", "case_insensitive": false } })
     );
@@ -222,7 +216,9 @@ mod tests {
         PrefixQuery {
             field: "user".to_string(),
             value: "Kimchy".to_string(),
+            rewrite: None,
             case_insensitive: true,
+            boost: None,
         },
         json!({ "user": { "value": "Kimchy", "case_insensitive": true } })
     );
@@ -232,11 +228,32 @@ mod tests {
         PrefixQuery {
             field: "user".to_string(),
             value: "Kimchy".to_string(),
+            rewrite: None,
             case_insensitive: false,
+            boost: None,
         },
         json!({ "user": { "value": "Kimchy", "case_insensitive": false } })
     );
 
+    test_case!(
+        with_rewrite_and_boost:
+        PrefixQuery {
+            field: "user".to_string(),
+            value: "ki".to_string(),
+            rewrite: Some("constant_score".to_string()),
+            case_insensitive: false,
+            boost: Some(2.0),
+        },
+        json!({
+            "user": {
+                "value": "ki",
+                "rewrite": "constant_score",
+                "case_insensitive": false,
+                "boost": 2.0
+            }
+        })
+    );
+
     #[test]
     fn deserialize_invalid_case_insensitive_is_err() {
         let j = r#"{ "user": { "value": "Kimchy", "case_insensitive": "nan" } }"#;
@@ -256,11 +273,16 @@ mod tests {
     }
 
     #[test]
-    fn deserialize_missing_values_is_err() {
-        // TODO: should we support this Elasticsearch schema?
-        let j = r#"{ "user": "missing" }"#;
-        assert!(serde_json::from_str::<PrefixQuery>(j).is_err(), "{}", &j);
+    fn can_deserialize_short_form() {
+        let j = json!({ "user": "ki" });
+        assert_eq!(
+            serde_json::from_value::<PrefixQuery>(j).unwrap(),
+            PrefixQuery::new("user", "ki")
+        );
+    }
 
+    #[test]
+    fn deserialize_missing_values_is_err() {
         let j = r#"{ "user": null }"#;
         assert!(serde_json::from_str::<PrefixQuery>(j).is_err(), "{}", &j);
 
@@ -2,15 +2,21 @@
 
 use std::fmt;
 
+use elastiql_derive::{input_twin, twin_impl};
+#[cfg(feature = "schemars")]
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
 use serde::{
     de::{self, MapAccess, Visitor},
-    ser::{SerializeMap, Serializer},
+    ser::Serializer,
     Deserialize, Serialize,
 };
 
+use super::RangeQueryRef;
+
 // TODO: should we present shortened or actual names via graphql? e.g. lt or less_than?
 
 #[allow(clippy::missing_docs_in_private_items)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[derive(Serialize, Deserialize)]
 struct InnerRangeQuery {
     #[serde(rename = "gt", default, skip_serializing_if = "Option::is_none")]
@@ -35,13 +41,16 @@ struct InnerRangeQuery {
 /// A [Range query] returns documents that contain terms within a provided range.
 ///
 /// [Range query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-range-query.html#query-dsl-range-query
-#[cfg(feature = "graphql")]
+#[input_twin(name = "RangeFilterInput")]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("RangeFilter"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 #[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(async_graphql::InputObject, Clone, Debug)]
-#[graphql(name = "RangeFilterInput")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
-pub struct RangeQueryInput {
+pub struct RangeQuery {
     /// The name of the field to query.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::field))]
     pub field: String,
 
     /// Greater than.
@@ -103,155 +112,117 @@ pub struct RangeQueryInput {
     ///
     /// [relevance scores]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-filter-context.html#relevance-scores
     #[cfg_attr(feature = "builder", builder(default))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::option_float))]
     pub boost: Option<f64>,
 }
 
-#[cfg(feature = "graphql")]
-impl From<RangeQuery> for RangeQueryInput {
+impl RangeQuery {
+    /// Constructs a new, unbounded `RangeQuery`.
     #[inline]
-    fn from(query: RangeQuery) -> Self {
-        Self {
-            field: query.field,
-            greater_than: query.greater_than,
-            greater_than_or_equal_to: query.greater_than_or_equal_to,
-            less_than: query.less_than,
-            less_than_or_equal_to: query.less_than_or_equal_to,
-            time_zone: query.time_zone,
-            boost: query.boost,
+    pub fn new(field: impl Into<String>) -> RangeQuery {
+        RangeQuery {
+            field: field.into(),
+            greater_than: None,
+            greater_than_or_equal_to: None,
+            less_than: None,
+            less_than_or_equal_to: None,
+            time_zone: None,
+            boost: None,
         }
     }
-}
 
-#[cfg(feature = "graphql")]
-impl Serialize for RangeQueryInput {
+    /// Sets the exclusive lower bound of this range.
     #[inline]
-    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        let mut map = serializer.serialize_map(Some(1))?;
-
-        let inner = InnerRangeQuery {
-            greater_than: self.greater_than.as_ref().map(|v| v.to_owned()),
-            greater_than_or_equal_to: self.greater_than_or_equal_to.as_ref().map(|v| v.to_owned()),
-            less_than: self.less_than.as_ref().map(|v| v.to_owned()),
-            less_than_or_equal_to: self.less_than_or_equal_to.as_ref().map(|v| v.to_owned()),
-            time_zone: self.time_zone.as_ref().map(|v| v.to_owned()),
-            boost: self.boost,
-        };
-
-        map.serialize_entry(&self.field, &inner)?;
-
-        map.end()
+    pub fn gt(mut self, value: impl ToString) -> RangeQuery {
+        self.greater_than = Some(value.to_string());
+        self
     }
-}
 
-/// A [Range query] returns documents that contain terms within a provided range.
-///
-/// [Range query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-range-query.html#query-dsl-range-query
-#[cfg_attr(test, derive(PartialEq))]
-#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
-#[cfg_attr(feature = "graphql", graphql(name = "RangeFilter"))]
-#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(Clone, Debug)]
-#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
-pub struct RangeQuery {
-    /// The name of the field to query.
-    pub field: String,
+    /// Sets the inclusive lower bound of this range.
+    #[inline]
+    pub fn gte(mut self, value: impl ToString) -> RangeQuery {
+        self.greater_than_or_equal_to = Some(value.to_string());
+        self
+    }
 
-    /// Greater than.
-    ///
-    /// Although this field is a `String`, it will match `numerical` fields; e.g.
-    /// `"1.2"` will match fields containing the floating point value `1.2`.
-    #[cfg_attr(feature = "builder", builder(default))]
-    pub greater_than: Option<String>,
+    /// Sets the exclusive upper bound of this range.
+    #[inline]
+    pub fn lt(mut self, value: impl ToString) -> RangeQuery {
+        self.less_than = Some(value.to_string());
+        self
+    }
 
-    /// Greater than or equal to.
-    ///
-    /// Although this field is a `String`, it will match `numerical` fields; e.g.
-    /// `"1.2"` will match fields containing the floating point value `1.2`.
-    #[cfg_attr(feature = "builder", builder(default))]
-    pub greater_than_or_equal_to: Option<String>,
+    /// Sets the inclusive upper bound of this range.
+    #[inline]
+    pub fn lte(mut self, value: impl ToString) -> RangeQuery {
+        self.less_than_or_equal_to = Some(value.to_string());
+        self
+    }
+}
 
-    /// Less than.
-    ///
-    /// Although this field is a `String`, it will match `numerical` fields; e.g.
-    /// `"1.2"` will match fields containing the floating point value `1.2`.
-    #[cfg_attr(feature = "builder", builder(default))]
-    pub less_than: Option<String>,
+#[cfg(feature = "graphql")]
+impl RangeQueryInput {
+    /// Constructs a new, unbounded `RangeQueryInput`.
+    #[inline]
+    pub fn new(field: impl Into<String>) -> RangeQueryInput {
+        RangeQueryInput {
+            field: field.into(),
+            greater_than: None,
+            greater_than_or_equal_to: None,
+            less_than: None,
+            less_than_or_equal_to: None,
+            time_zone: None,
+            boost: None,
+        }
+    }
 
-    /// Less than or equal to.
-    ///
-    /// Although this field is a `String`, it will match `numerical` fields; e.g.
-    /// `"1.2"` will match fields containing the floating point value `1.2`.
-    #[cfg_attr(feature = "builder", builder(default))]
-    pub less_than_or_equal_to: Option<String>,
+    /// Sets the exclusive lower bound of this range.
+    #[inline]
+    pub fn gt(mut self, value: impl ToString) -> RangeQueryInput {
+        self.greater_than = Some(value.to_string());
+        self
+    }
 
-    // TODO: #breaking make this a Rust newtype/GraphQL custom scalar ?
-    /// [Coordinated Universal Time (UTC) offset] or [IANA time zone] used to
-    /// convert date values in the query to UTC.
-    ///
-    /// Valid values are [ISO 8601] UTC offsets, such as `+01:00` or `-08:00`,
-    /// and [IANA time zone] IDs, such as `America/Los_Angeles`.
-    ///
-    /// **Note**: This parameter does not affect the date math value of `now`.
-    /// `now` is always the current system time in UTC. However, this parameter
-    /// does convert dates calculated using now and date math rounding. For
-    /// example, it will convert a value of `now/d` or `now-7d/d`.
-    ///
-    /// [Coordinated Universal Time (UTC) offset]: https://en.wikipedia.org/wiki/List_of_UTC_time_offsets
-    /// [ISO 8601]: https://en.wikipedia.org/wiki/ISO_8601
-    /// [IANA time zone]: https://en.wikipedia.org/wiki/List_of_tz_database_time_zones
-    /// [Time zone in range queries]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-range-query.html#range-query-time-zone
-    #[cfg_attr(feature = "builder", builder(default))]
-    pub time_zone: Option<String>,
+    /// Sets the inclusive lower bound of this range.
+    #[inline]
+    pub fn gte(mut self, value: impl ToString) -> RangeQueryInput {
+        self.greater_than_or_equal_to = Some(value.to_string());
+        self
+    }
 
-    /// Floating point number used to decrease or increase the
-    /// [relevance scores] of a query. (Defaults to `1.0`.)
-    ///
-    /// You can use the boost parameter to adjust relevance scores for searches
-    /// containing two or more queries.
-    ///
-    /// Boost values are relative to the default value of `1.0`. A boost value
-    /// between `0` and `1.0` decreases the relevance score. A value greater
-    /// than  `1.0` increases the relevance score.
-    ///
-    /// [relevance scores]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-filter-context.html#relevance-scores
-    #[cfg_attr(feature = "builder", builder(default))]
-    pub boost: Option<f64>,
-}
+    /// Sets the exclusive upper bound of this range.
+    #[inline]
+    pub fn lt(mut self, value: impl ToString) -> RangeQueryInput {
+        self.less_than = Some(value.to_string());
+        self
+    }
 
-#[cfg(feature = "graphql")]
-impl From<RangeQueryInput> for RangeQuery {
+    /// Sets the inclusive upper bound of this range.
     #[inline]
-    fn from(input: RangeQueryInput) -> RangeQuery {
-        RangeQuery {
-            field: input.field,
-            greater_than: input.greater_than,
-            greater_than_or_equal_to: input.greater_than_or_equal_to,
-            less_than: input.less_than,
-            less_than_or_equal_to: input.less_than_or_equal_to,
-            time_zone: input.time_zone,
-            boost: input.boost,
-        }
+    pub fn lte(mut self, value: impl ToString) -> RangeQueryInput {
+        self.less_than_or_equal_to = Some(value.to_string());
+        self
     }
 }
 
-// TODO: re-use the serializer from the input type
+#[twin_impl]
 impl Serialize for RangeQuery {
     #[inline]
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        let mut map = serializer.serialize_map(Some(1))?;
-
-        let inner = InnerRangeQuery {
-            greater_than: self.greater_than.as_ref().map(|v| v.to_owned()),
-            greater_than_or_equal_to: self.greater_than_or_equal_to.as_ref().map(|v| v.to_owned()),
-            less_than: self.less_than.as_ref().map(|v| v.to_owned()),
-            less_than_or_equal_to: self.less_than_or_equal_to.as_ref().map(|v| v.to_owned()),
-            time_zone: self.time_zone.as_ref().map(|v| v.to_owned()),
-            boost: self.boost,
-        };
+        RangeQueryRef::from(self).serialize(serializer)
+    }
+}
 
-        map.serialize_entry(&self.field, &inner)?;
+#[cfg(feature = "schemars")]
+impl JsonSchema for RangeQuery {
+    #[inline]
+    fn schema_name() -> String {
+        "RangeQuery".to_string()
+    }
 
-        map.end()
+    #[inline]
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        crate::schema::keyed(InnerRangeQuery::json_schema(gen))
     }
 }
 
@@ -0,0 +1,286 @@
+//! [Rank feature query](https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-rank-feature-query.html)
+
+use elastiql_derive::input_twin;
+use serde::{Deserialize, Serialize};
+
+/// A [Rank feature query] boosts the relevance scores of documents based on
+/// the numeric value of a `rank_feature` or `rank_features` field.
+///
+/// **Note**: until GraphQL [Union input types] are supported *at most one* of
+/// the functions (`saturation`, `log`, `sigmoid` or `linear`) may be
+/// specified; Elasticsearch uses `saturation` if none is.
+///
+/// [Rank feature query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-rank-feature-query.html
+/// [Union input types]: https://github.com/graphql/graphql-spec/blob/master/rfcs/InputUnion.md
+#[input_twin(name = "RankFeatureFilterInput")]
+#[cfg_attr(
+    feature = "graphql",
+    elastiql_derive::graphql_name("RankFeatureFilter")
+)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct RankFeatureQuery {
+    /// The name of the `rank_feature` or `rank_features` field to score by.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::field))]
+    pub field: String,
+
+    /// Scores documents by `S / (S + pivot)`, where `S` is the value of the
+    /// field, i.e. between `0` and `1`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[input(twin)]
+    pub saturation: Option<RankFeatureSaturation>,
+
+    /// Scores documents by `log(scaling_factor + S)`, where `S` is the value
+    /// of the field.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[input(twin)]
+    pub log: Option<RankFeatureLog>,
+
+    /// Scores documents by `S^exp / (S^exp + pivot^exp)`, where `S` is the
+    /// value of the field.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[input(twin)]
+    pub sigmoid: Option<RankFeatureSigmoid>,
+
+    /// Whether to score documents by the (indexed) value of the field as is.
+    #[serde(default, skip_serializing_if = "is_false", with = "empty_object")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<crate::schema::Map>"))]
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[input(graphql(default))]
+    pub linear: bool,
+
+    /// Floating point number used to decrease or increase the relevance scores
+    /// of the query. (Defaults to `1.0`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::option_float))]
+    pub boost: Option<f64>,
+}
+
+impl RankFeatureQuery {
+    /// Constructs a new `RankFeatureQuery` using the default `saturation`
+    /// function.
+    #[inline]
+    pub fn new(field: impl Into<String>) -> RankFeatureQuery {
+        RankFeatureQuery {
+            field: field.into(),
+            saturation: None,
+            log: None,
+            sigmoid: None,
+            linear: false,
+            boost: None,
+        }
+    }
+
+    /// Returns the name of the function used to score documents.
+    #[inline]
+    pub fn function(&self) -> &'static str {
+        if self.log.is_some() {
+            "log"
+        } else if self.sigmoid.is_some() {
+            "sigmoid"
+        } else if self.linear {
+            "linear"
+        } else {
+            "saturation"
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl RankFeatureQueryInput {
+    /// Constructs a new `RankFeatureQueryInput` using the default `saturation`
+    /// function.
+    #[inline]
+    pub fn new(field: impl Into<String>) -> RankFeatureQueryInput {
+        RankFeatureQueryInput {
+            field: field.into(),
+            saturation: None,
+            log: None,
+            sigmoid: None,
+            linear: false,
+            boost: None,
+        }
+    }
+}
+
+/// The options of the `saturation` function of a [`RankFeatureQuery`].
+#[input_twin]
+#[cfg_attr(
+    feature = "graphql",
+    elastiql_derive::graphql_name("RankFeatureSaturation")
+)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+pub struct RankFeatureSaturation {
+    /// The value of the field at which the score is `0.5`. (Defaults to the
+    /// approximate geometric mean of the values of the field in the index.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::option_float))]
+    pub pivot: Option<f64>,
+}
+
+/// The options of the `log` function of a [`RankFeatureQuery`].
+#[input_twin]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("RankFeatureLog"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RankFeatureLog {
+    /// The positive constant added to the value of the field, e.g. `1.0` so
+    /// the score is never negative.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::float))]
+    pub scaling_factor: f64,
+}
+
+/// The options of the `sigmoid` function of a [`RankFeatureQuery`].
+#[input_twin]
+#[cfg_attr(
+    feature = "graphql",
+    elastiql_derive::graphql_name("RankFeatureSigmoid")
+)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RankFeatureSigmoid {
+    /// The value of the field at which the score is `0.5`.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::float))]
+    pub pivot: f64,
+
+    /// The positive exponent, typically between `0.5` and `1.0`.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::float))]
+    pub exponent: f64,
+}
+
+#[inline]
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+/// (De)serializes a `bool` as an empty object if it is `true`, for the
+/// `linear` function, i.e. `"linear": {}`.
+mod empty_object {
+    use serde::{de::IgnoredAny, ser::SerializeMap, Deserialize, Deserializer, Serializer};
+
+    #[inline]
+    pub(super) fn serialize<S: Serializer>(_: &bool, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_map(Some(0))?.end()
+    }
+
+    #[inline]
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<bool, D::Error> {
+        Option::<IgnoredAny>::deserialize(deserializer).map(|value| value.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    macro_rules! test_case {
+        ($name:ident : $f:expr, $j:expr) => {
+            mod $name {
+                use super::*;
+
+                #[test]
+                fn can_serialize() {
+                    assert_eq!(serde_json::to_value(&$f).unwrap(), $j);
+                }
+
+                #[test]
+                fn can_deserialize() {
+                    assert_eq!(serde_json::from_value::<RankFeatureQuery>($j).unwrap(), $f);
+                }
+            }
+        };
+    }
+
+    test_case!(
+        simple:
+        RankFeatureQuery::new("pagerank"),
+        json!({ "field": "pagerank" })
+    );
+
+    test_case!(
+        saturation:
+        RankFeatureQuery {
+            saturation: Some(RankFeatureSaturation { pivot: Some(8.0) }),
+            boost: Some(2.0),
+            ..RankFeatureQuery::new("pagerank")
+        },
+        json!({ "field": "pagerank", "saturation": { "pivot": 8.0 }, "boost": 2.0 })
+    );
+
+    test_case!(
+        log:
+        RankFeatureQuery {
+            log: Some(RankFeatureLog { scaling_factor: 4.0 }),
+            ..RankFeatureQuery::new("pagerank")
+        },
+        json!({ "field": "pagerank", "log": { "scaling_factor": 4.0 } })
+    );
+
+    test_case!(
+        sigmoid:
+        RankFeatureQuery {
+            sigmoid: Some(RankFeatureSigmoid { pivot: 7.0, exponent: 0.6 }),
+            ..RankFeatureQuery::new("pagerank")
+        },
+        json!({ "field": "pagerank", "sigmoid": { "pivot": 7.0, "exponent": 0.6 } })
+    );
+
+    test_case!(
+        linear:
+        RankFeatureQuery {
+            linear: true,
+            ..RankFeatureQuery::new("topics.sports")
+        },
+        json!({ "field": "topics.sports", "linear": {} })
+    );
+
+    #[test]
+    fn deserialize_invalid_values_is_err() {
+        let j = r#"{ "saturation": {} }"#;
+        assert!(
+            serde_json::from_str::<RankFeatureQuery>(j).is_err(),
+            "{}",
+            &j
+        );
+
+        let j = r#"{ "field": "pagerank", "log": {} }"#;
+        assert!(
+            serde_json::from_str::<RankFeatureQuery>(j).is_err(),
+            "{}",
+            &j
+        );
+
+        let j = r#"{ "field": "pagerank", "sigmoid": { "pivot": 7 } }"#;
+        assert!(
+            serde_json::from_str::<RankFeatureQuery>(j).is_err(),
+            "{}",
+            &j
+        );
+    }
+}
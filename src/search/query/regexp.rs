@@ -2,6 +2,9 @@
 
 use std::{collections::HashMap, fmt};
 
+use elastiql_derive::{input_twin, twin_impl};
+#[cfg(feature = "schemars")]
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
 use serde::{
     de::{self, MapAccess, Visitor},
     ser::{Serialize, SerializeMap, Serializer},
@@ -17,13 +20,16 @@ use serde::{
 /// [Regexp query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-regexp-query.html#query-dsl-regexp-query
 /// [regular expression]: https://en.wikipedia.org/wiki/Regular_expression
 /// [Regular expression syntax]: https://www.elastic.co/guide/en/elasticsearch/reference/current/regexp-syntax.html
-#[cfg(feature = "graphql")]
+#[input_twin(name = "RegexpFilterInput")]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("RegexpFilter"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 #[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(async_graphql::InputObject, Clone, Debug)]
-#[graphql(name = "RegexpFilterInput")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
-pub struct RegexpQueryInput {
+pub struct RegexpQuery {
     /// The name of the field to query.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::field))]
     pub field: String,
 
     /// Regular expression for terms you wish to find in the provided `field`.
@@ -44,16 +50,15 @@ pub struct RegexpQueryInput {
     pub flags: Option<String>,
 }
 
-#[cfg(feature = "graphql")]
-impl RegexpQueryInput {
-    /// Constructs a new `RegexpQueryInput`.
+impl RegexpQuery {
+    /// Constructs a new `RegexpQuery`.
     #[inline]
     pub fn new(
         field: impl Into<String>,
         value: impl Into<String>,
         flags: Option<impl Into<String>>,
-    ) -> RegexpQueryInput {
-        RegexpQueryInput {
+    ) -> RegexpQuery {
+        RegexpQuery {
             field: field.into(),
             value: value.into(),
             flags: flags.map(Into::into),
@@ -62,84 +67,15 @@ impl RegexpQueryInput {
 }
 
 #[cfg(feature = "graphql")]
-impl From<RegexpQuery> for RegexpQueryInput {
-    #[inline]
-    fn from(query: RegexpQuery) -> Self {
-        Self {
-            field: query.field,
-            value: query.value,
-            flags: query.flags,
-        }
-    }
-}
-
-#[cfg(feature = "graphql")]
-impl Serialize for RegexpQueryInput {
-    #[inline]
-    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        let mut map = serializer.serialize_map(Some(1))?;
-
-        let mut values: HashMap<&str, &str> = HashMap::new();
-
-        values.insert("value", &self.value);
-
-        if let Some(flags) = &self.flags {
-            values.insert("flags", flags);
-        }
-
-        map.serialize_entry(&self.field, &values)?;
-
-        map.end()
-    }
-}
-
-/// A [Regexp query] returns documents that contain terms matching a
-/// [regular expression].
-///
-/// A [regular expression] is a way to match patterns in data using placeholder
-/// characters, called operators. For a list of operators supported by the
-/// regexp query, see [Regular expression syntax].
-///
-/// [Regexp query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-regexp-query.html#query-dsl-regexp-query
-/// [regular expression]: https://en.wikipedia.org/wiki/Regular_expression
-/// [Regular expression syntax]: https://www.elastic.co/guide/en/elasticsearch/reference/current/regexp-syntax.html
-#[cfg_attr(test, derive(PartialEq))]
-#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
-#[cfg_attr(feature = "graphql", graphql(name = "RegexpFilter"))]
-#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(Clone, Debug)]
-#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
-pub struct RegexpQuery {
-    /// The name of the field to query.
-    pub field: String,
-
-    /// Regular expression for terms you wish to find in the provided `field`.
-    /// For a list of supported operators, see [Regular expression syntax].
-    ///
-    /// [Regular expression syntax]: https://www.elastic.co/guide/en/elasticsearch/reference/current/regexp-syntax.html#regexp-syntax
-    pub value: String,
-
-    /// Enables optional operators for the regular expression. For valid values
-    /// and more information, see [Regular expression syntax].
-    ///
-    /// To enable multiple operators, use a `|` separator. For example, a flags
-    /// value of `COMPLEMENT|INTERVAL` enables the `COMPLEMENT` and `INTERVAL`
-    /// operators.
-    ///
-    /// [Regular expression syntax]: https://www.elastic.co/guide/en/elasticsearch/reference/current/regexp-syntax.html#regexp-optional-operators
-    #[cfg_attr(feature = "builder", builder(default))]
-    pub flags: Option<String>,
-}
-
-impl RegexpQuery {
-    /// Constructs a new `RegexpQuery`.
+impl RegexpQueryInput {
+    /// Constructs a new `RegexpQueryInput`.
     #[inline]
     pub fn new(
         field: impl Into<String>,
         value: impl Into<String>,
         flags: Option<impl Into<String>>,
-    ) -> RegexpQuery {
-        RegexpQuery {
+    ) -> RegexpQueryInput {
+        RegexpQueryInput {
             field: field.into(),
             value: value.into(),
             flags: flags.map(Into::into),
@@ -147,19 +83,8 @@ impl RegexpQuery {
     }
 }
 
-#[cfg(feature = "graphql")]
-impl From<RegexpQueryInput> for RegexpQuery {
-    #[inline]
-    fn from(input: RegexpQueryInput) -> RegexpQuery {
-        RegexpQuery {
-            field: input.field,
-            value: input.value,
-            flags: input.flags,
-        }
-    }
-}
-
 // TODO: re-use the serializer from the input type
+#[twin_impl]
 impl Serialize for RegexpQuery {
     #[inline]
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
@@ -180,6 +105,26 @@ impl Serialize for RegexpQuery {
     }
 }
 
+#[cfg(feature = "schemars")]
+impl JsonSchema for RegexpQuery {
+    #[inline]
+    fn schema_name() -> String {
+        "RegexpQuery".to_string()
+    }
+
+    #[inline]
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let body = crate::schema::object(
+            vec![
+                ("value", gen.subschema_for::<String>()),
+                ("flags", gen.subschema_for::<String>()),
+            ],
+            &["value"],
+        );
+        crate::schema::keyed(body.into())
+    }
+}
+
 /// Visits a `RegexpQuery` during deserialization.
 struct RegexpQueryVisitor;
 
@@ -0,0 +1,59 @@
+//! [Script query](https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-script-query.html)
+
+use elastiql_derive::input_twin;
+use serde::{Deserialize, Serialize};
+
+use crate::search::Script;
+#[cfg(feature = "graphql")]
+use crate::search::ScriptInput;
+
+/// A [Script query] returns documents for which the provided `script` returns
+/// `true`, which is typically used in a filter context.
+///
+/// [Script query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-script-query.html
+#[input_twin(name = "ScriptFilterInput")]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("ScriptFilter"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct ScriptQuery {
+    /// The script returning whether a document matches, e.g.
+    /// `doc['num1'].value > 1`.
+    #[input(twin)]
+    pub script: Script,
+}
+
+impl ScriptQuery {
+    /// Constructs a new `ScriptQuery`.
+    #[inline]
+    pub fn new(script: Script) -> ScriptQuery {
+        ScriptQuery { script }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn can_serialize_and_deserialize() {
+        let query = ScriptQuery::new(Script::inline("doc['num1'].value > 1").with_lang("painless"));
+        let j = json!({
+            "script": { "source": "doc['num1'].value > 1", "lang": "painless" }
+        });
+
+        assert_eq!(serde_json::to_value(&query).unwrap(), j);
+        assert_eq!(serde_json::from_value::<ScriptQuery>(j).unwrap(), query);
+    }
+
+    #[test]
+    fn deserialize_invalid_scripts_is_err() {
+        let j = r#"{ "script": {} }"#;
+        assert!(serde_json::from_str::<ScriptQuery>(j).is_err(), "{}", &j);
+    }
+}
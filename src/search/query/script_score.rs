@@ -0,0 +1,93 @@
+//! [Script score query](https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-script-score-query.html)
+
+use elastiql_derive::input_twin;
+use serde::{Deserialize, Serialize};
+
+use super::super::query::CompoundQuery;
+#[cfg(feature = "graphql")]
+use super::super::query::CompoundQueryInput;
+use crate::search::Script;
+#[cfg(feature = "graphql")]
+use crate::search::ScriptInput;
+
+/// A [Script score query] wraps another query to compute the relevance scores
+/// of the documents it returns with a [script].
+///
+/// [Script score query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-script-score-query.html
+/// [script]: https://www.elastic.co/guide/en/elasticsearch/reference/current/modules-scripting.html
+#[input_twin(name = "ScriptScoreFilterInput")]
+#[cfg_attr(
+    feature = "graphql",
+    elastiql_derive::graphql_name("ScriptScoreFilter")
+)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct ScriptScoreQuery {
+    /// The query returning the documents to score.
+    #[input(twin)]
+    pub query: CompoundQuery,
+
+    /// The script computing the relevance score of a document, e.g.
+    /// `doc['likes'].value / 10`, which may use the score of the `query` as
+    /// `_score`.
+    #[input(twin)]
+    pub script: Script,
+
+    /// Documents with a lower score are excluded from the results.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub min_score: Option<f64>,
+
+    /// Floating point number the scores computed by the `script` are
+    /// multiplied by. (Defaults to `1.0`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub boost: Option<f64>,
+}
+
+impl ScriptScoreQuery {
+    /// Constructs a new `ScriptScoreQuery`.
+    #[inline]
+    pub fn new(query: impl Into<CompoundQuery>, script: Script) -> ScriptScoreQuery {
+        ScriptScoreQuery {
+            query: query.into(),
+            script,
+            min_score: None,
+            boost: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::search::query::MatchQuery;
+
+    #[test]
+    fn can_serialize_and_deserialize() {
+        let query = ScriptScoreQuery {
+            min_score: Some(2.0),
+            ..ScriptScoreQuery::new(
+                CompoundQuery::from_query(MatchQuery::new("message", "elasticsearch")),
+                Script::inline("doc['my-int'].value / 10"),
+            )
+        };
+        let j = json!({
+            "query": { "match": { "message": { "query": "elasticsearch" } } },
+            "script": { "source": "doc['my-int'].value / 10" },
+            "min_score": 2.0
+        });
+
+        assert_eq!(serde_json::to_value(&query).unwrap(), j);
+        assert_eq!(
+            serde_json::from_value::<ScriptScoreQuery>(j).unwrap(),
+            query
+        );
+    }
+}
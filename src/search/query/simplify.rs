@@ -0,0 +1,392 @@
+//! Normalization of (possibly deeply nested) `bool` queries.
+
+use std::collections::HashSet;
+use std::mem;
+
+use super::{BooleanQuery, CompoundQuery, Query};
+
+/// The clause of a `bool` query a query occurs in.
+#[derive(Clone, Copy)]
+enum Occur {
+    Must,
+    Filter,
+    Should,
+    MustNot,
+}
+
+impl BooleanQuery {
+    /// Returns a smaller `bool` query that matches the same documents.
+    ///
+    /// This recursively:
+    ///
+    /// * removes empty clauses
+    /// * flattens nested `bool` queries with a single clause
+    /// * hoists the clauses of nested `bool` queries into this query where
+    ///   that doesn't change which documents match, e.g. the `must` and
+    ///   `filter` clauses of a nested `bool` query in a `filter` clause
+    /// * removes duplicate `filter` and `must_not` clauses
+    ///
+    /// Smaller queries are cheaper to parse and are easier for Elasticsearch to
+    /// cache. Note that hoisting `filter` clauses out of a `must` clause (and
+    /// vice versa) may change the *scores* of matching documents.
+    pub fn simplify(self) -> BooleanQuery {
+        let BooleanQuery {
+            must,
+            filter,
+            should,
+            must_not,
+            minimum_should_match,
+            boost,
+        } = self;
+
+        let had_required_clauses = !must.is_empty() || !filter.is_empty();
+
+        let mut simplified = BooleanQuery {
+            must: Vec::with_capacity(must.len()),
+            filter: Vec::with_capacity(filter.len()),
+            should: Vec::with_capacity(should.len()),
+            must_not: Vec::with_capacity(must_not.len()),
+            minimum_should_match,
+            boost,
+        };
+
+        for query in must {
+            simplified.add(Occur::Must, query.simplify());
+        }
+        for query in filter {
+            simplified.add(Occur::Filter, query.simplify());
+        }
+        for query in should {
+            simplified.add(Occur::Should, query.simplify());
+        }
+        for query in must_not {
+            simplified.add(Occur::MustNot, query.simplify());
+        }
+
+        dedup(&mut simplified.filter);
+        dedup(&mut simplified.must_not);
+
+        // `should` clauses are only optional when there are `must` or `filter`
+        // clauses, so keep them optional if we removed all of those.
+        if had_required_clauses
+            && simplified.must.is_empty()
+            && simplified.filter.is_empty()
+            && !simplified.should.is_empty()
+            && simplified.minimum_should_match.is_none()
+        {
+            simplified.minimum_should_match = Some("0".to_string());
+        }
+
+        simplified
+    }
+
+    /// Returns the list of clauses for `occur`.
+    #[inline]
+    fn clauses(&mut self, occur: Occur) -> &mut Vec<Query> {
+        match occur {
+            Occur::Must => &mut self.must,
+            Occur::Filter => &mut self.filter,
+            Occur::Should => &mut self.should,
+            Occur::MustNot => &mut self.must_not,
+        }
+    }
+
+    /// Adds an already simplified `query` to the `occur` clauses.
+    fn add(&mut self, occur: Occur, mut query: Query) {
+        if query.is_empty() {
+            return;
+        }
+
+        let child = match query.boolean.take() {
+            Some(child) if query.is_empty() => child,
+            child => {
+                query.boolean = child;
+                self.clauses(occur).push(query);
+                return;
+            }
+        };
+
+        if child.minimum_should_match.is_some() || child.boost.is_some() {
+            self.clauses(occur).push(child.into_query());
+            return;
+        }
+
+        let BooleanQuery {
+            must,
+            filter,
+            should,
+            must_not,
+            ..
+        } = *child;
+
+        match occur {
+            // an empty `bool` query matches all documents
+            Occur::Must | Occur::Filter if must.is_empty() && filter.is_empty() => {
+                if should.is_empty() && must_not.is_empty() {
+                    return;
+                }
+                if should.len() == 1 && must_not.is_empty() {
+                    self.clauses(occur).extend(should);
+                    return;
+                }
+                if should.is_empty() {
+                    self.must_not.extend(must_not);
+                    return;
+                }
+            }
+            Occur::Must | Occur::Filter if should.is_empty() => {
+                self.clauses(occur).extend(must);
+                self.filter.extend(filter);
+                self.must_not.extend(must_not);
+                return;
+            }
+            // not (a or b) == not a and not b
+            Occur::MustNot
+                if must.is_empty()
+                    && filter.is_empty()
+                    && must_not.is_empty()
+                    && !should.is_empty() =>
+            {
+                self.must_not.extend(should);
+                return;
+            }
+            Occur::MustNot
+                if should.is_empty() && must_not.is_empty() && must.len() + filter.len() == 1 =>
+            {
+                self.must_not.extend(must.into_iter().chain(filter));
+                return;
+            }
+            Occur::Should if filter.is_empty() && must_not.is_empty() => {
+                if should.is_empty() && must.len() == 1 {
+                    self.should.extend(must);
+                    return;
+                }
+                // an empty `bool` query matches all documents, so it can't be
+                // flattened into (no) `should` clauses
+                if must.is_empty() && !should.is_empty() && self.minimum_should_match.is_none() {
+                    self.should.extend(should);
+                    return;
+                }
+            }
+            _ => {}
+        }
+
+        let child = BooleanQuery {
+            must,
+            filter,
+            should,
+            must_not,
+            minimum_should_match: None,
+            boost: None,
+        };
+        self.clauses(occur).push(child.into_query());
+    }
+}
+
+impl Query {
+    /// Returns a smaller `Query` that matches the same documents, unwrapping
+    /// `bool` queries with a single required clause.
+    ///
+    /// See [`BooleanQuery::simplify`].
+    pub fn simplify(mut self) -> Query {
+        if let Some(ref mut script_score) = self.script_score {
+            script_score.query = mem::take(&mut script_score.query).simplify();
+        }
+        if let Some(ref mut nested) = self.nested {
+            nested.query = mem::take(&mut nested.query).simplify();
+        }
+
+        let boolean = match self.boolean.take() {
+            Some(boolean) => boolean.simplify(),
+            None => return self,
+        };
+
+        let unwrap = self.is_empty()
+            && boolean.minimum_should_match.is_none()
+            && boolean.boost.is_none()
+            && boolean.filter.is_empty()
+            && boolean.must_not.is_empty()
+            && boolean.must.len() + boolean.should.len() == 1;
+
+        if unwrap {
+            let BooleanQuery { must, should, .. } = boolean;
+            must.into_iter().chain(should).next().unwrap_or_default()
+        } else {
+            self.boolean = Some(Box::new(boolean));
+            self
+        }
+    }
+}
+
+impl CompoundQuery {
+    /// Returns a smaller `CompoundQuery` that matches the same documents.
+    ///
+    /// See [`BooleanQuery::simplify`].
+    #[inline]
+    pub fn simplify(self) -> CompoundQuery {
+        CompoundQuery {
+            boolean: self.boolean.map(BooleanQuery::simplify),
+            root: self.root.map(|query| Box::new(query.simplify())),
+        }
+    }
+}
+
+/// Removes duplicate queries from `queries`, preserving their order.
+fn dedup(queries: &mut Vec<Query>) {
+    let mut seen = HashSet::with_capacity(queries.len());
+    queries.retain(|query| match serde_json::to_string(query) {
+        Ok(json) => seen.insert(json),
+        Err(_) => true,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_case {
+        ($name:ident : $j:expr, $e:expr) => {
+            mod $name {
+                use super::*;
+
+                #[test]
+                fn can_simplify() {
+                    let query: BooleanQuery = serde_json::from_str($j).unwrap();
+                    let expected: serde_json::Value = serde_json::from_str($e).unwrap();
+
+                    let simplified = query.simplify();
+                    assert_eq!(serde_json::to_value(&simplified).unwrap(), expected);
+                }
+
+                #[test]
+                fn is_idempotent() {
+                    let query: BooleanQuery = serde_json::from_str($j).unwrap();
+
+                    let once = query.simplify();
+                    let twice = once.clone().simplify();
+                    assert_eq!(
+                        serde_json::to_value(&once).unwrap(),
+                        serde_json::to_value(&twice).unwrap(),
+                    );
+                }
+            }
+        };
+    }
+
+    test_case!(
+        removes_empty_clauses:
+        r#"{
+            "filter": [{ "bool": {} }, { "exists": { "field": "a" } }],
+            "must": [{}]
+        }"#,
+        r#"{ "filter": [{ "exists": { "field": "a" } }] }"#
+    );
+
+    test_case!(
+        flattens_single_clause_bools:
+        r#"{
+            "filter": [{ "bool": { "must": [{ "exists": { "field": "a" } }] } }],
+            "should": [{ "bool": { "must": [{ "exists": { "field": "b" } }] } }],
+            "must_not": [{ "bool": { "filter": [{ "exists": { "field": "c" } }] } }]
+        }"#,
+        r#"{
+            "filter": [{ "exists": { "field": "a" } }],
+            "should": [{ "exists": { "field": "b" } }],
+            "must_not": [{ "exists": { "field": "c" } }]
+        }"#
+    );
+
+    test_case!(
+        hoists_nested_clauses:
+        r#"{
+            "must": [{
+                "bool": {
+                    "must": [{ "exists": { "field": "a" } }],
+                    "filter": [{ "bool": { "filter": [{ "exists": { "field": "b" } }] } }],
+                    "must_not": [{ "exists": { "field": "c" } }]
+                }
+            }],
+            "must_not": [{
+                "bool": { "should": [{ "exists": { "field": "d" } }, { "exists": { "field": "e" } }] }
+            }]
+        }"#,
+        r#"{
+            "must": [{ "exists": { "field": "a" } }],
+            "filter": [{ "exists": { "field": "b" } }],
+            "must_not": [
+                { "exists": { "field": "c" } },
+                { "exists": { "field": "d" } },
+                { "exists": { "field": "e" } }
+            ]
+        }"#
+    );
+
+    test_case!(
+        merges_duplicate_filters:
+        r#"{
+            "filter": [
+                { "term": { "a": { "value": "1" } } },
+                { "bool": { "filter": [{ "term": { "a": { "value": "1" } } }] } }
+            ]
+        }"#,
+        r#"{ "filter": [{ "term": { "a": { "value": "1" } } }] }"#
+    );
+
+    test_case!(
+        keeps_bools_with_options:
+        r#"{
+            "filter": [{
+                "bool": {
+                    "should": [{ "exists": { "field": "a" } }, { "exists": { "field": "b" } }],
+                    "minimum_should_match": "2"
+                }
+            }]
+        }"#,
+        r#"{
+            "filter": [{
+                "bool": {
+                    "should": [{ "exists": { "field": "a" } }, { "exists": { "field": "b" } }],
+                    "minimum_should_match": "2"
+                }
+            }]
+        }"#
+    );
+
+    test_case!(
+        keeps_should_clauses_optional:
+        r#"{
+            "filter": [{ "bool": {} }],
+            "should": [{ "exists": { "field": "a" } }]
+        }"#,
+        r#"{
+            "should": [{ "exists": { "field": "a" } }],
+            "minimum_should_match": "0"
+        }"#
+    );
+
+    test_case!(
+        keeps_empty_should_clauses:
+        r#"{ "should": [{ "exists": { "field": "a" } }, { "bool": {} }] }"#,
+        r#"{ "should": [{ "exists": { "field": "a" } }, { "bool": {} }] }"#
+    );
+
+    test_case!(
+        keeps_nested_should_clauses_with_other_clauses:
+        r#"{
+            "filter": [{
+                "bool": {
+                    "should": [{ "exists": { "field": "a" } }, { "exists": { "field": "b" } }]
+                }
+            }],
+            "must_not": [{ "bool": {} }]
+        }"#,
+        r#"{
+            "filter": [{
+                "bool": {
+                    "should": [{ "exists": { "field": "a" } }, { "exists": { "field": "b" } }]
+                }
+            }],
+            "must_not": [{ "bool": {} }]
+        }"#
+    );
+}
@@ -0,0 +1,92 @@
+//! [Span containing query](https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-span-containing-query.html)
+
+use elastiql_derive::input_twin;
+use serde::{Deserialize, Serialize};
+
+use super::SpanQuery;
+#[cfg(feature = "graphql")]
+use super::SpanQueryInput;
+
+/// A [Span containing query] matches the spans of `big` which contain a span
+/// of `little`.
+///
+/// [Span containing query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-span-containing-query.html
+#[input_twin(name = "SpanContainingFilterInput")]
+#[cfg_attr(
+    feature = "graphql",
+    elastiql_derive::graphql_name("SpanContainingFilter")
+)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct SpanContainingQuery {
+    /// The span query whose spans are matched.
+    #[input(twin)]
+    pub big: SpanQuery,
+
+    /// The span query whose spans the matched spans must contain.
+    #[input(twin)]
+    pub little: SpanQuery,
+
+    /// Floating point number used to decrease or increase the relevance scores
+    /// of the query. (Defaults to `1.0`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub boost: Option<f64>,
+}
+
+impl SpanContainingQuery {
+    /// Constructs a new `SpanContainingQuery` for the spans of `big` which
+    /// contain a span of `little`.
+    #[inline]
+    pub fn new(big: impl Into<SpanQuery>, little: impl Into<SpanQuery>) -> Self {
+        SpanContainingQuery {
+            big: big.into(),
+            little: little.into(),
+            boost: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::search::query::span::{SpanNearQuery, SpanTermQuery};
+
+    #[test]
+    fn can_serialize_and_deserialize() {
+        let query = SpanContainingQuery::new(
+            SpanNearQuery::new(
+                vec![
+                    SpanTermQuery::new("field1", "bar"),
+                    SpanTermQuery::new("field1", "baz"),
+                ],
+                5,
+            ),
+            SpanTermQuery::new("field1", "foo"),
+        );
+        let j = json!({
+            "big": {
+                "span_near": {
+                    "clauses": [
+                        { "span_term": { "field1": { "value": "bar" } } },
+                        { "span_term": { "field1": { "value": "baz" } } }
+                    ],
+                    "slop": 5
+                }
+            },
+            "little": { "span_term": { "field1": { "value": "foo" } } }
+        });
+
+        assert_eq!(serde_json::to_value(&query).unwrap(), j);
+        assert_eq!(
+            serde_json::from_value::<SpanContainingQuery>(j).unwrap(),
+            query
+        );
+    }
+}
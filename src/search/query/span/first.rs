@@ -0,0 +1,75 @@
+//! [Span first query](https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-span-first-query.html)
+
+use elastiql_derive::input_twin;
+use serde::{Deserialize, Serialize};
+
+use super::SpanQuery;
+#[cfg(feature = "graphql")]
+use super::SpanQueryInput;
+
+/// A [Span first query] matches spans near the beginning of a field, i.e.
+/// which end at most at position `end`.
+///
+/// [Span first query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-span-first-query.html
+#[input_twin(name = "SpanFirstFilterInput")]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("SpanFirstFilter"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct SpanFirstQuery {
+    /// The span query to match.
+    #[serde(rename = "match")]
+    #[input(twin)]
+    pub match_: SpanQuery,
+
+    /// The maximum end position of a matching span.
+    pub end: u32,
+
+    /// Floating point number used to decrease or increase the relevance scores
+    /// of the query. (Defaults to `1.0`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub boost: Option<f64>,
+}
+
+impl SpanFirstQuery {
+    /// Constructs a new `SpanFirstQuery` for the spans of `match_` which end
+    /// at most at position `end`.
+    #[inline]
+    pub fn new(match_: impl Into<SpanQuery>, end: u32) -> Self {
+        SpanFirstQuery {
+            match_: match_.into(),
+            end,
+            boost: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::search::query::span::SpanTermQuery;
+
+    #[test]
+    fn can_serialize_and_deserialize() {
+        let query = SpanFirstQuery::new(SpanTermQuery::new("user.id", "kimchy"), 3);
+        let j = json!({
+            "match": { "span_term": { "user.id": { "value": "kimchy" } } },
+            "end": 3
+        });
+
+        assert_eq!(serde_json::to_value(&query).unwrap(), j);
+        assert_eq!(serde_json::from_value::<SpanFirstQuery>(j).unwrap(), query);
+    }
+
+    #[test]
+    fn deserialize_invalid_values_is_err() {
+        let j = r#"{ "match": { "span_term": { "user.id": "kimchy" } } }"#;
+        assert!(serde_json::from_str::<SpanFirstQuery>(j).is_err(), "{}", &j);
+    }
+}
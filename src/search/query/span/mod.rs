@@ -0,0 +1,314 @@
+//! [Span queries] are low-level positional queries, e.g. for legal documents
+//! or patents, which provide expert control over the order and proximity of
+//! the terms they match.
+//!
+//! Span queries can only be combined with other span queries (or wrap a
+//! multi-term query with [`SpanMultiQuery`]), so their clauses are
+//! [`SpanQuery`]s instead of [`Query`]s.
+//!
+//! ```
+//! use elastiql::search::query::span::{SpanNearQuery, SpanTermQuery};
+//!
+//! let query = SpanNearQuery::new(
+//!     vec![
+//!         SpanTermQuery::new("field", "value1"),
+//!         SpanTermQuery::new("field", "value2"),
+//!     ],
+//!     12,
+//! );
+//! assert_eq!(
+//!     serde_json::to_value(&query).unwrap(),
+//!     serde_json::json!({
+//!         "clauses": [
+//!             { "span_term": { "field": { "value": "value1" } } },
+//!             { "span_term": { "field": { "value": "value2" } } }
+//!         ],
+//!         "slop": 12
+//!     })
+//! );
+//! ```
+//!
+//! [Span queries]: https://www.elastic.co/guide/en/elasticsearch/reference/current/span-queries.html
+
+use elastiql_derive::input_twin;
+use serde::{Deserialize, Serialize};
+
+pub use self::{containing::*, first::*, multi::*, near::*, not::*, or::*, term::*, within::*};
+use super::Query;
+#[cfg(feature = "graphql")]
+use super::QueryInput;
+
+mod containing;
+mod first;
+mod multi;
+mod near;
+mod not;
+mod or;
+mod term;
+mod within;
+
+/// A single span query, e.g. one of the `clauses` of a `span_near` query.
+///
+/// **Note**: This should *never* have more than *one* defined (and non-null)
+/// field.
+///
+/// **TODO**: Change this type once [union input types] are supported by GraphQL
+/// to only allow specifying a single field.
+///
+/// [union input types]: https://github.com/graphql/graphql-spec/blob/master/rfcs/InputUnion.md
+#[allow(missing_docs)]
+#[input_twin(name = "SpanFilterInput")]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("SpanFilter"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(default, setter(into))))]
+pub struct SpanQuery {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[input(twin)]
+    pub span_term: Option<Box<SpanTermQuery>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[input(twin)]
+    pub span_near: Option<Box<SpanNearQuery>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[input(twin)]
+    pub span_first: Option<Box<SpanFirstQuery>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[input(twin)]
+    pub span_or: Option<Box<SpanOrQuery>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[input(twin)]
+    pub span_not: Option<Box<SpanNotQuery>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[input(twin)]
+    pub span_containing: Option<Box<SpanContainingQuery>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[input(twin)]
+    pub span_within: Option<Box<SpanWithinQuery>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[input(twin)]
+    pub span_multi: Option<Box<SpanMultiQuery>>,
+}
+
+impl SpanQuery {
+    /// Returns `true` if no field of this `SpanQuery` is defined.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.span_term.is_none()
+            && self.span_near.is_none()
+            && self.span_first.is_none()
+            && self.span_or.is_none()
+            && self.span_not.is_none()
+            && self.span_containing.is_none()
+            && self.span_within.is_none()
+            && self.span_multi.is_none()
+    }
+}
+
+impl From<SpanQuery> for Query {
+    /// Moves the span query to the root of a `Query`.
+    #[inline]
+    fn from(query: SpanQuery) -> Query {
+        Query {
+            span_term: query.span_term,
+            span_near: query.span_near,
+            span_first: query.span_first,
+            span_or: query.span_or,
+            span_not: query.span_not,
+            span_containing: query.span_containing,
+            span_within: query.span_within,
+            span_multi: query.span_multi,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl From<SpanQueryInput> for QueryInput {
+    /// Moves the span query to the root of a `QueryInput`.
+    #[inline]
+    fn from(query: SpanQueryInput) -> QueryInput {
+        QueryInput {
+            span_term: query.span_term,
+            span_near: query.span_near,
+            span_first: query.span_first,
+            span_or: query.span_or,
+            span_not: query.span_not,
+            span_containing: query.span_containing,
+            span_within: query.span_within,
+            span_multi: query.span_multi,
+            ..Default::default()
+        }
+    }
+}
+
+/// Implements `From` each kind of span query (and its input) for
+/// [`SpanQuery`] (and [`SpanQueryInput`]).
+macro_rules! impl_from {
+    ($($field:ident: $ty:ident, $input:ident;)*) => {
+        $(
+            impl From<$ty> for SpanQuery {
+                #[inline]
+                fn from(query: $ty) -> SpanQuery {
+                    SpanQuery {
+                        $field: Some(Box::new(query)),
+                        ..Default::default()
+                    }
+                }
+            }
+
+            #[cfg(feature = "graphql")]
+            impl From<$input> for SpanQueryInput {
+                #[inline]
+                fn from(query: $input) -> SpanQueryInput {
+                    SpanQueryInput {
+                        $field: Some(Box::new(query)),
+                        ..Default::default()
+                    }
+                }
+            }
+        )*
+    };
+}
+
+/// A query nested directly in a span query, see [`SpanQuery::clauses`].
+pub(crate) enum SpanClause<'a> {
+    /// A span clause, e.g. one of the `clauses` of a `span_near` query.
+    Span(&'a SpanQuery),
+
+    /// The multi-term query of a `span_multi` query.
+    MultiTerm(&'a Query),
+}
+
+/// Returns the queries nested directly in the span query fields of `$query`,
+/// along with their paths relative to it, e.g. `span_near.clauses[0]`.
+macro_rules! span_clauses {
+    ($query:expr) => {{
+        let query = $query;
+        let mut clauses = vec![];
+        if let Some(ref span_near) = query.span_near {
+            for (i, clause) in span_near.clauses.iter().enumerate() {
+                let path = format!("span_near.clauses[{}]", i);
+                clauses.push((path, SpanClause::Span(clause)));
+            }
+        }
+        if let Some(ref span_first) = query.span_first {
+            let path = "span_first.match".to_string();
+            clauses.push((path, SpanClause::Span(&span_first.match_)));
+        }
+        if let Some(ref span_or) = query.span_or {
+            for (i, clause) in span_or.clauses.iter().enumerate() {
+                let path = format!("span_or.clauses[{}]", i);
+                clauses.push((path, SpanClause::Span(clause)));
+            }
+        }
+        if let Some(ref span_not) = query.span_not {
+            let include = "span_not.include".to_string();
+            clauses.push((include, SpanClause::Span(&span_not.include)));
+            let exclude = "span_not.exclude".to_string();
+            clauses.push((exclude, SpanClause::Span(&span_not.exclude)));
+        }
+        if let Some(ref span_containing) = query.span_containing {
+            let big = "span_containing.big".to_string();
+            clauses.push((big, SpanClause::Span(&span_containing.big)));
+            let little = "span_containing.little".to_string();
+            clauses.push((little, SpanClause::Span(&span_containing.little)));
+        }
+        if let Some(ref span_within) = query.span_within {
+            let big = "span_within.big".to_string();
+            clauses.push((big, SpanClause::Span(&span_within.big)));
+            let little = "span_within.little".to_string();
+            clauses.push((little, SpanClause::Span(&span_within.little)));
+        }
+        if let Some(ref span_multi) = query.span_multi {
+            let path = "span_multi.match".to_string();
+            clauses.push((path, SpanClause::MultiTerm(&span_multi.match_)));
+        }
+        clauses
+    }};
+}
+
+impl SpanQuery {
+    /// Returns the span clauses (and multi-term query) nested directly in
+    /// this span query, along with their paths relative to it.
+    pub(crate) fn clauses(&self) -> Vec<(String, SpanClause<'_>)> {
+        span_clauses!(self)
+    }
+}
+
+impl Query {
+    /// Returns the span clauses (and multi-term query) nested directly in the
+    /// span query of this `Query` (if any), along with their paths relative
+    /// to it.
+    pub(crate) fn span_clauses(&self) -> Vec<(String, SpanClause<'_>)> {
+        span_clauses!(self)
+    }
+}
+
+impl_from! {
+    span_term: SpanTermQuery, SpanTermQueryInput;
+    span_near: SpanNearQuery, SpanNearQueryInput;
+    span_first: SpanFirstQuery, SpanFirstQueryInput;
+    span_or: SpanOrQuery, SpanOrQueryInput;
+    span_not: SpanNotQuery, SpanNotQueryInput;
+    span_containing: SpanContainingQuery, SpanContainingQueryInput;
+    span_within: SpanWithinQuery, SpanWithinQueryInput;
+    span_multi: SpanMultiQuery, SpanMultiQueryInput;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::search::query::PrefixQuery;
+
+    #[test]
+    fn can_serialize_and_deserialize_nested_spans() {
+        let query = SpanQuery::from(SpanNotQuery::new(
+            SpanNearQuery::new(
+                vec![
+                    SpanQuery::from(SpanTermQuery::new("body", "quick")),
+                    SpanMultiQuery::new(PrefixQuery::new("body", "fox")).into(),
+                ],
+                1,
+            ),
+            SpanTermQuery::new("body", "brown"),
+        ));
+        let j = json!({
+            "span_not": {
+                "include": {
+                    "span_near": {
+                        "clauses": [
+                            { "span_term": { "body": { "value": "quick" } } },
+                            { "span_multi": { "match": { "prefix": { "body": { "value": "fox", "case_insensitive": false } } } } }
+                        ],
+                        "slop": 1
+                    }
+                },
+                "exclude": { "span_term": { "body": { "value": "brown" } } }
+            }
+        });
+
+        assert_eq!(serde_json::to_value(&query).unwrap(), j);
+        assert_eq!(serde_json::from_value::<SpanQuery>(j).unwrap(), query);
+    }
+
+    #[test]
+    fn can_move_to_the_root_of_a_query() {
+        let query = Query::from(SpanQuery::from(SpanTermQuery::new("user", "kimchy")));
+        assert_eq!(
+            serde_json::to_value(&query).unwrap(),
+            json!({ "span_term": { "user": { "value": "kimchy" } } })
+        );
+    }
+}
@@ -0,0 +1,55 @@
+//! [Span multi-term query](https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-span-multi-term-query.html)
+
+use elastiql_derive::input_twin;
+use serde::{Deserialize, Serialize};
+
+use super::super::Query;
+#[cfg(feature = "graphql")]
+use super::super::QueryInput;
+
+/// A [Span multi-term query] wraps a multi-term query, i.e. a `prefix`,
+/// `wildcard`, `regexp`, `fuzzy` or `range` query, so it can be used as a
+/// span query.
+///
+/// [Span multi-term query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-span-multi-term-query.html
+#[input_twin(name = "SpanMultiFilterInput")]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("SpanMultiFilter"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct SpanMultiQuery {
+    /// The multi-term query to match the terms of.
+    #[serde(rename = "match")]
+    #[input(twin)]
+    pub match_: Query,
+}
+
+impl SpanMultiQuery {
+    /// Constructs a new `SpanMultiQuery` wrapping a multi-term query.
+    #[inline]
+    pub fn new(match_: impl Into<Query>) -> Self {
+        SpanMultiQuery {
+            match_: match_.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::search::query::PrefixQuery;
+
+    #[test]
+    fn can_serialize_and_deserialize() {
+        let query = SpanMultiQuery::new(PrefixQuery::new("user.id", "ki"));
+        let j = json!({ "match": { "prefix": { "user.id": { "value": "ki", "case_insensitive": false } } } });
+
+        assert_eq!(serde_json::to_value(&query).unwrap(), j);
+        assert_eq!(serde_json::from_value::<SpanMultiQuery>(j).unwrap(), query);
+    }
+}
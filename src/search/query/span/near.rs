@@ -0,0 +1,100 @@
+//! [Span near query](https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-span-near-query.html)
+
+use elastiql_derive::input_twin;
+use serde::{Deserialize, Serialize};
+
+use super::SpanQuery;
+#[cfg(feature = "graphql")]
+use super::SpanQueryInput;
+
+/// A [Span near query] matches spans which are near one another, i.e. with at
+/// most `slop` unmatched positions between them.
+///
+/// [Span near query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-span-near-query.html
+#[input_twin(name = "SpanNearFilterInput")]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("SpanNearFilter"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct SpanNearQuery {
+    /// The span queries to match near one another.
+    #[input(twin)]
+    pub clauses: Vec<SpanQuery>,
+
+    /// The maximum number of intervening unmatched positions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub slop: Option<u32>,
+
+    /// Whether the `clauses` must match in order. (Defaults to `true`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub in_order: Option<bool>,
+
+    /// Floating point number used to decrease or increase the relevance scores
+    /// of the query. (Defaults to `1.0`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub boost: Option<f64>,
+}
+
+impl SpanNearQuery {
+    /// Constructs a new `SpanNearQuery` of `clauses` with at most `slop`
+    /// unmatched positions between them.
+    #[inline]
+    pub fn new<T: Into<SpanQuery>>(clauses: impl IntoIterator<Item = T>, slop: u32) -> Self {
+        SpanNearQuery {
+            clauses: clauses.into_iter().map(Into::into).collect(),
+            slop: Some(slop),
+            in_order: None,
+            boost: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::search::query::span::SpanTermQuery;
+
+    #[test]
+    fn can_serialize_and_deserialize() {
+        let query = SpanNearQuery {
+            in_order: Some(false),
+            ..SpanNearQuery::new(
+                vec![
+                    SpanTermQuery::new("field", "value1"),
+                    SpanTermQuery::new("field", "value2"),
+                    SpanTermQuery::new("field", "value3"),
+                ],
+                12,
+            )
+        };
+        let j = json!({
+            "clauses": [
+                { "span_term": { "field": { "value": "value1" } } },
+                { "span_term": { "field": { "value": "value2" } } },
+                { "span_term": { "field": { "value": "value3" } } }
+            ],
+            "slop": 12,
+            "in_order": false
+        });
+
+        assert_eq!(serde_json::to_value(&query).unwrap(), j);
+        assert_eq!(serde_json::from_value::<SpanNearQuery>(j).unwrap(), query);
+    }
+
+    #[test]
+    fn deserialize_invalid_values_is_err() {
+        let j = r#"{ "slop": 1 }"#;
+        assert!(serde_json::from_str::<SpanNearQuery>(j).is_err(), "{}", &j);
+
+        let j = r#"{ "clauses": [], "slop": -1 }"#;
+        assert!(serde_json::from_str::<SpanNearQuery>(j).is_err(), "{}", &j);
+    }
+}
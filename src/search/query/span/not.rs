@@ -0,0 +1,103 @@
+//! [Span not query](https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-span-not-query.html)
+
+use elastiql_derive::input_twin;
+use serde::{Deserialize, Serialize};
+
+use super::SpanQuery;
+#[cfg(feature = "graphql")]
+use super::SpanQueryInput;
+
+/// A [Span not query] matches the spans of `include` which don't overlap
+/// (or aren't within `pre` positions before or `post` positions after) the
+/// spans of `exclude`.
+///
+/// [Span not query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-span-not-query.html
+#[input_twin(name = "SpanNotFilterInput")]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("SpanNotFilter"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct SpanNotQuery {
+    /// The span query whose spans are matched.
+    #[input(twin)]
+    pub include: SpanQuery,
+
+    /// The span query whose spans may not overlap the matched spans.
+    #[input(twin)]
+    pub exclude: SpanQuery,
+
+    /// The number of positions before the matched spans the spans of
+    /// `exclude` may not overlap. (Defaults to `0`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub pre: Option<u32>,
+
+    /// The number of positions after the matched spans the spans of `exclude`
+    /// may not overlap. (Defaults to `0`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub post: Option<u32>,
+
+    /// Shorthand for setting both `pre` and `post` to the same value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub dist: Option<u32>,
+
+    /// Floating point number used to decrease or increase the relevance scores
+    /// of the query. (Defaults to `1.0`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub boost: Option<f64>,
+}
+
+impl SpanNotQuery {
+    /// Constructs a new `SpanNotQuery` for the spans of `include` which don't
+    /// overlap the spans of `exclude`.
+    #[inline]
+    pub fn new(include: impl Into<SpanQuery>, exclude: impl Into<SpanQuery>) -> Self {
+        SpanNotQuery {
+            include: include.into(),
+            exclude: exclude.into(),
+            pre: None,
+            post: None,
+            dist: None,
+            boost: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::search::query::span::SpanTermQuery;
+
+    #[test]
+    fn can_serialize_and_deserialize() {
+        let query = SpanNotQuery {
+            dist: Some(1),
+            ..SpanNotQuery::new(
+                SpanTermQuery::new("field1", "hoya"),
+                SpanTermQuery::new("field1", "la"),
+            )
+        };
+        let j = json!({
+            "include": { "span_term": { "field1": { "value": "hoya" } } },
+            "exclude": { "span_term": { "field1": { "value": "la" } } },
+            "dist": 1
+        });
+
+        assert_eq!(serde_json::to_value(&query).unwrap(), j);
+        assert_eq!(serde_json::from_value::<SpanNotQuery>(j).unwrap(), query);
+    }
+
+    #[test]
+    fn deserialize_invalid_values_is_err() {
+        let j = r#"{ "include": { "span_term": { "field1": "hoya" } } }"#;
+        assert!(serde_json::from_str::<SpanNotQuery>(j).is_err(), "{}", &j);
+    }
+}
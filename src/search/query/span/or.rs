@@ -0,0 +1,67 @@
+//! [Span or query](https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-span-or-query.html)
+
+use elastiql_derive::input_twin;
+use serde::{Deserialize, Serialize};
+
+use super::SpanQuery;
+#[cfg(feature = "graphql")]
+use super::SpanQueryInput;
+
+/// A [Span or query] matches the union of the spans of its `clauses`.
+///
+/// [Span or query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-span-or-query.html
+#[input_twin(name = "SpanOrFilterInput")]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("SpanOrFilter"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct SpanOrQuery {
+    /// The span queries of which any may match.
+    #[input(twin)]
+    pub clauses: Vec<SpanQuery>,
+
+    /// Floating point number used to decrease or increase the relevance scores
+    /// of the query. (Defaults to `1.0`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub boost: Option<f64>,
+}
+
+impl SpanOrQuery {
+    /// Constructs a new `SpanOrQuery` of `clauses`.
+    #[inline]
+    pub fn new<T: Into<SpanQuery>>(clauses: impl IntoIterator<Item = T>) -> Self {
+        SpanOrQuery {
+            clauses: clauses.into_iter().map(Into::into).collect(),
+            boost: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::search::query::span::SpanTermQuery;
+
+    #[test]
+    fn can_serialize_and_deserialize() {
+        let query = SpanOrQuery::new(vec![
+            SpanTermQuery::new("field", "value1"),
+            SpanTermQuery::new("field", "value2"),
+        ]);
+        let j = json!({
+            "clauses": [
+                { "span_term": { "field": { "value": "value1" } } },
+                { "span_term": { "field": { "value": "value2" } } }
+            ]
+        });
+
+        assert_eq!(serde_json::to_value(&query).unwrap(), j);
+        assert_eq!(serde_json::from_value::<SpanOrQuery>(j).unwrap(), query);
+    }
+}
@@ -0,0 +1,177 @@
+//! [Span term query](https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-span-term-query.html)
+
+use std::fmt;
+
+use elastiql_derive::{input_twin, twin_impl};
+#[cfg(feature = "schemars")]
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::de::{self, MapAccess, Visitor};
+use serde::ser::{SerializeMap, Serializer};
+use serde::{Deserialize, Serialize};
+
+use super::super::term::Term;
+
+#[allow(clippy::missing_docs_in_private_items)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize)]
+struct InnerSpanTermQuery {
+    value: Term,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    boost: Option<f64>,
+}
+
+/// The body of a `SpanTermQuery`, which Elasticsearch also accepts as just the
+/// term itself, e.g. `{ "user.id": "kimchy" }`.
+#[allow(clippy::missing_docs_in_private_items)]
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SpanTermQueryBody {
+    Short(Term),
+    Long(InnerSpanTermQuery),
+}
+
+/// A [Span term query] matches the spans of an **exact** term in a provided
+/// field, i.e. the span query equivalent of a `term` query.
+///
+/// [Span term query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-span-term-query.html
+#[input_twin(name = "SpanTermFilterInput")]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("SpanTermFilter"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct SpanTermQuery {
+    /// The name of the field to query.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::field))]
+    pub field: String,
+
+    /// The term you wish to find in the provided field, which must exactly
+    /// match a token of the field value.
+    pub value: String,
+
+    /// Floating point number used to decrease or increase the relevance scores
+    /// of the query. (Defaults to `1.0`.)
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::option_float))]
+    pub boost: Option<f64>,
+}
+
+impl SpanTermQuery {
+    /// Constructs a new `SpanTermQuery`.
+    #[inline]
+    pub fn new(field: impl Into<String>, value: impl Into<String>) -> SpanTermQuery {
+        SpanTermQuery {
+            field: field.into(),
+            value: value.into(),
+            boost: None,
+        }
+    }
+}
+
+// TODO: re-use the serializer from the input type
+#[twin_impl]
+impl Serialize for SpanTermQuery {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(1))?;
+
+        let inner = InnerSpanTermQuery {
+            value: Term(self.value.to_owned()),
+            boost: self.boost,
+        };
+
+        map.serialize_entry(&self.field, &inner)?;
+
+        map.end()
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl JsonSchema for SpanTermQuery {
+    #[inline]
+    fn schema_name() -> String {
+        "SpanTermQuery".to_string()
+    }
+
+    #[inline]
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        crate::schema::keyed(InnerSpanTermQuery::json_schema(gen))
+    }
+}
+
+/// Visits a `SpanTermQuery` during deserialization.
+struct SpanTermQueryVisitor;
+
+impl<'de> serde::Deserialize<'de> for SpanTermQuery {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<SpanTermQuery, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(SpanTermQueryVisitor)
+    }
+}
+
+impl<'de> Visitor<'de> for SpanTermQueryVisitor {
+    type Value = SpanTermQuery;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a `SpanTermQuery`")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let field = map
+            .next_key::<String>()?
+            .ok_or_else(|| de::Error::missing_field("field"))?;
+
+        Ok(match map.next_value()? {
+            SpanTermQueryBody::Short(Term(value)) => SpanTermQuery::new(field, value),
+            SpanTermQueryBody::Long(inner) => SpanTermQuery {
+                field,
+                value: inner.value.0,
+                boost: inner.boost,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn can_serialize_and_deserialize() {
+        let query = SpanTermQuery {
+            boost: Some(2.0),
+            ..SpanTermQuery::new("user.id", "kimchy")
+        };
+        let j = json!({ "user.id": { "value": "kimchy", "boost": 2.0 } });
+
+        assert_eq!(serde_json::to_value(&query).unwrap(), j);
+        assert_eq!(serde_json::from_value::<SpanTermQuery>(j).unwrap(), query);
+    }
+
+    #[test]
+    fn can_deserialize_short_form() {
+        let j = json!({ "user.id": "kimchy" });
+        assert_eq!(
+            serde_json::from_value::<SpanTermQuery>(j).unwrap(),
+            SpanTermQuery::new("user.id", "kimchy")
+        );
+    }
+
+    #[test]
+    fn deserialize_invalid_values_is_err() {
+        let j = r#"{ "user.id": null }"#;
+        assert!(serde_json::from_str::<SpanTermQuery>(j).is_err(), "{}", &j);
+
+        let j = r#"{ "user.id": { "boost": 2.0 } }"#;
+        assert!(serde_json::from_str::<SpanTermQuery>(j).is_err(), "{}", &j);
+    }
+}
@@ -0,0 +1,76 @@
+//! [Span within query](https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-span-within-query.html)
+
+use elastiql_derive::input_twin;
+use serde::{Deserialize, Serialize};
+
+use super::SpanQuery;
+#[cfg(feature = "graphql")]
+use super::SpanQueryInput;
+
+/// A [Span within query] matches the spans of `little` which are within a
+/// span of `big`.
+///
+/// [Span within query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-span-within-query.html
+#[input_twin(name = "SpanWithinFilterInput")]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("SpanWithinFilter"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct SpanWithinQuery {
+    /// The span query whose spans the matched spans must be within.
+    #[input(twin)]
+    pub big: SpanQuery,
+
+    /// The span query whose spans are matched.
+    #[input(twin)]
+    pub little: SpanQuery,
+
+    /// Floating point number used to decrease or increase the relevance scores
+    /// of the query. (Defaults to `1.0`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub boost: Option<f64>,
+}
+
+impl SpanWithinQuery {
+    /// Constructs a new `SpanWithinQuery` for the spans of `little` which are
+    /// within a span of `big`.
+    #[inline]
+    pub fn new(big: impl Into<SpanQuery>, little: impl Into<SpanQuery>) -> Self {
+        SpanWithinQuery {
+            big: big.into(),
+            little: little.into(),
+            boost: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::search::query::span::SpanTermQuery;
+
+    #[test]
+    fn can_serialize_and_deserialize() {
+        let query = SpanWithinQuery {
+            boost: Some(2.0),
+            ..SpanWithinQuery::new(
+                SpanTermQuery::new("field1", "bar"),
+                SpanTermQuery::new("field1", "foo"),
+            )
+        };
+        let j = json!({
+            "big": { "span_term": { "field1": { "value": "bar" } } },
+            "little": { "span_term": { "field1": { "value": "foo" } } },
+            "boost": 2.0
+        });
+
+        assert_eq!(serde_json::to_value(&query).unwrap(), j);
+        assert_eq!(serde_json::from_value::<SpanWithinQuery>(j).unwrap(), query);
+    }
+}
@@ -0,0 +1,498 @@
+//! Queries with `{{placeholder}}` values that are substituted when rendered,
+//! a lightweight, in-process alternative to Elasticsearch [search templates].
+//!
+//! [search templates]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-template.html
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    error::Error,
+    fmt, mem,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+use super::span::SpanTermQuery;
+use super::{
+    FuzzyQuery, GeoBoundingBoxQuery, GeoDistanceQuery, IdsQuery, MatchPhrasePrefixQuery,
+    MatchPhraseQuery, MatchQuery, MoreLikeThisQuery, MultiMatchQuery, PrefixQuery, Query,
+    QueryStringQuery, QueryTransformer, RangeQuery, RegexpQuery, SimpleQueryStringQuery, TermQuery,
+    TermsQuery, TermsSetQuery, WildcardQuery,
+};
+use crate::scalars::SortedValue;
+
+/// The reason a [`QueryTemplate`] failed to render.
+#[derive(Clone, PartialEq, Debug)]
+pub enum TemplateError {
+    /// A placeholder has no matching parameter.
+    Unbound(String),
+    /// A value contains an unclosed or empty placeholder, e.g. `{{id` or `{{}}`.
+    Malformed(String),
+    /// A parameter can't be substituted where its placeholder is used, e.g. a
+    /// `null` or an array used anywhere but as one of the values of a `terms`,
+    /// `terms_set` or `ids` query.
+    InvalidParam(String),
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateError::Unbound(name) => write!(f, "placeholder `{}` is not bound", name),
+            TemplateError::Malformed(value) => {
+                write!(f, "`{}` contains a malformed placeholder", value)
+            }
+            TemplateError::InvalidParam(name) => {
+                write!(f, "parameter `{}` can't be used here", name)
+            }
+        }
+    }
+}
+
+impl Error for TemplateError {}
+
+/// A [`Query`] whose values may contain `{{placeholders}}` that are
+/// substituted with parameters when the template is rendered.
+///
+/// Placeholders can be used in the values (but not the fields) of `term`,
+/// `terms`, `terms_set`, `ids`, `range`, `prefix`, `wildcard`, `regexp`,
+/// `fuzzy`, `span_term`, `match`, `match_phrase`, `match_phrase_prefix`,
+/// `multi_match`, `simple_query_string` and `query_string` queries, in the
+/// `distance` of `geo_distance` queries, the `wkt` of `geo_bounding_box`
+/// queries and the `like` and `unlike` texts of `more_like_this` queries,
+/// either as the whole value or embedded in it, e.g. `user-{{id}}`. A `terms`,
+/// `terms_set` or `ids` value consisting of only a placeholder whose parameter
+/// is an array is expanded into all of its elements.
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use elastiql::search::{
+///     dsl::{self, QueryExt},
+///     query::{Query, QueryTemplate},
+/// };
+///
+/// let template = QueryTemplate::new(
+///     dsl::term("customer_id", "{{customer_id}}").and(dsl::terms("status", vec!["{{statuses}}"])),
+/// );
+///
+/// let mut params = HashMap::new();
+/// params.insert("customer_id".to_string(), 42u64.into());
+/// params.insert("statuses".to_string(), serde_json::json!(["open", "pending"]).into());
+///
+/// let query: Query = template.render(&params)?;
+/// # Ok::<(), elastiql::search::query::TemplateError>(())
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(transparent)]
+pub struct QueryTemplate {
+    query: Query,
+}
+
+impl QueryTemplate {
+    /// Constructs a new `QueryTemplate` from a query with placeholders.
+    #[inline]
+    pub fn new(query: impl Into<Query>) -> Self {
+        QueryTemplate {
+            query: query.into(),
+        }
+    }
+
+    /// Returns the query with its placeholders left as is.
+    #[inline]
+    pub fn query(&self) -> &Query {
+        &self.query
+    }
+
+    /// Returns the names of all of the placeholders in this template.
+    pub fn placeholders(&self) -> Result<BTreeSet<String>, TemplateError> {
+        let mut renderer = Renderer::new(None);
+        self.query.clone().transform(&mut renderer);
+        renderer.finish().map(|()| renderer.placeholders)
+    }
+
+    /// Returns the query with all of its placeholders substituted with the
+    /// matching `params`.
+    ///
+    /// Returns an error if any placeholder isn't bound, i.e. doesn't have a
+    /// parameter. Parameters that aren't used are ignored.
+    pub fn render(&self, params: &HashMap<String, SortedValue>) -> Result<Query, TemplateError> {
+        let mut query = self.query.clone();
+        let mut renderer = Renderer::new(Some(params));
+        query.transform(&mut renderer);
+        renderer.finish().map(|()| query)
+    }
+}
+
+impl<T: Into<Query>> From<T> for QueryTemplate {
+    #[inline]
+    fn from(query: T) -> Self {
+        QueryTemplate::new(query)
+    }
+}
+
+/// Returns the name of the placeholder that makes up all of `value`, if any.
+#[inline]
+fn whole_placeholder(value: &str) -> Option<&str> {
+    let name = value.strip_prefix("{{")?.strip_suffix("}}")?;
+    if name.contains("{{") || name.contains("}}") {
+        None
+    } else {
+        Some(name.trim())
+    }
+}
+
+/// Returns a parameter as it is substituted into a value.
+#[inline]
+fn scalar(name: &str, value: &SortedValue) -> Result<String, TemplateError> {
+    match value {
+        SortedValue::Bool(value) => Ok(value.to_string()),
+        SortedValue::Int(value) => Ok(value.to_string()),
+        SortedValue::NegInt(value) => Ok(value.to_string()),
+        SortedValue::Float(value) => Ok(value.to_string()),
+        SortedValue::String(value) => Ok(value.to_owned()),
+        SortedValue::Json(JsonValue::Bool(value)) => Ok(value.to_string()),
+        SortedValue::Json(JsonValue::Number(value)) => Ok(value.to_string()),
+        SortedValue::Json(JsonValue::String(value)) => Ok(value.to_owned()),
+        _ => Err(TemplateError::InvalidParam(name.to_string())),
+    }
+}
+
+/// A [`QueryTransformer`] that collects and substitutes placeholders.
+struct Renderer<'a> {
+    /// The parameters to substitute, or `None` to only collect placeholders.
+    params: Option<&'a HashMap<String, SortedValue>>,
+    placeholders: BTreeSet<String>,
+    error: Option<TemplateError>,
+}
+
+impl<'a> Renderer<'a> {
+    #[inline]
+    fn new(params: Option<&'a HashMap<String, SortedValue>>) -> Self {
+        Renderer {
+            params,
+            placeholders: BTreeSet::new(),
+            error: None,
+        }
+    }
+
+    /// Returns the first error encountered, if any.
+    #[inline]
+    fn finish(&mut self) -> Result<(), TemplateError> {
+        self.error.take().map_or(Ok(()), Err)
+    }
+
+    /// Returns the parameter for the placeholder `name`.
+    fn param(&mut self, name: &str) -> Result<Option<&'a SortedValue>, TemplateError> {
+        self.placeholders.insert(name.to_string());
+        match self.params {
+            Some(params) => params
+                .get(name)
+                .map(Some)
+                .ok_or_else(|| TemplateError::Unbound(name.to_string())),
+            None => Ok(None),
+        }
+    }
+
+    /// Substitutes all of the placeholders in `value`.
+    fn substitute(&mut self, value: &str) -> Result<String, TemplateError> {
+        let mut rendered = String::with_capacity(value.len());
+        let mut rest = value;
+
+        while let Some(start) = rest.find("{{") {
+            rendered.push_str(&rest[..start]);
+
+            let after = &rest[start + 2..];
+            let end = after
+                .find("}}")
+                .ok_or_else(|| TemplateError::Malformed(value.to_string()))?;
+
+            let name = after[..end].trim();
+            if name.is_empty() {
+                return Err(TemplateError::Malformed(value.to_string()));
+            }
+
+            match self.param(name)? {
+                Some(param) => rendered.push_str(&scalar(name, param)?),
+                None => rendered.push_str(&rest[start..start + 2 + end + 2]),
+            }
+
+            rest = &after[end + 2..];
+        }
+        rendered.push_str(rest);
+
+        Ok(rendered)
+    }
+
+    /// Substitutes the placeholders in `value` in place, remembering the first
+    /// error.
+    fn string(&mut self, value: &mut String) {
+        if self.error.is_some() {
+            return;
+        }
+
+        match self.substitute(value) {
+            Ok(rendered) => *value = rendered,
+            Err(err) => self.error = Some(err),
+        }
+    }
+
+    /// Substitutes the placeholders in an optional `value` in place.
+    #[inline]
+    fn option(&mut self, value: &mut Option<String>) {
+        if let Some(ref mut value) = value {
+            self.string(value);
+        }
+    }
+
+    /// Substitutes the placeholders in `values` in place, expanding the
+    /// placeholders that make up a whole value and are arrays into their
+    /// elements.
+    fn values(&mut self, values: &mut Vec<String>) {
+        let mut expanded = Vec::with_capacity(values.len());
+
+        for mut value in mem::take(values) {
+            let array =
+                whole_placeholder(&value).and_then(|name| match self.params?.get(name)? {
+                    SortedValue::Json(JsonValue::Array(array)) => Some((name, array)),
+                    _ => None,
+                });
+
+            match array {
+                Some((name, array)) => {
+                    self.placeholders.insert(name.to_string());
+                    for element in array {
+                        match scalar(name, &element.clone().into()) {
+                            Ok(element) => expanded.push(element),
+                            Err(err) => {
+                                self.error.get_or_insert(err);
+                            }
+                        }
+                    }
+                }
+                None => {
+                    self.string(&mut value);
+                    expanded.push(value);
+                }
+            }
+        }
+
+        *values = expanded;
+    }
+}
+
+impl QueryTransformer for Renderer<'_> {
+    fn transform_term(&mut self, query: &mut TermQuery) {
+        self.string(&mut query.value);
+    }
+
+    fn transform_terms(&mut self, query: &mut TermsQuery) {
+        self.values(&mut query.values);
+    }
+
+    fn transform_terms_set(&mut self, query: &mut TermsSetQuery) {
+        self.values(&mut query.terms);
+    }
+
+    fn transform_ids(&mut self, query: &mut IdsQuery) {
+        self.values(&mut query.values);
+    }
+
+    fn transform_range(&mut self, query: &mut RangeQuery) {
+        self.option(&mut query.greater_than);
+        self.option(&mut query.greater_than_or_equal_to);
+        self.option(&mut query.less_than);
+        self.option(&mut query.less_than_or_equal_to);
+    }
+
+    fn transform_prefix(&mut self, query: &mut PrefixQuery) {
+        self.string(&mut query.value);
+    }
+
+    fn transform_wildcard(&mut self, query: &mut WildcardQuery) {
+        self.string(&mut query.value);
+    }
+
+    fn transform_regexp(&mut self, query: &mut RegexpQuery) {
+        self.string(&mut query.value);
+    }
+
+    fn transform_fuzzy(&mut self, query: &mut FuzzyQuery) {
+        self.string(&mut query.value);
+    }
+
+    fn transform_span_term(&mut self, query: &mut SpanTermQuery) {
+        self.string(&mut query.value);
+    }
+
+    fn transform_geo_distance(&mut self, query: &mut GeoDistanceQuery) {
+        self.string(&mut query.distance);
+    }
+
+    fn transform_geo_bounding_box(&mut self, query: &mut GeoBoundingBoxQuery) {
+        self.option(&mut query.wkt);
+    }
+
+    fn transform_match(&mut self, query: &mut MatchQuery) {
+        self.string(&mut query.query);
+    }
+
+    fn transform_match_phrase(&mut self, query: &mut MatchPhraseQuery) {
+        self.string(&mut query.query);
+    }
+
+    fn transform_match_phrase_prefix(&mut self, query: &mut MatchPhrasePrefixQuery) {
+        self.string(&mut query.query);
+    }
+
+    fn transform_multi_match(&mut self, query: &mut MultiMatchQuery) {
+        self.string(&mut query.query);
+    }
+
+    fn transform_more_like_this(&mut self, query: &mut MoreLikeThisQuery) {
+        for item in query.like.iter_mut().chain(query.unlike.iter_mut()) {
+            self.option(&mut item.text);
+        }
+    }
+
+    fn transform_simple_query_string(&mut self, query: &mut SimpleQueryStringQuery) {
+        self.string(&mut query.query);
+    }
+
+    fn transform_query_string(&mut self, query: &mut QueryStringQuery) {
+        self.string(&mut query.query);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::search::dsl::{self, QueryExt};
+
+    fn params(j: JsonValue) -> HashMap<String, SortedValue> {
+        serde_json::from_value(j).unwrap()
+    }
+
+    macro_rules! test_case {
+        ($name:ident : $template:expr, $params:expr, $expected:expr) => {
+            #[test]
+            fn $name() {
+                let template = QueryTemplate::new($template);
+                let query = template.render(&params($params)).unwrap();
+                assert_eq!(query, Query::from($expected));
+            }
+        };
+    }
+
+    test_case!(
+        whole_value:
+        dsl::term("customer_id", "{{customer_id}}"),
+        json!({ "customer_id": 42 }),
+        dsl::term("customer_id", "42")
+    );
+
+    test_case!(
+        embedded_value:
+        dsl::prefix("user", "{{ tenant }}-{{user}}"),
+        json!({ "tenant": "acme", "user": "ki" }),
+        dsl::prefix("user", "acme-ki")
+    );
+
+    test_case!(
+        range:
+        dsl::range("created").gte("{{since}}").lt("now"),
+        json!({ "since": "now-7d" }),
+        dsl::range("created").gte("now-7d").lt("now")
+    );
+
+    test_case!(
+        expanded_terms:
+        dsl::terms("status", vec!["closed", "{{statuses}}"]),
+        json!({ "statuses": ["open", "pending"] }),
+        dsl::terms("status", vec!["closed", "open", "pending"])
+    );
+
+    test_case!(
+        nested_clauses:
+        dsl::term("enabled", "{{enabled}}").and(dsl::match_("message", "{{text}}").not()),
+        json!({ "enabled": true, "text": "error", "unused": null }),
+        dsl::term("enabled", "true").and(dsl::match_("message", "error").not())
+    );
+
+    #[test]
+    fn can_list_placeholders() {
+        let template = QueryTemplate::new(
+            dsl::term("customer_id", "{{customer_id}}")
+                .and(dsl::terms("status", vec!["{{statuses}}"]))
+                .and(dsl::range("created").gte("{{ since }}").lte("{{until}}")),
+        );
+
+        let placeholders: Vec<_> = template.placeholders().unwrap().into_iter().collect();
+        assert_eq!(
+            placeholders,
+            vec!["customer_id", "since", "statuses", "until"]
+        );
+    }
+
+    #[test]
+    fn unbound_placeholders_are_err() {
+        let template = QueryTemplate::new(
+            dsl::term("customer_id", "{{customer_id}}").and(dsl::term("user", "{{user}}")),
+        );
+
+        assert_eq!(
+            template.render(&params(json!({ "customer_id": 1 }))),
+            Err(TemplateError::Unbound("user".to_string()))
+        );
+    }
+
+    #[test]
+    fn malformed_placeholders_are_err() {
+        for value in &["{{user", "{{}}", "a {{ }} b"] {
+            let template = QueryTemplate::new(dsl::term("user", *value));
+            assert_eq!(
+                template.render(&params(json!({ "user": "kimchy" }))),
+                Err(TemplateError::Malformed(value.to_string())),
+                "{}",
+                value
+            );
+            assert!(template.placeholders().is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn invalid_params_are_err() {
+        let template = QueryTemplate::new(dsl::term("user", "{{user}}"));
+        for user in &[json!(null), json!(["a", "b"]), json!({ "a": "b" })] {
+            assert_eq!(
+                template.render(&params(json!({ "user": user }))),
+                Err(TemplateError::InvalidParam("user".to_string())),
+                "{}",
+                user
+            );
+        }
+
+        let template = QueryTemplate::new(dsl::terms("user", vec!["{{users}}"]));
+        assert_eq!(
+            template.render(&params(json!({ "users": [["nested"]] }))),
+            Err(TemplateError::InvalidParam("users".to_string()))
+        );
+    }
+
+    #[test]
+    fn can_round_trip_through_json() {
+        let template = QueryTemplate::new(dsl::term("customer_id", "{{customer_id}}"));
+
+        let j = serde_json::to_value(&template).unwrap();
+        assert_eq!(
+            j,
+            json!({ "term": { "customer_id": { "value": "{{customer_id}}" } } })
+        );
+        assert_eq!(
+            serde_json::from_value::<QueryTemplate>(j).unwrap(),
+            template
+        );
+    }
+}
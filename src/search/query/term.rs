@@ -2,28 +2,47 @@
 
 use std::fmt;
 
+use elastiql_derive::{input_twin, twin_impl};
+#[cfg(feature = "schemars")]
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
 use serde::de::{self, MapAccess, Visitor};
-use serde::ser::{SerializeMap, Serializer};
+use serde::ser::Serializer;
 use serde::{Deserialize, Serialize};
 
+use super::TermQueryRef;
+
 #[allow(clippy::missing_docs_in_private_items)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[derive(Serialize, Deserialize)]
 struct InnerTermQuery {
-    value: String,
+    value: Term,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     boost: Option<f64>,
 }
 
+/// The body of a `TermQuery`, which Elasticsearch also accepts as just the
+/// term itself, e.g. `{ "user": "kimchy" }`.
+#[allow(clippy::missing_docs_in_private_items)]
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TermQueryBody {
+    Short(Term),
+    Long(InnerTermQuery),
+}
+
 /// A [Term query] returns documents that contain an **exact** term in a provided field.
 ///
 /// [Term query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-term-query.html#query-dsl-term-query
-#[cfg(feature = "graphql")]
+#[input_twin(name = "TermFilterInput")]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("TermFilter"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 #[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(async_graphql::InputObject, Clone, Debug)]
-#[graphql(name = "TermFilterInput")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
-pub struct TermQueryInput {
+pub struct TermQuery {
     /// The name of the field to query.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::field))]
     pub field: String,
 
     /// A list of terms you wish to find in the provided field. To return a
@@ -46,15 +65,15 @@ pub struct TermQueryInput {
     ///
     /// [relevance scores]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-filter-context.html#relevance-scores
     #[cfg_attr(feature = "builder", builder(default))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::option_float))]
     pub boost: Option<f64>,
 }
 
-#[cfg(feature = "graphql")]
-impl TermQueryInput {
-    /// Constructs a new `TermQueryInput`.
+impl TermQuery {
+    /// Constructs a new `TermQuery`.
     #[inline]
     pub fn new(field: impl Into<String>, value: impl Into<String>) -> Self {
-        TermQueryInput {
+        TermQuery {
             field: field.into(),
             value: value.into(),
             boost: None,
@@ -63,108 +82,100 @@ impl TermQueryInput {
 }
 
 #[cfg(feature = "graphql")]
-impl From<TermQuery> for TermQueryInput {
+impl TermQueryInput {
+    /// Constructs a new `TermQueryInput`.
     #[inline]
-    fn from(query: TermQuery) -> Self {
-        Self {
-            field: query.field,
-            value: query.value,
-            boost: query.boost,
+    pub fn new(field: impl Into<String>, value: impl Into<String>) -> Self {
+        TermQueryInput {
+            field: field.into(),
+            value: value.into(),
+            boost: None,
         }
     }
 }
 
-#[cfg(feature = "graphql")]
-impl Serialize for TermQueryInput {
+#[twin_impl]
+impl Serialize for TermQuery {
     #[inline]
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        let mut map = serializer.serialize_map(Some(1))?;
-
-        let inner = InnerTermQuery {
-            value: self.value.to_owned(),
-            boost: self.boost,
-        };
+        TermQueryRef::from(self).serialize(serializer)
+    }
+}
 
-        map.serialize_entry(&self.field, &inner)?;
+#[cfg(feature = "schemars")]
+impl JsonSchema for TermQuery {
+    #[inline]
+    fn schema_name() -> String {
+        "TermQuery".to_string()
+    }
 
-        map.end()
+    #[inline]
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        crate::schema::keyed(InnerTermQuery::json_schema(gen))
     }
 }
 
-/// A [Term query] returns documents that contain an **exact** term in a provided field.
-///
-/// [Term query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-term-query.html#query-dsl-term-query
-#[cfg_attr(test, derive(PartialEq))]
-#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
-#[cfg_attr(feature = "graphql", graphql(name = "TermFilter"))]
-#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(Clone, Debug)]
-#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
-pub struct TermQuery {
-    /// The name of the field to query.
-    pub field: String,
+/// A term, which Elasticsearch accepts as a string, number or boolean, and is
+/// normalized to a string (e.g. `1.2` or `true`) when deserialized.
+#[derive(Serialize)]
+#[serde(transparent)]
+pub(super) struct Term(pub(super) String);
 
-    /// A list of terms you wish to find in the provided field. To return a
-    /// document, one or more terms must exactly match a field value, including
-    /// whitespace and capitalization.
-    ///
-    /// Although this field is a `String`, it will match `numerical` fields; e.g.
-    /// `"1.2"` will match fields containing the floating point value `1.2`.
-    pub value: String,
+impl<'de> Deserialize<'de> for Term {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Term, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        /// Visits a `Term` during deserialization.
+        struct TermVisitor;
 
-    /// Floating point number used to decrease or increase the
-    /// [relevance scores] of a query. (Defaults to `1.0`.)
-    ///
-    /// You can use the boost parameter to adjust relevance scores for searches
-    /// containing two or more queries.
-    ///
-    /// Boost values are relative to the default value of `1.0`. A boost value
-    /// between `0` and `1.0` decreases the relevance score. A value greater
-    /// than  `1.0` increases the relevance score.
-    ///
-    /// [relevance scores]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-filter-context.html#relevance-scores
-    #[cfg_attr(feature = "builder", builder(default))]
-    pub boost: Option<f64>,
-}
+        impl<'de> Visitor<'de> for TermVisitor {
+            type Value = Term;
 
-impl TermQuery {
-    /// Constructs a new `TermQuery`.
-    #[inline]
-    pub fn new(field: impl Into<String>, value: impl Into<String>) -> Self {
-        TermQuery {
-            field: field.into(),
-            value: value.into(),
-            boost: None,
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a string, number or boolean")
+            }
+
+            fn visit_bool<E: de::Error>(self, value: bool) -> Result<Self::Value, E> {
+                Ok(Term(value.to_string()))
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+                Ok(Term(value.to_string()))
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+                Ok(Term(value.to_string()))
+            }
+
+            fn visit_f64<E: de::Error>(self, value: f64) -> Result<Self::Value, E> {
+                Ok(Term(value.to_string()))
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                Ok(Term(value.to_string()))
+            }
+
+            fn visit_string<E: de::Error>(self, value: String) -> Result<Self::Value, E> {
+                Ok(Term(value))
+            }
         }
+
+        deserializer.deserialize_any(TermVisitor)
     }
 }
 
-#[cfg(feature = "graphql")]
-impl From<TermQueryInput> for TermQuery {
+#[cfg(feature = "schemars")]
+impl JsonSchema for Term {
     #[inline]
-    fn from(input: TermQueryInput) -> TermQuery {
-        TermQuery {
-            field: input.field,
-            value: input.value,
-            boost: input.boost,
-        }
+    fn schema_name() -> String {
+        "Term".to_string()
     }
-}
 
-// TODO: re-use the serializer from the input type
-impl Serialize for TermQuery {
     #[inline]
-    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        let mut map = serializer.serialize_map(Some(1))?;
-
-        let inner = InnerTermQuery {
-            value: self.value.to_owned(),
-            boost: self.boost,
-        };
-
-        map.serialize_entry(&self.field, &inner)?;
-
-        map.end()
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        crate::schema::term()
     }
 }
 
@@ -196,12 +207,15 @@ impl<'de> Visitor<'de> for TermQueryVisitor {
             .next_key::<String>()?
             .ok_or_else(|| de::Error::missing_field("field"))?;
 
-        let inner: InnerTermQuery = map.next_value()?;
+        let (value, boost) = match map.next_value()? {
+            TermQueryBody::Short(value) => (value, None),
+            TermQueryBody::Long(inner) => (inner.value, inner.boost),
+        };
 
         Ok(TermQuery {
             field,
-            value: inner.value.to_owned(),
-            boost: inner.boost,
+            value: value.0,
+            boost,
         })
     }
 }
@@ -260,6 +274,27 @@ mod tests {
         json!({ "user": { "value": "Kimchy" } })
     );
 
+    #[test]
+    fn can_deserialize_numbers_and_booleans() {
+        let j = json!({ "age": { "value": 42 } });
+        assert_eq!(
+            serde_json::from_value::<TermQuery>(j).unwrap(),
+            TermQuery::new("age", "42")
+        );
+
+        let j = json!({ "temperature": { "value": -1.5 } });
+        assert_eq!(
+            serde_json::from_value::<TermQuery>(j).unwrap(),
+            TermQuery::new("temperature", "-1.5")
+        );
+
+        let j = json!({ "active": { "value": true } });
+        assert_eq!(
+            serde_json::from_value::<TermQuery>(j).unwrap(),
+            TermQuery::new("active", "true")
+        );
+    }
+
     #[test]
     fn deserialize_invalid_boost_is_err() {
         let j = r#"{ "user": { "value": "Kimchy", "boost": "nan" } }"#;
@@ -279,11 +314,22 @@ mod tests {
     }
 
     #[test]
-    fn deserialize_missing_values_is_err() {
-        // TODO: should we support this Elasticsearch schema?
-        let j = r#"{ "user": "missing" }"#;
-        assert!(serde_json::from_str::<TermQuery>(j).is_err(), "{}", &j);
+    fn can_deserialize_short_form() {
+        let j = json!({ "user": "kimchy" });
+        assert_eq!(
+            serde_json::from_value::<TermQuery>(j).unwrap(),
+            TermQuery::new("user", "kimchy")
+        );
+
+        let j = json!({ "active": true });
+        assert_eq!(
+            serde_json::from_value::<TermQuery>(j).unwrap(),
+            TermQuery::new("active", "true")
+        );
+    }
 
+    #[test]
+    fn deserialize_missing_values_is_err() {
         let j = r#"{ "user": null }"#;
         assert!(serde_json::from_str::<TermQuery>(j).is_err(), "{}", &j);
 
@@ -296,15 +342,6 @@ mod tests {
         let j = r#"{ "user": { "value": null } }"#;
         assert!(serde_json::from_str::<TermQuery>(j).is_err(), "{}", &j);
 
-        let j = r#"{ "user": { "value": 1.1 } }"#;
-        assert!(serde_json::from_str::<TermQuery>(j).is_err(), "{}", &j);
-
-        let j = r#"{ "user": { "value": 1 } }"#;
-        assert!(serde_json::from_str::<TermQuery>(j).is_err(), "{}", &j);
-
-        let j = r#"{ "user": { "value": 999 } }"#;
-        assert!(serde_json::from_str::<TermQuery>(j).is_err(), "{}", &j);
-
         let j = r#"{ "user": { "value": [null] } }"#;
         assert!(serde_json::from_str::<TermQuery>(j).is_err(), "{}", &j);
 
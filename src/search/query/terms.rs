@@ -2,21 +2,29 @@
 
 use std::fmt;
 
+use elastiql_derive::{input_twin, twin_impl};
+#[cfg(feature = "schemars")]
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
 use serde::de::{self, MapAccess, Visitor};
 use serde::ser::{SerializeMap, Serializer};
 use serde::Serialize;
 
+use super::term::Term;
+
 /// A [Terms query] returns documents that contain one or more **exact** terms
 /// in a provided field.
 ///
 /// [Terms query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-terms-query.html#query-dsl-terms-query
-#[cfg(feature = "graphql")]
+#[input_twin(name = "TermsFilterInput")]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("TermsFilter"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 #[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(async_graphql::InputObject, Clone, Debug)]
-#[graphql(name = "TermsFilterInput")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
-pub struct TermsQueryInput {
+pub struct TermsQuery {
     /// The name of the field to query.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::field))]
     pub field: String,
 
     /// A list of terms you wish to find in the provided field. To return a
@@ -40,19 +48,19 @@ pub struct TermsQueryInput {
     ///
     /// [relevance scores]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-filter-context.html#relevance-scores
     #[cfg_attr(feature = "builder", builder(default))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::option_float))]
     pub boost: Option<f64>,
 }
 
-#[cfg(feature = "graphql")]
-impl TermsQueryInput {
-    /// Constructs a new `TermsQueryInput`.
+impl TermsQuery {
+    /// Constructs a new `TermsQuery`.
     #[inline]
     pub fn new<T: Into<String>>(
         field: impl Into<String>,
         // TODO: why can't this just be `impl Into<Vec<String>>`?
         values: impl IntoIterator<Item = T>,
     ) -> Self {
-        TermsQueryInput {
+        TermsQuery {
             field: field.into(),
             values: values.into_iter().map(Into::into).collect::<Vec<String>>(),
             boost: None,
@@ -61,77 +69,15 @@ impl TermsQueryInput {
 }
 
 #[cfg(feature = "graphql")]
-impl From<TermsQuery> for TermsQueryInput {
-    #[inline]
-    fn from(query: TermsQuery) -> Self {
-        Self {
-            field: query.field,
-            values: query.values,
-            boost: query.boost,
-        }
-    }
-}
-
-#[cfg(feature = "graphql")]
-impl Serialize for TermsQueryInput {
-    #[inline]
-    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        let mut map = serializer.serialize_map(Some(2))?;
-        map.serialize_entry(&self.field, &self.values)?;
-        if let Some(boost) = &self.boost {
-            map.serialize_entry("boost", &boost)?;
-        }
-        map.end()
-    }
-}
-
-/// A [Terms query] returns documents that contain one or more **exact** terms
-/// in a provided field.
-///
-/// [Terms query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-terms-query.html#query-dsl-terms-query
-#[cfg_attr(test, derive(PartialEq))]
-#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
-#[cfg_attr(feature = "graphql", graphql(name = "TermsFilter"))]
-#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(Clone, Debug)]
-#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
-pub struct TermsQuery {
-    /// The name of the field to query.
-    pub field: String,
-
-    /// A list of terms you wish to find in the provided field. To return a
-    /// document, one or more terms must exactly match a field value, including
-    /// whitespace and capitalization.
-    ///
-    /// Although this field is a `String`, it will match `numerical` fields; e.g.
-    /// `["1.2"]` will match fields containing the floating point value `1.2`.
-    #[cfg_attr(feature = "builder", builder(default))]
-    pub values: Vec<String>,
-
-    /// Floating point number used to decrease or increase the
-    /// [relevance scores] of a query. (Defaults to `1.0`.)
-    ///
-    /// You can use the boost parameter to adjust relevance scores for searches
-    /// containing two or more queries.
-    ///
-    /// Boost values are relative to the default value of `1.0`. A boost value
-    /// between `0` and `1.0` decreases the relevance score. A value greater
-    /// than  `1.0` increases the relevance score.
-    ///
-    /// [relevance scores]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-filter-context.html#relevance-scores
-    #[cfg_attr(feature = "builder", builder(default))]
-    pub boost: Option<f64>,
-}
-
-impl TermsQuery {
-    /// Constructs a new `TermsQuery`.
+impl TermsQueryInput {
+    /// Constructs a new `TermsQueryInput`.
     #[inline]
     pub fn new<T: Into<String>>(
         field: impl Into<String>,
         // TODO: why can't this just be `impl Into<Vec<String>>`?
         values: impl IntoIterator<Item = T>,
     ) -> Self {
-        TermsQuery {
+        TermsQueryInput {
             field: field.into(),
             values: values.into_iter().map(Into::into).collect::<Vec<String>>(),
             boost: None,
@@ -139,19 +85,8 @@ impl TermsQuery {
     }
 }
 
-#[cfg(feature = "graphql")]
-impl From<TermsQueryInput> for TermsQuery {
-    #[inline]
-    fn from(input: TermsQueryInput) -> TermsQuery {
-        TermsQuery {
-            field: input.field,
-            values: input.values,
-            boost: input.boost,
-        }
-    }
-}
-
 // TODO: re-use the serializer from the input type
+#[twin_impl]
 impl Serialize for TermsQuery {
     #[inline]
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
@@ -164,6 +99,25 @@ impl Serialize for TermsQuery {
     }
 }
 
+#[cfg(feature = "schemars")]
+impl JsonSchema for TermsQuery {
+    #[inline]
+    fn schema_name() -> String {
+        "TermsQuery".to_string()
+    }
+
+    #[inline]
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        // the values are keyed by the field, next to an optional `boost`
+        let mut schema = crate::schema::object(vec![("boost", gen.subschema_for::<f64>())], &[]);
+        let object = schema.object();
+        object.min_properties = Some(1);
+        object.max_properties = Some(2);
+        object.additional_properties = Some(Box::new(gen.subschema_for::<Vec<Term>>()));
+        schema.into()
+    }
+}
+
 /// Visits a `TermsQuery` during deserialization.
 struct TermsQueryVisitor;
 
@@ -206,7 +160,8 @@ impl<'de> Visitor<'de> for TermsQueryVisitor {
                 }
 
                 field = Some(key);
-                values = Some(access.next_value::<Vec<String>>()?);
+                let terms = access.next_value::<Vec<Term>>()?;
+                values = Some(terms.into_iter().map(|term| term.0).collect());
             }
         }
 
@@ -275,6 +230,21 @@ mod tests {
         json!({ "user": ["Kimchy", "elasticsearch"] })
     );
 
+    #[test]
+    fn can_deserialize_numbers_and_booleans() {
+        let j = json!({ "age": [1, -2, 3.5] });
+        assert_eq!(
+            serde_json::from_value::<TermsQuery>(j).unwrap(),
+            TermsQuery::new("age", vec!["1", "-2", "3.5"])
+        );
+
+        let j = json!({ "active": [true, false] });
+        assert_eq!(
+            serde_json::from_value::<TermsQuery>(j).unwrap(),
+            TermsQuery::new("active", vec!["true", "false"])
+        );
+    }
+
     #[test]
     fn deserialize_invalid_boost_is_err() {
         let j = r#"{ "user": { "value": "Kimchy", "boost": "nan" } }"#;
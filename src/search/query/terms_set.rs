@@ -0,0 +1,249 @@
+//! [Terms set query](https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-terms-set-query.html)
+
+use std::fmt;
+
+use elastiql_derive::{input_twin, twin_impl};
+#[cfg(feature = "schemars")]
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::de::{self, MapAccess, Visitor};
+use serde::ser::{SerializeMap, Serializer};
+use serde::{Deserialize, Serialize};
+
+use crate::search::Script;
+#[cfg(feature = "graphql")]
+use crate::search::ScriptInput;
+
+#[allow(clippy::missing_docs_in_private_items)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize)]
+#[serde(bound(deserialize = "S: Deserialize<'de>"))]
+struct InnerTermsSetQuery<S = Script> {
+    terms: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    minimum_should_match_field: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    minimum_should_match_script: Option<S>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    boost: Option<f64>,
+}
+
+/// A [Terms set query] returns documents that contain a minimum number of
+/// **exact** terms in a provided field, e.g. at least 2 of the requested tags.
+///
+/// **Note**: until GraphQL [Union input types] are supported the minimum
+/// number of matching terms must be specified *either* by the
+/// `minimum_should_match_field` *or* by the `minimum_should_match_script`.
+///
+/// [Terms set query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-terms-set-query.html
+/// [Union input types]: https://github.com/graphql/graphql-spec/blob/master/rfcs/InputUnion.md
+#[input_twin(name = "TermsSetFilterInput")]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("TermsSetFilter"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct TermsSetQuery {
+    /// The name of the field to query.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::field))]
+    pub field: String,
+
+    /// The terms you wish to find in the provided field, which must exactly
+    /// match a field value, including whitespace and capitalization.
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub terms: Vec<String>,
+
+    /// The numeric field of each document with the number of `terms` it must
+    /// contain.
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub minimum_should_match_field: Option<String>,
+
+    /// The script returning the number of `terms` a document must contain,
+    /// e.g. `Math.min(params.num_terms, doc['required_matches'].value)`.
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[input(twin)]
+    pub minimum_should_match_script: Option<Script>,
+
+    /// Floating point number used to decrease or increase the relevance scores
+    /// of the query. (Defaults to `1.0`.)
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::option_float))]
+    pub boost: Option<f64>,
+}
+
+impl TermsSetQuery {
+    /// Constructs a new `TermsSetQuery` where documents must contain as many
+    /// `terms` as their `minimum_should_match_field`.
+    #[inline]
+    pub fn new<T: Into<String>>(
+        field: impl Into<String>,
+        terms: impl IntoIterator<Item = T>,
+        minimum_should_match_field: impl Into<String>,
+    ) -> TermsSetQuery {
+        TermsSetQuery {
+            field: field.into(),
+            terms: terms.into_iter().map(Into::into).collect(),
+            minimum_should_match_field: Some(minimum_should_match_field.into()),
+            minimum_should_match_script: None,
+            boost: None,
+        }
+    }
+
+    /// Constructs a new `TermsSetQuery` where documents must contain as many
+    /// `terms` as `minimum_should_match_script` returns.
+    #[inline]
+    pub fn with_script<T: Into<String>>(
+        field: impl Into<String>,
+        terms: impl IntoIterator<Item = T>,
+        minimum_should_match_script: Script,
+    ) -> TermsSetQuery {
+        TermsSetQuery {
+            field: field.into(),
+            terms: terms.into_iter().map(Into::into).collect(),
+            minimum_should_match_field: None,
+            minimum_should_match_script: Some(minimum_should_match_script),
+            boost: None,
+        }
+    }
+}
+
+// TODO: re-use the serializer from the input type
+#[twin_impl]
+impl Serialize for TermsSetQuery {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(1))?;
+
+        let inner = InnerTermsSetQuery {
+            terms: self.terms.to_owned(),
+            minimum_should_match_field: self.minimum_should_match_field.to_owned(),
+            minimum_should_match_script: self.minimum_should_match_script.to_owned(),
+            boost: self.boost,
+        };
+
+        map.serialize_entry(&self.field, &inner)?;
+
+        map.end()
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl JsonSchema for TermsSetQuery {
+    #[inline]
+    fn schema_name() -> String {
+        "TermsSetQuery".to_string()
+    }
+
+    #[inline]
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        crate::schema::keyed(<InnerTermsSetQuery>::json_schema(gen))
+    }
+}
+
+/// Visits a `TermsSetQuery` during deserialization.
+struct TermsSetQueryVisitor;
+
+impl<'de> serde::Deserialize<'de> for TermsSetQuery {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<TermsSetQuery, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(TermsSetQueryVisitor)
+    }
+}
+
+impl<'de> Visitor<'de> for TermsSetQueryVisitor {
+    type Value = TermsSetQuery;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a `TermsSetQuery`")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let field = map
+            .next_key::<String>()?
+            .ok_or_else(|| de::Error::missing_field("field"))?;
+        let inner: InnerTermsSetQuery = map.next_value()?;
+
+        Ok(TermsSetQuery {
+            field,
+            terms: inner.terms,
+            minimum_should_match_field: inner.minimum_should_match_field,
+            minimum_should_match_script: inner.minimum_should_match_script,
+            boost: inner.boost,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    macro_rules! test_case {
+        ($name:ident : $f:expr, $j:expr) => {
+            mod $name {
+                use super::*;
+
+                #[test]
+                fn can_serialize() {
+                    assert_eq!(serde_json::to_value(&$f).unwrap(), $j);
+                }
+
+                #[test]
+                fn can_deserialize() {
+                    assert_eq!(serde_json::from_value::<TermsSetQuery>($j).unwrap(), $f);
+                }
+            }
+        };
+    }
+
+    test_case!(
+        minimum_should_match_field:
+        TermsSetQuery::new("programming_languages", vec!["c++", "java", "php"], "required_matches"),
+        json!({
+            "programming_languages": {
+                "terms": ["c++", "java", "php"],
+                "minimum_should_match_field": "required_matches"
+            }
+        })
+    );
+
+    test_case!(
+        minimum_should_match_script:
+        TermsSetQuery {
+            boost: Some(2.0),
+            ..TermsSetQuery::with_script(
+                "programming_languages",
+                vec!["c++", "java"],
+                Script::inline("Math.min(params.num_terms, doc['required_matches'].value)"),
+            )
+        },
+        json!({
+            "programming_languages": {
+                "terms": ["c++", "java"],
+                "minimum_should_match_script": {
+                    "source": "Math.min(params.num_terms, doc['required_matches'].value)"
+                },
+                "boost": 2.0
+            }
+        })
+    );
+
+    #[test]
+    fn deserialize_invalid_values_is_err() {
+        let j = r#"{ "tags": null }"#;
+        assert!(serde_json::from_str::<TermsSetQuery>(j).is_err(), "{}", &j);
+
+        let j = r#"{ "tags": { "minimum_should_match_field": "n" } }"#;
+        assert!(serde_json::from_str::<TermsSetQuery>(j).is_err(), "{}", &j);
+
+        let j = r#"{ "tags": { "terms": ["a"], "minimum_should_match_script": {} } }"#;
+        assert!(serde_json::from_str::<TermsSetQuery>(j).is_err(), "{}", &j);
+    }
+}
@@ -0,0 +1,907 @@
+//! A transformer for rewriting a (possibly deeply nested) [`Query`] tree in
+//! place.
+
+use super::span::{
+    SpanContainingQuery, SpanFirstQuery, SpanMultiQuery, SpanNearQuery, SpanNotQuery, SpanOrQuery,
+    SpanQuery, SpanTermQuery, SpanWithinQuery,
+};
+use super::{
+    BooleanQuery, CompoundQuery, ExistsQuery, FuzzyQuery, GeoBoundingBoxQuery, GeoDistanceQuery,
+    GeoPolygonQuery, GeoShapeQuery, IdsQuery, KnnQuery, MatchPhrasePrefixQuery, MatchPhraseQuery,
+    MatchQuery, MoreLikeThisQuery, MultiMatchQuery, NestedQuery, PercolateQuery, PrefixQuery,
+    Query, QueryStringQuery, RangeQuery, RankFeatureQuery, RegexpQuery, ScriptQuery,
+    ScriptScoreQuery, SimpleQueryStringQuery, TermQuery, TermsQuery, TermsSetQuery, WildcardQuery,
+};
+use crate::scalars::{GeoPoint, GeoShape};
+
+/// Rewrites each clause of a [`Query`] tree in place.
+///
+/// This is the mutable counterpart of [`QueryVisitor`](super::QueryVisitor).
+/// Every method has a no-op default implementation, and every method is called
+/// *before* the children of the clause are transformed; e.g. clauses added by
+/// [`QueryTransformer::transform_boolean`] are transformed too, and clauses
+/// removed by [`QueryTransformer::transform_query`] are never visited.
+///
+/// ```
+/// use elastiql::search::query::{BooleanQuery, CompoundQuery, QueryTransformer, TermQuery};
+///
+/// struct Tenant(&'static str);
+///
+/// impl QueryTransformer for Tenant {
+///     fn transform_boolean(&mut self, query: &mut BooleanQuery) {
+///         query.push(TermQuery::new("tenant", self.0));
+///     }
+/// }
+///
+/// let mut query = CompoundQuery::from(TermQuery::new("user", "kimchy"));
+/// query.transform(&mut Tenant("acme"));
+///
+/// assert_eq!(query.boolean.unwrap().filter.len(), 2);
+/// ```
+#[allow(unused_variables)]
+pub trait QueryTransformer {
+    /// Called for every [`Query`] in the tree, before its clause is
+    /// transformed.
+    #[inline]
+    fn transform_query(&mut self, query: &mut Query) {}
+
+    /// Called for every [`CompoundQuery`] in the tree, e.g. the query of a
+    /// `nested` query, before its clauses are transformed.
+    #[inline]
+    fn transform_compound(&mut self, query: &mut CompoundQuery) {}
+
+    /// Called for every `bool` query, before its clauses are transformed.
+    #[inline]
+    fn transform_boolean(&mut self, query: &mut BooleanQuery) {}
+
+    /// Called for every `nested` query, before its inner query is transformed.
+    #[inline]
+    fn transform_nested(&mut self, query: &mut NestedQuery) {}
+
+    /// Called for every `script_score` query, before its inner query is
+    /// transformed.
+    #[inline]
+    fn transform_script_score(&mut self, query: &mut ScriptScoreQuery) {}
+
+    /// Called for every `exists` query.
+    #[inline]
+    fn transform_exists(&mut self, query: &mut ExistsQuery) {}
+
+    /// Called for every `term` query.
+    #[inline]
+    fn transform_term(&mut self, query: &mut TermQuery) {}
+
+    /// Called for every `terms` query.
+    #[inline]
+    fn transform_terms(&mut self, query: &mut TermsQuery) {}
+
+    /// Called for every `terms_set` query.
+    #[inline]
+    fn transform_terms_set(&mut self, query: &mut TermsSetQuery) {}
+
+    /// Called for every `ids` query.
+    #[inline]
+    fn transform_ids(&mut self, query: &mut IdsQuery) {}
+
+    /// Called for every `range` query.
+    #[inline]
+    fn transform_range(&mut self, query: &mut RangeQuery) {}
+
+    /// Called for every `prefix` query.
+    #[inline]
+    fn transform_prefix(&mut self, query: &mut PrefixQuery) {}
+
+    /// Called for every `wildcard` query.
+    #[inline]
+    fn transform_wildcard(&mut self, query: &mut WildcardQuery) {}
+
+    /// Called for every `regexp` query.
+    #[inline]
+    fn transform_regexp(&mut self, query: &mut RegexpQuery) {}
+
+    /// Called for every `fuzzy` query.
+    #[inline]
+    fn transform_fuzzy(&mut self, query: &mut FuzzyQuery) {}
+
+    /// Called for every `match` query.
+    #[inline]
+    fn transform_match(&mut self, query: &mut MatchQuery) {}
+
+    /// Called for every `match_phrase` query.
+    #[inline]
+    fn transform_match_phrase(&mut self, query: &mut MatchPhraseQuery) {}
+
+    /// Called for every `match_phrase_prefix` query.
+    #[inline]
+    fn transform_match_phrase_prefix(&mut self, query: &mut MatchPhrasePrefixQuery) {}
+
+    /// Called for every `multi_match` query.
+    #[inline]
+    fn transform_multi_match(&mut self, query: &mut MultiMatchQuery) {}
+
+    /// Called for every `more_like_this` query.
+    #[inline]
+    fn transform_more_like_this(&mut self, query: &mut MoreLikeThisQuery) {}
+
+    /// Called for every `percolate` query.
+    #[inline]
+    fn transform_percolate(&mut self, query: &mut PercolateQuery) {}
+
+    /// Called for every `script` query.
+    #[inline]
+    fn transform_script(&mut self, query: &mut ScriptQuery) {}
+
+    /// Called for every `geo_distance` query.
+    #[inline]
+    fn transform_geo_distance(&mut self, query: &mut GeoDistanceQuery) {}
+
+    /// Called for every `geo_bounding_box` query.
+    #[inline]
+    fn transform_geo_bounding_box(&mut self, query: &mut GeoBoundingBoxQuery) {}
+
+    /// Called for every `geo_shape` query.
+    #[inline]
+    fn transform_geo_shape(&mut self, query: &mut GeoShapeQuery) {}
+
+    /// Called for every `geo_polygon` query.
+    #[inline]
+    fn transform_geo_polygon(&mut self, query: &mut GeoPolygonQuery) {}
+
+    /// Called for every `span_term` query.
+    #[inline]
+    fn transform_span_term(&mut self, query: &mut SpanTermQuery) {}
+
+    /// Called for every `span_near` query, before its clauses are rewritten.
+    #[inline]
+    fn transform_span_near(&mut self, query: &mut SpanNearQuery) {}
+
+    /// Called for every `span_first` query, before its inner span query is
+    /// rewritten.
+    #[inline]
+    fn transform_span_first(&mut self, query: &mut SpanFirstQuery) {}
+
+    /// Called for every `span_or` query, before its clauses are rewritten.
+    #[inline]
+    fn transform_span_or(&mut self, query: &mut SpanOrQuery) {}
+
+    /// Called for every `span_not` query, before its `include` and `exclude`
+    /// queries are rewritten.
+    #[inline]
+    fn transform_span_not(&mut self, query: &mut SpanNotQuery) {}
+
+    /// Called for every `span_containing` query, before its `big` and `little`
+    /// queries are rewritten.
+    #[inline]
+    fn transform_span_containing(&mut self, query: &mut SpanContainingQuery) {}
+
+    /// Called for every `span_within` query, before its `big` and `little`
+    /// queries are rewritten.
+    #[inline]
+    fn transform_span_within(&mut self, query: &mut SpanWithinQuery) {}
+
+    /// Called for every `span_multi` query, before its multi-term query is
+    /// rewritten.
+    #[inline]
+    fn transform_span_multi(&mut self, query: &mut SpanMultiQuery) {}
+
+    /// Called for every `simple_query_string` query.
+    #[inline]
+    fn transform_simple_query_string(&mut self, query: &mut SimpleQueryStringQuery) {}
+
+    /// Called for every `query_string` query.
+    #[inline]
+    fn transform_query_string(&mut self, query: &mut QueryStringQuery) {}
+
+    /// Called for every `knn` query.
+    #[inline]
+    fn transform_knn(&mut self, query: &mut KnnQuery) {}
+
+    /// Called for every `rank_feature` query.
+    #[inline]
+    fn transform_rank_feature(&mut self, query: &mut RankFeatureQuery) {}
+}
+
+/// A [`QueryTransformer`] that rewrites every field name (and `nested` path).
+struct FieldMapper<F>(F);
+
+impl<F: FnMut(&str) -> String> FieldMapper<F> {
+    #[inline]
+    fn map(&mut self, field: &mut String) {
+        *field = (self.0)(field);
+    }
+}
+
+impl<F: FnMut(&str) -> String> QueryTransformer for FieldMapper<F> {
+    fn transform_nested(&mut self, query: &mut NestedQuery) {
+        self.map(&mut query.path);
+    }
+
+    fn transform_exists(&mut self, query: &mut ExistsQuery) {
+        self.map(&mut query.field);
+    }
+
+    fn transform_term(&mut self, query: &mut TermQuery) {
+        self.map(&mut query.field);
+    }
+
+    fn transform_terms(&mut self, query: &mut TermsQuery) {
+        self.map(&mut query.field);
+    }
+
+    fn transform_terms_set(&mut self, query: &mut TermsSetQuery) {
+        self.map(&mut query.field);
+    }
+
+    fn transform_range(&mut self, query: &mut RangeQuery) {
+        self.map(&mut query.field);
+    }
+
+    fn transform_prefix(&mut self, query: &mut PrefixQuery) {
+        self.map(&mut query.field);
+    }
+
+    fn transform_wildcard(&mut self, query: &mut WildcardQuery) {
+        self.map(&mut query.field);
+    }
+
+    fn transform_regexp(&mut self, query: &mut RegexpQuery) {
+        self.map(&mut query.field);
+    }
+
+    fn transform_fuzzy(&mut self, query: &mut FuzzyQuery) {
+        self.map(&mut query.field);
+    }
+
+    fn transform_match(&mut self, query: &mut MatchQuery) {
+        self.map(&mut query.field);
+    }
+
+    fn transform_match_phrase(&mut self, query: &mut MatchPhraseQuery) {
+        self.map(&mut query.field);
+    }
+
+    fn transform_match_phrase_prefix(&mut self, query: &mut MatchPhrasePrefixQuery) {
+        self.map(&mut query.field);
+    }
+
+    fn transform_multi_match(&mut self, query: &mut MultiMatchQuery) {
+        query.fields.iter_mut().for_each(|field| self.map(field));
+    }
+
+    fn transform_more_like_this(&mut self, query: &mut MoreLikeThisQuery) {
+        query.fields.iter_mut().for_each(|field| self.map(field));
+    }
+
+    fn transform_percolate(&mut self, query: &mut PercolateQuery) {
+        self.map(&mut query.field);
+    }
+
+    fn transform_geo_distance(&mut self, query: &mut GeoDistanceQuery) {
+        self.map(&mut query.field);
+    }
+
+    fn transform_geo_bounding_box(&mut self, query: &mut GeoBoundingBoxQuery) {
+        self.map(&mut query.field);
+    }
+
+    fn transform_geo_shape(&mut self, query: &mut GeoShapeQuery) {
+        self.map(&mut query.field);
+    }
+
+    fn transform_geo_polygon(&mut self, query: &mut GeoPolygonQuery) {
+        self.map(&mut query.field);
+    }
+
+    fn transform_span_term(&mut self, query: &mut SpanTermQuery) {
+        self.map(&mut query.field);
+    }
+
+    fn transform_simple_query_string(&mut self, query: &mut SimpleQueryStringQuery) {
+        query.fields.iter_mut().for_each(|field| self.map(field));
+    }
+
+    fn transform_query_string(&mut self, query: &mut QueryStringQuery) {
+        query.fields.iter_mut().for_each(|field| self.map(field));
+        if let Some(ref mut field) = query.default_field {
+            self.map(field);
+        }
+    }
+
+    fn transform_knn(&mut self, query: &mut KnnQuery) {
+        self.map(&mut query.field);
+    }
+
+    fn transform_rank_feature(&mut self, query: &mut RankFeatureQuery) {
+        self.map(&mut query.field);
+    }
+}
+
+/// The value that [`ValueMasker`] replaces values with.
+const MASK: &str = "?";
+
+/// A [`QueryTransformer`] that replaces every value being searched for with
+/// [`MASK`], keeping the fields and the shape of the query.
+struct ValueMasker;
+
+impl QueryTransformer for ValueMasker {
+    fn transform_term(&mut self, query: &mut TermQuery) {
+        query.value = MASK.to_string();
+    }
+
+    fn transform_terms(&mut self, query: &mut TermsQuery) {
+        query.values = vec![MASK.to_string()];
+    }
+
+    fn transform_terms_set(&mut self, query: &mut TermsSetQuery) {
+        query.terms = vec![MASK.to_string()];
+    }
+
+    fn transform_ids(&mut self, query: &mut IdsQuery) {
+        query.values = vec![MASK.to_string()];
+    }
+
+    fn transform_range(&mut self, query: &mut RangeQuery) {
+        let bounds = vec![
+            &mut query.greater_than,
+            &mut query.greater_than_or_equal_to,
+            &mut query.less_than,
+            &mut query.less_than_or_equal_to,
+        ];
+        for bound in bounds.into_iter().flatten() {
+            *bound = MASK.to_string();
+        }
+    }
+
+    fn transform_prefix(&mut self, query: &mut PrefixQuery) {
+        query.value = MASK.to_string();
+    }
+
+    fn transform_wildcard(&mut self, query: &mut WildcardQuery) {
+        query.value = MASK.to_string();
+    }
+
+    fn transform_regexp(&mut self, query: &mut RegexpQuery) {
+        query.value = MASK.to_string();
+    }
+
+    fn transform_fuzzy(&mut self, query: &mut FuzzyQuery) {
+        query.value = MASK.to_string();
+    }
+
+    fn transform_match(&mut self, query: &mut MatchQuery) {
+        query.query = MASK.to_string();
+    }
+
+    fn transform_match_phrase(&mut self, query: &mut MatchPhraseQuery) {
+        query.query = MASK.to_string();
+    }
+
+    fn transform_match_phrase_prefix(&mut self, query: &mut MatchPhrasePrefixQuery) {
+        query.query = MASK.to_string();
+    }
+
+    fn transform_multi_match(&mut self, query: &mut MultiMatchQuery) {
+        query.query = MASK.to_string();
+    }
+
+    fn transform_more_like_this(&mut self, query: &mut MoreLikeThisQuery) {
+        for item in query.like.iter_mut().chain(query.unlike.iter_mut()) {
+            if item.text.is_some() {
+                item.text = Some(MASK.to_string());
+            }
+        }
+    }
+
+    // the values of a `Map` are only wrapped with the `graphql` feature
+    #[allow(clippy::useless_conversion)]
+    fn transform_percolate(&mut self, query: &mut PercolateQuery) {
+        for document in query.document.iter_mut().chain(query.documents.iter_mut()) {
+            for value in document.values_mut() {
+                *value = serde_json::json!(MASK).into();
+            }
+        }
+    }
+
+    fn transform_geo_distance(&mut self, query: &mut GeoDistanceQuery) {
+        query.point = GeoPoint::default();
+        query.distance = MASK.to_string();
+    }
+
+    fn transform_geo_bounding_box(&mut self, query: &mut GeoBoundingBoxQuery) {
+        query.top_left = query.top_left.map(|_| GeoPoint::default());
+        query.bottom_right = query.bottom_right.map(|_| GeoPoint::default());
+        query.wkt = query.wkt.as_ref().map(|_| MASK.to_string());
+        query.top = query.top.map(|_| 0.0);
+        query.left = query.left.map(|_| 0.0);
+        query.bottom = query.bottom.map(|_| 0.0);
+        query.right = query.right.map(|_| 0.0);
+    }
+
+    fn transform_geo_shape(&mut self, query: &mut GeoShapeQuery) {
+        // the shape itself is the value, so only its presence is kept
+        query.shape = query.shape.as_ref().map(|_| GeoShape::Point {
+            coordinates: [0.0, 0.0],
+        });
+        if let Some(ref mut indexed_shape) = query.indexed_shape {
+            indexed_shape.id = MASK.to_string();
+        }
+    }
+
+    fn transform_geo_polygon(&mut self, query: &mut GeoPolygonQuery) {
+        query.points = query.points.iter().map(|_| GeoPoint::default()).collect();
+    }
+
+    fn transform_span_term(&mut self, query: &mut SpanTermQuery) {
+        query.value = MASK.to_string();
+    }
+
+    fn transform_simple_query_string(&mut self, query: &mut SimpleQueryStringQuery) {
+        query.query = MASK.to_string();
+    }
+
+    fn transform_query_string(&mut self, query: &mut QueryStringQuery) {
+        query.query = MASK.to_string();
+    }
+
+    fn transform_knn(&mut self, query: &mut KnnQuery) {
+        query.query_vector.clear();
+    }
+}
+
+impl Query {
+    /// Rewrites this query and all of its nested clauses in place, calling the
+    /// matching method of `transformer` for each one.
+    pub fn transform(&mut self, transformer: &mut impl QueryTransformer) {
+        transformer.transform_query(self);
+
+        if let Some(ref mut query) = self.exists {
+            transformer.transform_exists(query);
+        }
+        if let Some(ref mut query) = self.term {
+            transformer.transform_term(query);
+        }
+        if let Some(ref mut query) = self.terms {
+            transformer.transform_terms(query);
+        }
+        if let Some(ref mut query) = self.terms_set {
+            transformer.transform_terms_set(query);
+        }
+        if let Some(ref mut query) = self.ids {
+            transformer.transform_ids(query);
+        }
+        if let Some(ref mut query) = self.range {
+            transformer.transform_range(query);
+        }
+        if let Some(ref mut query) = self.prefix {
+            transformer.transform_prefix(query);
+        }
+        if let Some(ref mut query) = self.wildcard {
+            transformer.transform_wildcard(query);
+        }
+        if let Some(ref mut query) = self.regexp {
+            transformer.transform_regexp(query);
+        }
+        if let Some(ref mut query) = self.fuzzy {
+            transformer.transform_fuzzy(query);
+        }
+        if let Some(ref mut query) = self.match_ {
+            transformer.transform_match(query);
+        }
+        if let Some(ref mut query) = self.match_phrase {
+            transformer.transform_match_phrase(query);
+        }
+        if let Some(ref mut query) = self.match_phrase_prefix {
+            transformer.transform_match_phrase_prefix(query);
+        }
+        if let Some(ref mut query) = self.multi_match {
+            transformer.transform_multi_match(query);
+        }
+        if let Some(ref mut query) = self.more_like_this {
+            transformer.transform_more_like_this(query);
+        }
+        if let Some(ref mut query) = self.percolate {
+            transformer.transform_percolate(query);
+        }
+        if let Some(ref mut query) = self.script {
+            transformer.transform_script(query);
+        }
+        if let Some(ref mut query) = self.geo_distance {
+            transformer.transform_geo_distance(query);
+        }
+        if let Some(ref mut query) = self.geo_bounding_box {
+            transformer.transform_geo_bounding_box(query);
+        }
+        if let Some(ref mut query) = self.geo_shape {
+            transformer.transform_geo_shape(query);
+        }
+        if let Some(ref mut query) = self.geo_polygon {
+            transformer.transform_geo_polygon(query);
+        }
+        if let Some(ref mut query) = self.span_term {
+            transformer.transform_span_term(query);
+        }
+        if let Some(ref mut query) = self.span_near {
+            query.transform(transformer);
+        }
+        if let Some(ref mut query) = self.span_first {
+            query.transform(transformer);
+        }
+        if let Some(ref mut query) = self.span_or {
+            query.transform(transformer);
+        }
+        if let Some(ref mut query) = self.span_not {
+            query.transform(transformer);
+        }
+        if let Some(ref mut query) = self.span_containing {
+            query.transform(transformer);
+        }
+        if let Some(ref mut query) = self.span_within {
+            query.transform(transformer);
+        }
+        if let Some(ref mut query) = self.span_multi {
+            query.transform(transformer);
+        }
+        if let Some(ref mut query) = self.simple_query_string {
+            transformer.transform_simple_query_string(query);
+        }
+        if let Some(ref mut query) = self.query_string {
+            transformer.transform_query_string(query);
+        }
+        if let Some(ref mut query) = self.knn {
+            transformer.transform_knn(query);
+        }
+        if let Some(ref mut query) = self.rank_feature {
+            transformer.transform_rank_feature(query);
+        }
+        if let Some(ref mut query) = self.script_score {
+            query.transform(transformer);
+        }
+        if let Some(ref mut query) = self.nested {
+            query.transform(transformer);
+        }
+        if let Some(ref mut query) = self.boolean {
+            query.transform(transformer);
+        }
+    }
+
+    /// Rewrites every field name (and `nested` path) referenced by this query,
+    /// e.g. to prefix them all with `metadata.`.
+    #[inline]
+    pub fn map_fields(&mut self, f: impl FnMut(&str) -> String) {
+        self.transform(&mut FieldMapper(f));
+    }
+
+    /// Replaces every value searched for by this query with `"?"`, e.g. the
+    /// `value` of a `term` query or the bounds of a `range` query, so that
+    /// only the fields and the shape of the query are left.
+    ///
+    /// The values of a `terms` query are replaced with a single `"?"` and the
+    /// vector of a `knn` query is cleared.
+    #[inline]
+    pub fn mask_values(&mut self) {
+        self.transform(&mut ValueMasker);
+    }
+
+    /// Returns a copy of this query with its values
+    /// [masked](Query::mask_values), e.g. so that it can be logged or traced
+    /// without leaking what was searched for.
+    #[inline]
+    pub fn redacted(&self) -> Query {
+        let mut query = self.clone();
+        query.mask_values();
+        query
+    }
+
+    /// Returns the [`fingerprint`](Query::fingerprint) of this query with its
+    /// values [masked](Query::mask_values), e.g. to group queries that only
+    /// differ by the values being searched for.
+    #[inline]
+    pub fn masked_fingerprint(&self) -> serde_json::Result<u64> {
+        self.redacted().fingerprint()
+    }
+}
+
+impl CompoundQuery {
+    /// Rewrites this query and all of its nested clauses in place.
+    ///
+    /// See [`Query::transform`].
+    pub fn transform(&mut self, transformer: &mut impl QueryTransformer) {
+        transformer.transform_compound(self);
+
+        if let Some(ref mut query) = self.boolean {
+            query.transform(transformer);
+        }
+        if let Some(ref mut query) = self.root {
+            query.transform(transformer);
+        }
+    }
+
+    /// Rewrites every field name (and `nested` path) referenced by this query.
+    ///
+    /// See [`Query::map_fields`].
+    #[inline]
+    pub fn map_fields(&mut self, f: impl FnMut(&str) -> String) {
+        self.transform(&mut FieldMapper(f));
+    }
+
+    /// Replaces every value searched for by this query with `"?"`.
+    ///
+    /// See [`Query::mask_values`].
+    #[inline]
+    pub fn mask_values(&mut self) {
+        self.transform(&mut ValueMasker);
+    }
+
+    /// Returns a copy of this query with its values masked.
+    ///
+    /// See [`Query::redacted`].
+    #[inline]
+    pub fn redacted(&self) -> CompoundQuery {
+        let mut query = self.clone();
+        query.mask_values();
+        query
+    }
+
+    /// Returns the fingerprint of this query with its values masked.
+    ///
+    /// See [`Query::masked_fingerprint`].
+    #[inline]
+    pub fn masked_fingerprint(&self) -> serde_json::Result<u64> {
+        self.redacted().fingerprint()
+    }
+}
+
+impl BooleanQuery {
+    /// Rewrites this query and all of its nested clauses in place, in `must`,
+    /// `filter`, `should`, `must_not` order.
+    ///
+    /// See [`Query::transform`].
+    pub fn transform(&mut self, transformer: &mut impl QueryTransformer) {
+        transformer.transform_boolean(self);
+
+        let clauses = self
+            .must
+            .iter_mut()
+            .chain(&mut self.filter)
+            .chain(&mut self.should)
+            .chain(&mut self.must_not);
+        for query in clauses {
+            query.transform(transformer);
+        }
+    }
+}
+
+impl NestedQuery {
+    /// Rewrites this query and its inner query in place.
+    ///
+    /// See [`Query::transform`].
+    pub fn transform(&mut self, transformer: &mut impl QueryTransformer) {
+        transformer.transform_nested(self);
+        self.query.transform(transformer);
+    }
+}
+
+impl ScriptScoreQuery {
+    /// Rewrites this query and its inner query in place.
+    ///
+    /// See [`Query::transform`].
+    pub fn transform(&mut self, transformer: &mut impl QueryTransformer) {
+        transformer.transform_script_score(self);
+        self.query.transform(transformer);
+    }
+}
+
+impl SpanQuery {
+    /// Rewrites this span query and all of its nested clauses in place.
+    ///
+    /// See [`Query::transform`].
+    pub fn transform(&mut self, transformer: &mut impl QueryTransformer) {
+        if let Some(ref mut query) = self.span_term {
+            transformer.transform_span_term(query);
+        }
+        if let Some(ref mut query) = self.span_near {
+            query.transform(transformer);
+        }
+        if let Some(ref mut query) = self.span_first {
+            query.transform(transformer);
+        }
+        if let Some(ref mut query) = self.span_or {
+            query.transform(transformer);
+        }
+        if let Some(ref mut query) = self.span_not {
+            query.transform(transformer);
+        }
+        if let Some(ref mut query) = self.span_containing {
+            query.transform(transformer);
+        }
+        if let Some(ref mut query) = self.span_within {
+            query.transform(transformer);
+        }
+        if let Some(ref mut query) = self.span_multi {
+            query.transform(transformer);
+        }
+    }
+}
+
+impl SpanNearQuery {
+    /// Rewrites this query and its nested clauses in place.
+    ///
+    /// See [`Query::transform`].
+    pub fn transform(&mut self, transformer: &mut impl QueryTransformer) {
+        transformer.transform_span_near(self);
+        for clause in &mut self.clauses {
+            clause.transform(transformer);
+        }
+    }
+}
+
+impl SpanFirstQuery {
+    /// Rewrites this query and its nested clauses in place.
+    ///
+    /// See [`Query::transform`].
+    pub fn transform(&mut self, transformer: &mut impl QueryTransformer) {
+        transformer.transform_span_first(self);
+        self.match_.transform(transformer);
+    }
+}
+
+impl SpanOrQuery {
+    /// Rewrites this query and its nested clauses in place.
+    ///
+    /// See [`Query::transform`].
+    pub fn transform(&mut self, transformer: &mut impl QueryTransformer) {
+        transformer.transform_span_or(self);
+        for clause in &mut self.clauses {
+            clause.transform(transformer);
+        }
+    }
+}
+
+impl SpanNotQuery {
+    /// Rewrites this query and its nested clauses in place.
+    ///
+    /// See [`Query::transform`].
+    pub fn transform(&mut self, transformer: &mut impl QueryTransformer) {
+        transformer.transform_span_not(self);
+        self.include.transform(transformer);
+        self.exclude.transform(transformer);
+    }
+}
+
+impl SpanContainingQuery {
+    /// Rewrites this query and its nested clauses in place.
+    ///
+    /// See [`Query::transform`].
+    pub fn transform(&mut self, transformer: &mut impl QueryTransformer) {
+        transformer.transform_span_containing(self);
+        self.big.transform(transformer);
+        self.little.transform(transformer);
+    }
+}
+
+impl SpanWithinQuery {
+    /// Rewrites this query and its nested clauses in place.
+    ///
+    /// See [`Query::transform`].
+    pub fn transform(&mut self, transformer: &mut impl QueryTransformer) {
+        transformer.transform_span_within(self);
+        self.big.transform(transformer);
+        self.little.transform(transformer);
+    }
+}
+
+impl SpanMultiQuery {
+    /// Rewrites this query and its nested clauses in place.
+    ///
+    /// See [`Query::transform`].
+    pub fn transform(&mut self, transformer: &mut impl QueryTransformer) {
+        transformer.transform_span_multi(self);
+        self.match_.transform(transformer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_map_fields() {
+        let inner = TermQuery::new("comments.author", "kimchy");
+
+        let mut query = BooleanQuery::from(ExistsQuery::new("user"));
+        query
+            .must_not
+            .push(NestedQuery::new("comments", inner, false).into());
+        let mut query = CompoundQuery::from(query);
+
+        query.map_fields(|field| format!("metadata.{}", field));
+
+        let expected = serde_json::json!({
+            "bool": {
+                "filter": [{ "exists": { "field": "metadata.user" } }],
+                "must_not": [{
+                    "nested": {
+                        "path": "metadata.comments",
+                        "query": {
+                            "bool": {
+                                "filter": [{
+                                    "term": { "metadata.comments.author": { "value": "kimchy" } }
+                                }]
+                            }
+                        },
+                        "ignore_unmapped": false
+                    }
+                }]
+            }
+        });
+        assert_eq!(serde_json::to_value(&query).unwrap(), expected);
+    }
+
+    #[test]
+    fn can_strip_queries() {
+        struct NoRegexp;
+
+        impl QueryTransformer for NoRegexp {
+            fn transform_boolean(&mut self, query: &mut BooleanQuery) {
+                query.filter.retain(|query| query.regexp.is_none());
+            }
+        }
+
+        let mut query = BooleanQuery::from(RegexpQuery::new("user", "k.*y", None::<String>));
+        query.push(TermQuery::new("user", "kimchy"));
+        let mut query = CompoundQuery::from(query);
+
+        query.transform(&mut NoRegexp);
+
+        let filter = query.boolean.unwrap().filter;
+        assert_eq!(filter.len(), 1);
+        assert!(filter[0].term.is_some());
+    }
+
+    #[test]
+    fn can_mask_values() {
+        let mut query = BooleanQuery::from(TermsQuery::new("status", vec!["open", "closed"]));
+        query.push(RangeQuery::new("age").gte(18));
+        query.push(ExistsQuery::new("user"));
+        let mut query = CompoundQuery::from(query);
+
+        query.mask_values();
+
+        let expected = serde_json::json!({
+            "bool": {
+                "filter": [
+                    { "terms": { "status": ["?"] } },
+                    { "range": { "age": { "gte": "?" } } },
+                    { "exists": { "field": "user" } }
+                ]
+            }
+        });
+        assert_eq!(serde_json::to_value(&query).unwrap(), expected);
+    }
+
+    #[test]
+    fn redacting_leaves_the_original() {
+        let query = Query::from(MatchQuery::new("email", "kimchy@elastic.co"));
+
+        let redacted = query.redacted();
+        assert_eq!(redacted.match_.unwrap().query, "?");
+        assert_eq!(query.match_.unwrap().query, "kimchy@elastic.co");
+    }
+
+    #[test]
+    fn masked_fingerprints_ignore_values() {
+        let a = Query::from(TermQuery::new("user", "kimchy"));
+        let b = Query::from(TermQuery::new("user", "elastic"));
+        let c = Query::from(TermQuery::new("author", "kimchy"));
+
+        assert_ne!(a.fingerprint().unwrap(), b.fingerprint().unwrap());
+        assert_eq!(
+            a.masked_fingerprint().unwrap(),
+            b.masked_fingerprint().unwrap()
+        );
+        assert_ne!(
+            a.masked_fingerprint().unwrap(),
+            c.masked_fingerprint().unwrap()
+        );
+    }
+}
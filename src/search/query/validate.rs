@@ -0,0 +1,654 @@
+//! Policies for rejecting expensive or disallowed queries, e.g. queries built
+//! by untrusted GraphQL clients.
+
+use std::{error::Error, fmt};
+
+use super::span::SpanClause;
+use super::{
+    BooleanQuery, CompoundQuery, Query, QueryKind, QueryStringQuery, RegexpQuery, WildcardQuery,
+};
+#[cfg(feature = "graphql")]
+use super::{BooleanQueryInput, CompoundQueryInput, QueryInput};
+
+/// The default `max_determinized_states` Elasticsearch uses for `regexp`
+/// queries.
+const DEFAULT_MAX_DETERMINIZED_STATES: u64 = 10_000;
+
+/// Limits that a [`Query`] must satisfy to pass [`validate`].
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct ValidationPolicy {
+    /// The maximum nesting depth of the query; e.g. a single `term` query has a
+    /// depth of `1` and a `bool` query containing it has a depth of `2`.
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub max_depth: Option<usize>,
+
+    /// The maximum total number of (leaf and compound) queries.
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub max_clauses: Option<usize>,
+
+    /// The kinds of queries that aren't allowed anywhere in the query.
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub denied: Vec<QueryKind>,
+
+    /// Whether to allow patterns starting with a wildcard, e.g. `*ing` in a
+    /// `wildcard` or `query_string` query or `.*ing` in a `regexp` query,
+    /// which need to check every term in the index. (Defaults to `true`.)
+    #[cfg_attr(feature = "builder", builder(default = true))]
+    pub allow_leading_wildcards: bool,
+
+    /// The maximum number of automaton states `regexp` and `query_string`
+    /// queries may use.
+    ///
+    /// **Note**: `regexp` queries always use the Elasticsearch default of
+    /// `10000` states.
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub max_determinized_states: Option<u64>,
+}
+
+impl Default for ValidationPolicy {
+    #[inline]
+    fn default() -> Self {
+        ValidationPolicy {
+            max_depth: None,
+            max_clauses: None,
+            denied: vec![],
+            allow_leading_wildcards: true,
+            max_determinized_states: None,
+        }
+    }
+}
+
+/// The reason a [`Query`] failed [`validate`].
+#[derive(Clone, PartialEq, Debug)]
+pub enum ValidationError {
+    /// The query is nested more than `max_depth` levels deep.
+    TooDeep {
+        /// The maximum allowed depth.
+        max_depth: usize,
+    },
+    /// The query has more than `max_clauses` queries.
+    TooManyClauses {
+        /// The maximum allowed number of queries.
+        max_clauses: usize,
+    },
+    /// The query contains a denied kind of query.
+    Denied(QueryKind),
+    /// The query contains a pattern starting with a wildcard.
+    LeadingWildcard {
+        /// The kind of query containing the pattern.
+        kind: QueryKind,
+        /// The pattern starting with a wildcard.
+        pattern: String,
+    },
+    /// The query may use more than `max_determinized_states` automaton states.
+    TooManyDeterminizedStates {
+        /// The kind of query using the states.
+        kind: QueryKind,
+        /// The maximum allowed number of states.
+        max_determinized_states: u64,
+        /// The number of states the query may use.
+        states: u64,
+    },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::TooDeep { max_depth } => {
+                write!(f, "query is nested more than {} levels deep", max_depth)
+            }
+            ValidationError::TooManyClauses { max_clauses } => {
+                write!(f, "query has more than {} clauses", max_clauses)
+            }
+            ValidationError::Denied(kind) => write!(f, "`{}` queries are not allowed", kind),
+            ValidationError::LeadingWildcard { kind, pattern } => write!(
+                f,
+                "`{}` query pattern `{}` starts with a wildcard",
+                kind, pattern
+            ),
+            ValidationError::TooManyDeterminizedStates {
+                kind,
+                max_determinized_states,
+                states,
+            } => write!(
+                f,
+                "`{}` query may use {} automaton states but at most {} are allowed",
+                kind, states, max_determinized_states
+            ),
+        }
+    }
+}
+
+impl Error for ValidationError {}
+
+/// Checks that `query` and all of its nested clauses satisfy `policy`.
+#[inline]
+pub fn validate(query: &Query, policy: &ValidationPolicy) -> Result<(), ValidationError> {
+    Validator::new(policy).query(query, 1)
+}
+
+impl Query {
+    /// Checks that this query and all of its nested clauses satisfy `policy`.
+    ///
+    /// See [`validate`].
+    #[inline]
+    pub fn validate(&self, policy: &ValidationPolicy) -> Result<(), ValidationError> {
+        validate(self, policy)
+    }
+}
+
+impl CompoundQuery {
+    /// Checks that this query and all of its nested clauses satisfy `policy`.
+    ///
+    /// See [`validate`].
+    #[inline]
+    pub fn validate(&self, policy: &ValidationPolicy) -> Result<(), ValidationError> {
+        Validator::new(policy).compound(self, 1)
+    }
+}
+
+/// Tracks the state of a single [`validate`] call.
+struct Validator<'a> {
+    policy: &'a ValidationPolicy,
+    clauses: usize,
+}
+
+impl<'a> Validator<'a> {
+    #[inline]
+    fn new(policy: &'a ValidationPolicy) -> Self {
+        Validator { policy, clauses: 0 }
+    }
+
+    fn query(&mut self, query: &Query, depth: usize) -> Result<(), ValidationError> {
+        self.clause(query.kinds(), depth)?;
+
+        if let Some(ref regexp) = query.regexp {
+            self.regexp(regexp)?;
+        }
+        if let Some(ref wildcard) = query.wildcard {
+            self.wildcard(wildcard)?;
+        }
+        if let Some(ref query_string) = query.query_string {
+            self.query_string(query_string)?;
+        }
+        self.spans(query.span_clauses(), depth)?;
+        if let Some(ref script_score) = query.script_score {
+            self.compound(&script_score.query, depth + 1)?;
+        }
+        if let Some(ref nested) = query.nested {
+            self.compound(&nested.query, depth + 1)?;
+        }
+        if let Some(ref boolean) = query.boolean {
+            self.boolean(boolean, depth)?;
+        }
+
+        Ok(())
+    }
+
+    /// Counts a (leaf or compound) query of the given `kinds` at `depth`.
+    fn clause(&mut self, kinds: Vec<QueryKind>, depth: usize) -> Result<(), ValidationError> {
+        self.clauses += 1;
+
+        if let Some(max_clauses) = self.policy.max_clauses {
+            if self.clauses > max_clauses {
+                return Err(ValidationError::TooManyClauses { max_clauses });
+            }
+        }
+        if let Some(max_depth) = self.policy.max_depth {
+            if depth > max_depth {
+                return Err(ValidationError::TooDeep { max_depth });
+            }
+        }
+
+        for kind in kinds {
+            if self.policy.denied.contains(&kind) {
+                return Err(ValidationError::Denied(kind));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validates the queries nested in a span query at `depth`.
+    fn spans(
+        &mut self,
+        clauses: Vec<(String, SpanClause<'_>)>,
+        depth: usize,
+    ) -> Result<(), ValidationError> {
+        for (_, clause) in clauses {
+            match clause {
+                SpanClause::Span(span) => {
+                    self.clause(span.kinds(), depth + 1)?;
+                    self.spans(span.clauses(), depth + 1)?;
+                }
+                SpanClause::MultiTerm(query) => self.query(query, depth + 1)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn compound(&mut self, query: &CompoundQuery, depth: usize) -> Result<(), ValidationError> {
+        if let Some(ref root) = query.root {
+            self.query(root, depth)?;
+        }
+
+        match query.boolean {
+            Some(ref boolean) => {
+                if self.policy.denied.contains(&QueryKind::Boolean) {
+                    return Err(ValidationError::Denied(QueryKind::Boolean));
+                }
+                self.boolean(boolean, depth)
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn boolean(&mut self, query: &BooleanQuery, depth: usize) -> Result<(), ValidationError> {
+        let clauses = query
+            .must
+            .iter()
+            .chain(&query.filter)
+            .chain(&query.should)
+            .chain(&query.must_not);
+        for clause in clauses {
+            self.query(clause, depth + 1)?;
+        }
+
+        Ok(())
+    }
+
+    fn regexp(&self, query: &RegexpQuery) -> Result<(), ValidationError> {
+        let kind = QueryKind::Regexp;
+
+        if !self.policy.allow_leading_wildcards
+            && (query.value.starts_with(".*") || query.value.starts_with(".+"))
+        {
+            return Err(ValidationError::LeadingWildcard {
+                kind,
+                pattern: query.value.clone(),
+            });
+        }
+
+        self.determinized_states(kind, DEFAULT_MAX_DETERMINIZED_STATES)
+    }
+
+    fn wildcard(&self, query: &WildcardQuery) -> Result<(), ValidationError> {
+        if !self.policy.allow_leading_wildcards
+            && (query.value.starts_with('*') || query.value.starts_with('?'))
+        {
+            return Err(ValidationError::LeadingWildcard {
+                kind: QueryKind::Wildcard,
+                pattern: query.value.clone(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn query_string(&self, query: &QueryStringQuery) -> Result<(), ValidationError> {
+        let kind = QueryKind::QueryString;
+
+        if !self.policy.allow_leading_wildcards && query.allow_leading_wildcard {
+            let is_separator = |c: char| c.is_whitespace() || "()[]{}:\"^~".contains(c);
+            let pattern = query
+                .query
+                .split(is_separator)
+                .find(|term| term.starts_with('*') || term.starts_with('?'));
+
+            if let Some(pattern) = pattern {
+                return Err(ValidationError::LeadingWildcard {
+                    kind,
+                    pattern: pattern.to_string(),
+                });
+            }
+        }
+
+        self.determinized_states(kind, query.max_determinized_states)
+    }
+
+    #[inline]
+    fn determinized_states(&self, kind: QueryKind, states: u64) -> Result<(), ValidationError> {
+        match self.policy.max_determinized_states {
+            Some(max_determinized_states) if states > max_determinized_states => {
+                Err(ValidationError::TooManyDeterminizedStates {
+                    kind,
+                    max_determinized_states,
+                    states,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The reason a [`QueryInput`] failed [`QueryInput::validate`].
+///
+/// [`QueryInput`]: super::QueryInput
+#[cfg(feature = "graphql")]
+#[derive(Clone, PartialEq, Debug)]
+pub struct QueryInputError {
+    /// The kinds of all of the defined fields of the invalid `QueryInput`;
+    /// i.e. either none or more than one.
+    pub kinds: Vec<QueryKind>,
+}
+
+#[cfg(feature = "graphql")]
+impl fmt::Display for QueryInputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.kinds.is_empty() {
+            return f.write_str("expected exactly one query but found none");
+        }
+
+        f.write_str("expected exactly one query but found ")?;
+        for (i, kind) in self.kinds.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "`{}`", kind)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl Error for QueryInputError {}
+
+#[cfg(feature = "graphql")]
+impl QueryInput {
+    /// Checks that this `QueryInput`, and every `QueryInput` nested in it,
+    /// defines exactly one field.
+    pub fn validate(&self) -> Result<(), QueryInputError> {
+        let kinds = self.kinds();
+        if kinds.len() != 1 {
+            return Err(QueryInputError { kinds });
+        }
+
+        if let Some(ref script_score) = self.script_score {
+            script_score.query.validate()?;
+        }
+        if let Some(ref nested) = self.nested {
+            nested.query.validate()?;
+        }
+        if let Some(ref boolean) = self.boolean {
+            boolean.validate()?;
+        }
+
+        Ok(())
+    }
+
+    /// Converts this `QueryInput` into a [`Query`] if it is
+    /// [valid](QueryInput::validate).
+    ///
+    /// **Note**: the `From<QueryInput>` impl for `Query` does *not* validate
+    /// its input.
+    #[inline]
+    pub fn try_into_query(self) -> Result<Query, QueryInputError> {
+        self.validate()?;
+        Ok(self.into())
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl CompoundQueryInput {
+    /// Checks that every `QueryInput` nested in this `CompoundQueryInput`
+    /// defines exactly one field.
+    #[inline]
+    pub fn validate(&self) -> Result<(), QueryInputError> {
+        if let Some(ref root) = self.root {
+            root.validate()?;
+        }
+
+        match self.boolean {
+            Some(ref boolean) => boolean.validate(),
+            None => Ok(()),
+        }
+    }
+
+    /// Converts this `CompoundQueryInput` into a [`CompoundQuery`] if it is
+    /// [valid](CompoundQueryInput::validate).
+    #[inline]
+    pub fn try_into_query(self) -> Result<CompoundQuery, QueryInputError> {
+        self.validate()?;
+        Ok(self.into())
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl BooleanQueryInput {
+    /// Checks that every `QueryInput` nested in this `BooleanQueryInput`
+    /// defines exactly one field.
+    pub fn validate(&self) -> Result<(), QueryInputError> {
+        self.must
+            .iter()
+            .chain(&self.filter)
+            .chain(&self.should)
+            .chain(&self.must_not)
+            .try_for_each(QueryInput::validate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::search::query::{ExistsQuery, NestedQuery, TermQuery};
+
+    fn query() -> Query {
+        let nested = NestedQuery::new(
+            "comments",
+            TermQuery::new("comments.author", "kimchy"),
+            false,
+        );
+
+        let mut boolean = BooleanQuery::from(ExistsQuery::new("user"));
+        boolean.must.push(nested.into());
+        boolean.into_query()
+    }
+
+    #[test]
+    fn default_policy_allows_everything() {
+        assert_eq!(validate(&query(), &ValidationPolicy::default()), Ok(()));
+    }
+
+    #[test]
+    fn can_limit_depth() {
+        let policy = ValidationPolicy {
+            max_depth: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(
+            validate(&query(), &policy),
+            Err(ValidationError::TooDeep { max_depth: 3 })
+        );
+
+        let policy = ValidationPolicy {
+            max_depth: Some(4),
+            ..Default::default()
+        };
+        assert_eq!(validate(&query(), &policy), Ok(()));
+    }
+
+    #[test]
+    fn can_limit_clauses() {
+        let policy = ValidationPolicy {
+            max_clauses: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(
+            validate(&query(), &policy),
+            Err(ValidationError::TooManyClauses { max_clauses: 3 })
+        );
+
+        let policy = ValidationPolicy {
+            max_clauses: Some(4),
+            ..Default::default()
+        };
+        assert_eq!(validate(&query(), &policy), Ok(()));
+    }
+
+    #[test]
+    fn can_deny_kinds() {
+        let policy = ValidationPolicy {
+            denied: vec![QueryKind::Term],
+            ..Default::default()
+        };
+        assert_eq!(
+            validate(&query(), &policy),
+            Err(ValidationError::Denied(QueryKind::Term))
+        );
+    }
+
+    #[test]
+    fn can_deny_leading_wildcards() {
+        let policy = ValidationPolicy {
+            allow_leading_wildcards: false,
+            ..Default::default()
+        };
+        let query = Query::from(RegexpQuery::new("user", ".*chy", None::<String>));
+        assert_eq!(
+            validate(&query, &policy),
+            Err(ValidationError::LeadingWildcard {
+                kind: QueryKind::Regexp,
+                pattern: ".*chy".to_string(),
+            })
+        );
+
+        let query: Query =
+            serde_json::from_str(r#"{ "wildcard": { "name": { "value": "*ing" } } }"#).unwrap();
+        assert_eq!(
+            validate(&query, &policy),
+            Err(ValidationError::LeadingWildcard {
+                kind: QueryKind::Wildcard,
+                pattern: "*ing".to_string(),
+            })
+        );
+        let query = Query::from(WildcardQuery::new("name", "k?m*"));
+        assert_eq!(validate(&query, &policy), Ok(()));
+
+        let query: Query =
+            serde_json::from_str(r#"{ "query_string": { "query": "user:(kimchy OR *chy)" } }"#)
+                .unwrap();
+        assert_eq!(
+            validate(&query, &policy),
+            Err(ValidationError::LeadingWildcard {
+                kind: QueryKind::QueryString,
+                pattern: "*chy".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn checks_span_clauses() {
+        let span_multi: Query = serde_json::from_str(
+            r#"{ "span_multi": { "match": { "regexp": { "user": { "value": ".*chy" } } } } }"#,
+        )
+        .unwrap();
+        let span_near: Query = serde_json::from_str(
+            r#"{
+                "span_near": {
+                    "clauses": [
+                        { "span_term": { "user": { "value": "kimchy" } } },
+                        { "span_multi": { "match": { "regexp": { "user": { "value": ".*chy" } } } } }
+                    ],
+                    "slop": 1
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let policy = ValidationPolicy {
+            denied: vec![QueryKind::Regexp],
+            ..Default::default()
+        };
+        for query in &[&span_multi, &span_near] {
+            assert_eq!(
+                validate(query, &policy),
+                Err(ValidationError::Denied(QueryKind::Regexp))
+            );
+        }
+
+        let policy = ValidationPolicy {
+            allow_leading_wildcards: false,
+            ..Default::default()
+        };
+        for query in &[&span_multi, &span_near] {
+            assert_eq!(
+                validate(query, &policy),
+                Err(ValidationError::LeadingWildcard {
+                    kind: QueryKind::Regexp,
+                    pattern: ".*chy".to_string(),
+                })
+            );
+        }
+
+        let policy = ValidationPolicy {
+            max_depth: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(
+            validate(&span_near, &policy),
+            Err(ValidationError::TooDeep { max_depth: 2 })
+        );
+
+        let policy = ValidationPolicy {
+            max_clauses: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(
+            validate(&span_near, &policy),
+            Err(ValidationError::TooManyClauses { max_clauses: 3 })
+        );
+    }
+
+    #[test]
+    fn can_limit_determinized_states() {
+        let policy = ValidationPolicy {
+            max_determinized_states: Some(1_000),
+            ..Default::default()
+        };
+        let query: Query = serde_json::from_str(
+            r#"{ "query_string": { "query": "user:/k.*y/", "max_determinized_states": 500 } }"#,
+        )
+        .unwrap();
+        assert_eq!(validate(&query, &policy), Ok(()));
+
+        let query = Query::from(RegexpQuery::new("user", "k.*y", None::<String>));
+        assert_eq!(
+            validate(&query, &policy),
+            Err(ValidationError::TooManyDeterminizedStates {
+                kind: QueryKind::Regexp,
+                max_determinized_states: 1_000,
+                states: 10_000,
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "graphql")]
+    fn query_input_must_have_exactly_one_field() {
+        use crate::search::query::{ExistsQueryInput, TermQueryInput};
+
+        let mut input = QueryInput::from(ExistsQueryInput::new("user"));
+        assert_eq!(
+            input.clone().try_into_query().unwrap(),
+            ExistsQuery::new("user").into()
+        );
+
+        input.term = Some(TermQueryInput::new("user", "kimchy"));
+        let err = input.clone().try_into_query().unwrap_err();
+        assert_eq!(err.kinds, vec![QueryKind::Exists, QueryKind::Term]);
+        assert_eq!(
+            err.to_string(),
+            "expected exactly one query but found `exists`, `term`"
+        );
+
+        input.exists = None;
+        input.term = None;
+        let input = CompoundQueryInput::from(BooleanQueryInput::from(input));
+        assert_eq!(input.validate(), Err(QueryInputError { kinds: vec![] }));
+    }
+}
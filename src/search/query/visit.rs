@@ -0,0 +1,534 @@
+//! A visitor for traversing a (possibly deeply nested) [`Query`] tree.
+
+use super::span::{
+    SpanContainingQuery, SpanFirstQuery, SpanMultiQuery, SpanNearQuery, SpanNotQuery, SpanOrQuery,
+    SpanQuery, SpanTermQuery, SpanWithinQuery,
+};
+use super::{
+    BooleanQuery, CompoundQuery, ExistsQuery, FuzzyQuery, GeoBoundingBoxQuery, GeoDistanceQuery,
+    GeoPolygonQuery, GeoShapeQuery, IdsQuery, KnnQuery, MatchPhrasePrefixQuery, MatchPhraseQuery,
+    MatchQuery, MoreLikeThisQuery, MultiMatchQuery, NestedQuery, PercolateQuery, PrefixQuery,
+    Query, QueryStringQuery, RangeQuery, RankFeatureQuery, RegexpQuery, ScriptQuery,
+    ScriptScoreQuery, SimpleQueryStringQuery, TermQuery, TermsQuery, TermsSetQuery, WildcardQuery,
+};
+
+/// Visits each clause of a [`Query`] tree.
+///
+/// Every method has a no-op default implementation, so implementors only need
+/// to override the clauses they care about. The traversal itself is driven by
+/// [`Query::walk`]; e.g. [`QueryVisitor::visit_boolean`] is called *before*
+/// the clauses of the `bool` query are visited.
+///
+/// ```
+/// use elastiql::search::query::{CompoundQuery, QueryVisitor, TermQuery};
+///
+/// #[derive(Default)]
+/// struct Fields(Vec<String>);
+///
+/// impl QueryVisitor for Fields {
+///     fn visit_term(&mut self, query: &TermQuery) {
+///         self.0.push(query.field.clone());
+///     }
+/// }
+///
+/// let query = CompoundQuery::from(TermQuery::new("user", "kimchy"));
+///
+/// let mut fields = Fields::default();
+/// query.walk(&mut fields);
+/// assert_eq!(fields.0, vec!["user".to_string()]);
+/// ```
+#[allow(unused_variables)]
+pub trait QueryVisitor {
+    /// Called for every [`Query`] in the tree, before its clause is visited.
+    #[inline]
+    fn visit_query(&mut self, query: &Query) {}
+
+    /// Called for every [`CompoundQuery`] in the tree, e.g. the query of a
+    /// `nested` query, before its clauses are visited.
+    #[inline]
+    fn visit_compound(&mut self, query: &CompoundQuery) {}
+
+    /// Called for every `bool` query, before its clauses are visited.
+    #[inline]
+    fn visit_boolean(&mut self, query: &BooleanQuery) {}
+
+    /// Called for every `nested` query, before its inner query is visited.
+    #[inline]
+    fn visit_nested(&mut self, query: &NestedQuery) {}
+
+    /// Called for every `script_score` query, before its inner query is
+    /// visited.
+    #[inline]
+    fn visit_script_score(&mut self, query: &ScriptScoreQuery) {}
+
+    /// Called for every `exists` query.
+    #[inline]
+    fn visit_exists(&mut self, query: &ExistsQuery) {}
+
+    /// Called for every `term` query.
+    #[inline]
+    fn visit_term(&mut self, query: &TermQuery) {}
+
+    /// Called for every `terms` query.
+    #[inline]
+    fn visit_terms(&mut self, query: &TermsQuery) {}
+
+    /// Called for every `terms_set` query.
+    #[inline]
+    fn visit_terms_set(&mut self, query: &TermsSetQuery) {}
+
+    /// Called for every `ids` query.
+    #[inline]
+    fn visit_ids(&mut self, query: &IdsQuery) {}
+
+    /// Called for every `range` query.
+    #[inline]
+    fn visit_range(&mut self, query: &RangeQuery) {}
+
+    /// Called for every `prefix` query.
+    #[inline]
+    fn visit_prefix(&mut self, query: &PrefixQuery) {}
+
+    /// Called for every `wildcard` query.
+    #[inline]
+    fn visit_wildcard(&mut self, query: &WildcardQuery) {}
+
+    /// Called for every `regexp` query.
+    #[inline]
+    fn visit_regexp(&mut self, query: &RegexpQuery) {}
+
+    /// Called for every `fuzzy` query.
+    #[inline]
+    fn visit_fuzzy(&mut self, query: &FuzzyQuery) {}
+
+    /// Called for every `match` query.
+    #[inline]
+    fn visit_match(&mut self, query: &MatchQuery) {}
+
+    /// Called for every `match_phrase` query.
+    #[inline]
+    fn visit_match_phrase(&mut self, query: &MatchPhraseQuery) {}
+
+    /// Called for every `match_phrase_prefix` query.
+    #[inline]
+    fn visit_match_phrase_prefix(&mut self, query: &MatchPhrasePrefixQuery) {}
+
+    /// Called for every `multi_match` query.
+    #[inline]
+    fn visit_multi_match(&mut self, query: &MultiMatchQuery) {}
+
+    /// Called for every `more_like_this` query.
+    #[inline]
+    fn visit_more_like_this(&mut self, query: &MoreLikeThisQuery) {}
+
+    /// Called for every `percolate` query.
+    #[inline]
+    fn visit_percolate(&mut self, query: &PercolateQuery) {}
+
+    /// Called for every `script` query.
+    #[inline]
+    fn visit_script(&mut self, query: &ScriptQuery) {}
+
+    /// Called for every `geo_distance` query.
+    #[inline]
+    fn visit_geo_distance(&mut self, query: &GeoDistanceQuery) {}
+
+    /// Called for every `geo_bounding_box` query.
+    #[inline]
+    fn visit_geo_bounding_box(&mut self, query: &GeoBoundingBoxQuery) {}
+
+    /// Called for every `geo_shape` query.
+    #[inline]
+    fn visit_geo_shape(&mut self, query: &GeoShapeQuery) {}
+
+    /// Called for every `geo_polygon` query.
+    #[inline]
+    fn visit_geo_polygon(&mut self, query: &GeoPolygonQuery) {}
+
+    /// Called for every `span_term` query.
+    #[inline]
+    fn visit_span_term(&mut self, query: &SpanTermQuery) {}
+
+    /// Called for every `span_near` query, before its clauses are visited.
+    #[inline]
+    fn visit_span_near(&mut self, query: &SpanNearQuery) {}
+
+    /// Called for every `span_first` query, before its inner span query is
+    /// visited.
+    #[inline]
+    fn visit_span_first(&mut self, query: &SpanFirstQuery) {}
+
+    /// Called for every `span_or` query, before its clauses are visited.
+    #[inline]
+    fn visit_span_or(&mut self, query: &SpanOrQuery) {}
+
+    /// Called for every `span_not` query, before its `include` and `exclude`
+    /// queries are visited.
+    #[inline]
+    fn visit_span_not(&mut self, query: &SpanNotQuery) {}
+
+    /// Called for every `span_containing` query, before its `big` and `little`
+    /// queries are visited.
+    #[inline]
+    fn visit_span_containing(&mut self, query: &SpanContainingQuery) {}
+
+    /// Called for every `span_within` query, before its `big` and `little`
+    /// queries are visited.
+    #[inline]
+    fn visit_span_within(&mut self, query: &SpanWithinQuery) {}
+
+    /// Called for every `span_multi` query, before its multi-term query is
+    /// visited.
+    #[inline]
+    fn visit_span_multi(&mut self, query: &SpanMultiQuery) {}
+
+    /// Called for every `simple_query_string` query.
+    #[inline]
+    fn visit_simple_query_string(&mut self, query: &SimpleQueryStringQuery) {}
+
+    /// Called for every `query_string` query.
+    #[inline]
+    fn visit_query_string(&mut self, query: &QueryStringQuery) {}
+
+    /// Called for every `knn` query.
+    #[inline]
+    fn visit_knn(&mut self, query: &KnnQuery) {}
+
+    /// Called for every `rank_feature` query.
+    #[inline]
+    fn visit_rank_feature(&mut self, query: &RankFeatureQuery) {}
+}
+
+impl Query {
+    /// Traverses this query and all of its nested clauses (e.g. the clauses of
+    /// `bool` and `nested` queries), calling the matching method of `visitor`
+    /// for each one.
+    pub fn walk(&self, visitor: &mut impl QueryVisitor) {
+        visitor.visit_query(self);
+
+        if let Some(ref query) = self.exists {
+            visitor.visit_exists(query);
+        }
+        if let Some(ref query) = self.term {
+            visitor.visit_term(query);
+        }
+        if let Some(ref query) = self.terms {
+            visitor.visit_terms(query);
+        }
+        if let Some(ref query) = self.terms_set {
+            visitor.visit_terms_set(query);
+        }
+        if let Some(ref query) = self.ids {
+            visitor.visit_ids(query);
+        }
+        if let Some(ref query) = self.range {
+            visitor.visit_range(query);
+        }
+        if let Some(ref query) = self.prefix {
+            visitor.visit_prefix(query);
+        }
+        if let Some(ref query) = self.wildcard {
+            visitor.visit_wildcard(query);
+        }
+        if let Some(ref query) = self.regexp {
+            visitor.visit_regexp(query);
+        }
+        if let Some(ref query) = self.fuzzy {
+            visitor.visit_fuzzy(query);
+        }
+        if let Some(ref query) = self.match_ {
+            visitor.visit_match(query);
+        }
+        if let Some(ref query) = self.match_phrase {
+            visitor.visit_match_phrase(query);
+        }
+        if let Some(ref query) = self.match_phrase_prefix {
+            visitor.visit_match_phrase_prefix(query);
+        }
+        if let Some(ref query) = self.multi_match {
+            visitor.visit_multi_match(query);
+        }
+        if let Some(ref query) = self.more_like_this {
+            visitor.visit_more_like_this(query);
+        }
+        if let Some(ref query) = self.percolate {
+            visitor.visit_percolate(query);
+        }
+        if let Some(ref query) = self.script {
+            visitor.visit_script(query);
+        }
+        if let Some(ref query) = self.geo_distance {
+            visitor.visit_geo_distance(query);
+        }
+        if let Some(ref query) = self.geo_bounding_box {
+            visitor.visit_geo_bounding_box(query);
+        }
+        if let Some(ref query) = self.geo_shape {
+            visitor.visit_geo_shape(query);
+        }
+        if let Some(ref query) = self.geo_polygon {
+            visitor.visit_geo_polygon(query);
+        }
+        if let Some(ref query) = self.span_term {
+            visitor.visit_span_term(query);
+        }
+        if let Some(ref query) = self.span_near {
+            query.walk(visitor);
+        }
+        if let Some(ref query) = self.span_first {
+            query.walk(visitor);
+        }
+        if let Some(ref query) = self.span_or {
+            query.walk(visitor);
+        }
+        if let Some(ref query) = self.span_not {
+            query.walk(visitor);
+        }
+        if let Some(ref query) = self.span_containing {
+            query.walk(visitor);
+        }
+        if let Some(ref query) = self.span_within {
+            query.walk(visitor);
+        }
+        if let Some(ref query) = self.span_multi {
+            query.walk(visitor);
+        }
+        if let Some(ref query) = self.simple_query_string {
+            visitor.visit_simple_query_string(query);
+        }
+        if let Some(ref query) = self.query_string {
+            visitor.visit_query_string(query);
+        }
+        if let Some(ref query) = self.knn {
+            visitor.visit_knn(query);
+        }
+        if let Some(ref query) = self.rank_feature {
+            visitor.visit_rank_feature(query);
+        }
+        if let Some(ref query) = self.script_score {
+            query.walk(visitor);
+        }
+        if let Some(ref query) = self.nested {
+            query.walk(visitor);
+        }
+        if let Some(ref query) = self.boolean {
+            query.walk(visitor);
+        }
+    }
+}
+
+impl CompoundQuery {
+    /// Traverses this query and all of its nested clauses.
+    ///
+    /// See [`Query::walk`].
+    pub fn walk(&self, visitor: &mut impl QueryVisitor) {
+        visitor.visit_compound(self);
+
+        if let Some(ref query) = self.boolean {
+            query.walk(visitor);
+        }
+        if let Some(ref query) = self.root {
+            query.walk(visitor);
+        }
+    }
+}
+
+impl BooleanQuery {
+    /// Traverses this query and all of its nested clauses, in `must`, `filter`,
+    /// `should`, `must_not` order.
+    ///
+    /// See [`Query::walk`].
+    pub fn walk(&self, visitor: &mut impl QueryVisitor) {
+        visitor.visit_boolean(self);
+
+        let clauses = self
+            .must
+            .iter()
+            .chain(&self.filter)
+            .chain(&self.should)
+            .chain(&self.must_not);
+        for query in clauses {
+            query.walk(visitor);
+        }
+    }
+}
+
+impl NestedQuery {
+    /// Traverses this query and its inner query.
+    ///
+    /// See [`Query::walk`].
+    pub fn walk(&self, visitor: &mut impl QueryVisitor) {
+        visitor.visit_nested(self);
+        self.query.walk(visitor);
+    }
+}
+
+impl ScriptScoreQuery {
+    /// Traverses this query and its inner query.
+    ///
+    /// See [`Query::walk`].
+    pub fn walk(&self, visitor: &mut impl QueryVisitor) {
+        visitor.visit_script_score(self);
+        self.query.walk(visitor);
+    }
+}
+
+impl SpanQuery {
+    /// Traverses this span query and all of its nested clauses.
+    ///
+    /// See [`Query::walk`].
+    pub fn walk(&self, visitor: &mut impl QueryVisitor) {
+        if let Some(ref query) = self.span_term {
+            visitor.visit_span_term(query);
+        }
+        if let Some(ref query) = self.span_near {
+            query.walk(visitor);
+        }
+        if let Some(ref query) = self.span_first {
+            query.walk(visitor);
+        }
+        if let Some(ref query) = self.span_or {
+            query.walk(visitor);
+        }
+        if let Some(ref query) = self.span_not {
+            query.walk(visitor);
+        }
+        if let Some(ref query) = self.span_containing {
+            query.walk(visitor);
+        }
+        if let Some(ref query) = self.span_within {
+            query.walk(visitor);
+        }
+        if let Some(ref query) = self.span_multi {
+            query.walk(visitor);
+        }
+    }
+}
+
+impl SpanNearQuery {
+    /// Traverses this query and its nested clauses.
+    ///
+    /// See [`Query::walk`].
+    pub fn walk(&self, visitor: &mut impl QueryVisitor) {
+        visitor.visit_span_near(self);
+        for clause in &self.clauses {
+            clause.walk(visitor);
+        }
+    }
+}
+
+impl SpanFirstQuery {
+    /// Traverses this query and its nested clauses.
+    ///
+    /// See [`Query::walk`].
+    pub fn walk(&self, visitor: &mut impl QueryVisitor) {
+        visitor.visit_span_first(self);
+        self.match_.walk(visitor);
+    }
+}
+
+impl SpanOrQuery {
+    /// Traverses this query and its nested clauses.
+    ///
+    /// See [`Query::walk`].
+    pub fn walk(&self, visitor: &mut impl QueryVisitor) {
+        visitor.visit_span_or(self);
+        for clause in &self.clauses {
+            clause.walk(visitor);
+        }
+    }
+}
+
+impl SpanNotQuery {
+    /// Traverses this query and its nested clauses.
+    ///
+    /// See [`Query::walk`].
+    pub fn walk(&self, visitor: &mut impl QueryVisitor) {
+        visitor.visit_span_not(self);
+        self.include.walk(visitor);
+        self.exclude.walk(visitor);
+    }
+}
+
+impl SpanContainingQuery {
+    /// Traverses this query and its nested clauses.
+    ///
+    /// See [`Query::walk`].
+    pub fn walk(&self, visitor: &mut impl QueryVisitor) {
+        visitor.visit_span_containing(self);
+        self.big.walk(visitor);
+        self.little.walk(visitor);
+    }
+}
+
+impl SpanWithinQuery {
+    /// Traverses this query and its nested clauses.
+    ///
+    /// See [`Query::walk`].
+    pub fn walk(&self, visitor: &mut impl QueryVisitor) {
+        visitor.visit_span_within(self);
+        self.big.walk(visitor);
+        self.little.walk(visitor);
+    }
+}
+
+impl SpanMultiQuery {
+    /// Traverses this query and its nested clauses.
+    ///
+    /// See [`Query::walk`].
+    pub fn walk(&self, visitor: &mut impl QueryVisitor) {
+        visitor.visit_span_multi(self);
+        self.match_.walk(visitor);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Counter {
+        queries: usize,
+        booleans: usize,
+        fields: Vec<String>,
+    }
+
+    impl QueryVisitor for Counter {
+        fn visit_query(&mut self, _query: &Query) {
+            self.queries += 1;
+        }
+
+        fn visit_boolean(&mut self, _query: &BooleanQuery) {
+            self.booleans += 1;
+        }
+
+        fn visit_nested(&mut self, query: &NestedQuery) {
+            self.fields.push(query.path.clone());
+        }
+
+        fn visit_term(&mut self, query: &TermQuery) {
+            self.fields.push(query.field.clone());
+        }
+
+        fn visit_exists(&mut self, query: &ExistsQuery) {
+            self.fields.push(query.field.clone());
+        }
+    }
+
+    #[test]
+    fn walks_nested_clauses() {
+        let inner = TermQuery::new("comments.author", "kimchy");
+
+        let mut query = BooleanQuery::from(ExistsQuery::new("user"));
+        query
+            .must_not
+            .push(NestedQuery::new("comments", inner, false).into());
+
+        let mut counter = Counter::default();
+        CompoundQuery::from(query).walk(&mut counter);
+
+        assert_eq!(counter.queries, 3);
+        assert_eq!(counter.booleans, 2);
+        assert_eq!(counter.fields, vec!["user", "comments", "comments.author"]);
+    }
+}
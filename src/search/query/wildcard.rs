@@ -0,0 +1,252 @@
+//! [Wildcard query](https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-wildcard-query.html)
+
+use std::fmt;
+
+use elastiql_derive::{input_twin, twin_impl};
+#[cfg(feature = "schemars")]
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::de::{self, MapAccess, Visitor};
+use serde::ser::{SerializeMap, Serializer};
+use serde::{Deserialize, Serialize};
+
+#[allow(clippy::missing_docs_in_private_items)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize)]
+struct InnerWildcardQuery {
+    value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    boost: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rewrite: Option<String>,
+    #[serde(default)]
+    case_insensitive: bool,
+}
+
+/// The body of a `WildcardQuery`, which Elasticsearch also accepts as just the
+/// pattern itself, e.g. `{ "user": "ki*y" }`.
+#[allow(clippy::missing_docs_in_private_items)]
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum WildcardQueryBody {
+    Short(String),
+    Long(InnerWildcardQuery),
+}
+
+/// A [Wildcard query] returns documents that contain terms matching a
+/// wildcard pattern.
+///
+/// [Wildcard query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-wildcard-query.html
+#[input_twin(name = "WildcardFilterInput")]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("WildcardFilter"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct WildcardQuery {
+    /// The name of the field to query.
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::field))]
+    pub field: String,
+
+    /// The wildcard pattern for terms you wish to find in the provided
+    /// `field`, where `?` matches any single character and `*` matches zero
+    /// or more characters.
+    ///
+    /// Avoid beginning patterns with `*` or `?`, which increases the
+    /// iterations needed to find matching terms and slows search performance.
+    pub value: String,
+
+    /// Floating point number used to decrease or increase the relevance scores
+    /// of the query. (Defaults to `1.0`.)
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::option_float))]
+    pub boost: Option<f64>,
+
+    /// Method used to rewrite the query. For valid values and more
+    /// information, see the [`rewrite` parameter].
+    ///
+    /// [`rewrite` parameter]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-multi-term-rewrite.html
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub rewrite: Option<String>,
+
+    /// Allows ASCII case insensitive matching of the pattern with the indexed
+    /// field values when set to true. Default is false which means the case
+    /// sensitivity of matching depends on the underlying field’s mapping.
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[input(graphql(default))]
+    pub case_insensitive: bool,
+}
+
+impl WildcardQuery {
+    /// Constructs a new `WildcardQuery`.
+    #[inline]
+    pub fn new(field: impl Into<String>, value: impl Into<String>) -> Self {
+        WildcardQuery {
+            field: field.into(),
+            value: value.into(),
+            boost: None,
+            rewrite: None,
+            case_insensitive: false,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl WildcardQueryInput {
+    /// Constructs a new `WildcardQueryInput`.
+    #[inline]
+    pub fn new(field: impl Into<String>, value: impl Into<String>) -> Self {
+        WildcardQueryInput {
+            field: field.into(),
+            value: value.into(),
+            boost: None,
+            rewrite: None,
+            case_insensitive: false,
+        }
+    }
+}
+
+// TODO: re-use the serializer from the input type
+#[twin_impl]
+impl Serialize for WildcardQuery {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(1))?;
+
+        let inner = InnerWildcardQuery {
+            value: self.value.to_owned(),
+            boost: self.boost,
+            rewrite: self.rewrite.to_owned(),
+            case_insensitive: self.case_insensitive,
+        };
+
+        map.serialize_entry(&self.field, &inner)?;
+
+        map.end()
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl JsonSchema for WildcardQuery {
+    #[inline]
+    fn schema_name() -> String {
+        "WildcardQuery".to_string()
+    }
+
+    #[inline]
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        crate::schema::keyed(InnerWildcardQuery::json_schema(gen))
+    }
+}
+
+/// Visits a `WildcardQuery` during deserialization.
+struct WildcardQueryVisitor;
+
+impl<'de> serde::Deserialize<'de> for WildcardQuery {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<WildcardQuery, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(WildcardQueryVisitor)
+    }
+}
+
+impl<'de> Visitor<'de> for WildcardQueryVisitor {
+    type Value = WildcardQuery;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a `WildcardQuery`")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let field = map
+            .next_key::<String>()?
+            .ok_or_else(|| de::Error::missing_field("field"))?;
+
+        Ok(match map.next_value()? {
+            WildcardQueryBody::Short(value) => WildcardQuery::new(field, value),
+            WildcardQueryBody::Long(inner) => WildcardQuery {
+                field,
+                value: inner.value,
+                boost: inner.boost,
+                rewrite: inner.rewrite,
+                case_insensitive: inner.case_insensitive,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    macro_rules! test_case {
+        ($name:ident : $f:expr, $j:expr) => {
+            mod $name {
+                use super::*;
+
+                #[test]
+                fn can_serialize() {
+                    assert_eq!(serde_json::to_value(&$f).unwrap(), $j);
+                }
+
+                #[test]
+                fn can_deserialize() {
+                    assert_eq!(serde_json::from_value::<WildcardQuery>($j).unwrap(), $f);
+                }
+            }
+        };
+    }
+
+    test_case!(
+        simple:
+        WildcardQuery::new("user", "ki*y"),
+        json!({ "user": { "value": "ki*y", "case_insensitive": false } })
+    );
+
+    test_case!(
+        with_options:
+        WildcardQuery {
+            field: "user".to_string(),
+            value: "ki*y".to_string(),
+            boost: Some(1.5),
+            rewrite: Some("constant_score".to_string()),
+            case_insensitive: true,
+        },
+        json!({
+            "user": {
+                "value": "ki*y",
+                "boost": 1.5,
+                "rewrite": "constant_score",
+                "case_insensitive": true
+            }
+        })
+    );
+
+    #[test]
+    fn can_deserialize_short_form() {
+        let j = json!({ "user": "ki*y" });
+        assert_eq!(
+            serde_json::from_value::<WildcardQuery>(j).unwrap(),
+            WildcardQuery::new("user", "ki*y")
+        );
+    }
+
+    #[test]
+    fn deserialize_invalid_values_is_err() {
+        let j = r#"{ "user": null }"#;
+        assert!(serde_json::from_str::<WildcardQuery>(j).is_err(), "{}", &j);
+
+        let j = r#"{ "user": { "value": 1 } }"#;
+        assert!(serde_json::from_str::<WildcardQuery>(j).is_err(), "{}", &j);
+
+        let j = r#"{ "user": { "value": "ki*y", "case_insensitive": "yes" } }"#;
+        assert!(serde_json::from_str::<WildcardQuery>(j).is_err(), "{}", &j);
+    }
+}
@@ -2,33 +2,43 @@
 //!
 //! [Search request]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-request-body.html
 
+use std::collections::HashMap;
+
+use elastiql_derive::input_twin;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
 #[cfg(feature = "graphql")]
 use crate::search::{query::CompoundQueryInput, SortInput};
 use crate::{
+    mapping::RuntimeField,
     scalars::SortedValue,
-    search::{query::CompoundQuery, Sort},
+    search::{query::CompoundQuery, ScriptField, Sort},
 };
 
 /// The [request body] for an Elasticsearch search request.
 ///
 /// [request body]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-request-body.html
-#[cfg(feature = "graphql")]
+#[input_twin]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("Request"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 #[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(async_graphql::InputObject, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
-pub struct RequestInput {
+pub struct Request {
     /// The query to perform in this search request.
     #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(skip_serializing_if = "CompoundQueryInput::is_empty")]
-    pub query: CompoundQueryInput,
+    #[serde(skip_serializing_if = "CompoundQuery::is_empty")]
+    #[input(twin, serde(skip_serializing_if = "CompoundQueryInput::is_empty"))]
+    pub query: CompoundQuery,
 
     /// Sorts the results.
     #[cfg_attr(feature = "builder", builder(default))]
     #[serde(skip_serializing_if = "Vec::is_empty")]
-    pub sort: Vec<SortInput>,
+    #[input(twin)]
+    pub sort: Vec<Sort>,
 
     /// The number of results to return.
     #[cfg_attr(feature = "builder", builder(default))]
@@ -39,8 +49,8 @@ pub struct RequestInput {
     /// which the query execution will terminate early.
     ///
     /// Defaults to `0`, which does not terminate query execution early.
-    #[graphql(default)]
     #[cfg_attr(feature = "builder", builder(default))]
+    #[input(graphql(default))]
     pub terminate_after: u64,
 
     /// The live cursor from which to search after to fascilitate [pagination].
@@ -48,6 +58,7 @@ pub struct RequestInput {
     /// [pagination]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-request-body.html#request-body-search-search-after
     #[cfg_attr(feature = "builder", builder(default))]
     #[serde(rename = "search_after", skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::sorted_values))]
     pub after: Vec<SortedValue>,
 
     /// Whether or not to include the document version in the search results.
@@ -63,8 +74,11 @@ pub struct RequestInput {
 
     // TODO: could also be a bool...
     /// The lower bound for the number of hits to track
+    ///
+    /// **Note**: this isn't sent to Elasticsearch `6.x`, which doesn't support
+    /// it.
     #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "crate::compat::is_none_or_before_v7")]
     pub track_total_hits: Option<u64>,
 
     // TODO: figure out a way to not use this for queries that don't support it like `count`
@@ -74,80 +88,33 @@ pub struct RequestInput {
     /// [highlighted]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-request-highlighting.html
     #[cfg_attr(feature = "builder", builder(default))]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub highlight: Option<HighlightOptionsInput>,
-}
-
-#[cfg(feature = "graphql")]
-impl RequestInput {
-    /// Get a mutable reference to the [`CompoundQueryInput`].
-    #[inline]
-    pub fn query_mut(&mut self) -> &mut CompoundQueryInput {
-        &mut self.query
-    }
-}
-
-/// The [request body] for an Elasticsearch search request.
-///
-/// [request body]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-request-body.html
-#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
-#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(Serialize, Clone, Debug)]
-#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
-pub struct Request {
-    /// The query to perform in this search request.
-    #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(skip_serializing_if = "CompoundQuery::is_empty")]
-    pub query: CompoundQuery,
-
-    /// Sorts the results.
-    #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    pub sort: Vec<Sort>,
-
-    /// The number of results to return.
-    #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub size: Option<u64>,
+    #[input(twin)]
+    pub highlight: Option<HighlightOptions>,
 
-    /// The maximum number of documents to collect for each shard, upon reaching
-    /// which the query execution will terminate early.
+    /// Fields whose values are [computed by a script] for each hit, by name.
     ///
-    /// Defaults to `0`, which does not terminate query execution early.
-    #[cfg_attr(feature = "builder", builder(default))]
-    pub terminate_after: u64,
-
-    /// The live cursor from which to search after to fascilitate [pagination].
+    /// **Note**: these aren't exposed through GraphQL, which has no maps of
+    /// (input) objects.
     ///
-    /// [pagination]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-request-body.html#request-body-search-search-after
+    /// [computed by a script]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-fields.html#script-fields
     #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(rename = "search_after", skip_serializing_if = "Vec::is_empty")]
-    pub after: Vec<SortedValue>,
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    #[input(graphql(skip))]
+    pub script_fields: HashMap<String, ScriptField>,
 
-    /// Whether or not to include the document version in the search results.
-    #[cfg_attr(feature = "builder", builder(default))]
-    pub version: bool,
-
-    /// Whether or not to include the [sequence number & primary term] in the
-    /// search results.
+    /// The [runtime fields] defined for this request only, by name, which can
+    /// be searched, sorted and aggregated on like any other field.
     ///
-    /// [sequence number & primary term]: https://www.elastic.co/guide/en/elasticsearch/reference/current/optimistic-concurrency-control.html
-    #[cfg_attr(feature = "builder", builder(default))]
-    pub seq_no_primary_term: bool,
-
-    // TODO: could also be a bool...
-    /// The lower bound for the number of hits to track
-    #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub track_total_hits: Option<u64>,
-
-    // TODO: figure out a way to not use this for queries that don't support it like `count`
-    /// The [highlighted] snippets of the part(s) of the field(s) matching the
-    /// search query.
+    /// **Note**: these aren't exposed through GraphQL, which has no maps of
+    /// (input) objects.
     ///
-    /// [highlighted]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-request-highlighting.html
+    /// [runtime fields]: https://www.elastic.co/guide/en/elasticsearch/reference/current/runtime-search-request.html
     #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub highlight: Option<HighlightOptions>,
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    #[input(graphql(skip))]
+    pub runtime_mappings: HashMap<String, RuntimeField>,
 }
 
 impl Request {
@@ -156,71 +123,44 @@ impl Request {
     pub fn query_mut(&mut self) -> &mut CompoundQuery {
         &mut self.query
     }
-}
-
-/// The [options] for highlighting.
-///
-/// **TODO**: add more options...
-///
-/// [options]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-request-highlighting.html#highlighting-settings
-#[cfg(feature = "graphql")]
-#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(async_graphql::InputObject, Serialize, Deserialize, Clone, Debug)]
-#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
-pub struct HighlightOptionsInput {
-    /// The field names and their options to highlight.
-    pub fields: crate::scalars::Map,
 
-    /// The highligher type to use.
-    #[graphql(name = "type", default)]
-    #[serde(rename = "type")]
-    #[cfg_attr(feature = "builder", builder(default))]
-    pub ty: HighlighterType,
-
-    /// The maximum number of fragments to return.
-    #[graphql(default_with = "5")]
-    #[cfg_attr(feature = "builder", builder(default = 5))]
-    pub number_of_fragments: u64,
-
-    /// The size of the highlighted fragment in characters.
-    #[graphql(default_with = "100")]
-    #[cfg_attr(feature = "builder", builder(default = 100))]
-    pub fragment_size: u32,
-
-    /// How far to scan for boundary characters.
-    #[graphql(default_with = "20")]
-    #[cfg_attr(feature = "builder", builder(default = 20))]
-    pub boundary_max_scan: u32,
-
-    // TODO: should be an enum?
-    /// Set to [`styled`] to use the built-in tag schema.
-    ///
-    /// [`styled`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-request-highlighting.html
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[cfg_attr(feature = "builder", builder(default))]
-    pub tags_schema: Option<String>,
+    /// Returns a copy of this request with the values of its query
+    /// [masked](crate::search::query::Query::mask_values) and its
+    /// `search_after` values replaced with `"?"`, e.g. so that it can be
+    /// logged or traced without leaking what was searched for.
+    #[inline]
+    pub fn redacted(&self) -> Request {
+        let mut request = self.clone();
+        request.query.mask_values();
+        for value in &mut request.after {
+            *value = "?".into();
+        }
+        request
+    }
 
-    /// Use in conjunction with `post_tags` to define the HTML tags to use for
-    /// the highlighted text. By default, highlighted text is wrapped in `<em>`
-    /// and `</em>` tags.
-    #[graphql(default)]
-    #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(skip_serializing_if = "Vec::is_empty")] // es errors without this
-    pub pre_tags: Vec<String>,
+    /// Returns the [`fingerprint`](Request::fingerprint) of this request with
+    /// the values of its query [masked](crate::search::query::Query::mask_values)
+    /// and without its `search_after` cursor, e.g. to group the pages of
+    /// requests that only differ by the values being searched for.
+    #[inline]
+    pub fn masked_fingerprint(&self) -> serde_json::Result<u64> {
+        let mut request = self.clone();
+        request.query.mask_values();
+        request.after.clear();
+        request.fingerprint()
+    }
+}
 
-    /// Use in conjunction with `pre_tags` to define the HTML tags to use for
-    /// the highlighted text. By default, highlighted text is wrapped in `<em>`
-    /// and `</em>` tags.
-    #[graphql(default)]
-    #[cfg_attr(feature = "builder", builder(default))]
-    #[serde(skip_serializing_if = "Vec::is_empty")] // es errors without this
-    pub post_tags: Vec<String>,
+impl_display_json!(Request);
+impl_canonical_json!(Request);
 
-    /// By default, only fields that contains a query match are highlighted. Set
-    /// `require_field_match` to `false` to highlight all fields.
-    #[graphql(default = true)]
-    #[cfg_attr(feature = "builder", builder(default = true))]
-    pub require_field_match: bool,
+#[cfg(feature = "graphql")]
+impl RequestInput {
+    /// Get a mutable reference to the [`CompoundQueryInput`].
+    #[inline]
+    pub fn query_mut(&mut self) -> &mut CompoundQueryInput {
+        &mut self.query
+    }
 }
 
 /// The [options] for highlighting.
@@ -228,29 +168,39 @@ pub struct HighlightOptionsInput {
 /// **TODO**: add more options...
 ///
 /// [options]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-request-highlighting.html#highlighting-settings
+#[input_twin(derive(Deserialize))]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("HighlightOptions"))]
 #[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 #[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
 pub struct HighlightOptions {
     /// The field names and their options to highlight.
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::Map"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::map))]
     pub fields: crate::scalars::Map,
 
     /// The highligher type to use.
     #[serde(rename = "type")]
     #[cfg_attr(feature = "builder", builder(default))]
+    #[input(graphql(name = "type", default))]
     pub ty: HighlighterType,
 
     /// The maximum number of fragments to return.
     #[cfg_attr(feature = "builder", builder(default = 5))]
+    #[input(graphql(default_with = "5"))]
     pub number_of_fragments: u64,
 
     /// The size of the highlighted fragment in characters.
     #[cfg_attr(feature = "builder", builder(default = 100))]
+    #[input(graphql(default_with = "100"))]
     pub fragment_size: u32,
 
     /// How far to scan for boundary characters.
     #[cfg_attr(feature = "builder", builder(default = 20))]
+    #[input(graphql(default_with = "20"))]
     pub boundary_max_scan: u32,
 
     // TODO: should be an enum?
@@ -266,6 +216,7 @@ pub struct HighlightOptions {
     /// and `</em>` tags.
     #[cfg_attr(feature = "builder", builder(default))]
     #[serde(skip_serializing_if = "Vec::is_empty")] // es errors without this
+    #[input(graphql(default))]
     pub pre_tags: Vec<String>,
 
     /// Use in conjunction with `pre_tags` to define the HTML tags to use for
@@ -273,11 +224,13 @@ pub struct HighlightOptions {
     /// and `</em>` tags.
     #[cfg_attr(feature = "builder", builder(default))]
     #[serde(skip_serializing_if = "Vec::is_empty")] // es errors without this
+    #[input(graphql(default))]
     pub post_tags: Vec<String>,
 
     /// By default, only fields that contains a query match are highlighted. Set
     /// `require_field_match` to `false` to highlight all fields.
     #[cfg_attr(feature = "builder", builder(default = true))]
+    #[input(graphql(default = true))]
     pub require_field_match: bool,
 }
 
@@ -303,37 +256,36 @@ impl Default for HighlightOptions {
     }
 }
 
-/// The different supported highlighter types/algorithm.
-#[cfg_attr(feature = "graphql", derive(async_graphql::Enum, Eq, PartialEq, Copy))]
-#[derive(Serialize, Deserialize, Clone, Debug)]
-#[serde(rename_all = "lowercase")]
-pub enum HighlighterType {
-    /// The `unified` highlighter uses the Lucene Unified Highlighter. This
-    /// highlighter breaks the text into sentences and uses the BM25 algorithm
-    /// to score individual sentences as if they were documents in the corpus.
-    /// It also supports accurate phrase and multi-term (fuzzy, prefix, regex)
-    /// highlighting. This is the default highlighter.
-    Unified,
-
-    /// The `plain` highlighter uses the standard Lucene highlighter. It
-    /// attempts to reflect the query matching logic in terms of understanding
-    /// word importance and any word positioning criteria in phrase queries.
-    Plain,
-
-    /// The `fvh` highlighter uses the Lucene Fast Vector highlighter. This
-    /// highlighter can be used on fields with term_vector set to
-    /// `with_positions_offsets` in the mapping. The fast vector highlighter:
-    ///
-    /// - Can be customized with a `boundary_scanner`.
-    /// - Requires setting term_vector to with_positions_offsets which increases
-    ///   the size of the index
-    /// - Can combine matches from multiple fields into one result. See
-    ///   `matched_fields`
-    /// - Can assign different weights to matches at different positions
-    ///   allowing for things like phrase matches being sorted above term
-    ///   matches when highlighting a Boosting Query that boosts phrase matches
-    ///   over term matches
-    Fvh,
+open_enum! {
+    /// The different supported highlighter types/algorithm.
+    pub enum HighlighterType as "HighlighterType" {
+        /// The `unified` highlighter uses the Lucene Unified Highlighter. This
+        /// highlighter breaks the text into sentences and uses the BM25 algorithm
+        /// to score individual sentences as if they were documents in the corpus.
+        /// It also supports accurate phrase and multi-term (fuzzy, prefix, regex)
+        /// highlighting. This is the default highlighter.
+        Unified => "unified" as "UNIFIED",
+
+        /// The `plain` highlighter uses the standard Lucene highlighter. It
+        /// attempts to reflect the query matching logic in terms of understanding
+        /// word importance and any word positioning criteria in phrase queries.
+        Plain => "plain" as "PLAIN",
+
+        /// The `fvh` highlighter uses the Lucene Fast Vector highlighter. This
+        /// highlighter can be used on fields with term_vector set to
+        /// `with_positions_offsets` in the mapping. The fast vector highlighter:
+        ///
+        /// - Can be customized with a `boundary_scanner`.
+        /// - Requires setting term_vector to with_positions_offsets which increases
+        ///   the size of the index
+        /// - Can combine matches from multiple fields into one result. See
+        ///   `matched_fields`
+        /// - Can assign different weights to matches at different positions
+        ///   allowing for things like phrase matches being sorted above term
+        ///   matches when highlighting a Boosting Query that boosts phrase matches
+        ///   over term matches
+        Fvh => "fvh" as "FVH",
+    }
 }
 
 impl Default for HighlighterType {
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, error::Error, fmt};
 
 use serde::Deserialize;
 
@@ -40,6 +40,51 @@ pub struct ErrResponse {
     pub root_cause: Vec<ErrResponse>,
 }
 
+impl ErrResponse {
+    /// Returns this error as a [`VersionConflict`] if it is one.
+    #[inline]
+    pub fn version_conflict(&self) -> Option<VersionConflict> {
+        if self.ty != VersionConflict::TYPE {
+            return None;
+        }
+
+        Some(VersionConflict {
+            index: self.index.clone(),
+            reason: self.reason.clone(),
+        })
+    }
+}
+
+/// A [version conflict], i.e. a write whose `if_seq_no` and `if_primary_term`
+/// (or `version`) didn't match the current version of the document, which
+/// was most likely changed concurrently.
+///
+/// [version conflict]: https://www.elastic.co/guide/en/elasticsearch/reference/current/optimistic-concurrency-control.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VersionConflict {
+    /// The name of the index of the document.
+    pub index: String,
+
+    /// The reason given by Elasticsearch, e.g. `[1]: version conflict,
+    /// required seqNo [5], primary term [1]. current document has seqNo [6]
+    /// and primary term [1]`.
+    pub reason: String,
+}
+
+impl VersionConflict {
+    /// The type of the errors that are version conflicts.
+    pub const TYPE: &'static str = "version_conflict_engine_exception";
+}
+
+impl fmt::Display for VersionConflict {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "version conflict in `{}`: {}", self.index, self.reason)
+    }
+}
+
+impl Error for VersionConflict {}
+
 // #[non_exhaustive]
 // #[derive(Deserialize, PartialEq, Debug)]
 // #[serde(untagged, rename_all = "snake_case")]
@@ -137,6 +182,12 @@ pub struct Hit<T> {
     #[serde(default)]
     pub highlight: HashMap<String, Vec<String>>,
 
+    /// The values of the requested [`script_fields`], by name.
+    ///
+    /// [`script_fields`]: crate::search::Request::script_fields
+    #[serde(default)]
+    pub fields: HashMap<String, Vec<serde_json::Value>>,
+
     /// The live cursor from which to search after to fascilitate [pagination].
     ///
     /// [pagination]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-request-body.html#request-body-search-search-after
@@ -145,9 +196,12 @@ pub struct Hit<T> {
 }
 
 /// The type of count.
-#[cfg_attr(feature = "graphql", derive(async_graphql::Enum, Eq, PartialEq, Copy))]
-#[cfg_attr(feature = "graphql", graphql(name = "SearchCountRelation"))]
-#[derive(Deserialize, Clone, Debug)]
+#[cfg_attr(
+    feature = "graphql",
+    elastiql_derive::graphql_name("SearchCountRelation")
+)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum CountRelation {
     /// An exact count.
     #[serde(rename = "eq")]
@@ -166,9 +220,10 @@ impl Default for CountRelation {
 }
 
 /// The total count of the hits/matches.
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("SearchCount"))]
 #[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
-#[cfg_attr(feature = "graphql", graphql(name = "SearchCount"))]
-#[derive(Deserialize, Default, Debug)]
+#[derive(Deserialize, Clone, Copy, Default, Debug, PartialEq, Eq, Hash)]
+#[serde(from = "TotalHits")]
 pub struct Count {
     /// The type of count this is.
     pub relation: CountRelation,
@@ -177,4 +232,55 @@ pub struct Count {
     pub value: u64,
 }
 
-// TODO: add tests!
+/// The total count of the hits/matches as returned by Elasticsearch, which is
+/// a plain number before `7.x`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TotalHits {
+    /// The exact count (Elasticsearch `6.x`).
+    Value(u64),
+
+    /// The count with its relation (Elasticsearch `7.x+`).
+    Count {
+        /// The type of count this is.
+        relation: CountRelation,
+
+        /// The actual count.
+        value: u64,
+    },
+}
+
+impl From<TotalHits> for Count {
+    #[inline]
+    fn from(total: TotalHits) -> Self {
+        match total {
+            TotalHits::Value(value) => Count {
+                relation: CountRelation::EqualTo,
+                value,
+            },
+            TotalHits::Count { relation, value } => Count { relation, value },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn can_deserialize_total_hits_object() {
+        let count: Count =
+            serde_json::from_value(json!({ "relation": "gte", "value": 10_000 })).unwrap();
+        assert_eq!(count.relation, CountRelation::GreaterThanOrEqualTo);
+        assert_eq!(count.value, 10_000);
+    }
+
+    #[test]
+    fn can_deserialize_total_hits_number() {
+        let count: Count = serde_json::from_value(json!(42)).unwrap();
+        assert_eq!(count.relation, CountRelation::EqualTo);
+        assert_eq!(count.value, 42);
+    }
+}
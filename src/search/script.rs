@@ -2,16 +2,23 @@
 //!
 //! [scripts]: https://www.elastic.co/guide/en/elasticsearch/reference/current/modules-scripting.html
 
-use std::default::Default;
+use std::{collections::HashMap, default::Default, error::Error, fmt};
 
-use serde::{Deserialize, Serialize};
+use elastiql_derive::input_twin;
+#[cfg(feature = "schemars")]
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::scalars::SortedValue;
+
+/// The `params` passed to a [`Script`], by name.
+pub type ScriptParams = HashMap<String, SortedValue>;
 
 /// Available sandboxed scripting [languages].
 ///
 /// [languages]: https://www.elastic.co/guide/en/elasticsearch/reference/current/modules-scripting.html
-#[cfg_attr(all(test, not(feature = "graphql")), derive(PartialEq))]
-#[cfg_attr(feature = "graphql", derive(async_graphql::Enum, Eq, PartialEq, Copy))]
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Description))]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum ScriptLanguage {
     /// [Lucene expressions language] compile a Javascript expression to
     /// bytecode. They are designed for high-performance custom ranking and
@@ -47,6 +54,103 @@ pub enum ScriptLanguage {
     /// [scripting]: https://www.elastic.co/guide/en/elasticsearch/painless/current/painless-walkthrough.html
     /// [Painless API reference]: https://www.elastic.co/guide/en/elasticsearch/painless/current/painless-api-reference.html
     Painless,
+
+    /// The [Mustache] templating language, which is only used for search
+    /// templates.
+    ///
+    /// [Mustache]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-template.html
+    Mustache,
+
+    /// Any other language, e.g. one provided by a plugin.
+    Other(String),
+}
+
+impl ScriptLanguage {
+    /// The name of this language as understood by Elasticsearch.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        match self {
+            ScriptLanguage::Expressions => "expression",
+            ScriptLanguage::Painless => "painless",
+            ScriptLanguage::Mustache => "mustache",
+            ScriptLanguage::Other(lang) => lang,
+        }
+    }
+
+    /// Returns `true` if this language is only used for (search) templates.
+    #[inline]
+    pub fn is_template(&self) -> bool {
+        *self == ScriptLanguage::Mustache
+    }
+}
+
+impl From<&str> for ScriptLanguage {
+    #[inline]
+    fn from(lang: &str) -> Self {
+        match lang {
+            "expression" => ScriptLanguage::Expressions,
+            "painless" => ScriptLanguage::Painless,
+            "mustache" => ScriptLanguage::Mustache,
+            lang => ScriptLanguage::Other(lang.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for ScriptLanguage {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for ScriptLanguage {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ScriptLanguage {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<ScriptLanguage, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(ScriptLanguage::from(
+            String::deserialize(deserializer)?.as_str(),
+        ))
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl JsonSchema for ScriptLanguage {
+    #[inline]
+    fn schema_name() -> String {
+        "ScriptLanguage".to_string()
+    }
+
+    #[inline]
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
+
+#[cfg(feature = "graphql")]
+#[async_graphql::Scalar(name = "ScriptLanguage", use_type_description)]
+impl async_graphql::ScalarType for ScriptLanguage {
+    #[inline]
+    fn parse(value: async_graphql::Value) -> async_graphql::InputValueResult<Self> {
+        match value {
+            async_graphql::Value::String(ref lang) => Ok(ScriptLanguage::from(lang.as_str())),
+            async_graphql::Value::Enum(ref lang) => Ok(ScriptLanguage::from(lang.as_str())),
+            _ => Err(async_graphql::InputValueError::expected_type(value)),
+        }
+    }
+
+    #[inline]
+    fn to_value(&self) -> async_graphql::Value {
+        async_graphql::Value::String(self.as_str().to_string())
+    }
 }
 
 impl Default for ScriptLanguage {
@@ -56,30 +160,201 @@ impl Default for ScriptLanguage {
     }
 }
 
-/// Evaluates custom expressions/[scripts].
+/// Evaluates custom expressions/[scripts], either defined inline by its
+/// `source` or [stored] and referenced by its `id`.
+///
+/// **Note**: exactly one of `source` or `id` *must* be specified.
 ///
 /// [scripts]: https://www.elastic.co/guide/en/elasticsearch/reference/current/modules-scripting.html
+/// [stored]: https://www.elastic.co/guide/en/elasticsearch/reference/current/create-stored-script-api.html
 #[cfg(feature = "graphql")]
+#[elastiql_derive::graphql_name("ScriptInput")]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[derive(async_graphql::InputObject, Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct ScriptInput {
-    source: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    params: Option<crate::scalars::Map>,
-    // #[graphql(default]
-    // #[serde(default, skip_serializing_if = "Option::is_none")]
-    // lang: Option<ScriptLanguage>,
+    source: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    lang: Option<ScriptLanguage>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    params: Option<ScriptParams>,
 }
 
-/// Evaluates custom expressions/[scripts].
+/// Evaluates custom expressions/[scripts], either defined inline by its
+/// `source` or [stored] and referenced by its `id`.
+///
+/// ```
+/// use elastiql::search::Script;
+///
+/// let script: Script = serde_json::from_str(r#"{ "id": "calculate-score" }"#).unwrap();
+/// assert_eq!(script, Script::stored("calculate-score"));
+///
+/// assert!(serde_json::from_str::<Script>(r#"{ "id": "a", "source": "1" }"#).is_err());
+/// ```
 ///
 /// [scripts]: https://www.elastic.co/guide/en/elasticsearch/reference/current/modules-scripting.html
-#[cfg_attr(all(test, not(feature = "graphql")), derive(PartialEq))]
-#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject, PartialEq))]
-#[derive(Serialize, Deserialize, Clone, Debug)]
+/// [stored]: https://www.elastic.co/guide/en/elasticsearch/reference/current/create-stored-script-api.html
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("Script"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Clone, Debug, PartialEq)]
 pub struct Script {
-    source: String,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    params: Option<crate::scalars::Map>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lang: Option<ScriptLanguage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    params: Option<ScriptParams>,
+}
+
+impl Script {
+    /// Constructs a new `Script` from its inline `source`.
+    #[inline]
+    pub fn inline(source: impl Into<String>) -> Self {
+        Script {
+            source: Some(source.into()),
+            id: None,
+            lang: None,
+            params: None,
+        }
+    }
+
+    /// Constructs a new `Script` referencing the stored script with `id`.
+    #[inline]
+    pub fn stored(id: impl Into<String>) -> Self {
+        Script {
+            source: None,
+            id: Some(id.into()),
+            lang: None,
+            params: None,
+        }
+    }
+
+    /// Sets the language of the script. (Defaults to `painless`.)
+    #[inline]
+    pub fn with_lang(mut self, lang: impl Into<ScriptLanguage>) -> Self {
+        self.lang = Some(lang.into());
+        self
+    }
+
+    /// Sets the `params` passed to the script.
+    #[inline]
+    pub fn with_params(mut self, params: ScriptParams) -> Self {
+        self.params = Some(params);
+        self
+    }
+
+    /// Checks that the script can be evaluated by a query, aggregation or
+    /// sort, i.e. that it isn't written in a template language such as
+    /// `mustache`, which is only used for search templates.
+    #[inline]
+    pub fn validate(&self) -> Result<(), ScriptError> {
+        match self.lang {
+            Some(ref lang) if lang.is_template() => {
+                Err(ScriptError::TemplateLanguage(lang.clone()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The reason a [`Script`] failed [`validate`](Script::validate).
+#[derive(Clone, PartialEq, Debug)]
+pub enum ScriptError {
+    /// The script is written in a language that is only used for (search)
+    /// templates.
+    TemplateLanguage(ScriptLanguage),
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScriptError::TemplateLanguage(lang) => {
+                write!(f, "`{}` scripts can only be used in search templates", lang)
+            }
+        }
+    }
+}
+
+impl Error for ScriptError {}
+
+impl<'de> Deserialize<'de> for Script {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Script, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        /// A `Script` that may define both (or neither) of `source` and `id`.
+        #[derive(Deserialize)]
+        struct ScriptDef {
+            #[serde(default)]
+            source: Option<String>,
+            #[serde(default)]
+            id: Option<String>,
+            #[serde(default)]
+            lang: Option<ScriptLanguage>,
+            #[serde(default)]
+            params: Option<ScriptParams>,
+        }
+
+        let ScriptDef {
+            source,
+            id,
+            lang,
+            params,
+        } = ScriptDef::deserialize(deserializer)?;
+        if source.is_some() == id.is_some() {
+            return Err(de::Error::custom(
+                "expected exactly one of `source` or `id`",
+            ));
+        }
+
+        Ok(Script {
+            source,
+            id,
+            lang,
+            params,
+        })
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Script {
+    #[inline]
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let name: String = u.arbitrary()?;
+        let script = if u.arbitrary()? {
+            Script::inline(name)
+        } else {
+            Script::stored(name)
+        };
+
+        let lang = match u.int_in_range(0..=2)? {
+            0 => None,
+            1 => Some(ScriptLanguage::Painless),
+            _ => Some(ScriptLanguage::Expressions),
+        };
+        let params = if u.arbitrary()? {
+            let values = crate::arbitrary::sorted_values(u)?;
+            let mut params = ScriptParams::with_capacity(values.len());
+            for value in values {
+                params.insert(crate::arbitrary::field(u)?, value);
+            }
+            Some(params)
+        } else {
+            None
+        };
+
+        Ok(Script {
+            lang,
+            params,
+            ..script
+        })
+    }
 }
 
 #[cfg(feature = "graphql")]
@@ -88,7 +363,220 @@ impl From<ScriptInput> for Script {
     fn from(script: ScriptInput) -> Self {
         Script {
             source: script.source,
+            id: script.id,
+            lang: script.lang,
+            params: script.params,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl From<Script> for ScriptInput {
+    #[inline]
+    fn from(script: Script) -> Self {
+        Self {
+            source: script.source,
+            id: script.id,
+            lang: script.lang,
             params: script.params,
         }
     }
 }
+
+/// A field whose value is computed by a [`Script`] for each hit, e.g. one of
+/// the [`script_fields`] of a search request.
+///
+/// [`script_fields`]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-fields.html#script-fields
+#[input_twin]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("ScriptField"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct ScriptField {
+    /// The script computing the value of the field.
+    #[input(twin)]
+    pub script: Script,
+
+    /// Whether to ignore errors evaluating the script, e.g. when a document
+    /// is missing a field it uses, instead of failing the request. (Defaults
+    /// to `false`.)
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ignore_failure: Option<bool>,
+}
+
+impl From<Script> for ScriptField {
+    #[inline]
+    fn from(script: Script) -> Self {
+        ScriptField {
+            script,
+            ignore_failure: None,
+        }
+    }
+}
+
+/// The body of a request to [create or update] a stored script.
+///
+/// [create or update]: https://www.elastic.co/guide/en/elasticsearch/reference/current/create-stored-script-api.html
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PutStoredScriptRequest {
+    /// The script to store.
+    pub script: StoredScript,
+}
+
+impl_display_json!(PutStoredScriptRequest);
+
+/// The response to a request to [get] a stored script.
+///
+/// [get]: https://www.elastic.co/guide/en/elasticsearch/reference/current/get-stored-script-api.html
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct GetStoredScriptResponse {
+    /// The id of the stored script.
+    #[serde(rename = "_id")]
+    pub id: String,
+
+    /// Whether the stored script exists.
+    pub found: bool,
+
+    /// The stored script, if it exists.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script: Option<StoredScript>,
+}
+
+/// A [stored] script, which can be referenced by the `id` of a [`Script`].
+///
+/// [stored]: https://www.elastic.co/guide/en/elasticsearch/reference/current/create-stored-script-api.html
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StoredScript {
+    /// The language of the script, e.g. `painless` or `mustache` (for search
+    /// templates).
+    pub lang: ScriptLanguage,
+
+    /// The source of the script.
+    pub source: String,
+
+    /// Compiler options of the script.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub options: HashMap<String, String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn can_deserialize_inline_and_stored_scripts() {
+        let script: Script = serde_json::from_value(
+            json!({ "source": "doc['a'].value * params.b", "params": { "b": 2 } }),
+        )
+        .unwrap();
+        assert_eq!(script.source.as_deref(), Some("doc['a'].value * params.b"));
+        assert_eq!(script.id, None);
+
+        let script: Script = serde_json::from_value(json!({ "id": "score" })).unwrap();
+        assert_eq!(script, Script::stored("score"));
+        assert_eq!(
+            serde_json::to_value(&script).unwrap(),
+            json!({ "id": "score" })
+        );
+
+        assert!(serde_json::from_value::<Script>(json!({ "id": "score", "source": "1" })).is_err());
+        assert!(serde_json::from_value::<Script>(json!({ "params": {} })).is_err());
+    }
+
+    #[test]
+    fn can_round_trip_lang_and_params() {
+        let json = json!({
+            "source": "doc[params.field].value * params.factor",
+            "lang": "painless",
+            "params": { "field": "price", "factor": 1.5, "limit": 9007199254740993u64 }
+        });
+
+        let script: Script = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(script.lang, Some(ScriptLanguage::Painless));
+        let params = script.params.as_ref().unwrap();
+        assert_eq!(params["factor"], SortedValue::Float(1.5));
+        assert_eq!(params["limit"], SortedValue::Int(9007199254740993));
+        assert_eq!(serde_json::to_value(&script).unwrap(), json);
+
+        let script = Script::inline("1").with_lang("groovy");
+        assert_eq!(
+            script.lang,
+            Some(ScriptLanguage::Other("groovy".to_string()))
+        );
+        assert_eq!(
+            serde_json::to_value(&script).unwrap(),
+            json!({ "source": "1", "lang": "groovy" })
+        );
+    }
+
+    #[test]
+    fn rejects_template_languages() {
+        assert_eq!(Script::inline("1").validate(), Ok(()));
+        assert_eq!(
+            Script::inline("{{x}}")
+                .with_lang(ScriptLanguage::Mustache)
+                .validate(),
+            Err(ScriptError::TemplateLanguage(ScriptLanguage::Mustache))
+        );
+    }
+
+    #[test]
+    fn can_serialize_script_fields() {
+        let request = crate::search::Request::builder()
+            .script_fields(
+                vec![(
+                    "total".to_string(),
+                    ScriptField {
+                        script: Script::inline("doc['price'].value * doc['quantity'].value"),
+                        ignore_failure: Some(true),
+                    },
+                )]
+                .into_iter()
+                .collect::<HashMap<_, _>>(),
+            )
+            .build();
+
+        assert_eq!(
+            serde_json::to_value(&request).unwrap()["script_fields"],
+            json!({
+                "total": {
+                    "script": { "source": "doc['price'].value * doc['quantity'].value" },
+                    "ignore_failure": true
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn can_deserialize_stored_script_response() {
+        let response: GetStoredScriptResponse = serde_json::from_value(json!({
+            "_id": "score",
+            "found": true,
+            "script": { "lang": "painless", "source": "Math.log(_score * 2)" }
+        }))
+        .unwrap();
+
+        assert!(response.found);
+        assert_eq!(
+            response.script,
+            Some(StoredScript {
+                lang: ScriptLanguage::Painless,
+                source: "Math.log(_score * 2)".to_string(),
+                options: HashMap::new(),
+            })
+        );
+
+        let response: GetStoredScriptResponse =
+            serde_json::from_value(json!({ "_id": "missing", "found": false })).unwrap();
+        assert_eq!(response.script, None);
+    }
+}
@@ -4,14 +4,18 @@
 
 use std::default::Default;
 
+#[cfg(feature = "schemars")]
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
 use serde::de::{self, Deserializer, Visitor};
 use serde::ser::{SerializeMap, Serializer};
 use serde::{Deserialize, Serialize};
 
 /// The [sort order](https://www.elastic.co/guide/en/elasticsearch/reference/current/search-request-sort.html#_sort_order)
-#[cfg_attr(all(test, not(feature = "graphql")), derive(PartialEq))]
-#[cfg_attr(feature = "graphql", derive(async_graphql::Enum, Eq, PartialEq, Copy))]
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("SortOrder"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum SortOrder {
     /// Sort in ascending order
@@ -21,29 +25,27 @@ pub enum SortOrder {
     Desc,
 }
 
-/// The [sort mode](https://www.elastic.co/guide/en/elasticsearch/reference/current/search-request-sort.html#_sort_mode_option)
-#[cfg_attr(all(test, not(feature = "graphql")), derive(PartialEq))]
-#[cfg_attr(feature = "graphql", derive(async_graphql::Enum, Eq, PartialEq, Copy))]
-#[derive(Serialize, Deserialize, Clone, Debug)]
-#[serde(rename_all = "snake_case")]
-pub enum SortMode {
-    /// Pick the lowest value.
-    Min,
+open_enum! {
+    /// The [sort mode](https://www.elastic.co/guide/en/elasticsearch/reference/current/search-request-sort.html#_sort_mode_option)
+    pub enum SortMode as "SortMode" {
+        /// Pick the lowest value.
+        Min => "min" as "MIN",
 
-    /// Pick the highest value.
-    Max,
+        /// Pick the highest value.
+        Max => "max" as "MAX",
 
-    /// Use the sum of all values as sort value.
-    /// Only applicable for number based array fields.
-    Sum,
+        /// Use the sum of all values as sort value.
+        /// Only applicable for number based array fields.
+        Sum => "sum" as "SUM",
 
-    /// Use the average of all values as sort value.
-    /// Only applicable for number based array fields.
-    Avg,
+        /// Use the average of all values as sort value.
+        /// Only applicable for number based array fields.
+        Avg => "avg" as "AVG",
 
-    /// Use the median of all values as sort value.
-    /// Only applicable for number based array fields.
-    Median,
+        /// Use the median of all values as sort value.
+        /// Only applicable for number based array fields.
+        Median => "median" as "MEDIAN",
+    }
 }
 
 /// The options for sorting.
@@ -57,7 +59,8 @@ pub enum SortMode {
 /// **NOTE**: the `id` field will always be used as a tie breaker or a default,
 /// regardless of any value specified.
 #[cfg(feature = "graphql")]
-#[derive(async_graphql::InputObject, PartialEq, Clone, Debug)]
+#[elastiql_derive::graphql_name("SortInput")]
+#[derive(async_graphql::InputObject, PartialEq, Eq, Hash, Clone, Debug)]
 pub struct SortInput {
     /// The field to sort by.
     ///
@@ -75,6 +78,13 @@ pub struct SortInput {
 
     /// The mode to sort with.
     pub mode: Option<SortMode>,
+
+    /// The [type] to sort the documents of indices without a mapping for
+    /// `field` as, e.g. `keyword` or `long`. Without one, searching indices
+    /// that don't map `field` fails.
+    ///
+    /// [type]: https://www.elastic.co/guide/en/elasticsearch/reference/current/sort-search-results.html#_ignoring_unmapped_fields
+    pub unmapped_type: Option<String>,
 }
 
 #[cfg(feature = "graphql")]
@@ -88,6 +98,7 @@ impl Default for SortInput {
             field: "id".to_string(),
             order: None,
             mode: None,
+            unmapped_type: None,
         }
     }
 }
@@ -120,9 +131,10 @@ impl Serialize for SortInput {
 ///
 /// **NOTE**: the `id` field will always be used as a tie breaker or a default,
 /// regardless of any value specified.
-#[cfg_attr(all(test, not(feature = "graphql")), derive(PartialEq))]
-#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject, PartialEq))]
-#[derive(Clone, Debug)]
+#[cfg_attr(feature = "graphql", elastiql_derive::graphql_name("Sort"))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Sort {
     /// The field to sort by.
     ///
@@ -133,13 +145,76 @@ pub struct Sort {
     /// **TODO**: should this be an enum?
     ///
     /// [union input types]: https://github.com/graphql/graphql-spec/blob/master/rfcs/InputUnion.md
-    field: String,
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::field))]
+    pub(crate) field: String,
 
     /// The order to sort by.
-    order: Option<SortOrder>,
+    pub(crate) order: Option<SortOrder>,
 
     /// The mode to sort with.
-    mode: Option<SortMode>,
+    pub(crate) mode: Option<SortMode>,
+
+    /// The [type] to sort the documents of indices without a mapping for
+    /// `field` as, e.g. `keyword` or `long`. Without one, searching indices
+    /// that don't map `field` fails.
+    ///
+    /// [type]: https://www.elastic.co/guide/en/elasticsearch/reference/current/sort-search-results.html#_ignoring_unmapped_fields
+    pub(crate) unmapped_type: Option<String>,
+}
+
+impl Sort {
+    /// Constructs a new `Sort` by `field`.
+    #[inline]
+    pub fn new(field: impl Into<String>) -> Self {
+        Sort {
+            field: field.into(),
+            order: None,
+            mode: None,
+            unmapped_type: None,
+        }
+    }
+
+    /// Constructs a new `Sort` by `field` that sorts the documents of indices
+    /// without a mapping for it as `keyword`s, unless it is a special field
+    /// such as `_score`.
+    ///
+    /// This is how every `Sort` used to be serialized.
+    #[inline]
+    pub fn unmapped_as_keyword(field: impl Into<String>) -> Self {
+        let field = field.into();
+        let unmapped_type = if field.starts_with('_') {
+            None
+        } else {
+            Some("keyword".to_string())
+        };
+
+        Sort {
+            unmapped_type,
+            ..Sort::new(field)
+        }
+    }
+
+    /// Sorts in the given `order`.
+    #[inline]
+    pub fn with_order(mut self, order: SortOrder) -> Self {
+        self.order = Some(order);
+        self
+    }
+
+    /// Sorts with the given `mode`.
+    #[inline]
+    pub fn with_mode(mut self, mode: SortMode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Sorts the documents of indices without a mapping for the field as the
+    /// given type.
+    #[inline]
+    pub fn with_unmapped_type(mut self, ty: impl Into<String>) -> Self {
+        self.unmapped_type = Some(ty.into());
+        self
+    }
 }
 
 impl Default for Sort {
@@ -152,6 +227,7 @@ impl Default for Sort {
             field: "id".to_string(),
             order: None,
             mode: None,
+            unmapped_type: None,
         }
     }
 }
@@ -164,6 +240,20 @@ impl From<SortInput> for Sort {
             field: input.field,
             order: input.order,
             mode: input.mode,
+            unmapped_type: input.unmapped_type,
+        }
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl From<Sort> for SortInput {
+    #[inline]
+    fn from(sort: Sort) -> Self {
+        Self {
+            field: sort.field,
+            order: sort.order,
+            mode: sort.mode.clone(),
+            unmapped_type: sort.unmapped_type,
         }
     }
 }
@@ -186,6 +276,32 @@ impl Serialize for Sort {
     }
 }
 
+#[cfg(feature = "schemars")]
+impl JsonSchema for Sort {
+    #[inline]
+    fn schema_name() -> String {
+        "Sort".to_string()
+    }
+
+    #[inline]
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        crate::schema::keyed(InnerSortValue::json_schema(gen))
+    }
+}
+
+#[cfg(all(feature = "graphql", feature = "schemars"))]
+impl JsonSchema for SortInput {
+    #[inline]
+    fn schema_name() -> String {
+        Sort::schema_name()
+    }
+
+    #[inline]
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        Sort::json_schema(gen)
+    }
+}
+
 impl<'de> Deserialize<'de> for Sort {
     #[inline]
     fn deserialize<D>(deserializer: D) -> Result<Sort, D::Error>
@@ -199,7 +315,16 @@ impl<'de> Deserialize<'de> for Sort {
             type Value = Sort;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-                formatter.write_str("a `Sort`")
+                formatter.write_str("a `Sort` or the name of a field to sort by")
+            }
+
+            fn visit_str<E: de::Error>(self, field: &str) -> Result<Self::Value, E> {
+                Ok(Sort {
+                    field: field.to_string(),
+                    order: None,
+                    mode: None,
+                    unmapped_type: None,
+                })
             }
 
             fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
@@ -210,21 +335,40 @@ impl<'de> Deserialize<'de> for Sort {
                     .next_key::<String>()?
                     .ok_or_else(|| de::Error::missing_field("field"))?;
 
-                let inner: InnerSortValue = map.next_value()?;
+                let inner = match map.next_value()? {
+                    SortValue::Order(order) => InnerSortValue {
+                        order: Some(order),
+                        mode: None,
+                        unmapped_type: None,
+                    },
+                    SortValue::Options(inner) => inner,
+                };
 
                 Ok(Sort {
                     field,
                     order: inner.order,
                     mode: inner.mode,
+                    unmapped_type: inner.unmapped_type,
                 })
             }
         }
 
-        deserializer.deserialize_map(SortVisitor)
+        deserializer.deserialize_any(SortVisitor)
     }
 }
 
+/// The sorting criteria of a field, which Elasticsearch also accepts as just
+/// the order, e.g. `{ "price": "desc" }`.
+#[allow(clippy::missing_docs_in_private_items)]
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SortValue {
+    Order(SortOrder),
+    Options(InnerSortValue),
+}
+
 /// Sorting criteria in a format suitable for Elasticsearch.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[derive(Serialize, Deserialize)]
 struct InnerSortValue {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -242,14 +386,8 @@ impl From<&SortInput> for InnerSortValue {
         // TODO: make it so we don't have to clone- maybe borrow data in InnerSortValue?
         InnerSortValue {
             order: sort.order,
-            mode: sort.mode,
-            // HACK: in case the field is one we don't have an index mapping for
-            //       see: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-request-body.html#_ignoring_unmapped_fields
-            unmapped_type: if sort.field.starts_with('_') {
-                None
-            } else {
-                Some("keyword".to_string())
-            },
+            mode: sort.mode.clone(),
+            unmapped_type: sort.unmapped_type.clone(),
         }
     }
 }
@@ -259,17 +397,10 @@ impl From<&Sort> for InnerSortValue {
     #[inline]
     fn from(sort: &Sort) -> Self {
         // TODO: make it so we don't have to clone- maybe borrow data in InnerSortValue?
-        #[allow(clippy::clone_on_copy)] // necessary for TypedBuilder
         InnerSortValue {
-            order: sort.order.clone(),
+            order: sort.order,
             mode: sort.mode.clone(),
-            // HACK: in case the field is one we don't have an index mapping for
-            //       see: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-request-body.html#_ignoring_unmapped_fields
-            unmapped_type: if sort.field.starts_with('_') {
-                None
-            } else {
-                Some("keyword".to_string())
-            },
+            unmapped_type: sort.unmapped_type.clone(),
         }
     }
 }
@@ -284,7 +415,7 @@ mod tests {
     #[test]
     fn can_serialize_default() {
         let f = Sort::default();
-        let j = json!({ "id": { "unmapped_type": "keyword" } });
+        let j = json!({ "id": { } });
         assert_eq!(serde_json::to_value(&f).unwrap(), j, "{}", &j);
     }
 
@@ -295,17 +426,19 @@ mod tests {
                 field: "id".to_string(),
                 mode: None,
                 order: Some(SortOrder::Asc),
+                unmapped_type: None,
             },
             Sort {
                 field: "id".to_string(),
                 mode: None,
                 order: Some(SortOrder::Desc),
+                unmapped_type: None,
             },
         ];
 
         let j = json!([
-            { "id": { "order": "asc", "unmapped_type": "keyword" } },
-            { "id": { "order": "desc", "unmapped_type": "keyword" } },
+            { "id": { "order": "asc" } },
+            { "id": { "order": "desc" } },
         ]);
 
         assert_eq!(serde_json::to_value(&sort).unwrap(), j, "{}", &j);
@@ -325,37 +458,83 @@ mod tests {
             field: "id".to_string(),
             mode: Some(m),
             order: None,
+            unmapped_type: None,
         })
         .collect();
 
         let j = json!([
-            { "id": { "mode": "min", "unmapped_type": "keyword" } },
-            { "id": { "mode": "max", "unmapped_type": "keyword" } },
-            { "id": { "mode": "sum", "unmapped_type": "keyword" } },
-            { "id": { "mode": "avg", "unmapped_type": "keyword" } },
-            { "id": { "mode": "median", "unmapped_type": "keyword" } },
+            { "id": { "mode": "min" } },
+            { "id": { "mode": "max" } },
+            { "id": { "mode": "sum" } },
+            { "id": { "mode": "avg" } },
+            { "id": { "mode": "median" } },
         ]);
 
         assert_eq!(serde_json::to_value(&sorts).unwrap(), j, "{}", &j);
     }
 
+    #[test]
+    fn can_round_trip_unknown_modes() {
+        let j = json!({ "id": { "mode": "geometric_mean" } });
+
+        let sort: Sort = serde_json::from_value(j.clone()).unwrap();
+        assert_eq!(
+            sort.mode,
+            Some(SortMode::Other("geometric_mean".to_string()))
+        );
+        assert_eq!(serde_json::to_value(&sort).unwrap(), j);
+    }
+
     #[test]
     fn can_serialize_with_everything() {
         let sort = Sort {
             field: "id".to_string(),
             mode: Some(SortMode::Max),
             order: Some(SortOrder::Desc),
+            unmapped_type: Some("long".to_string()),
         };
-        let j = json!({ "id": { "mode": "max", "order": "desc", "unmapped_type": "keyword" } });
+        let j = json!({ "id": { "mode": "max", "order": "desc", "unmapped_type": "long" } });
         assert_eq!(serde_json::to_value(&sort).unwrap(), j, "{}", &j);
     }
 
+    #[test]
+    fn can_serialize_unmapped_as_keyword() {
+        let sorts = vec![
+            Sort::unmapped_as_keyword("name").with_order(SortOrder::Asc),
+            Sort::unmapped_as_keyword("_score"),
+        ];
+        let j = json!([
+            { "name": { "order": "asc", "unmapped_type": "keyword" } },
+            { "_score": { } },
+        ]);
+        assert_eq!(serde_json::to_value(&sorts).unwrap(), j, "{}", &j);
+    }
+
+    #[test]
+    fn can_deduplicate() {
+        let sort = Sort {
+            field: "id".to_string(),
+            mode: None,
+            order: Some(SortOrder::Desc),
+            unmapped_type: None,
+        };
+
+        let sorts: std::collections::HashSet<Sort> =
+            vec![sort.clone(), sort.clone(), Sort::default()]
+                .into_iter()
+                .collect();
+
+        assert_eq!(sorts.len(), 2, "{:#?}", &sorts);
+        assert!(sorts.contains(&sort), "{:#?}", &sorts);
+    }
+
     #[test]
     fn can_serialize_with_special_field() {
         let sort = Sort {
             field: "_score".to_string(),
             mode: None,
             order: None,
+            unmapped_type: None,
         };
         let j = json!({ "_score": { } });
         assert_eq!(serde_json::to_value(&sort).unwrap(), j, "{}", &j);
@@ -364,6 +543,7 @@ mod tests {
             field: "_key".to_string(),
             mode: Some(SortMode::Avg),
             order: None,
+            unmapped_type: None,
         };
         let j = json!({ "_key": { "mode": "avg" } });
         assert_eq!(serde_json::to_value(&sort).unwrap(), j, "{}", &j);
@@ -372,6 +552,7 @@ mod tests {
             field: "_count".to_string(),
             mode: None,
             order: None,
+            unmapped_type: None,
         };
         let j = json!({ "_count": { } });
         assert_eq!(serde_json::to_value(&sort).unwrap(), j, "{}", &j);
@@ -386,8 +567,38 @@ mod tests {
             field: "id".to_string(),
             mode: Some(SortMode::Max),
             order: Some(SortOrder::Desc),
+            unmapped_type: Some("keyword".to_string()),
         };
 
         assert_eq!(actual, expected, "{:#?}", &actual);
     }
+
+    #[test]
+    fn can_deserialize_shorthands() {
+        let j = json!(["timestamp", { "price": "desc" }, "_score"]);
+        let actual: Vec<Sort> = serde_json::from_value(j).unwrap();
+
+        let expected = vec![
+            Sort {
+                field: "timestamp".to_string(),
+                mode: None,
+                order: None,
+                unmapped_type: None,
+            },
+            Sort {
+                field: "price".to_string(),
+                mode: None,
+                order: Some(SortOrder::Desc),
+                unmapped_type: None,
+            },
+            Sort {
+                field: "_score".to_string(),
+                mode: None,
+                order: None,
+                unmapped_type: None,
+            },
+        ];
+
+        assert_eq!(actual, expected, "{:#?}", &actual);
+    }
 }
@@ -0,0 +1,497 @@
+//! Request and response types for the [snapshot and restore] APIs.
+//!
+//! ```
+//! use elastiql::snapshot::{CreateSnapshotRequest, RestoreRequest};
+//!
+//! let snapshot = CreateSnapshotRequest::builder()
+//!     .indices(vec!["logs-*".to_string()])
+//!     .include_global_state(false)
+//!     .build();
+//!
+//! let restore = RestoreRequest::builder()
+//!     .indices(vec!["logs-*".to_string()])
+//!     .rename_pattern("logs-(.+)".to_string())
+//!     .rename_replacement("restored-logs-$1".to_string())
+//!     .build();
+//! ```
+//!
+//! [snapshot and restore]: https://www.elastic.co/guide/en/elasticsearch/reference/current/snapshot-restore.html
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// The body of a request to [register a snapshot repository].
+///
+/// [register a snapshot repository]: https://www.elastic.co/guide/en/elasticsearch/reference/current/put-snapshot-repo-api.html
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(setter(into))))]
+pub struct Repository {
+    /// The type of the repository, e.g. `fs`, `s3` or `gcs`.
+    #[serde(rename = "type")]
+    pub ty: String,
+
+    /// The settings of the repository, which depend on its type, e.g. the
+    /// `location` of an `fs` repository or the `bucket` of an `s3` one.
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default)]
+    pub settings: Map<String, Value>,
+}
+
+impl_display_json!(Repository);
+
+/// The body of a request to [create a snapshot].
+///
+/// [create a snapshot]: https://www.elastic.co/guide/en/elasticsearch/reference/current/create-snapshot-api.html
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(default, setter(into))))]
+pub struct CreateSnapshotRequest {
+    /// The indices and data streams to snapshot, which may be patterns, e.g.
+    /// `logs-*`. (Defaults to all of them.)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub indices: Vec<String>,
+
+    /// Whether missing or closed indices are ignored instead of failing the
+    /// request. (Defaults to `false`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ignore_unavailable: Option<bool>,
+
+    /// Whether the cluster state, e.g. persistent settings and index
+    /// templates, is included. (Defaults to `true`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_global_state: Option<bool>,
+
+    /// Whether a partial snapshot is created when some of the shards are
+    /// unavailable, instead of failing. (Defaults to `false`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partial: Option<bool>,
+
+    /// The feature states to include, e.g. `security`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub feature_states: Vec<String>,
+
+    /// Custom metadata to attach to the snapshot.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Map<String, Value>>,
+}
+
+impl_display_json!(CreateSnapshotRequest);
+
+/// The response to a request to create a snapshot.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct CreateSnapshotResponse {
+    /// Whether the snapshot was started, if the request didn't wait for it to
+    /// complete.
+    #[serde(default)]
+    pub accepted: Option<bool>,
+
+    /// The snapshot, if the request waited for it to complete.
+    #[serde(default)]
+    pub snapshot: Option<SnapshotInfo>,
+}
+
+/// The response to a request to [get snapshots].
+///
+/// [get snapshots]: https://www.elastic.co/guide/en/elasticsearch/reference/current/get-snapshot-api.html
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct GetSnapshotsResponse {
+    /// The snapshots.
+    #[serde(default)]
+    pub snapshots: Vec<SnapshotInfo>,
+}
+
+/// Information about a snapshot.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct SnapshotInfo {
+    /// The name of the snapshot.
+    pub snapshot: String,
+
+    /// The UUID of the snapshot.
+    pub uuid: String,
+
+    /// The name of the repository of the snapshot.
+    #[serde(default)]
+    pub repository: Option<String>,
+
+    /// The version of Elasticsearch that created the snapshot.
+    #[serde(default)]
+    pub version: Option<String>,
+
+    /// The indices in the snapshot.
+    #[serde(default)]
+    pub indices: Vec<String>,
+
+    /// The data streams in the snapshot.
+    #[serde(default)]
+    pub data_streams: Vec<String>,
+
+    /// Whether the snapshot includes the cluster state.
+    #[serde(default)]
+    pub include_global_state: Option<bool>,
+
+    /// The custom metadata attached to the snapshot.
+    #[serde(default)]
+    pub metadata: Option<Map<String, Value>>,
+
+    /// The state of the snapshot.
+    pub state: SnapshotState,
+
+    /// When the snapshot started, in milliseconds since the epoch.
+    #[serde(default)]
+    pub start_time_in_millis: Option<u64>,
+
+    /// When the snapshot ended, in milliseconds since the epoch.
+    #[serde(default)]
+    pub end_time_in_millis: Option<u64>,
+
+    /// How long the snapshot took, in milliseconds.
+    #[serde(default)]
+    pub duration_in_millis: Option<u64>,
+
+    /// The failures of the shards that couldn't be snapshotted.
+    #[serde(default)]
+    pub failures: Vec<Value>,
+
+    /// The number of shards that were (or weren't) snapshotted.
+    #[serde(default)]
+    pub shards: Option<ShardStats>,
+}
+
+/// The state of a snapshot.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SnapshotState {
+    /// The snapshot is being created.
+    InProgress,
+
+    /// The snapshot was created successfully.
+    Success,
+
+    /// The snapshot failed, i.e. no data was stored.
+    Failed,
+
+    /// The snapshot was created, but some of its shards weren't stored.
+    Partial,
+
+    /// The snapshot was created by an incompatible version of Elasticsearch.
+    Incompatible,
+}
+
+/// The number of shards that were (or weren't) snapshotted or restored.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ShardStats {
+    /// The total number of shards.
+    pub total: u64,
+
+    /// The number of shards that failed.
+    pub failed: u64,
+
+    /// The number of shards that succeeded.
+    pub successful: u64,
+}
+
+/// The response to a request to get the [status of snapshots].
+///
+/// [status of snapshots]: https://www.elastic.co/guide/en/elasticsearch/reference/current/get-snapshot-status-api.html
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct SnapshotStatusResponse {
+    /// The statuses of the snapshots.
+    #[serde(default)]
+    pub snapshots: Vec<SnapshotStatus>,
+}
+
+/// The detailed status of a snapshot.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct SnapshotStatus {
+    /// The name of the snapshot.
+    pub snapshot: String,
+
+    /// The name of the repository of the snapshot.
+    pub repository: String,
+
+    /// The UUID of the snapshot.
+    pub uuid: String,
+
+    /// The state of the snapshot, e.g. `STARTED` or `SUCCESS`.
+    pub state: String,
+
+    /// Whether the snapshot includes the cluster state.
+    #[serde(default)]
+    pub include_global_state: Option<bool>,
+
+    /// The number of shards in each stage of the snapshot.
+    pub shards_stats: ShardsStats,
+
+    /// The number and size of the files of the snapshot.
+    pub stats: SnapshotStats,
+}
+
+/// The number of shards in each stage of a snapshot.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ShardsStats {
+    /// The number of shards that are initializing.
+    pub initializing: u64,
+
+    /// The number of shards that are being snapshotted.
+    pub started: u64,
+
+    /// The number of shards that are being finalized.
+    pub finalizing: u64,
+
+    /// The number of shards that were snapshotted.
+    pub done: u64,
+
+    /// The number of shards that failed.
+    pub failed: u64,
+
+    /// The total number of shards.
+    pub total: u64,
+}
+
+/// The number and size of the files of a snapshot.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SnapshotStats {
+    /// The files that were copied by the snapshot, i.e. that weren't already
+    /// in the repository.
+    pub incremental: FileStats,
+
+    /// All of the files referenced by the snapshot.
+    pub total: FileStats,
+
+    /// When the snapshot started, in milliseconds since the epoch.
+    pub start_time_in_millis: u64,
+
+    /// How long the snapshot has taken, in milliseconds.
+    pub time_in_millis: u64,
+}
+
+/// The number and size of some files.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FileStats {
+    /// The number of files.
+    pub file_count: u64,
+
+    /// The size of the files, in bytes.
+    pub size_in_bytes: u64,
+}
+
+/// The body of a request to [restore a snapshot].
+///
+/// [restore a snapshot]: https://www.elastic.co/guide/en/elasticsearch/reference/current/restore-snapshot-api.html
+#[cfg_attr(feature = "builder", derive(typed_builder::TypedBuilder))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "builder", builder(field_defaults(default, setter(into))))]
+pub struct RestoreRequest {
+    /// The indices and data streams to restore, which may be patterns, e.g.
+    /// `logs-*`. (Defaults to all of them.)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub indices: Vec<String>,
+
+    /// Whether missing indices are ignored instead of failing the request.
+    /// (Defaults to `false`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ignore_unavailable: Option<bool>,
+
+    /// Whether the cluster state is restored. (Defaults to `false`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_global_state: Option<bool>,
+
+    /// Whether the aliases of the indices are restored. (Defaults to
+    /// `true`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_aliases: Option<bool>,
+
+    /// Whether indices of partial snapshots are restored, with the missing
+    /// shards empty. (Defaults to `false`.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partial: Option<bool>,
+
+    /// A regular expression matching the names of the indices to rename,
+    /// e.g. `logs-(.+)`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rename_pattern: Option<String>,
+
+    /// The new names of the indices matching the `rename_pattern`, which may
+    /// refer to its capture groups, e.g. `restored-logs-$1`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rename_replacement: Option<String>,
+
+    /// Settings to override on the restored indices, e.g.
+    /// `index.number_of_replicas`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index_settings: Option<Map<String, Value>>,
+
+    /// Settings of the indices that aren't restored, e.g.
+    /// `index.refresh_interval`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ignore_index_settings: Vec<String>,
+
+    /// The feature states to restore, e.g. `security`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub feature_states: Vec<String>,
+}
+
+impl_display_json!(RestoreRequest);
+
+/// The response to a request to restore a snapshot.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct RestoreResponse {
+    /// Whether the restore was started, if the request didn't wait for it to
+    /// complete.
+    #[serde(default)]
+    pub accepted: Option<bool>,
+
+    /// The restored snapshot, if the request waited for it to complete.
+    #[serde(default)]
+    pub snapshot: Option<RestoreInfo>,
+}
+
+/// Information about a restored snapshot.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct RestoreInfo {
+    /// The name of the snapshot.
+    pub snapshot: String,
+
+    /// The restored indices.
+    #[serde(default)]
+    pub indices: Vec<String>,
+
+    /// The number of shards that were (or weren't) restored.
+    pub shards: ShardStats,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn can_serialize_requests() {
+        let repository = Repository {
+            ty: "fs".to_string(),
+            settings: json!({ "location": "/mnt/backups" })
+                .as_object()
+                .cloned()
+                .unwrap(),
+        };
+        assert_eq!(
+            serde_json::to_value(&repository).unwrap(),
+            json!({ "type": "fs", "settings": { "location": "/mnt/backups" } })
+        );
+
+        let snapshot = CreateSnapshotRequest {
+            indices: vec!["logs-*".to_string()],
+            ignore_unavailable: Some(true),
+            include_global_state: Some(false),
+            ..CreateSnapshotRequest::default()
+        };
+        assert_eq!(
+            serde_json::to_value(&snapshot).unwrap(),
+            json!({
+                "indices": ["logs-*"],
+                "ignore_unavailable": true,
+                "include_global_state": false
+            })
+        );
+
+        let restore = RestoreRequest {
+            indices: vec!["logs-*".to_string()],
+            rename_pattern: Some("logs-(.+)".to_string()),
+            rename_replacement: Some("restored-logs-$1".to_string()),
+            include_aliases: Some(false),
+            ..RestoreRequest::default()
+        };
+        assert_eq!(
+            serde_json::to_value(&restore).unwrap(),
+            json!({
+                "indices": ["logs-*"],
+                "include_aliases": false,
+                "rename_pattern": "logs-(.+)",
+                "rename_replacement": "restored-logs-$1"
+            })
+        );
+    }
+
+    #[test]
+    fn can_deserialize_snapshot_responses() {
+        let response: CreateSnapshotResponse = serde_json::from_value(json!({
+            "snapshot": {
+                "snapshot": "nightly",
+                "uuid": "dKb54xw67gvdRctLCxSWCw",
+                "repository": "backups",
+                "version_id": 8_000_099,
+                "version": "8.0.0",
+                "indices": ["logs-2021.01.01"],
+                "data_streams": [],
+                "feature_states": [],
+                "include_global_state": false,
+                "metadata": { "taken_by": "cron" },
+                "state": "SUCCESS",
+                "start_time": "2021-01-01T00:00:00.000Z",
+                "start_time_in_millis": 1_609_459_200_000_u64,
+                "end_time": "2021-01-01T00:00:01.000Z",
+                "end_time_in_millis": 1_609_459_201_000_u64,
+                "duration_in_millis": 1000,
+                "failures": [],
+                "shards": { "total": 1, "failed": 0, "successful": 1 }
+            }
+        }))
+        .unwrap();
+        let snapshot = response.snapshot.unwrap();
+        assert_eq!(snapshot.state, SnapshotState::Success);
+        assert_eq!(snapshot.indices, vec!["logs-2021.01.01"]);
+
+        let response: CreateSnapshotResponse =
+            serde_json::from_value(json!({ "accepted": true })).unwrap();
+        assert_eq!(response.accepted, Some(true));
+
+        let response: SnapshotStatusResponse = serde_json::from_value(json!({
+            "snapshots": [{
+                "snapshot": "nightly",
+                "repository": "backups",
+                "uuid": "dKb54xw67gvdRctLCxSWCw",
+                "state": "STARTED",
+                "include_global_state": false,
+                "shards_stats": {
+                    "initializing": 0, "started": 1, "finalizing": 0,
+                    "done": 4, "failed": 0, "total": 5
+                },
+                "stats": {
+                    "incremental": { "file_count": 3, "size_in_bytes": 1024 },
+                    "total": { "file_count": 10, "size_in_bytes": 4096 },
+                    "start_time_in_millis": 1_609_459_200_000_u64,
+                    "time_in_millis": 500
+                },
+                "indices": {}
+            }]
+        }))
+        .unwrap();
+        assert_eq!(response.snapshots[0].shards_stats.done, 4);
+    }
+
+    #[test]
+    fn can_deserialize_restore_response() {
+        let response: RestoreResponse = serde_json::from_value(json!({
+            "snapshot": {
+                "snapshot": "nightly",
+                "indices": ["restored-logs-2021.01.01"],
+                "shards": { "total": 1, "failed": 0, "successful": 1 }
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            response.snapshot.unwrap().shards,
+            ShardStats {
+                total: 1,
+                failed: 0,
+                successful: 1
+            }
+        );
+    }
+}
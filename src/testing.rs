@@ -0,0 +1,556 @@
+//! Fabricates Elasticsearch responses from requests, for unit testing code
+//! that builds requests and handles responses without a live cluster.
+//!
+//! ```
+//! use elastiql::{
+//!     aggregation,
+//!     search::{self, Request},
+//!     testing::MockResponses,
+//! };
+//! use serde_json::json;
+//!
+//! let request = Request::builder().size(2).build();
+//! let aggregations: Vec<aggregation::Request> =
+//!     vec![serde_json::from_value(json!({ "statuses": { "terms": { "field": "status" } } })).unwrap()];
+//!
+//! let mock = MockResponses {
+//!     docs: vec![json!({ "id": "1" }), json!({ "id": "2" }), json!({ "id": "3" })],
+//!     ..MockResponses::default()
+//! };
+//! let json = mock.search(&request, &aggregations);
+//!
+//! let response: search::OkResponse<serde_json::Value> =
+//!     serde_json::from_value(json.clone()).unwrap();
+//! assert_eq!(response.hits.hits.len(), 2);
+//! assert_eq!(response.hits.total_count.value, 3);
+//!
+//! let response: aggregation::Response = serde_json::from_value(json).unwrap();
+//! assert_eq!(response.aggregations[0].values.len(), 3);
+//! ```
+
+use std::cmp::Ordering;
+
+use serde_json::{json, Map, Value};
+
+use crate::{
+    aggregation,
+    search::{self, SortOrder},
+};
+
+/// Fabricates responses to search and aggregation requests.
+///
+/// Hits are fabricated from `docs` (or from generated documents if there
+/// aren't any), ordered by the requested `sort` and paginated with `size` and
+/// `search_after` like Elasticsearch would. Aggregation results are fabricated
+/// for every requested aggregation (and sub aggregation) with deterministic
+/// values, keyed by their [typed keys] so they can be parsed into an
+/// [`aggregation::Response`].
+///
+/// [typed keys]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations.html#return-agg-type
+#[derive(Clone, Debug, PartialEq)]
+pub struct MockResponses {
+    /// The name of the index the hits belong to.
+    pub index: String,
+
+    /// The source documents of the hits.
+    pub docs: Vec<Value>,
+
+    /// The total count of the hits, which defaults to the number of `docs`.
+    ///
+    /// If there aren't any `docs`, this many documents are generated (up to
+    /// the requested `size`).
+    pub total: Option<u64>,
+
+    /// The maximum number of buckets per bucketing aggregation, regardless of
+    /// its requested `size`.
+    pub buckets: u64,
+}
+
+impl Default for MockResponses {
+    #[inline]
+    fn default() -> Self {
+        MockResponses {
+            index: "mock".to_string(),
+            docs: vec![],
+            total: None,
+            buckets: 3,
+        }
+    }
+}
+
+impl MockResponses {
+    /// Fabricates the JSON response to a search `request` with
+    /// `aggregations`.
+    pub fn search(
+        &self,
+        request: &search::Request,
+        aggregations: &[aggregation::Request],
+    ) -> Value {
+        let size = request.size.unwrap_or(10);
+
+        let docs: Vec<Value> = if self.docs.is_empty() {
+            (1..=self.total.unwrap_or(size))
+                .map(|i| json!({ "id": i.to_string() }))
+                .collect()
+        } else {
+            self.docs.clone()
+        };
+        let total = self.total.unwrap_or(docs.len() as u64);
+
+        let mut hits: Vec<(Vec<Value>, usize, Value)> = docs
+            .into_iter()
+            .enumerate()
+            .map(|(i, doc)| {
+                let sort = request
+                    .sort
+                    .iter()
+                    .map(|sort| sort_value(&sort.field, i, &doc))
+                    .collect();
+                (sort, i, doc)
+            })
+            .collect();
+        hits.sort_by(|(a, ..), (b, ..)| compare(&request.sort, a, b));
+
+        let after: Vec<Value> = request
+            .after
+            .iter()
+            .map(|value| serde_json::to_value(value).unwrap_or(Value::Null))
+            .collect();
+        if !after.is_empty() {
+            hits.retain(|(sort, ..)| compare(&request.sort, sort, &after) == Ordering::Greater);
+        }
+
+        let hits: Vec<Value> = hits
+            .into_iter()
+            .take(size as usize)
+            .map(|(sort, i, doc)| {
+                let id = match doc.get("id") {
+                    Some(Value::String(id)) => id.clone(),
+                    _ => (i + 1).to_string(),
+                };
+                let score = if request.sort.is_empty() {
+                    json!(1.0)
+                } else {
+                    Value::Null
+                };
+
+                let mut hit = json!({
+                    "_id": id,
+                    "_index": self.index,
+                    "_source": doc,
+                    "_score": score,
+                    "sort": sort,
+                });
+                if request.version {
+                    hit["_version"] = json!(1);
+                }
+                if request.seq_no_primary_term {
+                    hit["_seq_no"] = json!(i);
+                    hit["_primary_term"] = json!(1);
+                }
+                hit
+            })
+            .collect();
+
+        let max_score = if request.sort.is_empty() && !hits.is_empty() {
+            json!(1.0)
+        } else {
+            Value::Null
+        };
+
+        let mut response = json!({
+            "took": 1,
+            "timed_out": false,
+            "_shards": { "total": 1, "successful": 1, "skipped": 0, "failed": 0 },
+            "hits": {
+                "total": { "relation": "eq", "value": total },
+                "max_score": max_score,
+                "hits": hits,
+            },
+        });
+        if !aggregations.is_empty() {
+            response["aggregations"] = self.aggregate(aggregations, total);
+        }
+
+        response
+    }
+
+    /// Fabricates the JSON response to a search request with only
+    /// `aggregations`, i.e. with a `size` of `0`.
+    #[inline]
+    pub fn aggregations(&self, aggregations: &[aggregation::Request]) -> Value {
+        let request = search::Request {
+            size: Some(0),
+            ..search::Request::builder().build()
+        };
+        self.search(&request, aggregations)
+    }
+
+    /// Fabricates the results of `aggregations` over `doc_count` documents.
+    fn aggregate(&self, aggregations: &[aggregation::Request], doc_count: u64) -> Value {
+        let mut results = Map::new();
+
+        for aggregation in aggregations {
+            let (kind, mut result) = match self.aggregation(aggregation, doc_count) {
+                Some(result) => result,
+                None => continue,
+            };
+
+            if let Some(metadata) = &aggregation.metadata {
+                result["meta"] = json!(metadata);
+            }
+            results.insert(format!("{}#{}", kind, aggregation.name), result);
+        }
+
+        Value::Object(results)
+    }
+
+    /// Fabricates the result of a single `aggregation` over `doc_count`
+    /// documents, along with its type.
+    fn aggregation(
+        &self,
+        aggregation: &aggregation::Request,
+        doc_count: u64,
+    ) -> Option<(&'static str, Value)> {
+        let count = doc_count as f64;
+        let metric = |value: f64| json!({ "value": value });
+
+        let (kind, result) = if aggregation.avg.is_some() {
+            ("avg", metric(count / 2.0))
+        } else if aggregation.weighted_avg.is_some() {
+            ("weighted_avg", metric(count / 2.0))
+        } else if aggregation.cardinality.is_some() {
+            ("cardinality", metric(count.min(self.buckets as f64)))
+        } else if aggregation.max.is_some() {
+            ("max", metric(count))
+        } else if aggregation.min.is_some() {
+            ("min", metric(0.0))
+        } else if aggregation.median_absolute_deviation.is_some() {
+            ("median_absolute_deviation", metric(count / 4.0))
+        } else if aggregation.sum.is_some() {
+            ("sum", metric(count * count / 2.0))
+        } else if aggregation.value_count.is_some() {
+            ("value_count", metric(count))
+        } else if aggregation.percentiles.is_some() {
+            (
+                "percentiles",
+                json!({ "values": {
+                    "1.0": count * 0.01,
+                    "5.0": count * 0.05,
+                    "25.0": count * 0.25,
+                    "50.0": count * 0.5,
+                    "75.0": count * 0.75,
+                    "95.0": count * 0.95,
+                    "99.0": count * 0.99,
+                } }),
+            )
+        } else if let Some(percentile_ranks) = &aggregation.percentile_ranks {
+            // the values are spread evenly between `0` and `count`
+            let rank = |value: f64| {
+                if doc_count == 0 {
+                    Value::Null
+                } else {
+                    json!((value / count * 100.0).clamp(0.0, 100.0))
+                }
+            };
+            let values = &percentile_ranks.values;
+            let values = if percentile_ranks.keyed == Some(false) {
+                values
+                    .iter()
+                    .map(|&value| json!({ "key": value, "value": rank(value) }))
+                    .collect()
+            } else {
+                Value::Object(
+                    values
+                        .iter()
+                        .map(|&value| (format!("{:?}", value), rank(value)))
+                        .collect(),
+                )
+            };
+            ("percentile_ranks", json!({ "values": values }))
+        } else if aggregation.stats.is_some() || aggregation.extended_stats.is_some() {
+            let mut stats = json!({
+                "count": doc_count,
+                "min": 0.0,
+                "max": count,
+                "avg": count / 2.0,
+                "sum": count * count / 2.0,
+            });
+            if aggregation.extended_stats.is_some() {
+                stats["sum_of_squares"] = json!(count * count * count / 3.0);
+                stats["variance"] = json!(count * count / 12.0);
+                stats["std_deviation"] = json!((count * count / 12.0).sqrt());
+                ("extended_stats", stats)
+            } else {
+                ("stats", stats)
+            }
+        } else if aggregation.filters.is_some() {
+            ("filter", self.bucket(aggregation, doc_count / 2))
+        } else if aggregation.sampler.is_some() {
+            ("sampler", self.bucket(aggregation, doc_count))
+        } else if aggregation.nested.is_some() {
+            ("nested", self.bucket(aggregation, doc_count))
+        } else if aggregation.reverse_nested.is_some() {
+            ("reverse_nested", self.bucket(aggregation, doc_count))
+        } else if let Some(terms) = &aggregation.terms {
+            let size = terms.size.unwrap_or(10).min(self.buckets);
+            let mut result = self.buckets(aggregation, doc_count, size, |i| {
+                json!(format!(
+                    "{}_{}",
+                    terms.field.as_deref().unwrap_or("term"),
+                    i + 1
+                ))
+            });
+            result["doc_count_error_upper_bound"] = json!(0);
+            result["sum_other_doc_count"] = json!(0);
+            ("terms", result)
+        } else if aggregation.significant_text.is_some() {
+            let mut result = self.buckets(aggregation, doc_count, self.buckets, |i| {
+                json!(format!("term_{}", i + 1))
+            });
+            result["bg_count"] = json!(doc_count);
+            ("significant_text", result)
+        } else if let Some(range) = &aggregation.range {
+            let size = (range.ranges.len() as u64).min(self.buckets);
+            (
+                "range",
+                self.buckets(aggregation, doc_count, size, |i| {
+                    json!(format!("range_{}", i + 1))
+                }),
+            )
+        } else if let Some(range) = &aggregation.date_range {
+            let size = (range.ranges.len() as u64).min(self.buckets);
+            (
+                "date_range",
+                self.buckets(aggregation, doc_count, size, |i| {
+                    json!(format!("range_{}", i + 1))
+                }),
+            )
+        } else if aggregation.date_histogram.is_some() {
+            (
+                "date_histogram",
+                self.buckets(aggregation, doc_count, self.buckets, date_key),
+            )
+        } else if aggregation.auto_date_histogram.is_some() {
+            let mut result = self.buckets(aggregation, doc_count, self.buckets, date_key);
+            result["interval"] = json!("1d");
+            ("auto_date_histogram", result)
+        } else if let Some(histogram) = &aggregation.histogram {
+            let interval = histogram.interval.unwrap_or(1.0);
+            (
+                "histogram",
+                self.buckets(aggregation, doc_count, self.buckets, |i| {
+                    json!(i as f64 * interval)
+                }),
+            )
+        } else if aggregation.variable_width_histogram.is_some() {
+            (
+                "variable_width_histogram",
+                self.buckets(aggregation, doc_count, self.buckets, |i| json!(i as f64)),
+            )
+        } else if aggregation.bucket_script.is_some() {
+            ("bucket_script", metric(count))
+        } else {
+            // e.g. `bucket_selector` and `bucket_sort`, which only filter and
+            // sort the buckets of their parent
+            return None;
+        };
+
+        Some((kind, result))
+    }
+
+    /// Fabricates a single bucket with `doc_count` documents and the results
+    /// of the sub aggregations of `aggregation`.
+    fn bucket(&self, aggregation: &aggregation::Request, doc_count: u64) -> Value {
+        let mut bucket = match aggregation.aggregations.as_deref() {
+            Some(aggregations) => self.aggregate(aggregations, doc_count),
+            None => json!({}),
+        };
+        bucket["doc_count"] = json!(doc_count);
+        bucket
+    }
+
+    /// Fabricates `size` buckets, keyed by `key`, which split `doc_count`
+    /// documents in descending order of their counts.
+    fn buckets(
+        &self,
+        aggregation: &aggregation::Request,
+        doc_count: u64,
+        size: u64,
+        key: impl Fn(u64) -> Value,
+    ) -> Value {
+        let buckets: Vec<Value> = (0..size)
+            .map(|i| {
+                let mut bucket = self.bucket(aggregation, doc_count >> (i + 1).min(63));
+                bucket["key"] = key(i);
+                bucket
+            })
+            .collect();
+
+        json!({ "buckets": buckets })
+    }
+}
+
+/// The key of the `i`th bucket of a date histogram, i.e. the `i`th day of
+/// 2021.
+fn date_key(i: u64) -> Value {
+    const START: u64 = 1_609_459_200_000;
+    const DAY: u64 = 24 * 60 * 60 * 1000;
+
+    json!(START + i * DAY)
+}
+
+/// The value of the `i`th `doc` for sorting by `field`.
+fn sort_value(field: &str, i: usize, doc: &Value) -> Value {
+    match field {
+        "_score" | "score" => json!(1.0),
+        "_doc" => json!(i),
+        _ => field
+            .split('.')
+            .try_fold(doc, |value, key| value.get(key))
+            .cloned()
+            .unwrap_or_else(|| json!(i)),
+    }
+}
+
+/// Compares the sort values of two hits like Elasticsearch would for `sort`.
+fn compare(sort: &[search::Sort], a: &[Value], b: &[Value]) -> Ordering {
+    sort.iter()
+        .zip(a.iter().zip(b))
+        .map(|(sort, (a, b))| {
+            let ordering = compare_values(a, b);
+            match sort.order {
+                Some(SortOrder::Desc) => ordering.reverse(),
+                _ if sort.field == "_score" => ordering.reverse(),
+                _ => ordering,
+            }
+        })
+        .find(|ordering| *ordering != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Compares two sort values, with `null`s first, then booleans, numbers and
+/// strings.
+fn compare_values(a: &Value, b: &Value) -> Ordering {
+    /// The rank of the type of a `value` in the sort order.
+    fn rank(value: &Value) -> u8 {
+        match value {
+            Value::Null => 0,
+            Value::Bool(_) => 1,
+            Value::Number(_) => 2,
+            Value::String(_) => 3,
+            Value::Array(_) | Value::Object(_) => 4,
+        }
+    }
+
+    match (a, b) {
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        (Value::Number(a), Value::Number(b)) => a
+            .as_f64()
+            .partial_cmp(&b.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        _ => rank(a).cmp(&rank(b)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        aggregation::Ty,
+        search::{Cursor, OkResponse},
+    };
+
+    fn sort(sort: Value) -> Vec<search::Sort> {
+        serde_json::from_value(sort).unwrap()
+    }
+
+    fn mock() -> MockResponses {
+        MockResponses {
+            docs: vec![
+                json!({ "id": "a", "created": 2 }),
+                json!({ "id": "b", "created": 3 }),
+                json!({ "id": "c", "created": 1 }),
+            ],
+            ..MockResponses::default()
+        }
+    }
+
+    fn ids(response: &OkResponse<Value>) -> Vec<&str> {
+        response
+            .hits
+            .hits
+            .iter()
+            .map(|hit| hit.id.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn can_generate_documents() {
+        let request = search::Request {
+            size: Some(2),
+            ..search::Request::builder().build()
+        };
+        let mock = MockResponses {
+            total: Some(5),
+            ..MockResponses::default()
+        };
+
+        let response: OkResponse<Value> =
+            serde_json::from_value(mock.search(&request, &[])).unwrap();
+        assert_eq!(response.hits.total_count.value, 5);
+        assert_eq!(ids(&response), vec!["1", "2"]);
+    }
+
+    #[test]
+    fn respects_sort_size_and_after() {
+        let request = search::Request {
+            sort: sort(json!([{ "created": { "order": "desc" } }])),
+            size: Some(2),
+            ..search::Request::builder().build()
+        };
+
+        let response: OkResponse<Value> =
+            serde_json::from_value(mock().search(&request, &[])).unwrap();
+        assert_eq!(response.hits.total_count.value, 3);
+        assert_eq!(ids(&response), vec!["b", "a"]);
+
+        let cursor = Cursor::for_hit(&request, &response.hits.hits[1]);
+        let mut next = request.clone();
+        next.set_cursor(&cursor.encode()).unwrap();
+
+        let response: OkResponse<Value> =
+            serde_json::from_value(mock().search(&next, &[])).unwrap();
+        assert_eq!(ids(&response), vec!["c"]);
+    }
+
+    #[test]
+    fn can_generate_nested_aggregations() {
+        let aggregations: Vec<aggregation::Request> = vec![serde_json::from_value(json!({
+            "statuses": {
+                "terms": { "field": "status", "size": 2 },
+                "aggs": { "duration": { "avg": { "field": "duration" } } }
+            }
+        }))
+        .unwrap()];
+
+        let json = mock().aggregations(&aggregations);
+        assert_eq!(
+            json["aggregations"]["terms#statuses"]["buckets"][0]["key"],
+            "status_1"
+        );
+
+        let response: OkResponse<Value> = serde_json::from_value(json.clone()).unwrap();
+        assert!(response.hits.hits.is_empty());
+
+        let response: aggregation::Response = serde_json::from_value(json).unwrap();
+        let result = &response.aggregations[0];
+        assert_eq!(result.name, "duration");
+        assert_eq!(result.type_, Ty::Avg);
+        let mut fields = result.fields.clone();
+        fields.sort();
+        assert_eq!(fields, vec!["status_1", "status_2"]);
+    }
+}